@@ -2,21 +2,147 @@
 //!
 //! Launch and close applications with fuzzy matching and error handling.
 
+use crate::db::schema::{AppCategory, Application};
 use crate::db::AppDatabase;
 use crate::error::{LunaError, Result};
+use crate::utils::string_matching;
+use parking_lot::RwLock;
+use std::collections::HashMap;
+use std::path::PathBuf;
 use std::process::Command;
 use std::sync::Arc;
 use tracing::{debug, info, warn};
 
+/// Minimum fuzzy match score to act on an app without asking
+pub const APP_MATCH_THRESHOLD: f32 = 0.75;
+
+/// Minimum fuzzy match score to offer an app as a suggestion
+const APP_SUGGESTION_THRESHOLD: f32 = 0.5;
+
+/// Most apps listed when asking which one to open
+const MAX_CATEGORY_CHOICES: usize = 4;
+
+/// Result of resolving a spoken app name
+enum AppMatch {
+    /// Confident match
+    Found(Application),
+    /// Closest app, but not close enough to act on
+    Suggestion(Application),
+    /// Category with several apps and no clear preference
+    Ambiguous(CategoryChoice),
+}
+
+/// Apps that could answer a category request such as "open my browser"
+#[derive(Debug, Clone)]
+pub struct CategoryChoice {
+    /// Requested category
+    pub category: AppCategory,
+    /// Installed apps in the category, most used first
+    pub candidates: Vec<Application>,
+}
+
+impl CategoryChoice {
+    /// Question to ask the user, e.g. "Which browser would you like: Chrome or Firefox?"
+    pub fn question(&self) -> String {
+        let names: Vec<&str> = self
+            .candidates
+            .iter()
+            .map(|app| app.name.as_str())
+            .collect();
+        let listed = match names.split_last() {
+            Some((last, [])) => last.to_string(),
+            Some((last, rest)) => format!("{} or {}", rest.join(", "), last),
+            None => String::new(),
+        };
+        format!(
+            "Which {} would you like: {}?",
+            self.category.spoken_name(),
+            listed
+        )
+    }
+
+    /// Pick the candidate named in a reply such as "firefox please"
+    pub fn pick(&self, reply: &str) -> Option<&Application> {
+        let reply = string_matching::normalize(reply);
+        if reply.is_empty() {
+            return None;
+        }
+        let padded = format!(" {} ", reply);
+
+        let names = |app: &Application| {
+            std::iter::once(app.name.clone())
+                .chain(app.aliases.clone())
+                .map(|name| string_matching::normalize(&name))
+                .collect::<Vec<_>>()
+        };
+        self.candidates
+            .iter()
+            .find(|app| {
+                names(app)
+                    .iter()
+                    .any(|name| padded.contains(&format!(" {} ", name)))
+            })
+            .or_else(|| {
+                self.candidates
+                    .iter()
+                    .map(|app| {
+                        let score = names(app)
+                            .iter()
+                            .map(|name| string_matching::similarity_score(name, &reply))
+                            .fold(0.0, f32::max);
+                        (app, score)
+                    })
+                    .filter(|(_, score)| *score >= APP_MATCH_THRESHOLD)
+                    .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
+                    .map(|(app, _)| app)
+            })
+    }
+}
+
 /// Application launcher with database integration
+#[derive(Clone)]
 pub struct AppLauncher {
-    app_db: Arc<AppDatabase>,
+    app_db: Arc<RwLock<AppDatabase>>,
+    db_path: Option<PathBuf>,
+    preferred_apps: HashMap<AppCategory, String>,
 }
 
 impl AppLauncher {
     /// Create a new app launcher
-    pub fn new(app_db: Arc<AppDatabase>) -> Self {
-        Self { app_db }
+    pub fn new(app_db: Arc<RwLock<AppDatabase>>) -> Self {
+        Self {
+            app_db,
+            db_path: None,
+            preferred_apps: HashMap::new(),
+        }
+    }
+
+    /// Save launch history to the app database file after each launch
+    pub fn with_persistence(mut self, path: impl Into<PathBuf>) -> Self {
+        self.db_path = Some(path.into());
+        self
+    }
+
+    /// Set the app to open for category requests such as "open my browser"
+    ///
+    /// Categories without a preference use the most launched app, and ask
+    /// when there's no clear favourite.
+    pub fn with_preferred_apps(
+        mut self,
+        preferred: impl IntoIterator<Item = (AppCategory, String)>,
+    ) -> Self {
+        self.preferred_apps.extend(preferred);
+        self
+    }
+
+    /// Apps to choose between when `app_name` is a category without a clear preference
+    ///
+    /// Returns `None` when the name resolves to a single app or isn't a category.
+    pub fn category_choice(&self, app_name: &str) -> Option<CategoryChoice> {
+        match self.resolve_app(app_name) {
+            Ok(AppMatch::Ambiguous(choice)) => Some(choice),
+            _ => None,
+        }
     }
 
     /// Launch an application by name
@@ -26,12 +152,22 @@ impl AppLauncher {
         info!("Launching app: {}", app_name);
 
         // Find the application
-        let app = self.find_app(app_name)?;
+        let app = match self.resolve_app(app_name)? {
+            AppMatch::Found(app) => app,
+            AppMatch::Suggestion(app) => {
+                return Ok(format!(
+                    "I couldn't find '{}'. Did you mean {}?",
+                    app_name, app.name
+                ));
+            }
+            AppMatch::Ambiguous(choice) => return Ok(choice.question()),
+        };
 
         debug!("Found app: {} at {}", app.name, app.executable.display());
 
         // Launch the application
-        self.launch_app_by_path(&app.executable.to_string_lossy(), &[])?;
+        self.launch_app_by_path(&app.executable.to_string_lossy(), &app.launch_args)?;
+        self.record_launch(&app.id).await;
 
         Ok(format!("Launched {}", app.name))
     }
@@ -41,7 +177,9 @@ impl AppLauncher {
         info!("Launching app: {} with args: {:?}", app_name, args);
 
         let app = self.find_app(app_name)?;
-        self.launch_app_by_path(&app.executable.to_string_lossy(), args)?;
+        let args: Vec<String> = app.launch_args.iter().chain(args).cloned().collect();
+        self.launch_app_by_path(&app.executable.to_string_lossy(), &args)?;
+        self.record_launch(&app.id).await;
 
         Ok(format!("Launched {} with arguments", app.name))
     }
@@ -104,24 +242,99 @@ impl AppLauncher {
         }
     }
 
-    /// Find an application using fuzzy matching
-    fn find_app(&self, app_name: &str) -> Result<crate::db::schema::Application> {
-        let apps = self.app_db.search(app_name);
+    /// Find an application, requiring a confident fuzzy match
+    fn find_app(&self, app_name: &str) -> Result<Application> {
+        match self.resolve_app(app_name)? {
+            AppMatch::Found(app) => Ok(app),
+            AppMatch::Suggestion(_) | AppMatch::Ambiguous(_) => {
+                Err(LunaError::AppNotFound(app_name.to_string()))
+            }
+        }
+    }
 
-        if apps.is_empty() {
-            return Err(LunaError::AppNotFound(app_name.to_string()));
+    /// Resolve an app name by exact name, category, alias, then fuzzy match
+    fn resolve_app(&self, app_name: &str) -> Result<AppMatch> {
+        let app_db = self.app_db.read();
+        let exact = app_db.find_by_name(app_name);
+
+        // "open my browser", unless an app is literally called that
+        let named = exact.is_some_and(|app| {
+            string_matching::normalize(&app.name) == string_matching::normalize(app_name)
+        });
+        if !named {
+            if let Some(category) = AppCategory::from_spoken(app_name) {
+                if let Some(found) = self.resolve_category(&app_db, category) {
+                    return Ok(found);
+                }
+            }
         }
 
-        // AppDatabase::search() already returns sorted by best match
-        // So we just take the first one
-        let best_match = apps[0];
+        if let Some(app) = exact {
+            return Ok(AppMatch::Found(app.clone()));
+        }
 
-        debug!(
-            "Best match: {} (score based on internal matching)",
-            best_match.name
-        );
+        match app_db.find_best_match(app_name) {
+            Some((app, score)) if score >= APP_MATCH_THRESHOLD => {
+                debug!("Best match: {} (score {:.2})", app.name, score);
+                Ok(AppMatch::Found(app))
+            }
+            Some((app, score)) if score >= APP_SUGGESTION_THRESHOLD => {
+                debug!("Closest app {} below threshold ({:.2})", app.name, score);
+                Ok(AppMatch::Suggestion(app))
+            }
+            _ => Err(LunaError::AppNotFound(app_name.to_string())),
+        }
+    }
 
-        Ok(best_match.clone())
+    /// Pick the configured or clearly most used app in a category
+    ///
+    /// Returns `None` if no installed app is in the category.
+    fn resolve_category(&self, app_db: &AppDatabase, category: AppCategory) -> Option<AppMatch> {
+        if let Some(preferred) = self.preferred_apps.get(&category) {
+            match app_db.find_by_name(preferred) {
+                Some(app) => return Some(AppMatch::Found(app.clone())),
+                None => warn!(
+                    "Preferred {} '{}' isn't installed",
+                    category.spoken_name(),
+                    preferred
+                ),
+            }
+        }
+
+        let apps = app_db.apps_in_category(category.clone());
+        debug!("{} apps in category {:?}", apps.len(), category);
+        match apps.as_slice() {
+            [] => None,
+            [only] => Some(AppMatch::Found((*only).clone())),
+            [first, second, ..] if first.launch_count > second.launch_count => {
+                Some(AppMatch::Found((*first).clone()))
+            }
+            _ => Some(AppMatch::Ambiguous(CategoryChoice {
+                category,
+                candidates: apps
+                    .into_iter()
+                    .take(MAX_CATEGORY_CHOICES)
+                    .cloned()
+                    .collect(),
+            })),
+        }
+    }
+
+    /// Count a launch and save the history if persistence is enabled
+    async fn record_launch(&self, app_id: &str) {
+        let snapshot = {
+            let mut app_db = self.app_db.write();
+            if !app_db.record_launch(app_id) {
+                return;
+            }
+            self.db_path.as_ref().map(|_| app_db.clone())
+        };
+
+        if let (Some(app_db), Some(path)) = (snapshot, &self.db_path) {
+            if let Err(e) = app_db.save_to_disk(path).await {
+                warn!("Failed to save app launch history: {}", e);
+            }
+        }
     }
 
     /// Launch an application by its full path
@@ -164,10 +377,9 @@ impl AppLauncher {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::db::schema::Application;
     use std::path::PathBuf;
 
-    fn create_test_db() -> Arc<AppDatabase> {
+    fn create_test_db() -> Arc<RwLock<AppDatabase>> {
         let mut db = AppDatabase::new();
 
         // Add some test applications
@@ -187,7 +399,7 @@ mod tests {
         firefox.category = crate::db::schema::AppCategory::Browser;
         db.add_app(firefox);
 
-        Arc::new(db)
+        Arc::new(RwLock::new(db))
     }
 
     #[tokio::test]
@@ -212,4 +424,71 @@ mod tests {
         let result = launcher.find_app("NonexistentApp");
         assert!(result.is_err());
     }
+
+    #[tokio::test]
+    async fn test_launch_suggests_close_names() {
+        let db = create_test_db();
+        let launcher = AppLauncher::new(db);
+
+        // Too far from any app to launch, close enough to suggest
+        let message = launcher.launch("firefly").await.unwrap();
+        assert_eq!(message, "I couldn't find 'firefly'. Did you mean Firefox?");
+        assert!(launcher.find_app("firefly").is_err());
+    }
+
+    fn add_browser(db: &Arc<RwLock<AppDatabase>>, name: &str) {
+        let mut app = Application::new(name.to_string(), PathBuf::from("/usr/bin/browser"));
+        app.category = AppCategory::Browser;
+        db.write().add_app(app);
+    }
+
+    #[tokio::test]
+    async fn test_category_without_preference_asks() {
+        let db = create_test_db();
+        add_browser(&db, "Brave");
+        let launcher = AppLauncher::new(Arc::clone(&db));
+
+        let choice = launcher.category_choice("my browser").unwrap();
+        assert_eq!(choice.category, AppCategory::Browser);
+        assert_eq!(
+            choice.question(),
+            "Which browser would you like: Chrome, Firefox or Brave?"
+        );
+        assert_eq!(
+            launcher.launch("a browser").await.unwrap(),
+            choice.question()
+        );
+        assert_eq!(choice.pick("firefox please").unwrap().name, "Firefox");
+        assert_eq!(choice.pick("brav").unwrap().name, "Brave");
+        assert!(choice.pick("never mind").is_none());
+
+        // Names that aren't categories resolve as before
+        assert!(launcher.category_choice("chrome").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_category_uses_preference_then_usage() {
+        let db = create_test_db();
+        add_browser(&db, "Brave");
+
+        let launcher = AppLauncher::new(Arc::clone(&db))
+            .with_preferred_apps([(AppCategory::Browser, "brave".to_string())]);
+        assert!(launcher.category_choice("browser").is_none());
+        assert_eq!(launcher.find_app("my browser").unwrap().name, "Brave");
+
+        // A preferred app that isn't installed falls back to the most used
+        let launcher = AppLauncher::new(Arc::clone(&db))
+            .with_preferred_apps([(AppCategory::Browser, "opera".to_string())]);
+        assert!(launcher.category_choice("browser").is_some());
+        let id = db.read().find_by_name("firefox").unwrap().id.clone();
+        db.write().record_launch(&id);
+        assert!(launcher.category_choice("browser").is_none());
+        assert_eq!(launcher.find_app("my browser").unwrap().name, "Firefox");
+
+        // A single app in the category needs no preference
+        let single = Arc::new(RwLock::new(AppDatabase::new()));
+        add_browser(&single, "Chrome");
+        let launcher = AppLauncher::new(single);
+        assert_eq!(launcher.find_app("web browser").unwrap().name, "Chrome");
+    }
 }
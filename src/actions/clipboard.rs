@@ -83,11 +83,7 @@ impl Clipboard {
         {
             use notify_rust::Notification;
 
-            let body_preview = if body.len() > 50 {
-                format!("{}...", &body[..50])
-            } else {
-                body.to_string()
-            };
+            let body_preview = preview_text(body, 50);
 
             if let Err(e) = Notification::new()
                 .summary(summary)
@@ -108,6 +104,24 @@ impl Default for Clipboard {
     }
 }
 
+/// Shorten text to at most `max_chars` characters, appending "..." when cut
+///
+/// Control characters are replaced with spaces so the result is safe to speak.
+pub fn preview_text(text: &str, max_chars: usize) -> String {
+    let cleaned: String = text
+        .chars()
+        .map(|c| if c.is_control() { ' ' } else { c })
+        .collect();
+    let cleaned = cleaned.trim();
+
+    if cleaned.chars().count() > max_chars {
+        let cut: String = cleaned.chars().take(max_chars).collect();
+        format!("{}...", cut.trim_end())
+    } else {
+        cleaned.to_string()
+    }
+}
+
 /// Read clipboard content (convenience function)
 pub async fn read_clipboard() -> Result<String> {
     Clipboard::new().read().await
@@ -118,3 +132,20 @@ pub async fn write_clipboard(content: &str) -> Result<()> {
     Clipboard::new().write(content).await?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_preview_text_truncates_on_char_boundary() {
+        let text = "héllo wörld ".repeat(10);
+        let preview = preview_text(&text, 8);
+        assert_eq!(preview, "héllo wö...");
+    }
+
+    #[test]
+    fn test_preview_text_strips_control_chars() {
+        assert_eq!(preview_text("line one\nline\ttwo", 100), "line one line two");
+    }
+}
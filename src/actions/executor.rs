@@ -13,21 +13,56 @@
 //! 10. ✅ Wait/schedule actions
 
 use crate::actions::app_launcher::AppLauncher;
+use crate::actions::clipboard::{preview_text, Clipboard};
 use crate::actions::file_search::FileSearch;
 use crate::actions::media_control::MediaControl;
+use crate::actions::plugins::{command_from_params, Skill};
+use crate::actions::reminders::create_reminder_with_bus;
+use crate::actions::skills::SkillRegistry;
 use crate::actions::system_control::SystemControl;
-use crate::brain::task_planner::{ActionStep, ActionType, Precondition, TaskPlan};
+use crate::actions::window_control::WindowControl;
+use crate::brain::command_parser::ParsedCommand;
+use crate::brain::task_planner::{ActionStep, ActionType, Postcondition, Precondition, TaskPlan};
+use crate::brain::types::{Entities, Entity};
+use crate::brain::{Brain, ClassificationResult, IntentType};
+use crate::db::FileFilter;
 use crate::error::{LunaError, Result};
 use crate::events::{EventBus, LunaEvent};
+use crate::knowledge::QuestionAnswerer;
 use crate::metrics::{MetricPhase, Metrics};
+use crate::os::clipboard_manager::ClipboardHistory;
+use crate::os::desktop_environment::DesktopEnvironment;
+use crate::os::multi_monitor::{MonitorTarget, MultiMonitorManager};
+use crate::os::power_manager::PowerManager;
+use crate::os::process_priority::ProcessOptimizer;
+use crate::os::virtual_desktop::VirtualDesktopManager;
+use crate::os::window_manager::{WindowAction, WindowManager, WindowSelector};
+use crate::utils::time_helpers;
+use chrono::{DateTime, Local};
+use futures::future::BoxFuture;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
-use tokio::sync::RwLock;
+use sysinfo::System;
+use tokio::sync::{mpsc, oneshot, RwLock};
 use tokio::time::timeout;
 use tracing::{debug, info, warn};
 use uuid::Uuid;
 
+/// How retry backoff is randomised, so steps failing together don't retry in lockstep
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum JitterStrategy {
+    /// Exact exponential backoff
+    None,
+    /// Anywhere between zero and the computed backoff
+    Full,
+    /// Half the computed backoff plus up to another half
+    #[default]
+    Equal,
+}
+
 /// Retry policy configuration
 #[derive(Debug, Clone)]
 pub struct RetryPolicy {
@@ -35,6 +70,7 @@ pub struct RetryPolicy {
     pub initial_backoff_ms: u64,
     pub max_backoff_ms: u64,
     pub backoff_multiplier: f64,
+    pub jitter: JitterStrategy,
 }
 
 impl Default for RetryPolicy {
@@ -44,6 +80,7 @@ impl Default for RetryPolicy {
             initial_backoff_ms: 100,
             max_backoff_ms: 5000,
             backoff_multiplier: 2.0,
+            jitter: JitterStrategy::default(),
         }
     }
 }
@@ -54,17 +91,208 @@ pub struct ExecutionPolicy {
     pub require_confirmation: Vec<ActionType>,
     pub max_step_timeout_secs: u64,
     pub max_plan_timeout_secs: u64,
+    /// Per-action timeout overrides (falls back to `max_step_timeout_secs`)
+    pub action_timeouts: HashMap<ActionType, Duration>,
+    /// How long to keep polling for postconditions before failing the step
+    pub postcondition_timeout_ms: u64,
 }
 
 impl Default for ExecutionPolicy {
     fn default() -> Self {
         Self {
             require_confirmation: vec![
-                ActionType::SystemControl, // shutdown, restart, lock
+                ActionType::SystemControl,  // shutdown, restart, lock
+                ActionType::RunCommand,     // external programs
+                ActionType::ProcessControl, // renice, taskset
             ],
             max_step_timeout_secs: 30,
             max_plan_timeout_secs: 300,
+            action_timeouts: HashMap::new(),
+            postcondition_timeout_ms: 2000,
+        }
+    }
+}
+
+impl ExecutionPolicy {
+    /// Override the timeout for a single action type
+    pub fn with_action_timeout(mut self, action: ActionType, limit: Duration) -> Self {
+        self.action_timeouts.insert(action, limit);
+        self
+    }
+
+    /// Effective timeout for an action type
+    pub fn timeout_for(&self, action: &ActionType) -> Duration {
+        self.action_timeouts
+            .get(action)
+            .copied()
+            .unwrap_or_else(|| Duration::from_secs(self.max_step_timeout_secs))
+    }
+}
+
+/// Callback asked to confirm a sensitive action before it runs
+///
+/// Returning `false` aborts the step.
+pub type ConfirmationHandler = Arc<dyn Fn(&ActionStep) -> BoxFuture<'static, bool> + Send + Sync>;
+
+/// Question for a sensitive step and where to send the answer
+pub type ConfirmationRequest = (String, oneshot::Sender<bool>);
+
+/// Checks whether an application is running (used for postcondition verification)
+pub trait ProcessProbe: Send + Sync {
+    /// Return true if a process matching the application name is running
+    fn is_running(&self, app_name: &str) -> bool;
+}
+
+/// Process probe backed by the live system process table
+pub struct SystemProcessProbe;
+
+impl ProcessProbe for SystemProcessProbe {
+    fn is_running(&self, app_name: &str) -> bool {
+        let needle = app_name.to_lowercase();
+        let mut system = System::new();
+        system.refresh_processes();
+        system
+            .processes()
+            .values()
+            .any(|process| process.name().to_lowercase().contains(&needle))
+    }
+}
+
+/// Result of a single executed step
+#[derive(Debug, Clone)]
+pub struct StepOutcome {
+    /// Index of the step in the plan
+    pub step_index: usize,
+    /// Action that was executed
+    pub action: ActionType,
+    /// Whether the step succeeded
+    pub success: bool,
+    /// Optional step that failed without stopping the plan
+    pub skipped: bool,
+    /// Result message (or error text on failure)
+    pub message: String,
+    /// Time spent on the step including retries
+    pub duration_ms: u64,
+}
+
+/// Structured result of executing a plan
+#[derive(Debug, Clone)]
+pub struct PlanOutcome {
+    /// Plan correlation ID
+    pub plan_id: String,
+    /// Per-step outcomes in plan order
+    pub steps: Vec<StepOutcome>,
+    /// Total wall-clock time for the plan
+    pub total_duration_ms: u64,
+    /// Whether this was a dry-run
+    pub dry_run: bool,
+}
+
+impl PlanOutcome {
+    /// True if every executed step succeeded or was optional
+    pub fn success(&self) -> bool {
+        self.steps.iter().all(|s| s.success || s.skipped)
+    }
+
+    /// Message of the last successful step (the one worth speaking)
+    pub fn last_message(&self) -> Option<&str> {
+        self.steps
+            .iter()
+            .rev()
+            .find(|s| s.success && !s.message.is_empty())
+            .map(|s| s.message.as_str())
+    }
+
+    /// True if no step succeeded, including when there were no steps
+    pub fn did_nothing(&self) -> bool {
+        !self.steps.iter().any(|s| s.success)
+    }
+
+    /// All step messages joined into one sentence-like string
+    pub fn summary(&self) -> String {
+        if self.steps.is_empty() {
+            return "No actions to execute".to_string();
         }
+
+        self.steps
+            .iter()
+            .map(|s| s.message.as_str())
+            .collect::<Vec<_>>()
+            .join(". ")
+    }
+}
+
+/// What a command would do, worked out without doing it
+#[derive(Debug, Clone)]
+pub struct CommandPreview {
+    /// Plan built for the command
+    pub plan: TaskPlan,
+    /// Dry-run result of each step
+    pub outcome: PlanOutcome,
+}
+
+/// Runner-up interpretation offered when a plan failed or did nothing
+#[derive(Debug, Clone)]
+pub struct AlternativeSuggestion {
+    /// Question to ask, e.g. "I couldn't do that. Did you mean find budget.txt?"
+    pub question: String,
+    /// Plan to run if the user agrees
+    pub plan: TaskPlan,
+}
+
+/// Replies that accept a suggestion
+const AFFIRMATIVE_REPLIES: &[&str] = &["yes", "yeah", "yep", "sure", "ok", "okay", "please"];
+
+impl AlternativeSuggestion {
+    /// Whether a reply such as "yes please" accepts the suggestion
+    pub fn accepts(&self, reply: &str) -> bool {
+        is_affirmative(reply)
+    }
+}
+
+/// Whether a spoken reply such as "yes please" means yes
+pub fn is_affirmative(reply: &str) -> bool {
+    reply
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .any(|word| AFFIRMATIVE_REPLIES.contains(&word))
+}
+
+/// Question asked before a step gated by `require_confirmation` runs
+pub fn confirmation_question(step: &ActionStep) -> String {
+    let param = |key: &str| step.params.get(key).map(String::as_str).unwrap_or_default();
+    let action = match step.action {
+        ActionType::SystemControl => match param("action") {
+            "shutdown" => "shut down the computer".to_string(),
+            "restart" => "restart the computer".to_string(),
+            "lock" => "lock the computer".to_string(),
+            other => other.to_string(),
+        },
+        ActionType::RunCommand if param("skill").is_empty() => format!("run {}", param("command")),
+        ActionType::RunCommand => format!("run {}", param("skill")),
+        ActionType::ProcessControl => format!("change how {} runs", param("process")),
+        ref action => format!("run {:?}", action),
+    };
+    format!("Are you sure you want to {}?", action)
+}
+
+/// Short spoken form of a classification, e.g. "find budget.txt"
+fn describe_classification(classification: &ClassificationResult) -> String {
+    let entity = |key: &str| {
+        classification
+            .entities
+            .get(key)
+            .map(String::as_str)
+            .unwrap_or_default()
+    };
+    match classification.intent {
+        IntentType::LaunchApp => format!("open {}", entity("app_name")),
+        IntentType::CloseApp => format!("close {}", entity("app_name")),
+        IntentType::FindFile => format!("find {}", entity("file_name")),
+        IntentType::OpenFolder => format!("open the {} folder", entity("folder_name")),
+        IntentType::SearchWeb => format!("search the web for {}", entity("query")),
+        IntentType::Question => format!("answer \"{}\"", entity("query")),
+        ref intent => format!("{:?}", intent),
     }
 }
 
@@ -74,6 +302,35 @@ struct ExecutionContext {
     correlation_id: Uuid,
     dry_run: bool,
     step_results: HashMap<usize, String>,
+    /// Step indices in the order they completed (used for compensation)
+    completed_steps: Vec<usize>,
+    /// Per-step outcomes in the order they finished
+    step_outcomes: Vec<StepOutcome>,
+}
+
+impl ExecutionContext {
+    /// Record a finished step
+    fn record(
+        &mut self,
+        step_index: usize,
+        step: &ActionStep,
+        result: &Result<String>,
+        duration: Duration,
+    ) {
+        let (success, message) = match result {
+            Ok(msg) => (true, msg.clone()),
+            Err(e) => (false, e.to_string()),
+        };
+
+        self.step_outcomes.push(StepOutcome {
+            step_index,
+            action: step.action.clone(),
+            success,
+            skipped: !success && step.optional,
+            message,
+            duration_ms: duration.as_millis() as u64,
+        });
+    }
 }
 
 /// Task Executor (Unified GOD-LEVEL Implementation)
@@ -82,11 +339,32 @@ pub struct TaskExecutor {
     file_search: FileSearch,
     system_control: SystemControl,
     media_control: MediaControl,
+    window_control: WindowControl,
+    monitors: Arc<MultiMonitorManager>,
+    window_manager: WindowManager,
+    virtual_desktops: VirtualDesktopManager,
+    power: PowerManager,
+    clipboard: Clipboard,
+    /// Recent copies, recalled with "paste the thing before last"
+    clipboard_history: Arc<ClipboardHistory>,
+    question_answerer: Option<Arc<QuestionAnswerer>>,
+    /// Allowlisted command skills; `RunCommand` is refused without one
+    skills: Option<Arc<SkillRegistry>>,
+    /// Runtime skills consulted for unhandled commands, in registration order
+    custom_skills: Vec<Box<dyn Skill>>,
+    /// Priority and affinity changes; `ProcessControl` is refused without one
+    process_optimizer: Option<Arc<ProcessOptimizer>>,
+    process_probe: Arc<dyn ProcessProbe>,
+    confirmation_handler: Option<ConfirmationHandler>,
     event_bus: Option<Arc<EventBus>>,
     metrics: Option<Arc<Metrics>>,
     retry_policy: RetryPolicy,
+    /// Source of retry jitter
+    backoff_rng: parking_lot::Mutex<StdRng>,
     execution_policy: ExecutionPolicy,
     cancel_token: Arc<RwLock<bool>>,
+    /// Last successful plan response (target for "copy that")
+    last_response: Arc<RwLock<Option<String>>>,
 }
 
 impl TaskExecutor {
@@ -97,11 +375,26 @@ impl TaskExecutor {
             file_search,
             system_control: SystemControl::new(),
             media_control: MediaControl::new(),
+            window_control: WindowControl::new(),
+            monitors: Arc::new(MultiMonitorManager::new()),
+            window_manager: WindowManager::new(),
+            virtual_desktops: VirtualDesktopManager::new(),
+            power: PowerManager::new(),
+            clipboard: Clipboard::new(),
+            clipboard_history: Arc::new(ClipboardHistory::default()),
+            question_answerer: None,
+            skills: None,
+            custom_skills: Vec::new(),
+            process_optimizer: None,
+            process_probe: Arc::new(SystemProcessProbe),
+            confirmation_handler: None,
             event_bus: None,
             metrics: None,
             retry_policy: RetryPolicy::default(),
+            backoff_rng: parking_lot::Mutex::new(StdRng::from_entropy()),
             execution_policy: ExecutionPolicy::default(),
             cancel_token: Arc::new(RwLock::new(false)),
+            last_response: Arc::new(RwLock::new(None)),
         }
     }
 
@@ -117,12 +410,107 @@ impl TaskExecutor {
         self
     }
 
+    /// Add question answerer for `AnswerQuestion` actions
+    pub fn with_question_answerer(mut self, answerer: Arc<QuestionAnswerer>) -> Self {
+        self.question_answerer = Some(answerer);
+        self
+    }
+
+    /// Use a shared monitor manager for monitor-targeted window commands
+    pub fn with_monitors(mut self, monitors: Arc<MultiMonitorManager>) -> Self {
+        self.monitors = monitors;
+        self
+    }
+
+    /// Use a custom virtual desktop manager
+    pub fn with_virtual_desktops(mut self, manager: VirtualDesktopManager) -> Self {
+        self.virtual_desktops = manager;
+        self
+    }
+
+    /// Pick window and audio tools for the detected desktop environment
+    pub fn with_desktop_environment(mut self, environment: &DesktopEnvironment) -> Self {
+        let backends = environment.select_backends();
+        self.window_control = WindowControl::new().with_backend(backends.window);
+        self.system_control = SystemControl::new().with_audio_backend(backends.audio);
+        self
+    }
+
+    /// Share a clipboard history (e.g. one fed by clipboard polling)
+    pub fn with_clipboard_history(mut self, history: Arc<ClipboardHistory>) -> Self {
+        self.clipboard_history = history;
+        self
+    }
+
+    /// Add the optimizer `ProcessControl` actions go through
+    pub fn with_process_optimizer(mut self, optimizer: Arc<ProcessOptimizer>) -> Self {
+        self.process_optimizer = Some(optimizer);
+        self
+    }
+
+    /// Use a custom power manager
+    pub fn with_power_manager(mut self, manager: PowerManager) -> Self {
+        self.power = manager;
+        self
+    }
+
+    /// Add the command skills `RunCommand` actions may run
+    pub fn with_skills(mut self, skills: Arc<SkillRegistry>) -> Self {
+        self.skills = Some(skills);
+        self
+    }
+
+    /// Register a runtime skill for commands no built-in action handles
+    pub fn register_skill(&mut self, skill: Box<dyn Skill>) {
+        info!("Registered skill: {}", skill.name());
+        self.custom_skills.push(skill);
+    }
+
+    /// Use a custom process probe for postcondition verification
+    pub fn with_process_probe(mut self, probe: Arc<dyn ProcessProbe>) -> Self {
+        self.process_probe = probe;
+        self
+    }
+
+    /// Ask the handler before running actions listed in `require_confirmation`
+    pub fn with_confirmation_handler<F>(mut self, handler: F) -> Self
+    where
+        F: Fn(&ActionStep) -> BoxFuture<'static, bool> + Send + Sync + 'static,
+    {
+        self.confirmation_handler = Some(Arc::new(handler));
+        self
+    }
+
+    /// Send confirmation questions to `requests` and wait for the answer
+    ///
+    /// For callers that collect the answer themselves while the plan runs,
+    /// e.g. from the microphone or a script. A dropped request counts as no.
+    pub fn with_confirmation_channel(self, requests: mpsc::Sender<ConfirmationRequest>) -> Self {
+        self.with_confirmation_handler(move |step| {
+            let question = confirmation_question(step);
+            let requests = requests.clone();
+            Box::pin(async move {
+                let (reply_tx, reply_rx) = oneshot::channel();
+                if requests.send((question, reply_tx)).await.is_err() {
+                    return false;
+                }
+                reply_rx.await.unwrap_or(false)
+            })
+        })
+    }
+
     /// Create with custom retry policy
     pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
         self.retry_policy = policy;
         self
     }
 
+    /// Seed the retry jitter so backoff delays are reproducible
+    pub fn with_jitter_seed(mut self, seed: u64) -> Self {
+        self.backoff_rng = parking_lot::Mutex::new(StdRng::seed_from_u64(seed));
+        self
+    }
+
     /// Create with custom execution policy
     pub fn with_execution_policy(mut self, policy: ExecutionPolicy) -> Self {
         self.execution_policy = policy;
@@ -149,20 +537,88 @@ impl TaskExecutor {
 
     /// Execute a complete task plan
     pub async fn execute_plan(&self, plan: TaskPlan) -> Result<String> {
+        self.execute_plan_with_options(plan, false)
+            .await
+            .map(|outcome| outcome.summary())
+    }
+
+    /// Execute a task plan and return per-step results
+    pub async fn execute_plan_detailed(&self, plan: TaskPlan) -> Result<PlanOutcome> {
         self.execute_plan_with_options(plan, false).await
     }
 
     /// Execute plan in dry-run mode (preview only)
     pub async fn preview_plan(&self, plan: TaskPlan) -> Result<String> {
+        self.execute_plan_with_options(plan, true)
+            .await
+            .map(|outcome| outcome.summary())
+    }
+
+    /// Dry-run a plan and return per-step descriptions
+    pub async fn preview_plan_detailed(&self, plan: TaskPlan) -> Result<PlanOutcome> {
         self.execute_plan_with_options(plan, true).await
     }
 
+    /// Parse, classify and plan `text`, then dry-run the plan
+    ///
+    /// Every step takes the dry-run path, so nothing is launched, changed or
+    /// confirmed.
+    pub async fn preview_command(&self, brain: &Brain, text: &str) -> Result<CommandPreview> {
+        let plan = brain.process_async(text).await?;
+        let outcome = self.preview_plan_detailed(plan.clone()).await?;
+        Ok(CommandPreview { plan, outcome })
+    }
+
+    /// Offer the brain's best alternative when `plan` failed or did nothing
+    ///
+    /// The offer is published as `ClarificationRequested`; running the
+    /// suggested plan is left to the caller once the user agrees.
+    pub async fn suggest_alternative(
+        &self,
+        brain: &Brain,
+        plan: &TaskPlan,
+        result: &Result<PlanOutcome>,
+    ) -> Option<AlternativeSuggestion> {
+        if matches!(result, Ok(outcome) if !outcome.did_nothing()) {
+            return None;
+        }
+
+        let alternative = brain.plan_alternative(&plan.classification)?;
+        let question = format!(
+            "I couldn't do that. Did you mean {}?",
+            describe_classification(&alternative.classification)
+        );
+        info!("Offering alternative: {}", question);
+
+        if let Some(ref bus) = self.event_bus {
+            bus.publish_with_correlation(
+                LunaEvent::ClarificationRequested {
+                    command: describe_classification(&plan.classification),
+                    confidence: alternative.classification.confidence,
+                    missing_slots: Vec::new(),
+                    suggestions: vec![question.clone()],
+                },
+                plan.correlation_id.unwrap_or_else(Uuid::new_v4),
+            )
+            .await;
+        }
+
+        Some(AlternativeSuggestion {
+            question,
+            plan: alternative,
+        })
+    }
+
     /// Execute plan with options
-    async fn execute_plan_with_options(&self, plan: TaskPlan, dry_run: bool) -> Result<String> {
+    async fn execute_plan_with_options(
+        &self,
+        plan: TaskPlan,
+        dry_run: bool,
+    ) -> Result<PlanOutcome> {
         self.reset_cancel_token().await;
 
         let plan_id = EventBus::generate_plan_id();
-        let correlation_id = Uuid::new_v4();
+        let correlation_id = plan.correlation_id.unwrap_or_else(Uuid::new_v4);
         let plan_start = Instant::now();
 
         info!(
@@ -173,7 +629,12 @@ impl TaskExecutor {
         );
 
         if plan.steps.is_empty() {
-            return Ok("No actions to execute".to_string());
+            return Ok(PlanOutcome {
+                plan_id,
+                steps: Vec::new(),
+                total_duration_ms: 0,
+                dry_run,
+            });
         }
 
         // Validate plan
@@ -203,9 +664,10 @@ impl TaskExecutor {
             correlation_id,
             dry_run,
             step_results: HashMap::new(),
+            completed_steps: Vec::new(),
+            step_outcomes: Vec::new(),
         };
 
-        let mut results = Vec::new();
         let mut steps_completed = 0;
         let mut steps_failed = 0;
 
@@ -241,26 +703,32 @@ impl TaskExecutor {
             .await;
         }
 
-        // If execution failed, return error
+        // If execution failed, roll back completed steps and return error
         if let Err(e) = execution_result {
+            if !dry_run {
+                self.run_compensations(&plan, &context).await;
+            }
             return Err(e);
         }
 
-        // Collect results
-        for idx in 0..plan.steps.len() {
-            if let Some(result) = context.step_results.get(&idx) {
-                results.push(result.clone());
+        // Collect results in plan order
+        let mut steps = context.step_outcomes;
+        steps.sort_by_key(|s| s.step_index);
+
+        let outcome = PlanOutcome {
+            plan_id,
+            steps,
+            total_duration_ms: plan_duration.as_millis() as u64,
+            dry_run,
+        };
+
+        if !dry_run {
+            if let Some(msg) = outcome.last_message() {
+                *self.last_response.write().await = Some(msg.to_string());
             }
         }
 
-        if results.is_empty() {
-            Ok(format!(
-                "Task completed{}",
-                if dry_run { " (dry-run)" } else { "" }
-            ))
-        } else {
-            Ok(results.join(". "))
-        }
+        Ok(outcome)
     }
 
     /// Execute parallel groups
@@ -280,31 +748,66 @@ impl TaskExecutor {
                 ));
             }
 
-            // Execute all steps in group concurrently
+            // Steps whose preconditions fail are left out of the group;
+            // a required one fails the plan as it would sequentially
+            let mut runnable = Vec::new();
+            for &step_idx in group {
+                let step = &plan.steps[step_idx];
+                match self.check_preconditions(step, context).await {
+                    Ok(()) => runnable.push(step_idx),
+                    Err(e) => {
+                        *steps_failed += 1;
+                        if !step.optional {
+                            return Err(e);
+                        }
+                        warn!("Optional step {} skipped: {}", step_idx, e);
+                        context.record(step_idx, step, &Err(e), Duration::ZERO);
+                    }
+                }
+            }
+
+            // Execute all runnable steps in group concurrently
             let mut tasks = Vec::new();
 
-            for &step_idx in group {
+            for &step_idx in &runnable {
                 let step = &plan.steps[step_idx];
-                tasks.push(self.execute_step_with_retry(step, context));
+                let context = &*context;
+                tasks.push(async move {
+                    let start = Instant::now();
+                    let result = self.execute_step_with_retry(step, context).await;
+                    (result, start.elapsed())
+                });
             }
 
             // Wait for all steps in group to complete
             let results = futures::future::join_all(tasks).await;
 
-            // Check results
-            for (idx, result) in results.into_iter().enumerate() {
-                let step_idx = group[idx];
+            // Record every result before failing so that siblings which
+            // already succeeded are still compensated
+            let mut first_error = None;
+            for (idx, (result, duration)) in results.into_iter().enumerate() {
+                let step_idx = runnable[idx];
+                context.record(step_idx, &plan.steps[step_idx], &result, duration);
                 match result {
                     Ok(msg) => {
                         context.step_results.insert(step_idx, msg);
+                        context.completed_steps.push(step_idx);
                         *steps_completed += 1;
                     }
                     Err(e) => {
                         *steps_failed += 1;
-                        return Err(e);
+                        if !plan.steps[step_idx].optional {
+                            first_error.get_or_insert(e);
+                        } else {
+                            warn!("Optional step {} failed, continuing: {}", step_idx, e);
+                        }
                     }
                 }
             }
+
+            if let Some(e) = first_error {
+                return Err(e);
+            }
         }
 
         Ok(())
@@ -332,18 +835,31 @@ impl TaskExecutor {
             // Check preconditions
             if let Err(e) = self.check_preconditions(step, context).await {
                 *steps_failed += 1;
-                return Err(e);
+                if !step.optional {
+                    return Err(e);
+                }
+                warn!("Optional step {} skipped: {}", idx, e);
+                context.record(idx, step, &Err(e), Duration::ZERO);
+                continue;
             }
 
             // Execute with retry
-            match self.execute_step_with_retry(step, context).await {
+            let start = Instant::now();
+            let result = self.execute_step_with_retry(step, context).await;
+            context.record(idx, step, &result, start.elapsed());
+
+            match result {
                 Ok(msg) => {
                     context.step_results.insert(idx, msg);
+                    context.completed_steps.push(idx);
                     *steps_completed += 1;
                 }
                 Err(e) => {
                     *steps_failed += 1;
-                    return Err(e);
+                    if !step.optional {
+                        return Err(e);
+                    }
+                    warn!("Optional step {} failed, continuing: {}", idx, e);
                 }
             }
         }
@@ -377,11 +893,51 @@ impl TaskExecutor {
                 )
                 .await;
             }
+
+            // Without a handler nobody can confirm, so the action is refused
+            let confirmed = match self.confirmation_handler {
+                Some(ref handler) => handler(step).await,
+                None => {
+                    warn!(
+                        "No confirmation handler configured, refusing sensitive action {}",
+                        action_name
+                    );
+                    false
+                }
+            };
+
+            if !confirmed {
+                info!("Action {} not confirmed, skipping", action_name);
+
+                if let Some(ref bus) = self.event_bus {
+                    bus.publish_with_correlation(
+                        LunaEvent::ActionCompleted {
+                            action_type: action_name.clone(),
+                            success: false,
+                            result: "Action not confirmed".to_string(),
+                            duration_ms: 0,
+                        },
+                        context.correlation_id,
+                    )
+                    .await;
+                }
+
+                return Err(LunaError::SystemOperation(format!(
+                    "Action not confirmed: {}",
+                    action_name
+                )));
+            }
         }
 
+        // External commands may have side effects, so they only ever run once
+        let max_attempts = if step.action == ActionType::RunCommand {
+            1
+        } else {
+            self.retry_policy.max_attempts
+        };
         let mut last_error: Option<String> = None;
 
-        for attempt in 1..=self.retry_policy.max_attempts {
+        for attempt in 1..=max_attempts {
             if self.is_cancelled().await {
                 return Err(LunaError::SystemOperation(
                     "Execution cancelled".to_string(),
@@ -405,10 +961,15 @@ impl TaskExecutor {
             }
 
             // Execute with timeout
-            let step_timeout = Duration::from_secs(self.execution_policy.max_step_timeout_secs);
+            let step_timeout = self.execution_policy.timeout_for(&step.action);
             let start = Instant::now();
 
-            let result = timeout(step_timeout, self.execute_step(step, context.dry_run)).await;
+            let result = timeout(step_timeout, async {
+                let msg = self.execute_step(step, context.dry_run).await?;
+                self.verify_postconditions(step, context).await?;
+                Ok::<_, LunaError>(msg)
+            })
+            .await;
 
             let duration = start.elapsed();
 
@@ -441,7 +1002,7 @@ impl TaskExecutor {
                     let is_recoverable = e.is_recoverable();
 
                     // Only retry if error is recoverable
-                    if !is_recoverable || attempt == self.retry_policy.max_attempts {
+                    if !is_recoverable || attempt == max_attempts {
                         last_error = Some(error_msg.clone());
                         // Publish failure
                         if let Some(ref bus) = self.event_bus {
@@ -485,7 +1046,7 @@ impl TaskExecutor {
                             LunaEvent::ActionRetry {
                                 action_type: action_name.clone(),
                                 attempt,
-                                max_attempts: self.retry_policy.max_attempts,
+                                max_attempts,
                                 error: e.to_string(),
                             },
                             context.correlation_id,
@@ -500,12 +1061,15 @@ impl TaskExecutor {
                 Err(_timeout_err) => {
                     // Timeout
                     let timeout_msg = format!(
-                        "Step timeout after {}s",
-                        self.execution_policy.max_step_timeout_secs
+                        "{} timed out after {:?} (limit {:?})",
+                        action_name,
+                        duration,
+                        step_timeout
                     );
+                    warn!("{}", timeout_msg);
                     last_error = Some(timeout_msg.clone());
 
-                    if attempt == self.retry_policy.max_attempts {
+                    if attempt == max_attempts {
                         if let Some(ref metrics) = self.metrics {
                             metrics.record_command_failure();
                         }
@@ -520,15 +1084,71 @@ impl TaskExecutor {
         ))
     }
 
-    /// Calculate exponential backoff duration
+    /// Run compensating actions for completed steps in reverse order (best-effort)
+    async fn run_compensations(&self, plan: &TaskPlan, context: &ExecutionContext) {
+        for &idx in context.completed_steps.iter().rev() {
+            let compensation = match plan.steps.get(idx).and_then(|s| s.compensation.as_ref()) {
+                Some(compensation) => compensation,
+                None => continue,
+            };
+
+            info!(
+                "Compensating step {} ({:?}) with {:?}",
+                idx, plan.steps[idx].action, compensation.action
+            );
+
+            let step_timeout = self.execution_policy.timeout_for(&compensation.action);
+            let start = Instant::now();
+            let outcome = timeout(step_timeout, self.execute_step(compensation, false)).await;
+            let result = match outcome {
+                Ok(result) => result,
+                Err(_) => Err(LunaError::SystemOperation(format!(
+                    "Compensation {:?} timed out (limit {:?})",
+                    compensation.action, step_timeout
+                ))),
+            };
+            let duration = start.elapsed();
+
+            let (success, message) = match result {
+                Ok(msg) => (true, msg),
+                Err(e) => {
+                    warn!("Compensation for step {} failed: {}", idx, e);
+                    (false, format!("Error: {}", e))
+                }
+            };
+
+            if let Some(ref bus) = self.event_bus {
+                bus.publish_with_correlation(
+                    LunaEvent::ActionCompleted {
+                        action_type: format!("{:?}", compensation.action),
+                        success,
+                        result: message,
+                        duration_ms: duration.as_millis() as u64,
+                    },
+                    context.correlation_id,
+                )
+                .await;
+            }
+        }
+    }
+
+    /// Calculate exponential backoff duration, with the policy's jitter applied
     fn calculate_backoff(&self, attempt: usize) -> Duration {
         let backoff_ms = self.retry_policy.initial_backoff_ms as f64
             * self
                 .retry_policy
                 .backoff_multiplier
                 .powi(attempt as i32 - 1);
-        let backoff_ms = backoff_ms.min(self.retry_policy.max_backoff_ms as f64) as u64;
-        Duration::from_millis(backoff_ms)
+        let backoff_ms = backoff_ms.min(self.retry_policy.max_backoff_ms as f64);
+        let backoff_ms = match self.retry_policy.jitter {
+            JitterStrategy::None => backoff_ms,
+            JitterStrategy::Full => self.backoff_rng.lock().gen_range(0.0..=backoff_ms),
+            JitterStrategy::Equal => {
+                let half = backoff_ms / 2.0;
+                half + self.backoff_rng.lock().gen_range(0.0..=half)
+            }
+        };
+        Duration::from_millis(backoff_ms as u64)
     }
 
     /// Check preconditions before executing step
@@ -557,6 +1177,123 @@ impl TaskExecutor {
         Ok(())
     }
 
+    /// Verify postconditions after a step executed successfully
+    ///
+    /// State checks are polled until `postcondition_timeout_ms` elapses so that
+    /// slow-starting apps are not reported as failures.
+    async fn verify_postconditions(
+        &self,
+        step: &ActionStep,
+        context: &ExecutionContext,
+    ) -> Result<()> {
+        if context.dry_run {
+            return Ok(());
+        }
+
+        let deadline =
+            Instant::now() + Duration::from_millis(self.execution_policy.postcondition_timeout_ms);
+
+        for postcond in &step.postconditions {
+            match postcond {
+                Postcondition::StateChanged(key, value) if key == "app_running" => {
+                    let app_name = match step
+                        .params
+                        .get("app_name")
+                        .or_else(|| step.params.get("application"))
+                        .or_else(|| step.params.get("name"))
+                    {
+                        Some(name) => name,
+                        None => continue,
+                    };
+                    let expected = value == "true";
+
+                    loop {
+                        if self.process_probe.is_running(app_name) == expected {
+                            break;
+                        }
+                        if Instant::now() >= deadline {
+                            return Err(LunaError::SystemOperation(format!(
+                                "Postcondition failed: {} running={} not observed",
+                                app_name, value
+                            )));
+                        }
+                        tokio::time::sleep(Duration::from_millis(250)).await;
+                    }
+                }
+                Postcondition::ResourceModified(path) if !std::path::Path::new(path).exists() => {
+                    return Err(LunaError::SystemOperation(format!(
+                        "Postcondition failed: {} does not exist",
+                        path
+                    )));
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Move (or maximize) a window onto a spoken monitor target
+    async fn move_to_monitor(
+        &self,
+        action: &str,
+        target: Option<&str>,
+        monitor: &str,
+    ) -> Result<String> {
+        let Some(monitor_target) = MonitorTarget::parse(monitor) else {
+            return Ok(format!("I'm not sure which monitor \"{}\" is", monitor));
+        };
+
+        let maximize = matches!(action, "maximize" | "maximise" | "fullscreen");
+        let Some(placement) = self
+            .monitors
+            .placement_for(monitor_target, maximize)
+            .await?
+        else {
+            return Ok(format!("I can't find the {}", monitor));
+        };
+
+        let selector = target
+            .map(|t| WindowSelector::ByTitle(t.to_string()))
+            .unwrap_or(WindowSelector::Active);
+        self.window_manager
+            .apply_action(WindowAction::MoveTo {
+                selector,
+                rect: placement.rect,
+                maximize,
+            })
+            .await?;
+
+        Ok(format!(
+            "{} {} on monitor {} ({})",
+            if maximize { "Maximized" } else { "Moved" },
+            target.unwrap_or("the window"),
+            placement.number,
+            placement.display.name
+        ))
+    }
+
+    /// The registered skill that claims a step, with the command it runs
+    ///
+    /// Only `Custom` steps and questions planned from unparsed input, which
+    /// carry the utterance as `text`, are offered to skills.
+    fn claiming_skill(&self, step: &ActionStep) -> Option<(&dyn Skill, ParsedCommand)> {
+        let unhandled = match step.action {
+            ActionType::Custom => true,
+            ActionType::AnswerQuestion => {
+                step.params.contains_key("text") && !step.params.contains_key("question")
+            }
+            _ => false,
+        };
+        if !unhandled {
+            return None;
+        }
+
+        let command = command_from_params(&step.params);
+        let skill = self.custom_skills.iter().find(|s| s.matches(&command))?;
+        Some((skill.as_ref(), command))
+    }
+
     /// Execute a single action step
     async fn execute_step(&self, step: &ActionStep, dry_run: bool) -> Result<String> {
         if dry_run {
@@ -566,6 +1303,15 @@ impl TaskExecutor {
             ));
         }
 
+        if let Some((skill, command)) = self.claiming_skill(step) {
+            debug!(
+                "Skill {} handles \"{}\"",
+                skill.name(),
+                command.original_text
+            );
+            return skill.execute(&command).await;
+        }
+
         match step.action {
             ActionType::LaunchApp => {
                 let app_name = step
@@ -577,6 +1323,14 @@ impl TaskExecutor {
                         LunaError::InvalidParameter("Missing app_name parameter".to_string())
                     })?;
 
+                // "open vscode and open budget.txt in it"
+                if let Some(target_app) = step.params.get("target_app") {
+                    return self
+                        .app_launcher
+                        .launch_with_args(target_app, std::slice::from_ref(app_name))
+                        .await;
+                }
+
                 self.app_launcher.launch(app_name).await
             }
 
@@ -599,9 +1353,8 @@ impl TaskExecutor {
                     .get("query")
                     .or_else(|| step.params.get("filename"))
                     .or_else(|| step.params.get("file"))
-                    .ok_or_else(|| {
-                        LunaError::InvalidParameter("Missing query parameter".to_string())
-                    })?;
+                    .or_else(|| step.params.get("file_name"));
+                let filter = FileFilter::from_params(&step.params);
 
                 let limit = step
                     .params
@@ -609,14 +1362,33 @@ impl TaskExecutor {
                     .and_then(|s| s.parse::<usize>().ok())
                     .unwrap_or(5);
 
-                let files = self.file_search.search_by_name(query, limit).await?;
+                // Filters alone ("videos bigger than 1GB") are a valid search
+                let (files, description) = if filter.is_empty() {
+                    let query = query.ok_or_else(|| {
+                        LunaError::InvalidParameter("Missing query parameter".to_string())
+                    })?;
+                    let files = self.file_search.search_by_name(query, limit).await?;
+                    (files, format!("matching '{}'", query))
+                } else {
+                    let query = query.map(String::as_str).unwrap_or("");
+                    let files = self
+                        .file_search
+                        .search_filtered(query, &filter, limit)
+                        .await?;
+                    let description = if query.is_empty() {
+                        "matching the filters".to_string()
+                    } else {
+                        format!("matching '{}' and the filters", query)
+                    };
+                    (files, description)
+                };
 
                 if files.is_empty() {
-                    Ok(format!("No files found matching '{}'", query))
+                    Ok(format!("No files found {}", description))
                 } else if files.len() == 1 {
                     self.file_search.open_file(&files[0]).await
                 } else {
-                    Ok(format!("Found {} files matching '{}'", files.len(), query))
+                    Ok(format!("Found {} files {}", files.len(), description))
                 }
             }
 
@@ -665,23 +1437,15 @@ impl TaskExecutor {
 
                 match action {
                     "set" => {
-                        let level = step
-                            .params
-                            .get("level")
-                            .and_then(|s| s.parse::<u8>().ok())
-                            .ok_or_else(|| {
-                                LunaError::InvalidParameter(
-                                    "Missing or invalid volume level".to_string(),
-                                )
-                            })?;
+                        let level = level_param(&step.params, "level").ok_or_else(|| {
+                            LunaError::InvalidParameter(
+                                "Missing or invalid volume level".to_string(),
+                            )
+                        })?;
                         self.system_control.set_volume(level).await
                     }
                     "adjust" => {
-                        let delta = step
-                            .params
-                            .get("delta")
-                            .and_then(|s| s.parse::<i8>().ok())
-                            .unwrap_or(10);
+                        let delta = delta_param(&step.params, "delta").unwrap_or(10);
                         self.system_control.adjust_volume(delta).await
                     }
                     "up" => self.system_control.adjust_volume(10).await,
@@ -690,6 +1454,32 @@ impl TaskExecutor {
                 }
             }
 
+            ActionType::BrightnessControl => {
+                let action = step
+                    .params
+                    .get("action")
+                    .map(|s| s.as_str())
+                    .unwrap_or("set");
+
+                match action {
+                    "set" => {
+                        let level = level_param(&step.params, "level").ok_or_else(|| {
+                            LunaError::InvalidParameter(
+                                "Missing or invalid brightness level".to_string(),
+                            )
+                        })?;
+                        self.system_control.set_brightness(level).await
+                    }
+                    "adjust" => {
+                        let delta = delta_param(&step.params, "delta").unwrap_or(10);
+                        self.system_control.adjust_brightness(delta).await
+                    }
+                    "up" => self.system_control.adjust_brightness(10).await,
+                    "down" => self.system_control.adjust_brightness(-10).await,
+                    _ => Ok(format!("Unknown brightness action: {}", action)),
+                }
+            }
+
             ActionType::MediaControl => {
                 let action = step
                     .params
@@ -704,6 +1494,23 @@ impl TaskExecutor {
                     "stop" => self.media_control.stop().await,
                     "status" => self.media_control.get_status().await,
                     "current" | "track" => self.media_control.get_current_track().await,
+                    "seek" => match step.params.get("delta").and_then(|d| d.parse().ok()) {
+                        Some(delta) => self.media_control.seek(delta).await,
+                        None => Ok("How far should I skip?".to_string()),
+                    },
+                    "volume" => {
+                        let Some(level) = level_param(&step.params, "level") else {
+                            return Ok("What volume should I set the player to?".to_string());
+                        };
+                        match step.params.get("player") {
+                            Some(player) => {
+                                self.media_control
+                                    .set_player_volume_for(player, level)
+                                    .await
+                            }
+                            None => self.media_control.set_player_volume(level).await,
+                        }
+                    }
                     _ => Ok(format!("Unknown media control action: {}", action)),
                 }
             }
@@ -713,11 +1520,67 @@ impl TaskExecutor {
                     .params
                     .get("action")
                     .map(|s| s.as_str())
-                    .unwrap_or("manage");
-                Ok(format!(
-                    "Window management '{}' not yet implemented",
-                    action
-                ))
+                    .unwrap_or("focus");
+
+                // Fall back to the active window when no app is named
+                let target = step
+                    .params
+                    .get("app_name")
+                    .or_else(|| step.params.get("application"))
+                    .or_else(|| step.params.get("window"))
+                    .or_else(|| step.params.get("name"))
+                    .map(|s| s.as_str());
+
+                if let Some(monitor) = step.params.get("monitor") {
+                    return self.move_to_monitor(action, target, monitor).await;
+                }
+
+                self.window_control.perform(action, target).await
+            }
+
+            ActionType::VirtualDesktopControl => {
+                let action = step
+                    .params
+                    .get("action")
+                    .map(|s| s.as_str())
+                    .unwrap_or("list");
+                // "desktop two" -> 2
+                let number = level_param(&step.params, "desktop").map(usize::from);
+
+                self.virtual_desktops.perform(action, number).await
+            }
+
+            ActionType::PowerControl => {
+                let action = step
+                    .params
+                    .get("action")
+                    .map(|s| s.as_str())
+                    .unwrap_or("status");
+                let profile = step.params.get("profile").map(|s| s.as_str());
+
+                self.power.perform(action, profile).await
+            }
+
+            ActionType::ProcessControl => {
+                let action = step
+                    .params
+                    .get("action")
+                    .map(|s| s.as_str())
+                    .unwrap_or("priority");
+                let process = step.params.get("process").ok_or_else(|| {
+                    LunaError::InvalidParameter("Missing process parameter".to_string())
+                })?;
+                let value = match action {
+                    "affinity" => step.params.get("cores"),
+                    _ => step.params.get("priority"),
+                };
+
+                let optimizer = self.process_optimizer.as_ref().ok_or_else(|| {
+                    LunaError::InvalidParameter("Process control is not available".to_string())
+                })?;
+                optimizer
+                    .perform(action, process, value.map(|s| s.as_str()))
+                    .await
             }
 
             ActionType::SearchWeb => {
@@ -737,6 +1600,12 @@ impl TaskExecutor {
                 Ok(format!("Searching web for: {}", query))
             }
 
+            ActionType::Acknowledge => Ok(step
+                .params
+                .get("message")
+                .cloned()
+                .unwrap_or_else(|| "Okay".to_string())),
+
             ActionType::GetTime => {
                 let now = chrono::Local::now();
                 Ok(format!("Current time is {}", now.format("%I:%M %p")))
@@ -760,9 +1629,1110 @@ impl TaskExecutor {
                 Ok(format!("Waited {} seconds", duration_secs))
             }
 
-            ActionType::CreateReminder | ActionType::TakeNote | ActionType::AnswerQuestion => {
-                Ok(format!("{:?} not yet implemented", step.action))
+            ActionType::Custom => Err(LunaError::SystemOperation(format!(
+                "No skill handles \"{}\"",
+                command_from_params(&step.params).original_text
+            ))),
+
+            ActionType::AnswerQuestion => {
+                let question = step
+                    .params
+                    .get("question")
+                    .or_else(|| step.params.get("query"))
+                    .or_else(|| step.params.get("text"))
+                    .ok_or_else(|| {
+                        LunaError::InvalidParameter("Missing question parameter".to_string())
+                    })?;
+
+                let answerer = self.question_answerer.as_ref().ok_or_else(|| {
+                    LunaError::SystemOperation("Question answering is not configured".to_string())
+                })?;
+
+                let answer = answerer.answer(question).await?;
+                debug!("Answered \"{}\" from {}", question, answer.source);
+                Ok(answer.text)
             }
+
+            ActionType::ClipboardControl => {
+                let action = step
+                    .params
+                    .get("action")
+                    .map(|s| s.as_str())
+                    .unwrap_or("read");
+
+                match action {
+                    "read" | "paste" => match self.clipboard.read().await {
+                        Ok(text) if text.trim().is_empty() => {
+                            Ok("Your clipboard is empty".to_string())
+                        }
+                        Ok(text) => Ok(format!(
+                            "Your clipboard says: {}",
+                            preview_text(&text, 200)
+                        )),
+                        Err(e) => {
+                            // Images, files and non-UTF8 data can't be read as text
+                            warn!("Clipboard read failed: {}", e);
+                            Ok("Your clipboard doesn't contain readable text".to_string())
+                        }
+                    },
+                    "write" | "copy" => {
+                        let text = step.params.get("text").ok_or_else(|| {
+                            LunaError::InvalidParameter("Missing text parameter".to_string())
+                        })?;
+
+                        // "copy that" refers to the last thing Luna said
+                        let text = match text.trim() {
+                            "that" | "it" | "this" => {
+                                self.last_response.read().await.clone().ok_or_else(|| {
+                                    LunaError::InvalidParameter(
+                                        "Nothing to copy yet".to_string(),
+                                    )
+                                })?
+                            }
+                            _ => text.clone(),
+                        };
+
+                        let result = self.clipboard.write(&text).await?;
+                        self.clipboard_history.push(&text);
+                        Ok(result)
+                    }
+                    "clear" => self.clipboard.clear().await,
+                    "history" => {
+                        let entries = self.clipboard_history.list();
+                        if entries.is_empty() {
+                            return Ok("Your clipboard history is empty".to_string());
+                        }
+
+                        let items: Vec<String> = entries
+                            .iter()
+                            .filter_map(|entry| entry.text())
+                            .take(5)
+                            .enumerate()
+                            .map(|(i, text)| format!("{}: {}", i + 1, preview_text(text, 60)))
+                            .collect();
+                        Ok(format!("Clipboard history. {}", items.join(". ")))
+                    }
+                    "recall" => {
+                        // 1 is the latest copy, 2 the one before it
+                        let number = level_param(&step.params, "number").unwrap_or(1).max(1);
+                        let text = self
+                            .clipboard_history
+                            .get(usize::from(number) - 1)
+                            .and_then(|entry| entry.text().map(str::to_string))
+                            .ok_or_else(|| {
+                                LunaError::InvalidParameter(format!(
+                                    "There's no item {} in your clipboard history",
+                                    number
+                                ))
+                            })?;
+
+                        self.clipboard.write(&text).await?;
+                        self.clipboard_history.push(&text);
+                        Ok(format!("Ready to paste: {}", preview_text(&text, 100)))
+                    }
+                    _ => Ok(format!("Unknown clipboard action: {}", action)),
+                }
+            }
+
+            ActionType::CreateReminder => {
+                let message = step
+                    .params
+                    .get("message")
+                    .map(|s| s.as_str())
+                    .unwrap_or("Reminder");
+
+                let due = reminder_due(&step.params).ok_or_else(|| {
+                    LunaError::InvalidParameter("Missing or invalid reminder time".to_string())
+                })?;
+
+                create_reminder_with_bus(message, due - Local::now(), self.event_bus.clone())
+                    .await?;
+                Ok(format!(
+                    "I'll remind you to {} {}",
+                    message,
+                    due.format("on %A at %-I:%M %p")
+                ))
+            }
+
+            ActionType::RunCommand => {
+                let name = step
+                    .params
+                    .get("skill")
+                    .or_else(|| step.params.get("command"))
+                    .ok_or_else(|| {
+                        LunaError::InvalidParameter("Missing skill parameter".to_string())
+                    })?;
+
+                // Only registry-resolved commands reach the OS, never raw text
+                let skills = self.skills.as_ref().ok_or_else(|| {
+                    LunaError::InvalidParameter(format!("'{}' is not an allowed command", name))
+                })?;
+                let command = skills.resolve(name, &step.params)?;
+                crate::os::run_shell_intent(&command).await
+            }
+
+            ActionType::TakeNote => Ok(format!("{:?} not yet implemented", step.action)),
         }
     }
 }
+
+/// Read a 0-100 level param, accepting "50", "75%", "fifty" or "a quarter"
+fn level_param(params: &HashMap<String, String>, key: &str) -> Option<u8> {
+    Entities::from_legacy_hashmap(params)
+        .get(key)
+        .and_then(Entity::as_level)
+}
+
+/// Resolve when a reminder is due from its "when" or "duration" param
+fn reminder_due(params: &HashMap<String, String>) -> Option<DateTime<Local>> {
+    let entities = Entities::from_legacy_hashmap(params);
+    if let Some(when) = entities.get("when").and_then(Entity::as_datetime) {
+        return Some(when);
+    }
+
+    params
+        .get("duration")
+        .map(String::as_str)
+        .and_then(time_helpers::parse_duration)
+        .map(|d| Local::now() + d)
+}
+
+/// Read a signed step param, clamped to the `i8` range
+fn delta_param(params: &HashMap<String, String>, key: &str) -> Option<i8> {
+    Entities::from_legacy_hashmap(params)
+        .get(key)
+        .and_then(Entity::as_number)
+        .map(|n| n.round().clamp(i8::MIN as f64, i8::MAX as f64) as i8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::brain::command_parser::{CommandParser, IntentType, ParsedCommand};
+    use crate::brain::intent_classifier::ClassificationResult;
+    use crate::brain::task_planner::TaskPlanner;
+    use crate::db::{AppDatabase, FileIndex};
+    use crate::events::EventKind;
+    use crate::knowledge::{Entity, EntityType};
+    use crate::os::multi_monitor::{DisplayCapabilities, DisplayInfo, DisplayRotation};
+    use crate::os::power_manager::mock::MockPowerBackend;
+    use crate::os::process_priority::mock::MockPriorityBackend;
+    use crate::os::virtual_desktop::mock::MockDesktopBackend;
+
+    fn create_executor() -> TaskExecutor {
+        let app_launcher = AppLauncher::new(Arc::new(parking_lot::RwLock::new(AppDatabase::new())));
+        let file_search = FileSearch::new(Arc::new(FileIndex::new()));
+        TaskExecutor::new(app_launcher, file_search)
+    }
+
+    fn test_display(name: &str, x: i32, is_primary: bool) -> DisplayInfo {
+        DisplayInfo {
+            id: name.to_lowercase(),
+            name: name.to_string(),
+            resolution: (1920, 1080),
+            position: (x, 0),
+            scale_factor: 1.0,
+            refresh_rate: 60,
+            is_primary,
+            rotation: DisplayRotation::Normal,
+            capabilities: DisplayCapabilities::default(),
+        }
+    }
+
+    fn single_step_plan(action: ActionType, params: HashMap<String, String>) -> TaskPlan {
+        TaskPlan {
+            steps: vec![ActionStep {
+                action,
+                params: params.clone(),
+                step_number: 0,
+                preconditions: vec![],
+                postconditions: vec![Postcondition::Success],
+                parallel_group: None,
+                compensation: None,
+                optional: false,
+            }],
+            dependencies: vec![],
+            classification: ClassificationResult {
+                intent: IntentType::Question,
+                confidence: 1.0,
+                entities: params,
+                alternatives: vec![],
+                factors: vec![],
+            },
+            parallel_groups: vec![],
+            is_valid: true,
+            validation_errors: vec![],
+            correlation_id: None,
+        }
+    }
+
+    /// Answerer backed only by a pre-populated knowledge graph (no network)
+    fn mock_answerer() -> Arc<QuestionAnswerer> {
+        let qa = QuestionAnswerer::new();
+        qa.knowledge_graph().add_entity(Entity {
+            id: "luna".to_string(),
+            name: "Luna".to_string(),
+            entity_type: EntityType::Concept,
+            description: Some("Luna is a privacy-first voice assistant".to_string()),
+            aliases: vec![],
+            metadata: HashMap::new(),
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+        });
+        Arc::new(qa)
+    }
+
+    #[test]
+    fn test_reminder_due() {
+        let now = chrono::Local::now();
+
+        let mut params = HashMap::new();
+        params.insert("when".to_string(), "tomorrow at 9am".to_string());
+        assert!(reminder_due(&params).unwrap() > now);
+
+        let mut params = HashMap::new();
+        params.insert("duration".to_string(), "30 minutes".to_string());
+        let due = reminder_due(&params).unwrap();
+        assert!(due > now + chrono::Duration::minutes(29));
+
+        assert!(reminder_due(&HashMap::new()).is_none());
+    }
+
+    #[test]
+    fn test_typed_level_params() {
+        let mut params = HashMap::new();
+        params.insert("level".to_string(), "fifty".to_string());
+        assert_eq!(level_param(&params, "level"), Some(50));
+
+        params.insert("level".to_string(), "75%".to_string());
+        assert_eq!(level_param(&params, "level"), Some(75));
+
+        params.insert("delta".to_string(), "-5".to_string());
+        assert_eq!(delta_param(&params, "delta"), Some(-5));
+        assert_eq!(level_param(&params, "missing"), None);
+    }
+
+    #[tokio::test]
+    async fn test_answer_question_uses_answerer() {
+        let executor = create_executor().with_question_answerer(mock_answerer());
+
+        let mut params = HashMap::new();
+        params.insert("question".to_string(), "What is Luna?".to_string());
+
+        let result = executor
+            .execute_plan(single_step_plan(ActionType::AnswerQuestion, params))
+            .await
+            .unwrap();
+
+        // Only the answer is spoken, not where it came from
+        let answer = mock_answerer().answer("What is Luna?").await.unwrap();
+        assert_eq!(result, answer.text);
+        assert!(result.contains("privacy-first voice assistant"));
+    }
+
+    #[tokio::test]
+    async fn test_failed_step_runs_compensation() {
+        let bus = Arc::new(EventBus::new());
+        let handle = bus.start_processing().await;
+
+        let completed = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let completed_clone = completed.clone();
+        bus.subscribe_kinds(&[EventKind::ActionCompleted], move |envelope| {
+            if let LunaEvent::ActionCompleted {
+                action_type,
+                success,
+                ..
+            } = &envelope.event
+            {
+                completed_clone
+                    .lock()
+                    .unwrap()
+                    .push((action_type.clone(), *success));
+            }
+        })
+        .await;
+
+        let executor = create_executor()
+            .with_event_bus(bus.clone())
+            .with_retry_policy(RetryPolicy {
+                max_attempts: 1,
+                ..RetryPolicy::default()
+            });
+
+        // Step 1 succeeds and carries a compensation; step 2 fails
+        let mut plan = single_step_plan(ActionType::GetTime, HashMap::new());
+        plan.steps[0].compensation = Some(Box::new(ActionStep {
+            action: ActionType::GetDate,
+            params: HashMap::new(),
+            step_number: 0,
+            preconditions: vec![],
+            postconditions: vec![],
+            parallel_group: None,
+            compensation: None,
+            optional: false,
+        }));
+
+        let mut params = HashMap::new();
+        params.insert(
+            "path".to_string(),
+            "/nonexistent/luna/test/folder".to_string(),
+        );
+        plan.steps.push(ActionStep {
+            action: ActionType::OpenFolder,
+            params,
+            step_number: 1,
+            preconditions: vec![],
+            postconditions: vec![],
+            parallel_group: None,
+            compensation: None,
+            optional: false,
+        });
+
+        let result = executor.execute_plan(plan).await;
+        assert!(result.is_err());
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        let completed = completed.lock().unwrap();
+        assert_eq!(
+            completed.last(),
+            Some(&("GetDate".to_string(), true)),
+            "compensation for step 1 should run after step 2 fails"
+        );
+
+        handle.abort();
+    }
+
+    /// Probe that never sees the app and counts how often it was asked
+    struct AbsentProbe {
+        checks: std::sync::atomic::AtomicUsize,
+    }
+
+    impl ProcessProbe for AbsentProbe {
+        fn is_running(&self, _app_name: &str) -> bool {
+            self.checks.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            false
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unmet_postcondition_retries_step() {
+        let probe = Arc::new(AbsentProbe {
+            checks: std::sync::atomic::AtomicUsize::new(0),
+        });
+
+        let executor = create_executor()
+            .with_process_probe(probe.clone())
+            .with_retry_policy(RetryPolicy {
+                max_attempts: 3,
+                initial_backoff_ms: 1,
+                max_backoff_ms: 1,
+                backoff_multiplier: 1.0,
+                jitter: JitterStrategy::None,
+            })
+            .with_execution_policy(ExecutionPolicy {
+                postcondition_timeout_ms: 0,
+                ..ExecutionPolicy::default()
+            });
+
+        let mut params = HashMap::new();
+        params.insert("app_name".to_string(), "crashy".to_string());
+        let mut plan = single_step_plan(ActionType::GetTime, params);
+        plan.steps[0].postconditions = vec![Postcondition::StateChanged(
+            "app_running".to_string(),
+            "true".to_string(),
+        )];
+
+        let result = executor.execute_plan(plan).await;
+
+        assert!(result.is_err());
+        assert_eq!(probe.checks.load(std::sync::atomic::Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_denied_confirmation_blocks_action() {
+        let bus = Arc::new(EventBus::new());
+        let handle = bus.start_processing().await;
+
+        let started = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let started_clone = started.clone();
+        bus.subscribe_kinds(&[EventKind::ActionStarted], move |envelope| {
+            if let LunaEvent::ActionStarted { action_type, .. } = &envelope.event {
+                started_clone.lock().unwrap().push(action_type.clone());
+            }
+        })
+        .await;
+
+        let asked = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let asked_clone = asked.clone();
+        let executor = create_executor()
+            .with_event_bus(bus.clone())
+            .with_confirmation_handler(move |step| {
+                assert_eq!(step.action, ActionType::SystemControl);
+                asked_clone.store(true, std::sync::atomic::Ordering::SeqCst);
+                Box::pin(async { false })
+            });
+
+        let mut params = HashMap::new();
+        params.insert("action".to_string(), "shutdown".to_string());
+
+        let result = executor
+            .execute_plan(single_step_plan(ActionType::SystemControl, params))
+            .await;
+
+        assert!(result.unwrap_err().to_string().contains("not confirmed"));
+        assert!(asked.load(std::sync::atomic::Ordering::SeqCst));
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(started.lock().unwrap().is_empty());
+
+        handle.abort();
+    }
+
+    #[tokio::test]
+    async fn test_gated_action_refused_without_handler() {
+        let mut params = HashMap::new();
+        params.insert("action".to_string(), "shutdown".to_string());
+        let plan = single_step_plan(ActionType::SystemControl, params);
+
+        let result = create_executor().execute_plan(plan.clone()).await;
+        assert!(result.unwrap_err().to_string().contains("not confirmed"));
+
+        // A dry run describes the step without asking
+        assert!(create_executor().preview_plan(plan).await.is_ok());
+    }
+
+    #[test]
+    fn test_confirmation_question() {
+        let mut params = HashMap::new();
+        params.insert("action".to_string(), "shutdown".to_string());
+        let plan = single_step_plan(ActionType::SystemControl, params);
+        assert_eq!(
+            confirmation_question(&plan.steps[0]),
+            "Are you sure you want to shut down the computer?"
+        );
+        assert!(is_affirmative("Yes, do it"));
+        assert!(!is_affirmative("no"));
+    }
+
+    #[test]
+    fn test_per_action_timeout_fallback() {
+        let policy = ExecutionPolicy::default()
+            .with_action_timeout(ActionType::FindFile, Duration::from_secs(120));
+
+        assert_eq!(
+            policy.timeout_for(&ActionType::FindFile),
+            Duration::from_secs(120)
+        );
+        assert_eq!(
+            policy.timeout_for(&ActionType::GetTime),
+            Duration::from_secs(policy.max_step_timeout_secs)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_timeout_error_names_action() {
+        let executor = create_executor()
+            .with_retry_policy(RetryPolicy {
+                max_attempts: 1,
+                ..RetryPolicy::default()
+            })
+            .with_execution_policy(
+                ExecutionPolicy::default()
+                    .with_action_timeout(ActionType::Wait, Duration::from_millis(20)),
+            );
+
+        let mut params = HashMap::new();
+        params.insert("duration".to_string(), "5".to_string());
+
+        let err = executor
+            .execute_plan(single_step_plan(ActionType::Wait, params))
+            .await
+            .unwrap_err()
+            .to_string();
+
+        assert!(err.contains("Wait"));
+        assert!(err.contains("20ms"));
+    }
+
+    #[tokio::test]
+    async fn test_execute_plan_detailed() {
+        let executor = create_executor();
+
+        let mut plan = single_step_plan(ActionType::GetTime, HashMap::new());
+        plan.steps.push(ActionStep {
+            action: ActionType::GetDate,
+            params: HashMap::new(),
+            step_number: 1,
+            preconditions: vec![],
+            postconditions: vec![Postcondition::Success],
+            parallel_group: None,
+            compensation: None,
+            optional: false,
+        });
+
+        let outcome = executor.execute_plan_detailed(plan).await.unwrap();
+
+        assert!(outcome.success());
+        assert_eq!(outcome.steps.len(), 2);
+        assert_eq!(outcome.steps[0].action, ActionType::GetTime);
+        assert_eq!(outcome.steps[1].action, ActionType::GetDate);
+        assert!(outcome.last_message().unwrap().starts_with("Today is"));
+        assert_eq!(
+            outcome.summary(),
+            format!("{}. {}", outcome.steps[0].message, outcome.steps[1].message)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_answer_question_without_answerer() {
+        let executor = create_executor();
+
+        let mut params = HashMap::new();
+        params.insert("question".to_string(), "What is Luna?".to_string());
+
+        let result = executor
+            .execute_plan(single_step_plan(ActionType::AnswerQuestion, params))
+            .await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_window_to_missing_monitor() {
+        let monitors = Arc::new(MultiMonitorManager::new());
+        monitors
+            .set_displays(vec![
+                test_display("eDP-1", 0, true),
+                test_display("HDMI-1", 1920, false),
+            ])
+            .await;
+        let executor = create_executor().with_monitors(monitors);
+
+        let mut params = HashMap::new();
+        params.insert("action".to_string(), "move".to_string());
+        params.insert("app_name".to_string(), "chrome".to_string());
+        params.insert("monitor".to_string(), "third monitor".to_string());
+
+        let result = executor
+            .execute_plan(single_step_plan(ActionType::WindowManagement, params))
+            .await
+            .unwrap();
+        assert_eq!(result, "I can't find the third monitor");
+    }
+
+    #[tokio::test]
+    async fn test_virtual_desktop_dispatch() {
+        let backend = MockDesktopBackend::with_desktops(3);
+        let executor = create_executor()
+            .with_virtual_desktops(VirtualDesktopManager::new().with_backend(backend.clone()));
+
+        let steps = [
+            vec![("action", "switch"), ("desktop", "two")],
+            vec![("action", "move"), ("desktop", "3")],
+            vec![("action", "next")],
+            vec![("action", "create")],
+        ];
+        for params in steps {
+            let params = params
+                .into_iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect();
+            executor
+                .execute_plan(single_step_plan(ActionType::VirtualDesktopControl, params))
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(
+            backend.calls(),
+            ["switch 1", "move 2", "switch 2", "count 4"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_power_profile_dispatch() {
+        let backend = MockPowerBackend::with_battery(None);
+        let executor =
+            create_executor().with_power_manager(PowerManager::new().with_backend(backend.clone()));
+
+        let mut params = HashMap::new();
+        params.insert("action".to_string(), "profile".to_string());
+        params.insert("profile".to_string(), "battery saver".to_string());
+        let result = executor
+            .execute_plan(single_step_plan(ActionType::PowerControl, params))
+            .await
+            .unwrap();
+
+        assert_eq!(result, "Switched to battery saver mode");
+        assert_eq!(*backend.profiles.lock(), ["battery saver"]);
+    }
+
+    #[tokio::test]
+    async fn test_process_control_requires_confirmation() {
+        let backend = MockPriorityBackend::with_process("chrome", &[42]);
+        let optimizer = ProcessOptimizer::new().with_backend(backend.clone());
+        let confirm = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let confirm_clone = confirm.clone();
+        let executor = create_executor()
+            .with_process_optimizer(Arc::new(optimizer))
+            .with_confirmation_handler(move |step| {
+                assert_eq!(step.action, ActionType::ProcessControl);
+                let confirmed = confirm_clone.load(std::sync::atomic::Ordering::SeqCst);
+                Box::pin(async move { confirmed })
+            });
+
+        let mut params = HashMap::new();
+        params.insert("action".to_string(), "affinity".to_string());
+        params.insert("process".to_string(), "chrome".to_string());
+        params.insert("cores".to_string(), "0".to_string());
+
+        // Declined: nothing reaches the backend
+        assert!(executor
+            .execute_plan(single_step_plan(ActionType::ProcessControl, params.clone()))
+            .await
+            .is_err());
+        assert!(backend.calls().is_empty());
+
+        confirm.store(true, std::sync::atomic::Ordering::SeqCst);
+        let result = executor
+            .execute_plan(single_step_plan(ActionType::ProcessControl, params.clone()))
+            .await
+            .unwrap();
+        assert_eq!(result, "Pinned chrome to core 0");
+        assert_eq!(backend.calls(), ["affinity 42 0"]);
+
+        // A missing optimizer won't appear on retry, so it fails straight away
+        let executor = create_executor().with_confirmation_handler(|_| Box::pin(async { true }));
+        let error = executor
+            .execute_plan(single_step_plan(ActionType::ProcessControl, params))
+            .await
+            .unwrap_err();
+        assert!(!error.is_recoverable());
+    }
+
+    #[tokio::test]
+    async fn test_clipboard_history_listing() {
+        let history = Arc::new(ClipboardHistory::new(10));
+        history.push("first");
+        history.push("second");
+        let executor = create_executor().with_clipboard_history(history);
+
+        let mut params = HashMap::new();
+        params.insert("action".to_string(), "history".to_string());
+        let result = executor
+            .execute_plan(single_step_plan(ActionType::ClipboardControl, params))
+            .await
+            .unwrap();
+        assert_eq!(result, "Clipboard history. 1: second. 2: first");
+
+        // Recalling past the end fails before touching the clipboard
+        let mut params = HashMap::new();
+        params.insert("action".to_string(), "recall".to_string());
+        params.insert("number".to_string(), "3".to_string());
+        assert!(executor
+            .execute_plan(single_step_plan(ActionType::ClipboardControl, params))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_run_command_requires_allowlisted_skill() {
+        let skills = SkillRegistry::from_yaml(
+            "allowed_binaries: [echo]\nskills:\n  greet:\n    argv: [echo, \"hi {name}\"]\n",
+        )
+        .unwrap();
+        let executor = create_executor().with_confirmation_handler(|step| {
+            let approved = step.params.get("name").map(String::as_str) == Some("luna");
+            Box::pin(async move { approved })
+        });
+
+        let mut params = HashMap::new();
+        params.insert("skill".to_string(), "greet".to_string());
+        params.insert("name".to_string(), "luna".to_string());
+
+        // No registry: nothing runs
+        assert!(executor
+            .execute_plan(single_step_plan(ActionType::RunCommand, params.clone()))
+            .await
+            .is_err());
+
+        let executor = executor.with_skills(Arc::new(skills));
+        let mut unlisted = params.clone();
+        unlisted.insert("skill".to_string(), "rm".to_string());
+        assert!(executor
+            .execute_plan(single_step_plan(ActionType::RunCommand, unlisted))
+            .await
+            .is_err());
+
+        #[cfg(unix)]
+        assert_eq!(
+            executor
+                .execute_plan(single_step_plan(ActionType::RunCommand, params.clone()))
+                .await
+                .unwrap(),
+            "hi luna"
+        );
+
+        // An allowlisted command still doesn't run when the user declines
+        params.insert("name".to_string(), "world".to_string());
+        assert!(executor
+            .execute_plan(single_step_plan(ActionType::RunCommand, params))
+            .await
+            .unwrap_err()
+            .to_string()
+            .contains("not confirmed"));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_failed_command_is_not_retried() {
+        let bus = Arc::new(EventBus::new());
+        let handle = bus.start_processing().await;
+
+        let retries = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let retries_clone = retries.clone();
+        bus.subscribe_kinds(&[EventKind::ActionRetry], move |_| {
+            retries_clone.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        })
+        .await;
+
+        let skills = SkillRegistry::from_yaml(
+            "allowed_binaries: [\"false\"]\nskills:\n  fail:\n    argv: [\"false\"]\n",
+        )
+        .unwrap();
+        let executor = create_executor()
+            .with_event_bus(bus.clone())
+            .with_skills(Arc::new(skills))
+            .with_retry_policy(RetryPolicy {
+                max_attempts: 3,
+                initial_backoff_ms: 1,
+                max_backoff_ms: 1,
+                backoff_multiplier: 1.0,
+                jitter: JitterStrategy::None,
+            })
+            .with_confirmation_handler(|_| Box::pin(async { true }));
+
+        let mut params = HashMap::new();
+        params.insert("skill".to_string(), "fail".to_string());
+        assert!(executor
+            .execute_plan(single_step_plan(ActionType::RunCommand, params))
+            .await
+            .is_err());
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(retries.load(std::sync::atomic::Ordering::SeqCst), 0);
+
+        handle.abort();
+    }
+
+    struct DiceSkill;
+
+    impl Skill for DiceSkill {
+        fn matches(&self, command: &ParsedCommand) -> bool {
+            command.original_text.contains("roll")
+        }
+
+        fn execute<'a>(&'a self, command: &'a ParsedCommand) -> BoxFuture<'a, Result<String>> {
+            Box::pin(async move { Ok(format!("You rolled a 4 ({})", command.original_text)) })
+        }
+
+        fn name(&self) -> &str {
+            "dice"
+        }
+    }
+
+    #[tokio::test]
+    async fn test_registered_skill_handles_unknown_command() {
+        let plan_for = |text: &str| {
+            let parsed = CommandParser::new().parse(text).unwrap();
+            TaskPlanner::new().plan(ClassificationResult {
+                intent: parsed.intent,
+                confidence: parsed.confidence,
+                entities: parsed.entities,
+                alternatives: Vec::new(),
+                factors: Vec::new(),
+            })
+        };
+
+        let mut executor = create_executor().with_retry_policy(RetryPolicy {
+            max_attempts: 1,
+            ..RetryPolicy::default()
+        });
+        executor.register_skill(Box::new(DiceSkill));
+
+        let plan = plan_for("roll a die");
+        assert_eq!(plan.steps[0].action, ActionType::AnswerQuestion);
+        assert_eq!(
+            executor.execute_plan(plan).await.unwrap(),
+            "You rolled a 4 (roll a die)"
+        );
+
+        // Unclaimed, and nothing to answer it with
+        assert!(executor
+            .execute_plan(plan_for("flip a coin"))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_unclaimed_custom_step_is_not_answered() {
+        let mut executor = create_executor()
+            .with_question_answerer(mock_answerer())
+            .with_retry_policy(RetryPolicy {
+                max_attempts: 1,
+                ..RetryPolicy::default()
+            });
+        executor.register_skill(Box::new(DiceSkill));
+
+        let params = HashMap::from([("text".to_string(), "What is Luna?".to_string())]);
+        let result = executor
+            .execute_plan(single_step_plan(ActionType::Custom, params.clone()))
+            .await;
+        assert!(result.is_err());
+
+        // The same utterance planned as a question is still answered
+        let result = executor
+            .execute_plan(single_step_plan(ActionType::AnswerQuestion, params))
+            .await
+            .unwrap();
+        assert!(result.contains("privacy-first voice assistant"));
+    }
+
+    #[test]
+    fn test_backoff_jitter_bounds() {
+        let executor = |jitter| {
+            create_executor()
+                .with_retry_policy(RetryPolicy {
+                    jitter,
+                    ..RetryPolicy::default()
+                })
+                .with_jitter_seed(7)
+        };
+
+        // 100, 200, 400, 800, 1600, 3200, then capped at 5000
+        let uncapped = |attempt: usize| (100u64 << (attempt - 1)).min(5000);
+
+        let exact = executor(JitterStrategy::None);
+        for attempt in 1..=8 {
+            assert_eq!(
+                exact.calculate_backoff(attempt),
+                Duration::from_millis(uncapped(attempt))
+            );
+        }
+
+        let full = executor(JitterStrategy::Full);
+        let equal = executor(JitterStrategy::Equal);
+        for attempt in 1..=8 {
+            let max = uncapped(attempt);
+            for _ in 0..20 {
+                let delay = full.calculate_backoff(attempt).as_millis() as u64;
+                assert!(delay <= max, "full jitter {} > {}", delay, max);
+
+                let delay = equal.calculate_backoff(attempt).as_millis() as u64;
+                assert!(
+                    (max / 2..=max).contains(&delay),
+                    "equal jitter {} outside {}..={}",
+                    delay,
+                    max / 2,
+                    max
+                );
+            }
+        }
+
+        let delays: Vec<Duration> = (0..10).map(|_| equal.calculate_backoff(6)).collect();
+        assert!(delays.iter().any(|d| *d != delays[0]));
+
+        // The same seed gives the same sequence
+        let replay = executor(JitterStrategy::Equal);
+        let again = executor(JitterStrategy::Equal);
+        for attempt in 1..=5 {
+            assert_eq!(
+                replay.calculate_backoff(attempt),
+                again.calculate_backoff(attempt)
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_optional_step_failure_continues() {
+        let executor = create_executor().with_retry_policy(RetryPolicy {
+            max_attempts: 1,
+            ..RetryPolicy::default()
+        });
+
+        // Step 1 is optional and fails; step 2 is required and succeeds
+        let mut params = HashMap::new();
+        params.insert(
+            "path".to_string(),
+            "/nonexistent/luna/test/folder".to_string(),
+        );
+        let mut plan = single_step_plan(ActionType::OpenFolder, params);
+        plan.steps[0].optional = true;
+        plan.steps.push(ActionStep {
+            action: ActionType::GetTime,
+            params: HashMap::new(),
+            step_number: 1,
+            preconditions: vec![],
+            postconditions: vec![],
+            parallel_group: None,
+            compensation: None,
+            optional: false,
+        });
+
+        let outcome = executor.execute_plan_detailed(plan.clone()).await.unwrap();
+        assert_eq!(outcome.steps.len(), 2);
+        assert!(!outcome.steps[0].success);
+        assert!(outcome.steps[0].skipped);
+        assert!(outcome.steps[1].success);
+        assert!(!outcome.steps[1].skipped);
+        assert!(outcome.success());
+
+        // The same failure aborts the plan when the step is required
+        plan.steps[0].optional = false;
+        assert!(executor.execute_plan_detailed(plan).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_parallel_group_checks_preconditions() {
+        let executor = create_executor().with_retry_policy(RetryPolicy {
+            max_attempts: 1,
+            ..RetryPolicy::default()
+        });
+
+        // Step 1 waits for step 2, which only runs in the next group
+        let mut plan = single_step_plan(ActionType::GetTime, HashMap::new());
+        for (action, group) in [(ActionType::GetDate, 0), (ActionType::GetTime, 1)] {
+            let mut step = plan.steps[0].clone();
+            step.action = action;
+            step.step_number = plan.steps.len();
+            step.parallel_group = Some(group);
+            plan.steps.push(step);
+        }
+        plan.steps[1]
+            .preconditions
+            .push(Precondition::StepCompleted(2));
+        plan.steps[1].optional = true;
+        plan.parallel_groups = vec![vec![0, 1], vec![2]];
+
+        let outcome = executor.execute_plan_detailed(plan.clone()).await.unwrap();
+        assert_eq!(outcome.steps.len(), 3);
+        assert!(outcome.steps[0].success);
+        assert!(outcome.steps[1].skipped);
+        assert!(outcome.steps[1].message.contains("not completed"));
+        assert!(outcome.steps[2].success);
+
+        // A required step with an unmet precondition fails the group
+        plan.steps[1].optional = false;
+        assert!(executor.execute_plan_detailed(plan).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_parallel_failure_compensates_later_sibling() {
+        let bus = Arc::new(EventBus::new());
+        let handle = bus.start_processing().await;
+
+        let completed = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let completed_clone = completed.clone();
+        bus.subscribe_kinds(&[EventKind::ActionCompleted], move |envelope| {
+            if let LunaEvent::ActionCompleted {
+                action_type,
+                success,
+                ..
+            } = &envelope.event
+            {
+                completed_clone
+                    .lock()
+                    .unwrap()
+                    .push((action_type.clone(), *success));
+            }
+        })
+        .await;
+
+        let executor = create_executor()
+            .with_event_bus(bus.clone())
+            .with_retry_policy(RetryPolicy {
+                max_attempts: 1,
+                ..RetryPolicy::default()
+            });
+
+        // Step 0 fails; step 1 runs alongside it, succeeds and carries a compensation
+        let mut params = HashMap::new();
+        params.insert(
+            "path".to_string(),
+            "/nonexistent/luna/test/folder".to_string(),
+        );
+        let mut plan = single_step_plan(ActionType::OpenFolder, params);
+        plan.steps.push(ActionStep {
+            action: ActionType::GetTime,
+            params: HashMap::new(),
+            step_number: 1,
+            preconditions: vec![],
+            postconditions: vec![],
+            parallel_group: None,
+            compensation: Some(Box::new(ActionStep {
+                action: ActionType::GetDate,
+                params: HashMap::new(),
+                step_number: 1,
+                preconditions: vec![],
+                postconditions: vec![],
+                parallel_group: None,
+                compensation: None,
+                optional: false,
+            })),
+            optional: false,
+        });
+        plan.parallel_groups = vec![vec![0, 1]];
+
+        assert!(executor.execute_plan(plan).await.is_err());
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        let completed = completed.lock().unwrap();
+        assert_eq!(
+            completed.last(),
+            Some(&("GetDate".to_string(), true)),
+            "the successful sibling should be rolled back"
+        );
+
+        handle.abort();
+    }
+
+    #[tokio::test]
+    async fn test_alternative_offered_when_top_intent_fails() {
+        let brain = Brain::new(&crate::config::BrainConfig::default()).unwrap();
+        let executor = create_executor().with_retry_policy(RetryPolicy {
+            max_attempts: 1,
+            ..RetryPolicy::default()
+        });
+
+        // No app called "budget.txt", but it looks like a file
+        let plan = brain.process("open budget.txt").unwrap();
+        assert_eq!(plan.classification.intent, IntentType::LaunchApp);
+        let result = executor.execute_plan_detailed(plan.clone()).await;
+        assert!(result.is_err());
+
+        let suggestion = executor
+            .suggest_alternative(&brain, &plan, &result)
+            .await
+            .expect("Expected a file search to be offered");
+        let classification = &suggestion.plan.classification;
+        assert_eq!(classification.intent, IntentType::FindFile);
+        assert_eq!(
+            classification.entities.get("file_name").map(String::as_str),
+            Some("budget.txt")
+        );
+        assert_eq!(suggestion.plan.steps[0].action, ActionType::FindFile);
+        assert!(suggestion.question.contains("find budget.txt"));
+        assert!(suggestion.accepts("Yes please"));
+        assert!(!suggestion.accepts("no thanks"));
+
+        // Nothing is offered for a plan that did its job
+        let plan = brain.process("what time is it").unwrap();
+        let result = executor.execute_plan_detailed(plan.clone()).await;
+        assert!(executor
+            .suggest_alternative(&brain, &plan, &result)
+            .await
+            .is_none());
+    }
+}
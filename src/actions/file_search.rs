@@ -2,7 +2,7 @@
 //!
 //! Search for files across the system with fuzzy matching and content search.
 
-use crate::db::FileIndex;
+use crate::db::{FileFilter, FileIndex};
 use crate::error::{LunaError, Result};
 use crate::utils::string_matching::similarity_score;
 use std::fs;
@@ -66,6 +66,35 @@ impl FileSearch {
         Ok(results)
     }
 
+    /// Search for files matching a filter, ranked by name similarity
+    ///
+    /// An empty query lists matching files, most recently modified first.
+    pub async fn search_filtered(
+        &self,
+        query: &str,
+        filter: &FileFilter,
+        limit: usize,
+    ) -> Result<Vec<PathBuf>> {
+        info!("Searching for files: '{}' with {:?}", query, filter);
+
+        if self.index.is_empty() {
+            warn!("File index is empty. Consider running an index update.");
+            return Ok(Vec::new());
+        }
+
+        let results: Vec<PathBuf> = self
+            .index
+            .search_filtered(query, filter)
+            .into_iter()
+            .take(limit)
+            .map(|f| f.path.clone())
+            .collect();
+
+        debug!("Found {} matching files", results.len());
+
+        Ok(results)
+    }
+
     /// Search for files by content (simple grep-like search)
     pub async fn search_by_content(&self, query: &str, limit: usize) -> Result<Vec<PathBuf>> {
         info!("Searching file contents for: {}", query);
@@ -239,6 +268,38 @@ mod tests {
         assert_eq!(results.len(), 3); // We added 3 files
     }
 
+    #[tokio::test]
+    async fn test_search_filtered() {
+        let index = create_test_index();
+        let search = FileSearch::new(index);
+
+        let documents = FileFilter {
+            types: vec![FileType::Document],
+            ..Default::default()
+        };
+        let results = search.search_filtered("", &documents, 10).await.unwrap();
+        assert_eq!(results.len(), 2);
+        assert!(results[0].ends_with("notes.md"));
+
+        let large = FileFilter {
+            min_size: Some(1024),
+            ..Default::default()
+        };
+        let results = search.search_filtered("", &large, 10).await.unwrap();
+        assert_eq!(results.len(), 2);
+        assert!(!results.iter().any(|p| p.ends_with("notes.md")));
+
+        let large_documents = FileFilter {
+            min_size: Some(1024),
+            ..documents
+        };
+        let results = search
+            .search_filtered("document", &large_documents, 10)
+            .await
+            .unwrap();
+        assert_eq!(results, vec![PathBuf::from("/test/document.txt")]);
+    }
+
     #[test]
     fn test_is_text_file() {
         assert!(FileSearch::is_text_file(&PathBuf::from("test.txt")));
@@ -3,16 +3,137 @@
 //! Control media playback (play, pause, next, etc.) with cross-platform support.
 
 use crate::error::{LunaError, Result};
+use serde::{Deserialize, Serialize};
+#[cfg(target_os = "linux")]
+use std::path::PathBuf;
 use std::process::Command;
+use std::time::Duration;
 use tracing::{debug, info};
 
+/// PowerShell that binds `$session` to the current media session through
+/// the WinRT system media transport controls, exiting quietly if none
+#[cfg(target_os = "windows")]
+const SMTC_SESSION_PRELUDE: &str = r#"Add-Type -AssemblyName System.Runtime.WindowsRuntime
+$asTask = ([System.WindowsRuntimeSystemExtensions].GetMethods() | Where-Object { $_.Name -eq 'AsTask' -and $_.GetParameters().Count -eq 1 -and $_.GetParameters()[0].ParameterType.Name -eq 'IAsyncOperation`1' })[0]
+function Await($op, $type) { $task = $asTask.MakeGenericMethod($type).Invoke($null, @($op)); $task.Wait(-1) | Out-Null; $task.Result }
+[Windows.Media.Control.GlobalSystemMediaTransportControlsSessionManager, Windows.Media.Control, ContentType = WindowsRuntime] | Out-Null
+$manager = Await ([Windows.Media.Control.GlobalSystemMediaTransportControlsSessionManager]::RequestAsync()) ([Windows.Media.Control.GlobalSystemMediaTransportControlsSessionManager])
+$session = $manager.GetCurrentSession()
+if ($null -eq $session) { exit 0 }
+"#;
+
+/// What a media player lets LUNA control
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlayerCapabilities {
+    /// Player name as reported by the platform (e.g. "spotify")
+    pub player: String,
+    /// Whether the playback position can be changed
+    pub can_seek: bool,
+    /// Whether the player exposes its own volume
+    pub has_volume: bool,
+}
+
+/// Player features checked before acting on a player
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PlayerFeature {
+    Seek,
+    Volume,
+}
+
+/// Explain why `feature` can't be used on the player, or `None` if it can
+fn unsupported_message(
+    capabilities: Option<&PlayerCapabilities>,
+    feature: PlayerFeature,
+) -> Option<String> {
+    let Some(capabilities) = capabilities else {
+        return Some("No media player is active".to_string());
+    };
+
+    match feature {
+        PlayerFeature::Seek if !capabilities.can_seek => {
+            Some(format!("{} doesn't support seeking", capabilities.player))
+        }
+        PlayerFeature::Volume if !capabilities.has_volume => Some(format!(
+            "{} doesn't let me change its volume",
+            capabilities.player
+        )),
+        _ => None,
+    }
+}
+
+/// Speak a seek offset, e.g. "forward 30 seconds" or "back 2 minutes"
+fn describe_seek(delta_secs: i64) -> String {
+    let direction = if delta_secs < 0 { "back" } else { "forward" };
+    let secs = delta_secs.unsigned_abs();
+    let amount = match (secs / 60, secs % 60) {
+        (0, s) => format!("{} second{}", s, if s == 1 { "" } else { "s" }),
+        (m, 0) => format!("{} minute{}", m, if m == 1 { "" } else { "s" }),
+        (m, s) => format!(
+            "{} minute{} {} seconds",
+            m,
+            if m == 1 { "" } else { "s" },
+            s
+        ),
+    };
+    format!("{} {}", direction, amount)
+}
+
+/// Metadata for the track the active player is on
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TrackInfo {
+    /// Track title
+    pub title: String,
+    /// Performing artist
+    pub artist: Option<String>,
+    /// Album name
+    pub album: Option<String>,
+    /// Playback position within the track
+    pub position: Option<Duration>,
+    /// Track length
+    pub duration: Option<Duration>,
+    /// Whether the player is playing rather than paused
+    pub playing: bool,
+}
+
+impl TrackInfo {
+    /// Describe the track as a spoken sentence
+    pub fn describe(&self) -> String {
+        let mut text = if self.playing {
+            format!("Now playing {}", self.title)
+        } else {
+            format!("Paused on {}", self.title)
+        };
+        if let Some(artist) = &self.artist {
+            text.push_str(&format!(" by {}", artist));
+        }
+        if let Some(album) = &self.album {
+            text.push_str(&format!(" from {}", album));
+        }
+        text
+    }
+}
+
 /// Media control handler
-pub struct MediaControl;
+pub struct MediaControl {
+    /// MPRIS client used for metadata queries
+    #[cfg(target_os = "linux")]
+    playerctl: PathBuf,
+}
 
 impl MediaControl {
     /// Create a new media control handler
     pub fn new() -> Self {
-        Self
+        Self {
+            #[cfg(target_os = "linux")]
+            playerctl: PathBuf::from("playerctl"),
+        }
+    }
+
+    /// Use a different `playerctl` binary for metadata queries
+    #[cfg(target_os = "linux")]
+    pub fn with_playerctl(mut self, path: impl Into<PathBuf>) -> Self {
+        self.playerctl = path.into();
+        self
     }
 
     /// Play/pause media
@@ -277,53 +398,433 @@ impl MediaControl {
 
     /// Get currently playing track info
     pub async fn get_current_track(&self) -> Result<String> {
+        match self.current_track_info().await? {
+            Some(track) => Ok(track.describe()),
+            None => Ok("No track playing".to_string()),
+        }
+    }
+
+    /// Get structured metadata for the current track
+    ///
+    /// Returns `None` when no player is running or playback is stopped.
+    pub async fn current_track_info(&self) -> Result<Option<TrackInfo>> {
         debug!("Getting current track info");
 
         #[cfg(target_os = "linux")]
         {
-            let output = Command::new("playerctl")
-                .args(&["metadata", "--format", "{{ artist }} - {{ title }}"])
+            // Position and length are reported in microseconds
+            let output = Command::new(&self.playerctl)
+                .args([
+                    "metadata",
+                    "--format",
+                    "{{status}}\t{{title}}\t{{artist}}\t{{album}}\t{{position}}\t{{mpris:length}}",
+                ])
                 .output()
                 .map_err(|e| {
                     LunaError::SystemOperation(format!("Failed to get track info: {}", e))
                 })?;
 
-            if output.status.success() {
-                let track = String::from_utf8_lossy(&output.stdout).trim().to_string();
-                if track.is_empty() {
-                    Ok("No track playing".to_string())
-                } else {
-                    Ok(format!("Now playing: {}", track))
-                }
-            } else {
-                Err(LunaError::SystemOperation(
-                    "playerctl not found or no player active".to_string(),
-                ))
+            // playerctl exits non-zero when no player is running
+            if !output.status.success() {
+                return Ok(None);
             }
+            Ok(parse_track_line(
+                &String::from_utf8_lossy(&output.stdout),
+                1e6,
+            ))
         }
 
         #[cfg(target_os = "macos")]
         {
+            let script = r#"if application "Music" is running then
+    tell application "Music"
+        if player state is stopped then return ""
+        set t to current track
+        return (player state as string) & tab & (name of t) & tab & (artist of t) & tab & (album of t) & tab & (player position as string) & tab & (duration of t as string)
+    end tell
+end if
+return """#;
             let output = Command::new("osascript")
-                .args(&["-e", "tell application \"Music\" to get name of current track & \" - \" & artist of current track"])
+                .args(["-e", script])
                 .output()
-                .map_err(|e| LunaError::SystemOperation(format!("Failed to get track info: {}", e)))?;
+                .map_err(|e| {
+                    LunaError::SystemOperation(format!("Failed to get track info: {}", e))
+                })?;
 
-            if output.status.success() {
-                let track = String::from_utf8_lossy(&output.stdout).trim().to_string();
-                Ok(format!("Now playing: {}", track))
-            } else {
-                Ok("No track playing".to_string())
+            if !output.status.success() {
+                return Ok(None);
+            }
+            Ok(parse_track_line(
+                &String::from_utf8_lossy(&output.stdout),
+                1.0,
+            ))
+        }
+
+        #[cfg(target_os = "windows")]
+        {
+            let script = format!(
+                "{}{}",
+                SMTC_SESSION_PRELUDE,
+                r#"$props = Await ($session.TryGetMediaPropertiesAsync()) ([Windows.Media.Control.GlobalSystemMediaTransportControlsSessionMediaProperties])
+$timeline = $session.GetTimelineProperties()
+$status = $session.GetPlaybackInfo().PlaybackStatus
+"$status`t$($props.Title)`t$($props.Artist)`t$($props.AlbumTitle)`t$($timeline.Position.TotalSeconds)`t$($timeline.EndTime.TotalSeconds)""#
+            );
+            let output = Command::new("powershell")
+                .args(["-NoProfile", "-Command", &script])
+                .output()
+                .map_err(|e| {
+                    LunaError::SystemOperation(format!("Failed to get track info: {}", e))
+                })?;
+
+            if !output.status.success() {
+                return Ok(None);
+            }
+            Ok(parse_track_line(
+                &String::from_utf8_lossy(&output.stdout),
+                1.0,
+            ))
+        }
+
+        #[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+        {
+            Ok(None)
+        }
+    }
+
+    /// Seek the active player by a signed number of seconds
+    ///
+    /// Players without seeking, such as live streams, get an explanation
+    /// instead of an error.
+    pub async fn seek(&self, delta_secs: i64) -> Result<String> {
+        info!("Seeking {} seconds", delta_secs);
+
+        let capabilities = self.player_capabilities(None).await?;
+        if let Some(message) = unsupported_message(capabilities.as_ref(), PlayerFeature::Seek) {
+            return Ok(message);
+        }
+        let player = capabilities.map(|c| c.player).unwrap_or_default();
+
+        #[cfg(target_os = "linux")]
+        {
+            let offset = format!(
+                "{}{}",
+                delta_secs.unsigned_abs(),
+                if delta_secs < 0 { "-" } else { "+" }
+            );
+            let status = self
+                .playerctl_command(Some(&player))
+                .args(["position", &offset])
+                .status()
+                .map_err(|e| LunaError::SystemOperation(format!("Failed to seek: {}", e)))?;
+
+            if !status.success() {
+                return Err(LunaError::SystemOperation(format!(
+                    "{} rejected the seek",
+                    player
+                )));
+            }
+        }
+
+        #[cfg(target_os = "macos")]
+        {
+            let script = format!(
+                "tell application \"{}\" to set player position to (player position + ({}))",
+                player, delta_secs
+            );
+            let status = Command::new("osascript")
+                .args(["-e", &script])
+                .status()
+                .map_err(|e| LunaError::SystemOperation(format!("Failed to seek: {}", e)))?;
+
+            if !status.success() {
+                return Err(LunaError::SystemOperation(format!(
+                    "{} rejected the seek",
+                    player
+                )));
             }
         }
 
         #[cfg(target_os = "windows")]
         {
-            Ok("Track info unavailable on Windows".to_string())
+            let script = format!(
+                "{}$ticks = $session.GetTimelineProperties().Position.Ticks + ({} * 10000000)\n\
+                 Await ($session.TryChangePlaybackPositionAsync([Math]::Max(0, $ticks))) ([bool]) | Out-Null",
+                SMTC_SESSION_PRELUDE, delta_secs
+            );
+            let status = Command::new("powershell")
+                .args(["-NoProfile", "-Command", &script])
+                .status()
+                .map_err(|e| LunaError::SystemOperation(format!("Failed to seek: {}", e)))?;
+
+            if !status.success() {
+                return Err(LunaError::SystemOperation(format!(
+                    "{} rejected the seek",
+                    player
+                )));
+            }
         }
+
+        Ok(format!("Skipped {}", describe_seek(delta_secs)))
+    }
+
+    /// Set the active player's own volume, leaving the system volume alone
+    pub async fn set_player_volume(&self, percent: u8) -> Result<String> {
+        self.apply_player_volume(None, percent).await
+    }
+
+    /// Set the volume of a named player (e.g. "spotify")
+    pub async fn set_player_volume_for(&self, player: &str, percent: u8) -> Result<String> {
+        self.apply_player_volume(Some(player), percent).await
+    }
+
+    async fn apply_player_volume(&self, player: Option<&str>, percent: u8) -> Result<String> {
+        let percent = percent.min(100);
+        info!(
+            "Setting {} volume to {}%",
+            player.unwrap_or("player"),
+            percent
+        );
+
+        let capabilities = self.player_capabilities(player).await?;
+        if let Some(message) = unsupported_message(capabilities.as_ref(), PlayerFeature::Volume) {
+            return Ok(match (capabilities, player) {
+                (None, Some(name)) => format!("{} isn't playing anything", name),
+                _ => message,
+            });
+        }
+        let player = capabilities.map(|c| c.player).unwrap_or_default();
+
+        #[cfg(target_os = "linux")]
+        {
+            let status = self
+                .playerctl_command(Some(&player))
+                .args(["volume", &format!("{:.2}", percent as f32 / 100.0)])
+                .status()
+                .map_err(|e| LunaError::SystemOperation(format!("Failed to set volume: {}", e)))?;
+
+            if !status.success() {
+                return Err(LunaError::SystemOperation(format!(
+                    "Failed to set {} volume",
+                    player
+                )));
+            }
+        }
+
+        #[cfg(target_os = "macos")]
+        {
+            let script = format!(
+                "tell application \"{}\" to set sound volume to {}",
+                player, percent
+            );
+            let status = Command::new("osascript")
+                .args(["-e", &script])
+                .status()
+                .map_err(|e| LunaError::SystemOperation(format!("Failed to set volume: {}", e)))?;
+
+            if !status.success() {
+                return Err(LunaError::SystemOperation(format!(
+                    "Failed to set {} volume",
+                    player
+                )));
+            }
+        }
+
+        #[cfg(target_os = "windows")]
+        {
+            // nircmd scales per-app volume from 0.0 to 1.0
+            let status = Command::new("nircmd")
+                .args([
+                    "setappvolume",
+                    &format!("{}.exe", player),
+                    &format!("{:.2}", percent as f32 / 100.0),
+                ])
+                .status()
+                .map_err(|e| LunaError::SystemOperation(format!("Failed to set volume: {}", e)))?;
+
+            if !status.success() {
+                return Err(LunaError::SystemOperation(
+                    "nircmd not found. Install nircmd for media control".to_string(),
+                ));
+            }
+        }
+
+        Ok(format!("Set {} volume to {}%", player, percent))
+    }
+
+    /// Query what a player supports, or the active player if none is named
+    ///
+    /// Returns `None` when no matching player is running.
+    pub async fn player_capabilities(
+        &self,
+        player: Option<&str>,
+    ) -> Result<Option<PlayerCapabilities>> {
+        #[cfg(target_os = "linux")]
+        {
+            let output = self
+                .playerctl_command(player.map(str::to_lowercase).as_deref())
+                .args([
+                    "metadata",
+                    "--format",
+                    "{{playerName}}\t{{mpris:length}}\t{{volume}}",
+                ])
+                .output()
+                .map_err(|e| {
+                    LunaError::SystemOperation(format!("Failed to query player: {}", e))
+                })?;
+
+            if !output.status.success() {
+                return Ok(None);
+            }
+            Ok(parse_playerctl_capabilities(&String::from_utf8_lossy(
+                &output.stdout,
+            )))
+        }
+
+        #[cfg(target_os = "macos")]
+        {
+            // Music and Spotify both script position and volume
+            let app = match player {
+                Some(name) => {
+                    let mut chars = name.chars();
+                    chars
+                        .next()
+                        .map(|c| c.to_uppercase().chain(chars).collect())
+                        .unwrap_or_default()
+                }
+                None => "Music".to_string(),
+            };
+            let output = Command::new("osascript")
+                .args(["-e", &format!("application \"{}\" is running", app)])
+                .output()
+                .map_err(|e| {
+                    LunaError::SystemOperation(format!("Failed to query player: {}", e))
+                })?;
+
+            let running = String::from_utf8_lossy(&output.stdout).trim() == "true";
+            Ok(running.then(|| PlayerCapabilities {
+                player: app,
+                can_seek: true,
+                has_volume: true,
+            }))
+        }
+
+        #[cfg(target_os = "windows")]
+        {
+            // Per-app volume goes through nircmd, which needs the process name
+            if let Some(name) = player {
+                return Ok(Some(PlayerCapabilities {
+                    player: name.to_lowercase(),
+                    can_seek: false,
+                    has_volume: true,
+                }));
+            }
+
+            let script = format!(
+                "{}\"$($session.SourceAppUserModelId)`t$($session.GetPlaybackInfo().Controls.IsPlaybackPositionEnabled)\"",
+                SMTC_SESSION_PRELUDE
+            );
+            let output = Command::new("powershell")
+                .args(["-NoProfile", "-Command", &script])
+                .output()
+                .map_err(|e| {
+                    LunaError::SystemOperation(format!("Failed to query player: {}", e))
+                })?;
+
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            Ok(stdout
+                .trim()
+                .split_once('\t')
+                .filter(|(app, _)| !app.is_empty())
+                .map(|(app, can_seek)| PlayerCapabilities {
+                    player: app.to_string(),
+                    can_seek: can_seek.eq_ignore_ascii_case("true"),
+                    has_volume: false,
+                }))
+        }
+
+        #[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+        {
+            let _ = player;
+            Ok(None)
+        }
+    }
+
+    /// Build a `playerctl` command, optionally targeting one player
+    #[cfg(target_os = "linux")]
+    fn playerctl_command(&self, player: Option<&str>) -> Command {
+        let mut cmd = Command::new(&self.playerctl);
+        if let Some(player) = player {
+            cmd.arg(format!("--player={}", player));
+        }
+        cmd
     }
 }
 
+/// Parse `playerName, mpris:length, volume` as reported by playerctl
+///
+/// Players without a track length (live streams, radio) can't seek.
+#[cfg(target_os = "linux")]
+fn parse_playerctl_capabilities(line: &str) -> Option<PlayerCapabilities> {
+    let mut fields = line.trim_end_matches(['\r', '\n']).split('\t');
+    let player = fields.next()?.trim();
+    if player.is_empty() {
+        return None;
+    }
+    let length = fields.next().unwrap_or_default().trim();
+    let volume = fields.next().unwrap_or_default().trim();
+
+    Some(PlayerCapabilities {
+        player: player.to_string(),
+        can_seek: length.parse::<u64>().is_ok_and(|l| l > 0),
+        has_volume: volume.parse::<f64>().is_ok(),
+    })
+}
+
+/// Parse a tab-separated `status, title, artist, album, position, length` line
+///
+/// Times are divided by `units_per_second` to get seconds. Stopped or closed
+/// players and tracks without a title yield `None`.
+fn parse_track_line(line: &str, units_per_second: f64) -> Option<TrackInfo> {
+    let mut fields = line.trim_end_matches(['\r', '\n']).split('\t');
+    let status = fields.next()?.trim();
+    let title = fields.next()?.trim();
+    if title.is_empty()
+        || status.eq_ignore_ascii_case("stopped")
+        || status.eq_ignore_ascii_case("closed")
+    {
+        return None;
+    }
+
+    let mut text = || {
+        fields
+            .next()
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(str::to_string)
+    };
+    let artist = text();
+    let album = text();
+    let position = text();
+    let duration = text();
+
+    let time = |value: Option<String>| {
+        value
+            .and_then(|v| v.parse::<f64>().ok())
+            .filter(|v| v.is_finite() && *v > 0.0)
+            .map(|v| Duration::from_secs_f64(v / units_per_second))
+    };
+
+    Some(TrackInfo {
+        title: title.to_string(),
+        artist,
+        album,
+        position: time(position),
+        duration: time(duration),
+        playing: status.eq_ignore_ascii_case("playing"),
+    })
+}
+
 impl Default for MediaControl {
     fn default() -> Self {
         Self::new()
@@ -340,4 +841,174 @@ mod tests {
         // Just verify it can be created
         drop(control);
     }
+
+    #[test]
+    fn test_parse_track_line() {
+        let track =
+            parse_track_line("paused\tBlue in Green\tMiles Davis\t\t30.5\t337\n", 1.0).unwrap();
+        assert_eq!(track.title, "Blue in Green");
+        assert_eq!(track.artist.as_deref(), Some("Miles Davis"));
+        assert_eq!(track.album, None);
+        assert_eq!(track.position, Some(Duration::from_secs_f64(30.5)));
+        assert_eq!(track.duration, Some(Duration::from_secs(337)));
+        assert!(!track.playing);
+        assert_eq!(track.describe(), "Paused on Blue in Green by Miles Davis");
+
+        assert!(parse_track_line("Stopped\tSo What\t\t\t\t", 1.0).is_none());
+        assert!(parse_track_line("Playing\t\tMiles Davis", 1.0).is_none());
+        assert!(parse_track_line("", 1.0).is_none());
+    }
+
+    #[test]
+    fn test_unsupported_message() {
+        let stream = PlayerCapabilities {
+            player: "radio".to_string(),
+            can_seek: false,
+            has_volume: true,
+        };
+        let muted = PlayerCapabilities {
+            player: "vlc".to_string(),
+            can_seek: true,
+            has_volume: false,
+        };
+
+        assert_eq!(
+            unsupported_message(Some(&stream), PlayerFeature::Seek).as_deref(),
+            Some("radio doesn't support seeking")
+        );
+        assert!(unsupported_message(Some(&stream), PlayerFeature::Volume).is_none());
+        assert!(unsupported_message(Some(&muted), PlayerFeature::Seek).is_none());
+        assert_eq!(
+            unsupported_message(Some(&muted), PlayerFeature::Volume).as_deref(),
+            Some("vlc doesn't let me change its volume")
+        );
+        assert_eq!(
+            unsupported_message(None, PlayerFeature::Seek).as_deref(),
+            Some("No media player is active")
+        );
+    }
+
+    #[test]
+    fn test_describe_seek() {
+        assert_eq!(describe_seek(30), "forward 30 seconds");
+        assert_eq!(describe_seek(-1), "back 1 second");
+        assert_eq!(describe_seek(120), "forward 2 minutes");
+        assert_eq!(describe_seek(-90), "back 1 minute 30 seconds");
+    }
+
+    #[cfg(target_os = "linux")]
+    fn mock_playerctl(dir: &std::path::Path, script: &str) -> PathBuf {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path = dir.join("playerctl");
+        std::fs::write(&path, format!("#!/bin/sh\n{}\n", script)).unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+        path
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn test_current_track_from_mock_player() {
+        let dir = tempfile::tempdir().unwrap();
+        let player = mock_playerctl(
+            dir.path(),
+            r"printf 'Playing\tSo What\tMiles Davis\tKind of Blue\t61000000\t562000000\n'",
+        );
+        let control = MediaControl::new().with_playerctl(player);
+
+        let track = control.current_track_info().await.unwrap().unwrap();
+        assert_eq!(
+            track,
+            TrackInfo {
+                title: "So What".to_string(),
+                artist: Some("Miles Davis".to_string()),
+                album: Some("Kind of Blue".to_string()),
+                position: Some(Duration::from_secs(61)),
+                duration: Some(Duration::from_secs(562)),
+                playing: true,
+            }
+        );
+        assert_eq!(
+            control.get_current_track().await.unwrap(),
+            "Now playing So What by Miles Davis from Kind of Blue"
+        );
+    }
+
+    /// Mock player reporting `length` and `volume`, logging other commands
+    #[cfg(target_os = "linux")]
+    fn mock_player_with(dir: &std::path::Path, length: &str, volume: &str) -> PathBuf {
+        let log = dir.join("calls");
+        mock_playerctl(
+            dir,
+            &format!(
+                "case \"$*\" in\n  *metadata*) printf 'spotify\\t{}\\t{}\\n' ;;\n  *) echo \"$@\" >> '{}' ;;\nesac",
+                length,
+                volume,
+                log.display()
+            ),
+        )
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn test_seek_and_volume_with_mock_player() {
+        let dir = tempfile::tempdir().unwrap();
+        let control =
+            MediaControl::new().with_playerctl(mock_player_with(dir.path(), "240000000", "0.5"));
+
+        let capabilities = control.player_capabilities(None).await.unwrap().unwrap();
+        assert!(capabilities.can_seek && capabilities.has_volume);
+
+        assert_eq!(
+            control.seek(30).await.unwrap(),
+            "Skipped forward 30 seconds"
+        );
+        assert_eq!(control.seek(-15).await.unwrap(), "Skipped back 15 seconds");
+        assert_eq!(
+            control.set_player_volume_for("Spotify", 20).await.unwrap(),
+            "Set spotify volume to 20%"
+        );
+
+        let calls = std::fs::read_to_string(dir.path().join("calls")).unwrap();
+        assert_eq!(
+            calls,
+            "--player=spotify position 30+\n--player=spotify position 15-\n--player=spotify volume 0.20\n"
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn test_stream_without_length_cannot_seek() {
+        let dir = tempfile::tempdir().unwrap();
+        let control = MediaControl::new().with_playerctl(mock_player_with(dir.path(), "", ""));
+
+        assert_eq!(
+            control.seek(30).await.unwrap(),
+            "spotify doesn't support seeking"
+        );
+        assert_eq!(
+            control.set_player_volume(40).await.unwrap(),
+            "spotify doesn't let me change its volume"
+        );
+        assert!(!dir.path().join("calls").exists());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn test_no_player_is_none() {
+        let dir = tempfile::tempdir().unwrap();
+        let player = mock_playerctl(dir.path(), "echo 'No players found' >&2\nexit 1");
+        let control = MediaControl::new().with_playerctl(player);
+
+        assert!(control.current_track_info().await.unwrap().is_none());
+        assert!(control.player_capabilities(None).await.unwrap().is_none());
+        assert_eq!(
+            control.set_player_volume_for("spotify", 20).await.unwrap(),
+            "spotify isn't playing anything"
+        );
+        assert_eq!(
+            control.get_current_track().await.unwrap(),
+            "No track playing"
+        );
+    }
 }
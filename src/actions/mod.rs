@@ -9,18 +9,26 @@ pub mod executor;
 pub mod file_operations;
 pub mod file_search;
 pub mod media_control;
+pub mod plugins;
 pub mod question_handler;
 pub mod reminders;
+pub mod skills;
 pub mod system_control;
 pub mod window_control;
 
 // Re-export main structures
-pub use app_launcher::AppLauncher;
+pub use app_launcher::{AppLauncher, CategoryChoice};
 pub use capabilities::{Capability, CapabilityDetector};
 pub use clipboard::Clipboard;
-pub use executor::{ExecutionPolicy, RetryPolicy, TaskExecutor};
+pub use executor::{
+    confirmation_question, is_affirmative, AlternativeSuggestion, CommandPreview,
+    ConfirmationRequest, ExecutionPolicy, JitterStrategy, PlanOutcome, RetryPolicy, StepOutcome,
+    TaskExecutor,
+};
 pub use file_search::FileSearch;
-pub use media_control::MediaControl;
+pub use media_control::{MediaControl, TrackInfo};
+pub use plugins::Skill;
 pub use question_handler::QuestionHandler;
+pub use skills::{ResolvedCommand, SkillRegistry};
 pub use system_control::SystemControl;
-pub use window_control::WindowControl;
+pub use window_control::{TileSide, WindowControl};
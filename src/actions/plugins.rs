@@ -0,0 +1,39 @@
+//! Runtime skills for custom actions
+//!
+//! `ActionType` is a closed enum, so capabilities added without touching the
+//! planner and executor are registered as skills instead. The executor
+//! offers `ActionType::Custom` steps and commands no built-in intent handles
+//! to the first registered skill that claims them; unclaimed commands are
+//! still answered as questions.
+
+use crate::brain::command_parser::{IntentType, ParsedCommand};
+use crate::error::Result;
+use futures::future::BoxFuture;
+use std::collections::HashMap;
+
+/// A custom action registered on the `TaskExecutor`
+pub trait Skill: Send + Sync {
+    /// Whether this skill handles the command
+    fn matches(&self, command: &ParsedCommand) -> bool;
+
+    /// Run the command and return the response to speak
+    fn execute<'a>(&'a self, command: &'a ParsedCommand) -> BoxFuture<'a, Result<String>>;
+
+    /// Name used in logs
+    fn name(&self) -> &str {
+        "custom"
+    }
+}
+
+/// Rebuild the command a skill step was planned from
+///
+/// The parser keeps the utterance in the `text` entity for commands it
+/// couldn't match.
+pub fn command_from_params(params: &HashMap<String, String>) -> ParsedCommand {
+    ParsedCommand {
+        intent: IntentType::Unknown,
+        entities: params.clone(),
+        original_text: params.get("text").cloned().unwrap_or_default(),
+        confidence: 0.0,
+    }
+}
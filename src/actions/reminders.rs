@@ -110,6 +110,7 @@ fn show_notification(message: &str) -> Result<()> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::events::EventKind;
     use std::sync::atomic::{AtomicUsize, Ordering};
 
     #[tokio::test]
@@ -148,7 +149,7 @@ mod tests {
 
         // Subscribe to reminder events
         let bus_clone = bus.clone();
-        bus.subscribe(vec!["custom"], move |envelope| {
+        bus.subscribe_kinds(&[EventKind::Custom], move |envelope| {
             if let LunaEvent::Custom { event_type, .. } = &envelope.event {
                 if event_type == "reminder_triggered" {
                     triggered_clone.store(true, Ordering::SeqCst);
@@ -181,7 +182,7 @@ mod tests {
 
         // Subscribe and capture the message
         let bus_clone = bus.clone();
-        bus.subscribe(vec!["custom"], move |envelope| {
+        bus.subscribe_kinds(&[EventKind::Custom], move |envelope| {
             if let LunaEvent::Custom { event_type, data } = &envelope.event {
                 if event_type == "reminder_triggered" {
                     if let Some(msg) = data.get("message").and_then(|v| v.as_str()) {
@@ -211,7 +212,7 @@ mod tests {
         let bus = Arc::new(EventBus::new());
         let _handle = bus.start_processing().await;
 
-        bus.subscribe(vec!["custom"], move |envelope| {
+        bus.subscribe_kinds(&[EventKind::Custom], move |envelope| {
             if let LunaEvent::Custom { event_type, .. } = &envelope.event {
                 if event_type == "reminder_triggered" {
                     counter_clone.fetch_add(1, Ordering::SeqCst);
@@ -259,7 +260,7 @@ mod tests {
         let bus = Arc::new(EventBus::new());
         let _handle = bus.start_processing().await;
 
-        bus.subscribe(vec!["custom"], move |envelope| {
+        bus.subscribe_kinds(&[EventKind::Custom], move |envelope| {
             if let LunaEvent::Custom { event_type, .. } = &envelope.event {
                 if event_type == "reminder_triggered" {
                     triggered_clone.store(true, Ordering::SeqCst);
@@ -289,7 +290,7 @@ mod tests {
         let bus = Arc::new(EventBus::new());
         let _handle = bus.start_processing().await;
 
-        bus.subscribe(vec!["custom"], move |envelope| {
+        bus.subscribe_kinds(&[EventKind::Custom], move |envelope| {
             if let LunaEvent::Custom { event_type, data } = &envelope.event {
                 if event_type == "reminder_triggered" {
                     if let Some(msg) = data.get("message").and_then(|v| v.as_str()) {
@@ -345,7 +346,7 @@ mod tests {
         let bus = Arc::new(EventBus::new());
         let _handle = bus.start_processing().await;
 
-        bus.subscribe(vec!["custom"], move |envelope| {
+        bus.subscribe_kinds(&[EventKind::Custom], move |envelope| {
             if let LunaEvent::Custom { event_type, data } = &envelope.event {
                 if event_type == "reminder_triggered" {
                     if let Some(ts) = data.get("timestamp").and_then(|v| v.as_i64()) {
@@ -0,0 +1,377 @@
+//! Allowlisted command skills
+//!
+//! The only way LUNA runs external commands on request. Each skill maps a
+//! name to an exact argv template loaded from YAML; parameters fill
+//! `{placeholders}` inside single arguments and nothing is passed through a
+//! shell:
+//!
+//! ```yaml
+//! allowed_binaries: [git, systemctl]
+//! skills:
+//!   git_status:
+//!     description: Show uncommitted changes
+//!     argv: [git, -C, "{repo}", status, --short]
+//!     params:
+//!       repo: '[\w./~-]+'
+//!   restart_service:
+//!     argv: [systemctl, --user, restart, "{service}"]
+//! ```
+
+use crate::config_error;
+use crate::error::{LunaError, Result};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+static PLACEHOLDER_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\{([a-z_][a-z0-9_]*)\}").unwrap());
+
+/// Values accepted for placeholders without their own pattern
+const DEFAULT_PARAM_PATTERN: &str = r"[\w .,:@/+~-]+";
+
+/// Interpreters that would turn a parameter into arbitrary code
+const SHELL_BINARIES: &[&str] = &[
+    "sh",
+    "bash",
+    "zsh",
+    "fish",
+    "dash",
+    "ksh",
+    "csh",
+    "cmd",
+    "cmd.exe",
+    "powershell",
+    "powershell.exe",
+    "pwsh",
+    "osascript",
+    "python",
+    "python3",
+    "perl",
+    "ruby",
+    "node",
+];
+
+/// Skills file as written by the user
+#[derive(Debug, Deserialize)]
+struct SkillsFile {
+    #[serde(default)]
+    allowed_binaries: Vec<String>,
+    #[serde(default)]
+    skills: BTreeMap<String, SkillDefinition>,
+}
+
+/// A named command template
+#[derive(Debug, Clone, Deserialize)]
+pub struct SkillDefinition {
+    /// Human-readable summary
+    #[serde(default)]
+    pub description: String,
+    /// Program followed by its arguments; arguments may hold `{placeholders}`
+    pub argv: Vec<String>,
+    /// Regex each placeholder value must fully match
+    #[serde(default)]
+    pub params: BTreeMap<String, String>,
+}
+
+/// Skill with compiled parameter patterns
+#[derive(Debug, Clone)]
+struct CompiledSkill {
+    definition: SkillDefinition,
+    patterns: HashMap<String, Regex>,
+}
+
+/// Command resolved from a skill, ready to run
+///
+/// Only `SkillRegistry::resolve` can build one, so anything executed has
+/// passed the allowlist.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvedCommand {
+    skill: String,
+    argv: Vec<String>,
+}
+
+impl ResolvedCommand {
+    /// Name of the skill this command came from
+    pub fn skill(&self) -> &str {
+        &self.skill
+    }
+
+    /// Program to execute
+    pub fn program(&self) -> &str {
+        &self.argv[0]
+    }
+
+    /// Arguments after the program
+    pub fn args(&self) -> &[String] {
+        &self.argv[1..]
+    }
+}
+
+/// Registry of allowlisted skills
+#[derive(Debug, Clone, Default)]
+pub struct SkillRegistry {
+    allowed_binaries: Vec<String>,
+    skills: BTreeMap<String, CompiledSkill>,
+}
+
+impl SkillRegistry {
+    /// Load skills from a YAML file
+    pub fn load_from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let content = std::fs::read_to_string(path.as_ref())
+            .map_err(|e| config_error!("Failed to read skills file: {}", e))?;
+        Self::from_yaml(&content)
+    }
+
+    /// Parse and validate skills from YAML
+    ///
+    /// Fails if any skill runs a binary outside `allowed_binaries`, runs a
+    /// shell or interpreter, or templates its program name.
+    pub fn from_yaml(content: &str) -> Result<Self> {
+        let file: SkillsFile = serde_yaml::from_str(content)
+            .map_err(|e| config_error!("Failed to parse skills YAML: {}", e))?;
+
+        let mut registry = Self {
+            allowed_binaries: file.allowed_binaries,
+            skills: BTreeMap::new(),
+        };
+        for (name, definition) in file.skills {
+            registry.add_skill(&name, definition)?;
+        }
+        Ok(registry)
+    }
+
+    /// Add a skill, validating it against the allowlist
+    pub fn add_skill(&mut self, name: &str, definition: SkillDefinition) -> Result<()> {
+        let Some(program) = definition.argv.first() else {
+            return Err(config_error!("Skill '{}' has an empty argv", name));
+        };
+        if program.contains('{') {
+            return Err(config_error!(
+                "Skill '{}' can't template its program name",
+                name
+            ));
+        }
+        if is_shell(program) {
+            return Err(config_error!(
+                "Skill '{}' runs '{}'; shells and interpreters aren't allowed",
+                name,
+                program
+            ));
+        }
+        if !self.is_allowed(program) {
+            return Err(config_error!(
+                "Skill '{}' runs '{}', which isn't in allowed_binaries",
+                name,
+                program
+            ));
+        }
+
+        let mut patterns = HashMap::new();
+        for arg in &definition.argv[1..] {
+            for caps in PLACEHOLDER_REGEX.captures_iter(arg) {
+                let param = &caps[1];
+                let pattern = definition
+                    .params
+                    .get(param)
+                    .map(String::as_str)
+                    .unwrap_or(DEFAULT_PARAM_PATTERN);
+                let regex = Regex::new(&format!("^(?:{})$", pattern)).map_err(|e| {
+                    config_error!("Invalid pattern for '{}' in skill '{}': {}", param, name, e)
+                })?;
+                patterns.insert(param.to_string(), regex);
+            }
+        }
+
+        self.skills.insert(
+            normalize_name(name),
+            CompiledSkill {
+                definition,
+                patterns,
+            },
+        );
+        Ok(())
+    }
+
+    /// Resolve a skill and its parameters into a command
+    ///
+    /// Unknown skills, missing parameters and values that don't match their
+    /// pattern are rejected. Values can never start with `-`, so they can't
+    /// smuggle in extra options.
+    pub fn resolve(&self, name: &str, params: &HashMap<String, String>) -> Result<ResolvedCommand> {
+        let key = normalize_name(name);
+        let skill = self.skills.get(&key).ok_or_else(|| {
+            LunaError::InvalidParameter(format!("'{}' is not an allowed command", name))
+        })?;
+
+        let mut argv = Vec::with_capacity(skill.definition.argv.len());
+        for arg in &skill.definition.argv {
+            let mut resolved = String::with_capacity(arg.len());
+            let mut last = 0;
+            for caps in PLACEHOLDER_REGEX.captures_iter(arg) {
+                let whole = caps.get(0).unwrap();
+                let param = &caps[1];
+                let value = params.get(param).ok_or_else(|| {
+                    LunaError::InvalidParameter(format!(
+                        "Missing parameter '{}' for {}",
+                        param, key
+                    ))
+                })?;
+                if value.starts_with('-') || !skill.patterns[param].is_match(value) {
+                    return Err(LunaError::InvalidParameter(format!(
+                        "Invalid value for '{}' in {}: {:?}",
+                        param, key, value
+                    )));
+                }
+
+                resolved.push_str(&arg[last..whole.start()]);
+                resolved.push_str(value);
+                last = whole.end();
+            }
+            resolved.push_str(&arg[last..]);
+            argv.push(resolved);
+        }
+
+        // Defense in depth: the program is never templated, but check anyway
+        if !self.is_allowed(&argv[0]) {
+            return Err(LunaError::InvalidParameter(format!(
+                "'{}' is not an allowed binary",
+                argv[0]
+            )));
+        }
+
+        Ok(ResolvedCommand { skill: key, argv })
+    }
+
+    /// Look up a skill definition
+    pub fn get(&self, name: &str) -> Option<&SkillDefinition> {
+        self.skills
+            .get(&normalize_name(name))
+            .map(|skill| &skill.definition)
+    }
+
+    /// Names of all registered skills, sorted
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.skills.keys().map(String::as_str)
+    }
+
+    /// Number of skills
+    pub fn len(&self) -> usize {
+        self.skills.len()
+    }
+
+    /// Check if no skills are registered
+    pub fn is_empty(&self) -> bool {
+        self.skills.is_empty()
+    }
+
+    fn is_allowed(&self, program: &str) -> bool {
+        self.allowed_binaries
+            .iter()
+            .any(|allowed| allowed == program)
+    }
+}
+
+/// Skill names are matched as spoken: "git status" finds `git_status`
+fn normalize_name(name: &str) -> String {
+    name.trim()
+        .to_lowercase()
+        .split(|c: char| c.is_whitespace() || c == '-' || c == '_')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+fn is_shell(program: &str) -> bool {
+    let name = Path::new(program)
+        .file_name()
+        .map(|n| n.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    SHELL_BINARIES.contains(&name.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SKILLS: &str = r#"
+allowed_binaries: [git, echo]
+skills:
+  git_status:
+    description: Show uncommitted changes
+    argv: [git, -C, "{repo}", status, --short]
+    params:
+      repo: '[\w./~-]+'
+  greet:
+    argv: [echo, "hello {name}"]
+"#;
+
+    fn params(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_listed_skill_substitutes_params() {
+        let registry = SkillRegistry::from_yaml(SKILLS).unwrap();
+        assert_eq!(
+            registry.names().collect::<Vec<_>>(),
+            ["git_status", "greet"]
+        );
+
+        let command = registry
+            .resolve("git status", &params(&[("repo", "~/src/luna")]))
+            .unwrap();
+        assert_eq!(command.skill(), "git_status");
+        assert_eq!(command.program(), "git");
+        assert_eq!(command.args(), ["-C", "~/src/luna", "status", "--short"]);
+
+        // Plain values are substituted; shell metacharacters are rejected outright
+        let command = registry
+            .resolve("greet", &params(&[("name", "world")]))
+            .unwrap();
+        assert_eq!(command.args(), ["hello world"]);
+        assert!(registry
+            .resolve("greet", &params(&[("name", "x; rm -rf ~")]))
+            .is_err());
+    }
+
+    #[test]
+    fn test_unlisted_command_is_rejected() {
+        let registry = SkillRegistry::from_yaml(SKILLS).unwrap();
+
+        let err = registry.resolve("rm", &params(&[])).unwrap_err();
+        assert!(err.to_string().contains("not an allowed command"));
+        assert!(SkillRegistry::default()
+            .resolve("git status", &params(&[("repo", ".")]))
+            .is_err());
+    }
+
+    #[test]
+    fn test_invalid_params_are_rejected() {
+        let registry = SkillRegistry::from_yaml(SKILLS).unwrap();
+
+        // Missing, option-like and pattern-violating values
+        assert!(registry.resolve("git_status", &params(&[])).is_err());
+        assert!(registry
+            .resolve("git_status", &params(&[("repo", "--exec-path=/tmp")]))
+            .is_err());
+        assert!(registry
+            .resolve("git_status", &params(&[("repo", "a b")]))
+            .is_err());
+    }
+
+    #[test]
+    fn test_unsafe_skills_fail_to_load() {
+        let unlisted = "allowed_binaries: [git]\nskills:\n  wipe:\n    argv: [rm, -rf, /]\n";
+        let shell = "allowed_binaries: [bash]\nskills:\n  run:\n    argv: [bash, -c, \"{cmd}\"]\n";
+        let templated = "allowed_binaries: [git]\nskills:\n  any:\n    argv: [\"{bin}\"]\n";
+
+        for yaml in [unlisted, shell, templated] {
+            assert!(SkillRegistry::from_yaml(yaml).is_err(), "{}", yaml);
+        }
+    }
+}
@@ -3,16 +3,61 @@
 //! Volume, brightness, power management with cross-platform support.
 
 use crate::error::{LunaError, Result};
+use crate::os::desktop_environment::AudioBackend;
 use std::process::Command;
 use tracing::{debug, info, warn};
 
+/// Spoken when no brightness backend works on this machine
+const BRIGHTNESS_UNAVAILABLE: &str = "Brightness control unavailable on this system";
+
+/// Volume change requested from the mixer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+enum VolumeChange {
+    Set(u8),
+    Adjust(i8),
+    ToggleMute,
+}
+
 /// System control handler
-pub struct SystemControl;
+pub struct SystemControl {
+    #[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+    audio: AudioBackend,
+}
 
 impl SystemControl {
     /// Create a new system control handler
     pub fn new() -> Self {
-        Self
+        Self {
+            audio: AudioBackend::Pactl,
+        }
+    }
+
+    /// Use the mixer selected for the desktop environment
+    ///
+    /// The other Linux mixer stays as a fallback.
+    pub fn with_audio_backend(mut self, backend: AudioBackend) -> Self {
+        info!("System control using {:?} for audio", backend);
+        self.audio = backend;
+        self
+    }
+
+    /// Run a volume change through the preferred mixer, then the fallback
+    #[cfg(target_os = "linux")]
+    fn run_mixer(&self, change: VolumeChange) -> Result<()> {
+        let mut last_error = None;
+        for (program, args) in mixer_commands(self.audio, change) {
+            match Command::new(program).args(args).status() {
+                Ok(status) if status.success() => return Ok(()),
+                Ok(status) => last_error = Some(format!("{} exited with {}", program, status)),
+                Err(e) => last_error = Some(format!("{}: {}", program, e)),
+            }
+        }
+
+        Err(LunaError::SystemOperation(format!(
+            "No mixer worked ({})",
+            last_error.unwrap_or_default()
+        )))
     }
 
     /// Set system volume (0-100)
@@ -27,28 +72,8 @@ impl SystemControl {
 
         #[cfg(target_os = "linux")]
         {
-            // Try PulseAudio first, then ALSA
-            let result = Command::new("pactl")
-                .args(&["set-sink-volume", "@DEFAULT_SINK@", &format!("{}%", level)])
-                .status();
-
-            if result.is_ok() && result.unwrap().success() {
-                return Ok(format!("Volume set to {}%", level));
-            }
-
-            // Fallback to amixer (ALSA)
-            let status = Command::new("amixer")
-                .args(&["set", "Master", &format!("{}%", level)])
-                .status()
-                .map_err(|e| LunaError::SystemOperation(format!("Failed to set volume: {}", e)))?;
-
-            if status.success() {
-                Ok(format!("Volume set to {}%", level))
-            } else {
-                Err(LunaError::SystemOperation(
-                    "Failed to set volume".to_string(),
-                ))
-            }
+            self.run_mixer(VolumeChange::Set(level))?;
+            Ok(format!("Volume set to {}%", level))
         }
 
         #[cfg(target_os = "windows")]
@@ -92,25 +117,8 @@ impl SystemControl {
 
         #[cfg(target_os = "linux")]
         {
-            let sign = if delta > 0 { "+" } else { "" };
-            let status = Command::new("pactl")
-                .args(&[
-                    "set-sink-volume",
-                    "@DEFAULT_SINK@",
-                    &format!("{}{}%", sign, delta),
-                ])
-                .status()
-                .map_err(|e| {
-                    LunaError::SystemOperation(format!("Failed to adjust volume: {}", e))
-                })?;
-
-            if status.success() {
-                Ok(format!("Volume adjusted by {}", delta))
-            } else {
-                Err(LunaError::SystemOperation(
-                    "Failed to adjust volume".to_string(),
-                ))
-            }
+            self.run_mixer(VolumeChange::Adjust(delta))?;
+            Ok(format!("Volume adjusted by {}", delta))
         }
 
         #[cfg(not(target_os = "linux"))]
@@ -121,71 +129,35 @@ impl SystemControl {
         }
     }
 
-    /// Set screen brightness (0-100)
+    /// Set screen brightness (0-100, higher values are clamped)
     pub async fn set_brightness(&self, level: u8) -> Result<String> {
+        let level = level.min(100);
         info!("Setting brightness to {}%", level);
 
-        if level > 100 {
-            return Err(LunaError::InvalidParameter(
-                "Brightness must be 0-100".to_string(),
-            ));
-        }
-
-        #[cfg(target_os = "linux")]
-        {
-            // Try xbacklight first
-            let result = Command::new("xbacklight")
-                .args(&["-set", &level.to_string()])
-                .status();
-
-            if result.is_ok() && result.unwrap().success() {
-                return Ok(format!("Brightness set to {}%", level));
+        match crate::os::set_brightness(level) {
+            Ok(()) => Ok(format!("Brightness set to {}%", level)),
+            Err(e) => {
+                warn!("Brightness control failed: {}", e);
+                Ok(BRIGHTNESS_UNAVAILABLE.to_string())
             }
-
-            // Fallback to brightnessctl
-            let status = Command::new("brightnessctl")
-                .args(&["set", &format!("{}%", level)])
-                .status()
-                .map_err(|e| {
-                    LunaError::SystemOperation(format!("Failed to set brightness: {}", e))
-                })?;
-
-            if status.success() {
-                Ok(format!("Brightness set to {}%", level))
-            } else {
-                Err(LunaError::SystemOperation(
-                    "Failed to set brightness. Install xbacklight or brightnessctl".to_string(),
-                ))
-            }
-        }
-
-        #[cfg(target_os = "windows")]
-        {
-            // Windows brightness control requires WMI
-            warn!("Brightness control not implemented for Windows");
-            Err(LunaError::SystemOperation(
-                "Brightness control not supported on Windows".to_string(),
-            ))
         }
+    }
 
-        #[cfg(target_os = "macos")]
-        {
-            // Use brightness command on macOS
-            let status = Command::new("brightness")
-                .arg(&format!("{}", level as f32 / 100.0))
-                .status()
-                .map_err(|e| {
-                    LunaError::SystemOperation(format!("Failed to set brightness: {}", e))
-                })?;
+    /// Adjust screen brightness by delta (-100 to +100)
+    pub async fn adjust_brightness(&self, delta: i8) -> Result<String> {
+        info!("Adjusting brightness by {}", delta);
 
-            if status.success() {
-                Ok(format!("Brightness set to {}%", level))
-            } else {
-                Err(LunaError::SystemOperation(
-                    "brightness command not found".to_string(),
-                ))
+        let current = match crate::os::get_brightness() {
+            Ok(level) => level,
+            Err(e) => {
+                warn!("Brightness control failed: {}", e);
+                return Ok(BRIGHTNESS_UNAVAILABLE.to_string());
             }
-        }
+        };
+
+        let target = clamp_brightness(current, delta);
+        debug!("Brightness {}% -> {}%", current, target);
+        self.set_brightness(target).await
     }
 
     /// Lock the computer
@@ -355,18 +327,8 @@ impl SystemControl {
 
         #[cfg(target_os = "linux")]
         {
-            let status = Command::new("pactl")
-                .args(&["set-sink-mute", "@DEFAULT_SINK@", "toggle"])
-                .status()
-                .map_err(|e| LunaError::SystemOperation(format!("Failed to toggle mute: {}", e)))?;
-
-            if status.success() {
-                Ok("Audio mute toggled".to_string())
-            } else {
-                Err(LunaError::SystemOperation(
-                    "Failed to toggle mute".to_string(),
-                ))
-            }
+            self.run_mixer(VolumeChange::ToggleMute)?;
+            Ok("Audio mute toggled".to_string())
         }
 
         #[cfg(not(target_os = "linux"))]
@@ -378,6 +340,56 @@ impl SystemControl {
     }
 }
 
+/// Mixer commands for a volume change, preferred backend first
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn mixer_commands(
+    preferred: AudioBackend,
+    change: VolumeChange,
+) -> Vec<(&'static str, Vec<String>)> {
+    let pactl = || {
+        let args = match change {
+            VolumeChange::Set(level) => vec![
+                "set-sink-volume".to_string(),
+                "@DEFAULT_SINK@".to_string(),
+                format!("{}%", level),
+            ],
+            VolumeChange::Adjust(delta) => vec![
+                "set-sink-volume".to_string(),
+                "@DEFAULT_SINK@".to_string(),
+                format!("{:+}%", delta),
+            ],
+            VolumeChange::ToggleMute => vec![
+                "set-sink-mute".to_string(),
+                "@DEFAULT_SINK@".to_string(),
+                "toggle".to_string(),
+            ],
+        };
+        ("pactl", args)
+    };
+    let amixer = || {
+        let value = match change {
+            VolumeChange::Set(level) => format!("{}%", level),
+            VolumeChange::Adjust(delta) if delta < 0 => format!("{}%-", delta.unsigned_abs()),
+            VolumeChange::Adjust(delta) => format!("{}%+", delta),
+            VolumeChange::ToggleMute => "toggle".to_string(),
+        };
+        (
+            "amixer",
+            vec!["set".to_string(), "Master".to_string(), value],
+        )
+    };
+
+    match preferred {
+        AudioBackend::Amixer => vec![amixer(), pactl()],
+        _ => vec![pactl(), amixer()],
+    }
+}
+
+/// Apply a signed delta to a brightness level, clamped to 0-100
+pub fn clamp_brightness(current: u8, delta: i8) -> u8 {
+    (current as i16 + delta as i16).clamp(0, 100) as u8
+}
+
 impl Default for SystemControl {
     fn default() -> Self {
         Self::new()
@@ -402,10 +414,24 @@ mod tests {
         assert!(result.is_err());
     }
 
-    #[tokio::test]
-    async fn test_invalid_brightness() {
-        let control = SystemControl::new();
-        let result = control.set_brightness(150).await;
-        assert!(result.is_err());
+    #[test]
+    fn test_mixer_fallback_order() {
+        let commands = mixer_commands(AudioBackend::Amixer, VolumeChange::Adjust(-10));
+        assert_eq!(commands[0].0, "amixer");
+        assert_eq!(commands[0].1, ["set", "Master", "10%-"]);
+        assert_eq!(commands[1].0, "pactl");
+        assert_eq!(commands[1].1, ["set-sink-volume", "@DEFAULT_SINK@", "-10%"]);
+
+        let commands = mixer_commands(AudioBackend::Pactl, VolumeChange::Set(40));
+        assert_eq!(commands[0].1, ["set-sink-volume", "@DEFAULT_SINK@", "40%"]);
+        assert_eq!(commands[1].1, ["set", "Master", "40%"]);
+    }
+
+    #[test]
+    fn test_clamp_brightness() {
+        assert_eq!(clamp_brightness(50, 20), 70);
+        assert_eq!(clamp_brightness(95, 20), 100);
+        assert_eq!(clamp_brightness(5, -20), 0);
+        assert_eq!(clamp_brightness(100, 127), 100);
     }
 }
@@ -1,18 +1,107 @@
 //! Window management (GOD-LEVEL Enhancement)
 //!
 //! Cross-platform window control for move, resize, maximize, minimize, focus.
+//!
+//! Without a selected backend, Linux commands use `wmctrl` with `xdotool`
+//! fallbacks; `with_backend` routes them to the tool chosen for the detected
+//! desktop environment instead.
 
 use crate::error::{LunaError, Result};
+use crate::os::desktop_environment::WindowBackend;
 use std::process::Command;
 use tracing::{info, warn};
 
+/// Target name that refers to the currently focused window
+pub const ACTIVE_WINDOW: &str = ":ACTIVE:";
+
 /// Window control handler
-pub struct WindowControl;
+pub struct WindowControl {
+    backend: Option<WindowBackend>,
+}
 
 impl WindowControl {
     /// Create a new window control handler
     pub fn new() -> Self {
-        Self
+        Self { backend: None }
+    }
+
+    /// Use the tool selected for the desktop environment
+    pub fn with_backend(mut self, backend: WindowBackend) -> Self {
+        info!("Window control using {:?}", backend);
+        self.backend = Some(backend);
+        self
+    }
+
+    /// Perform a named window action on an app window, or the active window when
+    /// no target is given
+    ///
+    /// Supported actions: minimize, maximize, restore, tile_left, tile_right, close, focus.
+    pub async fn perform(&self, action: &str, target: Option<&str>) -> Result<String> {
+        let action = action.trim().to_lowercase().replace(['-', ' '], "_");
+        let target = target.unwrap_or(ACTIVE_WINDOW);
+
+        if !cfg!(target_os = "linux") && action != "focus" {
+            warn!("Window action '{}' not supported on this platform", action);
+            return Ok(format!(
+                "Window action '{}' is not supported on this platform",
+                action
+            ));
+        }
+
+        match self.backend {
+            Some(WindowBackend::Unsupported) => {
+                return Ok("No window tool found. Install wmctrl, xdotool or wlrctl".to_string());
+            }
+            Some(backend @ (WindowBackend::Wlrctl | WindowBackend::Xdotool)) => {
+                return self.perform_with(backend, &action, target);
+            }
+            _ => {}
+        }
+
+        let result = match action.as_str() {
+            "minimize" | "minimise" | "hide" => self.minimize_window(target).await,
+            "maximize" | "maximise" | "fullscreen" => self.maximize_window(target).await,
+            "restore" | "unmaximize" => self.restore_window(target).await,
+            "tile_left" | "left" | "left_half" => self.tile_window(target, TileSide::Left).await,
+            "tile_right" | "right" | "right_half" => {
+                self.tile_window(target, TileSide::Right).await
+            }
+            "close" => self.close_window(target).await,
+            "focus" | "switch" => self.focus_window(target).await,
+            _ => Ok(format!("Unknown window action: {}", action)),
+        };
+
+        result.map(|msg| msg.replace(ACTIVE_WINDOW, "active window"))
+    }
+
+    /// Run a window action through a single-tool backend
+    fn perform_with(&self, backend: WindowBackend, action: &str, target: &str) -> Result<String> {
+        let name = if target == ACTIVE_WINDOW {
+            "active window"
+        } else {
+            target
+        };
+        let Some((program, verb, args)) = backend_command(backend, action, target) else {
+            return Ok(format!(
+                "Window action '{}' is not supported with {:?}",
+                action, backend
+            ));
+        };
+
+        info!("{} window {} with {}", verb, name, program);
+        let status = Command::new(program)
+            .args(args)
+            .status()
+            .map_err(|e| LunaError::SystemOperation(format!("Failed to run {}: {}", program, e)))?;
+
+        if status.success() {
+            Ok(format!("{} window: {}", verb, name))
+        } else {
+            Err(LunaError::SystemOperation(format!(
+                "{} couldn't find window: {}",
+                program, name
+            )))
+        }
     }
 
     /// Focus a window by application name
@@ -135,8 +224,14 @@ if ($p) {{ [Window]::SetForegroundWindow($p.MainWindowHandle) }}"#,
 
         #[cfg(target_os = "linux")]
         {
+            let args: Vec<&str> = if app_name == ACTIVE_WINDOW {
+                vec!["getactivewindow", "windowminimize"]
+            } else {
+                vec!["search", "--name", app_name, "windowminimize"]
+            };
+
             let status = Command::new("xdotool")
-                .args(&["search", "--name", app_name, "windowminimize"])
+                .args(&args)
                 .status()
                 .map_err(|e| LunaError::SystemOperation(format!("Failed to minimize: {}", e)))?;
 
@@ -158,6 +253,104 @@ if ($p) {{ [Window]::SetForegroundWindow($p.MainWindowHandle) }}"#,
         }
     }
 
+    /// Restore a maximized window to its normal size
+    pub async fn restore_window(&self, app_name: &str) -> Result<String> {
+        info!("Restoring window: {}", app_name);
+
+        #[cfg(target_os = "linux")]
+        {
+            let status = Command::new("wmctrl")
+                .args(["-r", app_name, "-b", "remove,maximized_vert,maximized_horz"])
+                .status()
+                .map_err(|e| LunaError::SystemOperation(format!("Failed to restore: {}", e)))?;
+
+            if status.success() {
+                Ok(format!("Restored window: {}", app_name))
+            } else {
+                Err(LunaError::SystemOperation(
+                    "wmctrl not found. Install wmctrl".to_string(),
+                ))
+            }
+        }
+
+        #[cfg(not(target_os = "linux"))]
+        {
+            warn!("Restore not implemented for this platform");
+            Err(LunaError::SystemOperation(
+                "Restore not supported on this platform".to_string(),
+            ))
+        }
+    }
+
+    /// Tile a window to the left or right half of the screen
+    pub async fn tile_window(&self, app_name: &str, side: TileSide) -> Result<String> {
+        info!("Tiling window {} to {:?} half", app_name, side);
+
+        #[cfg(target_os = "linux")]
+        {
+            let output = Command::new("xdotool")
+                .arg("getdisplaygeometry")
+                .output()
+                .map_err(|e| {
+                    LunaError::SystemOperation(format!("Failed to get display size: {}", e))
+                })?;
+
+            let geometry = String::from_utf8_lossy(&output.stdout);
+            let mut dims = geometry
+                .split_whitespace()
+                .filter_map(|s| s.parse::<u32>().ok());
+            let (width, height) = match (dims.next(), dims.next()) {
+                (Some(w), Some(h)) => (w, h),
+                _ => {
+                    return Err(LunaError::SystemOperation(
+                        "xdotool not found. Install xdotool".to_string(),
+                    ))
+                }
+            };
+
+            let half = width / 2;
+            let x = match side {
+                TileSide::Left => 0,
+                TileSide::Right => half,
+            };
+
+            // Un-maximize first, otherwise the window manager ignores the geometry
+            let _ = Command::new("wmctrl")
+                .args(["-r", app_name, "-b", "remove,maximized_vert,maximized_horz"])
+                .status();
+
+            let status = Command::new("wmctrl")
+                .args([
+                    "-r",
+                    app_name,
+                    "-e",
+                    &format!("0,{},0,{},{}", x, half, height),
+                ])
+                .status()
+                .map_err(|e| LunaError::SystemOperation(format!("Failed to tile window: {}", e)))?;
+
+            if status.success() {
+                Ok(format!(
+                    "Moved window {} to the {} half",
+                    app_name,
+                    side.as_str()
+                ))
+            } else {
+                Err(LunaError::SystemOperation(
+                    "wmctrl not found. Install wmctrl".to_string(),
+                ))
+            }
+        }
+
+        #[cfg(not(target_os = "linux"))]
+        {
+            warn!("Tiling not implemented for this platform");
+            Err(LunaError::SystemOperation(
+                "Tiling not supported on this platform".to_string(),
+            ))
+        }
+    }
+
     /// Move window to specific position
     pub async fn move_window(&self, app_name: &str, x: i32, y: i32) -> Result<String> {
         info!("Moving window {} to ({}, {})", app_name, x, y);
@@ -253,6 +446,78 @@ if ($p) {{ [Window]::SetForegroundWindow($p.MainWindowHandle) }}"#,
     }
 }
 
+/// Program, past-tense verb and arguments for an action on a backend
+///
+/// Returns None when the backend has no equivalent (e.g. tiling with wlrctl).
+fn backend_command(
+    backend: WindowBackend,
+    action: &str,
+    target: &str,
+) -> Option<(&'static str, &'static str, Vec<String>)> {
+    let (verb, command): (&str, &[&str]) = match (backend, action) {
+        (WindowBackend::Wlrctl, "minimize" | "minimise" | "hide") => ("Minimized", &["minimize"]),
+        (WindowBackend::Wlrctl, "maximize" | "maximise" | "fullscreen") => {
+            ("Maximized", &["maximize"])
+        }
+        (WindowBackend::Wlrctl, "restore" | "unmaximize") => ("Restored", &["unmaximize"]),
+        (WindowBackend::Wlrctl, "close") => ("Closed", &["close"]),
+        (WindowBackend::Wlrctl, "focus" | "switch") => ("Focused", &["focus"]),
+        (WindowBackend::Xdotool, "minimize" | "minimise" | "hide") => {
+            ("Minimized", &["windowminimize"])
+        }
+        (WindowBackend::Xdotool, "maximize" | "maximise" | "fullscreen") => (
+            "Maximized",
+            &["windowmove", "0", "0", "windowsize", "100%", "100%"],
+        ),
+        (WindowBackend::Xdotool, "close") => ("Closed", &["windowclose"]),
+        (WindowBackend::Xdotool, "focus" | "switch") => ("Focused", &["windowactivate"]),
+        _ => return None,
+    };
+
+    let mut args: Vec<String> = Vec::new();
+    let program = match backend {
+        WindowBackend::Wlrctl => {
+            // wlrctl toplevel <action> <match>
+            args.push("toplevel".to_string());
+            args.extend(command.iter().map(|s| s.to_string()));
+            args.push(if target == ACTIVE_WINDOW {
+                "state:active".to_string()
+            } else {
+                format!("app_id:{}", target)
+            });
+            "wlrctl"
+        }
+        _ => {
+            // xdotool <window selection> <commands...>
+            if target == ACTIVE_WINDOW {
+                args.push("getactivewindow".to_string());
+            } else {
+                args.extend(["search", "--name", target].map(String::from));
+            }
+            args.extend(command.iter().map(|s| s.to_string()));
+            "xdotool"
+        }
+    };
+    Some((program, verb, args))
+}
+
+/// Screen half used when tiling a window
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TileSide {
+    Left,
+    Right,
+}
+
+impl TileSide {
+    /// Human-readable name
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TileSide::Left => "left",
+            TileSide::Right => "right",
+        }
+    }
+}
+
 impl Default for WindowControl {
     fn default() -> Self {
         Self::new()
@@ -268,4 +533,41 @@ mod tests {
         let control = WindowControl::new();
         drop(control);
     }
+
+    #[tokio::test]
+    async fn test_perform_unknown_action() {
+        let control = WindowControl::new();
+        let result = control.perform("spin", Some("firefox")).await.unwrap();
+        assert!(result.contains("spin"));
+    }
+
+    #[test]
+    fn test_backend_commands() {
+        let (program, verb, args) =
+            backend_command(WindowBackend::Wlrctl, "minimize", "firefox").unwrap();
+        assert_eq!((program, verb), ("wlrctl", "Minimized"));
+        assert_eq!(args, ["toplevel", "minimize", "app_id:firefox"]);
+
+        let (program, _, args) =
+            backend_command(WindowBackend::Xdotool, "focus", ACTIVE_WINDOW).unwrap();
+        assert_eq!(program, "xdotool");
+        assert_eq!(args, ["getactivewindow", "windowactivate"]);
+
+        assert!(backend_command(WindowBackend::Wlrctl, "tile_left", "firefox").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_unsupported_backend_explains() {
+        let control = WindowControl::new().with_backend(WindowBackend::Unsupported);
+        if cfg!(target_os = "linux") {
+            let result = control.perform("maximize", None).await.unwrap();
+            assert!(result.contains("No window tool found"));
+        }
+    }
+
+    #[test]
+    fn test_tile_side_names() {
+        assert_eq!(TileSide::Left.as_str(), "left");
+        assert_eq!(TileSide::Right.as_str(), "right");
+    }
 }
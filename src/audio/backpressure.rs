@@ -0,0 +1,209 @@
+//! Backpressure handling between the audio callback and consumers
+//!
+//! The real-time callback must never block, so chunks are handed to a
+//! `ChunkSender` that applies the configured `DropPolicy` when the
+//! consumer falls behind.
+
+use crate::error::LunaError;
+use crate::metrics::Metrics;
+use async_channel::{Receiver, Sender, TrySendError};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::debug;
+
+/// Chunks buffered between the callback and the `Block` forwarder thread
+const BLOCK_STAGING_CAPACITY: usize = 64;
+
+/// How often capture metrics are reported
+const METRICS_INTERVAL: Duration = Duration::from_secs(1);
+
+/// What to do with a captured chunk when the consumer channel is full
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DropPolicy {
+    /// Evict the oldest queued chunk to make room for the new one
+    DropOldest,
+    /// Discard the new chunk
+    DropNewest,
+    /// Wait for the consumer on a forwarder thread, off the RT thread
+    Block,
+}
+
+/// Parses the `audio.drop_policy` config value
+impl std::str::FromStr for DropPolicy {
+    type Err = LunaError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "dropnewest" | "drop_newest" => Ok(DropPolicy::DropNewest),
+            "block" => Ok(DropPolicy::Block),
+            "dropoldest" | "drop_oldest" => Ok(DropPolicy::DropOldest),
+            _ => Err(LunaError::InvalidParameter(format!(
+                "Unknown drop policy: {}",
+                s
+            ))),
+        }
+    }
+}
+
+/// Delivers captured chunks to the consumer channel according to a policy
+///
+/// `send` never blocks, so it is safe to call from the audio callback.
+#[derive(Clone)]
+pub struct ChunkSender {
+    policy: DropPolicy,
+    tx: Sender<Vec<f32>>,
+    /// Receiver end used to evict the oldest chunk under `DropOldest`
+    evict_rx: Receiver<Vec<f32>>,
+    /// Staging channel feeding the forwarder thread under `Block`
+    staging_tx: Option<Sender<Vec<f32>>>,
+    frames_dropped: Arc<AtomicU64>,
+}
+
+impl ChunkSender {
+    /// Create a sender for the given consumer channel
+    ///
+    /// Under `DropPolicy::Block` this spawns a forwarder thread that exits
+    /// once every clone of the sender has been dropped.
+    pub fn new(policy: DropPolicy, tx: Sender<Vec<f32>>, rx: Receiver<Vec<f32>>) -> Self {
+        let staging_tx = (policy == DropPolicy::Block).then(|| {
+            let (staging_tx, staging_rx) = async_channel::bounded(BLOCK_STAGING_CAPACITY);
+            let tx = tx.clone();
+            std::thread::spawn(move || {
+                while let Ok(chunk) = staging_rx.recv_blocking() {
+                    if tx.send_blocking(chunk).is_err() {
+                        break;
+                    }
+                }
+                debug!("Audio forwarder thread stopped");
+            });
+            staging_tx
+        });
+
+        Self {
+            policy,
+            tx,
+            evict_rx: rx,
+            staging_tx,
+            frames_dropped: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Deliver a chunk, dropping per policy if the consumer is behind
+    pub fn send(&self, chunk: Vec<f32>) {
+        let result = match (&self.staging_tx, self.policy) {
+            // Block: the staging buffer only overflows if the forwarder is stuck
+            (Some(staging), _) => staging.try_send(chunk).map_err(|_| ()),
+            (None, DropPolicy::DropOldest) => match self.tx.try_send(chunk) {
+                Err(TrySendError::Full(chunk)) => {
+                    let _ = self.evict_rx.try_recv();
+                    self.frames_dropped.fetch_add(1, Ordering::Relaxed);
+                    self.tx.try_send(chunk).map_err(|_| ())
+                }
+                other => other.map_err(|_| ()),
+            },
+            (None, _) => self.tx.try_send(chunk).map_err(|_| ()),
+        };
+
+        if result.is_err() {
+            self.frames_dropped.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Configured policy
+    pub fn policy(&self) -> DropPolicy {
+        self.policy
+    }
+
+    /// Total chunks dropped so far
+    pub fn frames_dropped(&self) -> u64 {
+        self.frames_dropped.load(Ordering::Relaxed)
+    }
+
+    /// Shared drop counter, e.g. for resetting or metrics reporting
+    pub fn dropped_counter(&self) -> Arc<AtomicU64> {
+        Arc::clone(&self.frames_dropped)
+    }
+}
+
+/// Periodically report drops and ring fill ratio until `running` is cleared
+///
+/// Runs on its own thread so the audio callback never touches metrics.
+pub fn spawn_metrics_reporter<F>(
+    metrics: Arc<Metrics>,
+    frames_dropped: Arc<AtomicU64>,
+    fill_ratio: F,
+    running: Arc<AtomicBool>,
+) where
+    F: Fn() -> f32 + Send + 'static,
+{
+    std::thread::spawn(move || {
+        let mut reported = frames_dropped.load(Ordering::Relaxed);
+        while running.load(Ordering::Relaxed) {
+            std::thread::sleep(METRICS_INTERVAL);
+
+            let dropped = frames_dropped.load(Ordering::Relaxed);
+            for _ in reported..dropped {
+                metrics.record_audio_frame_drop();
+            }
+            // Counter may have been reset
+            reported = dropped;
+
+            metrics.record_ring_fill_ratio(fill_ratio());
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn overflow(policy: DropPolicy) -> (ChunkSender, Receiver<Vec<f32>>) {
+        let (tx, rx) = async_channel::bounded(2);
+        let sender = ChunkSender::new(policy, tx, rx.clone());
+        for i in 0..5 {
+            sender.send(vec![i as f32]);
+        }
+        (sender, rx)
+    }
+
+    fn drain(rx: &Receiver<Vec<f32>>) -> Vec<f32> {
+        std::iter::from_fn(|| rx.try_recv().ok())
+            .map(|chunk| chunk[0])
+            .collect()
+    }
+
+    #[test]
+    fn test_drop_newest_keeps_first_chunks() {
+        let (sender, rx) = overflow(DropPolicy::DropNewest);
+        assert_eq!(drain(&rx), vec![0.0, 1.0]);
+        assert_eq!(sender.frames_dropped(), 3);
+    }
+
+    #[test]
+    fn test_drop_oldest_keeps_latest_chunks() {
+        let (sender, rx) = overflow(DropPolicy::DropOldest);
+        assert_eq!(drain(&rx), vec![3.0, 4.0]);
+        assert_eq!(sender.frames_dropped(), 3);
+    }
+
+    #[test]
+    fn test_block_delivers_every_chunk() {
+        let (sender, rx) = overflow(DropPolicy::Block);
+
+        let received: Vec<f32> = (0..5)
+            .map(|_| rx.recv_blocking().unwrap()[0])
+            .collect();
+        assert_eq!(received, vec![0.0, 1.0, 2.0, 3.0, 4.0]);
+        assert_eq!(sender.frames_dropped(), 0);
+    }
+
+    #[test]
+    fn test_drop_policy_from_str() {
+        let parse = |s: &str| s.parse::<DropPolicy>();
+        assert_eq!(parse("DropOldest").unwrap(), DropPolicy::DropOldest);
+        assert_eq!(parse("DropNewest").unwrap(), DropPolicy::DropNewest);
+        assert_eq!(parse("Block").unwrap(), DropPolicy::Block);
+        assert!(parse("bogus").is_err());
+    }
+}
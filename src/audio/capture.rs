@@ -8,12 +8,17 @@
 //! - Pre/post-roll capture
 //! - Comprehensive metrics
 
+use super::backpressure::{spawn_metrics_reporter, ChunkSender, DropPolicy};
 use super::traits::AudioCaptureInterface;
 use crate::config::AudioConfig;
 use crate::error::{LunaError, Result};
-use async_channel::{Receiver, Sender};
+use crate::metrics::Metrics;
+use async_channel::Receiver;
 use async_trait::async_trait;
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use hound::{WavSpec, WavWriter};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 use tracing::{debug, error, info, warn};
 
@@ -35,24 +40,43 @@ pub struct AudioCapture {
     config: AudioConfig,
     stream: Option<cpal::Stream>,
     ring_buffer: Arc<Mutex<RingBuffer>>,
-    audio_tx: Sender<Vec<f32>>,
+    chunk_sender: ChunkSender,
     audio_rx: Receiver<Vec<f32>>,
+    /// Rate of the samples in the ring buffer (the device's, once started)
+    device_sample_rate: u32,
+    /// Periodic drop/fill-ratio reporting while capturing
+    metrics: Option<Arc<Metrics>>,
+    reporting: Arc<AtomicBool>,
 }
 
 impl AudioCapture {
     /// Create a new audio capture instance
     pub fn new(config: AudioConfig) -> Result<Self> {
         let (audio_tx, audio_rx) = async_channel::bounded(10);
+        let drop_policy = config.drop_policy.parse().unwrap_or_else(|e| {
+            warn!("{}, using DropOldest", e);
+            DropPolicy::DropOldest
+        });
+        let chunk_sender = ChunkSender::new(drop_policy, audio_tx, audio_rx.clone());
 
         Ok(Self {
+            device_sample_rate: config.preferred_sample_rate,
             config,
             stream: None,
             ring_buffer: Arc::new(Mutex::new(RingBuffer::new(48000))), // 1 sec at 48kHz
-            audio_tx,
+            chunk_sender,
             audio_rx,
+            metrics: None,
+            reporting: Arc::new(AtomicBool::new(false)),
         })
     }
 
+    /// Report drops and ring fill ratio to metrics while capturing
+    pub fn with_metrics(mut self, metrics: Arc<Metrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
     /// Start capturing audio from the microphone
     fn start_internal(&mut self) -> Result<()> {
         info!("Starting audio capture...");
@@ -72,15 +96,15 @@ impl AudioCapture {
         info!("Audio config: {:?}", config);
 
         let ring_buffer = Arc::clone(&self.ring_buffer);
-        let audio_tx = self.audio_tx.clone();
-        let sample_rate = config.sample_rate().0;
+        let chunk_sender = self.chunk_sender.clone();
+        self.device_sample_rate = config.sample_rate().0;
 
         let stream = match config.sample_format() {
             cpal::SampleFormat::I16 => {
-                self.build_stream_i16(&device, &config.into(), ring_buffer, audio_tx)?
+                self.build_stream_i16(&device, &config.into(), ring_buffer, chunk_sender)?
             }
             cpal::SampleFormat::F32 => {
-                self.build_stream_f32(&device, &config.into(), ring_buffer, audio_tx)?
+                self.build_stream_f32(&device, &config.into(), ring_buffer, chunk_sender)?
             }
             format => return Err(LunaError::Audio(format!(
                 "Unsupported sample format: {:?}. Please configure your device to use I16 or F32.",
@@ -93,6 +117,18 @@ impl AudioCapture {
             .map_err(|e| LunaError::Audio(format!("Failed to play stream: {}", e)))?;
 
         self.stream = Some(stream);
+
+        if let Some(metrics) = &self.metrics {
+            self.reporting.store(true, Ordering::Relaxed);
+            let ring_buffer = Arc::clone(&self.ring_buffer);
+            spawn_metrics_reporter(
+                Arc::clone(metrics),
+                self.chunk_sender.dropped_counter(),
+                move || ring_buffer.lock().unwrap().fill_ratio(),
+                Arc::clone(&self.reporting),
+            );
+        }
+
         info!("✅ Audio capture started");
         Ok(())
     }
@@ -103,7 +139,7 @@ impl AudioCapture {
         device: &cpal::Device,
         config: &cpal::StreamConfig,
         ring_buffer: Arc<Mutex<RingBuffer>>,
-        audio_tx: Sender<Vec<f32>>,
+        chunk_sender: ChunkSender,
     ) -> Result<cpal::Stream> {
         let channels = config.channels as usize;
         let silence_threshold = self.config.silence_threshold;
@@ -132,7 +168,7 @@ impl AudioCapture {
                     let rms = calculate_rms(&mono_samples);
                     if rms > silence_threshold {
                         // Send to processing pipeline
-                        let _ = audio_tx.try_send(mono_samples);
+                        chunk_sender.send(mono_samples);
                     }
                 },
                 |err| {
@@ -151,7 +187,7 @@ impl AudioCapture {
         device: &cpal::Device,
         config: &cpal::StreamConfig,
         ring_buffer: Arc<Mutex<RingBuffer>>,
-        audio_tx: Sender<Vec<f32>>,
+        chunk_sender: ChunkSender,
     ) -> Result<cpal::Stream> {
         let channels = config.channels as usize;
         let silence_threshold = self.config.silence_threshold;
@@ -179,7 +215,7 @@ impl AudioCapture {
                     let rms = calculate_rms(&mono_samples);
                     if rms > silence_threshold {
                         // Send to processing pipeline
-                        let _ = audio_tx.try_send(mono_samples);
+                        chunk_sender.send(mono_samples);
                     }
                 },
                 |err| {
@@ -198,6 +234,31 @@ impl AudioCapture {
         buffer.get_last_n_samples(duration_ms)
     }
 
+    /// Write the last `duration_ms` of buffered audio to a WAV file
+    ///
+    /// Captures exactly what the wake word detector and STT were fed, for
+    /// debugging misrecognitions. Limited to the ring buffer's length.
+    pub fn dump_last<P: AsRef<Path>>(&self, duration_ms: u64, path: P) -> Result<()> {
+        let samples = self
+            .ring_buffer
+            .lock()
+            .unwrap()
+            .get_last_n_samples(duration_ms);
+        write_wav(path.as_ref(), &samples, self.device_sample_rate)?;
+
+        info!(
+            "Dumped {} samples to {}",
+            samples.len(),
+            path.as_ref().display()
+        );
+        Ok(())
+    }
+
+    /// Sample rate of buffered audio
+    pub fn sample_rate(&self) -> u32 {
+        self.device_sample_rate
+    }
+
     /// Record a command with timeout
     pub async fn record_command(&self, max_duration_secs: u64) -> Result<Vec<f32>> {
         let mut recording = Vec::new();
@@ -239,6 +300,7 @@ impl AudioCapture {
     fn stop_internal(&mut self) -> Result<()> {
         if let Some(stream) = self.stream.take() {
             drop(stream);
+            self.reporting.store(false, Ordering::Relaxed);
             info!("Audio capture stopped");
         }
         Ok(())
@@ -258,8 +320,8 @@ impl AudioCapture {
     pub fn get_stats(&self) -> CaptureStats {
         CaptureStats {
             frames_captured: 0,
-            frames_dropped: 0,
-            ring_fill_ratio: 0.0,
+            frames_dropped: self.chunk_sender.frames_dropped(),
+            ring_fill_ratio: self.ring_buffer.lock().unwrap().fill_ratio(),
             sample_rate: self.config.sample_rate,
         }
     }
@@ -295,6 +357,8 @@ pub struct RingBuffer {
     data: Vec<f32>,
     capacity: usize,
     write_pos: usize,
+    /// Samples written since the last clear, up to capacity
+    filled: usize,
 }
 
 impl RingBuffer {
@@ -303,6 +367,7 @@ impl RingBuffer {
             data: vec![0.0; capacity],
             capacity,
             write_pos: 0,
+            filled: 0,
         }
     }
 
@@ -311,6 +376,12 @@ impl RingBuffer {
             self.data[self.write_pos] = sample;
             self.write_pos = (self.write_pos + 1) % self.capacity;
         }
+        self.filled = (self.filled + samples.len()).min(self.capacity);
+    }
+
+    /// Fraction of the buffer holding captured audio (0.0 - 1.0)
+    pub fn fill_ratio(&self) -> f32 {
+        self.filled as f32 / self.capacity as f32
     }
 
     pub fn get_last_n_samples(&self, duration_ms: u64) -> Vec<f32> {
@@ -327,6 +398,31 @@ impl RingBuffer {
 
         result
     }
+
+    /// Discard all buffered audio
+    pub fn clear(&mut self) {
+        self.data.fill(0.0);
+        self.write_pos = 0;
+        self.filled = 0;
+    }
+}
+
+/// Write mono samples to a 16-bit PCM WAV file
+pub fn write_wav(path: &Path, samples: &[f32], sample_rate: u32) -> Result<()> {
+    let spec = WavSpec {
+        channels: 1,
+        sample_rate,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+
+    let mut writer = WavWriter::create(path, spec)?;
+    for &sample in samples {
+        let sample_i16 = (sample.clamp(-1.0, 1.0) * 32767.0) as i16;
+        writer.write_sample(sample_i16)?;
+    }
+    writer.finalize()?;
+    Ok(())
 }
 
 /// Calculate RMS (Root Mean Square) for voice activity detection
@@ -370,6 +466,53 @@ mod tests {
         assert_eq!(all_samples.len(), 10);
     }
 
+    #[test]
+    fn test_ring_fill_ratio() {
+        let mut buffer = RingBuffer::new(100);
+        assert_eq!(buffer.fill_ratio(), 0.0);
+
+        buffer.push_samples(&[0.1; 25]);
+        assert_eq!(buffer.fill_ratio(), 0.25);
+
+        buffer.push_samples(&[0.1; 200]);
+        assert_eq!(buffer.fill_ratio(), 1.0);
+
+        buffer.clear();
+        assert_eq!(buffer.fill_ratio(), 0.0);
+    }
+
+    #[test]
+    fn test_unknown_drop_policy_falls_back_to_drop_oldest() {
+        let config = AudioConfig {
+            drop_policy: "bogus".to_string(),
+            ..AudioConfig::default()
+        };
+        let capture = AudioCapture::new(config).unwrap();
+        assert_eq!(capture.chunk_sender.policy(), DropPolicy::DropOldest);
+    }
+
+    #[test]
+    fn test_dump_last_writes_wav() {
+        let capture = AudioCapture::new(AudioConfig::default()).unwrap();
+        let ramp: Vec<f32> = (0..9600).map(|i| (i % 100) as f32 / 100.0).collect();
+        capture.ring_buffer.lock().unwrap().push_samples(&ramp);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("dump.wav");
+        capture.dump_last(100, &path).unwrap();
+
+        let mut reader = hound::WavReader::open(&path).unwrap();
+        assert_eq!(reader.spec().sample_rate, capture.sample_rate());
+        assert_eq!(reader.spec().channels, 1);
+
+        // 100ms at 48kHz, the newest samples
+        let samples: Vec<i16> = reader.samples::<i16>().map(|s| s.unwrap()).collect();
+        assert_eq!(samples.len(), 4800);
+        for (written, &original) in samples.iter().zip(&ramp[9600 - 4800..]) {
+            assert_eq!(*written, (original * 32767.0) as i16);
+        }
+    }
+
     #[test]
     fn test_sample_conversion() {
         // Test i16 to f32 conversion
@@ -6,6 +6,7 @@ use super::traits::*;
 use crate::error::Result;
 use async_channel::{unbounded, Receiver, Sender};
 use async_trait::async_trait;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
 
 /// Mock audio capture for testing
@@ -136,15 +137,22 @@ impl WakeWordDetectorInterface for MockWakeWordDetector {
 /// Mock speech-to-text for testing
 pub struct MockSpeechToText {
     transcriptions: Arc<Mutex<Vec<String>>>,
+    calls: Arc<AtomicUsize>,
 }
 
 impl MockSpeechToText {
     pub fn new() -> Self {
         Self {
             transcriptions: Arc::new(Mutex::new(Vec::new())),
+            calls: Arc::new(AtomicUsize::new(0)),
         }
     }
 
+    /// Number of times `transcribe` was called
+    pub fn call_count(&self) -> usize {
+        self.calls.load(Ordering::SeqCst)
+    }
+
     /// Queue a transcription result
     pub fn queue_transcription(&self, text: String) {
         self.transcriptions.lock().unwrap().push(text);
@@ -154,6 +162,7 @@ impl MockSpeechToText {
 #[async_trait]
 impl SpeechToTextInterface for MockSpeechToText {
     async fn transcribe(&self, _audio: &[f32]) -> Result<String> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
         let mut transcriptions = self.transcriptions.lock().unwrap();
         if transcriptions.is_empty() {
             Ok("default transcription".to_string())
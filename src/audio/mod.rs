@@ -7,11 +7,13 @@
 //! - Wake word detection (Porcupine + energy)
 //! - Speech-to-text (Whisper + simulation)
 
+pub mod backpressure;
 pub mod capture;
 pub mod device;
 pub mod dsp;
 pub mod mocks;
 pub mod processor;
+pub mod registry;
 pub mod ring_buffer;
 pub mod speech_to_text;
 pub mod traits;
@@ -19,22 +21,28 @@ pub mod vad;
 pub mod wake_word;
 
 // Re-export commonly used types
-pub use capture::AudioCapture;
+pub use backpressure::{ChunkSender, DropPolicy};
+pub use capture::{write_wav, AudioCapture};
 pub use device::{AudioDeviceInfo, AudioDeviceManager};
 pub use dsp::{AudioResampler, AutomaticGainControl, DspProcessor, NoiseSuppressor};
 pub use processor::AudioProcessor;
+pub use registry::SttRegistry;
 pub use ring_buffer::LockFreeRingBuffer;
-pub use speech_to_text::SpeechToText;
+pub use speech_to_text::{SpeechToText, SttEngine, Transcript, WordTiming};
 pub use traits::*;
-pub use vad::{VadEngine, VoiceActivityDetector};
+pub use vad::{SpeechGate, VadEngine, VoiceActivityDetector};
 pub use wake_word::{WakeWordDetector, WakeWordEngine};
 
 use crate::config::{AudioConfig, BrainConfig};
 use crate::error::Result;
 use crate::events::EventBus;
-use std::path::Path;
+use crate::metrics::Metrics;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use tracing::info;
+use std::time::Duration;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
 
 /// Generic audio system coordinator with dependency injection
 pub struct AudioSystem<C, W, S, P>
@@ -49,6 +57,18 @@ where
     stt: S,
     processor: P,
     event_bus: Option<Arc<EventBus>>,
+    /// Shared flag that suspends wake word detection while set
+    paused: Arc<AtomicBool>,
+    /// Set while paused so audio heard during the pause is discarded
+    discard_on_resume: bool,
+    /// Factories and config used by `set_stt_engine`
+    stt_registry: Option<(Arc<SttRegistry>, BrainConfig)>,
+    /// Utterance being handled, minted when the wake word is detected
+    correlation_id: Option<Uuid>,
+    /// Skips STT for clips without detected speech
+    speech_gate: Option<SpeechGate>,
+    /// Where to save command audio STT fails on
+    error_dump_dir: Option<PathBuf>,
 }
 
 impl<C, W, S, P> AudioSystem<C, W, S, P>
@@ -66,15 +86,44 @@ where
             stt,
             processor,
             event_bus: None,
+            paused: Arc::new(AtomicBool::new(false)),
+            discard_on_resume: false,
+            stt_registry: None,
+            correlation_id: None,
+            speech_gate: None,
+            error_dump_dir: None,
         }
     }
 
+    /// Share a pause flag (see `LunaRuntime::pause_flag`)
+    pub fn with_pause_flag(mut self, paused: Arc<AtomicBool>) -> Self {
+        self.paused = paused;
+        self
+    }
+
+    /// Check if wake word detection is paused
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::SeqCst)
+    }
+
     /// Add event bus for publishing events
     pub fn with_event_bus(mut self, event_bus: Arc<EventBus>) -> Self {
         self.event_bus = Some(event_bus);
         self
     }
 
+    /// Only transcribe command audio the VAD detects speech in
+    pub fn with_speech_gate(mut self, gate: SpeechGate) -> Self {
+        self.speech_gate = Some(gate);
+        self
+    }
+
+    /// Save command audio to `dir` whenever transcription fails
+    pub fn with_error_dump_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.error_dump_dir = Some(dir.into());
+        self
+    }
+
     /// Start listening for audio
     pub fn start_listening(&mut self) -> Result<()> {
         self.capture.start()
@@ -83,23 +132,41 @@ where
     /// Wait for wake word detection
     ///
     /// Continuously monitors audio buffer for wake word.
-    /// Returns `true` when wake word is detected.
+    /// Returns `true` when wake word is detected, or `false` while paused.
+    /// Capture keeps running during a pause, but a wake word spoken then is
+    /// discarded rather than detected after resuming.
     pub async fn wait_for_wake_word(&mut self) -> Result<bool> {
         loop {
+            if self.is_paused() {
+                self.discard_on_resume = true;
+                tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+                return Ok(false);
+            }
+
             // Get last 1 second of audio from ring buffer
             let ring_buffer = self.capture.get_ring_buffer();
+            if std::mem::take(&mut self.discard_on_resume) {
+                ring_buffer.lock().unwrap().clear();
+            }
             let buffer = {
                 let buf = ring_buffer.lock().unwrap();
                 buf.get_last_n_samples(1000)
             };
 
             if let Some(_keyword_idx) = self.wake_word.detect(&buffer).await? {
+                // Every event for this utterance shares one ID
+                let correlation_id = Uuid::new_v4();
+                self.correlation_id = Some(correlation_id);
+
                 // Publish event if event bus is configured
                 if let Some(ref bus) = self.event_bus {
-                    bus.publish(crate::events::LunaEvent::WakeWordDetected {
-                        keyword: "luna".to_string(),
-                        confidence: 0.95,
-                    })
+                    bus.publish_with_correlation(
+                        crate::events::LunaEvent::WakeWordDetected {
+                            keyword: "luna".to_string(),
+                            confidence: 0.95,
+                        },
+                        correlation_id,
+                    )
                     .await;
                 }
                 return Ok(true);
@@ -120,6 +187,15 @@ where
         self.stt.is_simulated()
     }
 
+    /// Correlation ID of the utterance being handled
+    ///
+    /// Minted on each wake word detection; pass it to
+    /// `Brain::process_async_with_correlation` to trace the utterance through
+    /// execution.
+    pub fn correlation_id(&self) -> Option<Uuid> {
+        self.correlation_id
+    }
+
     /// Get reference to event bus (for testing)
     pub fn event_bus(&self) -> &Option<Arc<EventBus>> {
         &self.event_bus
@@ -175,30 +251,99 @@ where
             return Ok(String::new());
         }
 
+        if let Some(gate) = self.speech_gate {
+            if !gate.has_speech(&audio, self.stt.sample_rate())? {
+                debug!(
+                    "No speech in {} captured samples, skipping STT",
+                    audio.len()
+                );
+                return Ok(String::new());
+            }
+        }
+
         // 2. Process audio (noise gate + normalize)
         let mut processed = audio.clone();
         self.processor.apply_noise_gate(&mut processed);
         self.processor.normalize(&mut processed);
 
         // 3. Transcribe
-        let text = self.stt.transcribe(&processed).await?;
+        let transcript = match self.stt.transcribe_detailed(&processed).await {
+            Ok(transcript) => transcript,
+            Err(e) => {
+                self.dump_failed_audio(&processed);
+                return Err(e);
+            }
+        };
 
         // 4. Publish event if event bus is configured
         if let Some(ref bus) = self.event_bus {
-            bus.publish(crate::events::LunaEvent::CommandTranscribed {
-                text: text.clone(),
-                confidence: 1.0, // Best effort confidence; STT currently doesn't provide this
-            })
-            .await;
+            let event = crate::events::LunaEvent::CommandTranscribed {
+                text: transcript.text.clone(),
+                confidence: transcript.avg_confidence,
+            };
+            match self.correlation_id {
+                Some(correlation_id) => bus.publish_with_correlation(event, correlation_id).await,
+                None => bus.publish(event).await,
+            }
         }
 
-        Ok(text)
+        Ok(transcript.text)
+    }
+
+    /// Save audio STT failed on, if an error dump directory is set
+    fn dump_failed_audio(&self, audio: &[f32]) {
+        let Some(ref dir) = self.error_dump_dir else {
+            return;
+        };
+
+        let name = format!(
+            "stt-error-{}.wav",
+            chrono::Local::now().format("%Y%m%d-%H%M%S%.3f")
+        );
+        let path = dir.join(name);
+        if let Err(e) = std::fs::create_dir_all(dir) {
+            warn!("Failed to create {}: {}", dir.display(), e);
+            return;
+        }
+
+        match write_wav(&path, audio, self.stt.sample_rate()) {
+            Ok(()) => info!("Saved audio STT failed on to {}", path.display()),
+            Err(e) => warn!("Failed to save audio to {}: {}", path.display(), e),
+        }
+    }
+}
+
+/// Audio system whose recognizer can be swapped while running
+impl<C, W, P> AudioSystem<C, W, Box<dyn SpeechToTextInterface>, P>
+where
+    C: AudioCaptureInterface,
+    W: WakeWordDetectorInterface,
+    P: AudioProcessorInterface,
+{
+    /// Look up engines for `set_stt_engine` in `registry`
+    pub fn with_stt_registry(mut self, registry: Arc<SttRegistry>, config: BrainConfig) -> Self {
+        self.stt_registry = Some((registry, config));
+        self
+    }
+
+    /// Rebuild the speech-to-text engine from the registry
+    ///
+    /// The current recognizer stays active if the new one fails to build.
+    pub fn set_stt_engine(&mut self, name: &str) -> Result<()> {
+        let (registry, config) = self
+            .stt_registry
+            .as_ref()
+            .ok_or_else(|| crate::config_error!("No STT registry attached"))?;
+        self.stt = registry.create(name, config)?;
+
+        info!("Speech-to-text engine switched to {}", name);
+        Ok(())
     }
 }
 
 /// Type alias for production audio system
 pub type ProductionAudioSystem =
-    AudioSystem<AudioCapture, WakeWordDetector, SpeechToText, AudioProcessor>;
+    AudioSystem<AudioCapture, WakeWordDetector, Box<dyn SpeechToTextInterface>, AudioProcessor>;
 
 /// Factory for creating production audio system
 impl ProductionAudioSystem {
@@ -208,12 +353,23 @@ impl ProductionAudioSystem {
 
         let capture = AudioCapture::new(audio_config.clone())?;
 
-        let wake_word = WakeWordDetector::new(
+        let mut wake_word = WakeWordDetector::new(
             audio_config.wake_words.clone(),
             brain_config.wake_word_sensitivity,
-        )?;
+        )?
+        .with_cooldown(Duration::from_millis(audio_config.wake_word_cooldown_ms));
+        // `wait_for_wake_word` polls every 100ms
+        if let Some(window) = audio_config.vad_calibration_frames(100) {
+            wake_word =
+                wake_word.with_adaptive_threshold(audio_config.vad_noise_multiplier, window);
+        }
 
-        let stt = SpeechToText::new(Path::new(&brain_config.whisper_model_path))?;
+        let registry = Arc::new(SttRegistry::with_builtin());
+        let stt_engine = brain_config.stt_engine.parse().unwrap_or_else(|e| {
+            warn!("{}, using simulated speech-to-text", e);
+            SttEngine::Simulate
+        });
+        let stt = registry.create(stt_engine.name(), brain_config)?;
 
         let processor = AudioProcessor::new(
             audio_config.silence_threshold * 0.5,
@@ -222,7 +378,22 @@ impl ProductionAudioSystem {
 
         info!("✅ Audio system initialized");
 
-        Ok(Self::new(capture, wake_word, stt, processor))
+        let mut system = Self::new(capture, wake_word, stt, processor)
+            .with_stt_registry(registry, brain_config.clone())
+            .with_speech_gate(SpeechGate::from_config(audio_config));
+        if !audio_config.stt_error_dump_dir.is_empty() {
+            system = system.with_error_dump_dir(&audio_config.stt_error_dump_dir);
+        }
+
+        Ok(system)
+    }
+
+    /// Report capture drops and ring fill ratio to metrics
+    ///
+    /// Takes effect the next time capture starts.
+    pub fn with_capture_metrics(mut self, metrics: Arc<Metrics>) -> Self {
+        self.capture = self.capture.with_metrics(metrics);
+        self
     }
 }
 
@@ -271,4 +442,85 @@ mod tests {
 
         handle.abort();
     }
+
+    #[tokio::test]
+    async fn test_set_stt_engine_swaps_at_runtime() {
+        let mut registry = SttRegistry::new();
+        registry.register("mock", |_| {
+            let stt = MockSpeechToText::new();
+            stt.queue_transcription("from the swapped engine".to_string());
+            Ok(Box::new(stt))
+        });
+
+        let stt: Box<dyn SpeechToTextInterface> = Box::new(MockSpeechToText::new());
+        let mut system = AudioSystem::new(
+            MockAudioCapture::new(),
+            MockWakeWordDetector::new(),
+            stt,
+            MockAudioProcessor::new(),
+        );
+        assert!(system.set_stt_engine("mock").is_err(), "no registry yet");
+
+        let mut system = system.with_stt_registry(Arc::new(registry), BrainConfig::default());
+        assert_eq!(
+            system.stt.transcribe(&[0.0; 160]).await.unwrap(),
+            "default transcription"
+        );
+
+        system.set_stt_engine("mock").unwrap();
+        assert_eq!(
+            system.stt.transcribe(&[0.0; 160]).await.unwrap(),
+            "from the swapped engine"
+        );
+        assert!(system.set_stt_engine("missing").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_silence_skips_stt() {
+        let capture = MockAudioCapture::new();
+        capture.add_samples(vec![0.0; 16000]);
+        let gate = SpeechGate {
+            engine: VadEngine::Rms,
+            aggressiveness: 2,
+            min_speech_ms: 100,
+        };
+        let mut system = AudioSystem::new(
+            capture,
+            MockWakeWordDetector::new(),
+            MockSpeechToText::new(),
+            MockAudioProcessor::new(),
+        )
+        .with_speech_gate(gate);
+        system.start_listening().unwrap();
+
+        assert_eq!(system.listen_and_transcribe(1).await.unwrap(), "");
+        assert_eq!(system.stt.call_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_wake_word_suppressed_while_paused() {
+        let capture = MockAudioCapture::new();
+        let wake_word = MockWakeWordDetector::new();
+        let stt = MockSpeechToText::new();
+        let processor = MockAudioProcessor::new();
+
+        let paused = Arc::new(AtomicBool::new(true));
+        let mut system =
+            AudioSystem::new(capture, wake_word, stt, processor).with_pause_flag(paused.clone());
+
+        // Audio keeps arriving and a wake word is pending during the pause
+        system.wake_word.queue_detection(true);
+        #[allow(deprecated)]
+        let ring_buffer = system.capture.get_ring_buffer().clone();
+        ring_buffer.lock().unwrap().push_samples(&[0.5; 4800]);
+
+        assert!(!system.wait_for_wake_word().await.unwrap());
+        assert!(!system.wait_for_wake_word().await.unwrap());
+
+        // Audio captured during the pause is dropped on resume
+        paused.store(false, Ordering::SeqCst);
+        assert!(system.wait_for_wake_word().await.unwrap());
+        let samples = ring_buffer.lock().unwrap().get_last_n_samples(100);
+        assert!(samples.iter().all(|&s| s == 0.0));
+    }
 }
@@ -0,0 +1,100 @@
+//! Speech-to-text engine registry
+//!
+//! Maps engine names to factories so the recognizer can be rebuilt while
+//! running.
+
+use crate::audio::speech_to_text::{SpeechToText, SttEngine};
+use crate::audio::traits::SpeechToTextInterface;
+use crate::config::BrainConfig;
+use crate::config_error;
+use crate::error::Result;
+use std::collections::BTreeMap;
+
+/// Builds a recognizer from the brain config
+pub type SttFactory =
+    Box<dyn Fn(&BrainConfig) -> Result<Box<dyn SpeechToTextInterface>> + Send + Sync>;
+
+/// Named speech-to-text factories
+#[derive(Default)]
+pub struct SttRegistry {
+    factories: BTreeMap<String, SttFactory>,
+}
+
+impl SttRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a registry with the Whisper, Vosk and simulated engines
+    pub fn with_builtin() -> Self {
+        let mut registry = Self::new();
+        for engine in [SttEngine::Whisper, SttEngine::Vosk, SttEngine::Simulate] {
+            registry.register(engine.name(), move |config| {
+                let model_path = match engine {
+                    SttEngine::Vosk => &config.vosk_model_path,
+                    SttEngine::Whisper | SttEngine::Simulate => &config.whisper_model_path,
+                };
+                Ok(Box::new(SpeechToText::new_with_engine(engine, model_path)?))
+            });
+        }
+        registry
+    }
+
+    /// Register a factory under `name`, replacing any existing one
+    pub fn register<F>(&mut self, name: &str, factory: F)
+    where
+        F: Fn(&BrainConfig) -> Result<Box<dyn SpeechToTextInterface>> + Send + Sync + 'static,
+    {
+        self.factories
+            .insert(name.to_lowercase(), Box::new(factory));
+    }
+
+    /// Build the recognizer registered under `name`
+    pub fn create(
+        &self,
+        name: &str,
+        config: &BrainConfig,
+    ) -> Result<Box<dyn SpeechToTextInterface>> {
+        let factory = self.factories.get(&name.to_lowercase()).ok_or_else(|| {
+            config_error!(
+                "Unknown STT engine '{}' (available: {})",
+                name,
+                self.names().collect::<Vec<_>>().join(", ")
+            )
+        })?;
+        factory(config)
+    }
+
+    /// Check if an engine is registered
+    pub fn contains(&self, name: &str) -> bool {
+        self.factories.contains_key(&name.to_lowercase())
+    }
+
+    /// Registered engine names, sorted
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.factories.keys().map(String::as_str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_builtin_engines() {
+        let registry = SttRegistry::with_builtin();
+        assert_eq!(
+            registry.names().collect::<Vec<_>>(),
+            ["simulate", "vosk", "whisper"]
+        );
+
+        let stt = registry
+            .create("Simulate", &BrainConfig::default())
+            .unwrap();
+        assert!(stt.is_simulated());
+        assert!(registry
+            .create("deepspeech", &BrainConfig::default())
+            .is_err());
+    }
+}
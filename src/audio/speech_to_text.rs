@@ -2,12 +2,134 @@
 //!
 //! Converts audio to text. Stub implementation returns simulated transcriptions.
 //! Can be upgraded to use Whisper AI when models are available.
+//! A Vosk backend is available behind the `vosk` feature.
 
 use super::traits::SpeechToTextInterface;
-use crate::error::Result;
+use crate::error::{LunaError, Result};
 use async_trait::async_trait;
+use regex::Regex;
 use std::path::Path;
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
+
+#[cfg(feature = "vosk")]
+use vosk::{Model, Recognizer};
+
+/// Phrases Whisper is known to produce on silence or noise
+///
+/// Matched case-insensitively against the whole transcript, ignoring
+/// surrounding punctuation.
+pub const DEFAULT_HALLUCINATIONS: &[&str] = &[
+    r"thanks? (?:you )?for watching",
+    r"thank you(?: (?:so|very) much)?",
+    r"(?:please )?(?:like and )?subscribe(?: to (?:my|the) channel)?",
+    r"subtitles by .*",
+    r"you",
+    r"bye",
+    r"\[(?:music|blank_audio|silence|inaudible)\]",
+    r"\((?:music|silence)\)",
+    r"♪+",
+];
+
+/// Timing and confidence for a single recognized word
+#[derive(Debug, Clone, PartialEq)]
+pub struct WordTiming {
+    /// Recognized word
+    pub word: String,
+    /// Start offset in milliseconds
+    pub start_ms: u64,
+    /// End offset in milliseconds
+    pub end_ms: u64,
+    /// Recognition confidence (0.0 - 1.0)
+    pub confidence: f32,
+}
+
+/// Transcription result with per-word detail
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Transcript {
+    /// Full transcribed text
+    pub text: String,
+    /// Per-word timings (may be empty if the engine doesn't provide them)
+    pub words: Vec<WordTiming>,
+    /// Average word confidence (0.0 - 1.0)
+    pub avg_confidence: f32,
+}
+
+impl Transcript {
+    /// Build a transcript from recognized words, averaging their confidence
+    pub fn from_words(text: String, words: Vec<WordTiming>) -> Self {
+        let avg_confidence = if words.is_empty() {
+            0.0
+        } else {
+            words.iter().map(|w| w.confidence).sum::<f32>() / words.len() as f32
+        };
+
+        Self {
+            text,
+            words,
+            avg_confidence,
+        }
+    }
+
+    /// Build a transcript for text without engine-provided timing
+    ///
+    /// Words are spread evenly across `duration_ms` with the given confidence.
+    pub fn from_text(text: String, duration_ms: u64, confidence: f32) -> Self {
+        let tokens: Vec<&str> = text.split_whitespace().collect();
+        let slot = duration_ms / tokens.len().max(1) as u64;
+
+        let words = tokens
+            .iter()
+            .enumerate()
+            .map(|(i, word)| WordTiming {
+                word: word.to_string(),
+                start_ms: i as u64 * slot,
+                end_ms: (i as u64 + 1) * slot,
+                confidence,
+            })
+            .collect();
+
+        Self::from_words(text, words)
+    }
+}
+
+/// Speech-to-text engine type
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SttEngine {
+    /// Whisper (model file)
+    Whisper,
+    /// Vosk (model directory, feature-gated)
+    Vosk,
+    /// Simulated transcriptions
+    Simulate,
+}
+
+/// Parses the `brain.stt_engine` config value
+impl std::str::FromStr for SttEngine {
+    type Err = LunaError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "whisper" => Ok(SttEngine::Whisper),
+            "vosk" => Ok(SttEngine::Vosk),
+            "simulate" => Ok(SttEngine::Simulate),
+            _ => Err(LunaError::InvalidParameter(format!(
+                "Unknown STT engine: {}",
+                s
+            ))),
+        }
+    }
+}
+
+impl SttEngine {
+    /// Name used in config and the STT registry
+    pub fn name(&self) -> &'static str {
+        match self {
+            SttEngine::Whisper => "whisper",
+            SttEngine::Vosk => "vosk",
+            SttEngine::Simulate => "simulate",
+        }
+    }
+}
 
 /// Speech-to-text engine (stub implementation)
 ///
@@ -17,8 +139,16 @@ use tracing::{debug, info};
 /// whisper-rs = { version = "0.11", features = ["metal"] }
 /// ```
 pub struct SpeechToText {
+    engine: SttEngine,
     model_path: String,
     simulated_mode: bool,
+    /// Transcripts matching any of these are discarded
+    hallucination_filter: Vec<Regex>,
+    /// Only discard matches averaging below this confidence
+    hallucination_confidence: Option<f32>,
+
+    #[cfg(feature = "vosk")]
+    vosk_model: Option<Model>,
 }
 
 impl SpeechToText {
@@ -49,8 +179,86 @@ impl SpeechToText {
         }
 
         Ok(Self {
+            engine: SttEngine::Whisper,
             model_path: model_path_str,
             simulated_mode,
+            hallucination_filter: compile_filter(DEFAULT_HALLUCINATIONS),
+            hallucination_confidence: None,
+            #[cfg(feature = "vosk")]
+            vosk_model: None,
+        })
+    }
+
+    /// Replace the phrases discarded as hallucinations
+    ///
+    /// Each entry is a regex matched against the whole transcript; invalid
+    /// ones are skipped with a warning. An empty list disables filtering.
+    pub fn with_hallucination_filter(mut self, patterns: Vec<String>) -> Self {
+        self.hallucination_filter = compile_filter(&patterns);
+        self
+    }
+
+    /// Only discard filtered phrases recognized with less than `confidence`
+    ///
+    /// Lets a confidently spoken "thank you" through once the engine reports
+    /// word confidences.
+    pub fn with_hallucination_confidence(mut self, confidence: f32) -> Self {
+        self.hallucination_confidence = Some(confidence);
+        self
+    }
+
+    /// Create a speech-to-text instance for the given engine
+    ///
+    /// Vosk falls back to simulated mode (with a warning) when the feature is
+    /// disabled or the model cannot be loaded.
+    pub fn new_with_engine<P: AsRef<Path>>(engine: SttEngine, model_path: P) -> Result<Self> {
+        match engine {
+            SttEngine::Vosk => Self::new_vosk(model_path),
+            SttEngine::Whisper | SttEngine::Simulate => Self::new(model_path),
+        }
+    }
+
+    /// Create Vosk-based recognizer
+    #[cfg(feature = "vosk")]
+    fn new_vosk<P: AsRef<Path>>(model_path: P) -> Result<Self> {
+        let model_path_str = model_path.as_ref().display().to_string();
+
+        let vosk_model = if model_path.as_ref().exists() {
+            Model::new(model_path_str.as_str())
+        } else {
+            None
+        };
+
+        if vosk_model.is_none() {
+            warn!("Vosk model not found at: {}", model_path_str);
+            warn!("Speech-to-text falling back to simulated mode");
+            info!("   Download a model from https://alphacephei.com/vosk/models");
+        } else {
+            info!("✅ Speech-to-text initialized (Vosk)");
+            info!("   Model: {}", model_path_str);
+        }
+
+        Ok(Self {
+            engine: SttEngine::Vosk,
+            model_path: model_path_str,
+            simulated_mode: vosk_model.is_none(),
+            hallucination_filter: compile_filter(DEFAULT_HALLUCINATIONS),
+            hallucination_confidence: None,
+            vosk_model,
+        })
+    }
+
+    /// Vosk requested without the feature compiled in
+    #[cfg(not(feature = "vosk"))]
+    fn new_vosk<P: AsRef<Path>>(model_path: P) -> Result<Self> {
+        warn!("Vosk not available (feature disabled), falling back to simulated mode");
+
+        Ok(Self {
+            engine: SttEngine::Vosk,
+            model_path: model_path.as_ref().display().to_string(),
+            simulated_mode: true,
+            hallucination_filter: compile_filter(DEFAULT_HALLUCINATIONS),
+            hallucination_confidence: None,
         })
     }
 
@@ -66,29 +274,103 @@ impl SpeechToText {
     /// Currently returns simulated transcriptions based on audio characteristics.
     /// When Whisper is integrated, this will return actual transcriptions.
     pub async fn transcribe(&self, audio: &[f32]) -> Result<String> {
+        Ok(self.transcribe_detailed(audio).await?.text)
+    }
+
+    /// Transcribe audio with per-word timing and confidence
+    ///
+    /// Simulated transcriptions report full confidence with evenly spaced words.
+    pub async fn transcribe_detailed(&self, audio: &[f32]) -> Result<Transcript> {
         if audio.is_empty() {
-            return Ok(String::new());
+            return Ok(Transcript::default());
         }
 
         debug!("Transcribing {} samples", audio.len());
         let start = std::time::Instant::now();
+        let duration_ms = audio.len() as u64 * 1000 / 16000;
 
-        let text = if self.simulated_mode {
+        let transcript = if self.simulated_mode {
             // Simulated transcription based on audio characteristics
-            self.simulate_transcription(audio)
+            Transcript::from_text(self.simulate_transcription(audio), duration_ms, 1.0)
         } else {
-            // TODO: Actual Whisper transcription when model is available
-            self.simulate_transcription(audio)
+            match self.engine {
+                #[cfg(feature = "vosk")]
+                SttEngine::Vosk => self.transcribe_vosk(audio)?,
+                // TODO: Actual Whisper transcription when model is available
+                _ => Transcript::from_text(self.simulate_transcription(audio), duration_ms, 1.0),
+            }
         };
 
         let duration = start.elapsed();
         info!(
-            "💬 Transcribed in {:.2}s: \"{}\"",
+            "💬 Transcribed in {:.2}s (confidence {:.2}): \"{}\"",
             duration.as_secs_f32(),
-            text
+            transcript.avg_confidence,
+            transcript.text
         );
 
-        Ok(text)
+        if self.is_hallucination(&transcript) {
+            info!("Discarding likely hallucination: \"{}\"", transcript.text);
+            return Ok(Transcript::default());
+        }
+
+        Ok(transcript)
+    }
+
+    /// Check whether a transcript is a known junk phrase
+    pub fn is_hallucination(&self, transcript: &Transcript) -> bool {
+        if self
+            .hallucination_confidence
+            .is_some_and(|max| transcript.avg_confidence >= max)
+        {
+            return false;
+        }
+
+        // Brackets are part of markers like "[BLANK_AUDIO]"
+        let is_punctuation = |c: char| c.is_ascii_punctuation() && !"[]()".contains(c);
+        let text = transcript.text.trim().trim_matches(is_punctuation).trim();
+        !text.is_empty() && self.hallucination_filter.iter().any(|re| re.is_match(text))
+    }
+
+    /// Vosk-based transcription
+    #[cfg(feature = "vosk")]
+    fn transcribe_vosk(&self, audio: &[f32]) -> Result<Transcript> {
+        let model = self.vosk_model.as_ref().ok_or_else(|| {
+            LunaError::SpeechRecognition("Vosk model not loaded".to_string())
+        })?;
+
+        let mut recognizer = Recognizer::new(model, 16000.0).ok_or_else(|| {
+            LunaError::SpeechRecognition("Failed to create Vosk recognizer".to_string())
+        })?;
+        recognizer.set_words(true);
+
+        // Vosk expects i16 samples
+        let pcm: Vec<i16> = audio
+            .iter()
+            .map(|&s| (s.clamp(-1.0, 1.0) * 32767.0) as i16)
+            .collect();
+
+        recognizer.accept_waveform(&pcm).map_err(|e| {
+            LunaError::SpeechRecognition(format!("Vosk decoding failed: {:?}", e))
+        })?;
+
+        let Some(result) = recognizer.final_result().single() else {
+            return Ok(Transcript::default());
+        };
+
+        // Vosk reports word offsets in seconds
+        let words = result
+            .result
+            .iter()
+            .map(|w| WordTiming {
+                word: w.word.to_string(),
+                start_ms: (w.start * 1000.0) as u64,
+                end_ms: (w.end * 1000.0) as u64,
+                confidence: w.conf,
+            })
+            .collect();
+
+        Ok(Transcript::from_words(result.text.trim().to_string(), words))
     }
 
     /// Simulate transcription for testing
@@ -135,6 +417,36 @@ impl SpeechToText {
     pub fn is_simulated(&self) -> bool {
         self.simulated_mode
     }
+
+    /// Get the engine this instance was created for
+    pub fn engine(&self) -> SttEngine {
+        self.engine
+    }
+
+    /// Get the configured model path
+    pub fn model_path(&self) -> &str {
+        &self.model_path
+    }
+}
+
+/// Compile denylist entries into whole-transcript, case-insensitive regexes
+fn compile_filter<S: AsRef<str>>(patterns: &[S]) -> Vec<Regex> {
+    patterns
+        .iter()
+        .filter_map(|pattern| {
+            let pattern = pattern.as_ref();
+            match Regex::new(&format!("(?i)^(?:{})$", pattern)) {
+                Ok(regex) => Some(regex),
+                Err(e) => {
+                    warn!(
+                        "Ignoring invalid hallucination pattern '{}': {}",
+                        pattern, e
+                    );
+                    None
+                }
+            }
+        })
+        .collect()
 }
 
 // Implement SpeechToTextInterface trait
@@ -145,6 +457,10 @@ impl SpeechToTextInterface for SpeechToText {
         self.transcribe(audio).await
     }
 
+    async fn transcribe_detailed(&self, audio: &[f32]) -> Result<Transcript> {
+        self.transcribe_detailed(audio).await
+    }
+
     fn is_simulated(&self) -> bool {
         self.simulated_mode
     }
@@ -187,6 +503,59 @@ mod tests {
         assert_eq!(text, "");
     }
 
+    #[tokio::test]
+    async fn test_transcribe_detailed() {
+        let stt = SpeechToText::new(PathBuf::from("models/whisper-base.bin")).unwrap();
+
+        let audio = vec![0.3; 32000]; // ~2 seconds
+        let transcript = stt.transcribe_detailed(&audio).await.unwrap();
+
+        assert_eq!(transcript.text, stt.transcribe(&audio).await.unwrap());
+        assert_eq!(
+            transcript.words.len(),
+            transcript.text.split_whitespace().count()
+        );
+        assert!(transcript.words.windows(2).all(|w| w[0].end_ms <= w[1].start_ms));
+        assert!(transcript.words.last().unwrap().end_ms <= 2000);
+    }
+
+    #[test]
+    fn test_transcript_average_confidence() {
+        let word = |w: &str, confidence| WordTiming {
+            word: w.to_string(),
+            start_ms: 0,
+            end_ms: 0,
+            confidence,
+        };
+        let transcript = Transcript::from_words(
+            "open chrome".to_string(),
+            vec![word("open", 0.9), word("chrome", 0.5)],
+        );
+        assert!((transcript.avg_confidence - 0.7).abs() < 1e-6);
+
+        assert_eq!(Transcript::default().avg_confidence, 0.0);
+    }
+
+    #[test]
+    fn test_stt_engine_from_str() {
+        assert_eq!("vosk".parse::<SttEngine>().unwrap(), SttEngine::Vosk);
+        assert_eq!("Whisper".parse::<SttEngine>().unwrap(), SttEngine::Whisper);
+        assert_eq!(
+            "simulate".parse::<SttEngine>().unwrap(),
+            SttEngine::Simulate
+        );
+        assert!("unknown".parse::<SttEngine>().is_err());
+    }
+
+    #[test]
+    fn test_vosk_missing_model_falls_back_to_simulation() {
+        let stt =
+            SpeechToText::new_with_engine(SttEngine::Vosk, PathBuf::from("models/missing-vosk"))
+                .unwrap();
+        assert_eq!(stt.engine(), SttEngine::Vosk);
+        assert!(stt.is_simulated());
+    }
+
     #[test]
     fn test_energy_calculation() {
         let stt = SpeechToText::new(PathBuf::from("models/whisper-base.bin")).unwrap();
@@ -194,4 +563,43 @@ mod tests {
         let energy = stt.calculate_energy(&audio);
         assert!(energy > 0.0);
     }
+
+    #[test]
+    fn test_hallucination_filter() {
+        let stt = SpeechToText::new(PathBuf::from("models/whisper-base.bin")).unwrap();
+        let transcript = |text: &str| Transcript::from_text(text.to_string(), 1000, 0.4);
+
+        for junk in [
+            "Thanks for watching!",
+            "thank you.",
+            "Please subscribe to my channel",
+            "[BLANK_AUDIO]",
+            "you",
+        ] {
+            assert!(stt.is_hallucination(&transcript(junk)), "{}", junk);
+        }
+        for command in ["open chrome", "thank you luna", "what time is it", ""] {
+            assert!(!stt.is_hallucination(&transcript(command)), "{}", command);
+        }
+
+        // Confident speech is trusted when gated on confidence
+        let stt = stt.with_hallucination_confidence(0.6);
+        assert!(stt.is_hallucination(&transcript("thank you")));
+        let confident = Transcript::from_text("thank you".to_string(), 1000, 0.9);
+        assert!(!stt.is_hallucination(&confident));
+    }
+
+    #[tokio::test]
+    async fn test_transcribe_discards_filtered_phrases() {
+        let stt = SpeechToText::new(PathBuf::from("models/whisper-base.bin"))
+            .unwrap()
+            .with_hallucination_filter(vec!["open chrome".to_string()]);
+
+        // Simulated as "open chrome" and "what time is it"
+        assert_eq!(stt.transcribe(&vec![0.2; 32000]).await.unwrap(), "");
+        assert_eq!(
+            stt.transcribe(&vec![0.1; 32000]).await.unwrap(),
+            "what time is it"
+        );
+    }
 }
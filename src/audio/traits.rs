@@ -2,6 +2,7 @@
 //!
 //! Enables dependency injection and mock implementations for testing.
 
+use super::speech_to_text::Transcript;
 use crate::error::Result;
 use async_channel::Receiver;
 use async_trait::async_trait;
@@ -59,6 +60,15 @@ pub trait SpeechToTextInterface: Send + Sync {
     /// Transcribe audio to text
     async fn transcribe(&self, audio: &[f32]) -> Result<String>;
 
+    /// Transcribe audio with per-word timing and confidence
+    ///
+    /// Engines without confidence data report the text with full confidence.
+    async fn transcribe_detailed(&self, audio: &[f32]) -> Result<Transcript> {
+        let text = self.transcribe(audio).await?;
+        let duration_ms = audio.len() as u64 * 1000 / self.sample_rate().max(1) as u64;
+        Ok(Transcript::from_text(text, duration_ms, 1.0))
+    }
+
     /// Check if running in simulated mode
     fn is_simulated(&self) -> bool;
 
@@ -66,6 +76,26 @@ pub trait SpeechToTextInterface: Send + Sync {
     fn sample_rate(&self) -> u32;
 }
 
+/// Boxed engines, so the recognizer can be chosen and swapped at runtime
+#[async_trait]
+impl<T: SpeechToTextInterface + ?Sized> SpeechToTextInterface for Box<T> {
+    async fn transcribe(&self, audio: &[f32]) -> Result<String> {
+        (**self).transcribe(audio).await
+    }
+
+    async fn transcribe_detailed(&self, audio: &[f32]) -> Result<Transcript> {
+        (**self).transcribe_detailed(audio).await
+    }
+
+    fn is_simulated(&self) -> bool {
+        (**self).is_simulated()
+    }
+
+    fn sample_rate(&self) -> u32 {
+        (**self).sample_rate()
+    }
+}
+
 /// Audio processor interface
 pub trait AudioProcessorInterface: Send + Sync {
     /// Apply noise gate to remove low-level noise
@@ -5,7 +5,9 @@
 //! - RMS-based (simple fallback)
 //! - Silero VAD (future: ML-based)
 
+use crate::config::AudioConfig;
 use crate::error::{LunaError, Result};
+use std::collections::VecDeque;
 
 #[cfg(feature = "webrtc-audio")]
 use webrtc_vad::{SampleRate, Vad, VadMode};
@@ -31,12 +33,85 @@ impl VadEngine {
     }
 }
 
+/// Lowest threshold adaptation settles on, so a silent room doesn't turn
+/// every click into speech
+const MIN_ADAPTIVE_THRESHOLD: f32 = 0.005;
+
+/// Speech threshold that follows the background noise level
+///
+/// The noise floor is the average RMS of the last `window` non-speech
+/// frames, and frames louder than floor × multiplier are speech. A level
+/// that stays above the threshold for a whole window is too long to be
+/// speech, so it becomes the new floor; this lets the threshold rise when a
+/// room gets louder.
+#[derive(Debug, Clone)]
+pub struct AdaptiveThreshold {
+    multiplier: f32,
+    window: usize,
+    /// Levels of recent non-speech frames
+    background: VecDeque<f32>,
+    /// Levels since the last non-speech frame
+    loud_streak: Vec<f32>,
+    floor: f32,
+}
+
+impl AdaptiveThreshold {
+    /// Start at `initial` and calibrate over `window` frames
+    pub fn new(initial: f32, multiplier: f32, window: usize) -> Self {
+        let multiplier = multiplier.max(1.0);
+        let window = window.max(1);
+
+        Self {
+            multiplier,
+            window,
+            background: VecDeque::with_capacity(window),
+            loud_streak: Vec::new(),
+            floor: initial / multiplier,
+        }
+    }
+
+    /// Current speech threshold
+    pub fn threshold(&self) -> f32 {
+        (self.floor * self.multiplier).max(MIN_ADAPTIVE_THRESHOLD)
+    }
+
+    /// Current background noise estimate
+    pub fn noise_floor(&self) -> f32 {
+        self.floor
+    }
+
+    /// Classify a frame's RMS level as speech and update the noise floor
+    pub fn update(&mut self, rms: f32) -> bool {
+        if rms > self.threshold() {
+            self.loud_streak.push(rms);
+            if self.loud_streak.len() < self.window {
+                return true;
+            }
+
+            // Sustained for a whole window: the background got louder
+            self.background.clear();
+            self.background.extend(self.loud_streak.drain(..));
+        } else {
+            self.loud_streak.clear();
+            self.background.push_back(rms);
+            if self.background.len() > self.window {
+                self.background.pop_front();
+            }
+        }
+
+        self.floor = self.background.iter().sum::<f32>() / self.background.len() as f32;
+        false
+    }
+}
+
 /// Voice activity detector
 pub struct VoiceActivityDetector {
     engine: VadEngine,
     #[cfg(feature = "webrtc-audio")]
     webrtc_vad: Option<Vad>,
     rms_threshold: f32,
+    /// Replaces `rms_threshold` once enabled
+    adaptive: Option<AdaptiveThreshold>,
     hangover_frames: usize,
     current_hangover: usize,
     was_speech: bool,
@@ -99,12 +174,27 @@ impl VoiceActivityDetector {
             #[cfg(feature = "webrtc-audio")]
             webrtc_vad,
             rms_threshold,
+            adaptive: None,
             hangover_frames,
             current_hangover: 0,
             was_speech: false,
         })
     }
 
+    /// Adapt the RMS threshold to background noise
+    ///
+    /// Starts from the aggressiveness threshold and calibrates over
+    /// `window_frames` frames. WebRTC VAD keeps its own model; this only
+    /// affects RMS detection.
+    pub fn with_adaptive_threshold(mut self, multiplier: f32, window_frames: usize) -> Self {
+        self.adaptive = Some(AdaptiveThreshold::new(
+            self.rms_threshold,
+            multiplier,
+            window_frames,
+        ));
+        self
+    }
+
     /// Detect speech in audio frame
     ///
     /// # Arguments
@@ -159,9 +249,32 @@ impl VoiceActivityDetector {
     }
 
     /// RMS-based speech detection (fallback)
-    fn detect_rms(&self, frame: &[f32]) -> bool {
+    fn detect_rms(&mut self, frame: &[f32]) -> bool {
         let rms = calculate_rms(frame);
-        rms > self.rms_threshold
+        match self.adaptive {
+            Some(ref mut adaptive) => adaptive.update(rms),
+            None => rms > self.rms_threshold,
+        }
+    }
+
+    /// Milliseconds of speech detected in a clip
+    ///
+    /// Scans `samples` in 10ms frames. Hangover frames after speech are not
+    /// counted. The detector is reset before and after.
+    pub fn speech_ms(&mut self, samples: &[f32], sample_rate: u32) -> Result<u32> {
+        let frame_len = (sample_rate / 100).max(1) as usize;
+        self.reset();
+
+        let mut speech_frames = 0;
+        for frame in samples.chunks_exact(frame_len) {
+            self.is_speech(frame)?;
+            if self.current_hangover == self.hangover_frames {
+                speech_frames += 1;
+            }
+        }
+
+        self.reset();
+        Ok(speech_frames * 10)
     }
 
     /// Check if we just transitioned from silence to speech
@@ -186,6 +299,42 @@ impl VoiceActivityDetector {
     }
 }
 
+/// Speech precheck for captured commands
+///
+/// Clips with less detected speech than `min_speech_ms` are not worth
+/// transcribing; Whisper tends to hallucinate text ("thank you") on silence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpeechGate {
+    /// VAD engine to use
+    pub engine: VadEngine,
+    /// VAD aggressiveness (0-3)
+    pub aggressiveness: u8,
+    /// Speech a clip needs to reach STT (0 = always transcribe)
+    pub min_speech_ms: u32,
+}
+
+impl SpeechGate {
+    /// Build the gate from the audio configuration
+    pub fn from_config(config: &AudioConfig) -> Self {
+        Self {
+            engine: VadEngine::from_str(&config.vad_engine),
+            aggressiveness: config.vad_aggressiveness,
+            min_speech_ms: config.min_speech_ms,
+        }
+    }
+
+    /// Check whether a clip contains enough speech to transcribe
+    pub fn has_speech(&self, samples: &[f32], sample_rate: u32) -> Result<bool> {
+        if self.min_speech_ms == 0 {
+            return Ok(true);
+        }
+
+        // A fresh detector per clip, since WebRTC's isn't `Send`
+        let mut vad = VoiceActivityDetector::new(self.engine, self.aggressiveness, sample_rate)?;
+        Ok(vad.speech_ms(samples, sample_rate)? >= self.min_speech_ms)
+    }
+}
+
 /// Calculate RMS (Root Mean Square) of audio samples
 fn calculate_rms(samples: &[f32]) -> f32 {
     if samples.is_empty() {
@@ -258,6 +407,64 @@ mod tests {
         assert_eq!(calculate_rms(&silent), 0.0);
     }
 
+    #[test]
+    fn test_speech_gate() {
+        let gate = SpeechGate {
+            engine: VadEngine::Rms,
+            aggressiveness: 2,
+            min_speech_ms: 100,
+        };
+
+        assert!(!gate.has_speech(&[0.0; 16000], 16000).unwrap());
+        assert!(!gate.has_speech(&[0.01; 16000], 16000).unwrap());
+        assert!(gate.has_speech(&[0.5; 16000], 16000).unwrap());
+
+        // A click shorter than the minimum, even with hangover
+        let mut click = vec![0.0; 16000];
+        click[..480].fill(0.5);
+        assert!(!gate.has_speech(&click, 16000).unwrap());
+    }
+
+    #[test]
+    fn test_adaptive_threshold_follows_background_noise() {
+        let mut adaptive = AdaptiveThreshold::new(0.03, 3.0, 50);
+        assert!((adaptive.threshold() - 0.03).abs() < 1e-6);
+
+        // A fan turns on: louder than the initial threshold, but sustained
+        for _ in 0..100 {
+            adaptive.update(0.05);
+        }
+        assert!((adaptive.noise_floor() - 0.05).abs() < 1e-6);
+        assert!(adaptive.threshold() > 0.1);
+        assert!(!adaptive.update(0.05));
+
+        // Speech over the fan is still detected
+        assert!(adaptive.update(0.4));
+
+        // The room quiets down again
+        for _ in 0..100 {
+            adaptive.update(0.001);
+        }
+        assert!(adaptive.threshold() < 0.01);
+        assert!(adaptive.update(0.02));
+    }
+
+    #[test]
+    fn test_adaptive_vad() {
+        let mut vad = VoiceActivityDetector::new(VadEngine::Rms, 2, 16000)
+            .unwrap()
+            .with_adaptive_threshold(3.0, 50);
+
+        // Noise above the fixed threshold stops counting as speech
+        let noise = vec![0.05; 160];
+        for _ in 0..100 {
+            let _ = vad.is_speech(&noise);
+        }
+        vad.reset();
+        assert!(!vad.is_speech(&noise).unwrap());
+        assert!(vad.is_speech(&[0.5; 160]).unwrap());
+    }
+
     #[cfg(feature = "webrtc-audio")]
     #[test]
     fn test_webrtc_vad() {
@@ -6,9 +6,12 @@
 //! - OpenWakeWord (future: ONNX-based)
 
 use super::traits::WakeWordDetectorInterface;
+use super::vad::AdaptiveThreshold;
 use crate::error::Result;
 use async_trait::async_trait;
-use tracing::{info, warn};
+use parking_lot::Mutex;
+use std::time::{Duration, Instant};
+use tracing::{debug, info, warn};
 
 #[cfg(feature = "porcupine")]
 use pv_porcupine::porcupine::{Porcupine, PorcupineBuilder};
@@ -40,6 +43,11 @@ pub struct WakeWordDetector {
     keywords: Vec<String>,
     sensitivity: f32,
     energy_threshold: f32,
+    /// Replaces `energy_threshold` once enabled
+    adaptive: Option<Mutex<AdaptiveThreshold>>,
+    /// Detections within this window after a hit are suppressed
+    cooldown: Duration,
+    last_detection: Mutex<Option<Instant>>,
 
     #[cfg(feature = "porcupine")]
     porcupine: Option<Porcupine>,
@@ -89,6 +97,9 @@ impl WakeWordDetector {
             keywords,
             sensitivity,
             energy_threshold: 0.1 * (1.0 - sensitivity),
+            adaptive: None,
+            cooldown: Duration::ZERO,
+            last_detection: Mutex::new(None),
             #[cfg(feature = "porcupine")]
             porcupine: None,
         })
@@ -119,6 +130,9 @@ impl WakeWordDetector {
             keywords,
             sensitivity,
             energy_threshold: 0.0, // Not used in Porcupine mode
+            adaptive: None,
+            cooldown: Duration::ZERO,
+            last_detection: Mutex::new(None),
             porcupine: Some(porcupine),
         })
     }
@@ -128,14 +142,52 @@ impl WakeWordDetector {
         Self::new_energy(keywords, sensitivity)
     }
 
+    /// Suppress repeat detections for `cooldown` after each positive detection
+    pub fn with_cooldown(mut self, cooldown: Duration) -> Self {
+        self.cooldown = cooldown;
+        self
+    }
+
+    /// Adapt the energy threshold to background noise
+    ///
+    /// Starts from the sensitivity threshold and calibrates over
+    /// `window` calls to `detect`. Only the energy engine uses it.
+    pub fn with_adaptive_threshold(mut self, multiplier: f32, window: usize) -> Self {
+        self.adaptive = Some(Mutex::new(AdaptiveThreshold::new(
+            self.energy_threshold,
+            multiplier,
+            window,
+        )));
+        self
+    }
+
     /// Detect wake word in audio buffer
     ///
-    /// Returns `Some(keyword_index)` if wake word detected, `None` otherwise
+    /// Returns `Some(keyword_index)` if wake word detected, `None` otherwise.
+    /// Always returns `None` while the post-detection cooldown is active.
     pub async fn detect(&self, audio_buffer: &[f32]) -> Result<Option<usize>> {
         if audio_buffer.is_empty() {
             return Ok(None);
         }
 
+        if let Some(last) = *self.last_detection.lock() {
+            if last.elapsed() < self.cooldown {
+                debug!("Wake word detection suppressed (cooldown)");
+                return Ok(None);
+            }
+        }
+
+        let detection = self.detect_with_engine(audio_buffer)?;
+
+        if detection.is_some() {
+            *self.last_detection.lock() = Some(Instant::now());
+        }
+
+        Ok(detection)
+    }
+
+    /// Run the configured engine without cooldown handling
+    fn detect_with_engine(&self, audio_buffer: &[f32]) -> Result<Option<usize>> {
         match self.engine {
             #[cfg(feature = "porcupine")]
             WakeWordEngine::Porcupine => self.detect_porcupine(audio_buffer),
@@ -152,8 +204,12 @@ impl WakeWordDetector {
     /// Energy-based detection
     fn detect_energy(&self, audio_buffer: &[f32]) -> Result<Option<usize>> {
         let energy = self.calculate_energy(audio_buffer);
+        let is_loud = match self.adaptive {
+            Some(ref adaptive) => adaptive.lock().update(energy),
+            None => energy > self.energy_threshold,
+        };
 
-        if energy > self.energy_threshold {
+        if is_loud {
             info!("🎤 Wake word detected (energy: {:.3})", energy);
             return Ok(Some(0)); // Return first keyword
         }
@@ -266,6 +322,28 @@ mod tests {
         assert!(result.is_none());
     }
 
+    #[tokio::test]
+    async fn test_wake_word_cooldown() {
+        let detector = WakeWordDetector::new(vec!["hey luna".to_string()], 0.5)
+            .unwrap()
+            .with_cooldown(Duration::from_millis(200));
+
+        // Continuous matching signal polled every 20ms, like the main loop does
+        let loud_audio = vec![0.5; 1000];
+        let mut detections = 0;
+        for _ in 0..5 {
+            if detector.detect(&loud_audio).await.unwrap().is_some() {
+                detections += 1;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        assert_eq!(detections, 1);
+
+        // After the cooldown expires the next hit is reported again
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert!(detector.detect(&loud_audio).await.unwrap().is_some());
+    }
+
     #[test]
     fn test_energy_calculation() {
         let detector = WakeWordDetector::default();
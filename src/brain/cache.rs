@@ -1,20 +1,51 @@
 //! LRU caching for brain system
 //!
 //! Caches parsed commands and task plans to reduce latency for repeated commands.
+//! Both caches are bounded by entry count and, optionally, expire entries
+//! after a TTL.
 
 use crate::brain::command_parser::ParsedCommand;
 use crate::brain::task_planner::TaskPlan;
 use lru::LruCache;
 use parking_lot::RwLock;
 use std::num::NonZeroUsize;
+use std::time::{Duration, Instant};
+
+/// Cache sizing and expiry settings
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CacheConfig {
+    /// Maximum entries per cache before least-recently-used eviction
+    pub max_entries: usize,
+
+    /// Entries older than this are treated as misses (`None` = never expire)
+    pub ttl: Option<Duration>,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            max_entries: 100,
+            ttl: None,
+        }
+    }
+}
+
+/// Cached value with its insertion time
+struct CacheEntry<T> {
+    value: T,
+    inserted_at: Instant,
+}
 
 /// Cache for parsed commands and task plans
 pub struct BrainCache {
     /// Cache for parsed commands (text -> ParsedCommand)
-    parse_cache: RwLock<LruCache<String, ParsedCommand>>,
+    parse_cache: RwLock<LruCache<String, CacheEntry<ParsedCommand>>>,
 
     /// Cache for task plans (text -> TaskPlan)
-    plan_cache: RwLock<LruCache<String, TaskPlan>>,
+    plan_cache: RwLock<LruCache<String, CacheEntry<TaskPlan>>>,
+
+    /// Time-to-live for entries
+    ttl: Option<Duration>,
 
     /// Hit/miss statistics
     stats: RwLock<CacheStats>,
@@ -27,6 +58,12 @@ pub struct CacheStats {
     pub parse_misses: usize,
     pub plan_hits: usize,
     pub plan_misses: usize,
+    /// Entries evicted because they outlived the TTL
+    pub expired: usize,
+    /// Current number of cached parses
+    pub parse_size: usize,
+    /// Current number of cached plans
+    pub plan_size: usize,
 }
 
 impl CacheStats {
@@ -57,51 +94,92 @@ impl BrainCache {
 
     /// Create a cache with specific capacities
     pub fn with_capacity(parse_capacity: usize, plan_capacity: usize) -> Self {
+        Self::build(parse_capacity, plan_capacity, None)
+    }
+
+    /// Create a cache bounded by `config.max_entries` with optional TTL
+    pub fn with_config(config: CacheConfig) -> Self {
+        Self::build(config.max_entries, config.max_entries, config.ttl)
+    }
+
+    fn build(parse_capacity: usize, plan_capacity: usize, ttl: Option<Duration>) -> Self {
+        let capacity = |n: usize| NonZeroUsize::new(n.max(1)).unwrap();
         Self {
-            parse_cache: RwLock::new(LruCache::new(NonZeroUsize::new(parse_capacity).unwrap())),
-            plan_cache: RwLock::new(LruCache::new(NonZeroUsize::new(plan_capacity).unwrap())),
+            parse_cache: RwLock::new(LruCache::new(capacity(parse_capacity))),
+            plan_cache: RwLock::new(LruCache::new(capacity(plan_capacity))),
+            ttl,
             stats: RwLock::new(CacheStats::default()),
         }
     }
 
+    /// Look up a key, lazily evicting it if it has expired
+    fn lookup<T: Clone>(
+        &self,
+        cache: &RwLock<LruCache<String, CacheEntry<T>>>,
+        key: &str,
+    ) -> Option<T> {
+        let mut cache = cache.write();
+        let entry = cache.get(key)?;
+        match self.ttl {
+            Some(ttl) if entry.inserted_at.elapsed() >= ttl => {
+                cache.pop(key);
+                self.stats.write().expired += 1;
+                None
+            }
+            _ => Some(entry.value.clone()),
+        }
+    }
+
     /// Get a parsed command from cache
     pub fn get_parsed(&self, text: &str) -> Option<ParsedCommand> {
         let normalized = Self::normalize_key(text);
-        let mut cache = self.parse_cache.write();
+        let result = self.lookup(&self.parse_cache, &normalized);
 
-        if let Some(parsed) = cache.get(&normalized) {
-            self.stats.write().parse_hits += 1;
-            Some(parsed.clone())
+        let mut stats = self.stats.write();
+        if result.is_some() {
+            stats.parse_hits += 1;
         } else {
-            self.stats.write().parse_misses += 1;
-            None
+            stats.parse_misses += 1;
         }
+        result
     }
 
     /// Put a parsed command into cache
     pub fn put_parsed(&self, text: &str, parsed: ParsedCommand) {
         let normalized = Self::normalize_key(text);
-        self.parse_cache.write().put(normalized, parsed);
+        self.parse_cache.write().put(
+            normalized,
+            CacheEntry {
+                value: parsed,
+                inserted_at: Instant::now(),
+            },
+        );
     }
 
     /// Get a task plan from cache
     pub fn get_plan(&self, text: &str) -> Option<TaskPlan> {
         let normalized = Self::normalize_key(text);
-        let mut cache = self.plan_cache.write();
+        let result = self.lookup(&self.plan_cache, &normalized);
 
-        if let Some(plan) = cache.get(&normalized) {
-            self.stats.write().plan_hits += 1;
-            Some(plan.clone())
+        let mut stats = self.stats.write();
+        if result.is_some() {
+            stats.plan_hits += 1;
         } else {
-            self.stats.write().plan_misses += 1;
-            None
+            stats.plan_misses += 1;
         }
+        result
     }
 
     /// Put a task plan into cache
     pub fn put_plan(&self, text: &str, plan: TaskPlan) {
         let normalized = Self::normalize_key(text);
-        self.plan_cache.write().put(normalized, plan);
+        self.plan_cache.write().put(
+            normalized,
+            CacheEntry {
+                value: plan,
+                inserted_at: Instant::now(),
+            },
+        );
     }
 
     /// Clear all caches (e.g., after grammar reload)
@@ -122,7 +200,11 @@ impl BrainCache {
 
     /// Get cache statistics
     pub fn stats(&self) -> CacheStats {
-        self.stats.read().clone()
+        let mut stats = self.stats.read().clone();
+        let (parse_size, plan_size) = self.sizes();
+        stats.parse_size = parse_size;
+        stats.plan_size = plan_size;
+        stats
     }
 
     /// Reset statistics
@@ -130,6 +212,11 @@ impl BrainCache {
         *self.stats.write() = CacheStats::default();
     }
 
+    /// Configured time-to-live, if any
+    pub fn ttl(&self) -> Option<Duration> {
+        self.ttl
+    }
+
     /// Normalize cache key (lowercase, trim, collapse whitespace)
     fn normalize_key(text: &str) -> String {
         text.to_lowercase()
@@ -175,10 +262,12 @@ mod tests {
                 confidence: 0.9,
                 entities: HashMap::new(),
                 alternatives: Vec::new(),
+                factors: Vec::new(),
             },
             parallel_groups: Vec::new(),
             is_valid: true,
             validation_errors: Vec::new(),
+            correlation_id: None,
         }
     }
 
@@ -265,6 +354,43 @@ mod tests {
         assert!(cache.get_parsed("cmd3").is_some());
     }
 
+    #[test]
+    fn test_config_capacity_eviction() {
+        let cache = BrainCache::with_config(CacheConfig {
+            max_entries: 3,
+            ttl: None,
+        });
+
+        for i in 0..4 {
+            cache.put_plan(&format!("cmd{}", i), create_test_plan());
+        }
+
+        // Oldest entry is gone, size stays bounded
+        assert!(cache.get_plan("cmd0").is_none());
+        assert!(cache.get_plan("cmd3").is_some());
+        assert_eq!(cache.stats().plan_size, 3);
+    }
+
+    #[test]
+    fn test_ttl_expiry() {
+        let cache = BrainCache::with_config(CacheConfig {
+            max_entries: 10,
+            ttl: Some(Duration::from_millis(30)),
+        });
+
+        cache.put_parsed("open chrome", create_test_parsed(IntentType::LaunchApp));
+        assert!(cache.get_parsed("open chrome").is_some());
+
+        std::thread::sleep(Duration::from_millis(50));
+
+        // Expired entry misses and is evicted lazily
+        assert!(cache.get_parsed("open chrome").is_none());
+        let stats = cache.stats();
+        assert_eq!(stats.expired, 1);
+        assert_eq!(stats.parse_size, 0);
+        assert_eq!(stats.parse_misses, 1);
+    }
+
     #[test]
     fn test_key_normalization() {
         let key1 = BrainCache::normalize_key("  Open   Chrome  ");
@@ -3,11 +3,15 @@
 //! Parses natural language text into structured commands using regex patterns.
 //! Optimized with RegexSet for parallel pattern matching.
 
+use crate::brain::entity_extractor::EntityExtractor;
+use crate::brain::grammar::{CompiledGrammar, CompiledPattern};
+use crate::brain::locale::Locale;
 use crate::error::{LunaError, Result};
 use once_cell::sync::Lazy;
 use regex::{Captures, Regex, RegexSet};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::Arc;
 use tracing::{debug, info};
 
 /// Intent types that LUNA can understand
@@ -25,6 +29,8 @@ pub enum IntentType {
     SystemControl,
     /// Volume control (up, down, mute)
     VolumeControl,
+    /// Screen brightness control (set, up, down)
+    BrightnessControl,
     /// Window management (move, resize, minimize, maximize)
     WindowManagement,
     /// Media control (play, pause, next, previous)
@@ -41,6 +47,18 @@ pub enum IntentType {
     GetTime,
     /// Get current date
     GetDate,
+    /// Clipboard read/write/clear
+    Clipboard,
+    /// Run an allowlisted command skill
+    RunCommand,
+    /// Virtual desktops (switch, move window, create, list)
+    VirtualDesktop,
+    /// Power profiles and battery status
+    Power,
+    /// Process priority and CPU affinity
+    ProcessControl,
+    /// Cancel the current request ("never mind", "don't open chrome")
+    Cancel,
     /// Unknown/unrecognized command
     Unknown,
 }
@@ -69,6 +87,9 @@ struct CommandPattern {
 pub struct CommandParser {
     patterns: Vec<CommandPattern>,
     regex_set: RegexSet,
+    locale: Locale,
+    /// Locale grammar, tried before the built-in English patterns
+    locale_grammar: Option<Arc<CompiledGrammar>>,
 }
 
 impl CommandParser {
@@ -84,20 +105,59 @@ impl CommandParser {
         Self {
             patterns,
             regex_set,
+            locale: Locale::English,
+            locale_grammar: None,
         }
     }
 
+    /// Parse commands spoken in `locale` using its grammar
+    ///
+    /// The locale grammar is tried first; commands it doesn't match fall
+    /// through to the built-in English patterns.
+    pub fn with_locale(mut self, locale: Locale, grammar: Arc<CompiledGrammar>) -> Self {
+        self.locale = locale;
+        self.locale_grammar = Some(grammar);
+        self
+    }
+
+    /// Language commands are parsed in
+    pub fn locale(&self) -> Locale {
+        self.locale
+    }
+
     /// Parse text into a command (optimized with RegexSet)
+    ///
+    /// A trailing "if you can" or "if possible" is stripped and marks the
+    /// command with an `optional` entity, so its steps may fail without
+    /// failing the plan.
     pub fn parse(&self, text: &str) -> Result<ParsedCommand> {
         let normalized = self.normalize_text(text);
+        let Some(required) = strip_optional_suffix(&normalized) else {
+            return self.parse_normalized(text, &normalized);
+        };
+
+        let mut parsed = self.parse_normalized(text, required)?;
+        if parsed.intent != IntentType::Unknown {
+            parsed
+                .entities
+                .insert("optional".to_string(), "true".to_string());
+        }
+        Ok(parsed)
+    }
+
+    fn parse_normalized(&self, text: &str, normalized: &str) -> Result<ParsedCommand> {
         debug!("Parsing text: '{}' (normalized: '{}')", text, normalized);
 
+        if let Some(parsed) = self.parse_with_locale_grammar(text, normalized) {
+            return Ok(parsed);
+        }
+
         // Fast pre-filter with RegexSet (parallel matching)
-        let matches = self.regex_set.matches(&normalized);
+        let matches = self.regex_set.matches(normalized);
 
         // Only evaluate matched patterns (typically 1-2 instead of all 20+)
         for idx in matches.iter() {
-            if let Some(captures) = self.patterns[idx].regex.captures(&normalized) {
+            if let Some(captures) = self.patterns[idx].regex.captures(normalized) {
                 let entities = (self.patterns[idx].extract_entities)(&captures);
                 let intent = self.patterns[idx].intent.clone();
 
@@ -116,12 +176,35 @@ impl CommandParser {
         info!("No pattern matched for: '{}'", text);
         Ok(ParsedCommand {
             intent: IntentType::Unknown,
-            entities: HashMap::new(),
+            entities: HashMap::from([("text".to_string(), text.to_string())]),
             original_text: text.to_string(),
             confidence: 0.0,
         })
     }
 
+    /// Match the locale grammar, highest priority pattern first
+    fn parse_with_locale_grammar(&self, text: &str, normalized: &str) -> Option<ParsedCommand> {
+        let grammar = self.locale_grammar.as_ref()?;
+        grammar
+            .match_text(normalized)
+            .into_iter()
+            .find_map(|(_, pattern)| {
+                let captures = pattern.regex.captures(normalized)?;
+                let entities = grammar_entities(pattern, &captures);
+                info!(
+                    "Matched {} intent: {:?}, entities: {:?}",
+                    self.locale, pattern.intent, entities
+                );
+
+                Some(ParsedCommand {
+                    intent: pattern.intent.clone(),
+                    entities,
+                    original_text: text.to_string(),
+                    confidence: 0.95,
+                })
+            })
+    }
+
     /// Normalize text for better matching
     fn normalize_text(&self, text: &str) -> String {
         text.to_lowercase().trim().to_string()
@@ -130,6 +213,21 @@ impl CommandParser {
     /// Build all command patterns
     fn build_patterns() -> Vec<CommandPattern> {
         vec![
+            // Command skill: "run the git status skill", "run command backup"
+            // Must precede launch, which also accepts "run"
+            CommandPattern {
+                regex: Regex::new(
+                    r"^run\s+(?:the\s+)?(?:(?:skill|command)\s+(.+)|(.+?)\s+(?:skill|command))$",
+                )
+                .unwrap(),
+                intent: IntentType::RunCommand,
+                extract_entities: |caps| {
+                    let mut map = HashMap::new();
+                    let skill = caps.get(1).or_else(|| caps.get(2)).unwrap();
+                    map.insert("skill".to_string(), skill.as_str().to_string());
+                    map
+                },
+            },
             // Launch app: "open chrome", "launch firefox", "start vscode"
             CommandPattern {
                 regex: Regex::new(r"^(?:open|launch|start|run)\s+(.+)$").unwrap(),
@@ -180,6 +278,34 @@ impl CommandParser {
                     map
                 },
             },
+            CommandPattern {
+                regex: Regex::new(r"^(?:set\s+)?(?:the\s+)?volume\s+(?:to\s+)?(.+)$").unwrap(),
+                intent: IntentType::VolumeControl,
+                extract_entities: |caps| {
+                    let mut map = HashMap::new();
+                    map.insert("action".to_string(), "set".to_string());
+                    map.insert("level".to_string(), caps[1].to_string());
+                    map
+                },
+            },
+            CommandPattern {
+                regex: Regex::new(
+                    r"^turn\s+(?:it|the\s+volume|volume)\s+(up|down)(?:\s+(.+))?$",
+                )
+                .unwrap(),
+                intent: IntentType::VolumeControl,
+                extract_entities: |caps| {
+                    let mut map = HashMap::new();
+                    let step = caps
+                        .get(2)
+                        .and_then(|m| EntityExtractor::new().extract_adjustment(m.as_str()))
+                        .unwrap_or(10);
+                    let delta = if &caps[1] == "up" { step } else { -step };
+                    map.insert("action".to_string(), "adjust".to_string());
+                    map.insert("delta".to_string(), delta.to_string());
+                    map
+                },
+            },
             CommandPattern {
                 regex: Regex::new(r"^(?:turn\s+)?(?:up|down)\s+(?:the\s+)?volume$").unwrap(),
                 intent: IntentType::VolumeControl,
@@ -200,6 +326,41 @@ impl CommandParser {
                     map
                 },
             },
+            // Brightness control: "set brightness to 40%", "dim the screen", "brightness up"
+            CommandPattern {
+                regex: Regex::new(r"^(?:screen\s+)?brightness\s+(up|down)$").unwrap(),
+                intent: IntentType::BrightnessControl,
+                extract_entities: |caps| {
+                    let mut map = HashMap::new();
+                    map.insert("action".to_string(), caps[1].to_string());
+                    map
+                },
+            },
+            CommandPattern {
+                regex: Regex::new(
+                    r"^(?:set\s+)?(?:the\s+)?(?:screen\s+)?brightness\s+(?:to\s+)?(.+)$",
+                )
+                .unwrap(),
+                intent: IntentType::BrightnessControl,
+                extract_entities: |caps| {
+                    let mut map = HashMap::new();
+                    // "40%" stays "40"; spelled-out levels are typed later
+                    let level = caps[1].trim_end_matches('%').trim_end();
+                    map.insert("action".to_string(), "set".to_string());
+                    map.insert("level".to_string(), level.to_string());
+                    map
+                },
+            },
+            CommandPattern {
+                regex: Regex::new(r"^(dim|brighten)\s+(?:the\s+)?(?:screen|display)$").unwrap(),
+                intent: IntentType::BrightnessControl,
+                extract_entities: |caps| {
+                    let mut map = HashMap::new();
+                    let action = if &caps[1] == "dim" { "down" } else { "up" };
+                    map.insert("action".to_string(), action.to_string());
+                    map
+                },
+            },
             // System control: "lock computer", "shutdown", "restart"
             CommandPattern {
                 regex: Regex::new(r"^(?:lock|sleep|shutdown|restart)\s*(?:computer|system)?$")
@@ -232,6 +393,141 @@ impl CommandParser {
                     map
                 },
             },
+            // Seek: "skip forward 30 seconds", "go back ten seconds", "rewind a minute"
+            CommandPattern {
+                regex: Regex::new(
+                    r"^(skip|jump|seek|go|fast\s+forward|rewind)(?:\s+(forward|ahead|back|backward|backwards))?\s+(?:by\s+)?(\d+\s*(?:s|m)|(?:\d+|a|an|half\s+a|[a-z]+(?:[\s-][a-z]+)?)\s+(?:seconds?|secs?|minutes?|mins?))$",
+                )
+                .unwrap(),
+                intent: IntentType::MediaControl,
+                extract_entities: |caps| {
+                    let mut map = HashMap::new();
+                    let back = &caps[1] == "rewind"
+                        || caps.get(2).is_some_and(|m| m.as_str().starts_with("back"));
+                    let direction = if back { "back" } else { "forward" };
+                    map.insert("action".to_string(), "seek".to_string());
+                    map.insert("direction".to_string(), direction.to_string());
+                    map.insert("amount".to_string(), caps[3].to_string());
+                    map
+                },
+            },
+            // Player volume: "set spotify volume to 20%"
+            CommandPattern {
+                regex: Regex::new(r"^set\s+(?:the\s+)?([a-z0-9][a-z0-9 .-]*?)\s+volume\s+(?:to\s+)?(.+)$")
+                    .unwrap(),
+                intent: IntentType::MediaControl,
+                extract_entities: |caps| {
+                    let mut map = HashMap::new();
+                    map.insert("action".to_string(), "volume".to_string());
+                    if !matches!(&caps[1], "media" | "music" | "player" | "media player") {
+                        map.insert("player".to_string(), caps[1].to_string());
+                    }
+                    map.insert("level".to_string(), caps[2].to_string());
+                    map
+                },
+            },
+            // Move window to desktop: "move this window to desktop 3"
+            CommandPattern {
+                regex: Regex::new(
+                    r"^(?:move|send)\s+(?:this\s+|the\s+|my\s+)?(?:window\s+)?to\s+(?:desktop|workspace)\s+(?:number\s+)?(\w+)$",
+                )
+                .unwrap(),
+                intent: IntentType::VirtualDesktop,
+                extract_entities: |caps| {
+                    let mut map = HashMap::new();
+                    map.insert("action".to_string(), "move".to_string());
+                    map.insert("desktop".to_string(), caps[1].to_string());
+                    map
+                },
+            },
+            // Move window to monitor: "move chrome to my second monitor"
+            CommandPattern {
+                regex: Regex::new(
+                    r"^(?:move|put|send|throw)\s+(.+?)\s+(?:to|on|onto)\s+(?:the\s+|my\s+)?((?:\w+\s+)?(?:monitor|display|screen)(?:\s+\w+)?)$",
+                )
+                .unwrap(),
+                intent: IntentType::WindowManagement,
+                extract_entities: |caps| {
+                    let mut map = HashMap::new();
+                    map.insert("action".to_string(), "move".to_string());
+                    if !is_active_window(&caps[1]) {
+                        map.insert("app_name".to_string(), caps[1].to_string());
+                    }
+                    map.insert("monitor".to_string(), caps[2].to_string());
+                    map
+                },
+            },
+            // Maximize on monitor: "maximize chrome on monitor 2"
+            CommandPattern {
+                regex: Regex::new(
+                    r"^(?:maximi[sz]e|fullscreen)\s+(.+?)\s+on\s+(?:the\s+|my\s+)?((?:\w+\s+)?(?:monitor|display|screen)(?:\s+\w+)?)$",
+                )
+                .unwrap(),
+                intent: IntentType::WindowManagement,
+                extract_entities: |caps| {
+                    let mut map = HashMap::new();
+                    map.insert("action".to_string(), "maximize".to_string());
+                    if !is_active_window(&caps[1]) {
+                        map.insert("app_name".to_string(), caps[1].to_string());
+                    }
+                    map.insert("monitor".to_string(), caps[2].to_string());
+                    map
+                },
+            },
+            // Switch desktop: "switch to desktop 2", "go to workspace two"
+            CommandPattern {
+                regex: Regex::new(
+                    r"^(?:switch|go|change)\s+to\s+(?:desktop|workspace)\s+(?:number\s+)?(\w+)$",
+                )
+                .unwrap(),
+                intent: IntentType::VirtualDesktop,
+                extract_entities: |caps| {
+                    let mut map = HashMap::new();
+                    map.insert("action".to_string(), "switch".to_string());
+                    map.insert("desktop".to_string(), caps[1].to_string());
+                    map
+                },
+            },
+            // Relative desktop: "next desktop", "switch to the previous workspace"
+            CommandPattern {
+                regex: Regex::new(
+                    r"^(?:(?:switch|go|move)\s+to\s+(?:the\s+)?)?(next|previous|prev)\s+(?:desktop|workspace)$",
+                )
+                .unwrap(),
+                intent: IntentType::VirtualDesktop,
+                extract_entities: |caps| {
+                    let mut map = HashMap::new();
+                    let action = if &caps[1] == "next" { "next" } else { "previous" };
+                    map.insert("action".to_string(), action.to_string());
+                    map
+                },
+            },
+            // Create desktop: "create a new desktop", "new workspace"
+            CommandPattern {
+                regex: Regex::new(
+                    r"^(?:(?:create|add|make)\s+(?:a\s+)?(?:new\s+)?|new\s+)(?:virtual\s+)?(?:desktop|workspace)$",
+                )
+                .unwrap(),
+                intent: IntentType::VirtualDesktop,
+                extract_entities: |_| {
+                    let mut map = HashMap::new();
+                    map.insert("action".to_string(), "create".to_string());
+                    map
+                },
+            },
+            // List desktops: "list my desktops", "show me all workspaces"
+            CommandPattern {
+                regex: Regex::new(
+                    r"^(?:list|show)\s+(?:me\s+)?(?:my\s+|all\s+|the\s+)?(?:virtual\s+)?(?:desktops|workspaces)$",
+                )
+                .unwrap(),
+                intent: IntentType::VirtualDesktop,
+                extract_entities: |_| {
+                    let mut map = HashMap::new();
+                    map.insert("action".to_string(), "list".to_string());
+                    map
+                },
+            },
             // Search web: "search for rust tutorials", "google machine learning"
             CommandPattern {
                 regex: Regex::new(r"^(?:search\s+(?:for|the\s+web\s+for)|google)\s+(.+)$").unwrap(),
@@ -253,6 +549,20 @@ impl CommandParser {
                     map
                 },
             },
+            // Reminder at a time: "remind me to call mom tomorrow at 9am"
+            CommandPattern {
+                regex: Regex::new(
+                    r"^remind\s+me\s+(?:to\s+|about\s+)?(.+?)\s+((?:at|on|by|today|tonight|tomorrow|next|this)\b.*)$",
+                )
+                .unwrap(),
+                intent: IntentType::Reminder,
+                extract_entities: |caps| {
+                    let mut map = HashMap::new();
+                    map.insert("message".to_string(), caps[1].to_string());
+                    map.insert("when".to_string(), caps[2].to_string());
+                    map
+                },
+            },
             // Note: "take a note buy milk"
             CommandPattern {
                 regex: Regex::new(r"^(?:take\s+a\s+note|note|write\s+down)\s*:?\s*(.+)$").unwrap(),
@@ -281,6 +591,170 @@ impl CommandParser {
                 intent: IntentType::GetDate,
                 extract_entities: |_caps| HashMap::new(),
             },
+            // Clipboard write: "copy that to clipboard", "put hello world in my clipboard"
+            CommandPattern {
+                regex: Regex::new(
+                    r"^(?:copy|put)\s+(.+?)\s+(?:to|in|into|on)\s+(?:the\s+|my\s+)?clipboard$",
+                )
+                .unwrap(),
+                intent: IntentType::Clipboard,
+                extract_entities: |caps| {
+                    let mut map = HashMap::new();
+                    map.insert("action".to_string(), "write".to_string());
+                    map.insert("text".to_string(), caps[1].to_string());
+                    map
+                },
+            },
+            // Clipboard read: "what's in my clipboard", "read the clipboard"
+            CommandPattern {
+                regex: Regex::new(
+                    r"^(?:what(?:'s|\s+is)\s+(?:in|on)\s+(?:the\s+|my\s+)?clipboard|read\s+(?:the\s+|my\s+)?clipboard)$",
+                )
+                .unwrap(),
+                intent: IntentType::Clipboard,
+                extract_entities: |_caps| {
+                    let mut map = HashMap::new();
+                    map.insert("action".to_string(), "read".to_string());
+                    map
+                },
+            },
+            // Clipboard clear: "clear the clipboard"
+            CommandPattern {
+                regex: Regex::new(r"^clear\s+(?:the\s+|my\s+)?clipboard$").unwrap(),
+                intent: IntentType::Clipboard,
+                extract_entities: |_caps| {
+                    let mut map = HashMap::new();
+                    map.insert("action".to_string(), "clear".to_string());
+                    map
+                },
+            },
+            // Clipboard history: "show clipboard history"
+            CommandPattern {
+                regex: Regex::new(
+                    r"^(?:show|list|read)\s+(?:me\s+)?(?:the\s+|my\s+)?clipboard\s+history$",
+                )
+                .unwrap(),
+                intent: IntentType::Clipboard,
+                extract_entities: |_caps| {
+                    let mut map = HashMap::new();
+                    map.insert("action".to_string(), "history".to_string());
+                    map
+                },
+            },
+            // Clipboard recall: "paste the thing before last"
+            CommandPattern {
+                regex: Regex::new(
+                    r"^paste\s+(?:the\s+)?(?:thing|item|one|copy)\s+before\s+(?:the\s+)?last(?:\s+one)?$",
+                )
+                .unwrap(),
+                intent: IntentType::Clipboard,
+                extract_entities: |_caps| {
+                    let mut map = HashMap::new();
+                    map.insert("action".to_string(), "recall".to_string());
+                    map.insert("number".to_string(), "2".to_string());
+                    map
+                },
+            },
+            // Clipboard recall by number: "paste item 3 from clipboard history"
+            CommandPattern {
+                regex: Regex::new(
+                    r"^paste\s+(?:clipboard\s+)?(?:item|entry|number)\s+(\w+)(?:\s+from\s+(?:the\s+|my\s+)?clipboard(?:\s+history)?)?$",
+                )
+                .unwrap(),
+                intent: IntentType::Clipboard,
+                extract_entities: |caps| {
+                    let mut map = HashMap::new();
+                    map.insert("action".to_string(), "recall".to_string());
+                    map.insert("number".to_string(), caps[1].to_string());
+                    map
+                },
+            },
+            // Power profile: "enable battery saver", "switch to performance mode"
+            CommandPattern {
+                regex: Regex::new(
+                    r"^(?:enable|turn\s+on|switch\s+to|use|activate)\s+(?:the\s+)?(battery\s+saver|power\s+saver|low\s+power|performance|high\s+performance|balanced)(?:\s+(?:mode|profile))?$",
+                )
+                .unwrap(),
+                intent: IntentType::Power,
+                extract_entities: |caps| {
+                    let mut map = HashMap::new();
+                    map.insert("action".to_string(), "profile".to_string());
+                    map.insert("profile".to_string(), caps[1].to_string());
+                    map
+                },
+            },
+            // Power saver off: "turn off battery saver"
+            CommandPattern {
+                regex: Regex::new(
+                    r"^(?:disable|turn\s+off)\s+(?:the\s+)?(?:battery|power)\s+saver(?:\s+mode)?$",
+                )
+                .unwrap(),
+                intent: IntentType::Power,
+                extract_entities: |_caps| {
+                    let mut map = HashMap::new();
+                    map.insert("action".to_string(), "profile".to_string());
+                    map.insert("profile".to_string(), "balanced".to_string());
+                    map
+                },
+            },
+            // Battery status: "what's my battery", "how much battery do I have left"
+            CommandPattern {
+                regex: Regex::new(
+                    r"^(?:what(?:'s|\s+is)\s+(?:my\s+|the\s+)?battery(?:\s+(?:level|status|at))?|how\s+much\s+battery\s+(?:do\s+i\s+have\s+)?(?:left|is\s+left)|battery\s+(?:status|level))$",
+                )
+                .unwrap(),
+                intent: IntentType::Power,
+                extract_entities: |_caps| {
+                    let mut map = HashMap::new();
+                    map.insert("action".to_string(), "status".to_string());
+                    map
+                },
+            },
+            // Relative priority: "lower the priority of the build"
+            CommandPattern {
+                regex: Regex::new(
+                    r"^(lower|reduce|decrease|raise|increase|boost)\s+(?:the\s+)?priority\s+(?:of|for)\s+(?:the\s+)?(.+)$",
+                )
+                .unwrap(),
+                intent: IntentType::ProcessControl,
+                extract_entities: |caps| {
+                    let mut map = HashMap::new();
+                    map.insert("action".to_string(), "priority".to_string());
+                    map.insert("priority".to_string(), caps[1].to_string());
+                    map.insert("process".to_string(), caps[2].to_string());
+                    map
+                },
+            },
+            // Absolute priority: "set the priority of chrome to low"
+            CommandPattern {
+                regex: Regex::new(
+                    r"^set\s+(?:the\s+)?priority\s+(?:of|for)\s+(?:the\s+)?(.+?)\s+to\s+(idle|low|below\s+normal|normal|above\s+normal|high)$",
+                )
+                .unwrap(),
+                intent: IntentType::ProcessControl,
+                extract_entities: |caps| {
+                    let mut map = HashMap::new();
+                    map.insert("action".to_string(), "priority".to_string());
+                    map.insert("process".to_string(), caps[1].to_string());
+                    map.insert("priority".to_string(), caps[2].to_string());
+                    map
+                },
+            },
+            // Affinity: "pin chrome to cores 0 and 1"
+            CommandPattern {
+                regex: Regex::new(
+                    r"^(?:pin|bind|restrict)\s+(?:the\s+)?(.+?)\s+to\s+(?:cpu\s+)?(?:cores?|cpus?)\s+(.+)$",
+                )
+                .unwrap(),
+                intent: IntentType::ProcessControl,
+                extract_entities: |caps| {
+                    let mut map = HashMap::new();
+                    map.insert("action".to_string(), "affinity".to_string());
+                    map.insert("process".to_string(), caps[1].to_string());
+                    map.insert("cores".to_string(), caps[2].to_string());
+                    map
+                },
+            },
             // Question: "what is...", "how do I...", "why..."
             CommandPattern {
                 regex: Regex::new(r"^(?:what|how|why|when|where|who)\s+.+$").unwrap(),
@@ -298,6 +772,55 @@ impl CommandParser {
     }
 }
 
+/// Window phrases that mean the focused window rather than an app
+const ACTIVE_WINDOW_PHRASES: &[&str] = &[
+    "it",
+    "this",
+    "that",
+    "window",
+    "this window",
+    "the window",
+    "my window",
+    "the active window",
+    "the current window",
+];
+
+/// Fill a grammar pattern's entity templates: "$1" is a capture group,
+/// anything else is a literal value
+fn grammar_entities(pattern: &CompiledPattern, captures: &Captures) -> HashMap<String, String> {
+    pattern
+        .entity_map
+        .iter()
+        .filter_map(|(key, template)| {
+            let value = match template.strip_prefix('$').map(str::parse::<usize>) {
+                Some(Ok(group)) => captures.get(group)?.as_str().trim().to_string(),
+                _ => template.clone(),
+            };
+            Some((key.clone(), value))
+        })
+        .collect()
+}
+
+fn is_active_window(text: &str) -> bool {
+    ACTIVE_WINDOW_PHRASES.contains(&text)
+}
+
+/// Trailing phrases that make a command best-effort
+const OPTIONAL_SUFFIXES: &[&str] = &["if you can", "if possible"];
+
+/// Command without its "if you can" suffix, if it had one
+fn strip_optional_suffix(text: &str) -> Option<&str> {
+    let text = text.trim_end_matches(['.', '!']);
+    OPTIONAL_SUFFIXES.iter().find_map(|suffix| {
+        let rest = text.strip_suffix(suffix)?;
+        if !rest.ends_with([' ', ',']) {
+            return None;
+        }
+        let rest = rest.trim_end_matches([' ', ',']);
+        (!rest.is_empty()).then_some(rest)
+    })
+}
+
 impl Default for CommandParser {
     fn default() -> Self {
         Self::new()
@@ -307,6 +830,7 @@ impl Default for CommandParser {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::brain::grammar::GrammarConfig;
 
     #[test]
     fn test_parse_launch_app() {
@@ -355,6 +879,89 @@ mod tests {
         assert_eq!(result.entities.get("action"), Some(&"mute".to_string()));
     }
 
+    #[test]
+    fn test_parse_volume_quantities() {
+        let parser = CommandParser::new();
+
+        let result = parser.parse("set volume to fifty").unwrap();
+        assert_eq!(result.intent, IntentType::VolumeControl);
+        assert_eq!(result.entities.get("action"), Some(&"set".to_string()));
+        assert_eq!(result.entities.get("level"), Some(&"fifty".to_string()));
+
+        let result = parser.parse("volume 75%").unwrap();
+        assert_eq!(result.intent, IntentType::VolumeControl);
+        assert_eq!(result.entities.get("level"), Some(&"75%".to_string()));
+
+        let result = parser.parse("turn it up a bit").unwrap();
+        assert_eq!(result.intent, IntentType::VolumeControl);
+        assert_eq!(result.entities.get("action"), Some(&"adjust".to_string()));
+        assert_eq!(result.entities.get("delta"), Some(&"5".to_string()));
+    }
+
+    #[test]
+    fn test_parse_media_seek_and_volume() {
+        let parser = CommandParser::new();
+
+        let result = parser.parse("skip forward 30 seconds").unwrap();
+        assert_eq!(result.intent, IntentType::MediaControl);
+        assert_eq!(result.entities.get("action"), Some(&"seek".to_string()));
+        assert_eq!(
+            result.entities.get("direction"),
+            Some(&"forward".to_string())
+        );
+        assert_eq!(
+            result.entities.get("amount"),
+            Some(&"30 seconds".to_string())
+        );
+
+        let result = parser.parse("rewind ten seconds").unwrap();
+        assert_eq!(result.entities.get("direction"), Some(&"back".to_string()));
+        assert_eq!(
+            result.entities.get("amount"),
+            Some(&"ten seconds".to_string())
+        );
+
+        let result = parser.parse("go back a minute").unwrap();
+        assert_eq!(result.entities.get("direction"), Some(&"back".to_string()));
+
+        let result = parser.parse("set spotify volume to 20%").unwrap();
+        assert_eq!(result.intent, IntentType::MediaControl);
+        assert_eq!(result.entities.get("action"), Some(&"volume".to_string()));
+        assert_eq!(result.entities.get("player"), Some(&"spotify".to_string()));
+        assert_eq!(result.entities.get("level"), Some(&"20%".to_string()));
+
+        let result = parser.parse("set media volume to 20%").unwrap();
+        assert_eq!(result.entities.get("player"), None);
+
+        // System volume and track skipping keep their own intents
+        let result = parser.parse("set the volume to 20%").unwrap();
+        assert_eq!(result.intent, IntentType::VolumeControl);
+        assert_ne!(
+            parser.parse("skip songs").unwrap().entities.get("action"),
+            Some(&"seek".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_brightness_control() {
+        let parser = CommandParser::new();
+
+        let result = parser.parse("set brightness to 40%").unwrap();
+        assert_eq!(result.intent, IntentType::BrightnessControl);
+        assert_eq!(result.entities.get("action"), Some(&"set".to_string()));
+        assert_eq!(result.entities.get("level"), Some(&"40".to_string()));
+
+        let result = parser.parse("set brightness to fifty").unwrap();
+        assert_eq!(result.entities.get("level"), Some(&"fifty".to_string()));
+
+        let result = parser.parse("dim the screen").unwrap();
+        assert_eq!(result.intent, IntentType::BrightnessControl);
+        assert_eq!(result.entities.get("action"), Some(&"down".to_string()));
+
+        let result = parser.parse("brightness up").unwrap();
+        assert_eq!(result.entities.get("action"), Some(&"up".to_string()));
+    }
+
     #[test]
     fn test_parse_time_date() {
         let parser = CommandParser::new();
@@ -366,6 +973,159 @@ mod tests {
         assert_eq!(result.intent, IntentType::GetDate);
     }
 
+    #[test]
+    fn test_parse_window_to_monitor() {
+        let parser = CommandParser::new();
+
+        let result = parser.parse("move chrome to my second monitor").unwrap();
+        assert_eq!(result.intent, IntentType::WindowManagement);
+        assert_eq!(result.entities.get("app_name"), Some(&"chrome".to_string()));
+        assert_eq!(
+            result.entities.get("monitor"),
+            Some(&"second monitor".to_string())
+        );
+
+        let result = parser
+            .parse("maximize this window on the left monitor")
+            .unwrap();
+        assert_eq!(result.entities.get("action"), Some(&"maximize".to_string()));
+        assert_eq!(result.entities.get("app_name"), None);
+        assert_eq!(
+            result.entities.get("monitor"),
+            Some(&"left monitor".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_clipboard_history() {
+        let parser = CommandParser::new();
+        let cases = [
+            ("show clipboard history", "history", None),
+            ("paste the thing before last", "recall", Some("2")),
+            ("paste item three", "recall", Some("three")),
+        ];
+
+        for (text, action, number) in cases {
+            let result = parser.parse(text).unwrap();
+            assert_eq!(result.intent, IntentType::Clipboard, "{}", text);
+            assert_eq!(
+                result.entities.get("action").map(String::as_str),
+                Some(action)
+            );
+            assert_eq!(result.entities.get("number").map(String::as_str), number);
+        }
+    }
+
+    #[test]
+    fn test_parse_process_control() {
+        let parser = CommandParser::new();
+
+        let result = parser.parse("lower the priority of the build").unwrap();
+        assert_eq!(result.intent, IntentType::ProcessControl);
+        assert_eq!(result.entities.get("priority"), Some(&"lower".to_string()));
+        assert_eq!(result.entities.get("process"), Some(&"build".to_string()));
+
+        let result = parser
+            .parse("set the priority of chrome to below normal")
+            .unwrap();
+        assert_eq!(result.entities.get("process"), Some(&"chrome".to_string()));
+        assert_eq!(
+            result.entities.get("priority"),
+            Some(&"below normal".to_string())
+        );
+
+        let result = parser.parse("pin chrome to cores 0 and 1").unwrap();
+        assert_eq!(result.intent, IntentType::ProcessControl);
+        assert_eq!(result.entities.get("action"), Some(&"affinity".to_string()));
+        assert_eq!(result.entities.get("cores"), Some(&"0 and 1".to_string()));
+    }
+
+    #[test]
+    fn test_parse_power() {
+        let parser = CommandParser::new();
+        let cases = [
+            ("enable battery saver", "profile", Some("battery saver")),
+            ("switch to performance mode", "profile", Some("performance")),
+            ("turn off battery saver", "profile", Some("balanced")),
+            ("what's my battery", "status", None),
+            ("how much battery do i have left", "status", None),
+        ];
+
+        for (text, action, profile) in cases {
+            let result = parser.parse(text).unwrap();
+            assert_eq!(result.intent, IntentType::Power, "{}", text);
+            assert_eq!(
+                result.entities.get("action").map(String::as_str),
+                Some(action)
+            );
+            assert_eq!(result.entities.get("profile").map(String::as_str), profile);
+        }
+    }
+
+    #[test]
+    fn test_parse_virtual_desktop() {
+        let parser = CommandParser::new();
+        let cases = [
+            ("switch to desktop 2", "switch", Some("2")),
+            ("go to workspace two", "switch", Some("two")),
+            ("move this window to desktop 3", "move", Some("3")),
+            ("next desktop", "next", None),
+            ("switch to the previous workspace", "previous", None),
+            ("create a new desktop", "create", None),
+            ("list my desktops", "list", None),
+        ];
+
+        for (text, action, desktop) in cases {
+            let result = parser.parse(text).unwrap();
+            assert_eq!(result.intent, IntentType::VirtualDesktop, "{}", text);
+            assert_eq!(
+                result.entities.get("action").map(String::as_str),
+                Some(action)
+            );
+            assert_eq!(result.entities.get("desktop").map(String::as_str), desktop);
+        }
+    }
+
+    #[test]
+    fn test_parse_run_command() {
+        let parser = CommandParser::new();
+
+        let result = parser.parse("run the git status skill").unwrap();
+        assert_eq!(result.intent, IntentType::RunCommand);
+        assert_eq!(
+            result.entities.get("skill"),
+            Some(&"git status".to_string())
+        );
+
+        let result = parser.parse("run command backup").unwrap();
+        assert_eq!(result.intent, IntentType::RunCommand);
+        assert_eq!(result.entities.get("skill"), Some(&"backup".to_string()));
+
+        // Plain "run" still launches apps
+        let result = parser.parse("run firefox").unwrap();
+        assert_eq!(result.intent, IntentType::LaunchApp);
+    }
+
+    #[test]
+    fn test_parse_clipboard() {
+        let parser = CommandParser::new();
+
+        let result = parser.parse("copy that to clipboard").unwrap();
+        assert_eq!(result.intent, IntentType::Clipboard);
+        assert_eq!(result.entities.get("action"), Some(&"write".to_string()));
+        assert_eq!(result.entities.get("text"), Some(&"that".to_string()));
+
+        let result = parser.parse("what's in my clipboard").unwrap();
+        assert_eq!(result.intent, IntentType::Clipboard);
+        assert_eq!(result.entities.get("action"), Some(&"read".to_string()));
+
+        let result = parser.parse("what is in the clipboard").unwrap();
+        assert_eq!(result.intent, IntentType::Clipboard);
+
+        let result = parser.parse("clear clipboard").unwrap();
+        assert_eq!(result.entities.get("action"), Some(&"clear".to_string()));
+    }
+
     #[test]
     fn test_parse_unknown() {
         let parser = CommandParser::new();
@@ -373,6 +1133,10 @@ mod tests {
 
         assert_eq!(result.intent, IntentType::Unknown);
         assert_eq!(result.confidence, 0.0);
+        assert_eq!(
+            result.entities.get("text"),
+            Some(&"blah blah nonsense".to_string())
+        );
     }
 
     #[test]
@@ -397,5 +1161,36 @@ mod tests {
             result.entities.get("duration"),
             Some(&"30 minutes".to_string())
         );
+
+        let result = parser
+            .parse("remind me to call mom tomorrow at 9am")
+            .unwrap();
+        assert_eq!(result.intent, IntentType::Reminder);
+        assert_eq!(result.entities.get("message"), Some(&"call mom".to_string()));
+        assert_eq!(
+            result.entities.get("when"),
+            Some(&"tomorrow at 9am".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_spanish() {
+        let grammar = GrammarConfig::load_from_file("config/brain_patterns.es.yaml")
+            .unwrap()
+            .compile()
+            .unwrap();
+        let parser = CommandParser::new().with_locale(Locale::Spanish, Arc::new(grammar));
+
+        let result = parser.parse("Abre Chrome").unwrap();
+        assert_eq!(result.intent, IntentType::LaunchApp);
+        assert_eq!(result.entities.get("app_name"), Some(&"chrome".to_string()));
+
+        let result = parser.parse("sube el volumen").unwrap();
+        assert_eq!(result.intent, IntentType::VolumeControl);
+        assert_eq!(result.entities.get("action"), Some(&"up".to_string()));
+
+        // English still parses
+        let result = parser.parse("close firefox").unwrap();
+        assert_eq!(result.intent, IntentType::CloseApp);
     }
 }
@@ -14,6 +14,9 @@ const MAX_HISTORY_SIZE: usize = 10;
 /// Maximum age of context entries (5 minutes)
 const MAX_CONTEXT_AGE: i64 = 300;
 
+/// Entity keys that name an application
+const APP_KEYS: &[&str] = &["app", "app_name"];
+
 /// Conversation context for disambiguation
 #[derive(Debug, Clone)]
 pub struct ConversationContext {
@@ -25,6 +28,9 @@ pub struct ConversationContext {
 
     /// Success/failure statistics for learning
     stats: HashMap<String, CommandStats>,
+
+    /// Focused application and when focus moved to it
+    active_app: Option<(String, DateTime<Utc>)>,
 }
 
 /// A single context entry
@@ -76,9 +82,20 @@ impl ConversationContext {
             history: VecDeque::with_capacity(MAX_HISTORY_SIZE),
             entity_cache: HashMap::new(),
             stats: HashMap::new(),
+            active_app: None,
         }
     }
 
+    /// Record that focus moved to `app_name`
+    pub fn set_active_app(&mut self, app_name: impl Into<String>) {
+        self.active_app = Some((app_name.into(), Utc::now()));
+    }
+
+    /// Currently focused application, if known
+    pub fn active_app(&self) -> Option<&str> {
+        self.active_app.as_ref().map(|(app, _)| app.as_str())
+    }
+
     /// Add a command to history
     pub fn add_command(
         &mut self,
@@ -156,6 +173,30 @@ impl ConversationContext {
         None
     }
 
+    /// Get the most recent string value stored under any of `keys`
+    ///
+    /// For app keys, an app focused after the last mentioned one wins, so
+    /// "close it" after alt-tabbing targets the focused app.
+    pub fn recent_value(&self, keys: &[&str]) -> Option<String> {
+        let mentioned = self.history.iter().rev().find_map(|entry| {
+            keys.iter().find_map(|key| {
+                entry
+                    .entities
+                    .get(*key)
+                    .and_then(|e| e.as_string())
+                    .map(|value| (value, entry.timestamp))
+            })
+        });
+
+        if let Some((app, focused_at)) = &self.active_app {
+            let wants_app = keys.iter().any(|key| APP_KEYS.contains(key));
+            if wants_app && mentioned.is_none_or(|(_, at)| *focused_at > at) {
+                return Some(app.clone());
+            }
+        }
+        mentioned.map(|(value, _)| value.to_string())
+    }
+
     /// Get command statistics
     pub fn get_stats(&self, text: &str) -> Option<&CommandStats> {
         let normalized = Self::normalize_text(text);
@@ -202,6 +243,7 @@ impl ConversationContext {
         self.history.clear();
         self.entity_cache.clear();
         self.stats.clear();
+        self.active_app = None;
     }
 
     /// Get history size
@@ -310,6 +352,24 @@ mod tests {
         assert!(entities.contains_key("app"));
     }
 
+    #[test]
+    fn test_focus_change_overrides_older_mention() {
+        let mut ctx = ConversationContext::new();
+        ctx.set_active_app("terminal");
+
+        let cmd = create_test_command("open chrome", IntentType::LaunchApp);
+        let mut entities = HashMap::new();
+        entities.insert("app".to_string(), Entity::App("chrome".to_string()));
+        ctx.add_command(cmd, entities, true);
+        assert_eq!(ctx.recent_value(APP_KEYS).as_deref(), Some("chrome"));
+
+        // Alt-tab after the command
+        std::thread::sleep(std::time::Duration::from_millis(2));
+        ctx.set_active_app("code");
+        assert_eq!(ctx.recent_value(APP_KEYS).as_deref(), Some("code"));
+        assert_eq!(ctx.recent_value(&["file"]), None);
+    }
+
     #[test]
     fn test_get_recent_entity() {
         let mut ctx = ConversationContext::new();
@@ -2,28 +2,134 @@
 //!
 //! Extracts specific entities (app names, file names, numbers, durations) from text.
 
+use crate::brain::locale::Locale;
+use crate::brain::types::Entity;
 use crate::error::Result;
 use crate::utils::time_helpers;
-use chrono::Duration;
+use chrono::{DateTime, Duration, Local};
 use once_cell::sync::Lazy;
 use regex::Regex;
+use std::collections::HashMap;
 
 static NUMBER_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"\b(\d+)\b").unwrap());
 static APP_NAME_REGEX: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r"\b([a-zA-Z][a-zA-Z0-9_-]*(?:\s+[a-zA-Z][a-zA-Z0-9_-]*)*)\b").unwrap()
 });
+static FILE_SIZE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?i)\b(?:(larger|bigger|greater|more|over)|(smaller|less|under))(?:\s+than)?\s+(\d+(?:\.\d+)?)\s*(bytes?|b|kb|k|mb|m|gb|g|tb|t)\b",
+    )
+    .unwrap()
+});
+static FILE_AGE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?i)\b(?:(?:from|in|during|within|modified|changed)\s+)?(?:the\s+)?(?:last|past)\s+(?:(\d+)\s+)?(day|week|month|year)s?\b",
+    )
+    .unwrap()
+});
+
+/// Spelled-out numbers below twenty
+const UNITS: &[&str] = &[
+    "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten",
+    "eleven", "twelve", "thirteen", "fourteen", "fifteen", "sixteen", "seventeen", "eighteen",
+    "nineteen",
+];
+
+/// Spelled-out multiples of ten, starting at twenty
+const TENS: &[&str] = &[
+    "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+];
+
+/// Fraction phrases understood as percentages
+const FRACTIONS: &[(&str, u8)] = &[
+    ("three quarters", 75),
+    ("a quarter", 25),
+    ("one quarter", 25),
+    ("a half", 50),
+    ("half", 50),
+];
+
+/// Relative adjustment phrases and their step size
+const ADJUSTMENTS: &[(&str, i8)] = &[
+    ("a tiny bit", 5),
+    ("a little bit", 5),
+    ("a little", 5),
+    ("a bit", 5),
+    ("slightly", 5),
+    ("a lot", 25),
+];
+
+/// Words naming a category of files, and the `FileType` they map to
+const FILE_TYPE_WORDS: &[(&str, &str)] = &[
+    ("document", "document"),
+    ("documents", "document"),
+    ("docs", "document"),
+    ("image", "image"),
+    ("images", "image"),
+    ("photo", "image"),
+    ("photos", "image"),
+    ("picture", "image"),
+    ("pictures", "image"),
+    ("video", "video"),
+    ("videos", "video"),
+    ("movie", "video"),
+    ("movies", "video"),
+    ("song", "audio"),
+    ("songs", "audio"),
+    ("music", "audio"),
+    ("audio", "audio"),
+    ("recordings", "audio"),
+    ("code", "code"),
+    ("archive", "archive"),
+    ("archives", "archive"),
+];
+
+/// Extensions recognized when spoken as a word ("PDFs", "the zip")
+const FILE_EXTENSION_WORDS: &[&str] = &[
+    "pdf", "doc", "docx", "odt", "txt", "png", "jpg", "jpeg", "gif", "svg", "mp3", "wav", "flac",
+    "mp4", "mkv", "mov", "avi", "zip", "tar", "gz",
+];
+
+/// Connecting words left over once file filters are removed
+const FILE_QUERY_FILLERS: &[&str] = &[
+    "about", "named", "called", "titled", "with", "of", "my", "all", "the", "any", "some", "file",
+    "files", "that", "are", "is", "and",
+];
 
 /// Entity extractor for parsing specific information from text
 #[derive(Clone)]
-pub struct EntityExtractor;
+pub struct EntityExtractor {
+    /// Lowercase alias -> canonical app name
+    app_aliases: HashMap<String, String>,
+
+    /// Language numbers and dates are spoken in
+    locale: Locale,
+}
 
 impl EntityExtractor {
     /// Create a new entity extractor
     pub fn new() -> Self {
-        Self
+        Self {
+            app_aliases: HashMap::new(),
+            locale: Locale::English,
+        }
+    }
+
+    /// Read numbers, durations and dates spoken in `locale`
+    pub fn with_locale(mut self, locale: Locale) -> Self {
+        self.locale = locale;
+        self
     }
 
-    /// Extract app name from text
+    /// Register aliases that normalize to a canonical app name
+    pub fn add_app_aliases(&mut self, app_name: &str, aliases: &[String]) {
+        for alias in aliases {
+            self.app_aliases
+                .insert(alias.trim().to_lowercase(), app_name.to_string());
+        }
+    }
+
+    /// Extract app name from text, normalizing known aliases
     pub fn extract_app_name(&self, text: &str) -> Option<String> {
         // Remove common words that aren't part of app names
         let filtered = self.remove_common_words(text);
@@ -32,7 +138,13 @@ impl EntityExtractor {
             return None;
         }
 
-        Some(filtered.trim().to_string())
+        let name = filtered.trim();
+        Some(
+            self.app_aliases
+                .get(&name.to_lowercase())
+                .cloned()
+                .unwrap_or_else(|| name.to_string()),
+        )
     }
 
     /// Extract file name from text
@@ -55,7 +167,41 @@ impl EntityExtractor {
 
     /// Extract duration from text (e.g., "30 minutes", "2 hours")
     pub fn extract_duration(&self, text: &str) -> Option<Duration> {
-        time_helpers::parse_duration(text)
+        time_helpers::parse_duration(&self.locale.rewrite_to_english(text))
+    }
+
+    /// Extract a spoken duration (e.g. "30 seconds", "thirty seconds", "a minute")
+    ///
+    /// Unlike `extract_duration` this accepts spelled-out amounts, which is
+    /// how relative offsets like "skip forward ten seconds" are usually said.
+    pub fn extract_spoken_duration(&self, text: &str) -> Option<Duration> {
+        let text = self.locale.rewrite_to_english(text);
+        if let Some(duration) = time_helpers::parse_duration(&text) {
+            return Some(duration);
+        }
+
+        let lower = text.to_lowercase();
+        let unit_secs = if lower.contains("hour") {
+            3600.0
+        } else if lower.contains("min") {
+            60.0
+        } else if lower.contains("sec") {
+            1.0
+        } else {
+            return None;
+        };
+
+        let count = match self.extract_quantity(&lower) {
+            Some(Entity::Number(n)) => n,
+            // "half a minute"
+            Some(Entity::Percentage(p)) => p as f64 / 100.0,
+            None if matches!(lower.split_whitespace().next(), Some("a" | "an")) => 1.0,
+            _ => return None,
+        };
+
+        Some(Duration::milliseconds(
+            (count * unit_secs * 1000.0).round() as i64
+        ))
     }
 
     /// Extract a number from text
@@ -78,6 +224,56 @@ impl EntityExtractor {
             .map(|n: u8| n.min(100))
     }
 
+    /// Extract an absolute date/time (e.g. "tomorrow at 9am", "next monday")
+    ///
+    /// Ambiguous times resolve to their next future occurrence.
+    pub fn extract_datetime(&self, text: &str) -> Option<DateTime<Local>> {
+        time_helpers::parse_datetime(&self.locale.rewrite_to_english(text), Local::now())
+    }
+
+    /// Extract a typed quantity from text
+    ///
+    /// Handles digits ("75", "75%"), spelled-out numbers ("fifty",
+    /// "twenty-five percent") and fractions ("a quarter" = 25%). Returns
+    /// `Entity::Percentage` when the quantity is marked as a percentage and
+    /// `Entity::Number` otherwise.
+    pub fn extract_quantity(&self, text: &str) -> Option<Entity> {
+        let lower = self.locale.rewrite_to_english(text).to_lowercase();
+        let padded = format!(" {} ", lower.replace('-', " "));
+
+        for (phrase, percent) in FRACTIONS {
+            if padded.contains(&format!(" {} ", phrase)) {
+                return Some(Entity::Percentage(*percent));
+            }
+        }
+
+        let tokens: Vec<&str> = padded.split_whitespace().collect();
+        for start in 0..tokens.len() {
+            let Some((value, used, marked)) = parse_number_at(&tokens[start..]) else {
+                continue;
+            };
+            let percent = marked
+                || matches!(tokens.get(start + used), Some(&"percent") | Some(&"%"));
+
+            return Some(if percent {
+                Entity::Percentage(value.round().clamp(0.0, 100.0) as u8)
+            } else {
+                Entity::Number(value)
+            });
+        }
+
+        None
+    }
+
+    /// Extract a relative adjustment size (e.g. "a bit" = 5, "a lot" = 25)
+    pub fn extract_adjustment(&self, text: &str) -> Option<i8> {
+        let padded = format!(" {} ", text.to_lowercase());
+        ADJUSTMENTS
+            .iter()
+            .find(|(phrase, _)| padded.contains(&format!(" {} ", phrase)))
+            .map(|(_, step)| *step)
+    }
+
     /// Extract folder name from text
     pub fn extract_folder_name(&self, text: &str) -> Option<String> {
         let filtered = self.remove_common_words(text);
@@ -149,6 +345,75 @@ impl EntityExtractor {
             .join(" ")
     }
 
+    /// Extract file search filters from a file query
+    ///
+    /// Recognizes categories ("videos", "photos"), extensions ("PDFs"), sizes
+    /// ("larger than 1GB") and recency ("from the last 2 weeks"). Returns
+    /// `file_type`, `extension`, `min_size`/`max_size` in bytes and
+    /// `modified_after` as a Unix timestamp, plus `file_name` holding whatever
+    /// text remains. Returns an empty map if no filter was found.
+    pub fn extract_file_filters(&self, text: &str) -> HashMap<String, String> {
+        let mut filters = HashMap::new();
+        let mut remaining = text.to_string();
+
+        if let Some(caps) = FILE_SIZE_REGEX.captures(text) {
+            let value: f64 = caps[3].parse().unwrap_or(0.0);
+            let bytes = (value * size_unit_bytes(&caps[4].to_lowercase()) as f64) as u64;
+            let key = if caps.get(1).is_some() {
+                "min_size"
+            } else {
+                "max_size"
+            };
+            filters.insert(key.to_string(), bytes.to_string());
+            remaining = remaining.replace(&caps[0], " ");
+        }
+
+        if let Some(caps) = FILE_AGE_REGEX.captures(text) {
+            let count: i64 = caps
+                .get(1)
+                .and_then(|m| m.as_str().parse().ok())
+                .unwrap_or(1);
+            let unit_days = match caps[2].to_lowercase().as_str() {
+                "week" => 7,
+                "month" => 30,
+                "year" => 365,
+                _ => 1,
+            };
+            let after = Local::now() - Duration::days(count * unit_days);
+            filters.insert("modified_after".to_string(), after.timestamp().to_string());
+            remaining = remaining.replace(&caps[0], " ");
+        }
+
+        let mut query_words = Vec::new();
+        for word in remaining.split_whitespace() {
+            let lower = word.to_lowercase();
+            let singular = lower.strip_suffix('s').unwrap_or(&lower);
+
+            if let Some((_, file_type)) = FILE_TYPE_WORDS.iter().find(|(w, _)| *w == lower) {
+                filters.insert("file_type".to_string(), file_type.to_string());
+            } else if FILE_EXTENSION_WORDS.contains(&singular) {
+                filters.insert("extension".to_string(), singular.to_string());
+            } else {
+                query_words.push(word);
+            }
+        }
+
+        if filters.is_empty() {
+            return filters;
+        }
+
+        let query = query_words
+            .into_iter()
+            .filter(|w| !FILE_QUERY_FILLERS.contains(&w.to_lowercase().as_str()))
+            .collect::<Vec<_>>()
+            .join(" ");
+        if !query.is_empty() {
+            filters.insert("file_name".to_string(), query);
+        }
+
+        filters
+    }
+
     /// Extract time of day (e.g., "3pm", "14:30")
     pub fn extract_time_of_day(&self, text: &str) -> Option<(u8, u8)> {
         // Pattern: "3pm", "3:30pm", "15:30"
@@ -179,6 +444,54 @@ impl EntityExtractor {
     }
 }
 
+/// Parse a number starting at the first token
+///
+/// Returns the value, the number of tokens consumed, and whether the number
+/// carried a trailing `%`.
+fn parse_number_at(tokens: &[&str]) -> Option<(f64, usize, bool)> {
+    let first = *tokens.first()?;
+
+    // Digits, optionally with a percent sign attached
+    let (digits, marked) = match first.strip_suffix('%') {
+        Some(d) => (d, true),
+        None => (first, false),
+    };
+    if let Ok(value) = digits.parse::<f64>() {
+        return Some((value, 1, marked));
+    }
+
+    // Spelled-out numbers up to the hundreds ("one hundred and five")
+    let mut total = 0.0;
+    let mut used = 0;
+    for (i, token) in tokens.iter().enumerate() {
+        if let Some(n) = UNITS.iter().position(|w| w == token) {
+            total += n as f64;
+        } else if let Some(n) = TENS.iter().position(|w| w == token) {
+            total += (n as f64 + 2.0) * 10.0;
+        } else if *token == "hundred" && used > 0 {
+            total *= 100.0;
+        } else if *token == "and" && used > 0 && i + 1 < tokens.len() {
+            continue;
+        } else {
+            break;
+        }
+        used = i + 1;
+    }
+
+    (used > 0).then_some((total, used, false))
+}
+
+/// Bytes per size unit as spoken ("gb", "mb", ...)
+fn size_unit_bytes(unit: &str) -> u64 {
+    match unit {
+        "kb" | "k" => 1024,
+        "mb" | "m" => 1024 * 1024,
+        "gb" | "g" => 1024 * 1024 * 1024,
+        "tb" | "t" => 1024 * 1024 * 1024 * 1024,
+        _ => 1,
+    }
+}
+
 impl Default for EntityExtractor {
     fn default() -> Self {
         Self::new()
@@ -189,6 +502,36 @@ impl Default for EntityExtractor {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_extract_file_filters() {
+        let extractor = EntityExtractor::new();
+
+        let filters = extractor.extract_file_filters("PDFs about taxes");
+        assert_eq!(filters.get("extension").map(String::as_str), Some("pdf"));
+        assert_eq!(filters.get("file_name").map(String::as_str), Some("taxes"));
+
+        let filters = extractor.extract_file_filters("videos bigger than 1GB");
+        assert_eq!(filters.get("file_type").map(String::as_str), Some("video"));
+        assert_eq!(
+            filters.get("min_size").map(String::as_str),
+            Some("1073741824")
+        );
+        assert!(!filters.contains_key("file_name"));
+
+        let filters =
+            extractor.extract_file_filters("photos smaller than 500 kb from the last week");
+        assert_eq!(filters.get("file_type").map(String::as_str), Some("image"));
+        assert_eq!(filters.get("max_size").map(String::as_str), Some("512000"));
+        let after: i64 = filters["modified_after"].parse().unwrap();
+        let week_ago = (Local::now() - Duration::days(7)).timestamp();
+        assert!((after - week_ago).abs() < 5);
+
+        // Plain names have no filters
+        for name in ["budget.pdf", "quarterly report"] {
+            assert!(extractor.extract_file_filters(name).is_empty());
+        }
+    }
+
     #[test]
     fn test_extract_app_name() {
         let extractor = EntityExtractor::new();
@@ -208,6 +551,14 @@ mod tests {
             extractor.extract_app_name("the chrome application"),
             Some("chrome".to_string())
         );
+
+        // Aliases normalize to the canonical name
+        let mut extractor = EntityExtractor::new();
+        extractor.add_app_aliases("Visual Studio Code", &["vs code".to_string()]);
+        assert_eq!(
+            extractor.extract_app_name("the vs code app"),
+            Some("Visual Studio Code".to_string())
+        );
     }
 
     #[test]
@@ -260,6 +611,98 @@ mod tests {
         assert_eq!(extractor.extract_percentage("150%"), Some(100)); // Capped at 100
     }
 
+    #[test]
+    fn test_extract_datetime() {
+        let extractor = EntityExtractor::new();
+        let now = Local::now();
+
+        for phrase in ["tomorrow at 9am", "in 2 hours", "next monday", "friday at 5pm"] {
+            let when = extractor.extract_datetime(phrase).unwrap();
+            assert!(when > now, "{} should be in the future", phrase);
+        }
+
+        assert!(extractor.extract_datetime("chrome").is_none());
+    }
+
+    #[test]
+    fn test_extract_quantity() {
+        let extractor = EntityExtractor::new();
+
+        assert_eq!(
+            extractor.extract_quantity("set volume to fifty"),
+            Some(Entity::Number(50.0))
+        );
+        assert_eq!(
+            extractor.extract_quantity("volume 75%"),
+            Some(Entity::Percentage(75))
+        );
+        assert_eq!(
+            extractor.extract_quantity("twenty-five percent"),
+            Some(Entity::Percentage(25))
+        );
+        assert_eq!(
+            extractor.extract_quantity("one hundred"),
+            Some(Entity::Number(100.0))
+        );
+        assert_eq!(
+            extractor.extract_quantity("a quarter"),
+            Some(Entity::Percentage(25))
+        );
+        assert_eq!(extractor.extract_quantity("turn it up a bit"), None);
+    }
+
+    #[test]
+    fn test_extract_spoken_duration() {
+        let extractor = EntityExtractor::new();
+
+        assert_eq!(
+            extractor.extract_spoken_duration("30 seconds"),
+            Some(Duration::seconds(30))
+        );
+        assert_eq!(
+            extractor.extract_spoken_duration("thirty seconds"),
+            Some(Duration::seconds(30))
+        );
+        assert_eq!(
+            extractor.extract_spoken_duration("a minute"),
+            Some(Duration::seconds(60))
+        );
+        assert_eq!(
+            extractor.extract_spoken_duration("half a minute"),
+            Some(Duration::seconds(30))
+        );
+        assert_eq!(extractor.extract_spoken_duration("a while"), None);
+    }
+
+    #[test]
+    fn test_spanish_numbers_and_dates() {
+        let extractor = EntityExtractor::new().with_locale(Locale::Spanish);
+
+        assert_eq!(
+            extractor.extract_quantity("pon el volumen al cincuenta"),
+            Some(Entity::Number(50.0))
+        );
+        assert_eq!(
+            extractor.extract_quantity("treinta y cinco por ciento"),
+            Some(Entity::Percentage(35))
+        );
+        assert_eq!(
+            extractor.extract_spoken_duration("diez segundos"),
+            Some(Duration::seconds(10))
+        );
+        assert!(extractor.extract_datetime("mañana a las 9").unwrap() > Local::now());
+        assert!(extractor.extract_datetime("en 2 horas").unwrap() > Local::now());
+    }
+
+    #[test]
+    fn test_extract_adjustment() {
+        let extractor = EntityExtractor::new();
+
+        assert_eq!(extractor.extract_adjustment("turn it up a bit"), Some(5));
+        assert_eq!(extractor.extract_adjustment("turn it down a lot"), Some(25));
+        assert_eq!(extractor.extract_adjustment("turn it up"), None);
+    }
+
     #[test]
     fn test_extract_time_of_day() {
         let extractor = EntityExtractor::new();
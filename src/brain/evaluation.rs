@@ -0,0 +1,173 @@
+//! Offline evaluation of intent classification
+//!
+//! Compares batch results against expected labels to track per-intent
+//! precision and recall across grammar or classifier changes.
+
+use crate::brain::command_parser::IntentType;
+use crate::brain::task_planner::TaskPlan;
+use crate::error::Result;
+use std::collections::HashMap;
+use std::fmt;
+
+/// Confusion counts for a single intent
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct IntentMetrics {
+    /// Predicted this intent and it was expected
+    pub true_positives: usize,
+
+    /// Predicted this intent but another was expected
+    pub false_positives: usize,
+
+    /// Expected this intent but something else was predicted
+    pub false_negatives: usize,
+}
+
+impl IntentMetrics {
+    /// Fraction of predictions of this intent that were correct
+    pub fn precision(&self) -> f64 {
+        let predicted = self.true_positives + self.false_positives;
+        if predicted == 0 {
+            return 0.0;
+        }
+        self.true_positives as f64 / predicted as f64
+    }
+
+    /// Fraction of expected occurrences of this intent that were found
+    pub fn recall(&self) -> f64 {
+        let expected = self.true_positives + self.false_negatives;
+        if expected == 0 {
+            return 0.0;
+        }
+        self.true_positives as f64 / expected as f64
+    }
+}
+
+/// Summary of batch classification results against expected labels
+#[derive(Debug, Clone, Default)]
+pub struct ClassificationReport {
+    /// Number of labeled examples
+    pub total: usize,
+
+    /// Examples whose predicted intent matched the label
+    pub correct: usize,
+
+    /// Examples that failed to produce a plan
+    pub errors: usize,
+
+    /// Per-intent confusion counts
+    pub per_intent: HashMap<IntentType, IntentMetrics>,
+}
+
+impl ClassificationReport {
+    /// Build a report from expected intents and `Brain::process_batch` results
+    ///
+    /// Results are paired with labels by position; a failed result counts
+    /// as a miss for its expected intent.
+    pub fn from_results(expected: &[IntentType], results: &[Result<TaskPlan>]) -> Self {
+        let mut report = Self::default();
+
+        for (label, result) in expected.iter().zip(results) {
+            report.total += 1;
+
+            let predicted = match result {
+                Ok(plan) => &plan.classification.intent,
+                Err(_) => {
+                    report.errors += 1;
+                    report.per_intent.entry(label.clone()).or_default().false_negatives += 1;
+                    continue;
+                }
+            };
+
+            if predicted == label {
+                report.correct += 1;
+                report.per_intent.entry(label.clone()).or_default().true_positives += 1;
+            } else {
+                report.per_intent.entry(label.clone()).or_default().false_negatives += 1;
+                report.per_intent.entry(predicted.clone()).or_default().false_positives += 1;
+            }
+        }
+
+        report
+    }
+
+    /// Fraction of examples classified correctly
+    pub fn accuracy(&self) -> f64 {
+        if self.total == 0 {
+            return 0.0;
+        }
+        self.correct as f64 / self.total as f64
+    }
+
+    /// Metrics for one intent, if it was expected or predicted
+    pub fn metrics(&self, intent: &IntentType) -> Option<&IntentMetrics> {
+        self.per_intent.get(intent)
+    }
+}
+
+impl fmt::Display for ClassificationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Accuracy: {:.1}% ({}/{}, {} errors)",
+            self.accuracy() * 100.0,
+            self.correct,
+            self.total,
+            self.errors
+        )?;
+
+        let mut intents: Vec<_> = self.per_intent.iter().collect();
+        intents.sort_by_key(|(intent, _)| format!("{:?}", intent));
+
+        for (intent, metrics) in intents {
+            writeln!(
+                f,
+                "  {:<20} precision {:>5.1}%  recall {:>5.1}%",
+                format!("{:?}", intent),
+                metrics.precision() * 100.0,
+                metrics.recall() * 100.0
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::brain::Brain;
+    use crate::config::BrainConfig;
+
+    #[test]
+    fn test_classification_report() {
+        let brain = Brain::new(&BrainConfig::default()).unwrap();
+        let texts: Vec<String> = ["open chrome", "close slack", "volume up", "open firefox"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let expected = vec![
+            IntentType::LaunchApp,
+            IntentType::CloseApp,
+            IntentType::VolumeControl,
+            // Deliberately mislabeled to exercise the error counts
+            IntentType::CloseApp,
+        ];
+
+        let results = brain.process_batch(&texts);
+        let report = ClassificationReport::from_results(&expected, &results);
+
+        assert_eq!(report.total, 4);
+        assert_eq!(report.correct, 3);
+        assert!((report.accuracy() - 0.75).abs() < 1e-9);
+
+        let launch = report.metrics(&IntentType::LaunchApp).unwrap();
+        assert_eq!(launch.true_positives, 1);
+        assert_eq!(launch.false_positives, 1);
+        assert!((launch.precision() - 0.5).abs() < 1e-9);
+
+        let close = report.metrics(&IntentType::CloseApp).unwrap();
+        assert!((close.recall() - 0.5).abs() < 1e-9);
+        assert!((close.precision() - 1.0).abs() < 1e-9);
+
+        assert!(report.to_string().starts_with("Accuracy: 75.0%"));
+    }
+}
@@ -4,11 +4,13 @@
 //! for fast matching. Supports hot-reloading and validation.
 
 use crate::brain::command_parser::IntentType;
+use crate::brain::locale::Locale;
 use crate::error::{LunaError, Result};
 use regex::{Regex, RegexSet};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::path::Path;
+use std::fmt;
+use std::path::{Path, PathBuf};
 use tracing::{debug, info, warn};
 
 /// Grammar configuration loaded from YAML
@@ -22,6 +24,10 @@ pub struct GrammarConfig {
 
     #[serde(default)]
     pub slots: Vec<SlotDefinition>,
+
+    /// Intent name -> keyword -> confidence boost
+    #[serde(default)]
+    pub keyword_boosts: HashMap<String, HashMap<String, f32>>,
 }
 
 /// Intent definition with patterns
@@ -38,6 +44,10 @@ pub struct IntentDefinition {
 pub struct PatternDefinition {
     pub pattern: String,
     pub entities: HashMap<String, String>,
+
+    /// Overrides the intent priority for this pattern
+    #[serde(default)]
+    pub priority: Option<u32>,
 }
 
 /// Slot (entity) type definition
@@ -71,6 +81,41 @@ pub struct CompiledGrammar {
 
     /// Slot definitions
     slots: HashMap<String, SlotDefinition>,
+
+    /// Warnings found while compiling
+    diagnostics: Vec<GrammarWarning>,
+
+    /// Keyword boosts to seed the classifier with
+    keyword_boosts: Vec<(IntentType, String, f32)>,
+}
+
+/// Problem detected while compiling a grammar
+#[derive(Debug, Clone, PartialEq)]
+pub enum GrammarWarning {
+    /// Pattern can never win because a higher-priority pattern matches everything it does
+    ShadowedPattern {
+        pattern: String,
+        intent: IntentType,
+        shadowed_by: String,
+        shadowed_by_intent: IntentType,
+    },
+}
+
+impl fmt::Display for GrammarWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GrammarWarning::ShadowedPattern {
+                pattern,
+                intent,
+                shadowed_by,
+                shadowed_by_intent,
+            } => write!(
+                f,
+                "Pattern '{}' ({:?}) is unreachable: shadowed by '{}' ({:?})",
+                pattern, intent, shadowed_by, shadowed_by_intent
+            ),
+        }
+    }
 }
 
 /// A single compiled pattern
@@ -110,6 +155,44 @@ impl GrammarConfig {
         Ok(config)
     }
 
+    /// Grammar file for `locale` next to `path`
+    ///
+    /// `brain_patterns.yaml` becomes `brain_patterns.es.yaml`; English uses
+    /// `path` unchanged.
+    pub fn locale_path(path: &Path, locale: Locale) -> PathBuf {
+        if locale == Locale::English {
+            return path.to_path_buf();
+        }
+        let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+        let file_name = match path.extension() {
+            Some(ext) => format!("{}.{}.{}", stem, locale.code(), ext.to_string_lossy()),
+            None => format!("{}.{}", stem, locale.code()),
+        };
+        path.with_file_name(file_name)
+    }
+
+    /// Pick the grammar file for `locale`, falling back to the English file
+    /// at `path` when the locale has none
+    ///
+    /// Returns the file with the locale it holds.
+    pub fn locale_file(path: &Path, locale: Locale) -> (PathBuf, Locale) {
+        let localized = Self::locale_path(path, locale);
+        if locale == Locale::English || localized.exists() {
+            return (localized, locale);
+        }
+        warn!(
+            "No {} grammar at {:?}, falling back to English",
+            locale, localized
+        );
+        (path.to_path_buf(), Locale::English)
+    }
+
+    /// Load the grammar for `locale`, falling back to English
+    pub fn load_for_locale<P: AsRef<Path>>(path: P, locale: Locale) -> Result<(Self, Locale)> {
+        let (file, loaded) = Self::locale_file(path.as_ref(), locale);
+        Ok((Self::load_from_file(file)?, loaded))
+    }
+
     /// Default built-in grammar as fallback
     pub fn default_grammar() -> Self {
         Self {
@@ -121,6 +204,7 @@ impl GrammarConfig {
                     patterns: vec![PatternDefinition {
                         pattern: r"^(?:open|launch|start|run)\s+(.+)$".to_string(),
                         entities: HashMap::from([("app_name".to_string(), "$1".to_string())]),
+                        priority: None,
                     }],
                     examples: vec!["open chrome".to_string()],
                 },
@@ -130,19 +214,25 @@ impl GrammarConfig {
                     patterns: vec![PatternDefinition {
                         pattern: r"^(?:close|quit|exit|kill)\s+(.+)$".to_string(),
                         entities: HashMap::from([("app_name".to_string(), "$1".to_string())]),
+                        priority: None,
                     }],
                     examples: vec!["close chrome".to_string()],
                 },
             ],
             synonyms: HashMap::new(),
             slots: Vec::new(),
+            keyword_boosts: HashMap::new(),
         }
     }
 
     /// Compile to executable grammar
+    ///
+    /// Patterns are ordered by priority (pattern override, else intent priority),
+    /// keeping declaration order for ties. Unreachable patterns are reported as
+    /// warnings and exposed via `CompiledGrammar::diagnostics`.
     pub fn compile(&self) -> Result<CompiledGrammar> {
-        let mut patterns = Vec::new();
-        let mut pattern_strings = Vec::new();
+        // Each pattern paired with its intent's examples (used to detect shadowing)
+        let mut entries: Vec<(CompiledPattern, &Vec<String>)> = Vec::new();
 
         // Compile each intent
         for intent_def in &self.intents {
@@ -157,18 +247,32 @@ impl GrammarConfig {
                     ))
                 })?;
 
-                pattern_strings.push(pattern_def.pattern.clone());
-
-                patterns.push(CompiledPattern {
-                    regex: regex.clone(),
+                let pattern = CompiledPattern {
+                    regex,
                     intent: intent.clone(),
                     entity_map: pattern_def.entities.clone(),
-                    priority: intent_def.priority,
+                    priority: pattern_def.priority.unwrap_or(intent_def.priority),
                     original_pattern: pattern_def.pattern.clone(),
-                });
+                };
+                entries.push((pattern, &intent_def.examples));
             }
         }
 
+        // Stable sort keeps declaration order among equal priorities
+        entries.sort_by_key(|(pattern, _)| std::cmp::Reverse(pattern.priority));
+        let (patterns, examples): (Vec<CompiledPattern>, Vec<&Vec<String>>) =
+            entries.into_iter().unzip();
+
+        let diagnostics = Self::find_shadowed(&patterns, &examples);
+        for warning in &diagnostics {
+            warn!("{}", warning);
+        }
+
+        let pattern_strings: Vec<&str> = patterns
+            .iter()
+            .map(|p| p.original_pattern.as_str())
+            .collect();
+
         // Build RegexSet for fast pre-filtering
         let regex_set = RegexSet::new(&pattern_strings)
             .map_err(|e| LunaError::Config(format!("Failed to build RegexSet: {}", e)))?;
@@ -180,6 +284,16 @@ impl GrammarConfig {
             .map(|s| (s.name.clone(), s.clone()))
             .collect();
 
+        let mut keyword_boosts = Vec::new();
+        for (intent_name, keywords) in &self.keyword_boosts {
+            let intent = Self::parse_intent_name(intent_name)?;
+            for (keyword, weight) in keywords {
+                keyword_boosts.push((intent.clone(), keyword.clone(), *weight));
+            }
+        }
+        // Stable order regardless of map iteration
+        keyword_boosts.sort_by(|a, b| a.1.cmp(&b.1));
+
         info!("✅ Compiled {} patterns into grammar", patterns.len());
 
         Ok(CompiledGrammar {
@@ -187,9 +301,47 @@ impl GrammarConfig {
             patterns,
             synonyms: self.synonyms.clone(),
             slots,
+            diagnostics,
+            keyword_boosts,
         })
     }
 
+    /// Find patterns that can never win against an earlier (higher-priority) one
+    ///
+    /// A pattern is shadowed when an earlier pattern is textually identical, or
+    /// when every example it matches is also matched by a single earlier pattern.
+    fn find_shadowed(
+        patterns: &[CompiledPattern],
+        examples: &[&Vec<String>],
+    ) -> Vec<GrammarWarning> {
+        let mut warnings = Vec::new();
+
+        for (i, pattern) in patterns.iter().enumerate() {
+            let witnesses: Vec<String> = examples[i]
+                .iter()
+                .map(|e| e.to_lowercase())
+                .filter(|e| pattern.regex.is_match(e))
+                .collect();
+
+            let shadow = patterns[..i].iter().find(|earlier| {
+                earlier.original_pattern == pattern.original_pattern
+                    || (!witnesses.is_empty()
+                        && witnesses.iter().all(|w| earlier.regex.is_match(w)))
+            });
+
+            if let Some(earlier) = shadow {
+                warnings.push(GrammarWarning::ShadowedPattern {
+                    pattern: pattern.original_pattern.clone(),
+                    intent: pattern.intent.clone(),
+                    shadowed_by: earlier.original_pattern.clone(),
+                    shadowed_by_intent: earlier.intent.clone(),
+                });
+            }
+        }
+
+        warnings
+    }
+
     /// Parse intent name string to IntentType
     fn parse_intent_name(name: &str) -> Result<IntentType> {
         match name {
@@ -199,6 +351,7 @@ impl GrammarConfig {
             "OpenFolder" => Ok(IntentType::OpenFolder),
             "SystemControl" => Ok(IntentType::SystemControl),
             "VolumeControl" => Ok(IntentType::VolumeControl),
+            "BrightnessControl" => Ok(IntentType::BrightnessControl),
             "WindowManagement" => Ok(IntentType::WindowManagement),
             "MediaControl" => Ok(IntentType::MediaControl),
             "SearchWeb" => Ok(IntentType::SearchWeb),
@@ -207,6 +360,12 @@ impl GrammarConfig {
             "Question" => Ok(IntentType::Question),
             "GetTime" => Ok(IntentType::GetTime),
             "GetDate" => Ok(IntentType::GetDate),
+            "Clipboard" => Ok(IntentType::Clipboard),
+            "RunCommand" => Ok(IntentType::RunCommand),
+            "VirtualDesktop" => Ok(IntentType::VirtualDesktop),
+            "Power" => Ok(IntentType::Power),
+            "ProcessControl" => Ok(IntentType::ProcessControl),
+            "Cancel" => Ok(IntentType::Cancel),
             _ => {
                 warn!("Unknown intent name: {}, defaulting to Unknown", name);
                 Ok(IntentType::Unknown)
@@ -261,6 +420,16 @@ impl CompiledGrammar {
     pub fn pattern_count(&self) -> usize {
         self.patterns.len()
     }
+
+    /// Warnings found while compiling (e.g. shadowed patterns)
+    pub fn diagnostics(&self) -> Vec<GrammarWarning> {
+        self.diagnostics.clone()
+    }
+
+    /// Keyword boosts as (intent, keyword, weight), sorted by keyword
+    pub fn keyword_boosts(&self) -> &[(IntentType, String, f32)] {
+        &self.keyword_boosts
+    }
 }
 
 #[cfg(test)]
@@ -319,6 +488,76 @@ slots: []
         let grammar = GrammarConfig::load_from_file(temp_file.path()).unwrap();
         assert_eq!(grammar.intents.len(), 1);
         assert_eq!(grammar.intents[0].name, "LaunchApp");
+        assert!(grammar.keyword_boosts.is_empty());
+    }
+
+    #[test]
+    fn test_keyword_boosts_section() {
+        let yaml = r#"
+version: "1.0"
+intents: []
+keyword_boosts:
+  FindFile:
+    orion: 0.2
+    roadmap: 0.1
+"#;
+        let config: GrammarConfig = serde_yaml::from_str(yaml).unwrap();
+        let compiled = config.compile().unwrap();
+        assert_eq!(
+            compiled.keyword_boosts(),
+            [
+                (IntentType::FindFile, "orion".to_string(), 0.2),
+                (IntentType::FindFile, "roadmap".to_string(), 0.1),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pattern_priority_and_shadowing() {
+        let yaml = r#"
+version: "1.0"
+intents:
+  - name: LaunchApp
+    priority: 80
+    patterns:
+      - pattern: "^open (.+)$"
+        entities:
+          app_name: "$1"
+    examples:
+      - "open chrome"
+  - name: OpenFolder
+    priority: 80
+    patterns:
+      - pattern: "^open (.+) folder$"
+        entities:
+          folder_name: "$1"
+        priority: 90
+      - pattern: "^open the (.+) folder$"
+        entities:
+          folder_name: "$1"
+    examples:
+      - "open the downloads folder"
+"#;
+        let config: GrammarConfig = serde_yaml::from_str(yaml).unwrap();
+        let compiled = config.compile().unwrap();
+
+        // Pattern-level priority beats the generic launch pattern
+        let matches = compiled.match_text("open downloads folder");
+        assert_eq!(matches[0].1.intent, IntentType::OpenFolder);
+        assert_eq!(matches[0].1.priority, 90);
+
+        // "^open the (.+) folder$" can never win against "^open (.+) folder$"
+        let diagnostics = compiled.diagnostics();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0],
+            GrammarWarning::ShadowedPattern {
+                pattern: "^open the (.+) folder$".to_string(),
+                intent: IntentType::OpenFolder,
+                shadowed_by: "^open (.+) folder$".to_string(),
+                shadowed_by_intent: IntentType::OpenFolder,
+            }
+        );
     }
 
     #[test]
@@ -333,6 +572,8 @@ slots: []
             patterns: vec![],
             synonyms,
             slots: HashMap::new(),
+            diagnostics: vec![],
+            keyword_boosts: vec![],
         };
 
         let expansions = compiled.expand_synonyms("apps", "chrome");
@@ -357,10 +598,45 @@ slots: []
             patterns: vec![],
             synonyms: HashMap::new(),
             slots,
+            diagnostics: vec![],
+            keyword_boosts: vec![],
         };
 
         assert!(compiled.validate_entity("action", "up"));
         assert!(compiled.validate_entity("action", "UP"));
         assert!(!compiled.validate_entity("action", "invalid"));
     }
+
+    #[test]
+    fn test_locale_file_falls_back_to_english() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("brain_patterns.yaml");
+        let spanish = dir.path().join("brain_patterns.es.yaml");
+        assert_eq!(GrammarConfig::locale_path(&path, Locale::Spanish), spanish);
+        assert_eq!(GrammarConfig::locale_path(&path, Locale::English), path);
+
+        // Missing locale file: English grammar
+        assert_eq!(
+            GrammarConfig::locale_file(&path, Locale::Spanish),
+            (path.clone(), Locale::English)
+        );
+
+        let yaml = r#"
+version: "1.0"
+intents:
+  - name: LaunchApp
+    priority: 100
+    patterns:
+      - pattern: "^abre (.+)$"
+        entities:
+          app_name: "$1"
+    examples: ["abre chrome"]
+"#;
+        std::fs::write(&spanish, yaml).unwrap();
+        let (grammar, locale) = GrammarConfig::load_for_locale(&path, Locale::Spanish).unwrap();
+        assert_eq!(locale, Locale::Spanish);
+        let compiled = grammar.compile().unwrap();
+        let matches = compiled.match_text("abre chrome");
+        assert_eq!(matches[0].1.intent, IntentType::LaunchApp);
+    }
 }
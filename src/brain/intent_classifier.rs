@@ -3,13 +3,16 @@
 //! Classifies commands with confidence scores using pattern matching and heuristics.
 
 use crate::brain::command_parser::{IntentType, ParsedCommand};
+use crate::brain::locale::Locale;
+use crate::brain::types::ConfidenceFactor;
 use crate::error::Result;
 use crate::utils::string_matching;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use tracing::{debug, info};
 
 /// Classification result with confidence and fallback intents
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ClassificationResult {
     /// Primary intent
     pub intent: IntentType,
@@ -19,12 +22,103 @@ pub struct ClassificationResult {
     pub entities: HashMap<String, String>,
     /// Alternative intents with their confidence scores
     pub alternatives: Vec<(IntentType, f32)>,
+    /// Signals behind the confidence score (filled in by ranking)
+    pub factors: Vec<ConfidenceFactor>,
+}
+
+impl ClassificationResult {
+    /// Best runner-up scoring at least `min_score`, as a classification
+    ///
+    /// The current intent becomes an alternative of the result.
+    pub fn best_alternative(&self, min_score: f32) -> Option<ClassificationResult> {
+        let index = self
+            .alternatives
+            .iter()
+            .enumerate()
+            .filter(|(_, (intent, score))| *score >= min_score && *intent != self.intent)
+            .max_by(|(_, (_, a)), (_, (_, b))| a.total_cmp(b))
+            .map(|(index, _)| index)?;
+
+        let mut result = self.clone();
+        result.promote_alternative(index);
+        let promoted = result.intent.clone();
+        result
+            .alternatives
+            .retain(|(intent, _)| *intent != promoted);
+        Some(result)
+    }
+
+    /// Swap in the alternative at `index`, moving the target entity across
+    fn promote_alternative(&mut self, index: usize) {
+        let (intent, score) = self.alternatives.remove(index);
+
+        let target = target_key(&self.intent).and_then(|key| self.entities.get(key).cloned());
+        if let (Some(key), Some(value)) = (target_key(&intent), target) {
+            self.entities.entry(key.to_string()).or_insert(value);
+        }
+
+        let previous = std::mem::replace(&mut self.intent, intent);
+        self.alternatives.push((previous, self.confidence));
+        self.confidence = score;
+    }
+}
+
+/// Phrases that cancel the current request outright
+const CANCEL_PHRASES: &[&str] = &[
+    "cancel",
+    "never mind",
+    "nevermind",
+    "forget it",
+    "forget that",
+    "forget about it",
+    "abort",
+];
+
+/// Leading words that negate the rest of the command ("don't open chrome")
+const NEGATORS: &[&str] = &["don't", "dont", "do not", "never"];
+
+/// Filler words that may precede a cancellation ("stop, never mind")
+const CANCEL_FILLERS: &[&str] = &["stop", "no", "wait", "oh", "actually", "okay", "ok"];
+
+/// Most confidence keyword boosts can add to one intent, however many match
+pub const MAX_KEYWORD_BOOST: f32 = 0.3;
+
+/// Entity holding the main target of each intent, used when a keyword boost
+/// moves a command to another intent
+fn target_key(intent: &IntentType) -> Option<&'static str> {
+    match intent {
+        IntentType::LaunchApp | IntentType::CloseApp => Some("app_name"),
+        IntentType::FindFile => Some("file_name"),
+        IntentType::OpenFolder => Some("folder_name"),
+        IntentType::SearchWeb | IntentType::Question => Some("query"),
+        _ => None,
+    }
+}
+
+/// Domain word that nudges classification toward an intent
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeywordBoost {
+    /// Intent the keyword points to
+    pub intent: IntentType,
+    /// Lowercase word or phrase, matched on word boundaries
+    pub keyword: String,
+    /// Confidence added when the keyword is present
+    pub weight: f32,
 }
 
 /// Intent classifier with confidence scoring
 pub struct IntentClassifier {
     /// Application database for app name validation
     known_apps: Vec<String>,
+
+    /// Lowercase alias -> canonical app name
+    app_aliases: HashMap<String, String>,
+
+    /// Domain vocabulary boosts, in registration order
+    keyword_boosts: Vec<KeywordBoost>,
+
+    /// Language for cancellation phrases
+    locale: Locale,
 }
 
 impl IntentClassifier {
@@ -32,16 +126,53 @@ impl IntentClassifier {
     pub fn new() -> Self {
         Self {
             known_apps: Self::default_known_apps(),
+            app_aliases: HashMap::new(),
+            keyword_boosts: Vec::new(),
+            locale: Locale::English,
         }
     }
 
+    /// Also recognize cancellations spoken in `locale`
+    pub fn with_locale(mut self, locale: Locale) -> Self {
+        self.locale = locale;
+        self
+    }
+
     /// Classify a parsed command and compute confidence
     pub fn classify(&self, command: &ParsedCommand) -> Result<ClassificationResult> {
+        // Negation pass: "don't open chrome" / "never mind" cancel the request
+        if Self::is_cancellation_in(&command.original_text, self.locale) {
+            info!("Negated/cancelled command: \"{}\"", command.original_text);
+
+            let mut alternatives = Vec::new();
+            if command.intent != IntentType::Unknown && command.intent != IntentType::Cancel {
+                alternatives.push((command.intent.clone(), command.confidence * 0.1));
+            }
+
+            return Ok(ClassificationResult {
+                intent: IntentType::Cancel,
+                confidence: 0.95,
+                entities: HashMap::new(),
+                alternatives,
+                factors: Vec::new(),
+            });
+        }
+
         let mut confidence = command.confidence;
         let mut alternatives = Vec::new();
 
+        // Normalize app aliases to their canonical name
+        let mut entities = command.entities.clone();
+        if let Some(canonical) = entities
+            .get("app_name")
+            .and_then(|name| self.canonical_app_name(name))
+        {
+            debug!("Normalized app alias to: {}", canonical);
+            entities.insert("app_name".to_string(), canonical.to_string());
+        }
+
         // Boost confidence if entities are validated
-        if let Some(app_name) = command.entities.get("app_name") {
+        if let Some(app_name) = entities.get("app_name") {
             if self.validate_app_name(app_name) {
                 confidence = (confidence * 1.1).min(1.0);
                 debug!("Boosted confidence for known app: {}", app_name);
@@ -64,17 +195,145 @@ impl IntentClassifier {
         // Add context-based alternatives
         alternatives.extend(self.compute_alternatives(&command.intent, &command.entities));
 
+        let mut result = ClassificationResult {
+            intent: command.intent.clone(),
+            confidence,
+            entities,
+            alternatives,
+            factors: Vec::new(),
+        };
+        self.apply_keyword_boosts(&mut result, &command.original_text);
+
         info!(
             "Classified intent: {:?} with confidence: {:.2}",
-            command.intent, confidence
+            result.intent, result.confidence
         );
 
-        Ok(ClassificationResult {
-            intent: command.intent.clone(),
-            confidence,
-            entities: command.entities.clone(),
-            alternatives,
-        })
+        Ok(result)
+    }
+
+    /// Boost intents whose keywords appear in the text
+    ///
+    /// Each intent's boost is the sum of its matching keyword weights, capped
+    /// at `MAX_KEYWORD_BOOST`. Boosted intents without a score yet join the
+    /// alternatives, and an alternative that ends up ahead of the primary
+    /// intent replaces it, taking over the primary's target entity.
+    fn apply_keyword_boosts(&self, result: &mut ClassificationResult, text: &str) {
+        if self.keyword_boosts.is_empty() {
+            return;
+        }
+
+        let text = format!(" {} ", string_matching::normalize(text));
+        let mut boosts: HashMap<&IntentType, f32> = HashMap::new();
+        for boost in &self.keyword_boosts {
+            if text.contains(&format!(" {} ", boost.keyword)) {
+                *boosts.entry(&boost.intent).or_default() += boost.weight;
+            }
+        }
+        if boosts.is_empty() {
+            return;
+        }
+        let boost_for =
+            |intent: &IntentType| boosts.get(intent).map_or(0.0, |b| b.min(MAX_KEYWORD_BOOST));
+
+        result.confidence = (result.confidence + boost_for(&result.intent)).min(1.0);
+        for (intent, score) in &mut result.alternatives {
+            *score = (*score + boost_for(intent)).min(1.0);
+        }
+        for intent in boosts.keys() {
+            let scored = **intent == result.intent
+                || result.alternatives.iter().any(|(alt, _)| alt == *intent);
+            if !scored {
+                let score = boost_for(intent);
+                result.alternatives.push(((*intent).clone(), score));
+            }
+        }
+
+        let best = result
+            .alternatives
+            .iter()
+            .enumerate()
+            .max_by(|(_, (_, a)), (_, (_, b))| a.total_cmp(b))
+            .filter(|(_, (_, score))| *score > result.confidence)
+            .map(|(index, _)| index);
+        if let Some(index) = best {
+            let (intent, score) = &result.alternatives[index];
+            debug!(
+                "Keyword boost moved {:?} ({:.2}) to {:?} ({:.2})",
+                result.intent, result.confidence, intent, score
+            );
+            result.promote_alternative(index);
+        }
+    }
+
+    /// Boost `intent` when `keyword` is spoken, e.g. a project name for `FindFile`
+    ///
+    /// Weights add up across matching keywords, but one intent never gains
+    /// more than `MAX_KEYWORD_BOOST`. Re-adding a keyword replaces its weight.
+    pub fn add_keyword_boost(&mut self, intent: IntentType, keyword: &str, weight: f32) {
+        let keyword = string_matching::normalize(keyword);
+        if keyword.is_empty() {
+            return;
+        }
+        let weight = weight.clamp(0.0, MAX_KEYWORD_BOOST);
+
+        match self
+            .keyword_boosts
+            .iter_mut()
+            .find(|boost| boost.intent == intent && boost.keyword == keyword)
+        {
+            Some(existing) => existing.weight = weight,
+            None => self.keyword_boosts.push(KeywordBoost {
+                intent,
+                keyword,
+                weight,
+            }),
+        }
+    }
+
+    /// Registered keyword boosts, in registration order
+    pub fn keyword_boosts(&self) -> &[KeywordBoost] {
+        &self.keyword_boosts
+    }
+
+    /// Check whether text negates or cancels the request
+    ///
+    /// Matches cancellation phrases ("cancel that", "never mind"), optionally after
+    /// fillers like "stop" or "no", and leading negators ("don't open chrome").
+    pub fn is_cancellation(text: &str) -> bool {
+        Self::is_cancellation_in(text, Locale::English)
+    }
+
+    /// Check for a cancellation in English or in `locale`
+    ///
+    /// "olvídalo" and "no abras chrome" cancel in Spanish.
+    pub fn is_cancellation_in(text: &str, locale: Locale) -> bool {
+        let words: Vec<String> = text
+            .to_lowercase()
+            .split_whitespace()
+            .map(|w| w.trim_matches(|c: char| c.is_ascii_punctuation()).to_string())
+            .filter(|w| !w.is_empty())
+            .collect();
+
+        // Spanish "no" negates rather than fills ("no abras chrome")
+        let start = words
+            .iter()
+            .take_while(|w| {
+                CANCEL_FILLERS.contains(&w.as_str()) && !locale.negators().contains(&w.as_str())
+            })
+            .count();
+        let rest = words[start..].join(" ");
+
+        if rest.is_empty() {
+            return false;
+        }
+
+        let starts_with = |phrase: &str| rest == phrase || rest.starts_with(&format!("{} ", phrase));
+
+        // A bare negator ("no", "never") isn't a cancellation on its own
+        let mut phrases = CANCEL_PHRASES.iter().chain(locale.cancel_phrases());
+        let mut negators = NEGATORS.iter().chain(locale.negators());
+        phrases.any(|p| starts_with(p)) || negators.any(|n| rest.len() > n.len() && starts_with(n))
     }
 
     /// Validate if a string is likely an app name
@@ -181,6 +440,22 @@ impl IntentClassifier {
         }
     }
 
+    /// Add a known app along with aliases that normalize to it
+    pub fn add_known_app_with_aliases(&mut self, app_name: String, aliases: Vec<String>) {
+        for alias in aliases {
+            self.app_aliases
+                .insert(alias.trim().to_lowercase(), app_name.clone());
+        }
+        self.add_known_app(app_name);
+    }
+
+    /// Canonical app name for an alias, if one is registered
+    pub fn canonical_app_name(&self, name: &str) -> Option<&str> {
+        self.app_aliases
+            .get(&name.trim().to_lowercase())
+            .map(|s| s.as_str())
+    }
+
     /// Bulk add known apps
     pub fn add_known_apps(&mut self, apps: Vec<String>) {
         for app in apps {
@@ -235,6 +510,42 @@ mod tests {
         assert_eq!(result.confidence, 0.9);
     }
 
+    fn classify_text(text: &str) -> ClassificationResult {
+        let parsed = crate::brain::command_parser::CommandParser::new()
+            .parse(text)
+            .unwrap();
+        IntentClassifier::new().classify(&parsed).unwrap()
+    }
+
+    #[test]
+    fn test_negated_command_cancels() {
+        let result = classify_text("don't open chrome");
+        assert_eq!(result.intent, IntentType::Cancel);
+    }
+
+    #[test]
+    fn test_cancel_phrases() {
+        assert_eq!(classify_text("cancel that").intent, IntentType::Cancel);
+        assert_eq!(classify_text("never mind").intent, IntentType::Cancel);
+        assert_eq!(classify_text("stop, never mind").intent, IntentType::Cancel);
+
+        // Plain commands are left alone
+        assert_eq!(classify_text("stop").intent, IntentType::MediaControl);
+        assert_eq!(classify_text("open chrome").intent, IntentType::LaunchApp);
+    }
+
+    #[test]
+    fn test_spanish_cancellations() {
+        let es = Locale::Spanish;
+        assert!(IntentClassifier::is_cancellation_in("olvídalo", es));
+        assert!(IntentClassifier::is_cancellation_in("no abras chrome", es));
+        assert!(IntentClassifier::is_cancellation_in("never mind", es));
+        assert!(!IntentClassifier::is_cancellation_in("abre chrome", es));
+
+        // English alone doesn't know Spanish negation
+        assert!(!IntentClassifier::is_cancellation("no abras chrome"));
+    }
+
     #[test]
     fn test_looks_like_filename() {
         let classifier = IntentClassifier::new();
@@ -245,6 +556,27 @@ mod tests {
         assert!(!classifier.looks_like_filename("some text"));
     }
 
+    #[test]
+    fn test_app_alias_normalization() {
+        let mut classifier = IntentClassifier::new();
+        classifier.add_known_app_with_aliases(
+            "Visual Studio Code".to_string(),
+            vec!["vs code".to_string(), "VSCode".to_string()],
+        );
+
+        let mut entities = HashMap::new();
+        entities.insert("app_name".to_string(), "vscode".to_string());
+        let command = create_test_command(IntentType::LaunchApp, entities);
+
+        let result = classifier.classify(&command).unwrap();
+        assert_eq!(result.intent, IntentType::LaunchApp);
+        assert_eq!(
+            result.entities.get("app_name"),
+            Some(&"Visual Studio Code".to_string())
+        );
+        assert_eq!(classifier.canonical_app_name("chrome"), None);
+    }
+
     #[test]
     fn test_add_known_app() {
         let mut classifier = IntentClassifier::new();
@@ -254,6 +586,63 @@ mod tests {
         assert!(classifier.validate_app_name("MyApp")); // Case insensitive
     }
 
+    fn project_command(confidence: f32) -> ParsedCommand {
+        let mut entities = HashMap::new();
+        entities.insert("app_name".to_string(), "orion roadmap".to_string());
+        ParsedCommand {
+            intent: IntentType::LaunchApp,
+            entities,
+            original_text: "open orion roadmap".to_string(),
+            confidence,
+        }
+    }
+
+    #[test]
+    fn test_keyword_boost_flips_borderline_command() {
+        let mut classifier = IntentClassifier::new();
+        classifier.add_keyword_boost(IntentType::FindFile, "Orion", 0.25);
+        assert_eq!(classifier.keyword_boosts().len(), 1);
+        assert_eq!(classifier.keyword_boosts()[0].keyword, "orion");
+
+        let result = classifier.classify(&project_command(0.5)).unwrap();
+        assert_eq!(result.intent, IntentType::FindFile);
+        assert!((result.confidence - 0.55).abs() < 1e-6);
+        assert_eq!(
+            result.entities.get("file_name"),
+            Some(&"orion roadmap".to_string())
+        );
+        assert!(result
+            .alternatives
+            .iter()
+            .any(|(intent, _)| *intent == IntentType::LaunchApp));
+
+        // Unboosted text is untouched
+        let result = classifier
+            .classify(&create_test_command(IntentType::LaunchApp, HashMap::new()))
+            .unwrap();
+        assert_eq!(result.intent, IntentType::LaunchApp);
+    }
+
+    #[test]
+    fn test_keyword_boosts_are_capped() {
+        let mut classifier = IntentClassifier::new();
+        classifier.add_keyword_boost(IntentType::FindFile, "orion", 0.25);
+        classifier.add_keyword_boost(IntentType::FindFile, "roadmap", 0.25);
+        classifier.add_keyword_boost(IntentType::FindFile, "roadmap", 5.0);
+        assert_eq!(classifier.keyword_boosts().len(), 2);
+        assert_eq!(classifier.keyword_boosts()[1].weight, MAX_KEYWORD_BOOST);
+
+        // Both keywords match, but together they add at most the cap
+        let result = classifier.classify(&project_command(0.5)).unwrap();
+        assert_eq!(result.intent, IntentType::FindFile);
+        assert!((result.confidence - (0.3 + MAX_KEYWORD_BOOST)).abs() < 1e-6);
+
+        // A confident command keeps its intent
+        let result = classifier.classify(&project_command(0.9)).unwrap();
+        assert_eq!(result.intent, IntentType::LaunchApp);
+        assert!((result.confidence - 0.9).abs() < 1e-6);
+    }
+
     #[test]
     fn test_alternatives() {
         let classifier = IntentClassifier::new();
@@ -0,0 +1,210 @@
+//! Command language support
+//!
+//! English is built in. Other locales add a grammar file
+//! (`brain_patterns.<lang>.yaml`), cancellation phrases, and a word table
+//! that rewrites spoken numbers and dates into English so the existing
+//! number and date parsers can read them.
+
+use std::borrow::Cow;
+use std::fmt;
+
+/// Language commands are spoken in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Locale {
+    /// English (built-in patterns)
+    #[default]
+    English,
+    /// Spanish
+    Spanish,
+}
+
+/// Spanish phrases rewritten into English before number and date parsing,
+/// longest first so "por la mañana" wins over "mañana"
+const SPANISH_WORDS: &[(&str, &str)] = &[
+    ("por la mañana", "morning"),
+    ("por la tarde", "afternoon"),
+    ("por la noche", "evening"),
+    ("esta noche", "tonight"),
+    ("por ciento", "percent"),
+    ("que viene", "next"),
+    ("a las", "at"),
+    ("a la", "at"),
+    ("mañana", "tomorrow"),
+    ("hoy", "today"),
+    ("mediodía", "noon"),
+    ("medianoche", "midnight"),
+    ("próximo", "next"),
+    ("próxima", "next"),
+    ("lunes", "monday"),
+    ("martes", "tuesday"),
+    ("miércoles", "wednesday"),
+    ("jueves", "thursday"),
+    ("viernes", "friday"),
+    ("sábado", "saturday"),
+    ("domingo", "sunday"),
+    ("en", "in"),
+    ("el", "on"),
+    ("segundos", "seconds"),
+    ("segundo", "second"),
+    ("minutos", "minutes"),
+    ("minuto", "minute"),
+    ("horas", "hours"),
+    ("hora", "hour"),
+    ("días", "days"),
+    ("día", "day"),
+    ("un", "a"),
+    ("una", "a"),
+    ("medio", "half"),
+    ("media", "half"),
+    ("cero", "zero"),
+    ("uno", "one"),
+    ("dos", "two"),
+    ("tres", "three"),
+    ("cuatro", "four"),
+    ("cinco", "five"),
+    ("seis", "six"),
+    ("siete", "seven"),
+    ("ocho", "eight"),
+    ("nueve", "nine"),
+    ("diez", "ten"),
+    ("once", "eleven"),
+    ("doce", "twelve"),
+    ("trece", "thirteen"),
+    ("catorce", "fourteen"),
+    ("quince", "fifteen"),
+    ("dieciséis", "sixteen"),
+    ("diecisiete", "seventeen"),
+    ("dieciocho", "eighteen"),
+    ("diecinueve", "nineteen"),
+    ("veinte", "twenty"),
+    ("treinta", "thirty"),
+    ("cuarenta", "forty"),
+    ("cincuenta", "fifty"),
+    ("sesenta", "sixty"),
+    ("setenta", "seventy"),
+    ("ochenta", "eighty"),
+    ("noventa", "ninety"),
+    ("cien", "one hundred"),
+    ("ciento", "one hundred"),
+    // "treinta y cinco" -> "thirty and five", which the number parser sums
+    ("y", "and"),
+];
+
+const SPANISH_CANCEL_PHRASES: &[&str] = &[
+    "cancela",
+    "cancelar",
+    "olvídalo",
+    "olvidalo",
+    "déjalo",
+    "dejalo",
+    "no importa",
+];
+
+/// "no abras chrome"
+const SPANISH_NEGATORS: &[&str] = &["no", "nunca"];
+
+impl Locale {
+    /// Parse a language code such as "en", "es" or "es-MX"
+    pub fn from_code(code: &str) -> Option<Self> {
+        let language = code
+            .trim()
+            .split(['-', '_'])
+            .next()
+            .unwrap_or_default()
+            .to_lowercase();
+        match language.as_str() {
+            "en" | "english" => Some(Locale::English),
+            "es" | "spanish" | "español" => Some(Locale::Spanish),
+            _ => None,
+        }
+    }
+
+    /// Two-letter language code, used in grammar file names
+    pub fn code(&self) -> &'static str {
+        match self {
+            Locale::English => "en",
+            Locale::Spanish => "es",
+        }
+    }
+
+    /// Phrases that cancel a request, in addition to the English ones
+    pub fn cancel_phrases(&self) -> &'static [&'static str] {
+        match self {
+            Locale::English => &[],
+            Locale::Spanish => SPANISH_CANCEL_PHRASES,
+        }
+    }
+
+    /// Leading words that negate a command, in addition to the English ones
+    pub fn negators(&self) -> &'static [&'static str] {
+        match self {
+            Locale::English => &[],
+            Locale::Spanish => SPANISH_NEGATORS,
+        }
+    }
+
+    /// Rewrite spoken numbers and dates into English words
+    ///
+    /// "mañana a las 9" becomes "tomorrow at 9" and "treinta y cinco por
+    /// ciento" becomes "thirty and five percent". Unknown words are kept.
+    pub fn rewrite_to_english<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        let table = match self {
+            Locale::English => return Cow::Borrowed(text),
+            Locale::Spanish => SPANISH_WORDS,
+        };
+
+        let lower = text.to_lowercase();
+        let words: Vec<&str> = lower.split_whitespace().collect();
+        let mut out: Vec<&str> = Vec::with_capacity(words.len());
+        let mut i = 0;
+        'words: while i < words.len() {
+            for (phrase, english) in table {
+                let len = phrase.split(' ').count();
+                if words.len() - i >= len && words[i..i + len].join(" ") == *phrase {
+                    out.push(*english);
+                    i += len;
+                    continue 'words;
+                }
+            }
+            out.push(words[i]);
+            i += 1;
+        }
+
+        Cow::Owned(out.join(" "))
+    }
+}
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_code() {
+        assert_eq!(Locale::from_code("es"), Some(Locale::Spanish));
+        assert_eq!(Locale::from_code("es-MX"), Some(Locale::Spanish));
+        assert_eq!(Locale::from_code("EN_us"), Some(Locale::English));
+        assert_eq!(Locale::from_code("klingon"), None);
+    }
+
+    #[test]
+    fn test_spanish_numbers_and_dates_to_english() {
+        let es = Locale::Spanish;
+        assert_eq!(es.rewrite_to_english("mañana a las 9"), "tomorrow at 9");
+        assert_eq!(
+            es.rewrite_to_english("el lunes por la mañana"),
+            "on monday morning"
+        );
+        assert_eq!(
+            es.rewrite_to_english("treinta y cinco por ciento"),
+            "thirty and five percent"
+        );
+        assert_eq!(es.rewrite_to_english("en 10 minutos"), "in 10 minutes");
+        assert_eq!(Locale::English.rewrite_to_english("Tomorrow"), "Tomorrow");
+    }
+}
@@ -12,35 +12,81 @@ pub mod task_planner;
 // God-level enhancements
 pub mod cache;
 pub mod contextualizer;
+pub mod evaluation;
 pub mod grammar;
+pub mod locale;
 pub mod multi_intent;
 pub mod providers;
 pub mod ranking;
 pub mod types;
 
 use crate::config::BrainConfig;
-use crate::error::Result;
+use crate::context::unified::{self, UnifiedContext};
+use crate::error::{LunaError, Result};
+use crate::events::{EventBus, EventEnvelope, LunaEvent};
 use command_parser::{CommandParser, ParsedCommand};
 use entity_extractor::EntityExtractor;
-use intent_classifier::{ClassificationResult, IntentClassifier};
+use intent_classifier::IntentClassifier;
+use notify::{Event, RecursiveMode, Watcher};
 use task_planner::{TaskPlan, TaskPlanner};
-use tracing::info;
+use tracing::{debug, error, info};
 
 // Re-export key types
-pub use cache::BrainCache;
+pub use cache::{BrainCache, CacheConfig};
 pub use command_parser::IntentType;
 pub use contextualizer::ConversationContext;
-pub use grammar::{CompiledGrammar, GrammarConfig};
-pub use providers::{CompositeProvider, FileSystemProvider, KnownAppProvider};
+pub use evaluation::{ClassificationReport, IntentMetrics};
+pub use grammar::{CompiledGrammar, GrammarConfig, GrammarWarning};
+pub use intent_classifier::ClassificationResult;
+pub use locale::Locale;
+pub use providers::{
+    AppKnowledge, CompositeProvider, FileSystemProvider, KnowledgeProvider, KnownAppProvider,
+};
 pub use task_planner::ActionType;
-pub use types::{Confidence, ConfidenceFactor, Entities, Entity};
+pub use types::{Confidence, ConfidenceExplanation, ConfidenceFactor, Entities, Entity};
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::Duration;
+
+/// Pronouns that may refer to an entity from an earlier command
+const PRONOUNS: &[&str] = &["it", "that", "this", "them", "there"];
+
+/// Grammar file loaded at startup; locales use e.g. `brain_patterns.es.yaml`
+const GRAMMAR_FILE: &str = "config/brain_patterns.yaml";
+
+/// Quiet period after the last file event before reloading grammar
+const GRAMMAR_RELOAD_DEBOUNCE: Duration = Duration::from_millis(300);
+
+type SharedGrammar = Arc<parking_lot::RwLock<Option<Arc<CompiledGrammar>>>>;
+
+/// Handle for an active grammar file watcher; watching stops when dropped
+pub struct GrammarWatcher {
+    _watcher: notify::RecommendedWatcher,
+    path: PathBuf,
+}
+
+impl GrammarWatcher {
+    /// Path of the grammar file being watched
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+/// Options for `Brain::process_batch_with`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BatchOptions {
+    /// Skip pronoun resolution and context-based ranking so results don't
+    /// depend on conversation history
+    pub isolate_context: bool,
+}
+
+/// Hook that can inspect or rewrite a classification before planning
+pub type ClassifierMiddleware = Box<dyn Fn(&mut ClassificationResult) + Send + Sync>;
 
 /// Brain system that coordinates all NLP components with god-level enhancements
 pub struct Brain {
-    parser: CommandParser,
+    parser: Arc<parking_lot::RwLock<CommandParser>>,
     classifier: IntentClassifier,
     extractor: EntityExtractor,
     planner: TaskPlanner,
@@ -48,52 +94,211 @@ pub struct Brain {
     // Enhancements
     cache: Arc<BrainCache>,
     context: Arc<parking_lot::RwLock<ConversationContext>>,
+    unified: Arc<parking_lot::RwLock<UnifiedContext>>,
     providers: Arc<CompositeProvider>,
-    grammar: Option<Arc<CompiledGrammar>>,
+    grammar: SharedGrammar,
     ranker: Arc<ranking::RankingScorer>,
+    event_bus: Option<Arc<EventBus>>,
+    middleware: Vec<ClassifierMiddleware>,
+    locale: Locale,
 
     config: BrainConfig,
 }
 
+/// Split a token into its lowercase word and trailing punctuation
+fn split_punctuation(token: &str) -> (String, &str) {
+    let word = token.trim_end_matches(|c: char| c.is_ascii_punctuation());
+    (word.to_lowercase(), &token[word.len()..])
+}
+
+/// Entity types a pronoun most likely refers to for a given intent
+fn referent_kinds(intent: &IntentType) -> &'static [&'static str] {
+    match intent {
+        IntentType::CloseApp | IntentType::WindowManagement => &["app"],
+        IntentType::LaunchApp => &["app", "file", "folder"],
+        IntentType::FindFile => &["file", "folder"],
+        IntentType::OpenFolder => &["folder"],
+        IntentType::SearchWeb | IntentType::Question => &["query"],
+        // Free-form text: "remind me that..." shouldn't be rewritten
+        IntentType::Reminder | IntentType::Note | IntentType::Clipboard => &[],
+        _ => &["app", "file", "folder", "query"],
+    }
+}
+
+/// Context keys (typed and legacy parser keys) holding an entity type
+fn entity_keys(kind: &str) -> &'static [&'static str] {
+    match kind {
+        "app" => &["app", "app_name"],
+        "file" => &["file", "file_name"],
+        "folder" => &["folder", "folder_name"],
+        "query" => &["query"],
+        _ => &[],
+    }
+}
+
+/// Apps, files and folders named by a command's entities, in spoken order
+///
+/// Lists like "chrome and firefox" yield one entity per item.
+fn mentions_in(entities: &std::collections::HashMap<String, String>) -> Vec<Entity> {
+    let mut mentions = Vec::new();
+    for kind in ["app", "file", "folder"] {
+        for key in entity_keys(kind) {
+            let Some(value) = entities.get(*key) else {
+                continue;
+            };
+            let items = value
+                .split(", ")
+                .flat_map(|part| part.split(" and "))
+                .flat_map(|part| part.split(" or "))
+                .map(|item| item.trim().trim_end_matches(',').trim())
+                .filter(|item| !item.is_empty());
+            for item in items {
+                let item = item.to_string();
+                mentions.push(match kind {
+                    "app" => Entity::App(item),
+                    "file" => Entity::File(item),
+                    _ => Entity::Folder(item),
+                });
+            }
+        }
+    }
+    mentions
+}
+
 impl Brain {
     /// Create a new brain instance
     pub fn new(config: &BrainConfig) -> Result<Self> {
         info!("Initializing brain system with god-level enhancements...");
 
-        let parser = CommandParser::new();
-        let classifier = IntentClassifier::new();
-        let extractor = EntityExtractor::new();
+        let locale = Locale::from_code(&config.language).unwrap_or_default();
+        let mut classifier = IntentClassifier::new().with_locale(locale);
+        let extractor = EntityExtractor::new().with_locale(locale);
         let planner = TaskPlanner::new();
 
         // Initialize enhancements
         let cache = Arc::new(BrainCache::new());
         let context = Arc::new(parking_lot::RwLock::new(ConversationContext::new()));
+        let unified = Arc::new(parking_lot::RwLock::new(UnifiedContext::default()));
         let providers = Arc::new(CompositeProvider::default());
-        let ranker = Arc::new(ranking::RankingScorer::new());
+        let mut ranker = ranking::RankingScorer::new();
+        if !config.calibration_path.is_empty() {
+            let curve = ranking::CalibrationCurve::load_from_file(&config.calibration_path)?;
+            info!("Confidence calibration loaded from {}", config.calibration_path);
+            ranker = ranker.with_calibration(curve);
+        }
+        let ranker = Arc::new(ranker);
 
-        // Try to load grammar from config
-        let grammar = Self::try_load_grammar()?;
+        // Try to load grammar from config, preferring the locale's file
+        let (grammar, grammar_locale) = Self::try_load_grammar(locale)?;
+        let parser = Self::parser_for(grammar_locale, &grammar);
+        for (intent, keyword, weight) in grammar.iter().flat_map(|g| g.keyword_boosts()) {
+            classifier.add_keyword_boost(intent.clone(), keyword, *weight);
+        }
 
         info!("✅ Brain system initialized with caching, context, providers, and ranking");
 
         Ok(Self {
-            parser,
+            parser: Arc::new(parking_lot::RwLock::new(parser)),
             classifier,
             extractor,
             planner,
             cache,
             context,
+            unified,
             providers,
-            grammar,
+            grammar: Arc::new(parking_lot::RwLock::new(grammar)),
             ranker,
+            event_bus: None,
+            middleware: Vec::new(),
+            locale,
             config: config.clone(),
         })
     }
 
+    /// Register a hook that runs on every classification before planning
+    ///
+    /// Middleware runs in registration order, each seeing the previous one's
+    /// changes, after classification and before ranking. It can remap the
+    /// intent, add entities or rewrite the command into `IntentType::Cancel`
+    /// to block it. Lowering `confidence` caps the ranked score, so middleware
+    /// can force clarification; raising it only feeds the pattern-match signal.
+    ///
+    /// Cached plans are dropped so earlier commands pick up the new hook.
+    pub fn add_classifier_middleware<F>(&mut self, middleware: F)
+    where
+        F: Fn(&mut ClassificationResult) + Send + Sync + 'static,
+    {
+        self.middleware.push(Box::new(middleware));
+        self.cache.invalidate_plan();
+    }
+
+    /// Run middleware in order, returning the confidence cap it set, if any
+    fn apply_middleware(&self, classified: &mut ClassificationResult) -> Option<f32> {
+        let before = classified.confidence;
+        for middleware in &self.middleware {
+            middleware(classified);
+        }
+        (classified.confidence < before).then_some(classified.confidence)
+    }
+
+    /// Rank a classification, keeping any confidence cap set by middleware
+    fn rank(
+        &self,
+        parsed: &ParsedCommand,
+        classified: &mut ClassificationResult,
+        ctx: Option<&ConversationContext>,
+    ) -> Confidence {
+        let cap = self.apply_middleware(classified);
+        let mut confidence = self.ranker.score(parsed, classified, &self.providers, ctx);
+        if let Some(cap) = cap {
+            confidence.score = confidence.score.min(cap);
+        }
+        confidence
+    }
+
+    /// Consult custom knowledge providers, highest priority first, before
+    /// the built-in app list
+    pub fn with_knowledge_providers(mut self, providers: Vec<Arc<dyn KnowledgeProvider>>) -> Self {
+        match Arc::get_mut(&mut self.providers) {
+            Some(composite) => composite.set_providers(providers),
+            None => tracing::warn!("Knowledge providers are shared; custom providers ignored"),
+        }
+        self
+    }
+
+    /// Attach an event bus for publishing brain events (e.g. grammar reloads)
+    pub fn with_event_bus(mut self, event_bus: Arc<EventBus>) -> Self {
+        self.event_bus = Some(event_bus);
+        self
+    }
+
     /// Try to load grammar from file
-    fn try_load_grammar() -> Result<Option<Arc<CompiledGrammar>>> {
-        let grammar_path = Path::new("config/brain_patterns.yaml");
+    ///
+    /// Returns the grammar with the locale it was written for; a locale
+    /// without its own file falls back to the English grammar.
+    fn try_load_grammar(locale: Locale) -> Result<(Option<Arc<CompiledGrammar>>, Locale)> {
+        let (path, loaded) = GrammarConfig::locale_file(Path::new(GRAMMAR_FILE), locale);
+        Ok((Self::load_grammar_from(&path)?, loaded))
+    }
+
+    /// Grammar file for this brain's locale, the one to watch for hot-reload
+    pub fn grammar_file(&self) -> PathBuf {
+        GrammarConfig::locale_file(Path::new(GRAMMAR_FILE), self.locale).0
+    }
+
+    /// Command parser for a grammar written in `locale`; non-English
+    /// grammars are tried before the built-in English patterns
+    fn parser_for(locale: Locale, grammar: &Option<Arc<CompiledGrammar>>) -> CommandParser {
+        match grammar {
+            Some(grammar) if locale != Locale::English => {
+                CommandParser::new().with_locale(locale, grammar.clone())
+            }
+            _ => CommandParser::new(),
+        }
+    }
 
+    /// Load and compile grammar from a specific file
+    fn load_grammar_from(grammar_path: &Path) -> Result<Option<Arc<CompiledGrammar>>> {
         if grammar_path.exists() {
             let config = GrammarConfig::load_from_file(grammar_path)?;
             let compiled = config.compile()?;
@@ -106,7 +311,9 @@ impl Brain {
 
     /// Reload grammar from file (for hot-reload)
     pub fn reload_grammar(&mut self) -> Result<()> {
-        self.grammar = Self::try_load_grammar()?;
+        let (grammar, grammar_locale) = Self::try_load_grammar(self.locale)?;
+        *self.parser.write() = Self::parser_for(grammar_locale, &grammar);
+        *self.grammar.write() = grammar;
 
         // Invalidate caches since patterns changed
         self.cache.invalidate_all();
@@ -115,6 +322,95 @@ impl Brain {
         Ok(())
     }
 
+    /// Watch a grammar file and reload it whenever it is written
+    ///
+    /// Rapid successive saves are debounced into a single reload. A file that
+    /// fails to parse or compile is logged and the previous grammar is kept.
+    /// Publishes `GrammarReloaded` if an event bus is attached.
+    pub fn enable_grammar_hot_reload<P: AsRef<Path>>(&self, path: P) -> Result<GrammarWatcher> {
+        let path = path.as_ref().to_path_buf();
+        let file_name = path
+            .file_name()
+            .map(|name| name.to_os_string())
+            .ok_or_else(|| {
+                LunaError::Config(format!("Invalid grammar file path: {:?}", path))
+            })?;
+
+        // Watch the parent directory so editors that save via rename are still seen
+        let watch_dir = match path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir.to_path_buf(),
+            _ => PathBuf::from("."),
+        };
+
+        let (tx, rx) = std::sync::mpsc::channel::<()>();
+        let mut watcher = notify::recommended_watcher(move |res: notify::Result<Event>| {
+            match res {
+                Ok(event) => {
+                    let touches_file = event
+                        .paths
+                        .iter()
+                        .any(|p| p.file_name() == Some(file_name.as_os_str()));
+
+                    if touches_file && (event.kind.is_modify() || event.kind.is_create()) {
+                        let _ = tx.send(());
+                    }
+                }
+                Err(e) => error!("Grammar watch error: {:?}", e),
+            }
+        })
+        .map_err(|e| LunaError::Config(format!("Failed to create grammar watcher: {}", e)))?;
+
+        watcher
+            .watch(&watch_dir, RecursiveMode::NonRecursive)
+            .map_err(|e| LunaError::Config(format!("Failed to watch grammar file: {}", e)))?;
+
+        let grammar = self.grammar.clone();
+        let parser = self.parser.clone();
+        let cache = self.cache.clone();
+        let event_bus = self.event_bus.clone();
+        let reload_path = path.clone();
+
+        // Reload thread exits once the watcher (and with it the sender) is dropped
+        std::thread::spawn(move || {
+            while rx.recv().is_ok() {
+                // Debounce: wait until events stop arriving
+                while rx.recv_timeout(GRAMMAR_RELOAD_DEBOUNCE).is_ok() {}
+
+                match Self::load_grammar_from(&reload_path) {
+                    Ok(new_grammar) => {
+                        let pattern_count = new_grammar
+                            .as_ref()
+                            .map(|g| g.pattern_count())
+                            .unwrap_or(0);
+                        // The parser keeps the locale the grammar was written for
+                        let locale = parser.read().locale();
+                        *parser.write() = Self::parser_for(locale, &new_grammar);
+                        *grammar.write() = new_grammar;
+                        cache.invalidate_all();
+                        info!("✅ Grammar hot-reloaded: {} patterns", pattern_count);
+
+                        if let Some(ref bus) = event_bus {
+                            let _ = bus.get_sender().try_send(EventEnvelope::new(
+                                LunaEvent::GrammarReloaded {
+                                    pattern_count,
+                                    timestamp: chrono::Utc::now().timestamp() as u64,
+                                },
+                            ));
+                        }
+                    }
+                    Err(e) => error!("Grammar reload failed, keeping previous grammar: {}", e),
+                }
+            }
+        });
+
+        info!("Grammar hot-reload enabled for: {:?}", path);
+
+        Ok(GrammarWatcher {
+            _watcher: watcher,
+            path,
+        })
+    }
+
     /// Process text through the complete NLP pipeline with caching and context
     ///
     /// # Arguments
@@ -139,7 +435,7 @@ impl Brain {
             info!("   ✨ Parse cache hit");
             cached_parsed
         } else {
-            let parsed = self.parser.parse(&resolved_text)?;
+            let parsed = self.parse(&resolved_text)?;
             self.cache.put_parsed(&resolved_text, parsed.clone());
             parsed
         };
@@ -147,7 +443,8 @@ impl Brain {
         info!("   Parsed intent: {:?}", parsed.intent);
 
         // 3. Classify with confidence
-        let classified = self.classifier.classify(&parsed)?;
+        let mut classified = self.classifier.classify(&parsed)?;
+        self.apply_middleware(&mut classified);
         info!("   Confidence: {:.2}", classified.confidence);
 
         // 4. Plan tasks
@@ -170,7 +467,12 @@ impl Brain {
 
         // Record in context if callback provided
         if let Some(success) = success_callback {
-            let parsed = self.parser.parse(text)?;
+            let mentions = mentions_in(&plan.classification.entities);
+            if !mentions.is_empty() {
+                self.unified.write().mentions.record(mentions);
+            }
+
+            let parsed = self.parse(text)?;
             let mut entities = std::collections::HashMap::new();
 
             // Convert legacy entities to typed (simplified for now)
@@ -185,34 +487,122 @@ impl Brain {
     }
 
     /// Resolve context references like "it", "that file", etc.
+    ///
+    /// Only standalone pronoun tokens are replaced. The referent type (app, file,
+    /// folder, query) is chosen from the command's likely intent, and a following
+    /// type noun ("that file") narrows it explicitly.
     fn resolve_context(&self, text: &str) -> String {
+        let text = self.resolve_ordinal(text);
         let ctx = self.context.read();
+        self.resolve_context_in(&text, &ctx)
+    }
 
-        if let Some(resolved_entities) = ctx.resolve_references(text) {
-            // Simple substitution: replace "it" with actual entity
-            let mut resolved = text.to_string();
-
-            for (entity_type, entity) in resolved_entities {
-                if let Some(value) = entity.as_string() {
-                    // Replace reference words with actual values
-                    resolved = resolved
-                        .replace("it", value)
-                        .replace("that", value)
-                        .replace("this", value);
+    /// Replace "the first one", "the other one" and similar with the entity
+    /// they point to
+    ///
+    /// Items named earlier in the same utterance ("open chrome and firefox,
+    /// then close the first one") count as mentions too.
+    fn resolve_ordinal(&self, text: &str) -> String {
+        let Some(span) = unified::ordinal_span(text) else {
+            return text.to_string();
+        };
 
-                    info!("   🔗 Resolved '{}' to '{}'", entity_type, value);
+        let mut mentions = self.unified.read().mentions.clone();
+        for segment in text[..span.start].split(" then ") {
+            let segment = segment.trim().trim_end_matches(',');
+            let segment = segment.strip_suffix(" and").unwrap_or(segment);
+            if let Ok(parsed) = self.parse(segment) {
+                let found = mentions_in(&parsed.entities);
+                if !found.is_empty() {
+                    mentions.record(found);
                 }
             }
+        }
 
-            resolved
-        } else {
-            text.to_string()
+        match mentions.resolve(text).as_ref().and_then(Entity::as_string) {
+            Some(value) => {
+                info!("   🔗 Resolved '{}' to '{}'", &text[span.clone()], value);
+                format!("{}{}{}", &text[..span.start], value, &text[span.end..])
+            }
+            None => text.to_string(),
+        }
+    }
+
+    /// Resolve pronouns against an already-locked context
+    fn resolve_context_in(&self, text: &str, ctx: &ConversationContext) -> String {
+        // "cancel that" / "forget it" refer to the request itself
+        if IntentClassifier::is_cancellation_in(text, self.locale) {
+            return text.to_string();
+        }
+
+        let tokens: Vec<&str> = text.split_whitespace().collect();
+        let is_pronoun = |token: &str| PRONOUNS.contains(&split_punctuation(token).0.as_str());
+
+        if !tokens.iter().any(|t| is_pronoun(t)) {
+            return text.to_string();
+        }
+
+        let intent = self
+            .parse(text)
+            .map(|parsed| parsed.intent)
+            .unwrap_or(IntentType::Unknown);
+
+        let mut resolved = Vec::with_capacity(tokens.len());
+        let mut i = 0;
+        while i < tokens.len() {
+            let (word, trailing) = split_punctuation(tokens[i]);
+
+            if !PRONOUNS.contains(&word.as_str()) {
+                resolved.push(tokens[i].to_string());
+                i += 1;
+                continue;
+            }
+
+            // "that file" / "this app" name the referent type directly
+            let noun = tokens
+                .get(i + 1)
+                .map(|t| split_punctuation(t))
+                .filter(|(noun, _)| matches!(noun.as_str(), "app" | "file" | "folder"));
+
+            let kinds: &[&str] = match (&noun, word.as_str()) {
+                (Some((noun, _)), "that" | "this") => match noun.as_str() {
+                    "app" => &["app"],
+                    "file" => &["file"],
+                    _ => &["folder"],
+                },
+                (_, "there") => &["folder"],
+                _ => referent_kinds(&intent),
+            };
+
+            let keys: Vec<&str> = kinds.iter().flat_map(|k| entity_keys(k)).copied().collect();
+
+            match ctx.recent_value(&keys) {
+                Some(value) => {
+                    info!("   🔗 Resolved '{}' to '{}'", word, value);
+                    match noun {
+                        Some((_, noun_trailing)) if matches!(word.as_str(), "that" | "this") => {
+                            resolved.push(format!("{}{}", value, noun_trailing));
+                            i += 2;
+                        }
+                        _ => {
+                            resolved.push(format!("{}{}", value, trailing));
+                            i += 1;
+                        }
+                    }
+                }
+                None => {
+                    resolved.push(tokens[i].to_string());
+                    i += 1;
+                }
+            }
         }
+
+        resolved.join(" ")
     }
 
     /// Parse text into a structured command
     pub fn parse(&self, text: &str) -> Result<ParsedCommand> {
-        self.parser.parse(text)
+        self.parser.read().parse(text)
     }
 
     /// Classify a parsed command
@@ -225,6 +615,19 @@ impl Brain {
         self.planner.plan(classification)
     }
 
+    /// Plan the best runner-up interpretation of a command, for "did you mean"
+    ///
+    /// Returns `None` unless an alternative scores at least
+    /// `alternative_threshold`.
+    pub fn plan_alternative(&self, classification: &ClassificationResult) -> Option<TaskPlan> {
+        let alternative = classification.best_alternative(self.config.alternative_threshold)?;
+        debug!(
+            "Alternative to {:?}: {:?} ({:.2})",
+            classification.intent, alternative.intent, alternative.confidence
+        );
+        Some(self.planner.plan(alternative))
+    }
+
     /// Get the entity extractor
     pub fn extractor(&self) -> &EntityExtractor {
         &self.extractor
@@ -235,6 +638,31 @@ impl Brain {
         self.classifier.add_known_app(app_name);
     }
 
+    /// Boost `intent` when `keyword` is spoken (see `IntentClassifier::add_keyword_boost`)
+    pub fn add_keyword_boost(&mut self, intent: IntentType, keyword: &str, weight: f32) {
+        self.classifier.add_keyword_boost(intent, keyword, weight);
+        self.cache.invalidate_plan();
+    }
+
+    /// Keyword boosts currently applied by the classifier
+    pub fn keyword_boosts(&self) -> &[intent_classifier::KeywordBoost] {
+        self.classifier.keyword_boosts()
+    }
+
+    /// Add a known application whose aliases normalize to its name
+    ///
+    /// "open vs code" then classifies as `LaunchApp` with the canonical
+    /// name as the `app_name` entity.
+    pub fn add_known_app_with_aliases(&mut self, app_name: String, aliases: Vec<String>) {
+        self.extractor.add_app_aliases(&app_name, &aliases);
+        if let Some(provider) =
+            Arc::get_mut(&mut self.providers).and_then(|p| p.app_provider_mut())
+        {
+            provider.add_app(app_name.clone(), aliases.clone());
+        }
+        self.classifier.add_known_app_with_aliases(app_name, aliases);
+    }
+
     // Enhanced accessors
 
     /// Get cache statistics
@@ -252,9 +680,15 @@ impl Brain {
         self.cache.invalidate_all();
     }
 
+    /// Get the unified context tracking mentioned entities
+    pub fn unified_context(&self) -> Arc<parking_lot::RwLock<UnifiedContext>> {
+        Arc::clone(&self.unified)
+    }
+
     /// Clear conversation context
     pub fn clear_context(&self) {
         self.context.write().clear();
+        self.unified.write().clear();
     }
 
     /// Get success rate for a command
@@ -264,21 +698,48 @@ impl Brain {
 
     /// Check if providers know an app
     pub fn is_known_app(&self, name: &str) -> bool {
-        self.providers.app_provider().is_known(name)
+        self.providers.is_known_app(name)
     }
 
     /// Get grammar if loaded
     pub fn grammar(&self) -> Option<Arc<CompiledGrammar>> {
-        self.grammar.clone()
+        self.grammar.read().clone()
+    }
+
+    /// Process a command as part of one utterance
+    ///
+    /// The plan carries `correlation_id`, so the executor publishes its
+    /// events under the same ID as the wake word and transcription.
+    pub async fn process_async_with_correlation(
+        &self,
+        text: &str,
+        correlation_id: uuid::Uuid,
+    ) -> Result<TaskPlan> {
+        self.process_correlated(text, Some(correlation_id)).await
     }
 
     /// Async processing with parallel entity extraction and classification
+    ///
+    /// Publishes `CommandParsed` for each classified command if an event bus
+    /// is attached.
     pub async fn process_async(&self, text: &str) -> Result<TaskPlan> {
+        self.process_correlated(text, None).await
+    }
+
+    /// `process_async`, tagging the plan and its events with `correlation_id`
+    async fn process_correlated(
+        &self,
+        text: &str,
+        correlation_id: Option<uuid::Uuid>,
+    ) -> Result<TaskPlan> {
         info!("🧠 Async processing: \"{}\"", text);
 
         // Fast path: check cache
-        if let Some(cached_plan) = self.cache.get_plan(text) {
+        if let Some(mut cached_plan) = self.cache.get_plan(text) {
             info!("   ✨ Cache hit! Returning cached plan");
+            self.publish_parsed(text, &cached_plan.classification, correlation_id)
+                .await;
+            cached_plan.correlation_id = correlation_id;
             return Ok(cached_plan);
         }
 
@@ -297,7 +758,10 @@ impl Brain {
             let mut items = Vec::new();
             for segment in &multi_intent.segments {
                 // Classify the segment
-                let classified = self.classifier.classify(&segment.command)?;
+                let mut classified = self.classifier.classify(&segment.command)?;
+                self.apply_middleware(&mut classified);
+                self.publish_parsed(&segment.command.original_text, &classified, correlation_id)
+                    .await;
 
                 // Extract temporal duration if present
                 let duration = segment.temporal.as_ref().and_then(|t| {
@@ -310,12 +774,13 @@ impl Brain {
             }
 
             // Plan multi-intent with coordination
-            let plan = self.planner.plan_multi(items, multi_intent.coordination);
+            let mut plan = self.planner.plan_multi(items, multi_intent.coordination);
             info!("   Plan: {} steps (multi-intent)", plan.steps.len());
 
             // Cache the plan
             self.cache.put_plan(text, plan.clone());
 
+            plan.correlation_id = correlation_id;
             return Ok(plan);
         }
 
@@ -325,7 +790,7 @@ impl Brain {
             info!("   ✨ Parse cache hit");
             cached_parsed
         } else {
-            let parsed = self.parser.parse(&resolved_text)?;
+            let parsed = self.parse(&resolved_text)?;
             self.cache.put_parsed(&resolved_text, parsed.clone());
             parsed
         };
@@ -335,17 +800,18 @@ impl Brain {
         // Classify with enhanced ranking
         let mut classified = self.classifier.classify(&parsed)?;
 
-        // Apply advanced ranking with all signals
-        let ctx_read = self.context.read();
-        let enhanced_confidence =
-            self.ranker
-                .score(&parsed, &classified, &self.providers, Some(&*ctx_read));
-        drop(ctx_read);
+        // Apply middleware, then advanced ranking with all signals
+        let enhanced_confidence = {
+            let ctx_read = self.context.read();
+            self.rank(&parsed, &mut classified, Some(&*ctx_read))
+        };
 
         // Update classification with enhanced confidence
         classified.confidence = enhanced_confidence.score;
+        classified.factors = enhanced_confidence.factors;
 
         info!("   Confidence: {:.2}", classified.confidence);
+        self.publish_parsed(text, &classified, correlation_id).await;
 
         // Check confidence threshold for clarification
         if classified.confidence < self.config.confidence_threshold {
@@ -356,15 +822,123 @@ impl Brain {
         }
 
         // Plan tasks
-        let plan = self.planner.plan(classified);
+        let mut plan = self.planner.plan(classified);
         info!("   Plan: {} steps", plan.steps.len());
 
         // Cache the plan
         self.cache.put_plan(text, plan.clone());
 
+        plan.correlation_id = correlation_id;
         Ok(plan)
     }
 
+    /// Publish `CommandParsed` for a classification, if an event bus is attached
+    async fn publish_parsed(
+        &self,
+        command: &str,
+        classified: &ClassificationResult,
+        correlation_id: Option<uuid::Uuid>,
+    ) {
+        let Some(ref bus) = self.event_bus else {
+            return;
+        };
+
+        let event = LunaEvent::CommandParsed {
+            command: command.to_string(),
+            intent: format!("{:?}", classified.intent),
+            entities: classified.entities.clone(),
+            confidence: classified.confidence,
+        };
+        match correlation_id {
+            Some(id) => bus.publish_with_correlation(event, id).await,
+            None => bus.publish(event).await,
+        }
+    }
+
+    /// Process many commands for offline evaluation
+    ///
+    /// Equivalent to `process_batch_with` using default options.
+    pub fn process_batch(&self, texts: &[String]) -> Vec<Result<TaskPlan>> {
+        self.process_batch_with(texts, BatchOptions::default())
+    }
+
+    /// Process many commands under a single context read lock
+    ///
+    /// Bypasses the caches and never records history. With
+    /// `isolate_context`, each result depends only on its own text.
+    pub fn process_batch_with(
+        &self,
+        texts: &[String],
+        options: BatchOptions,
+    ) -> Vec<Result<TaskPlan>> {
+        let ctx = self.context.read();
+        let ctx = (!options.isolate_context).then_some(&*ctx);
+
+        texts
+            .iter()
+            .map(|text| {
+                let resolved = match ctx {
+                    Some(ctx) => self.resolve_context_in(text, ctx),
+                    None => text.clone(),
+                };
+                self.plan_uncached(&resolved, ctx)
+            })
+            .collect()
+    }
+
+    /// Parse, classify, rank and plan without touching caches
+    fn plan_uncached(&self, text: &str, ctx: Option<&ConversationContext>) -> Result<TaskPlan> {
+        let multi_parser = multi_intent::MultiIntentParser::new();
+        if multi_parser.is_multi_intent(text) {
+            let multi_intent = multi_parser.parse(text)?;
+            let mut items = Vec::new();
+            for segment in &multi_intent.segments {
+                let mut classified = self.classifier.classify(&segment.command)?;
+                self.apply_middleware(&mut classified);
+                let duration = segment.temporal.as_ref().and_then(|t| {
+                    t.duration
+                        .map(|cd| std::time::Duration::from_secs(cd.num_seconds() as u64))
+                });
+                items.push((classified, duration));
+            }
+            return Ok(self.planner.plan_multi(items, multi_intent.coordination));
+        }
+
+        let parsed = self.parse(text)?;
+        let mut classified = self.classifier.classify(&parsed)?;
+        let confidence = self.rank(&parsed, &mut classified, ctx);
+        classified.confidence = confidence.score;
+        classified.factors = confidence.factors;
+
+        Ok(self.planner.plan(classified))
+    }
+
+    /// Explain the confidence a command would receive, factor by factor
+    ///
+    /// Runs the same parse/classify/rank pipeline as `process_async` without
+    /// touching caches or conversation history.
+    pub fn explain(&self, text: &str) -> Result<ConfidenceExplanation> {
+        let resolved_text = self.resolve_context(text);
+        let parsed = self.parse(&resolved_text)?;
+        let mut classified = self.classifier.classify(&parsed)?;
+
+        let ctx_read = self.context.read();
+        let confidence = self.rank(&parsed, &mut classified, Some(&*ctx_read));
+        drop(ctx_read);
+
+        Ok(ConfidenceExplanation {
+            text: resolved_text,
+            intent: format!("{:?}", classified.intent),
+            score: confidence.score,
+            raw_score: confidence.raw_score,
+            factors: confidence
+                .factors
+                .into_iter()
+                .map(|factor| (factor.label(), factor))
+                .collect(),
+        })
+    }
+
     /// Process with clarification support
     pub async fn process_with_clarification(
         &self,
@@ -424,6 +998,7 @@ impl Brain {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::events::EventKind;
 
     #[test]
     fn test_brain_creation() {
@@ -461,6 +1036,352 @@ mod tests {
         );
     }
 
+    fn write_grammar(path: &Path, patterns: &[&str]) {
+        let mut yaml = String::from("version: \"1.0\"\nintents:\n");
+        yaml.push_str("  - name: GetTime\n    priority: 100\n    patterns:\n");
+        for pattern in patterns {
+            yaml.push_str(&format!("      - pattern: \"{}\"\n        entities: {{}}\n", pattern));
+        }
+        yaml.push_str("    examples: []\n");
+        std::fs::write(path, yaml).unwrap();
+    }
+
+    #[test]
+    fn test_grammar_hot_reload() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("patterns.yaml");
+        write_grammar(&path, &["what time is it"]);
+
+        let brain = Brain::new(&BrainConfig::default()).unwrap();
+        let _watcher = brain.enable_grammar_hot_reload(&path).unwrap();
+
+        write_grammar(&path, &["what time is it", "tell me the time", "current time"]);
+
+        let deadline = std::time::Instant::now() + Duration::from_secs(5);
+        let mut pattern_count = None;
+        while std::time::Instant::now() < deadline {
+            pattern_count = brain.grammar().map(|g| g.pattern_count());
+            if pattern_count == Some(3) {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(50));
+        }
+
+        assert_eq!(pattern_count, Some(3));
+    }
+
+    #[test]
+    fn test_grammar_file_follows_locale() {
+        let brain = Brain::new(&BrainConfig::default()).unwrap();
+        assert_eq!(
+            brain.grammar_file(),
+            Path::new("config/brain_patterns.yaml")
+        );
+
+        let config = BrainConfig {
+            language: "es".to_string(),
+            ..BrainConfig::default()
+        };
+        let brain = Brain::new(&config).unwrap();
+        assert_eq!(
+            brain.grammar_file(),
+            Path::new("config/brain_patterns.es.yaml")
+        );
+    }
+
+    #[test]
+    fn test_grammar_hot_reload_updates_parser() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("patterns.es.yaml");
+        write_grammar(&path, &["^que hora es$"]);
+
+        let config = BrainConfig {
+            language: "es".to_string(),
+            ..BrainConfig::default()
+        };
+        let brain = Brain::new(&config).unwrap();
+        let _watcher = brain.enable_grammar_hot_reload(&path).unwrap();
+        let is_time = |brain: &Brain| {
+            brain
+                .parse("la hora por favor")
+                .map(|parsed| parsed.intent == IntentType::GetTime)
+                .unwrap_or(false)
+        };
+        // Not in the bundled Spanish grammar
+        assert!(!is_time(&brain));
+
+        write_grammar(&path, &["^que hora es$", "^la hora por favor$"]);
+
+        let deadline = std::time::Instant::now() + Duration::from_secs(5);
+        while std::time::Instant::now() < deadline && !is_time(&brain) {
+            std::thread::sleep(Duration::from_millis(50));
+        }
+
+        assert!(is_time(&brain));
+    }
+
+    fn remember(brain: &Brain, text: &str, intent: IntentType, key: &str, entity: Entity) {
+        let command = ParsedCommand {
+            intent,
+            entities: std::collections::HashMap::new(),
+            original_text: text.to_string(),
+            confidence: 0.9,
+        };
+        let entities = std::collections::HashMap::from([(key.to_string(), entity)]);
+        brain.context.write().add_command(command, entities, true);
+    }
+
+    #[test]
+    fn test_resolve_context_replaces_whole_pronouns_only() {
+        let brain = Brain::new(&BrainConfig::default()).unwrap();
+        remember(
+            &brain,
+            "open chrome",
+            IntentType::LaunchApp,
+            "app",
+            Entity::App("chrome".to_string()),
+        );
+
+        assert_eq!(brain.resolve_context("quit it"), "quit chrome");
+        assert_eq!(brain.resolve_context("edit the quilt"), "edit the quilt");
+    }
+
+    #[test]
+    fn test_resolve_context_picks_referent_by_intent() {
+        let brain = Brain::new(&BrainConfig::default()).unwrap();
+        remember(
+            &brain,
+            "find budget.pdf",
+            IntentType::FindFile,
+            "file",
+            Entity::File("budget.pdf".to_string()),
+        );
+        remember(
+            &brain,
+            "open chrome",
+            IntentType::LaunchApp,
+            "app",
+            Entity::App("chrome".to_string()),
+        );
+
+        // Closing targets the app even though a file was mentioned too
+        assert_eq!(brain.resolve_context("close it"), "close chrome");
+        // Explicit noun picks the file
+        assert_eq!(brain.resolve_context("find that file"), "find budget.pdf");
+    }
+
+    #[test]
+    fn test_resolve_ordinal_references() {
+        let brain = Brain::new(&BrainConfig::default()).unwrap();
+
+        // Earlier items in the same utterance
+        assert_eq!(
+            brain.resolve_context("open chrome and firefox, then close the first one"),
+            "open chrome and firefox, then close chrome"
+        );
+
+        brain
+            .process_with_context("open chrome and firefox", Some(true))
+            .unwrap();
+        assert_eq!(
+            brain.resolve_context("close the second one"),
+            "close firefox"
+        );
+
+        brain
+            .process_with_context("close the first one", Some(true))
+            .unwrap();
+        assert_eq!(
+            brain.resolve_context("close the other one"),
+            "close firefox"
+        );
+
+        brain.clear_context();
+        assert_eq!(
+            brain.resolve_context("close the other one"),
+            "close the other one"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_known_app_aliases() {
+        let mut brain = Brain::new(&BrainConfig::default()).unwrap();
+        brain.add_known_app_with_aliases(
+            "Visual Studio Code".to_string(),
+            vec!["vs code".to_string(), "vscode".to_string()],
+        );
+
+        let plan = brain.process_async("open vs code").await.unwrap();
+        assert_eq!(plan.classification.intent, IntentType::LaunchApp);
+        assert_eq!(
+            plan.classification.entities.get("app_name"),
+            Some(&"Visual Studio Code".to_string())
+        );
+        assert!(brain.is_known_app("vs code"));
+    }
+
+    #[test]
+    fn test_process_batch() {
+        let brain = Brain::new(&BrainConfig::default()).unwrap();
+        remember(
+            &brain,
+            "open chrome",
+            IntentType::LaunchApp,
+            "app",
+            Entity::App("chrome".to_string()),
+        );
+
+        let texts: Vec<String> = ["open slack", "quit it", "volume up"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+
+        // With context, "it" resolves to the last app
+        let plans = brain.process_batch(&texts);
+        assert_eq!(plans.len(), 3);
+        let quit = plans[1].as_ref().unwrap();
+        assert_eq!(quit.classification.intent, IntentType::CloseApp);
+        assert_eq!(
+            quit.classification.entities.get("app_name"),
+            Some(&"chrome".to_string())
+        );
+
+        // Isolated: no pronoun resolution, deterministic across runs
+        let options = BatchOptions {
+            isolate_context: true,
+        };
+        let isolated = brain.process_batch_with(&texts, options);
+        let quit = isolated[1].as_ref().unwrap();
+        assert_eq!(
+            quit.classification.entities.get("app_name"),
+            Some(&"it".to_string())
+        );
+
+        let again = brain.process_batch_with(&texts, options);
+        for (a, b) in isolated.iter().zip(&again) {
+            let (a, b) = (a.as_ref().unwrap(), b.as_ref().unwrap());
+            assert_eq!(a.classification.intent, b.classification.intent);
+            assert_eq!(a.classification.confidence, b.classification.confidence);
+        }
+
+        // Batch mode doesn't populate the cache
+        assert_eq!(brain.cache_stats().plan_size, 0);
+    }
+
+    #[tokio::test]
+    async fn test_plan_carries_confidence_factors() {
+        let brain = Brain::new(&BrainConfig::default()).unwrap();
+
+        let plan = brain.process_async("open chrome").await.unwrap();
+        assert!(plan
+            .classification
+            .factors
+            .iter()
+            .any(|f| f.name == "pattern_match"));
+
+        let explanation = brain.explain("open chrome").unwrap();
+        assert_eq!(explanation.intent, "LaunchApp");
+        assert!(explanation
+            .factors
+            .iter()
+            .any(|(label, _)| label == "Pattern match"));
+    }
+
+    #[tokio::test]
+    async fn test_process_publishes_command_parsed() {
+        let bus = Arc::new(EventBus::new());
+        let handle = bus.start_processing().await;
+        let parsed = Arc::new(parking_lot::Mutex::new(Vec::new()));
+        let sink = Arc::clone(&parsed);
+        bus.subscribe_kinds(&[EventKind::CommandParsed], move |envelope| {
+            sink.lock().push(envelope.event.clone());
+        })
+        .await;
+
+        let brain = Brain::new(&BrainConfig::default())
+            .unwrap()
+            .with_event_bus(Arc::clone(&bus));
+        brain.process_async("open discord").await.unwrap();
+
+        for _ in 0..100 {
+            if !parsed.lock().is_empty() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        handle.abort();
+
+        let events = parsed.lock().clone();
+        assert_eq!(events.len(), 1);
+        match &events[0] {
+            LunaEvent::CommandParsed {
+                command,
+                intent,
+                entities,
+                confidence,
+            } => {
+                assert_eq!(command, "open discord");
+                assert_eq!(intent, "LaunchApp");
+                assert_eq!(
+                    entities.get("app_name").map(String::as_str),
+                    Some("discord")
+                );
+                assert!(*confidence > 0.0);
+            }
+            other => panic!("unexpected event: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_spanish_commands() {
+        let config = BrainConfig {
+            language: "es".to_string(),
+            ..BrainConfig::default()
+        };
+        let brain = Brain::new(&config).unwrap();
+
+        let plan = brain.process_async("abre chrome").await.unwrap();
+        assert_eq!(plan.classification.intent, IntentType::LaunchApp);
+        assert_eq!(plan.steps[0].action, ActionType::LaunchApp);
+
+        let plan = brain.process_async("olvídalo").await.unwrap();
+        assert_eq!(plan.classification.intent, IntentType::Cancel);
+    }
+
+    #[tokio::test]
+    async fn test_classifier_middleware_rewrites_plan() {
+        let mut brain = Brain::new(&BrainConfig::default()).unwrap();
+        let before = brain.process_async("open discord").await.unwrap();
+        assert_eq!(before.steps[0].action, ActionType::LaunchApp);
+
+        // Chat stays closed during focus time
+        brain.add_classifier_middleware(|c| {
+            if c.intent == IntentType::LaunchApp
+                && c.entities
+                    .get("app_name")
+                    .is_some_and(|app| app == "discord")
+            {
+                c.intent = IntentType::CloseApp;
+            }
+        });
+        // Runs second, so it sees the remapped intent
+        brain.add_classifier_middleware(|c| {
+            if c.intent == IntentType::CloseApp {
+                c.confidence = 0.2;
+            }
+        });
+
+        let plan = brain.process_async("open discord").await.unwrap();
+        assert_eq!(plan.classification.intent, IntentType::CloseApp);
+        assert_eq!(plan.steps[0].action, ActionType::CloseApp);
+        assert!(plan.classification.confidence <= 0.2);
+        assert!(plan.classification.confidence < BrainConfig::default().confidence_threshold);
+
+        // Other commands pass through untouched
+        let plan = brain.process_async("open chrome").await.unwrap();
+        assert_eq!(plan.steps[0].action, ActionType::LaunchApp);
+    }
+
     #[test]
     fn test_end_to_end_pipeline() {
         let config = BrainConfig::default();
@@ -33,12 +33,66 @@ pub struct IntentSegment {
 
     /// Temporal modifier (e.g., "after 5 minutes")
     pub temporal: Option<TemporalModifier>,
+
+    /// Earlier segment whose result this one uses ("open budget.txt in it")
+    ///
+    /// Also stored in the command's `refers_to` entity so the planner can
+    /// link the two.
+    pub refers_to: Option<usize>,
 }
 
+/// Connectives that impose strict ordering, longest first
+const SEQUENTIAL_CONNECTIVES: &[&str] = &[
+    " and after that ",
+    ", after that ",
+    " after that ",
+    " and then ",
+    ", then ",
+    " then ",
+];
+
+/// Connectives that name the later action first ("X but first Y" = Y, X)
+const REVERSED_CONNECTIVES: &[&str] = &[", but first ", " but first "];
+
+/// Connectives that allow parallel execution, longest first
+const PARALLEL_CONNECTIVES: &[&str] = &[" and also ", ", also ", " also ", " and "];
+
+/// Trailing phrases pointing back at the previous segment, longest first
+const REFERENCE_SUFFIXES: &[&str] = &[
+    " in there",
+    " in that",
+    " with that",
+    " into it",
+    " using it",
+    " with it",
+    " in it",
+];
+
+/// Pronouns standing for the previous segment's result ("maximize it")
+const REFERENCE_PRONOUNS: &[&str] = &["it", "them", "there"];
+
+/// App-directed verbs whose object may be a reference pronoun
+const REFERENCE_VERBS: &[&str] = &[
+    "bring up",
+    "switch to",
+    "maximize",
+    "minimize",
+    "restart",
+    "resize",
+    "close",
+    "focus",
+    "hide",
+    "kill",
+    "move",
+    "quit",
+    "show",
+    "exit",
+];
+
 /// How intents are coordinated
 #[derive(Debug, Clone, PartialEq)]
 pub enum CoordinationType {
-    /// Sequential ("and then", "then")
+    /// Sequential ("then", "after that", "but first", "before X, Y")
     Sequential,
 
     /// Parallel ("and", "also")
@@ -98,13 +152,26 @@ impl MultiIntentParser {
             // Extract temporal modifier if present
             let (cleaned_text, temporal) = self.extract_temporal(segment_text)?;
 
+            // Only later segments can refer back
+            let (cleaned_text, refers_back) = match i {
+                0 => (cleaned_text, false),
+                _ => extract_reference(&cleaned_text),
+            };
+            let refers_to = refers_back.then(|| i - 1);
+
             // Parse the cleaned command
-            let command = self.base_parser.parse(&cleaned_text)?;
+            let mut command = self.base_parser.parse(&cleaned_text)?;
+            if let Some(index) = refers_to {
+                command
+                    .entities
+                    .insert("refers_to".to_string(), index.to_string());
+            }
 
             intent_segments.push(IntentSegment {
                 command,
                 position: i,
                 temporal,
+                refers_to,
             });
         }
 
@@ -116,34 +183,35 @@ impl MultiIntentParser {
     }
 
     /// Segment text by coordinators
+    ///
+    /// Segments are returned in execution order, so reversed connectives
+    /// ("but first", a leading "before X,") swap the spoken order.
     fn segment_text(&self, text: &str) -> Result<(Vec<String>, CoordinationType)> {
         let normalized = text.to_lowercase();
 
         // Check for coordinators in order of precedence
-        if normalized.contains(" and then ") {
-            let parts: Vec<String> = text
-                .split(" and then ")
-                .map(|s| s.trim().to_string())
-                .filter(|s| !s.is_empty())
-                .collect();
+        if let Some(mut parts) = split_on_connectives(&normalized, REVERSED_CONNECTIVES) {
+            parts.reverse();
             return Ok((parts, CoordinationType::Sequential));
         }
-        
-        if normalized.contains(" then ") {
-            let parts: Vec<String> = text
-                .split(" then ")
-                .map(|s| s.trim().to_string())
-                .filter(|s| !s.is_empty())
-                .collect();
+
+        // "before X, Y" runs Y first
+        if let Some(rest) = normalized.strip_prefix("before ") {
+            if let Some((later, first)) = rest.split_once(", ") {
+                let parts: Vec<String> = [first, later]
+                    .iter()
+                    .map(|s| s.trim().to_string())
+                    .filter(|s| !s.is_empty())
+                    .collect();
+                return Ok((parts, CoordinationType::Sequential));
+            }
+        }
+
+        if let Some(parts) = split_on_connectives(&normalized, SEQUENTIAL_CONNECTIVES) {
             return Ok((parts, CoordinationType::Sequential));
         }
 
-        if normalized.contains(" and ") {
-            let parts: Vec<String> = text
-                .split(" and ")
-                .map(|s| s.trim().to_string())
-                .filter(|s| !s.is_empty())
-                .collect();
+        if let Some(parts) = split_on_connectives(&normalized, PARALLEL_CONNECTIVES) {
             return Ok((parts, CoordinationType::Parallel));
         }
 
@@ -228,12 +296,71 @@ impl MultiIntentParser {
 
         normalized.contains(" and ")
             || normalized.contains(" then ")
+            || normalized.contains(" also ")
+            || normalized.contains(" but first ")
+            || normalized.starts_with("before ")
             || normalized.contains(" after ")
             || normalized.contains(" before ")
             || normalized.contains(", ")
     }
 }
 
+/// Find a reference to the previous segment
+///
+/// A trailing "in it" is stripped so the rest parses as a normal command;
+/// bare pronouns are left for the parser, and only count when they are the
+/// object of an app verb ("maximize it", not "what time it is"). Returns the
+/// text to parse and whether a reference was found.
+fn extract_reference(text: &str) -> (String, bool) {
+    let lower = text.to_lowercase();
+    for suffix in REFERENCE_SUFFIXES {
+        if let Some(rest) = lower.strip_suffix(suffix) {
+            return (rest.trim().to_string(), true);
+        }
+    }
+
+    let refers = REFERENCE_VERBS.iter().any(|verb| {
+        lower
+            .strip_prefix(verb)
+            .and_then(|rest| rest.strip_prefix(' '))
+            .and_then(|rest| rest.split(|c: char| !c.is_alphanumeric()).next())
+            .is_some_and(|word| REFERENCE_PRONOUNS.contains(&word))
+    });
+    (text.to_string(), refers)
+}
+
+/// Split text on every occurrence of any connective
+///
+/// Returns `None` when no connective is present. Connectives are tried in
+/// slice order at each position, so longer variants must come first.
+fn split_on_connectives(text: &str, connectives: &[&str]) -> Option<Vec<String>> {
+    let mut parts = Vec::new();
+    let mut rest = text;
+
+    loop {
+        let next = connectives
+            .iter()
+            .filter_map(|c| rest.find(c).map(|idx| (idx, c.len())))
+            .min_by_key(|(idx, _)| *idx);
+
+        match next {
+            Some((idx, len)) => {
+                parts.push(rest[..idx].trim().to_string());
+                rest = &rest[idx + len..];
+            }
+            None => break,
+        }
+    }
+
+    if parts.is_empty() {
+        return None;
+    }
+
+    parts.push(rest.trim().to_string());
+    parts.retain(|p| !p.is_empty());
+    Some(parts)
+}
+
 impl Default for MultiIntentParser {
     fn default() -> Self {
         Self::new()
@@ -271,6 +398,140 @@ mod tests {
         assert_eq!(result.coordination, CoordinationType::Sequential);
     }
 
+    #[test]
+    fn test_connective_coordination() {
+        let parser = MultiIntentParser::new();
+
+        let result = parser.parse("open chrome then play music").unwrap();
+        assert_eq!(result.coordination, CoordinationType::Sequential);
+        assert_eq!(result.segments[0].command.intent, IntentType::LaunchApp);
+
+        let result = parser.parse("open chrome after that play music").unwrap();
+        assert_eq!(result.coordination, CoordinationType::Sequential);
+        assert_eq!(result.segments.len(), 2);
+
+        let result = parser.parse("open chrome also play music").unwrap();
+        assert_eq!(result.coordination, CoordinationType::Parallel);
+
+        // Reversed connectives put the "first" clause first
+        let result = parser.parse("play music but first open chrome").unwrap();
+        assert_eq!(result.coordination, CoordinationType::Sequential);
+        assert_eq!(result.segments[0].command.intent, IntentType::LaunchApp);
+
+        let result = parser.parse("before you play music, open chrome").unwrap();
+        assert_eq!(result.segments.len(), 2);
+        assert_eq!(result.segments[0].command.intent, IntentType::LaunchApp);
+    }
+
+    #[test]
+    fn test_connective_plan_shape() {
+        use crate::brain::intent_classifier::IntentClassifier;
+        use crate::brain::task_planner::TaskPlanner;
+
+        let parser = MultiIntentParser::new();
+        let classifier = IntentClassifier::new();
+        let planner = TaskPlanner::new();
+
+        let plan_for = |text: &str| {
+            let multi = parser.parse(text).unwrap();
+            let items = multi
+                .segments
+                .iter()
+                .map(|s| (classifier.classify(&s.command).unwrap(), None))
+                .collect();
+            planner.plan_multi(items, multi.coordination)
+        };
+
+        // "and": one parallel group, no ordering edges
+        let plan = plan_for("open chrome and open slack");
+        assert_eq!(plan.parallel_groups, vec![vec![0, 1]]);
+        assert!(plan.dependencies.is_empty());
+
+        // "then": strict chain, no parallel groups
+        let plan = plan_for("open chrome then open slack");
+        assert!(plan.parallel_groups.is_empty());
+        assert_eq!(plan.dependencies, vec![(0, 1)]);
+
+        // "but first": chain in reversed order
+        let plan = plan_for("open slack but first open chrome");
+        assert_eq!(plan.dependencies, vec![(0, 1)]);
+        assert_eq!(plan.steps[0].params.get("app_name").map(String::as_str), Some("chrome"));
+    }
+
+    #[test]
+    fn test_reference_to_previous_segment() {
+        let parser = MultiIntentParser::new();
+
+        let result = parser
+            .parse("open vscode and open budget.txt in it")
+            .unwrap();
+        assert_eq!(result.segments.len(), 2);
+        assert_eq!(result.segments[0].refers_to, None);
+        assert_eq!(result.segments[1].refers_to, Some(0));
+        let command = &result.segments[1].command;
+        assert_eq!(command.intent, IntentType::LaunchApp);
+        assert_eq!(
+            command.entities.get("app_name").map(String::as_str),
+            Some("budget.txt")
+        );
+        assert_eq!(
+            command.entities.get("refers_to").map(String::as_str),
+            Some("0")
+        );
+
+        let result = parser.parse("open chrome and open slack").unwrap();
+        assert!(result.segments.iter().all(|s| s.refers_to.is_none()));
+
+        let result = parser.parse("open spotify and maximize it").unwrap();
+        assert_eq!(result.segments[1].refers_to, Some(0));
+    }
+
+    #[test]
+    fn test_pronoun_outside_app_verb_is_not_a_reference() {
+        let parser = MultiIntentParser::new();
+
+        let result = parser
+            .parse("open spotify and tell me what time it is")
+            .unwrap();
+        assert_eq!(result.segments.len(), 2);
+        assert!(result.segments.iter().all(|s| s.refers_to.is_none()));
+        let command = &result.segments[1].command;
+        assert!(!command.entities.contains_key("refers_to"));
+    }
+
+    #[test]
+    fn test_reference_plan_runs_after_referenced_segment() {
+        use crate::brain::intent_classifier::IntentClassifier;
+        use crate::brain::task_planner::{Precondition, TaskPlanner};
+
+        let multi = MultiIntentParser::new()
+            .parse("open vscode and open budget.txt in it")
+            .unwrap();
+        let classifier = IntentClassifier::new();
+        let items = multi
+            .segments
+            .iter()
+            .map(|s| (classifier.classify(&s.command).unwrap(), None))
+            .collect();
+        let plan = TaskPlanner::new().plan_multi(items, multi.coordination);
+
+        // "and" would run both at once; the reference orders them
+        assert_eq!(plan.dependencies, vec![(0, 1)]);
+        assert!(plan.parallel_groups.is_empty());
+        assert!(plan.steps[1]
+            .preconditions
+            .contains(&Precondition::StepCompleted(0)));
+        assert_eq!(TaskPlanner::new().get_execution_order(&plan), vec![0, 1]);
+
+        // The file is opened in the app launched first
+        let params = &plan.steps[1].params;
+        assert_eq!(
+            params.get("target_app"),
+            plan.steps[0].params.get("app_name")
+        );
+        assert!(!params.contains_key("refers_to"));
+    }
+
     #[test]
     fn test_temporal_modifier() {
         let parser = MultiIntentParser::new();
@@ -204,10 +204,36 @@ impl FileSystemProvider for DefaultFileSystemProvider {
     }
 }
 
+/// What a knowledge provider knows about an application
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppKnowledge {
+    /// Canonical application name
+    pub name: String,
+    /// Other names the application answers to
+    pub aliases: Vec<String>,
+    /// Whether the application can be used; `false` overrides lower-priority
+    /// providers that would otherwise recognise it
+    pub available: bool,
+}
+
+/// Pluggable source of application knowledge, e.g. a software catalog
+pub trait KnowledgeProvider: Send + Sync {
+    /// Provider name for logs
+    fn name(&self) -> &str;
+
+    /// Look up an application by name or alias
+    ///
+    /// Returning `None` defers to the next provider.
+    fn lookup_app(&self, name: &str) -> Option<AppKnowledge>;
+}
+
 /// Composite provider that combines multiple knowledge sources
 pub struct CompositeProvider {
     app_provider: Arc<dyn KnownAppProvider>,
     fs_provider: Arc<dyn FileSystemProvider>,
+    /// Custom providers, highest priority first, consulted before the
+    /// built-in app provider
+    knowledge: Vec<Arc<dyn KnowledgeProvider>>,
 }
 
 impl CompositeProvider {
@@ -219,7 +245,37 @@ impl CompositeProvider {
         Self {
             app_provider,
             fs_provider,
+            knowledge: Vec::new(),
+        }
+    }
+
+    /// Consult `providers`, in priority order, before the built-in app provider
+    pub fn with_providers(mut self, providers: Vec<Arc<dyn KnowledgeProvider>>) -> Self {
+        self.set_providers(providers);
+        self
+    }
+
+    /// Replace the custom providers, highest priority first
+    pub fn set_providers(&mut self, providers: Vec<Arc<dyn KnowledgeProvider>>) {
+        self.knowledge = providers;
+    }
+
+    /// Look up an application, stopping at the first provider that knows it
+    pub fn lookup_app(&self, name: &str) -> Option<AppKnowledge> {
+        if let Some(knowledge) = self.knowledge.iter().find_map(|p| p.lookup_app(name)) {
+            return Some(knowledge);
         }
+
+        self.app_provider.is_known(name).then(|| AppKnowledge {
+            name: name.to_string(),
+            aliases: self.app_provider.aliases(name),
+            available: true,
+        })
+    }
+
+    /// Check if an application is known and available
+    pub fn is_known_app(&self, name: &str) -> bool {
+        self.lookup_app(name).is_some_and(|app| app.available)
     }
 
     /// Get the app provider
@@ -231,6 +287,11 @@ impl CompositeProvider {
     pub fn fs_provider(&self) -> &Arc<dyn FileSystemProvider> {
         &self.fs_provider
     }
+
+    /// Get the app provider mutably, if it isn't shared elsewhere
+    pub fn app_provider_mut(&mut self) -> Option<&mut (dyn KnownAppProvider + 'static)> {
+        Arc::get_mut(&mut self.app_provider)
+    }
 }
 
 impl Default for CompositeProvider {
@@ -296,4 +357,74 @@ mod tests {
         assert!(provider.app_provider().is_known("chrome"));
         assert!(provider.fs_provider().is_file_like("test.pdf"));
     }
+
+    /// Catalog that answers for a fixed set of apps and counts lookups
+    struct Catalog {
+        name: &'static str,
+        apps: Vec<AppKnowledge>,
+        lookups: std::sync::atomic::AtomicUsize,
+    }
+
+    impl Catalog {
+        fn new(name: &'static str, apps: &[(&str, bool)]) -> Self {
+            Self {
+                name,
+                apps: apps
+                    .iter()
+                    .map(|(app, available)| AppKnowledge {
+                        name: app.to_string(),
+                        aliases: Vec::new(),
+                        available: *available,
+                    })
+                    .collect(),
+                lookups: Default::default(),
+            }
+        }
+    }
+
+    impl KnowledgeProvider for Catalog {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn lookup_app(&self, name: &str) -> Option<AppKnowledge> {
+            self.lookups
+                .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            self.apps
+                .iter()
+                .find(|app| app.name.eq_ignore_ascii_case(name))
+                .cloned()
+        }
+    }
+
+    #[test]
+    fn test_custom_provider_overrides_builtin() {
+        let corporate = Arc::new(Catalog::new(
+            "corporate",
+            &[("zoom", false), ("corpchat", true)],
+        ));
+        let fallback = Arc::new(Catalog::new("fallback", &[("zoom", true)]));
+        let provider =
+            CompositeProvider::default().with_providers(vec![corporate.clone(), fallback.clone()]);
+
+        // The built-in list knows zoom, but the catalog has it blocked
+        assert!(provider.app_provider().is_known("zoom"));
+        assert!(!provider.is_known_app("Zoom"));
+        assert_eq!(
+            fallback.lookups.load(std::sync::atomic::Ordering::SeqCst),
+            0
+        );
+
+        // Apps only the catalog knows
+        assert!(provider.is_known_app("corpchat"));
+        assert!(!CompositeProvider::default().is_known_app("corpchat"));
+
+        // Falls through to the built-in provider
+        assert!(provider.is_known_app("chrome"));
+        assert_eq!(
+            fallback.lookups.load(std::sync::atomic::Ordering::SeqCst),
+            1
+        );
+        assert!(!provider.is_known_app("unknownapp123"));
+    }
 }
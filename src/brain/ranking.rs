@@ -9,7 +9,10 @@ use crate::brain::intent_classifier::ClassificationResult;
 use crate::brain::providers::CompositeProvider;
 use crate::brain::types::{Confidence, ConfidenceFactor};
 use crate::config::BrainConfig;
+use crate::error::{LunaError, Result};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::Path;
 
 /// Feature weights for ranking
 #[derive(Debug, Clone)]
@@ -66,9 +69,107 @@ impl RankingWeights {
     }
 }
 
+/// Monotonic mapping from raw ranking scores to calibrated confidence
+///
+/// Loadable from YAML, e.g.:
+/// ```yaml
+/// type: piecewise_linear
+/// points: [[0.0, 0.0], [0.6, 0.85], [1.0, 1.0]]
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CalibrationCurve {
+    /// Leave scores unchanged
+    Identity,
+
+    /// Linear interpolation between `(raw, calibrated)` points sorted by raw score
+    PiecewiseLinear { points: Vec<(f32, f32)> },
+
+    /// Logistic curve `1 / (1 + e^(-steepness * (x - midpoint)))`
+    Logistic { midpoint: f32, steepness: f32 },
+}
+
+impl CalibrationCurve {
+    /// Load a curve from a YAML file
+    pub fn load_from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let contents = std::fs::read_to_string(path.as_ref()).map_err(|e| {
+            LunaError::Config(format!("Failed to read calibration file: {}", e))
+        })?;
+        Self::from_yaml(&contents)
+    }
+
+    /// Parse and validate a curve from YAML
+    pub fn from_yaml(yaml: &str) -> Result<Self> {
+        let curve: Self = serde_yaml::from_str(yaml)
+            .map_err(|e| LunaError::Config(format!("Failed to parse calibration YAML: {}", e)))?;
+        curve.validate()?;
+        Ok(curve)
+    }
+
+    /// Check that the curve is monotonic non-decreasing
+    pub fn validate(&self) -> Result<()> {
+        match self {
+            CalibrationCurve::Identity => Ok(()),
+            CalibrationCurve::PiecewiseLinear { points } => {
+                if points.is_empty() {
+                    return Err(LunaError::Config(
+                        "Calibration curve needs at least one point".to_string(),
+                    ));
+                }
+                for pair in points.windows(2) {
+                    let ((x0, y0), (x1, y1)) = (pair[0], pair[1]);
+                    if x1 <= x0 || y1 < y0 {
+                        return Err(LunaError::Config(format!(
+                            "Calibration points must be increasing: ({}, {}) -> ({}, {})",
+                            x0, y0, x1, y1
+                        )));
+                    }
+                }
+                Ok(())
+            }
+            CalibrationCurve::Logistic { steepness, .. } => {
+                if *steepness <= 0.0 {
+                    return Err(LunaError::Config(format!(
+                        "Logistic steepness {} must be positive",
+                        steepness
+                    )));
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Map a raw score (0.0 - 1.0) to a calibrated score (0.0 - 1.0)
+    pub fn apply(&self, raw: f32) -> f32 {
+        let raw = raw.clamp(0.0, 1.0);
+
+        let calibrated = match self {
+            CalibrationCurve::Identity => raw,
+            CalibrationCurve::PiecewiseLinear { points } => {
+                match points.iter().position(|&(x, _)| x >= raw) {
+                    None => points.last().map(|&(_, y)| y).unwrap_or(raw),
+                    Some(0) => points[0].1,
+                    Some(i) => {
+                        let (x0, y0) = points[i - 1];
+                        let (x1, y1) = points[i];
+                        y0 + (y1 - y0) * (raw - x0) / (x1 - x0)
+                    }
+                }
+            }
+            CalibrationCurve::Logistic {
+                midpoint,
+                steepness,
+            } => 1.0 / (1.0 + (-steepness * (raw - midpoint)).exp()),
+        };
+
+        calibrated.clamp(0.0, 1.0)
+    }
+}
+
 /// Feature-based ranking scorer
 pub struct RankingScorer {
     weights: RankingWeights,
+    calibration: Option<CalibrationCurve>,
 }
 
 impl RankingScorer {
@@ -76,12 +177,38 @@ impl RankingScorer {
     pub fn new() -> Self {
         Self {
             weights: RankingWeights::default(),
+            calibration: None,
         }
     }
 
     /// Create with custom weights
     pub fn with_weights(weights: RankingWeights) -> Self {
-        Self { weights }
+        Self {
+            weights,
+            calibration: None,
+        }
+    }
+
+    /// Map final scores through a calibration curve
+    pub fn with_calibration(mut self, curve: CalibrationCurve) -> Self {
+        self.calibration = Some(curve);
+        self
+    }
+
+    /// Highest confidence `score` can produce with these weights
+    ///
+    /// Synonym overlap and pattern priority are not scored yet, so they
+    /// don't count towards the maximum.
+    pub fn max_score(&self) -> f32 {
+        let raw = (self.weights.pattern_match
+            + self.weights.entity_validation
+            + self.weights.context_match
+            + self.weights.recent_success)
+            .clamp(0.0, 1.0);
+        match &self.calibration {
+            Some(curve) => curve.apply(raw),
+            None => raw,
+        }
     }
 
     /// Score a classification result using multiple features
@@ -141,8 +268,12 @@ impl RankingScorer {
             }
         }
 
-        // Set final score
-        confidence.score = total_score.clamp(0.0, 1.0);
+        // Set final score, calibrated if a curve is configured
+        confidence.raw_score = total_score.clamp(0.0, 1.0);
+        confidence.score = match &self.calibration {
+            Some(curve) => curve.apply(confidence.raw_score),
+            None => confidence.raw_score,
+        };
 
         confidence
     }
@@ -161,7 +292,7 @@ impl RankingScorer {
 
             match key.as_str() {
                 "app_name" => {
-                    if providers.app_provider().is_known(value) {
+                    if providers.is_known_app(value) {
                         validation_score += 1.0;
                     } else {
                         validation_score += 0.3; // Partial credit for unknown apps
@@ -229,6 +360,11 @@ impl RankingScorer {
     pub fn set_weights(&mut self, weights: RankingWeights) {
         self.weights = weights;
     }
+
+    /// Get calibration curve, if any
+    pub fn calibration(&self) -> Option<&CalibrationCurve> {
+        self.calibration.as_ref()
+    }
 }
 
 impl Default for RankingScorer {
@@ -259,6 +395,7 @@ mod tests {
             confidence,
             entities: HashMap::new(),
             alternatives: Vec::new(),
+            factors: Vec::new(),
         }
     }
 
@@ -326,4 +463,53 @@ mod tests {
         // Should have context factors
         assert!(confidence.factors.len() > 2);
     }
+
+    fn assert_monotonic(curve: &CalibrationCurve) {
+        let mut previous = curve.apply(0.0);
+        for i in 1..=100 {
+            let value = curve.apply(i as f32 / 100.0);
+            assert!(value >= previous, "{:?} decreases at {}", curve, i);
+            previous = value;
+        }
+    }
+
+    #[test]
+    fn test_calibration_is_monotonic() {
+        let piecewise = CalibrationCurve::from_yaml(
+            "type: piecewise_linear\npoints: [[0.0, 0.0], [0.6, 0.85], [1.0, 1.0]]",
+        )
+        .unwrap();
+        assert!((piecewise.apply(0.6) - 0.85).abs() < 1e-6);
+        assert!((piecewise.apply(0.3) - 0.425).abs() < 1e-6);
+        assert_monotonic(&piecewise);
+
+        let logistic = CalibrationCurve::Logistic {
+            midpoint: 0.5,
+            steepness: 10.0,
+        };
+        assert_monotonic(&logistic);
+
+        // Decreasing curves are rejected
+        assert!(CalibrationCurve::from_yaml(
+            "type: piecewise_linear\npoints: [[0.0, 1.0], [1.0, 0.0]]"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_identity_calibration_changes_nothing() {
+        let providers = CompositeProvider::default();
+        let mut entities = HashMap::new();
+        entities.insert("app_name".to_string(), "chrome".to_string());
+        let parsed = create_test_parsed(entities);
+        let classification = create_test_classification(0.8);
+
+        let plain = RankingScorer::new().score(&parsed, &classification, &providers, None);
+        let calibrated = RankingScorer::new()
+            .with_calibration(CalibrationCurve::Identity)
+            .score(&parsed, &classification, &providers, None);
+
+        assert_eq!(plain.score, calibrated.score);
+        assert_eq!(calibrated.score, calibrated.raw_score);
+    }
 }
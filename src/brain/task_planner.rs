@@ -3,12 +3,18 @@
 //! Breaks complex commands into sequential action steps with dependencies.
 
 use crate::brain::command_parser::IntentType;
+use crate::brain::entity_extractor::EntityExtractor;
 use crate::brain::intent_classifier::ClassificationResult;
+use crate::error::Result;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::fmt::Write;
+use std::ops::Range;
 use tracing::{debug, info};
+use uuid::Uuid;
 
 /// Action type for execution
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum ActionType {
     /// Launch an application
     LaunchApp,
@@ -22,6 +28,8 @@ pub enum ActionType {
     SystemControl,
     /// Control volume
     VolumeControl,
+    /// Control screen brightness
+    BrightnessControl,
     /// Manage windows
     WindowManagement,
     /// Control media playback
@@ -40,10 +48,24 @@ pub enum ActionType {
     GetDate,
     /// Wait/delay
     Wait,
+    /// Read, write, or clear the clipboard
+    ClipboardControl,
+    /// Run an allowlisted command skill
+    RunCommand,
+    /// Switch, create, or move windows between virtual desktops
+    VirtualDesktopControl,
+    /// Change the power profile or report battery status
+    PowerControl,
+    /// Change a process's priority or CPU affinity
+    ProcessControl,
+    /// No-op that just acknowledges the user
+    Acknowledge,
+    /// Handled by a skill registered on the executor
+    Custom,
 }
 
 /// Single action step in a task plan
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ActionStep {
     /// The action type
     pub action: ActionType,
@@ -57,10 +79,15 @@ pub struct ActionStep {
     pub postconditions: Vec<Postcondition>,
     /// Can this step run in parallel with others?
     pub parallel_group: Option<usize>,
+    /// Action that undoes this step if a later step fails
+    pub compensation: Option<Box<ActionStep>>,
+    /// Failure is recorded and the rest of the plan still runs
+    #[serde(default)]
+    pub optional: bool,
 }
 
 /// Precondition that must be satisfied before an action
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Precondition {
     /// Another step must complete first
     StepCompleted(usize),
@@ -73,7 +100,7 @@ pub enum Precondition {
 }
 
 /// Postcondition that is true after an action
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Postcondition {
     /// A state has changed
     StateChanged(String, String),
@@ -84,7 +111,7 @@ pub enum Postcondition {
 }
 
 /// Complete task plan with steps and dependencies
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TaskPlan {
     /// Sequential steps to execute
     pub steps: Vec<ActionStep>,
@@ -98,6 +125,68 @@ pub struct TaskPlan {
     pub is_valid: bool,
     /// Validation errors if any
     pub validation_errors: Vec<String>,
+    /// Utterance this plan was made for; execution events reuse it
+    pub correlation_id: Option<Uuid>,
+}
+
+impl TaskPlan {
+    /// Pretty-printed JSON, for inspecting or saving a plan
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Parse a plan written by `to_json`
+    pub fn from_json(json: &str) -> Result<Self> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Graphviz DOT graph of the steps, their dependencies and parallel groups
+    ///
+    /// Render with `dot -Tsvg`. Optional steps are drawn dashed.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph plan {\n    rankdir=LR;\n    node [shape=box];\n");
+
+        for (idx, step) in self.steps.iter().enumerate() {
+            let mut params: Vec<_> = step.params.iter().collect();
+            params.sort();
+            let mut label = format!("{}: {:?}", idx, step.action);
+            for (key, value) in params {
+                label.push_str(&format!("\n{}={}", key, value));
+            }
+            let style = if step.optional { ", style=dashed" } else { "" };
+            let _ = writeln!(
+                dot,
+                "    s{} [label=\"{}\"{}];",
+                idx,
+                dot_escape(&label),
+                style
+            );
+        }
+
+        for (group_idx, group) in self.parallel_groups.iter().enumerate() {
+            let _ = writeln!(dot, "    subgraph cluster_{} {{", group_idx);
+            let _ = writeln!(dot, "        label=\"parallel group {}\";", group_idx);
+            for step in group {
+                let _ = writeln!(dot, "        s{};", step);
+            }
+            dot.push_str("    }\n");
+        }
+
+        for (from, to) in &self.dependencies {
+            let _ = writeln!(dot, "    s{} -> s{};", from, to);
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+/// Escape a DOT label, keeping `\n` line breaks
+fn dot_escape(label: &str) -> String {
+    label
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
 }
 
 /// Task planner that breaks commands into executable steps
@@ -115,7 +204,7 @@ impl TaskPlanner {
 
         let steps = self.create_steps(&classification);
         let dependencies = self.compute_dependencies(&steps);
-        let parallel_groups = self.compute_parallel_groups(&steps);
+        let parallel_groups = self.compute_parallel_groups(&steps, &dependencies);
 
         debug!(
             "Created {} steps with {} dependencies and {} parallel groups",
@@ -131,6 +220,7 @@ impl TaskPlanner {
             parallel_groups,
             is_valid: true,
             validation_errors: Vec::new(),
+            correlation_id: None,
         };
 
         // Validate the plan
@@ -156,6 +246,17 @@ impl TaskPlanner {
                         Postcondition::StateChanged("app_running".to_string(), "true".to_string()),
                     ],
                     parallel_group: None,
+                    compensation: Some(Box::new(ActionStep {
+                        action: ActionType::CloseApp,
+                        params: classification.entities.clone(),
+                        step_number: 0,
+                        preconditions: vec![],
+                        postconditions: vec![Postcondition::Success],
+                        parallel_group: None,
+                        compensation: None,
+                        optional: false,
+                    })),
+                    optional: false,
                 });
             }
 
@@ -167,18 +268,33 @@ impl TaskPlanner {
                     preconditions: vec![],
                     postconditions: vec![Postcondition::Success],
                     parallel_group: None,
+                    compensation: None,
+                    optional: false,
                 });
             }
 
             IntentType::FindFile => {
+                // "PDFs about taxes" -> extension=pdf, file_name=taxes
+                let mut params = classification.entities.clone();
+                let filters = params
+                    .get("file_name")
+                    .map(|name| EntityExtractor::new().extract_file_filters(name))
+                    .unwrap_or_default();
+                if !filters.is_empty() {
+                    params.remove("file_name");
+                    params.extend(filters);
+                }
+
                 // Multi-step: find file, then potentially open it
                 steps.push(ActionStep {
                     action: ActionType::FindFile,
-                    params: classification.entities.clone(),
+                    params,
                     step_number: 0,
                     preconditions: vec![],
                     postconditions: vec![Postcondition::Success],
                     parallel_group: None,
+                    compensation: None,
+                    optional: false,
                 });
             }
 
@@ -190,6 +306,8 @@ impl TaskPlanner {
                     preconditions: vec![],
                     postconditions: vec![Postcondition::Success],
                     parallel_group: None,
+                    compensation: None,
+                    optional: false,
                 });
             }
 
@@ -201,6 +319,8 @@ impl TaskPlanner {
                     preconditions: vec![],
                     postconditions: vec![Postcondition::Success],
                     parallel_group: None,
+                    compensation: None,
+                    optional: false,
                 });
             }
 
@@ -212,17 +332,48 @@ impl TaskPlanner {
                     preconditions: vec![],
                     postconditions: vec![Postcondition::Success],
                     parallel_group: None,
+                    compensation: None,
+                    optional: false,
+                });
+            }
+
+            IntentType::BrightnessControl => {
+                steps.push(ActionStep {
+                    action: ActionType::BrightnessControl,
+                    params: classification.entities.clone(),
+                    step_number: 0,
+                    preconditions: vec![],
+                    postconditions: vec![Postcondition::Success],
+                    parallel_group: None,
+                    compensation: None,
+                    optional: false,
                 });
             }
 
             IntentType::MediaControl => {
+                // "skip back ten seconds" -> delta=-10
+                let mut params = classification.entities.clone();
+                if let Some(secs) = params.get("amount").and_then(|amount| {
+                    EntityExtractor::new()
+                        .extract_spoken_duration(amount)
+                        .map(|d| d.num_seconds())
+                }) {
+                    let back = params
+                        .get("direction")
+                        .is_some_and(|d| d.starts_with("back"));
+                    let delta = if back { -secs } else { secs };
+                    params.insert("delta".to_string(), delta.to_string());
+                }
+
                 steps.push(ActionStep {
                     action: ActionType::MediaControl,
-                    params: classification.entities.clone(),
+                    params,
                     step_number: 0,
                     preconditions: vec![],
                     postconditions: vec![Postcondition::Success],
                     parallel_group: None,
+                    compensation: None,
+                    optional: false,
                 });
             }
 
@@ -234,6 +385,8 @@ impl TaskPlanner {
                     preconditions: vec![],
                     postconditions: vec![Postcondition::Success],
                     parallel_group: None,
+                    compensation: None,
+                    optional: false,
                 });
             }
 
@@ -245,6 +398,8 @@ impl TaskPlanner {
                     preconditions: vec![],
                     postconditions: vec![Postcondition::Success],
                     parallel_group: None,
+                    compensation: None,
+                    optional: false,
                 });
             }
 
@@ -256,6 +411,8 @@ impl TaskPlanner {
                     preconditions: vec![],
                     postconditions: vec![Postcondition::Success],
                     parallel_group: None,
+                    compensation: None,
+                    optional: false,
                 });
             }
 
@@ -267,6 +424,8 @@ impl TaskPlanner {
                     preconditions: vec![],
                     postconditions: vec![Postcondition::Success],
                     parallel_group: None,
+                    compensation: None,
+                    optional: false,
                 });
             }
 
@@ -278,6 +437,8 @@ impl TaskPlanner {
                     preconditions: vec![],
                     postconditions: vec![Postcondition::Success],
                     parallel_group: None,
+                    compensation: None,
+                    optional: false,
                 });
             }
 
@@ -289,6 +450,8 @@ impl TaskPlanner {
                     preconditions: vec![],
                     postconditions: vec![Postcondition::Success],
                     parallel_group: None,
+                    compensation: None,
+                    optional: false,
                 });
             }
 
@@ -300,6 +463,89 @@ impl TaskPlanner {
                     preconditions: vec![],
                     postconditions: vec![Postcondition::Success],
                     parallel_group: None,
+                    compensation: None,
+                    optional: false,
+                });
+            }
+
+            IntentType::Clipboard => {
+                steps.push(ActionStep {
+                    action: ActionType::ClipboardControl,
+                    params: classification.entities.clone(),
+                    step_number: 0,
+                    preconditions: vec![],
+                    postconditions: vec![Postcondition::Success],
+                    parallel_group: None,
+                    compensation: None,
+                    optional: false,
+                });
+            }
+
+            IntentType::VirtualDesktop => {
+                steps.push(ActionStep {
+                    action: ActionType::VirtualDesktopControl,
+                    params: classification.entities.clone(),
+                    step_number: 0,
+                    preconditions: vec![],
+                    postconditions: vec![Postcondition::Success],
+                    parallel_group: None,
+                    compensation: None,
+                    optional: false,
+                });
+            }
+
+            IntentType::Power => {
+                steps.push(ActionStep {
+                    action: ActionType::PowerControl,
+                    params: classification.entities.clone(),
+                    step_number: 0,
+                    preconditions: vec![],
+                    postconditions: vec![Postcondition::Success],
+                    parallel_group: None,
+                    compensation: None,
+                    optional: false,
+                });
+            }
+
+            IntentType::ProcessControl => {
+                steps.push(ActionStep {
+                    action: ActionType::ProcessControl,
+                    params: classification.entities.clone(),
+                    step_number: 0,
+                    preconditions: vec![],
+                    postconditions: vec![Postcondition::Success],
+                    parallel_group: None,
+                    compensation: None,
+                    optional: false,
+                });
+            }
+
+            IntentType::RunCommand => {
+                steps.push(ActionStep {
+                    action: ActionType::RunCommand,
+                    params: classification.entities.clone(),
+                    step_number: 0,
+                    preconditions: vec![],
+                    postconditions: vec![Postcondition::Success],
+                    parallel_group: None,
+                    compensation: None,
+                    optional: false,
+                });
+            }
+
+            IntentType::Cancel => {
+                steps.push(ActionStep {
+                    action: ActionType::Acknowledge,
+                    params: HashMap::from([(
+                        "message".to_string(),
+                        "Okay, cancelled".to_string(),
+                    )]),
+                    step_number: 0,
+                    preconditions: vec![],
+                    postconditions: vec![Postcondition::Success],
+                    parallel_group: None,
+                    compensation: None,
+                    optional: false,
                 });
             }
 
@@ -312,10 +558,23 @@ impl TaskPlanner {
                     preconditions: vec![],
                     postconditions: vec![Postcondition::Success],
                     parallel_group: None,
+                    compensation: None,
+                    optional: false,
                 });
             }
         }
 
+        // Set by the parser for commands ending in "if you can"
+        if classification
+            .entities
+            .get("optional")
+            .is_some_and(|value| value == "true")
+        {
+            for step in &mut steps {
+                step.optional = true;
+            }
+        }
+
         steps
     }
 
@@ -348,7 +607,8 @@ impl TaskPlanner {
 
         let mut all_steps = Vec::new();
         let mut dependencies = Vec::new();
-        let mut parallel_groups = Vec::new();
+        // Action steps of each item (waits excluded) and the item it refers to
+        let mut segments = Vec::new();
         let mut step_offset = 0;
 
         // Get first classification for overall plan metadata
@@ -364,18 +624,18 @@ impl TaskPlanner {
                     confidence: 0.0,
                     entities: std::collections::HashMap::new(),
                     alternatives: Vec::new(),
+                    factors: Vec::new(),
                 },
                 parallel_groups: Vec::new(),
                 is_valid: true,
                 validation_errors: Vec::new(),
+                correlation_id: None,
             };
         };
 
         match coordination {
             CoordinationType::Parallel => {
                 // All items can run in parallel
-                let mut parallel_group = Vec::new();
-
                 for (classification, temporal) in items {
                     // Add wait step if temporal modifier present
                     if let Some(duration) = temporal {
@@ -390,25 +650,30 @@ impl TaskPlanner {
                             preconditions: vec![],
                             postconditions: vec![Postcondition::Success],
                             parallel_group: None,
+                            compensation: None,
+                            optional: false,
                         };
                         all_steps.push(wait_step);
                         step_offset += 1;
                     }
 
-                    // Add the action steps
+                    // Only a segment's first step is free to start at once;
+                    // anything after a wait or within a multi-step segment
+                    // stays ordered
                     let mut segment_steps = self.create_steps(&classification);
-                    for step in &mut segment_steps {
+                    for (i, step) in segment_steps.iter_mut().enumerate() {
                         step.step_number = step_offset;
-                        step.parallel_group = Some(0); // All in group 0
-                        parallel_group.push(step_offset);
+                        if i > 0 || temporal.is_some() {
+                            dependencies.push((step_offset - 1, step_offset));
+                        }
                         step_offset += 1;
                     }
+                    segments.push((
+                        step_offset - segment_steps.len()..step_offset,
+                        referenced_segment(&classification),
+                    ));
                     all_steps.extend(segment_steps);
                 }
-
-                if !parallel_group.is_empty() {
-                    parallel_groups.push(parallel_group);
-                }
             }
             CoordinationType::Sequential | CoordinationType::Temporal => {
                 // Items run sequentially
@@ -426,6 +691,8 @@ impl TaskPlanner {
                             preconditions: vec![],
                             postconditions: vec![Postcondition::Success],
                             parallel_group: None,
+                            compensation: None,
+                            optional: false,
                         };
                         all_steps.push(wait_step);
                         
@@ -447,6 +714,10 @@ impl TaskPlanner {
                         }
                         step_offset += 1;
                     }
+                    segments.push((
+                        step_offset - segment_steps.len()..step_offset,
+                        referenced_segment(&classification),
+                    ));
                     all_steps.extend(segment_steps);
                 }
             }
@@ -465,6 +736,8 @@ impl TaskPlanner {
                             preconditions: vec![],
                             postconditions: vec![Postcondition::Success],
                             parallel_group: None,
+                            compensation: None,
+                            optional: false,
                         };
                         all_steps.push(wait_step);
                         if step_offset > 0 {
@@ -481,11 +754,18 @@ impl TaskPlanner {
                         }
                         step_offset += 1;
                     }
+                    segments.push((
+                        step_offset - segment_steps.len()..step_offset,
+                        referenced_segment(&classification),
+                    ));
                     all_steps.extend(segment_steps);
                 }
             }
         }
 
+        link_references(&mut all_steps, &mut dependencies, &segments);
+
+        let parallel_groups = self.compute_parallel_groups(&all_steps, &dependencies);
         let mut plan = TaskPlan {
             steps: all_steps,
             dependencies,
@@ -493,6 +773,7 @@ impl TaskPlanner {
             parallel_groups,
             is_valid: true,
             validation_errors: Vec::new(),
+            correlation_id: None,
         };
 
         // Validate the plan
@@ -508,23 +789,41 @@ impl TaskPlanner {
     }
 
     /// Get execution order considering dependencies
+    ///
+    /// Falls back to plan order when the dependencies contain a cycle.
     pub fn get_execution_order(&self, plan: &TaskPlan) -> Vec<usize> {
-        // Simple topological sort for now
-        // Since we mostly have sequential dependencies, this is straightforward
-        (0..plan.steps.len()).collect()
+        match topological_layers(plan.steps.len(), &plan.dependencies) {
+            Some(layers) => layers.into_iter().flatten().collect(),
+            None => (0..plan.steps.len()).collect(),
+        }
     }
 
     /// Compute parallel execution groups
-    fn compute_parallel_groups(&self, steps: &[ActionStep]) -> Vec<Vec<usize>> {
-        let mut groups: HashMap<usize, Vec<usize>> = HashMap::new();
-
-        for (idx, step) in steps.iter().enumerate() {
-            if let Some(group_id) = step.parallel_group {
-                groups.entry(group_id).or_insert_with(Vec::new).push(idx);
-            }
+    ///
+    /// Each group is a layer of the dependency graph: steps whose
+    /// dependencies all ran in earlier groups. Steps sharing an explicit
+    /// `parallel_group` id are kept in one group, unless that would run a
+    /// step before one of its dependencies. Every step lands in exactly one
+    /// group; a plain chain gets no groups and runs sequentially.
+    fn compute_parallel_groups(
+        &self,
+        steps: &[ActionStep],
+        dependencies: &[(usize, usize)],
+    ) -> Vec<Vec<usize>> {
+        let has_explicit = steps.iter().any(|step| step.parallel_group.is_some());
+        let explicit = if has_explicit {
+            explicit_layers(steps, dependencies)
+        } else {
+            None
+        };
+        if has_explicit && explicit.is_none() {
+            debug!("Explicit parallel groups conflict with dependencies, ignoring them");
         }
 
-        groups.into_values().collect()
+        match explicit.or_else(|| topological_layers(steps.len(), dependencies)) {
+            Some(groups) if groups.iter().any(|group| group.len() > 1) => groups,
+            _ => Vec::new(),
+        }
     }
 
     /// Validate a task plan
@@ -579,6 +878,21 @@ impl TaskPlanner {
                     }
                 }
             }
+
+            // Steps in a group start together, so none can wait on another
+            for &step_idx in group {
+                for precond in &plan.steps[step_idx].preconditions {
+                    if let Precondition::StepCompleted(dep_idx) = precond {
+                        if group.contains(dep_idx) {
+                            plan.is_valid = false;
+                            plan.validation_errors.push(format!(
+                                "Step {} waits for step {} in the same parallel group",
+                                step_idx, dep_idx
+                            ));
+                        }
+                    }
+                }
+            }
         }
     }
 
@@ -629,6 +943,149 @@ impl TaskPlanner {
     }
 }
 
+/// Index of the earlier multi-intent segment a classification refers to
+fn referenced_segment(classification: &ClassificationResult) -> Option<usize> {
+    classification.entities.get("refers_to")?.parse().ok()
+}
+
+/// Make segments that refer back ("open budget.txt in it") wait for the
+/// segment they name, and point them at its app
+///
+/// The referring step keeps its own `app_name` and gets the earlier one as
+/// `target_app`, or takes it as `app_name` if it had none ("maximize it").
+fn link_references(
+    steps: &mut [ActionStep],
+    dependencies: &mut Vec<(usize, usize)>,
+    segments: &[(Range<usize>, Option<usize>)],
+) {
+    for (range, refers_to) in segments {
+        for step in &mut steps[range.clone()] {
+            step.params.remove("refers_to");
+        }
+
+        let Some((referenced, _)) = refers_to.and_then(|index| segments.get(index)) else {
+            continue;
+        };
+        if referenced.is_empty() || range.is_empty() || referenced.end > range.start {
+            continue;
+        }
+        let (source, first) = (referenced.end - 1, range.start);
+
+        if !dependencies.contains(&(source, first)) {
+            dependencies.push((source, first));
+        }
+
+        let source_app = steps[source].params.get("app_name").cloned();
+        let step = &mut steps[first];
+        step.preconditions.push(Precondition::StepCompleted(source));
+        if let Some(app) = source_app {
+            let key = if step.params.contains_key("app_name") {
+                "target_app"
+            } else {
+                "app_name"
+            };
+            step.params.insert(key.to_string(), app);
+        }
+    }
+}
+
+/// Layered topological sort (Kahn's algorithm)
+///
+/// Each layer holds the steps whose dependencies are all in earlier layers,
+/// in step order. Returns `None` if the dependencies contain a cycle.
+fn topological_layers(
+    step_count: usize,
+    dependencies: &[(usize, usize)],
+) -> Option<Vec<Vec<usize>>> {
+    let mut in_degree = vec![0usize; step_count];
+    let mut dependents: Vec<Vec<usize>> = vec![Vec::new(); step_count];
+    for &(from, to) in dependencies {
+        if from >= step_count || to >= step_count {
+            continue;
+        }
+        in_degree[to] += 1;
+        dependents[from].push(to);
+    }
+
+    let mut layers = Vec::new();
+    let mut ready: Vec<usize> = (0..step_count).filter(|&i| in_degree[i] == 0).collect();
+    let mut scheduled = 0;
+    while !ready.is_empty() {
+        let mut next = Vec::new();
+        for &step in &ready {
+            for &dependent in &dependents[step] {
+                in_degree[dependent] -= 1;
+                if in_degree[dependent] == 0 {
+                    next.push(dependent);
+                }
+            }
+        }
+        next.sort_unstable();
+        scheduled += ready.len();
+        layers.push(std::mem::replace(&mut ready, next));
+    }
+
+    (scheduled == step_count).then_some(layers)
+}
+
+/// Dependency layers that keep steps with the same explicit group id together
+///
+/// Each id is layered as a single unit. Within a layer, ungrouped steps run
+/// as one group and each id as its own, ordered by first step. Returns `None`
+/// if the ids can't be honoured without breaking a dependency.
+fn explicit_layers(
+    steps: &[ActionStep],
+    dependencies: &[(usize, usize)],
+) -> Option<Vec<Vec<usize>>> {
+    // One unit per explicit id and per ungrouped step, in order of first step
+    let mut units: Vec<(Option<usize>, Vec<usize>)> = Vec::new();
+    let mut unit_of = Vec::with_capacity(steps.len());
+    for (idx, step) in steps.iter().enumerate() {
+        let existing = step
+            .parallel_group
+            .and_then(|id| units.iter().position(|(unit_id, _)| *unit_id == Some(id)));
+        match existing {
+            Some(unit) => {
+                units[unit].1.push(idx);
+                unit_of.push(unit);
+            }
+            None => {
+                unit_of.push(units.len());
+                units.push((step.parallel_group, vec![idx]));
+            }
+        }
+    }
+
+    let mut unit_dependencies = Vec::new();
+    for &(from, to) in dependencies {
+        let (Some(&from), Some(&to)) = (unit_of.get(from), unit_of.get(to)) else {
+            continue;
+        };
+        if from == to {
+            return None;
+        }
+        unit_dependencies.push((from, to));
+    }
+
+    let mut groups = Vec::new();
+    for layer in topological_layers(units.len(), &unit_dependencies)? {
+        let mut ungrouped = Vec::new();
+        let mut layer_groups = Vec::new();
+        for unit in layer {
+            match &units[unit] {
+                (Some(_), members) => layer_groups.push(members.clone()),
+                (None, members) => ungrouped.extend(members),
+            }
+        }
+        if !ungrouped.is_empty() {
+            layer_groups.push(ungrouped);
+        }
+        layer_groups.sort_by_key(|group| group[0]);
+        groups.extend(layer_groups);
+    }
+    Some(groups)
+}
+
 impl Default for TaskPlanner {
     fn default() -> Self {
         Self::new()
@@ -649,6 +1106,7 @@ mod tests {
             confidence: 0.9,
             entities,
             alternatives: Vec::new(),
+            factors: Vec::new(),
         }
     }
 
@@ -663,6 +1121,28 @@ mod tests {
         assert_eq!(plan.steps[0].action, ActionType::LaunchApp);
     }
 
+    #[test]
+    fn test_plan_cancel() {
+        let planner = TaskPlanner::new();
+        let classification = create_test_classification(IntentType::Cancel);
+
+        let plan = planner.plan(classification);
+
+        assert_eq!(plan.steps.len(), 1);
+        assert_eq!(plan.steps[0].action, ActionType::Acknowledge);
+    }
+
+    #[test]
+    fn test_plan_clipboard() {
+        let planner = TaskPlanner::new();
+        let classification = create_test_classification(IntentType::Clipboard);
+
+        let plan = planner.plan(classification);
+
+        assert_eq!(plan.steps.len(), 1);
+        assert_eq!(plan.steps[0].action, ActionType::ClipboardControl);
+    }
+
     #[test]
     fn test_plan_find_file() {
         let planner = TaskPlanner::new();
@@ -674,6 +1154,47 @@ mod tests {
         assert_eq!(plan.steps[0].action, ActionType::FindFile);
     }
 
+    #[test]
+    fn test_plan_find_file_filters() {
+        let planner = TaskPlanner::new();
+        let mut classification = create_test_classification(IntentType::FindFile);
+        classification.entities.insert(
+            "file_name".to_string(),
+            "videos bigger than 2 gb".to_string(),
+        );
+
+        let plan = planner.plan(classification);
+        let params = &plan.steps[0].params;
+        assert_eq!(params.get("file_type").map(String::as_str), Some("video"));
+        assert_eq!(
+            params.get("min_size").map(String::as_str),
+            Some("2147483648")
+        );
+        assert!(!params.contains_key("file_name"));
+    }
+
+    #[test]
+    fn test_plan_media_seek_delta() {
+        let planner = TaskPlanner::new();
+        let mut classification = create_test_classification(IntentType::MediaControl);
+        for (key, value) in [
+            ("action", "seek"),
+            ("direction", "back"),
+            ("amount", "thirty seconds"),
+        ] {
+            classification
+                .entities
+                .insert(key.to_string(), value.to_string());
+        }
+
+        let plan = planner.plan(classification);
+        assert_eq!(plan.steps[0].action, ActionType::MediaControl);
+        assert_eq!(
+            plan.steps[0].params.get("delta").map(String::as_str),
+            Some("-30")
+        );
+    }
+
     #[test]
     fn test_is_executable() {
         let planner = TaskPlanner::new();
@@ -703,4 +1224,154 @@ mod tests {
         // Should still create a plan (with answer question fallback)
         assert!(!plan.steps.is_empty());
     }
+
+    #[test]
+    fn test_same_group_step_precondition_is_invalid() {
+        use crate::brain::multi_intent::CoordinationType;
+
+        let planner = TaskPlanner::new();
+        let items = vec![
+            (create_test_classification(IntentType::GetTime), None),
+            (create_test_classification(IntentType::GetDate), None),
+        ];
+        let mut plan = planner.plan_multi(items, CoordinationType::Parallel);
+        assert_eq!(plan.parallel_groups, vec![vec![0, 1]]);
+        assert!(plan.is_valid);
+
+        plan.steps[1]
+            .preconditions
+            .push(Precondition::StepCompleted(0));
+        planner.validate_plan(&mut plan);
+
+        assert!(!plan.is_valid);
+        assert!(plan
+            .validation_errors
+            .iter()
+            .any(|e| e.contains("same parallel group")));
+    }
+
+    #[test]
+    fn test_parallel_groups_from_dependencies() {
+        use crate::brain::multi_intent::CoordinationType;
+
+        let planner = TaskPlanner::new();
+        let step = planner
+            .create_steps(&create_test_classification(IntentType::GetTime))
+            .remove(0);
+        let steps = vec![step; 4];
+
+        // 0 and 1 are independent; 2 needs both; 3 needs 2
+        let dependencies = [(0, 2), (1, 2), (2, 3)];
+        assert_eq!(
+            planner.compute_parallel_groups(&steps, &dependencies),
+            vec![vec![0, 1], vec![2], vec![3]]
+        );
+
+        // A chain has nothing to parallelise
+        assert!(planner
+            .compute_parallel_groups(&steps, &[(0, 1), (1, 2), (2, 3)])
+            .is_empty());
+
+        // An explicit group holds step 2 back to run alongside step 1
+        let mut explicit = steps.clone();
+        explicit[1].parallel_group = Some(5);
+        explicit[2].parallel_group = Some(5);
+        assert_eq!(
+            planner.compute_parallel_groups(&explicit, &[(0, 1)]),
+            vec![vec![0, 3], vec![1, 2]]
+        );
+
+        // A group that would run step 3 before step 2 is ignored
+        let mut explicit = steps.clone();
+        explicit[1].parallel_group = Some(0);
+        explicit[3].parallel_group = Some(0);
+        assert_eq!(
+            planner.compute_parallel_groups(&explicit, &dependencies),
+            vec![vec![0, 1], vec![2], vec![3]]
+        );
+
+        // Parallel segments share a layer; a delayed one waits for its timer
+        let items = vec![
+            (create_test_classification(IntentType::GetTime), None),
+            (
+                create_test_classification(IntentType::GetDate),
+                Some(std::time::Duration::from_secs(5)),
+            ),
+        ];
+        let plan = planner.plan_multi(items, CoordinationType::Parallel);
+        assert_eq!(plan.steps[1].action, ActionType::Wait);
+        assert_eq!(plan.parallel_groups, vec![vec![0, 1], vec![2]]);
+        assert_eq!(planner.get_execution_order(&plan), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_plan_json_round_trip_and_dot() {
+        use crate::brain::multi_intent::CoordinationType;
+
+        let planner = TaskPlanner::new();
+        let items = vec![
+            (create_test_classification(IntentType::GetTime), None),
+            (
+                create_test_classification(IntentType::GetDate),
+                Some(std::time::Duration::from_secs(5)),
+            ),
+        ];
+        let mut plan = planner.plan_multi(items, CoordinationType::Parallel);
+        plan.correlation_id = Some(Uuid::new_v4());
+
+        let json = plan.to_json().unwrap();
+        let restored = TaskPlan::from_json(&json).unwrap();
+        assert_eq!(restored.steps.len(), 3);
+        assert_eq!(restored.dependencies, plan.dependencies);
+        assert_eq!(restored.parallel_groups, plan.parallel_groups);
+        assert_eq!(restored.correlation_id, plan.correlation_id);
+        assert_eq!(restored.classification.intent, IntentType::GetTime);
+        for (restored, original) in restored.steps.iter().zip(&plan.steps) {
+            assert_eq!(restored.action, original.action);
+            assert_eq!(restored.params, original.params);
+            assert_eq!(restored.postconditions, original.postconditions);
+        }
+
+        let dot = plan.to_dot();
+        assert!(dot.starts_with("digraph plan {"));
+        assert!(dot.contains("s1 [label=\"1: Wait\\nduration=5\"];"));
+        assert!(dot.contains("s1 -> s2;"));
+        assert!(dot.contains("subgraph cluster_0"));
+        assert!(!dot.contains("s0 -> "));
+    }
+
+    #[test]
+    fn test_if_you_can_marks_steps_optional() {
+        use crate::brain::command_parser::CommandParser;
+
+        let parsed = CommandParser::new()
+            .parse("open spotify, if you can")
+            .unwrap();
+        assert_eq!(parsed.intent, IntentType::LaunchApp);
+        assert_eq!(
+            parsed.entities.get("app_name").map(String::as_str),
+            Some("spotify")
+        );
+
+        let plan = TaskPlanner::new().plan(ClassificationResult {
+            intent: parsed.intent,
+            confidence: parsed.confidence,
+            entities: parsed.entities,
+            alternatives: Vec::new(),
+            factors: Vec::new(),
+        });
+        assert!(!plan.steps.is_empty());
+        assert!(plan.steps.iter().all(|step| step.optional));
+
+        let parsed = CommandParser::new().parse("open spotify").unwrap();
+        assert!(!parsed.entities.contains_key("optional"));
+
+        // Plans saved before steps could be optional still load
+        let mut json: serde_json::Value = serde_json::from_str(&plan.to_json().unwrap()).unwrap();
+        for step in json["steps"].as_array_mut().unwrap() {
+            step.as_object_mut().unwrap().remove("optional");
+        }
+        let restored = TaskPlan::from_json(&json.to_string()).unwrap();
+        assert!(restored.steps.iter().all(|step| !step.optional));
+    }
 }
@@ -3,11 +3,15 @@
 //! Replaces stringly-typed HashMap<String, String> with proper types
 //! for better safety, IDE support, and refactorability.
 
-use chrono::Duration;
+use crate::brain::entity_extractor::EntityExtractor;
+use chrono::{DateTime, Duration, Local};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fmt;
 
+/// Legacy keys whose values are quantities and may be spelled out
+const QUANTITY_KEYS: &[&str] = &["level", "delta", "amount", "number", "percent", "desktop"];
+
 /// Strongly-typed entity extracted from commands
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Entity {
@@ -23,11 +27,11 @@ pub enum Entity {
     /// Duration (e.g., "30 minutes")
     Duration(Duration),
 
-    /// Numeric value
-    Number(i64),
+    /// Numeric value (digits or spelled out, e.g. "fifty")
+    Number(f64),
 
-    /// Percentage (0-100)
-    Percent(u8),
+    /// Percentage (0-100), e.g. "75%" or "a quarter"
+    Percentage(u8),
 
     /// Time of day (hour, minute)
     TimeOfDay { hour: u8, minute: u8 },
@@ -35,6 +39,9 @@ pub enum Entity {
     /// Date (year, month, day)
     Date { year: i32, month: u8, day: u8 },
 
+    /// Absolute local instant (e.g., "tomorrow at 9am")
+    DateTime(DateTime<Local>),
+
     /// Web search query
     Query(String),
 
@@ -75,6 +82,28 @@ impl Entity {
         }
     }
 
+    /// Get entity as a number if it is numeric
+    pub fn as_number(&self) -> Option<f64> {
+        match self {
+            Entity::Number(n) => Some(*n),
+            Entity::Percentage(p) => Some(f64::from(*p)),
+            _ => None,
+        }
+    }
+
+    /// Get entity as a 0-100 level, clamping out-of-range numbers
+    pub fn as_level(&self) -> Option<u8> {
+        self.as_number().map(|n| n.round().clamp(0.0, 100.0) as u8)
+    }
+
+    /// Get entity as an absolute instant if it is a datetime
+    pub fn as_datetime(&self) -> Option<DateTime<Local>> {
+        match self {
+            Entity::DateTime(dt) => Some(*dt),
+            _ => None,
+        }
+    }
+
     /// Get entity type name
     pub fn type_name(&self) -> &'static str {
         match self {
@@ -83,9 +112,10 @@ impl Entity {
             Entity::Folder(_) => "folder",
             Entity::Duration(_) => "duration",
             Entity::Number(_) => "number",
-            Entity::Percent(_) => "percent",
+            Entity::Percentage(_) => "percentage",
             Entity::TimeOfDay { .. } => "time_of_day",
             Entity::Date { .. } => "date",
+            Entity::DateTime(_) => "datetime",
             Entity::Query(_) => "query",
             Entity::Text(_) => "text",
             Entity::Action(_) => "action",
@@ -118,7 +148,7 @@ impl Entity {
             Entity::Number(n) => {
                 map.insert("number".to_string(), n.to_string());
             }
-            Entity::Percent(p) => {
+            Entity::Percentage(p) => {
                 map.insert("percent".to_string(), p.to_string());
             }
             Entity::TimeOfDay { hour, minute } => {
@@ -130,6 +160,9 @@ impl Entity {
                     format!("{:04}-{:02}-{:02}", year, month, day),
                 );
             }
+            Entity::DateTime(dt) => {
+                map.insert("when".to_string(), dt.to_rfc3339());
+            }
             Entity::Query(s) => {
                 map.insert("query".to_string(), s.clone());
             }
@@ -164,11 +197,12 @@ impl fmt::Display for Entity {
             Entity::Folder(s) => write!(f, "Folder({})", s),
             Entity::Duration(d) => write!(f, "Duration({} sec)", d.num_seconds()),
             Entity::Number(n) => write!(f, "Number({})", n),
-            Entity::Percent(p) => write!(f, "Percent({}%)", p),
+            Entity::Percentage(p) => write!(f, "Percentage({}%)", p),
             Entity::TimeOfDay { hour, minute } => write!(f, "Time({:02}:{:02})", hour, minute),
             Entity::Date { year, month, day } => {
                 write!(f, "Date({:04}-{:02}-{:02})", year, month, day)
             }
+            Entity::DateTime(dt) => write!(f, "DateTime({})", dt.format("%Y-%m-%d %H:%M")),
             Entity::Query(s) => write!(f, "Query({})", s),
             Entity::Text(s) => write!(f, "Text({})", s),
             Entity::Action(s) => write!(f, "Action({})", s),
@@ -250,16 +284,24 @@ impl Entities {
                 "action" => Entity::Action(value.clone()),
                 "url" => Entity::Url(value.clone()),
                 "contact" => Entity::Contact(value.clone()),
+                "when" => EntityExtractor::new()
+                    .extract_datetime(value)
+                    .map(Entity::DateTime)
+                    .unwrap_or_else(|| Entity::String(value.clone())),
                 _ => {
                     // Try to parse as number or percent
-                    if let Ok(n) = value.parse::<i64>() {
+                    if let Ok(n) = value.parse::<f64>() {
                         Entity::Number(n)
-                    } else if value.ends_with('%') {
-                        if let Ok(p) = value.trim_end_matches('%').parse::<u8>() {
-                            Entity::Percent(p)
-                        } else {
-                            Entity::String(value.clone())
-                        }
+                    } else if let Some(p) = value
+                        .strip_suffix('%')
+                        .and_then(|p| p.trim().parse::<u8>().ok())
+                    {
+                        Entity::Percentage(p.min(100))
+                    } else if QUANTITY_KEYS.contains(&key.as_str()) {
+                        // Spelled-out quantities ("fifty", "a quarter")
+                        EntityExtractor::new()
+                            .extract_quantity(value)
+                            .unwrap_or_else(|| Entity::String(value.clone()))
                     } else {
                         Entity::String(value.clone())
                     }
@@ -275,9 +317,13 @@ impl Entities {
 /// Confidence score with justification
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Confidence {
-    /// Raw confidence score (0.0 - 1.0)
+    /// Final confidence score (0.0 - 1.0), after calibration if any
     pub score: f32,
 
+    /// Score before calibration (equal to `score` when uncalibrated)
+    #[serde(default)]
+    pub raw_score: f32,
+
     /// Factors that contributed to the score
     pub factors: Vec<ConfidenceFactor>,
 }
@@ -285,8 +331,10 @@ pub struct Confidence {
 impl Confidence {
     /// Create a new confidence score
     pub fn new(score: f32) -> Self {
+        let score = score.clamp(0.0, 1.0);
         Self {
-            score: score.clamp(0.0, 1.0),
+            score,
+            raw_score: score,
             factors: Vec::new(),
         }
     }
@@ -333,6 +381,54 @@ impl ConfidenceFactor {
             description: description.into(),
         }
     }
+
+    /// Human-readable label for this factor
+    pub fn label(&self) -> String {
+        match self.name.as_str() {
+            "pattern_match" => "Pattern match".to_string(),
+            "entity_validation" => "Entity validation".to_string(),
+            "context_match" => "Similar past commands".to_string(),
+            "recent_success" => "Past success rate".to_string(),
+            other => other.replace('_', " "),
+        }
+    }
+}
+
+/// Why a command received its confidence score
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfidenceExplanation {
+    /// Text that was scored (after context resolution)
+    pub text: String,
+
+    /// Intent name the text classified as
+    pub intent: String,
+
+    /// Final (calibrated) confidence
+    pub score: f32,
+
+    /// Confidence before calibration
+    pub raw_score: f32,
+
+    /// Contributing factors as `(label, factor)` pairs
+    pub factors: Vec<(String, ConfidenceFactor)>,
+}
+
+impl fmt::Display for ConfidenceExplanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "\"{}\" → {} ({:.2}, raw {:.2})",
+            self.text, self.intent, self.score, self.raw_score
+        )?;
+        for (label, factor) in &self.factors {
+            writeln!(
+                f,
+                "  {}: +{:.3} ({})",
+                label, factor.weight, factor.description
+            )?;
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -351,7 +447,7 @@ mod tests {
         let app = Entity::App("firefox".to_string());
         assert_eq!(format!("{}", app), "App(firefox)");
 
-        let num = Entity::Number(42);
+        let num = Entity::Number(42.0);
         assert_eq!(format!("{}", num), "Number(42)");
 
         let time = Entity::TimeOfDay {
@@ -365,7 +461,7 @@ mod tests {
     fn test_entities_collection() {
         let mut entities = Entities::new();
         entities.insert("app".to_string(), Entity::App("chrome".to_string()));
-        entities.insert("number".to_string(), Entity::Number(42));
+        entities.insert("number".to_string(), Entity::Number(42.0));
 
         assert_eq!(entities.len(), 2);
         assert!(entities.contains_key("app"));
@@ -374,6 +470,31 @@ mod tests {
         assert_eq!(app.as_string(), Some("chrome"));
     }
 
+    #[test]
+    fn test_legacy_quantities() {
+        let mut legacy = HashMap::new();
+        legacy.insert("level".to_string(), "fifty".to_string());
+        legacy.insert("delta".to_string(), "-10".to_string());
+        legacy.insert("percent".to_string(), "75%".to_string());
+
+        let entities = Entities::from_legacy_hashmap(&legacy);
+        assert_eq!(entities.get("level"), Some(&Entity::Number(50.0)));
+        assert_eq!(entities.get("delta").and_then(Entity::as_number), Some(-10.0));
+        assert_eq!(entities.get("percent"), Some(&Entity::Percentage(75)));
+        assert_eq!(Entity::Number(150.0).as_level(), Some(100));
+    }
+
+    #[test]
+    fn test_legacy_datetime() {
+        let mut legacy = HashMap::new();
+        legacy.insert("when".to_string(), "tomorrow at 9am".to_string());
+
+        let entities = Entities::from_legacy_hashmap(&legacy);
+        let when = entities.get("when").and_then(Entity::as_datetime).unwrap();
+        assert!(when > Local::now());
+        assert_eq!(entities.get("when").unwrap().type_name(), "datetime");
+    }
+
     #[test]
     fn test_legacy_conversion() {
         let mut legacy = HashMap::new();
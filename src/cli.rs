@@ -3,8 +3,15 @@
 //! Provides diagnostic and maintenance tools:
 //! - `doctor`: System diagnostics
 //! - `index`: Rebuild application/file indices
-//! - `events`: Live event stream monitoring
+//! - `events`: Live event stream monitoring and recording
+//! - `replay`: Replay a recorded event stream
 //! - `metrics`: Display metrics snapshot
+//! - `resources`: Display system resource usage
+//! - `say`: Speak text through the TTS system
+//! - `ask`: Answer a question with the knowledge module
+//! - `benchmark`: Measure brain pipeline latency and cache behavior
+//! - `explain`: Show what a command would do without running it
+//! - `run-script`: Run text commands from a file, one per line
 
 use crate::error::Result;
 use crate::{ConfigManager, LunaConfig};
@@ -53,6 +60,17 @@ pub enum AudioCommands {
         output: PathBuf,
     },
 
+    /// Capture until Ctrl+C, then save the last few seconds heard to WAV
+    Dump {
+        /// Milliseconds of audio to save (limited by the ring buffer)
+        #[arg(short, long, default_value = "1000")]
+        duration_ms: u64,
+
+        /// Output file path
+        #[arg(short, long, default_value = "dump.wav")]
+        output: PathBuf,
+    },
+
     /// Test wake word detection
     TestWake {
         /// Test duration in seconds
@@ -89,6 +107,12 @@ pub enum BrainCommands {
         preview: bool,
     },
 
+    /// Explain the confidence score for text
+    Explain {
+        /// Text to explain
+        text: String,
+    },
+
     /// Reload grammar from YAML file
     GrammarReload,
 }
@@ -115,11 +139,19 @@ pub enum Commands {
         /// Rebuild all indices
         #[arg(long)]
         all: bool,
+
+        /// Keep the file index updated as files change
+        #[arg(short, long)]
+        watch: bool,
+
+        /// Discard the existing file index instead of updating it
+        #[arg(long)]
+        full: bool,
     },
 
     /// Monitor live event stream
     Events {
-        /// Filter by event type
+        /// Filter by event type (comma-separated, `*` wildcards, e.g. `tts_*,action_started`)
         #[arg(short, long)]
         filter: Option<String>,
 
@@ -130,6 +162,24 @@ pub enum Commands {
         /// Maximum events to display
         #[arg(short, long, default_value = "100")]
         limit: usize,
+
+        /// Record events to a JSON-lines file
+        #[arg(short, long)]
+        record: Option<PathBuf>,
+    },
+
+    /// Replay a recorded JSON-lines event file
+    Replay {
+        /// Recording produced by `events --record`
+        file: PathBuf,
+
+        /// Playback speed multiplier (0 replays without delays)
+        #[arg(short, long, default_value = "1.0")]
+        speed: f64,
+
+        /// Filter by event type (comma-separated, `*` wildcards, e.g. `tts_*,action_started`)
+        #[arg(short, long)]
+        filter: Option<String>,
     },
 
     /// Display metrics snapshot
@@ -143,6 +193,85 @@ pub enum Commands {
         output: Option<PathBuf>,
     },
 
+    /// Display CPU, memory, and top processes
+    Resources {
+        /// Refresh until Ctrl+C
+        #[arg(short, long)]
+        watch: bool,
+
+        /// Seconds between refreshes in watch mode
+        #[arg(short, long, default_value = "3")]
+        interval: u64,
+    },
+
+    /// Speak text through the TTS system
+    Say {
+        /// Text to speak
+        #[arg(required_unless_present = "list_voices")]
+        text: Option<String>,
+
+        /// Message kind (critical, error, confirmation, prompt, reading, info, background)
+        #[arg(short, long, default_value = "info")]
+        kind: String,
+
+        /// Voice name or ID to use
+        #[arg(short, long)]
+        voice: Option<String>,
+
+        /// Treat text as SSML
+        #[arg(long)]
+        ssml: bool,
+
+        /// List available voices and exit
+        #[arg(long)]
+        list_voices: bool,
+    },
+
+    /// Answer a question with the knowledge module
+    Ask {
+        /// Question to answer
+        question: String,
+
+        /// Allow online web search as a fallback
+        #[arg(long)]
+        online: bool,
+    },
+
+    /// Show what a command would do, without running it
+    Explain {
+        /// Command text, e.g. "open chrome and set volume to 50"
+        text: String,
+
+        /// Also print the plan as a Graphviz DOT graph
+        #[arg(long)]
+        dot: bool,
+
+        /// Also print the plan as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Benchmark the brain pipeline
+    Benchmark {
+        /// Passes over the command set
+        #[arg(short, long, default_value = "10")]
+        iterations: usize,
+
+        /// File with one command per line (default: built-in corpus)
+        #[arg(short, long)]
+        file: Option<PathBuf>,
+    },
+
+    /// Run commands from a file, one per line
+    RunScript {
+        /// Script file; clarification answers go on the line after the command
+        file: PathBuf,
+
+        /// Preview each plan instead of executing it
+        #[arg(long)]
+        dry_run: bool,
+    },
+
     /// Audio system tools
     Audio {
         #[command(subcommand)]
@@ -258,14 +387,14 @@ pub async fn run_doctor(extended: bool) -> Result<()> {
 }
 
 /// Run the index rebuild command
-pub async fn run_index(apps: bool, files: bool, all: bool) -> Result<()> {
+pub async fn run_index(apps: bool, files: bool, all: bool, watch: bool, full: bool) -> Result<()> {
     use crate::db::schema::{FileEntry, FileType};
     use crate::db::{AppDatabase, FileIndex};
     use crate::os::discovery::discover_applications;
     use std::time::Instant;
 
-    if !apps && !files && !all {
-        println!("Please specify --apps, --files, or --all");
+    if !apps && !files && !all && !watch {
+        println!("Please specify --apps, --files, --all, or --watch");
         return Ok(());
     }
 
@@ -290,8 +419,13 @@ pub async fn run_index(apps: bool, files: bool, all: bool) -> Result<()> {
             db.add_app(app);
         }
 
-        // Save to disk
+        // Keep launch history from the previous database
         let db_path = data_dir.join("app_database.json");
+        if let Ok(previous) = AppDatabase::load_from_disk(&db_path).await {
+            db.merge_usage(&previous);
+        }
+
+        // Save to disk
         db.save_to_disk(&db_path).await?;
 
         let elapsed = start.elapsed();
@@ -303,12 +437,23 @@ pub async fn run_index(apps: bool, files: bool, all: bool) -> Result<()> {
         println!("  Saved to: {:?}", db_path);
     }
 
-    if files || all {
+    let mut watched_index = None;
+    if files || all || watch {
         println!("\n🔄 Rebuilding file index...");
         let start = Instant::now();
 
-        let mut index = FileIndex::new();
-        let mut total_files = 0;
+        // Start from the saved index so unchanged files can be skipped
+        let index_path = data_dir.join("file_index.json");
+        let mut index = if !full && index_path.exists() {
+            FileIndex::load_from_disk(&index_path)
+                .await
+                .unwrap_or_else(|e| {
+                    println!("  ⚠️  Starting from an empty index: {}", e);
+                    FileIndex::new()
+                })
+        } else {
+            FileIndex::new()
+        };
 
         // Index configured search paths
         let search_paths = &config.paths.search_paths;
@@ -316,130 +461,194 @@ pub async fn run_index(apps: bool, files: bool, all: bool) -> Result<()> {
 
         println!("  Indexing {} search paths...", search_paths.len());
 
+        let mut roots = Vec::new();
         for search_path in search_paths {
             let path = std::path::PathBuf::from(search_path);
             if !path.exists() {
                 println!("  ⚠️  Skipping non-existent path: {:?}", path);
                 continue;
             }
-
             println!("  Scanning: {:?}", path);
-            match index_directory(&path, exclude_paths, &mut index).await {
-                Ok(count) => {
-                    total_files += count;
-                    println!("    Indexed {} files", count);
-                }
-                Err(e) => {
-                    println!("    ⚠️  Error: {}", e);
-                }
-            }
+            roots.push(path);
         }
 
+        let stats = index.rebuild_incremental(&roots, exclude_paths)?;
+        println!(
+            "    Added {}, updated {}, removed {}, unchanged {}",
+            stats.added, stats.updated, stats.removed, stats.unchanged
+        );
+
         // Save to disk
-        let index_path = data_dir.join("file_index.json");
-        index.save_to_disk(&index_path).await?;
+        if stats.changed() > 0 || !index_path.exists() {
+            index.save_to_disk(&index_path).await?;
+        }
 
         let elapsed = start.elapsed();
         println!(
             "  ✅ File index rebuilt: {} files ({:.2}s)",
-            total_files,
+            index.len(),
             elapsed.as_secs_f32()
         );
         println!("  Saved to: {:?}", index_path);
+
+        if watch {
+            watched_index = Some((index, index_path));
+        }
     }
 
     println!("\n━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
     println!("✅ Index rebuild complete\n");
+
+    if let Some((index, index_path)) = watched_index {
+        watch_file_index(
+            index,
+            &config.paths.search_paths,
+            &config.paths.exclude_paths,
+            &index_path,
+            config.performance.index_update_interval_secs,
+        )
+        .await?;
+    }
     Ok(())
 }
 
-/// Index a directory recursively
-fn index_directory<'a>(
-    path: &'a std::path::Path,
-    exclude_paths: &'a [String],
-    index: &'a mut crate::db::FileIndex,
-) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<usize>> + 'a>> {
-    Box::pin(async move {
-        use crate::db::schema::{FileEntry, FileType};
-        use tokio::fs;
-
-        let mut count = 0;
-
-        // Check if path should be excluded
-        let path_str = path.to_string_lossy();
-        for exclude in exclude_paths {
-            if path_str.contains(exclude) {
-                return Ok(0);
-            }
-        }
-
-        // Read directory
-        let mut entries = match fs::read_dir(path).await {
-            Ok(entries) => entries,
-            Err(_) => return Ok(0), // Skip inaccessible directories
-        };
+/// Keep the file index in sync with the search paths until Ctrl+C
+///
+/// Changes are applied as they happen and persisted every `save_interval_secs`
+/// if anything changed, plus once more on exit.
+async fn watch_file_index(
+    index: crate::db::FileIndex,
+    search_paths: &[String],
+    exclude_paths: &[String],
+    index_path: &std::path::Path,
+    save_interval_secs: u64,
+) -> Result<()> {
+    use crate::db::file_index::FileIndexWatcher;
+    use std::sync::Arc;
 
-        while let Some(entry) = entries.next_entry().await? {
-            let entry_path = entry.path();
+    let paths: Vec<PathBuf> = search_paths
+        .iter()
+        .map(PathBuf::from)
+        .filter(|p| p.exists())
+        .collect();
+    let index = Arc::new(parking_lot::RwLock::new(index));
+    let watcher = FileIndexWatcher::start(Arc::clone(&index), &paths, exclude_paths.to_vec())?;
 
-            // Check exclude patterns
-            let entry_str = entry_path.to_string_lossy();
-            let excluded = exclude_paths.iter().any(|ex| entry_str.contains(ex));
-            if excluded {
-                continue;
-            }
+    println!(
+        "👀 Watching {} paths, saving changes every {}s. Press Ctrl+C to stop...\n",
+        paths.len(),
+        save_interval_secs
+    );
 
-            if entry_path.is_dir() {
-                // Recurse into subdirectory (limit depth to avoid infinite loops)
-                count += index_directory(&entry_path, exclude_paths, index).await?;
-            } else if entry_path.is_file() {
-                // Add file to index
-                if let Some(file_entry) = create_file_entry(&entry_path).await {
-                    index.add_file(file_entry);
-                    count += 1;
+    let mut ticker =
+        tokio::time::interval(std::time::Duration::from_secs(save_interval_secs.max(1)));
+    ticker.tick().await;
+    loop {
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => break,
+            _ = ticker.tick() => {
+                if watcher.take_dirty() {
+                    save_file_index(&index, index_path).await?;
                 }
             }
         }
+    }
 
-        Ok(count)
-    })
+    if watcher.take_dirty() {
+        save_file_index(&index, index_path).await?;
+    }
+    Ok(())
 }
 
-/// Create a FileEntry from a path
-async fn create_file_entry(path: &std::path::Path) -> Option<crate::db::schema::FileEntry> {
-    use crate::db::schema::{FileEntry, FileType};
+/// Persist a snapshot of a shared file index
+async fn save_file_index(
+    index: &parking_lot::RwLock<crate::db::FileIndex>,
+    path: &std::path::Path,
+) -> Result<()> {
+    let snapshot = index.read().clone();
+    snapshot.save_to_disk(path).await?;
+    println!("  💾 Saved file index: {} files", snapshot.len());
+    Ok(())
+}
 
-    let metadata = tokio::fs::metadata(path).await.ok()?;
-    let name = path.file_name()?.to_string_lossy().to_string();
-    let extension = path.extension().and_then(|e| e.to_str()).map(String::from);
-
-    // Determine file type from extension
-    let file_type = match extension.as_deref() {
-        Some("txt") | Some("md") | Some("doc") | Some("docx") | Some("pdf") => FileType::Document,
-        Some("jpg") | Some("jpeg") | Some("png") | Some("gif") | Some("bmp") => FileType::Image,
-        Some("mp4") | Some("avi") | Some("mkv") | Some("mov") => FileType::Video,
-        Some("mp3") | Some("wav") | Some("flac") | Some("ogg") => FileType::Audio,
-        _ => FileType::Other,
-    };
+/// Event type matcher parsed from a `--filter` argument
+///
+/// The argument is a comma-separated list of patterns where `*` matches any
+/// run of characters. An event passes if it matches any pattern; an empty
+/// filter passes everything.
+#[derive(Debug, Clone, Default)]
+struct EventTypeFilter {
+    patterns: Vec<String>,
+}
 
-    Some(FileEntry {
-        path: path.to_path_buf(),
-        name,
-        extension,
-        size: metadata.len(),
-        modified: metadata
-            .modified()
-            .ok()?
-            .duration_since(std::time::UNIX_EPOCH)
-            .ok()?
-            .as_secs() as i64,
-        file_type,
-    })
+impl EventTypeFilter {
+    fn parse(spec: &str) -> Self {
+        Self {
+            patterns: spec
+                .split(',')
+                .map(|p| p.trim().to_lowercase())
+                .filter(|p| !p.is_empty())
+                .collect(),
+        }
+    }
+
+    fn from_option(spec: Option<&str>) -> Self {
+        spec.map(Self::parse).unwrap_or_default()
+    }
+
+    fn matches(&self, event_type: &str) -> bool {
+        self.patterns.is_empty() || self.patterns.iter().any(|p| glob_match(p, event_type))
+    }
+
+    /// Patterns that can't match any known event type, likely typos
+    fn unmatched(&self) -> Vec<&str> {
+        use crate::events::EventKind;
+
+        self.patterns
+            .iter()
+            .filter(|p| !EventKind::ALL.iter().any(|k| glob_match(p, k.as_str())))
+            .map(String::as_str)
+            .collect()
+    }
+}
+
+/// Match text against a pattern where `*` matches any run of characters
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    // Position of the last `*` and the text index it was tried against
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, t));
+            p += 1;
+        } else if p < pattern.len() && pattern[p] == text[t] {
+            p += 1;
+            t += 1;
+        } else if let Some((star, matched)) = backtrack {
+            // Let the last `*` swallow one more character
+            p = star + 1;
+            t = matched + 1;
+            backtrack = Some((star, t));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
 }
 
 /// Run the events monitor command
-pub async fn run_events(filter: Option<String>, tail: bool, limit: usize) -> Result<()> {
+pub async fn run_events(
+    filter: Option<String>,
+    tail: bool,
+    limit: usize,
+    record: Option<PathBuf>,
+) -> Result<()> {
     use crate::events::EventBus;
+    use crate::subscribers::FileEventSubscriber;
     use std::sync::Arc;
     use tokio::sync::Mutex;
 
@@ -450,6 +659,10 @@ pub async fn run_events(filter: Option<String>, tail: bool, limit: usize) -> Res
     } else {
         println!("Filter: <all events>");
     }
+    let filter = EventTypeFilter::from_option(filter.as_deref());
+    for pattern in filter.unmatched() {
+        println!("⚠️  Filter '{}' matches no known event type", pattern);
+    }
 
     println!("Tail mode: {}", if tail { "ON" } else { "OFF" });
     println!("Limit: {} events\n", limit);
@@ -459,19 +672,21 @@ pub async fn run_events(filter: Option<String>, tail: bool, limit: usize) -> Res
     let bus = Arc::new(EventBus::new());
     let handle = bus.start_processing().await;
 
+    if let Some(ref path) = record {
+        FileEventSubscriber::create(path)?.attach(&bus, &[]).await;
+        println!("Recording to: {:?}\n", path);
+    }
+
     let event_count = Arc::new(Mutex::new(0));
     let count_clone = Arc::clone(&event_count);
-    let filter_clone = filter.clone();
 
     // Subscribe to events
     bus.subscribe(vec![], move |envelope| {
         let event_type = envelope.event_type();
 
         // Apply filter if specified
-        if let Some(ref filter_str) = filter_clone {
-            if !event_type.contains(filter_str.as_str()) {
-                return;
-            }
+        if !filter.matches(event_type) {
+            return;
         }
 
         // Check limit
@@ -523,10 +738,69 @@ pub async fn run_events(filter: Option<String>, tail: bool, limit: usize) -> Res
     Ok(())
 }
 
+/// Run the event replay command
+pub async fn run_replay(file: PathBuf, speed: f64, filter: Option<String>) -> Result<()> {
+    use crate::events::EventBus;
+    use crate::subscribers::file::{read_recording, replay};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    println!("\n⏯️  LUNA Event Replay\n");
+
+    let envelopes = read_recording(&file)?;
+    println!("Recording: {:?} ({} events)", file, envelopes.len());
+    println!("Speed: {}x\n", speed);
+    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n");
+
+    let bus = EventBus::new();
+    let handle = bus.start_processing().await;
+
+    let filter = EventTypeFilter::from_option(filter.as_deref());
+    let shown = Arc::new(AtomicUsize::new(0));
+    let shown_clone = Arc::clone(&shown);
+    bus.subscribe(vec![], move |envelope| {
+        let event_type = envelope.event_type();
+        if !filter.matches(event_type) {
+            return;
+        }
+        shown_clone.fetch_add(1, Ordering::Relaxed);
+
+        let timestamp = chrono::DateTime::from_timestamp(
+            (envelope.timestamp / 1_000_000) as i64,
+            ((envelope.timestamp % 1_000_000) * 1000) as u32,
+        )
+        .unwrap_or_default();
+
+        println!(
+            "[{}] {} - {:?}",
+            timestamp.format("%H:%M:%S%.3f"),
+            event_type,
+            envelope.event
+        );
+    })
+    .await;
+
+    let published = replay(&bus, envelopes, speed).await;
+
+    // Deliver everything replayed before reporting
+    bus.shutdown().await;
+    let _ = handle.await;
+
+    println!("\n━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    println!(
+        "Replayed {} events ({} displayed)",
+        published,
+        shown.load(Ordering::Relaxed)
+    );
+    println!("✅ Replay complete\n");
+
+    Ok(())
+}
+
 /// Run the metrics command
 pub async fn run_metrics(detailed: bool, output: Option<PathBuf>) -> Result<()> {
     use crate::error::LunaError;
-    use crate::metrics::Metrics;
+    use crate::metrics::{percentile_name, MetricPhase, Metrics, REPORTED_PERCENTILES};
     use tokio::fs;
 
     let metrics = Metrics::new();
@@ -547,22 +821,7 @@ pub async fn run_metrics(detailed: bool, output: Option<PathBuf>) -> Result<()>
         let extension = output_path.extension().and_then(|s| s.to_str());
 
         let content = match extension {
-            Some("json") => {
-                // Export as JSON
-                let metrics_json = serde_json::json!({
-                    "commands_processed": metrics.get_commands_processed(),
-                    "commands_succeeded": metrics.get_commands_succeeded(),
-                    "commands_failed": metrics.get_commands_failed(),
-                    "success_rate": metrics.get_success_rate(),
-                    "wake_words_detected": metrics.get_wake_words_detected(),
-                    "avg_audio_capture_ms": metrics.get_avg_audio_capture_ms(),
-                    "avg_stt_ms": metrics.get_avg_stt_ms(),
-                    "avg_parsing_ms": metrics.get_avg_parsing_ms(),
-                    "avg_execution_ms": metrics.get_avg_execution_ms(),
-                    "timestamp": chrono::Utc::now().to_rfc3339(),
-                });
-                serde_json::to_string_pretty(&metrics_json)?
-            }
+            Some("json") => serde_json::to_string_pretty(&metrics.to_json())?,
             Some("csv") => {
                 // Export as CSV
                 let mut csv = String::from("metric,value\n");
@@ -596,6 +855,16 @@ pub async fn run_metrics(detailed: bool, output: Option<PathBuf>) -> Result<()>
                     "avg_execution_ms,{:.2}\n",
                     metrics.get_avg_execution_ms()
                 ));
+                for phase in MetricPhase::ALL {
+                    for q in REPORTED_PERCENTILES {
+                        csv.push_str(&format!(
+                            "{}_{}_ms,{:.2}\n",
+                            phase.label(),
+                            percentile_name(q),
+                            metrics.get_percentile(phase, q)
+                        ));
+                    }
+                }
                 csv
             }
             _ => {
@@ -614,6 +883,557 @@ pub async fn run_metrics(detailed: bool, output: Option<PathBuf>) -> Result<()>
     Ok(())
 }
 
+/// Render a progress bar for a percentage
+fn usage_bar(percent: f32, width: usize) -> String {
+    let filled = ((percent.clamp(0.0, 100.0) / 100.0) * width as f32).round() as usize;
+    format!("{}{}", "█".repeat(filled), "░".repeat(width - filled))
+}
+
+/// Format a resource snapshot for the terminal
+fn format_resources(
+    metrics: &crate::os::resource_monitor::SystemMetrics,
+    processes: &[crate::os::resource_monitor::ProcessUsage],
+) -> String {
+    let mut out = String::from("\n🖥️  LUNA System Resources\n\n");
+
+    out.push_str(&format!(
+        "CPU    {} {:5.1}%\n",
+        usage_bar(metrics.cpu_total, 30),
+        metrics.cpu_total
+    ));
+    for (core, usage) in metrics.cpu_per_core.iter().enumerate() {
+        out.push_str(&format!(
+            "  #{:<3} {} {:5.1}%\n",
+            core,
+            usage_bar(*usage, 20),
+            usage
+        ));
+    }
+
+    let memory_percent = if metrics.memory_total_mb > 0 {
+        metrics.memory_used_mb as f32 / metrics.memory_total_mb as f32 * 100.0
+    } else {
+        0.0
+    };
+    out.push_str(&format!(
+        "\nMemory {} {:5.1}%  ({} / {} MB, {} MB available)\n",
+        usage_bar(memory_percent, 30),
+        memory_percent,
+        metrics.memory_used_mb,
+        metrics.memory_total_mb,
+        metrics.memory_available_mb
+    ));
+
+    if let Some(gpu) = &metrics.gpu_usage {
+        out.push_str(&format!(
+            "GPU    {} {:5.1}%  ({} / {} MB{})\n",
+            usage_bar(gpu.usage_percent, 30),
+            gpu.usage_percent,
+            gpu.memory_used_mb,
+            gpu.memory_total_mb,
+            gpu.temperature_c
+                .map(|t| format!(", {:.0}°C", t))
+                .unwrap_or_default()
+        ));
+    }
+
+    out.push_str(&format!(
+        "\nDisk   read {:>8.1} KB/s  write {:>8.1} KB/s\n",
+        metrics.disk_io.read_bytes_per_sec as f64 / 1024.0,
+        metrics.disk_io.write_bytes_per_sec as f64 / 1024.0
+    ));
+    out.push_str(&format!(
+        "Net    recv {:>8.1} KB/s  sent  {:>8.1} KB/s\n",
+        metrics.network_io.received_bytes_per_sec as f64 / 1024.0,
+        metrics.network_io.transmitted_bytes_per_sec as f64 / 1024.0
+    ));
+
+    if !processes.is_empty() {
+        out.push_str(&format!(
+            "\n{:>8}  {:<24} {:>7} {:>10}\n",
+            "PID", "PROCESS", "CPU%", "MEMORY"
+        ));
+        for process in processes {
+            let name: String = process.name.chars().take(24).collect();
+            out.push_str(&format!(
+                "{:>8}  {:<24} {:>7.1} {:>7} MB\n",
+                process.pid, name, process.cpu_percent, process.memory_mb
+            ));
+        }
+    }
+
+    out
+}
+
+/// Run the resources command
+pub async fn run_resources(watch: bool, interval: u64) -> Result<()> {
+    use crate::os::resource_monitor::ResourceMonitor;
+    use std::time::Duration;
+
+    let monitor = ResourceMonitor::new();
+
+    // CPU usage needs two samples some time apart
+    tokio::time::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL).await;
+    monitor.refresh().await;
+
+    let interval = Duration::from_secs(interval.max(1));
+    loop {
+        let metrics = monitor.get_current_metrics().await;
+        let processes = monitor.top_processes(10).await;
+
+        if watch {
+            // Clear screen and move cursor home
+            print!("\x1b[2J\x1b[H");
+        }
+        print!("{}", format_resources(&metrics, &processes));
+
+        if !watch {
+            return Ok(());
+        }
+        println!(
+            "\nRefreshing every {}s, press Ctrl+C to exit",
+            interval.as_secs()
+        );
+
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => return Ok(()),
+            _ = tokio::time::sleep(interval) => {
+                monitor.refresh().await;
+            }
+        }
+    }
+}
+
+/// Run the say command
+pub async fn run_say(
+    text: Option<String>,
+    kind: String,
+    voice: Option<String>,
+    ssml: bool,
+    list_voices: bool,
+) -> Result<()> {
+    use crate::tts::{MessageKind, TtsMessage, TtsSystem};
+
+    let config_mgr = ConfigManager::new(None).await?;
+    let mut tts_config = config_mgr.get().await.tts.clone();
+    let kind: MessageKind = kind.parse()?;
+
+    if let Some(voice) = voice {
+        tts_config.policy.profile_for_mut(kind).voice = Some(voice);
+    }
+
+    let mut tts = TtsSystem::with_config(tts_config)?;
+
+    if list_voices {
+        let voices = tts.voice_info().await?;
+        println!("\n🔊 Available voices ({})\n", voices.len());
+        for voice in &voices {
+            match &voice.locale {
+                Some(locale) => println!("  {} [{}] ({})", voice.name, locale, voice.id),
+                None => println!("  {} ({})", voice.name, voice.id),
+            }
+        }
+        println!();
+        return Ok(());
+    }
+
+    let Some(text) = text else {
+        return Ok(());
+    };
+
+    tts.start().await?;
+    tts.enqueue(TtsMessage::new(text, kind).with_ssml(ssml))
+        .await;
+    tts.wait_until_idle().await;
+    tts.stop().await;
+
+    let stats = tts.stats().await;
+    if stats.total_errors > 0 {
+        return Err(crate::error::LunaError::tts_error(
+            "Failed to speak text, see logs for details",
+        ));
+    }
+
+    Ok(())
+}
+
+/// Run the ask command
+pub async fn run_ask(question: String, online: bool) -> Result<()> {
+    use crate::knowledge::QuestionAnswerer;
+
+    println!("\n🤔 Answering Question\n");
+    println!("Question: \"{}\"", question);
+    if !online {
+        println!("Mode: offline (use --online to allow web search)");
+    }
+    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n");
+
+    let qa = QuestionAnswerer::new().with_web_search(online);
+    let answer = qa.answer(&question).await?;
+    println!("{}", format_answer(&answer));
+
+    Ok(())
+}
+
+/// Format an answer with its source and confidence
+fn format_answer(answer: &crate::knowledge::Answer) -> String {
+    let mut out = format!("{}\n\n", answer.text);
+    out.push_str(&format!("Source: {}\n", answer.source));
+    out.push_str(&format!("Confidence: {:.2}\n", answer.confidence));
+    if let Some(context) = &answer.context {
+        out.push_str(&format!("Context: {}\n", context));
+    }
+    if let Some(url) = &answer.source_url {
+        out.push_str(&format!("URL: {}\n", url));
+    }
+    out
+}
+
+/// Run the explain command
+pub async fn run_explain(text: String, dot: bool, json: bool) -> Result<()> {
+    use crate::actions::{AppLauncher, FileSearch, TaskExecutor};
+    use crate::brain::Brain;
+    use crate::db::{AppDatabase, FileIndex};
+
+    println!("\n🔎 Explaining Command (dry run)\n");
+    println!("Input: \"{}\"", text);
+    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n");
+
+    let config_mgr = ConfigManager::new(None).await?;
+    let config = config_mgr.get().await;
+    let brain = Brain::new(&config.brain)?;
+
+    // Nothing runs, so empty indices are enough to build the executor
+    let app_launcher = AppLauncher::new(std::sync::Arc::new(parking_lot::RwLock::new(
+        AppDatabase::new(),
+    )));
+    let file_search = FileSearch::new(std::sync::Arc::new(FileIndex::new()));
+    let executor = TaskExecutor::new(app_launcher, file_search);
+
+    let preview = executor.preview_command(&brain, &text).await?;
+    print!("{}", format_preview(&preview));
+
+    if dot {
+        println!("\n{}", preview.plan.to_dot());
+    }
+    if json {
+        println!("\n{}", preview.plan.to_json()?);
+    }
+
+    Ok(())
+}
+
+/// Format a command preview: classification, then one line per step
+fn format_preview(preview: &crate::actions::CommandPreview) -> String {
+    let classification = &preview.plan.classification;
+    let mut out = format!("Intent: {:?}\n", classification.intent);
+    out.push_str(&format!("Confidence: {:.2}\n", classification.confidence));
+
+    if preview.outcome.steps.is_empty() {
+        out.push_str("\nNo actions would run\n");
+        return out;
+    }
+
+    out.push_str("\nWould run:\n");
+    for step in &preview.outcome.steps {
+        out.push_str(&format!("  {}. {}\n", step.step_index + 1, step.message));
+    }
+    out
+}
+
+/// Commands used by `benchmark` when no file is given
+const BENCHMARK_CORPUS: &[&str] = &[
+    "open firefox",
+    "launch visual studio code",
+    "close spotify",
+    "find my budget spreadsheet",
+    "search for files named report",
+    "set volume to 50 percent",
+    "mute the sound",
+    "what time is it",
+    "what is the weather in paris",
+    "take a screenshot",
+    "lock the screen",
+    "remind me to stretch in 10 minutes",
+    "play some music",
+    "open terminal and then open firefox",
+    "switch to workspace 2",
+];
+
+/// Accumulated latency for one pipeline stage
+#[derive(Debug, Clone, Copy, Default)]
+struct StageTiming {
+    total: std::time::Duration,
+    samples: u32,
+}
+
+impl StageTiming {
+    fn record(&mut self, elapsed: std::time::Duration) {
+        self.total += elapsed;
+        self.samples += 1;
+    }
+
+    fn mean(&self) -> std::time::Duration {
+        if self.samples == 0 {
+            return std::time::Duration::ZERO;
+        }
+        self.total / self.samples
+    }
+
+    /// Commands processed per second
+    fn throughput(&self) -> f64 {
+        let secs = self.total.as_secs_f64();
+        if secs == 0.0 {
+            return 0.0;
+        }
+        self.samples as f64 / secs
+    }
+}
+
+/// Results of a brain benchmark run
+#[derive(Debug, Clone, Default)]
+struct BenchmarkReport {
+    commands: usize,
+    iterations: usize,
+    errors: usize,
+    parse: StageTiming,
+    classify: StageTiming,
+    plan: StageTiming,
+    /// End-to-end with an empty cache
+    cold: StageTiming,
+    /// End-to-end after the cache was warmed
+    warm: StageTiming,
+    /// Cache lookups during the warm passes only
+    warm_cache: crate::brain::cache::CacheStats,
+}
+
+/// Load benchmark commands from a file, skipping blanks and `#` comments
+fn load_benchmark_commands(file: Option<&std::path::Path>) -> Result<Vec<String>> {
+    let Some(path) = file else {
+        return Ok(BENCHMARK_CORPUS.iter().map(|c| c.to_string()).collect());
+    };
+
+    let content = std::fs::read_to_string(path)?;
+    let commands: Vec<String> = content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_string)
+        .collect();
+
+    if commands.is_empty() {
+        return Err(crate::error::LunaError::InvalidParameter(format!(
+            "No commands found in {}",
+            path.display()
+        )));
+    }
+    Ok(commands)
+}
+
+/// Time each pipeline stage, then cold and cached end-to-end processing
+async fn benchmark_brain(
+    brain: &crate::brain::Brain,
+    commands: &[String],
+    iterations: usize,
+) -> BenchmarkReport {
+    use std::time::Instant;
+
+    let mut report = BenchmarkReport {
+        commands: commands.len(),
+        iterations,
+        ..Default::default()
+    };
+
+    // Individual stages bypass the cache
+    for _ in 0..iterations {
+        for command in commands {
+            let start = Instant::now();
+            let Ok(parsed) = brain.parse(command) else {
+                report.errors += 1;
+                continue;
+            };
+            report.parse.record(start.elapsed());
+
+            let start = Instant::now();
+            let Ok(classification) = brain.classify(&parsed) else {
+                report.errors += 1;
+                continue;
+            };
+            report.classify.record(start.elapsed());
+
+            let start = Instant::now();
+            brain.plan(classification);
+            report.plan.record(start.elapsed());
+        }
+    }
+
+    // Cold passes, the last one leaves the cache warm
+    for _ in 0..iterations {
+        brain.clear_cache();
+        brain.clear_context();
+        for command in commands {
+            let start = Instant::now();
+            if brain.process_async(command).await.is_err() {
+                report.errors += 1;
+            }
+            report.cold.record(start.elapsed());
+        }
+    }
+
+    let before = brain.cache_stats();
+    for _ in 0..iterations {
+        for command in commands {
+            let start = Instant::now();
+            if brain.process_async(command).await.is_err() {
+                report.errors += 1;
+            }
+            report.warm.record(start.elapsed());
+        }
+    }
+    let after = brain.cache_stats();
+
+    report.warm_cache = crate::brain::cache::CacheStats {
+        parse_hits: after.parse_hits - before.parse_hits,
+        parse_misses: after.parse_misses - before.parse_misses,
+        plan_hits: after.plan_hits - before.plan_hits,
+        plan_misses: after.plan_misses - before.plan_misses,
+        ..after
+    };
+    report
+}
+
+/// Format a benchmark report as a summary table
+fn format_benchmark(report: &BenchmarkReport) -> String {
+    let mut out = format!(
+        "{} commands × {} iterations ({} errors)\n\n",
+        report.commands, report.iterations, report.errors
+    );
+    out.push_str(&format!(
+        "{:<12} {:>12} {:>14}\n",
+        "Stage", "Mean (µs)", "Throughput/s"
+    ));
+    out.push_str(&format!("{}\n", "─".repeat(40)));
+
+    let rows = [
+        ("parse", &report.parse),
+        ("classify", &report.classify),
+        ("plan", &report.plan),
+        ("cold total", &report.cold),
+        ("warm total", &report.warm),
+    ];
+    for (name, timing) in rows {
+        out.push_str(&format!(
+            "{:<12} {:>12.1} {:>14.0}\n",
+            name,
+            timing.mean().as_secs_f64() * 1_000_000.0,
+            timing.throughput()
+        ));
+    }
+
+    out.push_str(&format!(
+        "\nWarm cache hit rate: {:.1}% plans, {:.1}% parses\n",
+        report.warm_cache.plan_hit_rate() * 100.0,
+        report.warm_cache.parse_hit_rate() * 100.0
+    ));
+    out
+}
+
+/// Run the benchmark command
+pub async fn run_benchmark(iterations: usize, file: Option<PathBuf>) -> Result<()> {
+    use crate::brain::Brain;
+
+    let commands = load_benchmark_commands(file.as_deref())?;
+    let iterations = iterations.max(1);
+
+    println!("\n⏱️  Brain Pipeline Benchmark\n");
+    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n");
+
+    let config_mgr = ConfigManager::new(None).await?;
+    let config = config_mgr.get().await;
+    let brain = Brain::new(&config.brain)?;
+
+    let report = benchmark_brain(&brain, &commands, iterations).await;
+    print!("{}", format_benchmark(&report));
+
+    Ok(())
+}
+
+/// Run the run-script command
+pub async fn run_script(file: PathBuf, dry_run: bool) -> Result<()> {
+    use crate::actions::{AppLauncher, FileSearch, TaskExecutor};
+    use crate::brain::Brain;
+    use crate::db::{AppDatabase, FileIndex};
+    use crate::runtime::ScriptRunner;
+    use std::sync::Arc;
+
+    let content = std::fs::read_to_string(&file)?;
+    let lines: Vec<&str> = content.lines().collect();
+
+    println!("\n📜 Running Script: {}\n", file.display());
+    if dry_run {
+        println!("Mode: dry run (nothing is executed)");
+    }
+    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n");
+
+    let config_mgr = ConfigManager::new(None).await?;
+    let config = config_mgr.get().await;
+    let data_dir = PathBuf::from(&config.system.data_dir);
+
+    // Use the saved indices so app and file commands resolve as they do live
+    let app_db = AppDatabase::load_from_disk(data_dir.join("app_database.json"))
+        .await
+        .unwrap_or_default();
+    let file_index = FileIndex::load_from_disk(data_dir.join("file_index.json"))
+        .await
+        .unwrap_or_else(|_| FileIndex::new());
+
+    let mut brain = Brain::new(&config.brain)?;
+    for app in app_db.all() {
+        brain.add_known_app_with_aliases(app.name.clone(), app.aliases.clone());
+    }
+
+    let app_launcher = AppLauncher::new(Arc::new(parking_lot::RwLock::new(app_db)));
+    let file_search = FileSearch::new(Arc::new(file_index));
+    // Sensitive actions only run if the next script line confirms them
+    let (confirmation_tx, confirmation_rx) = tokio::sync::mpsc::channel(1);
+    let executor = TaskExecutor::new(app_launcher.clone(), file_search)
+        .with_confirmation_channel(confirmation_tx);
+
+    let mut runner = ScriptRunner::new(Arc::new(brain), Arc::new(executor))
+        .with_confidence_threshold(config.brain.confidence_threshold)
+        .with_category_resolver(app_launcher)
+        .with_dry_run(dry_run)
+        .with_confirmations(confirmation_rx);
+    match crate::tts::TtsSystem::with_config(config.tts.clone()) {
+        Ok(tts) => runner = runner.with_tts(Arc::new(tts)),
+        Err(e) => tracing::warn!("TTS unavailable, responses are only printed: {}", e),
+    }
+    let results = runner.run_commands(&lines).await;
+
+    let mut failed = 0;
+    for result in &results {
+        let mark = if result.success { "✅" } else { "❌" };
+        println!("{} \"{}\"", mark, result.text);
+        for clarification in &result.clarifications {
+            println!("   ↳ \"{}\"", clarification);
+        }
+        if let Some(plan) = &result.plan {
+            println!(
+                "   Intent: {:?} ({:.2})",
+                plan.classification.intent, plan.classification.confidence
+            );
+        }
+        println!("   {}", result.response);
+        if !result.success {
+            failed += 1;
+        }
+    }
+
+    println!("\n{} commands, {} failed\n", results.len(), failed);
+
+    Ok(())
+}
+
 /// Run the config command
 pub async fn run_config(show: bool, validate: bool) -> Result<()> {
     let config_mgr = ConfigManager::new(None).await?;
@@ -623,11 +1443,40 @@ pub async fn run_config(show: bool, validate: bool) -> Result<()> {
         println!("\n🔍 Validating configuration...");
         config.validate()?;
         println!("✅ Configuration is valid\n");
+
+        let grammar_path = std::path::Path::new("config/brain_patterns.yaml");
+        if grammar_path.exists() {
+            use crate::brain::GrammarConfig;
+
+            println!("🔍 Validating grammar...");
+            let grammar = GrammarConfig::load_from_file(grammar_path)?.compile()?;
+            let diagnostics = grammar.diagnostics();
+
+            if diagnostics.is_empty() {
+                println!("✅ Grammar is valid ({} patterns)\n", grammar.pattern_count());
+            } else {
+                for warning in &diagnostics {
+                    println!("  ⚠️  {}", warning);
+                }
+                println!(
+                    "⚠️  Grammar compiled with {} warning(s)\n",
+                    diagnostics.len()
+                );
+            }
+        }
     }
 
     if show {
         println!("\n📋 Current Configuration:\n");
-        println!("{:#?}", *config);
+        for entry in config_mgr.explain_all().await? {
+            println!(
+                "  {} = {} {}",
+                entry.path,
+                entry.value,
+                entry.source.label()
+            );
+        }
+        println!();
     }
 
     Ok(())
@@ -676,6 +1525,10 @@ pub async fn run_audio_monitor(duration: u64) -> Result<()> {
         config.vad_aggressiveness,
         config.target_sample_rate,
     )?;
+    // Frames are read every 100ms
+    if let Some(window) = config.vad_calibration_frames(100) {
+        vad = vad.with_adaptive_threshold(config.vad_noise_multiplier, window);
+    }
 
     capture.start()?;
 
@@ -717,9 +1570,8 @@ pub async fn run_audio_monitor(duration: u64) -> Result<()> {
 
 /// Run audio recording
 pub async fn run_audio_record(duration: u64, output: PathBuf) -> Result<()> {
-    use crate::audio::AudioCapture;
+    use crate::audio::{write_wav, AudioCapture};
     use crate::config::AudioConfig;
-    use hound::{WavSpec, WavWriter};
 
     println!("\n🎙️  Recording Audio ({}s)\n", duration);
 
@@ -735,22 +1587,28 @@ pub async fn run_audio_record(duration: u64, output: PathBuf) -> Result<()> {
     capture.stop()?;
 
     println!("Saving to {:?}...", output);
+    write_wav(&output, &audio, config.target_sample_rate)?;
 
-    let spec = WavSpec {
-        channels: 1,
-        sample_rate: config.target_sample_rate,
-        bits_per_sample: 16,
-        sample_format: hound::SampleFormat::Int,
-    };
+    println!("✅ Recorded {} samples to {:?}\n", audio.len(), output);
+    Ok(())
+}
 
-    let mut writer = WavWriter::create(&output, spec)?;
-    for &sample in &audio {
-        let sample_i16 = (sample.clamp(-1.0, 1.0) * 32767.0) as i16;
-        writer.write_sample(sample_i16)?;
-    }
-    writer.finalize()?;
+/// Capture until Ctrl+C, then dump the ring buffer
+pub async fn run_audio_dump(duration_ms: u64, output: PathBuf) -> Result<()> {
+    use crate::audio::AudioCapture;
+    use crate::config::AudioConfig;
 
-    println!("✅ Recorded {} samples to {:?}\n", audio.len(), output);
+    println!("\n🎙️  Capturing audio\n");
+    println!("Press Ctrl+C to save the last {}ms\n", duration_ms);
+
+    let mut capture = AudioCapture::new(AudioConfig::default())?;
+    capture.start()?;
+    tokio::signal::ctrl_c().await.ok();
+
+    capture.dump_last(duration_ms, &output)?;
+    capture.stop()?;
+
+    println!("✅ Saved to {:?} ({} Hz)\n", output, capture.sample_rate());
     Ok(())
 }
 
@@ -770,7 +1628,10 @@ pub async fn run_audio_test_wake(duration: u64) -> Result<()> {
         WakeWordEngine::from_str(&audio_config.wake_word_engine),
         audio_config.wake_words.clone(),
         brain_config.wake_word_sensitivity,
-    )?;
+    )?
+    .with_cooldown(std::time::Duration::from_millis(
+        audio_config.wake_word_cooldown_ms,
+    ));
 
     capture.start()?;
 
@@ -839,6 +1700,10 @@ pub async fn run_audio(command: AudioCommands) -> Result<()> {
         AudioCommands::Devices => run_audio_devices().await,
         AudioCommands::Monitor { duration } => run_audio_monitor(duration).await,
         AudioCommands::Record { duration, output } => run_audio_record(duration, output).await,
+        AudioCommands::Dump {
+            duration_ms,
+            output,
+        } => run_audio_dump(duration_ms, output).await,
         AudioCommands::TestWake { duration } => run_audio_test_wake(duration).await,
         AudioCommands::Stats => run_audio_stats().await,
     }
@@ -912,6 +1777,31 @@ pub async fn run_brain_classify(text: String) -> Result<()> {
     Ok(())
 }
 
+/// Run brain explain command
+pub async fn run_brain_explain(text: String) -> Result<()> {
+    use crate::brain::Brain;
+    use crate::config::BrainConfig;
+
+    println!("\n🧠 Explaining Confidence\n");
+    println!("Input: \"{}\"", text);
+    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n");
+
+    let config = BrainConfig::default();
+    let brain = Brain::new(&config)?;
+
+    match brain.explain(&text) {
+        Ok(explanation) => {
+            print!("{}", explanation);
+            println!("\n✅ Explanation complete");
+        }
+        Err(e) => {
+            println!("❌ Explanation failed: {}", e);
+        }
+    }
+
+    Ok(())
+}
+
 /// Run brain plan command
 pub async fn run_brain_plan(text: String, preview: bool) -> Result<()> {
     use crate::brain::Brain;
@@ -1024,6 +1914,7 @@ pub async fn run_brain(command: BrainCommands) -> Result<()> {
         BrainCommands::Parse { text } => run_brain_parse(text).await,
         BrainCommands::Classify { text } => run_brain_classify(text).await,
         BrainCommands::Plan { text, preview } => run_brain_plan(text, preview).await,
+        BrainCommands::Explain { text } => run_brain_explain(text).await,
         BrainCommands::GrammarReload => run_brain_grammar_reload().await,
     }
 }
@@ -1032,14 +1923,38 @@ pub async fn run_brain(command: BrainCommands) -> Result<()> {
 pub async fn run_cli(cli: Cli) -> Result<()> {
     match cli.command {
         Some(Commands::Doctor { extended }) => run_doctor(extended).await,
-        Some(Commands::Index { apps, files, all }) => run_index(apps, files, all).await,
+        Some(Commands::Index {
+            apps,
+            files,
+            all,
+            watch,
+            full,
+        }) => run_index(apps, files, all, watch, full).await,
         Some(Commands::Events {
             filter,
             tail,
             limit,
-        }) => run_events(filter, tail, limit).await,
+            record,
+        }) => run_events(filter, tail, limit, record).await,
+        Some(Commands::Replay {
+            file,
+            speed,
+            filter,
+        }) => run_replay(file, speed, filter).await,
         Some(Commands::Metrics { detailed, output }) => run_metrics(detailed, output).await,
         Some(Commands::Config { show, validate }) => run_config(show, validate).await,
+        Some(Commands::Resources { watch, interval }) => run_resources(watch, interval).await,
+        Some(Commands::Say {
+            text,
+            kind,
+            voice,
+            ssml,
+            list_voices,
+        }) => run_say(text, kind, voice, ssml, list_voices).await,
+        Some(Commands::Ask { question, online }) => run_ask(question, online).await,
+        Some(Commands::Explain { text, dot, json }) => run_explain(text, dot, json).await,
+        Some(Commands::Benchmark { iterations, file }) => run_benchmark(iterations, file).await,
+        Some(Commands::RunScript { file, dry_run }) => run_script(file, dry_run).await,
         Some(Commands::Audio { command }) => run_audio(command).await,
         Some(Commands::Brain { command }) => run_brain(command).await,
         None => {
@@ -1060,4 +1975,160 @@ mod tests {
         // Test that CLI can be built
         let _cli = Cli::command();
     }
+
+    #[tokio::test]
+    async fn test_format_resources() {
+        use crate::os::resource_monitor::{ProcessUsage, ResourceMonitor};
+
+        let monitor = ResourceMonitor::new();
+        let metrics = monitor.get_current_metrics().await;
+        let processes = vec![ProcessUsage {
+            pid: 42,
+            name: "editor".to_string(),
+            cpu_percent: 12.5,
+            memory_mb: 512,
+        }];
+
+        let output = format_resources(&metrics, &processes);
+        assert!(output.contains("CPU"));
+        assert!(output.contains("Memory"));
+        assert!(output.contains("editor"));
+        assert_eq!(
+            output.lines().filter(|l| l.starts_with("  #")).count(),
+            metrics.cpu_per_core.len()
+        );
+    }
+
+    #[test]
+    fn test_say_arguments() {
+        let cli = Cli::try_parse_from(["luna", "say", "hello", "--kind", "error"]).unwrap();
+        match cli.command {
+            Some(Commands::Say { text, kind, .. }) => {
+                assert_eq!(text.as_deref(), Some("hello"));
+                assert!(kind.parse::<crate::tts::MessageKind>().is_ok());
+            }
+            _ => panic!("expected say command"),
+        }
+
+        assert!(Cli::try_parse_from(["luna", "say"]).is_err());
+        assert!(Cli::try_parse_from(["luna", "say", "--list-voices"]).is_ok());
+    }
+
+    #[test]
+    fn test_ask_is_offline_by_default() {
+        let cli = Cli::try_parse_from(["luna", "ask", "what is rust"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Ask { online: false, .. })
+        ));
+
+        let cli = Cli::try_parse_from(["luna", "ask", "what is rust", "--online"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Ask { online: true, .. })
+        ));
+    }
+
+    #[test]
+    fn test_format_answer() {
+        use crate::knowledge::{Answer, AnswerSource};
+
+        let answer = Answer {
+            text: "Paris".to_string(),
+            confidence: 0.85,
+            source: AnswerSource::Wikipedia,
+            source_url: Some("https://en.wikipedia.org/wiki/Paris".to_string()),
+            context: None,
+        };
+
+        let output = format_answer(&answer);
+        assert!(output.starts_with("Paris"));
+        assert!(output.contains("Source: Wikipedia"));
+        assert!(output.contains("Confidence: 0.85"));
+        assert!(output.contains("URL: https://en.wikipedia.org/wiki/Paris"));
+        assert!(!output.contains("Context:"));
+    }
+
+    #[test]
+    fn test_load_benchmark_commands() {
+        let commands = load_benchmark_commands(None).unwrap();
+        assert_eq!(commands.len(), BENCHMARK_CORPUS.len());
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("commands.txt");
+        std::fs::write(&path, "# smoke set\nopen firefox\n\n  mute the sound  \n").unwrap();
+        assert_eq!(
+            load_benchmark_commands(Some(&path)).unwrap(),
+            vec!["open firefox", "mute the sound"]
+        );
+
+        std::fs::write(&path, "# nothing here\n").unwrap();
+        assert!(load_benchmark_commands(Some(&path)).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_benchmark_separates_cold_and_warm() {
+        use crate::brain::Brain;
+        use crate::config::BrainConfig;
+
+        let brain = Brain::new(&BrainConfig::default()).unwrap();
+        let commands = vec!["open firefox".to_string(), "mute the sound".to_string()];
+        let report = benchmark_brain(&brain, &commands, 2).await;
+
+        assert_eq!(report.cold.samples, 4);
+        assert_eq!(report.warm.samples, 4);
+        assert_eq!(report.warm_cache.plan_hits, 4);
+        assert!((report.warm_cache.plan_hit_rate() - 1.0).abs() < f64::EPSILON);
+        assert!(format_benchmark(&report).contains("warm total"));
+    }
+
+    #[test]
+    fn test_event_filter_exact() {
+        let filter = EventTypeFilter::parse("action_started");
+        assert!(filter.matches("action_started"));
+        assert!(!filter.matches("action_completed"));
+
+        // "error" only matches the error event, not every type containing it
+        let filter = EventTypeFilter::parse("error");
+        assert!(filter.matches("error"));
+        assert!(!filter.matches("action_retry"));
+    }
+
+    #[test]
+    fn test_event_filter_glob() {
+        let filter = EventTypeFilter::parse("tts_*");
+        assert!(filter.matches("tts_started"));
+        assert!(filter.matches("tts_interrupted"));
+        assert!(!filter.matches("action_started"));
+
+        let filter = EventTypeFilter::parse("*_completed");
+        assert!(filter.matches("action_completed"));
+        assert!(filter.matches("plan_completed"));
+        assert!(!filter.matches("plan_started"));
+
+        assert!(glob_match("a*b*c", "axxbyyc"));
+        assert!(!glob_match("a*b*c", "axxbyy"));
+    }
+
+    #[test]
+    fn test_event_filter_multiple() {
+        let filter = EventTypeFilter::parse("action_*, plan_*,TTS_STARTED");
+        assert!(filter.matches("action_retry"));
+        assert!(filter.matches("plan_started"));
+        assert!(filter.matches("tts_started"));
+        assert!(!filter.matches("tts_completed"));
+        assert!(!filter.matches("wake_word_detected"));
+
+        assert!(EventTypeFilter::from_option(None).matches("custom"));
+        assert_eq!(
+            EventTypeFilter::parse("action_*,acton_started").unmatched(),
+            vec!["acton_started"]
+        );
+    }
+
+    #[test]
+    fn test_usage_bar() {
+        assert_eq!(usage_bar(50.0, 4), "██░░");
+        assert_eq!(usage_bar(150.0, 2), "██");
+    }
 }
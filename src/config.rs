@@ -4,13 +4,23 @@
 //! Provides validation and default values for all settings.
 
 use crate::config_error;
+use crate::db::schema::AppCategory;
 use crate::error::{LunaError, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 use std::path::{Path, PathBuf};
 
+/// Current config schema version
+///
+/// Bump this and add a step to `ConfigManager::migrate` when renaming keys.
+pub const CONFIG_VERSION: u32 = 2;
+
 /// Main configuration structure for LUNA
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LunaConfig {
+    /// Schema version of this configuration
+    #[serde(default = "default_config_version")]
+    pub version: u32,
     /// Audio system configuration
     pub audio: AudioConfig,
     /// Brain/NLP configuration
@@ -21,6 +31,30 @@ pub struct LunaConfig {
     pub paths: PathsConfig,
     /// Performance tuning
     pub performance: PerformanceConfig,
+    /// Text-to-speech settings
+    #[serde(default)]
+    pub tts: crate::tts::TtsConfig,
+    /// Health monitoring and remediation (detect-only by default)
+    #[serde(default)]
+    pub health: crate::os::health_monitor::HealthMonitorConfig,
+}
+
+/// A config value that differs between two configurations
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChangedField {
+    /// Dotted key path, e.g. "audio.sample_rate"
+    pub path: String,
+    /// Value before the change
+    pub old: toml::Value,
+    /// Value after the change
+    pub new: toml::Value,
+}
+
+impl ChangedField {
+    /// Top-level section of the key, e.g. "audio"
+    pub fn section(&self) -> &str {
+        self.path.split('.').next().unwrap_or_default()
+    }
 }
 
 /// Audio capture and processing configuration
@@ -87,6 +121,14 @@ pub struct AudioConfig {
     #[serde(default = "default_vad_aggressiveness")]
     pub vad_aggressiveness: u8,
 
+    /// Energy thresholds sit this many times above the background noise
+    #[serde(default = "default_vad_noise_multiplier")]
+    pub vad_noise_multiplier: f32,
+
+    /// Background noise averaging window in milliseconds (0 = fixed thresholds)
+    #[serde(default = "default_vad_calibration_ms")]
+    pub vad_calibration_ms: u64,
+
     /// Enable noise suppression
     #[serde(default = "default_true")]
     pub noise_suppression: bool,
@@ -106,6 +148,19 @@ pub struct AudioConfig {
     /// Ring buffer capacity in samples
     #[serde(default = "default_ring_buffer_capacity")]
     pub ring_buffer_capacity: usize,
+
+    /// Minimum time between wake word detections in milliseconds
+    #[serde(default = "default_wake_word_cooldown_ms")]
+    pub wake_word_cooldown_ms: u64,
+
+    /// Speech the VAD must detect in a command before it is transcribed
+    /// (milliseconds, 0 = always transcribe)
+    #[serde(default = "default_min_speech_ms")]
+    pub min_speech_ms: u32,
+
+    /// Directory to save command audio to when STT fails (empty = disabled)
+    #[serde(default)]
+    pub stt_error_dump_dir: String,
 }
 
 /// Brain/NLP system configuration
@@ -127,17 +182,37 @@ pub struct BrainConfig {
     #[serde(default = "default_confidence_threshold")]
     pub confidence_threshold: f32,
 
+    /// Minimum score for offering a runner-up intent when a command does nothing (0.0 - 1.0)
+    #[serde(default = "default_alternative_threshold")]
+    pub alternative_threshold: f32,
+
     /// Wake word detection sensitivity (0.0 - 1.0, higher = more sensitive)
     #[serde(default = "default_wake_word_sensitivity")]
     pub wake_word_sensitivity: f32,
 
-    /// STT engine: "whisper", "simulate"
+    /// STT engine: "whisper", "vosk", "simulate"
     #[serde(default = "default_stt_engine")]
     pub stt_engine: String,
 
+    /// Path to Vosk model directory (used when `stt_engine = "vosk"`)
+    #[serde(default = "default_vosk_model_path")]
+    pub vosk_model_path: String,
+
     /// Number of threads for STT processing
     #[serde(default = "default_stt_threads")]
     pub stt_threads: usize,
+
+    /// Allow question answering to fall back to online web search
+    #[serde(default)]
+    pub enable_web_search: bool,
+
+    /// YAML file with a confidence calibration curve (empty = uncalibrated)
+    #[serde(default)]
+    pub calibration_path: String,
+
+    /// Language commands are spoken in: "en", "es"
+    #[serde(default = "default_language")]
+    pub language: String,
 }
 
 /// System-level configuration
@@ -158,6 +233,14 @@ pub struct SystemConfig {
     /// Enable telemetry (currently unused, for future)
     #[serde(default)]
     pub enable_telemetry: bool,
+
+    /// YAML file of allowlisted command skills (empty = none)
+    #[serde(default)]
+    pub skills_path: String,
+
+    /// App to open per category, e.g. `browser = "Firefox"`
+    #[serde(default)]
+    pub preferred_apps: BTreeMap<String, String>,
 }
 
 /// Path configurations for search and discovery
@@ -190,9 +273,17 @@ pub struct PerformanceConfig {
     /// File index update interval in seconds
     #[serde(default = "default_index_update_interval")]
     pub index_update_interval_secs: u64,
+
+    /// Interval between published metrics snapshots in seconds (0 disables)
+    #[serde(default = "default_metrics_snapshot_interval")]
+    pub metrics_snapshot_interval_secs: u64,
 }
 
 // Default value functions
+fn default_config_version() -> u32 {
+    CONFIG_VERSION
+}
+
 fn default_wake_words() -> Vec<String> {
     vec!["hey luna".to_string(), "okay luna".to_string()]
 }
@@ -250,6 +341,14 @@ fn default_vad_aggressiveness() -> u8 {
     2
 }
 
+fn default_vad_noise_multiplier() -> f32 {
+    3.0
+}
+
+fn default_vad_calibration_ms() -> u64 {
+    3000
+}
+
 fn default_drop_policy() -> String {
     "DropOldest".to_string()
 }
@@ -258,6 +357,14 @@ fn default_ring_buffer_capacity() -> usize {
     48000 // 1 second at 48kHz
 }
 
+fn default_wake_word_cooldown_ms() -> u64 {
+    1500
+}
+
+fn default_min_speech_ms() -> u32 {
+    100
+}
+
 fn default_true() -> bool {
     true
 }
@@ -282,6 +389,10 @@ fn default_confidence_threshold() -> f32 {
     0.7
 }
 
+fn default_alternative_threshold() -> f32 {
+    0.5
+}
+
 fn default_wake_word_sensitivity() -> f32 {
     0.5
 }
@@ -290,10 +401,18 @@ fn default_stt_engine() -> String {
     "simulate".to_string()
 }
 
+fn default_vosk_model_path() -> String {
+    "models/vosk-model-small-en-us".to_string()
+}
+
 fn default_stt_threads() -> usize {
     4
 }
 
+fn default_language() -> String {
+    "en".to_string()
+}
+
 fn default_log_level() -> String {
     "info".to_string()
 }
@@ -353,14 +472,21 @@ fn default_index_update_interval() -> u64 {
     300 // 5 minutes
 }
 
+fn default_metrics_snapshot_interval() -> u64 {
+    30
+}
+
 impl Default for LunaConfig {
     fn default() -> Self {
         Self {
+            version: CONFIG_VERSION,
             audio: AudioConfig::default(),
             brain: BrainConfig::default(),
             system: SystemConfig::default(),
             paths: PathsConfig::default(),
             performance: PerformanceConfig::default(),
+            tts: crate::tts::TtsConfig::default(),
+            health: crate::os::health_monitor::HealthMonitorConfig::default(),
         }
     }
 }
@@ -383,11 +509,16 @@ impl Default for AudioConfig {
             wake_word_engine: default_wake_word_engine(),
             vad_engine: default_vad_engine(),
             vad_aggressiveness: default_vad_aggressiveness(),
+            vad_noise_multiplier: default_vad_noise_multiplier(),
+            vad_calibration_ms: default_vad_calibration_ms(),
             noise_suppression: default_true(),
             agc: default_true(),
             aec: default_false(),
             drop_policy: default_drop_policy(),
             ring_buffer_capacity: default_ring_buffer_capacity(),
+            wake_word_cooldown_ms: default_wake_word_cooldown_ms(),
+            min_speech_ms: default_min_speech_ms(),
+            stt_error_dump_dir: String::new(),
         }
     }
 }
@@ -419,6 +550,13 @@ impl AudioConfig {
             ));
         }
 
+        if self.vad_noise_multiplier < 1.0 {
+            return Err(config_error!(
+                "VAD noise multiplier {} must be at least 1.0",
+                self.vad_noise_multiplier
+            ));
+        }
+
         // Timeout validation
         if self.recording_timeout_secs == 0 || self.recording_timeout_secs > 300 {
             return Err(config_error!(
@@ -437,6 +575,12 @@ impl AudioConfig {
 
         Ok(())
     }
+
+    /// Noise calibration window in frames of `frame_ms`, if adaptation is on
+    pub fn vad_calibration_frames(&self, frame_ms: u64) -> Option<usize> {
+        (self.vad_calibration_ms > 0)
+            .then(|| (self.vad_calibration_ms / frame_ms.max(1)).max(1) as usize)
+    }
 }
 
 impl Default for BrainConfig {
@@ -446,9 +590,14 @@ impl Default for BrainConfig {
             response_timeout_ms: default_response_timeout(),
             context_window_size: default_context_window(),
             confidence_threshold: default_confidence_threshold(),
+            alternative_threshold: default_alternative_threshold(),
             wake_word_sensitivity: default_wake_word_sensitivity(),
             stt_engine: default_stt_engine(),
+            vosk_model_path: default_vosk_model_path(),
             stt_threads: default_stt_threads(),
+            enable_web_search: false,
+            calibration_path: String::new(),
+            language: default_language(),
         }
     }
 }
@@ -475,6 +624,13 @@ impl BrainConfig {
             ));
         }
 
+        if !(0.0..=1.0).contains(&self.alternative_threshold) {
+            return Err(config_error!(
+                "Alternative threshold {} must be between 0.0 and 1.0",
+                self.alternative_threshold
+            ));
+        }
+
         // Wake word sensitivity
         if !(0.0..=1.0).contains(&self.wake_word_sensitivity) {
             return Err(config_error!(
@@ -491,6 +647,14 @@ impl BrainConfig {
             ));
         }
 
+        // Command language
+        if crate::brain::Locale::from_code(&self.language).is_none() {
+            return Err(config_error!(
+                "Unsupported language '{}' (must be one of: en, es)",
+                self.language
+            ));
+        }
+
         Ok(())
     }
 }
@@ -502,6 +666,8 @@ impl Default for SystemConfig {
             data_dir: default_data_dir(),
             cache_dir: default_cache_dir(),
             enable_telemetry: false,
+            skills_path: String::new(),
+            preferred_apps: BTreeMap::new(),
         }
     }
 }
@@ -526,8 +692,30 @@ impl SystemConfig {
         std::fs::create_dir_all(&self.cache_dir)
             .map_err(|e| config_error!("Cannot create cache directory: {}", e))?;
 
+        // Reject unsafe skills at startup rather than when first spoken
+        if !self.skills_path.is_empty() {
+            crate::actions::skills::SkillRegistry::load_from_file(&self.skills_path)?;
+        }
+
+        for category in self.preferred_apps.keys() {
+            if AppCategory::from_spoken(category).is_none() {
+                return Err(config_error!(
+                    "Unknown app category '{}' in preferred_apps",
+                    category
+                ));
+            }
+        }
+
         Ok(())
     }
+
+    /// Preferred apps keyed by category
+    pub fn preferred_apps(&self) -> HashMap<AppCategory, String> {
+        self.preferred_apps
+            .iter()
+            .filter_map(|(category, app)| Some((AppCategory::from_spoken(category)?, app.clone())))
+            .collect()
+    }
 }
 
 impl Default for PathsConfig {
@@ -562,6 +750,7 @@ impl Default for PerformanceConfig {
             max_threads: default_max_threads(),
             cache_size_mb: default_cache_size(),
             index_update_interval_secs: default_index_update_interval(),
+            metrics_snapshot_interval_secs: default_metrics_snapshot_interval(),
         }
     }
 }
@@ -652,6 +841,32 @@ impl LunaConfig {
         Ok(())
     }
 
+    /// Fields whose values differ in `other`, in key order
+    pub fn diff(&self, other: &LunaConfig) -> Vec<ChangedField> {
+        let old = self.flatten();
+        let new = other.flatten();
+
+        new.into_iter()
+            .filter_map(|(path, new)| {
+                let old = old.get(&path)?;
+                (*old != new).then(|| ChangedField {
+                    path,
+                    old: old.clone(),
+                    new,
+                })
+            })
+            .collect()
+    }
+
+    /// Leaf values by dotted key path
+    fn flatten(&self) -> std::collections::BTreeMap<String, toml::Value> {
+        let mut leaves = Vec::new();
+        if let Ok(value) = toml::Value::try_from(self) {
+            flatten_keys("", &value, &mut leaves);
+        }
+        leaves.into_iter().collect()
+    }
+
     /// Validate configuration values
     pub fn validate(&self) -> Result<()> {
         // Validate each subsection
@@ -660,8 +875,87 @@ impl LunaConfig {
         self.system.validate()?;
         self.paths.validate()?;
         self.performance.validate()?;
+        self.tts
+            .policy
+            .validate()
+            .map_err(|e| config_error!("Invalid TTS voice profile: {}", e))?;
+        self.validate_cross_fields()?;
         Ok(())
     }
+
+    /// Validate relationships between fields of different subsections
+    fn validate_cross_fields(&self) -> Result<()> {
+        let audio = &self.audio;
+
+        // Audio is captured at the preferred rate and resampled down
+        if audio.target_sample_rate > audio.preferred_sample_rate {
+            return Err(config_error!(
+                "Target sample rate {} Hz exceeds preferred sample rate {} Hz (resampling only goes down)",
+                audio.target_sample_rate,
+                audio.preferred_sample_rate
+            ));
+        }
+
+        // Pre- and post-roll are both read back from the ring buffer
+        if audio.preferred_sample_rate == 0 {
+            return Err(config_error!(
+                "Preferred sample rate must be greater than 0"
+            ));
+        }
+        let ring_buffer_ms =
+            audio.ring_buffer_capacity as u64 * 1000 / audio.preferred_sample_rate as u64;
+        let roll_ms = audio.pre_roll_ms as u64 + audio.post_roll_ms as u64;
+        if roll_ms > ring_buffer_ms {
+            return Err(config_error!(
+                "Pre-roll ({} ms) + post-roll ({} ms) exceeds ring buffer duration ({} ms for {} samples at {} Hz)",
+                audio.pre_roll_ms,
+                audio.post_roll_ms,
+                ring_buffer_ms,
+                audio.ring_buffer_capacity,
+                audio.preferred_sample_rate
+            ));
+        }
+
+        // Every command would be rejected if the ranker can never reach the threshold
+        let mut ranker = crate::brain::ranking::RankingScorer::new();
+        if !self.brain.calibration_path.is_empty() {
+            let curve = crate::brain::ranking::CalibrationCurve::load_from_file(
+                &self.brain.calibration_path,
+            )?;
+            ranker = ranker.with_calibration(curve);
+        }
+        let max_confidence = ranker.max_score();
+        if self.brain.confidence_threshold > max_confidence {
+            return Err(config_error!(
+                "Confidence threshold {} is unreachable: the ranker scores at most {:.2}",
+                self.brain.confidence_threshold,
+                max_confidence
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// Collect leaf values of a TOML tree under dotted paths
+pub(crate) fn flatten_keys(
+    prefix: &str,
+    value: &toml::Value,
+    out: &mut Vec<(String, toml::Value)>,
+) {
+    match value {
+        toml::Value::Table(table) => {
+            for (key, child) in table {
+                let path = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", prefix, key)
+                };
+                flatten_keys(&path, child, out);
+            }
+        }
+        leaf => out.push((prefix.to_string(), leaf.clone())),
+    }
 }
 
 #[cfg(test)]
@@ -700,6 +994,111 @@ mod tests {
         assert_eq!(loaded.audio.sample_rate, config.audio.sample_rate);
     }
 
+    #[test]
+    fn test_target_rate_above_preferred_rate() {
+        let mut config = LunaConfig::default();
+        config.audio.preferred_sample_rate = 16000;
+        config.audio.target_sample_rate = 22050;
+
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("exceeds preferred sample rate"), "{}", err);
+    }
+
+    #[test]
+    fn test_roll_exceeds_ring_buffer() {
+        let mut config = LunaConfig::default();
+        // 0.25 s at 48 kHz, but 500 ms of roll
+        config.audio.ring_buffer_capacity = 12000;
+
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("exceeds ring buffer duration"), "{}", err);
+
+        config.audio.ring_buffer_capacity = 24000;
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_unreachable_confidence_threshold() {
+        let mut config = LunaConfig::default();
+        config.brain.confidence_threshold = 0.95;
+
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("unreachable"), "{}", err);
+    }
+
+    #[test]
+    fn test_preferred_apps_by_category() {
+        let mut config = LunaConfig::default();
+        config
+            .system
+            .preferred_apps
+            .insert("web browser".to_string(), "Firefox".to_string());
+        assert!(config.validate().is_ok());
+        assert_eq!(
+            config.system.preferred_apps().get(&AppCategory::Browser),
+            Some(&"Firefox".to_string())
+        );
+
+        config
+            .system
+            .preferred_apps
+            .insert("spaceship".to_string(), "Rocket".to_string());
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("spaceship"), "{}", err);
+    }
+
+    #[test]
+    fn test_calibration_makes_threshold_reachable() {
+        let dir = tempdir().unwrap();
+        let curve_path = dir.path().join("calibration.yaml");
+        fs::write(
+            &curve_path,
+            "type: piecewise_linear\npoints: [[0.0, 0.0], [0.8, 1.0]]\n",
+        )
+        .unwrap();
+
+        let mut config = LunaConfig::default();
+        config.brain.confidence_threshold = 0.95;
+        config.brain.calibration_path = curve_path.to_string_lossy().to_string();
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_diff_identical_is_empty() {
+        let config = LunaConfig::default();
+        assert!(config.diff(&config.clone()).is_empty());
+    }
+
+    #[test]
+    fn test_diff_reports_only_changed_audio_fields() {
+        let old = LunaConfig::default();
+        let mut new = old.clone();
+        new.audio.sample_rate = 44100;
+        new.audio.wake_words.push("hi luna".to_string());
+
+        let diff = old.diff(&new);
+        let changes = new_paths(&diff);
+        assert_eq!(changes, vec!["audio.sample_rate", "audio.wake_words"]);
+        assert!(diff.iter().all(|c| c.section() == "audio"));
+
+        let change = &diff[0];
+        assert_eq!(change.old.as_integer(), Some(16000));
+        assert_eq!(change.new.as_integer(), Some(44100));
+    }
+
+    #[test]
+    fn test_diff_reports_only_changed_brain_fields() {
+        let old = LunaConfig::default();
+        let mut new = old.clone();
+        new.brain.stt_engine = "vosk".to_string();
+
+        assert_eq!(new_paths(&old.diff(&new)), vec!["brain.stt_engine"]);
+    }
+
+    fn new_paths(changes: &[ChangedField]) -> Vec<&str> {
+        changes.iter().map(|c| c.path.as_str()).collect()
+    }
+
     #[test]
     fn test_load_nonexistent_file() {
         // Should return default config
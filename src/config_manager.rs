@@ -6,14 +6,62 @@
 //! - Atomic config updates with validation
 //! - Configuration versioning and migration
 
-use crate::config::LunaConfig;
+use crate::config::{flatten_keys, LunaConfig, CONFIG_VERSION};
 use crate::error::{LunaError, Result};
+use crate::events::{EventBus, EventEnvelope, LunaEvent};
 use notify::{Event, RecursiveMode, Watcher};
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::{error, info, warn};
 
+/// Layer a configuration value came from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigSource {
+    /// Built-in default
+    Default,
+    /// A TOML config file
+    File(PathBuf),
+    /// An environment variable override
+    Env(String),
+}
+
+impl ConfigSource {
+    /// Short label for listings, e.g. "[from env]"
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Default => "[default]",
+            Self::File(_) => "[from file]",
+            Self::Env(_) => "[from env]",
+        }
+    }
+}
+
+impl std::fmt::Display for ConfigSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Default => write!(f, "default"),
+            Self::File(path) => write!(f, "file {:?}", path),
+            Self::Env(var) => write!(f, "env {}", var),
+        }
+    }
+}
+
+/// Effective value of a config key and the layer it came from
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigProvenance {
+    /// Dotted key path, e.g. "audio.sample_rate"
+    pub path: String,
+    /// Effective value
+    pub value: toml::Value,
+    /// Layer that set the value
+    pub source: ConfigSource,
+}
+
+/// Non-default sources by dotted key path
+type ConfigSources = HashMap<String, ConfigSource>;
+
 /// Configuration manager with hot-reload capability
 pub struct ConfigManager {
     /// Current configuration (atomic access)
@@ -25,6 +73,12 @@ pub struct ConfigManager {
     /// Configuration version for migrations
     config_version: u32,
 
+    /// Layer each non-default key was loaded from
+    sources: Arc<parking_lot::RwLock<ConfigSources>>,
+
+    /// Event bus for `ConfigReloaded` notifications
+    event_bus: Option<Arc<EventBus>>,
+
     /// Watch handle (optional, only if hot-reload enabled)
     _watcher: Option<notify::RecommendedWatcher>,
 }
@@ -41,21 +95,142 @@ impl ConfigManager {
     pub async fn new(config_path: Option<PathBuf>) -> Result<Self> {
         let config_path = config_path.unwrap_or_else(|| Self::default_config_path());
 
+        // Bring an older config file up to the current schema first
+        if config_path.exists() {
+            Self::migrate_file(&config_path)?;
+        }
+
         // Load configuration with layering
-        let config = Self::load_layered(&config_path)?;
+        let (config, sources) = Self::load_layered(&config_path)?;
 
         Ok(Self {
+            config_version: config.version,
             config: Arc::new(RwLock::new(config)),
             config_path,
-            config_version: 1,
+            sources: Arc::new(parking_lot::RwLock::new(sources)),
+            event_bus: None,
             _watcher: None,
         })
     }
 
-    /// Load configuration with full layering
-    fn load_layered(primary_path: &Path) -> Result<LunaConfig> {
+    /// Publish `ConfigReloaded` events on hot-reload
+    ///
+    /// Must be set before `enable_hot_reload`.
+    pub fn with_event_bus(mut self, event_bus: Arc<EventBus>) -> Self {
+        self.event_bus = Some(event_bus);
+        self
+    }
+
+    /// Upgrade a config value from schema version `from` to `CONFIG_VERSION`
+    ///
+    /// Each step applies the renames and defaults introduced by the next
+    /// version, then the `version` key is set to the current version.
+    pub fn migrate(from: u32, mut value: toml::Value) -> toml::Value {
+        let mut version = from;
+        while version < CONFIG_VERSION {
+            value = match version {
+                1 => Self::migrate_v1_to_v2(value),
+                _ => value,
+            };
+            version += 1;
+            info!("Migrated config schema v{} -> v{}", version - 1, version);
+        }
+
+        if let Some(table) = value.as_table_mut() {
+            table.insert("version".to_string(), toml::Value::Integer(version as i64));
+        }
+        value
+    }
+
+    /// v2 made the model path engine-specific when Vosk was added
+    fn migrate_v1_to_v2(mut value: toml::Value) -> toml::Value {
+        Self::rename_key(&mut value, "brain", "model_path", "whisper_model_path");
+        value
+    }
+
+    /// Rename `section.from` to `section.to`, keeping an existing `to` value
+    fn rename_key(value: &mut toml::Value, section: &str, from: &str, to: &str) {
+        let Some(table) = value.get_mut(section).and_then(toml::Value::as_table_mut) else {
+            return;
+        };
+        let Some(old) = table.remove(from) else {
+            return;
+        };
+
+        if table.contains_key(to) {
+            warn!(
+                "Dropping obsolete config key {}.{}: {}.{} is already set",
+                section, from, section, to
+            );
+        } else {
+            info!(
+                "Renamed config key {}.{} -> {}.{}",
+                section, from, section, to
+            );
+            table.insert(to.to_string(), old);
+        }
+    }
+
+    /// Schema version of a parsed config file (files without one are v1)
+    fn file_version(value: &toml::Value) -> u32 {
+        value
+            .get("version")
+            .and_then(toml::Value::as_integer)
+            .map(|v| v as u32)
+            .unwrap_or(1)
+    }
+
+    /// Parse a config file and migrate it in memory if it is outdated
+    fn read_migrated(path: &Path) -> Result<(toml::Value, bool)> {
+        let contents = std::fs::read_to_string(path).map_err(|e| {
+            LunaError::Config(format!("Failed to read config file {:?}: {}", path, e))
+        })?;
+        let value: toml::Value = toml::from_str(&contents).map_err(|e| {
+            LunaError::Config(format!("Failed to parse config file {:?}: {}", path, e))
+        })?;
+
+        let version = Self::file_version(&value);
+        if version > CONFIG_VERSION {
+            warn!(
+                "Config file {:?} has schema v{}, newer than supported v{}",
+                path, version, CONFIG_VERSION
+            );
+        }
+        if version >= CONFIG_VERSION {
+            return Ok((value, false));
+        }
+
+        info!(
+            "Config file {:?} uses schema v{}, migrating to v{}",
+            path, version, CONFIG_VERSION
+        );
+        Ok((Self::migrate(version, value), true))
+    }
+
+    /// Rewrite an outdated config file in the current schema
+    ///
+    /// Returns `true` if the file was migrated.
+    fn migrate_file(path: &Path) -> Result<bool> {
+        let (value, migrated) = Self::read_migrated(path)?;
+        if !migrated {
+            return Ok(false);
+        }
+
+        let contents = toml::to_string_pretty(&value)
+            .map_err(|e| LunaError::Config(format!("Failed to serialize config: {}", e)))?;
+        std::fs::write(path, contents).map_err(|e| {
+            LunaError::Config(format!("Failed to write config file {:?}: {}", path, e))
+        })?;
+        info!("Rewrote migrated config file: {:?}", path);
+
+        Ok(true)
+    }
+
+    /// Load configuration with full layering, recording each key's source
+    fn load_layered(primary_path: &Path) -> Result<(LunaConfig, ConfigSources)> {
         // Start with defaults
         let mut config = LunaConfig::default();
+        let mut sources = ConfigSources::new();
 
         // Layer 1: System config (/etc/luna/config.toml)
         #[cfg(target_os = "linux")]
@@ -63,7 +238,7 @@ impl ConfigManager {
             let system_path = PathBuf::from("/etc/luna/config.toml");
             if system_path.exists() {
                 info!("Loading system config from: {:?}", system_path);
-                config = Self::merge_config(config, &system_path)?;
+                config = Self::merge_config(config, &system_path, &mut sources)?;
             }
         }
 
@@ -73,14 +248,14 @@ impl ConfigManager {
         if let Some(ref path) = user_config_path {
             if path.exists() {
                 info!("Loading user config from: {:?}", path);
-                config = Self::merge_config(config, path)?;
+                config = Self::merge_config(config, path, &mut sources)?;
             }
         }
 
         // Layer 3: Specified config file
         if primary_path.exists() {
             info!("Loading primary config from: {:?}", primary_path);
-            config = Self::merge_config(config, primary_path)?;
+            config = Self::merge_config(config, primary_path, &mut sources)?;
         } else {
             warn!(
                 "Primary config file not found: {:?}, using defaults",
@@ -89,27 +264,36 @@ impl ConfigManager {
         }
 
         // Layer 4: Environment variables (LUNA_*)
-        config = Self::apply_env_overrides(config)?;
+        config = Self::apply_env_overrides(config, &mut sources)?;
 
         // Validate final configuration
         config.validate()?;
 
-        Ok(config)
+        Ok((config, sources))
     }
 
     /// Merge configuration from a TOML file
-    fn merge_config(base: LunaConfig, path: &Path) -> Result<LunaConfig> {
-        let contents = std::fs::read_to_string(path).map_err(|e| {
-            LunaError::Config(format!("Failed to read config file {:?}: {}", path, e))
-        })?;
-
-        // Parse TOML
-        let file_config: LunaConfig = toml::from_str(&contents).map_err(|e| {
+    fn merge_config(
+        base: LunaConfig,
+        path: &Path,
+        sources: &mut ConfigSources,
+    ) -> Result<LunaConfig> {
+        // Older system/user layers are migrated in memory only
+        let (value, _) = Self::read_migrated(path)?;
+
+        let mut keys = Vec::new();
+        flatten_keys("", &value, &mut keys);
+
+        let file_config: LunaConfig = value.try_into().map_err(|e| {
             LunaError::Config(format!("Failed to parse config file {:?}: {}", path, e))
         })?;
 
         // For simplicity, we replace the entire config
         // In production, you'd want field-by-field merging
+        sources.clear();
+        for (key, _) in keys {
+            sources.insert(key, ConfigSource::File(path.to_path_buf()));
+        }
         Ok(file_config)
     }
 
@@ -117,7 +301,14 @@ impl ConfigManager {
     ///
     /// Format: LUNA_<SECTION>__<KEY>=value
     /// Example: LUNA_AUDIO__SAMPLE_RATE=44100
-    fn apply_env_overrides(mut config: LunaConfig) -> Result<LunaConfig> {
+    fn apply_env_overrides(
+        mut config: LunaConfig,
+        sources: &mut ConfigSources,
+    ) -> Result<LunaConfig> {
+        let mut from_env = |key: &str, var: &str| {
+            sources.insert(key.to_string(), ConfigSource::Env(var.to_string()));
+        };
+
         // Audio overrides
         if let Ok(val) = std::env::var("LUNA_AUDIO__SAMPLE_RATE") {
             config.audio.sample_rate = val.parse().map_err(|e| {
@@ -127,6 +318,7 @@ impl ConfigManager {
                 "Applied env override: sample_rate={}",
                 config.audio.sample_rate
             );
+            from_env("audio.sample_rate", "LUNA_AUDIO__SAMPLE_RATE");
         }
 
         if let Ok(val) = std::env::var("LUNA_AUDIO__CHANNELS") {
@@ -134,6 +326,7 @@ impl ConfigManager {
                 .parse()
                 .map_err(|e| LunaError::Config(format!("Invalid LUNA_AUDIO__CHANNELS: {}", e)))?;
             info!("Applied env override: channels={}", config.audio.channels);
+            from_env("audio.channels", "LUNA_AUDIO__CHANNELS");
         }
 
         if let Ok(val) = std::env::var("LUNA_AUDIO__SILENCE_THRESHOLD") {
@@ -144,6 +337,7 @@ impl ConfigManager {
                 "Applied env override: silence_threshold={}",
                 config.audio.silence_threshold
             );
+            from_env("audio.silence_threshold", "LUNA_AUDIO__SILENCE_THRESHOLD");
         }
 
         // System overrides
@@ -153,11 +347,13 @@ impl ConfigManager {
                 "Applied env override: log_level={}",
                 config.system.log_level
             );
+            from_env("system.log_level", "LUNA_SYSTEM__LOG_LEVEL");
         }
 
         if let Ok(val) = std::env::var("LUNA_SYSTEM__DATA_DIR") {
             config.system.data_dir = val;
             info!("Applied env override: data_dir={}", config.system.data_dir);
+            from_env("system.data_dir", "LUNA_SYSTEM__DATA_DIR");
         }
 
         // Brain overrides
@@ -169,6 +365,10 @@ impl ConfigManager {
                 "Applied env override: confidence_threshold={}",
                 config.brain.confidence_threshold
             );
+            from_env(
+                "brain.confidence_threshold",
+                "LUNA_BRAIN__CONFIDENCE_THRESHOLD",
+            );
         }
 
         // Performance overrides
@@ -180,6 +380,7 @@ impl ConfigManager {
                 "Applied env override: max_threads={}",
                 config.performance.max_threads
             );
+            from_env("performance.max_threads", "LUNA_PERFORMANCE__MAX_THREADS");
         }
 
         Ok(config)
@@ -190,7 +391,9 @@ impl ConfigManager {
         use notify::Watcher as _;
 
         let config_arc = self.config.clone();
+        let sources_arc = self.sources.clone();
         let config_path = self.config_path.clone();
+        let event_sender = self.event_bus.as_ref().map(|bus| bus.get_sender());
 
         let mut watcher = notify::recommended_watcher(move |res: notify::Result<Event>| {
             match res {
@@ -199,7 +402,7 @@ impl ConfigManager {
                         info!("Config file changed, reloading...");
 
                         match Self::load_layered(&config_path) {
-                            Ok(new_config) => {
+                            Ok((new_config, new_sources)) => {
                                 // Validate before applying
                                 if let Err(e) = new_config.validate() {
                                     error!("New config validation failed: {}", e);
@@ -208,8 +411,35 @@ impl ConfigManager {
 
                                 // Atomic swap
                                 if let Ok(mut guard) = config_arc.try_write() {
+                                    let changes = guard.diff(&new_config);
                                     *guard = new_config;
-                                    info!("✅ Configuration reloaded successfully");
+                                    *sources_arc.write() = new_sources;
+
+                                    if changes.is_empty() {
+                                        info!("Configuration reloaded, no values changed");
+                                        return;
+                                    }
+                                    info!(
+                                        "✅ Configuration reloaded successfully ({} changed)",
+                                        changes.len()
+                                    );
+
+                                    if let Some(ref tx) = event_sender {
+                                        let timestamp = std::time::SystemTime::now()
+                                            .duration_since(std::time::UNIX_EPOCH)
+                                            .unwrap_or_default()
+                                            .as_secs();
+                                        // Watcher callbacks are synchronous
+                                        let _ = tx.try_send(EventEnvelope::new(
+                                            LunaEvent::ConfigReloaded {
+                                                timestamp,
+                                                changed_fields: changes
+                                                    .into_iter()
+                                                    .map(|c| c.path)
+                                                    .collect(),
+                                            },
+                                        ));
+                                    }
                                 } else {
                                     warn!("Could not acquire write lock for config reload");
                                 }
@@ -245,6 +475,39 @@ impl ConfigManager {
         self.config.read().await.clone()
     }
 
+    /// Explain where the effective value of a dotted key comes from
+    ///
+    /// Example: `explain_key("audio.sample_rate")`
+    pub async fn explain_key(&self, path: &str) -> Result<ConfigProvenance> {
+        self.explain_all()
+            .await?
+            .into_iter()
+            .find(|p| p.path == path)
+            .ok_or_else(|| LunaError::Config(format!("Unknown config key: {}", path)))
+    }
+
+    /// Provenance of every leaf key in the effective configuration
+    pub async fn explain_all(&self) -> Result<Vec<ConfigProvenance>> {
+        let value = toml::Value::try_from(&*self.config.read().await)
+            .map_err(|e| LunaError::Config(format!("Failed to serialize config: {}", e)))?;
+
+        let mut keys = Vec::new();
+        flatten_keys("", &value, &mut keys);
+
+        let sources = self.sources.read();
+        Ok(keys
+            .into_iter()
+            .map(|(path, value)| {
+                let source = sources.get(&path).cloned().unwrap_or(ConfigSource::Default);
+                ConfigProvenance {
+                    path,
+                    value,
+                    source,
+                }
+            })
+            .collect())
+    }
+
     /// Update configuration programmatically
     pub async fn update<F>(&self, updater: F) -> Result<()>
     where
@@ -264,6 +527,11 @@ impl ConfigManager {
         Ok(())
     }
 
+    /// Schema version of the loaded configuration
+    pub fn config_version(&self) -> u32 {
+        self.config_version
+    }
+
     /// Get the default config file path
     fn default_config_path() -> PathBuf {
         dirs::config_dir()
@@ -306,6 +574,123 @@ mod tests {
         assert_eq!(config.audio.sample_rate, 44100);
     }
 
+    const V1_CONFIG: &str = r#"
+[audio]
+sample_rate = 16000
+
+[brain]
+model_path = "models/custom-whisper.bin"
+
+[system]
+log_level = "info"
+
+[paths]
+
+[performance]
+"#;
+
+    #[test]
+    fn test_migrate_v1_renames_model_path() {
+        let value: toml::Value = toml::from_str(V1_CONFIG).unwrap();
+        assert_eq!(ConfigManager::file_version(&value), 1);
+
+        let migrated = ConfigManager::migrate(1, value);
+
+        assert_eq!(
+            migrated["version"].as_integer(),
+            Some(CONFIG_VERSION as i64)
+        );
+        assert!(migrated["brain"].get("model_path").is_none());
+        assert_eq!(
+            migrated["brain"]["whisper_model_path"].as_str(),
+            Some("models/custom-whisper.bin")
+        );
+    }
+
+    #[test]
+    fn test_migrate_keeps_existing_new_key() {
+        let value: toml::Value = toml::from_str(
+            r#"
+[brain]
+model_path = "old.bin"
+whisper_model_path = "new.bin"
+"#,
+        )
+        .unwrap();
+
+        let migrated = ConfigManager::migrate(1, value);
+        assert_eq!(
+            migrated["brain"]["whisper_model_path"].as_str(),
+            Some("new.bin")
+        );
+        assert!(migrated["brain"].get("model_path").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_new_rewrites_outdated_file() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        std::fs::write(&path, V1_CONFIG).unwrap();
+
+        let manager = ConfigManager::new(Some(path.clone())).await.unwrap();
+        assert_eq!(manager.config_version(), CONFIG_VERSION);
+        assert_eq!(
+            manager.get().await.brain.whisper_model_path,
+            "models/custom-whisper.bin"
+        );
+
+        // The file on disk is now in the current schema
+        let rewritten: toml::Value =
+            toml::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(ConfigManager::file_version(&rewritten), CONFIG_VERSION);
+        assert!(!ConfigManager::migrate_file(&path).unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_provenance_file_and_default() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        std::fs::write(
+            &path,
+            "version = 2\n[audio]\n[brain]\nstt_threads = 2\n[system]\n[paths]\n[performance]\n",
+        )
+        .unwrap();
+
+        let manager = ConfigManager::new(Some(path.clone())).await.unwrap();
+
+        let threads = manager.explain_key("brain.stt_threads").await.unwrap();
+        assert_eq!(threads.value.as_integer(), Some(2));
+        assert_eq!(threads.source, ConfigSource::File(path));
+
+        let frame_ms = manager.explain_key("audio.frame_ms").await.unwrap();
+        assert_eq!(frame_ms.source, ConfigSource::Default);
+        assert_eq!(frame_ms.source.label(), "[default]");
+
+        assert!(manager.explain_key("audio.no_such_key").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_provenance_env_override() {
+        std::env::set_var("LUNA_PERFORMANCE__MAX_THREADS", "3");
+
+        let dir = tempdir().unwrap();
+        let manager = ConfigManager::new(Some(dir.path().join("missing.toml")))
+            .await
+            .unwrap();
+        std::env::remove_var("LUNA_PERFORMANCE__MAX_THREADS");
+
+        let threads = manager
+            .explain_key("performance.max_threads")
+            .await
+            .unwrap();
+        assert_eq!(threads.value.as_integer(), Some(3));
+        assert_eq!(
+            threads.source,
+            ConfigSource::Env("LUNA_PERFORMANCE__MAX_THREADS".to_string())
+        );
+        assert_eq!(threads.source.label(), "[from env]");
+    }
+
     #[tokio::test]
     async fn test_env_overrides() {
         std::env::set_var("LUNA_AUDIO__SAMPLE_RATE", "48000");
@@ -3,9 +3,16 @@
 //! Stores and retrieves conversation history for contextual understanding.
 
 use crate::brain::IntentType;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
 use std::collections::VecDeque;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+/// Appended entries after which the log should be folded into the snapshot
+const COMPACT_THRESHOLD: usize = 50;
 
 /// Entry in the conversation history
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -40,12 +47,59 @@ impl ConversationEntry {
     }
 }
 
+/// Filters for `ConversationMemory::query`
+///
+/// Unset fields match every entry, so `MemoryQuery::default()` returns the
+/// whole history.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryQuery {
+    /// Case-insensitive text in the user input or action taken
+    pub text: Option<String>,
+    /// Only entries parsed as this intent
+    pub intent: Option<IntentType>,
+    /// Only entries that succeeded (`true`) or failed (`false`)
+    pub success: Option<bool>,
+    /// Only entries at or after this time
+    pub since: Option<DateTime<Utc>>,
+}
+
+impl MemoryQuery {
+    /// Check whether an entry passes every set filter
+    pub fn matches(&self, entry: &ConversationEntry) -> bool {
+        if let Some(text) = &self.text {
+            let text = text.to_lowercase();
+            if !entry.user_input.to_lowercase().contains(&text)
+                && !entry.action_taken.to_lowercase().contains(&text)
+            {
+                return false;
+            }
+        }
+
+        self.intent
+            .as_ref()
+            .is_none_or(|intent| &entry.parsed_intent == intent)
+            && self.success.is_none_or(|success| entry.success == success)
+            && self
+                .since
+                .is_none_or(|since| entry.timestamp >= since.timestamp())
+    }
+}
+
+/// Callback run on each entry evicted at capacity
+type EvictHook = Box<dyn Fn(&ConversationEntry) + Send + Sync>;
+
 /// Conversation memory manager
 pub struct ConversationMemory {
     /// History of conversation entries
     history: VecDeque<ConversationEntry>,
     /// Maximum number of entries to keep
     max_size: usize,
+    /// JSONL log each new entry is appended to
+    append_log: Option<PathBuf>,
+    /// Entries appended since the last compaction
+    appended: usize,
+    /// Hooks run before an entry is evicted
+    evict_hooks: Vec<EvictHook>,
 }
 
 impl ConversationMemory {
@@ -59,19 +113,76 @@ impl ConversationMemory {
         Self {
             history: VecDeque::with_capacity(max_size),
             max_size,
+            append_log: None,
+            appended: 0,
+            evict_hooks: Vec::new(),
         }
     }
 
+    /// Append every new entry to a JSONL log as it's added
+    ///
+    /// Entries survive a crash without a final `save_to_disk`; load them back
+    /// with `load_with_append_log`.
+    pub fn with_append_log<P: AsRef<Path>>(mut self, path: P) -> Self {
+        self.append_log = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Run `hook` on each entry just before it's evicted to make room
+    ///
+    /// Use it to summarize or archive history that no longer fits.
+    pub fn on_evict<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&ConversationEntry) + Send + Sync + 'static,
+    {
+        self.evict_hooks.push(Box::new(hook));
+        self
+    }
+
+    /// Append evicted entries to a JSONL cold-storage file
+    pub fn archive_to<P: AsRef<Path>>(self, path: P) -> Self {
+        let path = path.as_ref().to_path_buf();
+        self.on_evict(move |entry| {
+            if let Err(e) = append_line(&path, entry) {
+                warn!("Failed to archive conversation entry: {}", e);
+            }
+        })
+    }
+
     /// Add an entry to the conversation history
     pub fn add_entry(&mut self, entry: ConversationEntry) {
+        if let Some(log) = &self.append_log {
+            match append_line(log, &entry) {
+                Ok(()) => self.appended += 1,
+                Err(e) => warn!("Failed to append to conversation log: {}", e),
+            }
+        }
+
         // If at capacity, remove oldest entry
         if self.history.len() >= self.max_size {
+            if let Some(oldest) = self.history.front() {
+                for hook in &self.evict_hooks {
+                    hook(oldest);
+                }
+            }
             self.history.pop_front();
         }
 
         self.history.push_back(entry);
     }
 
+    /// Whether enough entries have been appended to warrant `compact`
+    pub fn needs_compaction(&self) -> bool {
+        self.appended >= COMPACT_THRESHOLD
+    }
+
+    /// Fold the append log into the snapshot at `path`
+    pub async fn compact<P: AsRef<Path>>(&mut self, path: P) -> crate::error::Result<()> {
+        self.save_to_disk(path).await?;
+        self.appended = 0;
+        Ok(())
+    }
+
     /// Get the full conversation context (all entries)
     pub fn get_context(&self) -> Vec<&ConversationEntry> {
         self.history.iter().collect()
@@ -128,17 +239,30 @@ impl ConversationMemory {
 
     /// Find entries related to a specific keyword (in user input or action)
     pub fn search(&self, keyword: &str) -> Vec<&ConversationEntry> {
-        let keyword_lower = keyword.to_lowercase();
-        self.history
+        self.query(&MemoryQuery {
+            text: Some(keyword.to_string()),
+            ..Default::default()
+        })
+    }
+
+    /// Find entries matching every filter in `query`, newest first
+    pub fn query(&self, query: &MemoryQuery) -> Vec<&ConversationEntry> {
+        let mut matches: Vec<_> = self
+            .history
             .iter()
-            .filter(|e| {
-                e.user_input.to_lowercase().contains(&keyword_lower)
-                    || e.action_taken.to_lowercase().contains(&keyword_lower)
-            })
-            .collect()
+            .rev()
+            .filter(|e| query.matches(e))
+            .collect();
+        // History is normally in time order already; the stable sort only
+        // matters for entries added with older timestamps
+        matches.sort_by_key(|e| Reverse(e.timestamp));
+        matches
     }
 
     /// Save conversation history to disk
+    ///
+    /// The append log, if any, is emptied once the snapshot is written since
+    /// the snapshot now holds its entries.
     pub async fn save_to_disk<P: AsRef<std::path::Path>>(
         &self,
         path: P,
@@ -146,6 +270,9 @@ impl ConversationMemory {
         let entries: Vec<ConversationEntry> = self.history.iter().cloned().collect();
         let json = serde_json::to_string_pretty(&entries)?;
         tokio::fs::write(path, json).await?;
+        if let Some(log) = &self.append_log {
+            tokio::fs::write(log, "").await?;
+        }
         Ok(())
     }
 
@@ -170,6 +297,51 @@ impl ConversationMemory {
         
         Ok(memory)
     }
+
+    /// Load the snapshot at `path`, replay `log_path` over it, and keep
+    /// appending new entries to `log_path`
+    ///
+    /// A truncated last line, as left by a crash mid-write, is skipped and
+    /// dropped from the log so later appends start on a clean line.
+    pub async fn load_with_append_log<P, L>(path: P, log_path: L) -> crate::error::Result<Self>
+    where
+        P: AsRef<Path>,
+        L: AsRef<Path>,
+    {
+        let mut memory = Self::load_from_disk(path).await?;
+        let log_path = log_path.as_ref();
+
+        if log_path.exists() {
+            let log = tokio::fs::read_to_string(log_path).await?;
+            let mut valid = String::with_capacity(log.len());
+            for line in log.lines().filter(|line| !line.trim().is_empty()) {
+                match serde_json::from_str::<ConversationEntry>(line) {
+                    Ok(entry) => {
+                        memory.add_entry(entry);
+                        memory.appended += 1;
+                        valid.push_str(line);
+                        valid.push('\n');
+                    }
+                    Err(e) => warn!("Skipping unreadable conversation log line: {}", e),
+                }
+            }
+            if valid != log {
+                tokio::fs::write(log_path, valid).await?;
+            }
+        }
+
+        Ok(memory.with_append_log(log_path))
+    }
+}
+
+fn append_line(path: &Path, entry: &ConversationEntry) -> std::io::Result<()> {
+    let mut line = serde_json::to_string(entry)?;
+    line.push('\n');
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    file.write_all(line.as_bytes())
 }
 
 impl Default for ConversationMemory {
@@ -236,6 +408,52 @@ mod tests {
         assert!(memory.get_context()[0].user_input == "command 1");
     }
 
+    #[test]
+    fn test_evict_hook_sees_each_dropped_entry() {
+        let evicted = std::sync::Arc::new(parking_lot::Mutex::new(Vec::new()));
+        let evicted_clone = evicted.clone();
+        let mut memory = ConversationMemory::with_capacity(2)
+            .on_evict(move |entry| evicted_clone.lock().push(entry.user_input.clone()));
+
+        for i in 0..5 {
+            memory.add_entry(ConversationEntry::new(
+                format!("command {}", i),
+                IntentType::LaunchApp,
+                format!("action {}", i),
+                true,
+            ));
+        }
+
+        assert_eq!(*evicted.lock(), ["command 0", "command 1", "command 2"]);
+        assert_eq!(inputs(memory.get_context()), ["command 3", "command 4"]);
+    }
+
+    #[test]
+    fn test_archive_to_appends_evicted_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive = dir.path().join("archive.jsonl");
+        let mut memory = ConversationMemory::with_capacity(1).archive_to(&archive);
+
+        for input in ["open chrome", "close chrome", "open firefox"] {
+            memory.add_entry(ConversationEntry::new(
+                input.to_string(),
+                IntentType::LaunchApp,
+                "done".to_string(),
+                true,
+            ));
+        }
+
+        let archived: Vec<ConversationEntry> = std::fs::read_to_string(&archive)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(
+            inputs(archived.iter().collect()),
+            ["open chrome", "close chrome"]
+        );
+    }
+
     #[test]
     fn test_search() {
         let mut memory = ConversationMemory::new();
@@ -258,4 +476,163 @@ mod tests {
         assert_eq!(results.len(), 1);
         assert!(results[0].user_input.contains("chrome"));
     }
+
+    fn seeded_memory() -> ConversationMemory {
+        let now = Utc::now().timestamp();
+        let seeds = [
+            ("open chrome", IntentType::LaunchApp, true, 3 * 86_400),
+            ("close chrome", IntentType::CloseApp, false, 2 * 3_600),
+            ("open firefox", IntentType::LaunchApp, false, 3_600),
+            ("find report.pdf", IntentType::FindFile, true, 60),
+        ];
+
+        let mut memory = ConversationMemory::new();
+        for (input, intent, success, age_secs) in seeds {
+            let mut entry =
+                ConversationEntry::new(input.to_string(), intent, "done".to_string(), success);
+            entry.timestamp = now - age_secs;
+            memory.add_entry(entry);
+        }
+        memory
+    }
+
+    #[tokio::test]
+    async fn test_append_log_recovers_after_crash() {
+        let dir = tempfile::tempdir().unwrap();
+        let snapshot = dir.path().join("conversation.json");
+        let log = dir.path().join("conversation.jsonl");
+
+        let mut memory = ConversationMemory::new().with_append_log(&log);
+        memory.add_entry(ConversationEntry::new(
+            "open chrome".to_string(),
+            IntentType::LaunchApp,
+            "Launched Chrome".to_string(),
+            true,
+        ));
+        memory.compact(&snapshot).await.unwrap();
+        for input in ["close chrome", "open firefox"] {
+            memory.add_entry(ConversationEntry::new(
+                input.to_string(),
+                IntentType::LaunchApp,
+                "done".to_string(),
+                true,
+            ));
+        }
+        // Crash: no final save, and a half-written line at the end
+        drop(memory);
+        std::fs::OpenOptions::new()
+            .append(true)
+            .open(&log)
+            .unwrap()
+            .write_all(b"{\"timestamp\": 17")
+            .unwrap();
+
+        let mut recovered = ConversationMemory::load_with_append_log(&snapshot, &log)
+            .await
+            .unwrap();
+        assert_eq!(
+            inputs(recovered.get_context()),
+            ["open chrome", "close chrome", "open firefox"]
+        );
+        recovered.add_entry(ConversationEntry::new(
+            "find notes".to_string(),
+            IntentType::FindFile,
+            "done".to_string(),
+            true,
+        ));
+
+        // Saving folds the log into the snapshot without duplicating entries
+        recovered.compact(&snapshot).await.unwrap();
+        let reloaded = ConversationMemory::load_with_append_log(&snapshot, &log)
+            .await
+            .unwrap();
+        assert_eq!(reloaded.len(), 4);
+        assert!(!reloaded.needs_compaction());
+    }
+
+    fn inputs(entries: Vec<&ConversationEntry>) -> Vec<&str> {
+        entries.iter().map(|e| e.user_input.as_str()).collect()
+    }
+
+    #[test]
+    fn test_query_filters_independently() {
+        let memory = seeded_memory();
+
+        // Everything, newest first
+        assert_eq!(
+            inputs(memory.query(&MemoryQuery::default())),
+            [
+                "find report.pdf",
+                "open firefox",
+                "close chrome",
+                "open chrome"
+            ]
+        );
+
+        let by_text = MemoryQuery {
+            text: Some("CHROME".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            inputs(memory.query(&by_text)),
+            ["close chrome", "open chrome"]
+        );
+
+        let by_intent = MemoryQuery {
+            intent: Some(IntentType::LaunchApp),
+            ..Default::default()
+        };
+        assert_eq!(
+            inputs(memory.query(&by_intent)),
+            ["open firefox", "open chrome"]
+        );
+
+        let failures = MemoryQuery {
+            success: Some(false),
+            ..Default::default()
+        };
+        assert_eq!(
+            inputs(memory.query(&failures)),
+            ["open firefox", "close chrome"]
+        );
+
+        let today = MemoryQuery {
+            since: Some(Utc::now() - chrono::Duration::hours(12)),
+            ..Default::default()
+        };
+        assert_eq!(
+            inputs(memory.query(&today)),
+            ["find report.pdf", "open firefox", "close chrome"]
+        );
+    }
+
+    #[test]
+    fn test_query_filters_combined() {
+        let memory = seeded_memory();
+
+        let recent_failed_launches = MemoryQuery {
+            intent: Some(IntentType::LaunchApp),
+            success: Some(false),
+            since: Some(Utc::now() - chrono::Duration::hours(12)),
+            ..Default::default()
+        };
+        assert_eq!(
+            inputs(memory.query(&recent_failed_launches)),
+            ["open firefox"]
+        );
+
+        let successful_chrome = MemoryQuery {
+            text: Some("chrome".to_string()),
+            success: Some(true),
+            ..Default::default()
+        };
+        assert_eq!(inputs(memory.query(&successful_chrome)), ["open chrome"]);
+
+        let nothing = MemoryQuery {
+            text: Some("chrome".to_string()),
+            intent: Some(IntentType::FindFile),
+            ..Default::default()
+        };
+        assert!(memory.query(&nothing).is_empty());
+    }
 }
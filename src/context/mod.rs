@@ -11,9 +11,9 @@ use memory::ConversationMemory;
 use tracing::info;
 
 // Re-export key types
-pub use memory::ConversationEntry;
+pub use memory::{ConversationEntry, MemoryQuery};
 pub use state::{ReferenceType, StateManager};
-pub use unified::{ActionResult, CommandStats, ContextEntry, UnifiedContext};
+pub use unified::{ActionResult, CommandStats, ContextEntry, MentionList, UnifiedContext};
 
 /// Context manager that coordinates memory and state tracking
 pub struct ContextManager {
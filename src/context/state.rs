@@ -3,8 +3,13 @@
 //! Tracks active applications, windows, and file operations for contextual commands.
 
 use std::path::PathBuf;
+use std::time::{Duration, Instant};
 
 /// State manager for tracking current application state
+///
+/// With a TTL set, the active app, last file and last query expire that
+/// long after they were recorded, so "close it" doesn't reach back to
+/// something opened long ago.
 #[derive(Debug, Clone, Default)]
 pub struct StateManager {
     /// Currently active application
@@ -21,20 +26,23 @@ pub struct StateManager {
     recent_apps: Vec<String>,
     /// Maximum number of recent items to track
     max_recent: usize,
+    /// How long tracked references stay valid; `None` keeps them forever
+    ttl: Option<Duration>,
+    /// When the active app was set
+    active_app_at: Option<Instant>,
+    /// When the last file was opened
+    last_opened_file_at: Option<Instant>,
+    /// When the last query was made
+    last_query_at: Option<Instant>,
+    /// Time skipped ahead by tests
+    #[cfg(test)]
+    clock_offset: Duration,
 }
 
 impl StateManager {
     /// Create a new state manager with default capacity
     pub fn new() -> Self {
-        Self {
-            active_app: None,
-            open_windows: Vec::new(),
-            last_opened_file: None,
-            last_query: None,
-            recent_files: Vec::new(),
-            recent_apps: Vec::new(),
-            max_recent: 10,
-        }
+        Self::with_capacity(10)
     }
 
     /// Create a state manager with specified capacity for recent items
@@ -47,12 +55,25 @@ impl StateManager {
             recent_files: Vec::with_capacity(max_recent),
             recent_apps: Vec::with_capacity(max_recent),
             max_recent,
+            ttl: None,
+            active_app_at: None,
+            last_opened_file_at: None,
+            last_query_at: None,
+            #[cfg(test)]
+            clock_offset: Duration::ZERO,
         }
     }
 
+    /// Expire the active app, last file and last query after `ttl`
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
     /// Set the currently active application
     pub fn set_active_app(&mut self, app_name: String) {
         self.active_app = Some(app_name.clone());
+        self.active_app_at = Some(self.now());
         self.add_recent_app(app_name);
     }
 
@@ -61,9 +82,11 @@ impl StateManager {
         self.active_app = None;
     }
 
-    /// Get the currently active application
+    /// Get the currently active application, unless it has expired
     pub fn get_active_app(&self) -> Option<&str> {
-        self.active_app.as_deref()
+        self.active_app
+            .as_deref()
+            .filter(|_| self.is_fresh(self.active_app_at))
     }
 
     /// Add a window to the list of open windows
@@ -91,22 +114,55 @@ impl StateManager {
     /// Set the last opened file
     pub fn set_last_opened_file(&mut self, file_path: PathBuf) {
         self.last_opened_file = Some(file_path.clone());
+        self.last_opened_file_at = Some(self.now());
         self.add_recent_file(file_path);
     }
 
-    /// Get the last opened file
+    /// Get the last opened file, unless it has expired
     pub fn get_last_opened_file(&self) -> Option<&PathBuf> {
-        self.last_opened_file.as_ref()
+        self.last_opened_file
+            .as_ref()
+            .filter(|_| self.is_fresh(self.last_opened_file_at))
     }
 
     /// Set the last search query
     pub fn set_last_query(&mut self, query: String) {
         self.last_query = Some(query);
+        self.last_query_at = Some(self.now());
     }
 
-    /// Get the last search query
+    /// Get the last search query, unless it has expired
     pub fn get_last_query(&self) -> Option<&str> {
-        self.last_query.as_deref()
+        self.last_query
+            .as_deref()
+            .filter(|_| self.is_fresh(self.last_query_at))
+    }
+
+    /// Whether a reference recorded at `at` is still within the TTL
+    ///
+    /// References assigned directly through the public fields have no
+    /// timestamp and never expire.
+    fn is_fresh(&self, at: Option<Instant>) -> bool {
+        match (self.ttl, at) {
+            (Some(ttl), Some(at)) => self.now().saturating_duration_since(at) < ttl,
+            _ => true,
+        }
+    }
+
+    #[cfg(not(test))]
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    #[cfg(test)]
+    fn now(&self) -> Instant {
+        Instant::now() + self.clock_offset
+    }
+
+    /// Move the clock forward without sleeping
+    #[cfg(test)]
+    fn advance_clock(&mut self, by: Duration) {
+        self.clock_offset += by;
     }
 
     /// Add a file to recent files list
@@ -150,12 +206,11 @@ impl StateManager {
     /// Resolve contextual reference (e.g., "it", "that file")
     pub fn resolve_reference(&self, reference_type: ReferenceType) -> Option<String> {
         match reference_type {
-            ReferenceType::App => self.active_app.clone(),
+            ReferenceType::App => self.get_active_app().map(str::to_string),
             ReferenceType::File => self
-                .last_opened_file
-                .as_ref()
+                .get_last_opened_file()
                 .map(|p| p.to_string_lossy().to_string()),
-            ReferenceType::Query => self.last_query.clone(),
+            ReferenceType::Query => self.get_last_query().map(str::to_string),
             ReferenceType::Window => self.open_windows.last().cloned(),
         }
     }
@@ -174,7 +229,7 @@ impl StateManager {
     pub fn summary(&self) -> String {
         let mut parts = Vec::new();
 
-        if let Some(app) = &self.active_app {
+        if let Some(app) = self.get_active_app() {
             parts.push(format!("Active app: {}", app));
         }
 
@@ -182,7 +237,7 @@ impl StateManager {
             parts.push(format!("{} open windows", self.open_windows.len()));
         }
 
-        if let Some(file) = &self.last_opened_file {
+        if let Some(file) = self.get_last_opened_file() {
             parts.push(format!("Last file: {}", file.display()));
         }
 
@@ -226,6 +281,41 @@ mod tests {
         assert!(state.get_active_app().is_none());
     }
 
+    #[test]
+    fn test_references_expire_after_ttl() {
+        let mut state = StateManager::new().with_ttl(Duration::from_secs(120));
+        state.set_active_app("Chrome".to_string());
+        state.set_last_opened_file(PathBuf::from("/tmp/notes.txt"));
+        state.set_last_query("rust lifetimes".to_string());
+
+        state.advance_clock(Duration::from_secs(90));
+        state.set_last_query("rust traits".to_string());
+        assert_eq!(state.get_active_app(), Some("Chrome"));
+
+        // App and file were set 121s ago, the query only 31s ago
+        state.advance_clock(Duration::from_secs(31));
+        assert_eq!(state.get_active_app(), None);
+        assert_eq!(state.get_last_opened_file(), None);
+        assert_eq!(state.resolve_reference(ReferenceType::App), None);
+        assert_eq!(state.get_last_query(), Some("rust traits"));
+
+        state.advance_clock(Duration::from_secs(90));
+        assert_eq!(state.get_last_query(), None);
+
+        // Setting again refreshes the reference
+        state.set_active_app("Firefox".to_string());
+        assert_eq!(state.get_active_app(), Some("Firefox"));
+    }
+
+    #[test]
+    fn test_no_ttl_keeps_references() {
+        let mut state = StateManager::new();
+        state.set_active_app("Chrome".to_string());
+
+        state.advance_clock(Duration::from_secs(24 * 3600));
+        assert_eq!(state.get_active_app(), Some("Chrome"));
+    }
+
     #[test]
     fn test_windows() {
         let mut state = StateManager::new();
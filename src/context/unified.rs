@@ -6,12 +6,22 @@ use crate::brain::command_parser::{IntentType, ParsedCommand};
 use crate::brain::types::Entity;
 use crate::context::memory::ConversationEntry;
 use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, VecDeque};
+use std::ops::Range;
 use std::path::PathBuf;
 use tokio::fs;
 use tracing::{debug, error, info, warn};
 
+/// Most mentions remembered for ordinal references
+const MAX_MENTIONS: usize = 8;
+
+/// "the first one", "the last one", "the other one"
+static ORDINAL_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)\bthe\s+(first|second|third|last|other)\s+one\b").unwrap());
+
 /// Unified context that combines both memory systems
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UnifiedContext {
@@ -23,6 +33,9 @@ pub struct UnifiedContext {
     pub stats: HashMap<String, CommandStats>,
     /// Maximum history size
     pub max_size: usize,
+    /// Recently mentioned apps, files and folders in mention order
+    #[serde(default)]
+    pub mentions: MentionList,
     /// Persistence path
     #[serde(skip)]
     pub persist_path: Option<PathBuf>,
@@ -58,6 +71,98 @@ pub enum ActionResult {
     Unknown,
 }
 
+/// Recently mentioned entities for ordinal references
+///
+/// Entities recorded together form a group ("open chrome and firefox").
+/// "The first one" indexes the latest group with more than one mention, and
+/// "the other one" is the latest mention of the same kind that isn't the
+/// one referred to last.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MentionList {
+    entries: VecDeque<(u64, Entity)>,
+    next_group: u64,
+    focus: Option<Entity>,
+}
+
+impl MentionList {
+    /// Record entities mentioned by one command, in spoken order
+    ///
+    /// Entities already in the list keep their position and only become
+    /// the focus.
+    pub fn record(&mut self, mentions: impl IntoIterator<Item = Entity>) {
+        let group = self.next_group;
+        self.next_group += 1;
+
+        for entity in mentions {
+            if !self.entries.iter().any(|(_, e)| *e == entity) {
+                if self.entries.len() >= MAX_MENTIONS {
+                    self.entries.pop_front();
+                }
+                self.entries.push_back((group, entity.clone()));
+            }
+            self.focus = Some(entity);
+        }
+    }
+
+    /// Resolve the ordinal reference in `text`, if any
+    pub fn resolve(&self, text: &str) -> Option<Entity> {
+        let caps = ORDINAL_REGEX.captures(text)?;
+        let ordinal = caps[1].to_lowercase();
+        if ordinal == "other" {
+            let focus = self.focus.as_ref()?;
+            return self
+                .entries
+                .iter()
+                .rev()
+                .map(|(_, e)| e)
+                .find(|e| {
+                    *e != focus && std::mem::discriminant(*e) == std::mem::discriminant(focus)
+                })
+                .cloned();
+        }
+
+        let candidates = self.ordinal_candidates();
+        let entity = match ordinal.as_str() {
+            "first" => candidates.first(),
+            "second" => candidates.get(1),
+            "third" => candidates.get(2),
+            _ => candidates.last(),
+        };
+        entity.map(|e| (*e).clone())
+    }
+
+    /// Mentioned entities, oldest first
+    pub fn entries(&self) -> impl Iterator<Item = &Entity> {
+        self.entries.iter().map(|(_, e)| e)
+    }
+
+    /// Forget all mentions
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.focus = None;
+    }
+
+    fn ordinal_candidates(&self) -> Vec<&Entity> {
+        let latest_list = self
+            .entries
+            .iter()
+            .rev()
+            .map(|(g, _)| *g)
+            .find(|group| self.entries.iter().filter(|(g, _)| g == group).count() > 1);
+
+        self.entries
+            .iter()
+            .filter(|(g, _)| latest_list.is_none_or(|group| *g == group))
+            .map(|(_, e)| e)
+            .collect()
+    }
+}
+
+/// Byte range of an ordinal reference like "the first one" in `text`
+pub fn ordinal_span(text: &str) -> Option<Range<usize>> {
+    ORDINAL_REGEX.find(text).map(|m| m.range())
+}
+
 /// Command execution statistics
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct CommandStats {
@@ -100,6 +205,7 @@ impl UnifiedContext {
             entities: HashMap::new(),
             stats: HashMap::new(),
             max_size,
+            mentions: MentionList::default(),
             persist_path: None,
         }
     }
@@ -118,6 +224,16 @@ impl UnifiedContext {
             self.entities.insert(key.clone(), entity.clone());
         }
 
+        // Keys are unordered, so sort for a stable mention order
+        let mut mentioned: Vec<_> = entry
+            .entities
+            .iter()
+            .filter(|(_, e)| matches!(e, Entity::App(_) | Entity::File(_) | Entity::Folder(_)))
+            .collect();
+        mentioned.sort_by(|a, b| a.0.cmp(b.0));
+        self.mentions
+            .record(mentioned.into_iter().map(|(_, e)| e.clone()));
+
         // Update stats
         let normalized = Self::normalize_text(&entry.user_input);
         let stats = self
@@ -176,6 +292,11 @@ impl UnifiedContext {
         self.entities.get(reference_type)
     }
 
+    /// Resolve "the first one", "the other one" and similar to an entity
+    pub fn resolve_ordinal(&self, text: &str) -> Option<Entity> {
+        self.mentions.resolve(text)
+    }
+
     /// Get command statistics
     pub fn get_stats(&self, text: &str) -> Option<&CommandStats> {
         let normalized = Self::normalize_text(text);
@@ -206,6 +327,7 @@ impl UnifiedContext {
     pub fn clear(&mut self) {
         self.history.clear();
         self.entities.clear();
+        self.mentions.clear();
         // Keep stats for learning
     }
 
@@ -364,6 +486,50 @@ mod tests {
         assert!(resolved.is_some());
     }
 
+    fn apps(names: &[&str]) -> Vec<Entity> {
+        names.iter().map(|n| Entity::App(n.to_string())).collect()
+    }
+
+    #[test]
+    fn test_resolve_ordinal() {
+        let mut ctx = UnifiedContext::new(10);
+        ctx.mentions.record(apps(&["slack"]));
+        ctx.mentions.record(apps(&["chrome", "firefox", "code"]));
+
+        let app = |name: &str| Some(Entity::App(name.to_string()));
+        assert_eq!(ctx.resolve_ordinal("close the first one"), app("chrome"));
+        assert_eq!(ctx.resolve_ordinal("close The Second One"), app("firefox"));
+        assert_eq!(ctx.resolve_ordinal("close the last one"), app("code"));
+        assert_eq!(ctx.resolve_ordinal("close the first tab"), None);
+
+        // A single later mention doesn't reset the list being indexed
+        ctx.mentions.record(apps(&["terminal"]));
+        assert_eq!(ctx.resolve_ordinal("close the first one"), app("chrome"));
+
+        ctx.clear();
+        assert_eq!(ctx.resolve_ordinal("close the first one"), None);
+    }
+
+    #[test]
+    fn test_resolve_other_one() {
+        let mut ctx = UnifiedContext::new(10);
+        ctx.mentions.record(apps(&["chrome", "firefox"]));
+        ctx.mentions.record([Entity::File("notes.txt".to_string())]);
+        assert_eq!(
+            ctx.resolve_ordinal("open the other one"),
+            None,
+            "no other file was mentioned"
+        );
+
+        // "close chrome" refers back to chrome, so the other one is firefox
+        ctx.mentions.record(apps(&["chrome"]));
+        assert_eq!(
+            ctx.resolve_ordinal("close the other one"),
+            Some(Entity::App("firefox".to_string()))
+        );
+        assert_eq!(ordinal_span("now close the other one please"), Some(10..23));
+    }
+
     #[test]
     fn test_stats_tracking() {
         let mut ctx = UnifiedContext::new(10);
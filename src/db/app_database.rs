@@ -76,12 +76,50 @@ impl AppDatabase {
     }
 
     /// Find application by exact name
+    ///
+    /// When several apps share a name or alias, the most used one wins.
     pub fn find_by_name(&self, name: &str) -> Option<&Application> {
         let normalized = string_matching::normalize(name);
-        self.index
-            .get(&normalized)
-            .and_then(|indices| indices.first())
-            .and_then(|&idx| self.apps.get(idx))
+        self.index.get(&normalized).and_then(|indices| {
+            indices
+                .iter()
+                .rev()
+                .filter_map(|&idx| self.apps.get(idx))
+                .max_by_key(|app| usage_rank(app))
+        })
+    }
+
+    /// Record a successful launch of the app with the given id
+    ///
+    /// Returns `false` if no app has that id.
+    pub fn record_launch(&mut self, id: &str) -> bool {
+        match self.apps.iter_mut().find(|app| app.id == id) {
+            Some(app) => {
+                app.launch_count = app.launch_count.saturating_add(1);
+                app.last_used = Some(chrono::Utc::now().timestamp());
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Copy launch history from a previous database, matching apps by id
+    ///
+    /// Used when rediscovering applications so usage survives a rebuild.
+    pub fn merge_usage(&mut self, previous: &AppDatabase) {
+        let usage: HashMap<&str, &Application> = previous
+            .apps
+            .iter()
+            .filter(|app| app.launch_count > 0)
+            .map(|app| (app.id.as_str(), app))
+            .collect();
+
+        for app in &mut self.apps {
+            if let Some(old) = usage.get(app.id.as_str()) {
+                app.launch_count = old.launch_count;
+                app.last_used = old.last_used;
+            }
+        }
     }
 
     /// Find applications by category
@@ -92,6 +130,15 @@ impl AppDatabase {
             .collect()
     }
 
+    /// Find applications in a category, most used first
+    ///
+    /// Apps with the same usage keep the order they were added in.
+    pub fn apps_in_category(&self, category: AppCategory) -> Vec<&Application> {
+        let mut apps = self.find_by_category(category);
+        apps.sort_by_key(|app| std::cmp::Reverse(usage_rank(app)));
+        apps
+    }
+
     /// Search for applications with fuzzy matching
     pub fn search(&self, query: &str) -> Vec<&Application> {
         let mut results: Vec<(&Application, f32)> = self
@@ -113,9 +160,37 @@ impl AppDatabase {
         results.into_iter().map(|(app, _)| app).collect()
     }
 
+    /// Find the closest application to a possibly misspelled or partial name
+    ///
+    /// Scores the name and every alias, returning the best app with a
+    /// confidence in `0.0..=1.0`. Ties go to the most launched app, then the
+    /// most recently used, then the earlier one.
+    pub fn find_best_match(&self, query: &str) -> Option<(Application, f32)> {
+        let mut best: Option<(&Application, f32)> = None;
+        for app in &self.apps {
+            let score = self.calculate_match_score(app, query);
+            let better = best.is_none_or(|(current, best_score)| {
+                if (score - best_score).abs() <= f32::EPSILON {
+                    usage_rank(app) > usage_rank(current)
+                } else {
+                    score > best_score
+                }
+            });
+            if better {
+                best = Some((app, score));
+            }
+        }
+
+        best.filter(|(_, score)| *score > 0.0)
+            .map(|(app, score)| (app.clone(), score))
+    }
+
     /// Calculate match score for an application
     fn calculate_match_score(&self, app: &Application, query: &str) -> f32 {
         let query = string_matching::normalize(query);
+        if query.is_empty() {
+            return 0.0;
+        }
 
         // Exact match gets highest score
         if string_matching::normalize(&app.name) == query {
@@ -140,7 +215,13 @@ impl AppDatabase {
             .max_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
             .unwrap_or(0.0);
 
-        name_score.max(alias_score)
+        // Partial names ("visual studio", "chrom") score by how much they cover
+        let partial_score = std::iter::once(&app.name)
+            .chain(&app.aliases)
+            .map(|candidate| partial_match_score(&string_matching::normalize(candidate), &query))
+            .fold(0.0, f32::max);
+
+        name_score.max(alias_score).max(partial_score)
     }
 
     /// Rebuild the search index
@@ -174,6 +255,31 @@ impl AppDatabase {
     }
 }
 
+/// Ordering key for usage tiebreaks: launch count, then recency
+fn usage_rank(app: &Application) -> (u32, i64) {
+    (app.launch_count, app.last_used.unwrap_or(0))
+}
+
+/// Score a query that appears inside a name starting at a word boundary
+///
+/// Prefixes ("chrom" in "chrome") score higher than later words ("code" in
+/// "visual studio code"), and both grow with the share of the name covered.
+fn partial_match_score(name: &str, query: &str) -> f32 {
+    // Very short fragments match too many names
+    if query.len() < 3 || name.len() <= query.len() {
+        return 0.0;
+    }
+
+    let coverage = query.len() as f32 / name.len() as f32;
+    if name.starts_with(query) {
+        0.8 + 0.15 * coverage
+    } else if name.contains(&format!(" {}", query)) {
+        0.75 + 0.15 * coverage
+    } else {
+        0.0
+    }
+}
+
 impl Default for AppDatabase {
     fn default() -> Self {
         Self::new()
@@ -221,6 +327,110 @@ mod tests {
         assert!(!results.is_empty());
     }
 
+    fn editors_db() -> AppDatabase {
+        let mut db = AppDatabase::new();
+        let mut code = Application::new(
+            "Visual Studio Code".to_string(),
+            PathBuf::from("/usr/bin/code"),
+        );
+        code.add_alias("vscode".to_string());
+        db.add_app(code);
+        db.add_app(Application::new(
+            "Chrome".to_string(),
+            PathBuf::from("/usr/bin/chrome"),
+        ));
+        db.add_app(Application::new(
+            "Firefox".to_string(),
+            PathBuf::from("/usr/bin/firefox"),
+        ));
+        db
+    }
+
+    #[test]
+    fn test_best_match_typos() {
+        let db = editors_db();
+
+        let (app, score) = db.find_best_match("chrom").unwrap();
+        assert_eq!(app.name, "Chrome");
+        assert!(score > 0.8);
+
+        let (app, score) = db.find_best_match("firefx").unwrap();
+        assert_eq!(app.name, "Firefox");
+        assert!(score > 0.8);
+
+        let (app, score) = db.find_best_match("vs code").unwrap();
+        assert_eq!(app.name, "Visual Studio Code");
+        assert!(score > 0.75);
+    }
+
+    #[test]
+    fn test_best_match_partial_names() {
+        let db = editors_db();
+
+        let (app, score) = db.find_best_match("visual studio").unwrap();
+        assert_eq!(app.name, "Visual Studio Code");
+        assert!(score > 0.8);
+
+        let (app, _) = db.find_best_match("studio code").unwrap();
+        assert_eq!(app.name, "Visual Studio Code");
+
+        // Unrelated names stay well below a launchable score
+        let score = db.find_best_match("spreadsheet").map_or(0.0, |(_, s)| s);
+        assert!(score < 0.5);
+        assert!(AppDatabase::new().find_best_match("chrome").is_none());
+    }
+
+    #[test]
+    fn test_usage_breaks_ties() {
+        let mut db = AppDatabase::new();
+        let mut chrome = Application::new("Chrome".to_string(), PathBuf::from("/usr/bin/chrome"));
+        chrome.add_alias("browser".to_string());
+        let mut firefox =
+            Application::new("Firefox".to_string(), PathBuf::from("/usr/bin/firefox"));
+        firefox.add_alias("browser".to_string());
+        let firefox_id = firefox.id.clone();
+        db.add_app(chrome);
+        db.add_app(firefox);
+
+        // Without history the earlier app wins
+        assert_eq!(db.find_best_match("browser").unwrap().0.name, "Chrome");
+        assert_eq!(db.find_by_name("browser").unwrap().name, "Chrome");
+
+        assert!(db.record_launch(&firefox_id));
+        assert!(!db.record_launch("missing"));
+
+        let (app, score) = db.find_best_match("browser").unwrap();
+        assert_eq!(app.name, "Firefox");
+        assert_eq!(app.launch_count, 1);
+        assert!(app.last_used.is_some());
+        assert!((score - 0.95).abs() < f32::EPSILON);
+        assert_eq!(db.find_by_name("browser").unwrap().name, "Firefox");
+
+        // A better score still beats usage
+        assert_eq!(db.find_best_match("chrome").unwrap().0.name, "Chrome");
+    }
+
+    #[tokio::test]
+    async fn test_usage_persists() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app_database.json");
+
+        let mut db = editors_db();
+        let id = db.find_by_name("firefox").unwrap().id.clone();
+        db.record_launch(&id);
+        db.record_launch(&id);
+        db.save_to_disk(&path).await.unwrap();
+
+        let loaded = AppDatabase::load_from_disk(&path).await.unwrap();
+        assert_eq!(loaded.find_by_name("firefox").unwrap().launch_count, 2);
+
+        // Rediscovered apps pick up the saved history
+        let mut rebuilt = editors_db();
+        rebuilt.merge_usage(&loaded);
+        assert_eq!(rebuilt.find_by_name("firefox").unwrap().launch_count, 2);
+        assert_eq!(rebuilt.find_by_name("chrome").unwrap().launch_count, 0);
+    }
+
     #[test]
     fn test_search_by_category() {
         let mut db = AppDatabase::new();
@@ -237,4 +447,26 @@ mod tests {
         let browsers = db.find_by_category(AppCategory::Browser);
         assert_eq!(browsers.len(), 2);
     }
+
+    #[test]
+    fn test_apps_in_category_most_used_first() {
+        let mut db = editors_db();
+        for app in &mut db.apps {
+            if app.name != "Visual Studio Code" {
+                app.category = AppCategory::Browser;
+            }
+        }
+        let names = |db: &AppDatabase| {
+            db.apps_in_category(AppCategory::Browser)
+                .iter()
+                .map(|app| app.name.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names(&db), ["Chrome", "Firefox"]);
+
+        let id = db.find_by_name("firefox").unwrap().id.clone();
+        db.record_launch(&id);
+        assert_eq!(names(&db), ["Firefox", "Chrome"]);
+        assert!(db.apps_in_category(AppCategory::Terminal).is_empty());
+    }
 }
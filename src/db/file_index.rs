@@ -1,12 +1,21 @@
 //! File indexing and search functionality
 //!
-//! Maintains an index of files for fast searching.
+//! Maintains an index of files for fast searching. Rebuilds are incremental:
+//! files whose modification time matches the stored entry are skipped. The
+//! index can also be kept up to date by watching the indexed directories.
 
 use crate::db::schema::{FileEntry, FileType};
 use crate::error::{LunaError, Result};
 use crate::utils::string_matching;
+use notify::event::ModifyKind;
+use notify::{Event, EventKind, RecursiveMode, Watcher};
+use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tracing::{debug, error, info};
 
 /// File index for fast file searching
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -16,6 +25,94 @@ pub struct FileIndex {
 
     /// Last update timestamp
     last_updated: i64,
+
+    /// Position of each path in `files`, rebuilt after loading
+    #[serde(skip)]
+    positions: HashMap<PathBuf, usize>,
+}
+
+/// Constraints narrowing a file search
+///
+/// Empty fields don't constrain results.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FileFilter {
+    /// Accepted file types
+    pub types: Vec<FileType>,
+    /// Accepted extensions, lowercase without the dot
+    pub extensions: Vec<String>,
+    /// Minimum size in bytes
+    pub min_size: Option<u64>,
+    /// Maximum size in bytes
+    pub max_size: Option<u64>,
+    /// Only files modified at or after this Unix timestamp
+    pub modified_after: Option<i64>,
+}
+
+impl FileFilter {
+    /// Build a filter from action parameters
+    ///
+    /// Reads `file_type`, `extension`, `min_size`, `max_size` and
+    /// `modified_after`; unparseable values are ignored.
+    pub fn from_params(params: &HashMap<String, String>) -> Self {
+        Self {
+            types: params
+                .get("file_type")
+                .and_then(|t| FileType::from_name(t))
+                .into_iter()
+                .collect(),
+            extensions: params
+                .get("extension")
+                .map(|e| e.trim_start_matches('.').to_lowercase())
+                .into_iter()
+                .collect(),
+            min_size: params.get("min_size").and_then(|s| s.parse().ok()),
+            max_size: params.get("max_size").and_then(|s| s.parse().ok()),
+            modified_after: params.get("modified_after").and_then(|s| s.parse().ok()),
+        }
+    }
+
+    /// Whether the filter places no constraints
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Whether an entry satisfies every constraint
+    pub fn matches(&self, entry: &FileEntry) -> bool {
+        let type_ok = self.types.is_empty() || self.types.contains(&entry.file_type);
+        let extension_ok = self.extensions.is_empty()
+            || entry
+                .extension
+                .as_ref()
+                .is_some_and(|ext| self.extensions.contains(&ext.to_lowercase()));
+
+        type_ok
+            && extension_ok
+            && self.min_size.is_none_or(|min| entry.size >= min)
+            && self.max_size.is_none_or(|max| entry.size <= max)
+            && self
+                .modified_after
+                .is_none_or(|after| entry.modified >= after)
+    }
+}
+
+/// Outcome of an incremental rebuild
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct IndexStats {
+    /// Files that weren't indexed before
+    pub added: usize,
+    /// Files whose modification time changed
+    pub updated: usize,
+    /// Entries whose files no longer exist
+    pub removed: usize,
+    /// Files skipped because their modification time matched
+    pub unchanged: usize,
+}
+
+impl IndexStats {
+    /// Number of entries that changed
+    pub fn changed(&self) -> usize {
+        self.added + self.updated + self.removed
+    }
 }
 
 impl FileIndex {
@@ -24,6 +121,7 @@ impl FileIndex {
         Self {
             files: Vec::new(),
             last_updated: 0,
+            positions: HashMap::new(),
         }
     }
 
@@ -33,7 +131,8 @@ impl FileIndex {
             .await
             .map_err(|e| LunaError::Database(format!("Failed to load file index: {}", e)))?;
 
-        let index: FileIndex = serde_json::from_str(&contents)?;
+        let mut index: FileIndex = serde_json::from_str(&contents)?;
+        index.rebuild_positions();
         Ok(index)
     }
 
@@ -56,9 +155,118 @@ impl FileIndex {
 
     /// Add a file to the index
     pub fn add_file(&mut self, file: FileEntry) {
+        self.positions.insert(file.path.clone(), self.files.len());
         self.files.push(file);
     }
 
+    /// Add a file, replacing any existing entry with the same path
+    ///
+    /// Returns `true` if the path was not indexed before.
+    pub fn upsert_file(&mut self, file: FileEntry) -> bool {
+        self.last_updated = chrono::Utc::now().timestamp();
+        match self.positions.get(&file.path) {
+            Some(&pos) => {
+                self.files[pos] = file;
+                false
+            }
+            None => {
+                self.add_file(file);
+                true
+            }
+        }
+    }
+
+    /// Remove a path and, if it was a directory, everything beneath it
+    ///
+    /// Returns the number of entries removed.
+    pub fn remove_path(&mut self, path: &Path) -> usize {
+        self.retain(|f| !f.path.starts_with(path))
+    }
+
+    /// Keep only entries matching a predicate, returning how many were removed
+    fn retain(&mut self, keep: impl Fn(&FileEntry) -> bool) -> usize {
+        let before = self.files.len();
+        self.files.retain(keep);
+        let removed = before - self.files.len();
+        if removed > 0 {
+            self.rebuild_positions();
+            self.last_updated = chrono::Utc::now().timestamp();
+        }
+        removed
+    }
+
+    fn rebuild_positions(&mut self) {
+        self.positions = self
+            .files
+            .iter()
+            .enumerate()
+            .map(|(pos, f)| (f.path.clone(), pos))
+            .collect();
+    }
+
+    /// Get the entry for an exact path
+    pub fn get(&self, path: &Path) -> Option<&FileEntry> {
+        self.positions.get(path).map(|&pos| &self.files[pos])
+    }
+
+    /// Whether a file is missing from the index or was modified since indexed
+    ///
+    /// `mtime` is the file's modification time in seconds since the Unix epoch.
+    pub fn needs_update(&self, path: &Path, mtime: i64) -> bool {
+        !matches!(self.get(path), Some(entry) if entry.modified == mtime)
+    }
+
+    /// Bring the index in line with the files under `roots`
+    ///
+    /// Only new and modified files are re-read; files whose modification time
+    /// matches the stored entry are skipped. Entries for files that no longer
+    /// exist under any root are removed.
+    pub fn rebuild_incremental(
+        &mut self,
+        roots: &[PathBuf],
+        exclude_paths: &[String],
+    ) -> Result<IndexStats> {
+        let mut stats = IndexStats::default();
+        let mut seen = HashSet::new();
+
+        for root in roots {
+            walk_files(root, exclude_paths, &mut |path, metadata| {
+                let mtime = modified_secs(metadata);
+                if !self.needs_update(&path, mtime) {
+                    stats.unchanged += 1;
+                    seen.insert(path);
+                    return;
+                }
+
+                if let Ok(entry) = FileEntry::from_path(path.clone()) {
+                    if self.upsert_file(entry) {
+                        stats.added += 1;
+                    } else {
+                        stats.updated += 1;
+                    }
+                    seen.insert(path);
+                }
+            })?;
+        }
+
+        stats.removed = self.retain(|f| seen.contains(&f.path));
+        Ok(stats)
+    }
+
+    /// Recursively index files under a directory, skipping excluded paths
+    ///
+    /// Unreadable subdirectories are skipped. Returns the number of files indexed.
+    pub fn index_directory(&mut self, dir: &Path, exclude_paths: &[String]) -> Result<usize> {
+        let mut count = 0;
+        walk_files(dir, exclude_paths, &mut |path, _| {
+            if let Ok(file_entry) = FileEntry::from_path(path) {
+                self.upsert_file(file_entry);
+                count += 1;
+            }
+        })?;
+        Ok(count)
+    }
+
     /// Search for files by name
     pub fn search_by_name(&self, query: &str) -> Vec<&FileEntry> {
         let mut results: Vec<(&FileEntry, f32)> = self
@@ -78,6 +286,34 @@ impl FileIndex {
         results.into_iter().map(|(file, _)| file).collect()
     }
 
+    /// Search for files matching a filter, ranked by name similarity
+    ///
+    /// An empty query returns every matching file, most recently modified first.
+    pub fn search_filtered(&self, query: &str, filter: &FileFilter) -> Vec<&FileEntry> {
+        let query = query.trim().to_lowercase();
+        let matching = self.files.iter().filter(|file| filter.matches(file));
+
+        if query.is_empty() {
+            let mut results: Vec<&FileEntry> = matching.collect();
+            results.sort_by_key(|file| std::cmp::Reverse(file.modified));
+            return results;
+        }
+
+        let mut results: Vec<(&FileEntry, f32)> = matching
+            .filter_map(|file| {
+                let score = if file.name.to_lowercase().contains(&query) {
+                    1.0
+                } else {
+                    string_matching::similarity_score(&file.name, &query)
+                };
+                (score > 0.5).then_some((file, score))
+            })
+            .collect();
+
+        results.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        results.into_iter().map(|(file, _)| file).collect()
+    }
+
     /// Search for files by type
     pub fn search_by_type(&self, file_type: FileType) -> Vec<&FileEntry> {
         self.files
@@ -94,6 +330,7 @@ impl FileIndex {
     /// Clear the index
     pub fn clear(&mut self) {
         self.files.clear();
+        self.positions.clear();
     }
 
     /// Get number of indexed files
@@ -113,6 +350,166 @@ impl Default for FileIndex {
     }
 }
 
+/// Visit every file under a directory, skipping excluded paths
+///
+/// Unreadable subdirectories are skipped.
+fn walk_files(
+    dir: &Path,
+    exclude_paths: &[String],
+    visit: &mut impl FnMut(PathBuf, &std::fs::Metadata),
+) -> Result<()> {
+    if is_excluded(dir, exclude_paths) {
+        return Ok(());
+    }
+
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return Ok(()),
+    };
+
+    for entry in entries {
+        let entry_path = entry?.path();
+        if is_excluded(&entry_path, exclude_paths) {
+            continue;
+        }
+
+        // Follows symlinks, like `Path::is_dir`
+        let Ok(metadata) = std::fs::metadata(&entry_path) else {
+            continue;
+        };
+        if metadata.is_dir() {
+            walk_files(&entry_path, exclude_paths, visit)?;
+        } else if metadata.is_file() {
+            visit(entry_path, &metadata);
+        }
+    }
+
+    Ok(())
+}
+
+/// Modification time in seconds since the Unix epoch, matching `FileEntry::modified`
+fn modified_secs(metadata: &std::fs::Metadata) -> i64 {
+    metadata
+        .modified()
+        .ok()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+/// Whether a path matches any of the configured exclude patterns
+pub fn is_excluded(path: &Path, exclude_paths: &[String]) -> bool {
+    let path_str = path.to_string_lossy();
+    exclude_paths
+        .iter()
+        .any(|ex| path_str.contains(ex.as_str()))
+}
+
+/// Handle for an active file index watcher; watching stops when dropped
+pub struct FileIndexWatcher {
+    _watcher: notify::RecommendedWatcher,
+    dirty: Arc<AtomicBool>,
+}
+
+impl FileIndexWatcher {
+    /// Watch directories and apply file changes to a shared index
+    ///
+    /// Created and modified files are (re)indexed, removed files are dropped,
+    /// and renames are handled as a removal of the old path plus an addition
+    /// of the new one. Paths matching `exclude_paths` are ignored.
+    pub fn start(
+        index: Arc<RwLock<FileIndex>>,
+        paths: &[PathBuf],
+        exclude_paths: Vec<String>,
+    ) -> Result<Self> {
+        let (tx, rx) = std::sync::mpsc::channel::<Event>();
+        let mut watcher =
+            notify::recommended_watcher(move |res: notify::Result<Event>| match res {
+                Ok(event) if !event.kind.is_access() => {
+                    let _ = tx.send(event);
+                }
+                Ok(_) => {}
+                Err(e) => error!("File index watch error: {:?}", e),
+            })
+            .map_err(|e| LunaError::Database(format!("Failed to create file watcher: {}", e)))?;
+
+        for path in paths {
+            watcher.watch(path, RecursiveMode::Recursive).map_err(|e| {
+                LunaError::Database(format!("Failed to watch {}: {}", path.display(), e))
+            })?;
+            info!("Watching for file changes: {}", path.display());
+        }
+
+        let dirty = Arc::new(AtomicBool::new(false));
+        let dirty_flag = Arc::clone(&dirty);
+
+        // Exits once the watcher (and with it the sender) is dropped
+        std::thread::spawn(move || {
+            while let Ok(event) = rx.recv() {
+                // Only scan directories that newly appeared, not ones whose
+                // contents or metadata changed
+                let new_dirs = matches!(
+                    event.kind,
+                    EventKind::Create(_) | EventKind::Modify(ModifyKind::Name(_))
+                );
+
+                let mut index = index.write();
+                for path in &event.paths {
+                    if is_excluded(path, &exclude_paths) {
+                        continue;
+                    }
+                    if apply_change(&mut index, path, &exclude_paths, new_dirs) {
+                        dirty_flag.store(true, Ordering::SeqCst);
+                    }
+                }
+            }
+        });
+
+        Ok(Self {
+            _watcher: watcher,
+            dirty,
+        })
+    }
+
+    /// Whether the index changed since the last call
+    pub fn take_dirty(&self) -> bool {
+        self.dirty.swap(false, Ordering::SeqCst)
+    }
+}
+
+/// Bring the index in line with the current state of a path on disk
+///
+/// Returns `true` if the index changed.
+fn apply_change(
+    index: &mut FileIndex,
+    path: &Path,
+    exclude_paths: &[String],
+    scan_dirs: bool,
+) -> bool {
+    if path.is_file() {
+        match FileEntry::from_path(path.to_path_buf()) {
+            Ok(entry) => {
+                debug!("Indexed {}", path.display());
+                index.upsert_file(entry);
+                true
+            }
+            Err(_) => false,
+        }
+    } else if path.is_dir() {
+        // A directory moved into a watched tree brings its files with it
+        scan_dirs
+            && index
+                .index_directory(path, exclude_paths)
+                .is_ok_and(|count| count > 0)
+    } else {
+        let removed = index.remove_path(path);
+        if removed > 0 {
+            debug!("Removed {} entries under {}", removed, path.display());
+        }
+        removed > 0
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -142,4 +539,199 @@ mod tests {
         let results = index.search_by_name("document");
         assert_eq!(results.len(), 1);
     }
+
+    fn entry(name: &str, size: u64, modified: i64) -> FileEntry {
+        let extension = Path::new(name)
+            .extension()
+            .map(|e| e.to_string_lossy().to_string());
+        FileEntry {
+            path: PathBuf::from("/home/user").join(name),
+            name: name.to_string(),
+            file_type: FileType::from_extension(extension.as_deref()),
+            extension,
+            size,
+            modified,
+        }
+    }
+
+    #[test]
+    fn test_search_filtered_by_type_and_size() {
+        let mut index = FileIndex::new();
+        index.add_file(entry("taxes_2023.pdf", 200_000, 100));
+        index.add_file(entry("taxes_notes.txt", 2_000, 300));
+        index.add_file(entry("holiday.mp4", 3_000_000_000, 200));
+        index.add_file(entry("clip.mp4", 50_000_000, 400));
+
+        let pdfs = FileFilter {
+            extensions: vec!["pdf".to_string()],
+            ..Default::default()
+        };
+        let results = index.search_filtered("taxes", &pdfs);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].name, "taxes_2023.pdf");
+        assert_eq!(
+            index.search_filtered("taxes", &FileFilter::default()).len(),
+            2
+        );
+
+        let big_videos = FileFilter {
+            types: vec![FileType::Video],
+            min_size: Some(1024 * 1024 * 1024),
+            ..Default::default()
+        };
+        let results = index.search_filtered("", &big_videos);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].name, "holiday.mp4");
+
+        // Empty query lists matches newest first
+        let recent = FileFilter {
+            modified_after: Some(200),
+            ..Default::default()
+        };
+        let names: Vec<_> = index
+            .search_filtered("", &recent)
+            .iter()
+            .map(|f| f.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["clip.mp4", "taxes_notes.txt", "holiday.mp4"]);
+    }
+
+    #[test]
+    fn test_filter_from_params() {
+        let params: HashMap<String, String> = [
+            ("file_type", "video"),
+            ("min_size", "1024"),
+            ("max_size", "not a number"),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+
+        let filter = FileFilter::from_params(&params);
+        assert_eq!(filter.types, vec![FileType::Video]);
+        assert_eq!(filter.min_size, Some(1024));
+        assert_eq!(filter.max_size, None);
+        assert!(FileFilter::from_params(&HashMap::new()).is_empty());
+    }
+
+    #[test]
+    fn test_upsert_and_remove() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("sub")).unwrap();
+        std::fs::write(dir.path().join("a.txt"), b"a").unwrap();
+        std::fs::write(dir.path().join("sub/b.rs"), b"b").unwrap();
+        std::fs::create_dir(dir.path().join("target")).unwrap();
+        std::fs::write(dir.path().join("target/c.o"), b"c").unwrap();
+
+        let mut index = FileIndex::new();
+        let count = index
+            .index_directory(dir.path(), &["target".to_string()])
+            .unwrap();
+        assert_eq!(count, 2);
+
+        let entry = FileEntry::from_path(dir.path().join("a.txt")).unwrap();
+        assert!(!index.upsert_file(entry));
+        assert_eq!(index.len(), 2);
+
+        assert_eq!(index.remove_path(&dir.path().join("sub")), 1);
+        assert!(index.get(&dir.path().join("a.txt")).is_some());
+        assert_eq!(index.len(), 1);
+    }
+
+    #[test]
+    fn test_incremental_rebuild_skips_unchanged() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in ["a.txt", "b.txt", "c.txt"] {
+            std::fs::write(dir.path().join(name), name).unwrap();
+        }
+        let roots = vec![dir.path().to_path_buf()];
+
+        let mut index = FileIndex::new();
+        let stats = index.rebuild_incremental(&roots, &[]).unwrap();
+        assert_eq!(stats.added, 3);
+
+        // Nothing changed: every file is skipped
+        let stats = index.rebuild_incremental(&roots, &[]).unwrap();
+        assert_eq!(
+            stats,
+            IndexStats {
+                unchanged: 3,
+                ..Default::default()
+            }
+        );
+        assert_eq!(stats.changed(), 0);
+
+        // One modified, one removed, one new
+        let file = std::fs::File::options()
+            .write(true)
+            .open(dir.path().join("a.txt"))
+            .unwrap();
+        file.set_modified(std::time::SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(60))
+            .unwrap();
+        std::fs::remove_file(dir.path().join("b.txt")).unwrap();
+        std::fs::write(dir.path().join("d.txt"), b"d").unwrap();
+
+        let stats = index.rebuild_incremental(&roots, &[]).unwrap();
+        assert_eq!(
+            stats,
+            IndexStats {
+                added: 1,
+                updated: 1,
+                removed: 1,
+                unchanged: 1,
+            }
+        );
+        assert_eq!(index.len(), 3);
+        assert_eq!(index.get(&dir.path().join("a.txt")).unwrap().modified, 60);
+    }
+
+    #[tokio::test]
+    async fn test_positions_restored_after_load() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.txt"), b"a").unwrap();
+        let mut index = FileIndex::new();
+        index.index_directory(dir.path(), &[]).unwrap();
+
+        let path = dir.path().join("index.json");
+        index.save_to_disk(&path).await.unwrap();
+        let loaded = FileIndex::load_from_disk(&path).await.unwrap();
+
+        let mtime = loaded.get(&dir.path().join("a.txt")).unwrap().modified;
+        assert!(!loaded.needs_update(&dir.path().join("a.txt"), mtime));
+        assert!(loaded.needs_update(&dir.path().join("a.txt"), mtime + 1));
+        assert!(loaded.needs_update(&dir.path().join("missing.txt"), mtime));
+    }
+
+    #[test]
+    fn test_watcher_tracks_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let index = Arc::new(RwLock::new(FileIndex::new()));
+        let watcher =
+            FileIndexWatcher::start(Arc::clone(&index), &[dir.path().to_path_buf()], vec![])
+                .unwrap();
+
+        let wait_for = |present: bool, path: &Path| {
+            for _ in 0..100 {
+                if index.read().get(path).is_some() == present {
+                    return;
+                }
+                std::thread::sleep(std::time::Duration::from_millis(20));
+            }
+            panic!("index did not update for {}", path.display());
+        };
+
+        let file = dir.path().join("notes.md");
+        std::fs::write(&file, b"hello").unwrap();
+        wait_for(true, &file);
+        assert!(watcher.take_dirty());
+
+        let renamed = dir.path().join("renamed.md");
+        std::fs::rename(&file, &renamed).unwrap();
+        wait_for(true, &renamed);
+        wait_for(false, &file);
+
+        std::fs::remove_file(&renamed).unwrap();
+        wait_for(false, &renamed);
+        assert!(index.read().is_empty());
+    }
 }
@@ -7,5 +7,5 @@ pub mod file_index;
 pub mod schema;
 
 pub use app_database::AppDatabase;
-pub use file_index::FileIndex;
+pub use file_index::{FileFilter, FileIndex, IndexStats};
 pub use schema::*;
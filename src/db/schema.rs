@@ -17,6 +17,10 @@ pub struct Application {
     /// Path to the executable file
     pub executable: PathBuf,
 
+    /// Arguments always passed to the executable
+    #[serde(default)]
+    pub launch_args: Vec<String>,
+
     /// Alternative names and common misspellings
     pub aliases: Vec<String>,
 
@@ -34,10 +38,18 @@ pub struct Application {
 
     /// Installation date (ISO 8601 format)
     pub install_date: Option<String>,
+
+    /// Number of successful launches through LUNA
+    #[serde(default)]
+    pub launch_count: u32,
+
+    /// Unix timestamp of the most recent launch
+    #[serde(default)]
+    pub last_used: Option<i64>,
 }
 
 /// Categories for organizing applications
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum AppCategory {
     /// Web browsers (Chrome, Firefox, etc.)
     Browser,
@@ -86,12 +98,15 @@ impl Application {
             id,
             name,
             executable,
+            launch_args: Vec::new(),
             aliases: Vec::new(),
             category: AppCategory::Other,
             icon: None,
             description: None,
             version: None,
             install_date: None,
+            launch_count: 0,
+            last_used: None,
         }
     }
 
@@ -204,11 +219,67 @@ impl FileEntry {
     }
 }
 
+impl AppCategory {
+    /// Parse a spoken category such as "browser" or "my text editor"
+    pub fn from_spoken(phrase: &str) -> Option<Self> {
+        let phrase = phrase.trim().to_lowercase();
+        let phrase = ["my ", "a ", "an ", "the ", "default "]
+            .iter()
+            .fold(phrase.as_str(), |rest, prefix| {
+                rest.strip_prefix(prefix).unwrap_or(rest)
+            });
+
+        match phrase {
+            "browser" | "web browser" => Some(AppCategory::Browser),
+            "ide" => Some(AppCategory::IDE),
+            "editor" | "text editor" | "code editor" => Some(AppCategory::TextEditor),
+            "terminal" | "terminal emulator" => Some(AppCategory::Terminal),
+            "media player" | "music player" | "video player" => Some(AppCategory::Media),
+            "chat app" | "messenger" | "email client" | "mail client" => {
+                Some(AppCategory::Communication)
+            }
+            "office suite" | "word processor" => Some(AppCategory::Office),
+            _ => None,
+        }
+    }
+
+    /// Name used when talking about the category, e.g. "Which browser?"
+    pub fn spoken_name(&self) -> &'static str {
+        match self {
+            AppCategory::Browser => "browser",
+            AppCategory::IDE => "IDE",
+            AppCategory::TextEditor => "editor",
+            AppCategory::Terminal => "terminal",
+            AppCategory::Media => "media player",
+            AppCategory::Communication => "chat app",
+            AppCategory::Office => "office app",
+            AppCategory::Games => "game",
+            AppCategory::System => "system utility",
+            AppCategory::Other => "app",
+        }
+    }
+}
+
 impl FileType {
+    /// Parse a lowercase category name such as "image" or "video"
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "document" => Some(FileType::Document),
+            "code" => Some(FileType::Code),
+            "image" => Some(FileType::Image),
+            "video" => Some(FileType::Video),
+            "audio" => Some(FileType::Audio),
+            "archive" => Some(FileType::Archive),
+            "directory" => Some(FileType::Directory),
+            "other" => Some(FileType::Other),
+            _ => None,
+        }
+    }
+
     /// Determine file type from extension
     pub fn from_extension(ext: Option<&str>) -> Self {
         match ext {
-            Some("txt") | Some("pdf") | Some("doc") | Some("docx") | Some("odt") => {
+            Some("txt") | Some("md") | Some("pdf") | Some("doc") | Some("docx") | Some("odt") => {
                 FileType::Document
             }
             Some("rs") | Some("py") | Some("js") | Some("ts") | Some("java") | Some("c")
@@ -252,4 +323,21 @@ mod tests {
         assert_eq!(FileType::from_extension(Some("mp4")), FileType::Video);
         assert_eq!(FileType::from_extension(None), FileType::Other);
     }
+
+    #[test]
+    fn test_app_category_from_spoken() {
+        assert_eq!(
+            AppCategory::from_spoken("browser"),
+            Some(AppCategory::Browser)
+        );
+        assert_eq!(
+            AppCategory::from_spoken("My Text Editor"),
+            Some(AppCategory::TextEditor)
+        );
+        assert_eq!(
+            AppCategory::from_spoken("a terminal"),
+            Some(AppCategory::Terminal)
+        );
+        assert_eq!(AppCategory::from_spoken("chrome"), None);
+    }
 }
@@ -7,19 +7,31 @@
 //! - Tracing integration
 
 use crate::error::LunaError;
-use async_channel::{bounded, unbounded, Receiver, Sender};
+use async_channel::{bounded, unbounded, Receiver, Sender, TrySendError};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::SystemTime;
-use tokio::sync::RwLock;
+use tokio::sync::{watch, RwLock};
 use tokio::task::JoinHandle;
 use uuid::Uuid;
 
 /// Events that can occur in the LUNA system (typed with real structs)
-#[derive(Debug, Clone)]
+///
+/// Serialized with a `type` tag matching `event_type()`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
 pub enum LunaEvent {
     /// Raw audio captured from microphone
-    AudioCaptured { samples: Vec<f32>, timestamp: u64 },
+    ///
+    /// Samples are not serialized to keep recordings small; they
+    /// deserialize as empty.
+    AudioCaptured {
+        #[serde(skip)]
+        samples: Vec<f32>,
+        timestamp: u64,
+    },
 
     /// Wake word detected
     WakeWordDetected { keyword: String, confidence: f32 },
@@ -64,7 +76,14 @@ pub enum LunaEvent {
     StateChanged { from: String, to: String },
 
     /// Configuration reloaded
-    ConfigReloaded { timestamp: u64 },
+    ///
+    /// `changed_fields` lists the dotted paths that changed, e.g.
+    /// "audio.sample_rate".
+    ConfigReloaded {
+        timestamp: u64,
+        #[serde(default)]
+        changed_fields: Vec<String>,
+    },
 
     /// Metrics snapshot
     MetricsSnapshot {
@@ -167,39 +186,185 @@ pub enum LunaEvent {
 impl LunaEvent {
     /// Get the event type as a string
     pub fn event_type(&self) -> &'static str {
+        self.kind().as_str()
+    }
+
+    /// Get the payload-free kind of this event
+    pub fn kind(&self) -> EventKind {
+        match self {
+            Self::AudioCaptured { .. } => EventKind::AudioCaptured,
+            Self::WakeWordDetected { .. } => EventKind::WakeWordDetected,
+            Self::CommandTranscribed { .. } => EventKind::CommandTranscribed,
+            Self::CommandParsed { .. } => EventKind::CommandParsed,
+            Self::TaskPlanned { .. } => EventKind::TaskPlanned,
+            Self::ActionStarted { .. } => EventKind::ActionStarted,
+            Self::ActionCompleted { .. } => EventKind::ActionCompleted,
+            Self::Error { .. } => EventKind::Error,
+            Self::StateChanged { .. } => EventKind::StateChanged,
+            Self::ConfigReloaded { .. } => EventKind::ConfigReloaded,
+            Self::MetricsSnapshot { .. } => EventKind::MetricsSnapshot,
+            Self::ClarificationRequested { .. } => EventKind::ClarificationRequested,
+            Self::ClarificationAnswered { .. } => EventKind::ClarificationAnswered,
+            Self::GrammarReloaded { .. } => EventKind::GrammarReloaded,
+            Self::CacheInvalidated { .. } => EventKind::CacheInvalidated,
+            Self::PlanStarted { .. } => EventKind::PlanStarted,
+            Self::PlanCompleted { .. } => EventKind::PlanCompleted,
+            Self::CapabilityDetected { .. } => EventKind::CapabilityDetected,
+            Self::ActionRetry { .. } => EventKind::ActionRetry,
+            Self::PolicyGateTriggered { .. } => EventKind::PolicyGateTriggered,
+            Self::TtsStarted { .. } => EventKind::TtsStarted,
+            Self::TtsCompleted { .. } => EventKind::TtsCompleted,
+            Self::TtsInterrupted { .. } => EventKind::TtsInterrupted,
+            Self::HealthIssueDetected { .. } => EventKind::HealthIssueDetected,
+            Self::HealthRemediated { .. } => EventKind::HealthRemediated,
+            Self::Custom { .. } => EventKind::Custom,
+        }
+    }
+}
+
+/// Payload-free discriminant of `LunaEvent`
+///
+/// Used to filter subscriptions without spelling event types as strings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventKind {
+    AudioCaptured,
+    WakeWordDetected,
+    CommandTranscribed,
+    CommandParsed,
+    TaskPlanned,
+    ActionStarted,
+    ActionCompleted,
+    Error,
+    StateChanged,
+    ConfigReloaded,
+    MetricsSnapshot,
+    ClarificationRequested,
+    ClarificationAnswered,
+    GrammarReloaded,
+    CacheInvalidated,
+    PlanStarted,
+    PlanCompleted,
+    CapabilityDetected,
+    ActionRetry,
+    PolicyGateTriggered,
+    TtsStarted,
+    TtsCompleted,
+    TtsInterrupted,
+    HealthIssueDetected,
+    HealthRemediated,
+    Custom,
+}
+
+impl EventKind {
+    /// Every event kind, in declaration order
+    pub const ALL: &'static [EventKind] = &[
+        Self::AudioCaptured,
+        Self::WakeWordDetected,
+        Self::CommandTranscribed,
+        Self::CommandParsed,
+        Self::TaskPlanned,
+        Self::ActionStarted,
+        Self::ActionCompleted,
+        Self::Error,
+        Self::StateChanged,
+        Self::ConfigReloaded,
+        Self::MetricsSnapshot,
+        Self::ClarificationRequested,
+        Self::ClarificationAnswered,
+        Self::GrammarReloaded,
+        Self::CacheInvalidated,
+        Self::PlanStarted,
+        Self::PlanCompleted,
+        Self::CapabilityDetected,
+        Self::ActionRetry,
+        Self::PolicyGateTriggered,
+        Self::TtsStarted,
+        Self::TtsCompleted,
+        Self::TtsInterrupted,
+        Self::HealthIssueDetected,
+        Self::HealthRemediated,
+        Self::Custom,
+    ];
+
+    /// The event type string, as returned by `LunaEvent::event_type`
+    pub fn as_str(&self) -> &'static str {
         match self {
-            Self::AudioCaptured { .. } => "audio_captured",
-            Self::WakeWordDetected { .. } => "wake_word_detected",
-            Self::CommandTranscribed { .. } => "command_transcribed",
-            Self::CommandParsed { .. } => "command_parsed",
-            Self::TaskPlanned { .. } => "task_planned",
-            Self::ActionStarted { .. } => "action_started",
-            Self::ActionCompleted { .. } => "action_completed",
-            Self::Error { .. } => "error",
-            Self::StateChanged { .. } => "state_changed",
-            Self::ConfigReloaded { .. } => "config_reloaded",
-            Self::MetricsSnapshot { .. } => "metrics_snapshot",
-            Self::ClarificationRequested { .. } => "clarification_requested",
-            Self::ClarificationAnswered { .. } => "clarification_answered",
-            Self::GrammarReloaded { .. } => "grammar_reloaded",
-            Self::CacheInvalidated { .. } => "cache_invalidated",
-            Self::PlanStarted { .. } => "plan_started",
-            Self::PlanCompleted { .. } => "plan_completed",
-            Self::CapabilityDetected { .. } => "capability_detected",
-            Self::ActionRetry { .. } => "action_retry",
-            Self::PolicyGateTriggered { .. } => "policy_gate_triggered",
-            Self::TtsStarted { .. } => "tts_started",
-            Self::TtsCompleted { .. } => "tts_completed",
-            Self::TtsInterrupted { .. } => "tts_interrupted",
-            Self::HealthIssueDetected { .. } => "health_issue_detected",
-            Self::HealthRemediated { .. } => "health_remediated",
-            Self::Custom { .. } => "custom",
+            Self::AudioCaptured => "audio_captured",
+            Self::WakeWordDetected => "wake_word_detected",
+            Self::CommandTranscribed => "command_transcribed",
+            Self::CommandParsed => "command_parsed",
+            Self::TaskPlanned => "task_planned",
+            Self::ActionStarted => "action_started",
+            Self::ActionCompleted => "action_completed",
+            Self::Error => "error",
+            Self::StateChanged => "state_changed",
+            Self::ConfigReloaded => "config_reloaded",
+            Self::MetricsSnapshot => "metrics_snapshot",
+            Self::ClarificationRequested => "clarification_requested",
+            Self::ClarificationAnswered => "clarification_answered",
+            Self::GrammarReloaded => "grammar_reloaded",
+            Self::CacheInvalidated => "cache_invalidated",
+            Self::PlanStarted => "plan_started",
+            Self::PlanCompleted => "plan_completed",
+            Self::CapabilityDetected => "capability_detected",
+            Self::ActionRetry => "action_retry",
+            Self::PolicyGateTriggered => "policy_gate_triggered",
+            Self::TtsStarted => "tts_started",
+            Self::TtsCompleted => "tts_completed",
+            Self::TtsInterrupted => "tts_interrupted",
+            Self::HealthIssueDetected => "health_issue_detected",
+            Self::HealthRemediated => "health_remediated",
+            Self::Custom => "custom",
         }
     }
 }
 
+impl std::fmt::Display for EventKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for EventKind {
+    type Err = LunaError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|kind| kind.as_str() == s)
+            .ok_or_else(|| LunaError::InvalidParameter(format!("Unknown event type: {}", s)))
+    }
+}
+
+/// Borrow the payload of an envelope if it matches a `LunaEvent` pattern
+///
+/// Evaluates to `Some(body)` when the pattern matches and `None` otherwise:
+///
+/// ```
+/// use luna::{event_payload, EventEnvelope, LunaEvent};
+///
+/// let envelope = EventEnvelope::new(LunaEvent::WakeWordDetected {
+///     keyword: "hey luna".to_string(),
+///     confidence: 0.9,
+/// });
+/// let keyword = event_payload!(envelope, LunaEvent::WakeWordDetected { keyword, .. } => keyword.clone());
+/// assert_eq!(keyword.as_deref(), Some("hey luna"));
+/// ```
+#[macro_export]
+macro_rules! event_payload {
+    ($envelope:expr, $pattern:pat => $body:expr) => {
+        match &$envelope.event {
+            $pattern => Some($body),
+            #[allow(unreachable_patterns)]
+            _ => None,
+        }
+    };
+}
+
 /// Event envelope with metadata for observability
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EventEnvelope {
     /// Unique event ID
     pub id: Uuid,
@@ -250,12 +415,115 @@ impl EventEnvelope {
 /// Event handler function type
 pub type EventHandler = Arc<dyn Fn(&EventEnvelope) + Send + Sync>;
 
+/// Custom event type published when a subscriber queue drops events
+pub const SUBSCRIBER_OVERFLOW_EVENT: &str = "subscriber_overflow";
+
 /// Subscription information
 struct Subscriber {
-    id: usize,
     event_types: Vec<&'static str>,
-    handler: EventHandler,
-    queue_size: usize,
+    queue: SubscriberQueue,
+}
+
+impl Subscriber {
+    fn matches(&self, event_type: &str) -> bool {
+        self.event_types.is_empty() || self.event_types.contains(&event_type)
+    }
+}
+
+/// Counts a running dispatch loop until dropped, even if its task is aborted
+struct DispatcherGuard(Arc<watch::Sender<usize>>);
+
+impl DispatcherGuard {
+    fn new(dispatchers: Arc<watch::Sender<usize>>) -> Self {
+        dispatchers.send_modify(|n| *n += 1);
+        Self(dispatchers)
+    }
+}
+
+impl Drop for DispatcherGuard {
+    fn drop(&mut self) {
+        self.0.send_modify(|n| *n -= 1);
+    }
+}
+
+/// Per-subscriber queue drained by the subscriber's own worker thread
+#[derive(Clone)]
+struct SubscriberQueue {
+    id: usize,
+    tx: Sender<EventEnvelope>,
+    /// Receiver end used to evict the oldest event under `DropOldest`
+    evict_rx: Receiver<EventEnvelope>,
+    dropped: Arc<AtomicU64>,
+    worker: Arc<parking_lot::Mutex<Option<std::thread::JoinHandle<()>>>>,
+}
+
+impl SubscriberQueue {
+    /// Spawn a worker thread that runs `handler` for each queued event
+    ///
+    /// The worker exits once the queue is dropped on unsubscribe.
+    fn spawn(id: usize, queue_size: usize, handler: EventHandler) -> Self {
+        let (tx, rx) = if queue_size == 0 {
+            unbounded()
+        } else {
+            bounded(queue_size)
+        };
+
+        let worker_rx = rx.clone();
+        let worker = std::thread::spawn(move || {
+            while let Ok(envelope) = worker_rx.recv_blocking() {
+                handler(&envelope);
+            }
+        });
+
+        Self {
+            id,
+            tx,
+            evict_rx: rx,
+            dropped: Arc::new(AtomicU64::new(0)),
+            worker: Arc::new(parking_lot::Mutex::new(Some(worker))),
+        }
+    }
+
+    /// Close the queue and wait for the worker to handle what is left
+    fn close_and_join(&self) {
+        self.tx.close();
+        if let Some(worker) = self.worker.lock().take() {
+            let _ = worker.join();
+        }
+    }
+
+    /// Queue an event, applying `strategy` if the queue is full
+    ///
+    /// Returns the subscriber's total drop count when this delivery dropped
+    /// an event.
+    async fn deliver(
+        &self,
+        envelope: EventEnvelope,
+        strategy: BackpressureStrategy,
+    ) -> Option<u64> {
+        let envelope = match self.tx.try_send(envelope) {
+            Err(TrySendError::Full(envelope)) => envelope,
+            // Delivered, or the worker is gone
+            _ => return None,
+        };
+
+        match strategy {
+            BackpressureStrategy::Block => {
+                let _ = self.tx.send(envelope).await;
+                None
+            }
+            BackpressureStrategy::DropNewest => Some(self.record_drop()),
+            BackpressureStrategy::DropOldest => {
+                let _ = self.evict_rx.try_recv();
+                let _ = self.tx.try_send(envelope);
+                Some(self.record_drop())
+            }
+        }
+    }
+
+    fn record_drop(&self) -> u64 {
+        self.dropped.fetch_add(1, Ordering::Relaxed) + 1
+    }
 }
 
 /// Backpressure strategy for full queues
@@ -270,12 +538,17 @@ pub enum BackpressureStrategy {
 }
 
 /// Event bus for publish-subscribe communication
+///
+/// Each subscriber runs its handler on its own worker thread, so a slow
+/// handler only delays its own events.
 pub struct EventBus {
     subscribers: Arc<RwLock<Vec<Subscriber>>>,
     tx: Sender<EventEnvelope>,
     rx: Receiver<EventEnvelope>,
     next_id: Arc<RwLock<usize>>,
     backpressure: BackpressureStrategy,
+    /// Number of running dispatch loops, watched by `shutdown`
+    dispatchers: Arc<watch::Sender<usize>>,
 }
 
 impl EventBus {
@@ -288,6 +561,7 @@ impl EventBus {
             rx,
             next_id: Arc::new(RwLock::new(0)),
             backpressure: BackpressureStrategy::DropOldest,
+            dispatchers: Arc::new(watch::channel(0).0),
         }
     }
 
@@ -300,15 +574,21 @@ impl EventBus {
             rx,
             next_id: Arc::new(RwLock::new(0)),
             backpressure: BackpressureStrategy::DropOldest,
+            dispatchers: Arc::new(watch::channel(0).0),
         }
     }
 
+    /// Set the strategy applied when a bounded subscriber queue is full
+    pub fn with_backpressure(mut self, strategy: BackpressureStrategy) -> Self {
+        self.backpressure = strategy;
+        self
+    }
+
     /// Subscribe to specific event types
     ///
     /// # Arguments
     /// * `event_types` - List of event types to subscribe to. Empty vec = all events.
     /// * `handler` - Function to call when matching event is published
-    /// * `queue_size` - Maximum queue size for this subscriber (0 = unbounded)
     ///
     /// # Returns
     /// Subscription ID for unsubscribing later
@@ -319,7 +599,22 @@ impl EventBus {
         self.subscribe_with_queue(event_types, handler, 0).await
     }
 
+    /// Subscribe to specific event kinds
+    ///
+    /// Typed equivalent of `subscribe`; an empty slice subscribes to all
+    /// events.
+    pub async fn subscribe_kinds<F>(&self, kinds: &[EventKind], handler: F) -> usize
+    where
+        F: Fn(&EventEnvelope) + Send + Sync + 'static,
+    {
+        let event_types = kinds.iter().map(EventKind::as_str).collect();
+        self.subscribe(event_types, handler).await
+    }
+
     /// Subscribe with specific queue size
+    ///
+    /// `queue_size` bounds the events waiting for this subscriber
+    /// (0 = unbounded); the bus backpressure strategy applies when full.
     pub async fn subscribe_with_queue<F>(
         &self,
         event_types: Vec<&'static str>,
@@ -335,10 +630,8 @@ impl EventBus {
         *next_id += 1;
 
         subs.push(Subscriber {
-            id,
             event_types,
-            handler: Arc::new(handler),
-            queue_size,
+            queue: SubscriberQueue::spawn(id, queue_size, Arc::new(handler)),
         });
         id
     }
@@ -346,7 +639,7 @@ impl EventBus {
     /// Unsubscribe by ID
     pub async fn unsubscribe(&self, id: usize) {
         let mut subs = self.subscribers.write().await;
-        subs.retain(|s| s.id != id);
+        subs.retain(|s| s.queue.id != id);
     }
 
     /// Publish an event to all subscribers (wraps in envelope)
@@ -368,29 +661,88 @@ impl EventBus {
 
     /// Start the event processing loop
     ///
-    /// This spawns a background task that dispatches events to subscribers.
-    /// Returns a JoinHandle that can be aborted to stop the processing loop.
+    /// This spawns a background task that dispatches events to subscriber
+    /// queues. Returns a JoinHandle that can be aborted to stop the
+    /// processing loop.
     pub async fn start_processing(&self) -> JoinHandle<()> {
         let rx = self.rx.clone();
+        let tx = self.tx.clone();
         let subscribers = self.subscribers.clone();
+        let strategy = self.backpressure;
+        let guard = DispatcherGuard::new(self.dispatchers.clone());
 
         tokio::spawn(async move {
+            let _guard = guard;
             while let Ok(envelope) = rx.recv().await {
                 let event_type = envelope.event_type();
-                let subs = subscribers.read().await;
-
-                for subscriber in subs.iter() {
-                    // Call handler if subscribed to all events or this specific type
-                    if subscriber.event_types.is_empty()
-                        || subscriber.event_types.contains(&event_type)
-                    {
-                        (subscriber.handler)(&envelope);
+
+                // Release the lock before delivering so a blocked queue
+                // does not hold up subscribe/unsubscribe
+                let queues: Vec<SubscriberQueue> = subscribers
+                    .read()
+                    .await
+                    .iter()
+                    .filter(|s| s.matches(event_type))
+                    .map(|s| s.queue.clone())
+                    .collect();
+
+                for queue in queues {
+                    let Some(dropped) = queue.deliver(envelope.clone(), strategy).await else {
+                        continue;
+                    };
+
+                    // Never report drops of overflow events, to avoid a
+                    // feedback loop through an overflowing "custom" subscriber
+                    if is_overflow_event(&envelope) {
+                        continue;
                     }
+                    let overflow = EventEnvelope::new(LunaEvent::Custom {
+                        event_type: SUBSCRIBER_OVERFLOW_EVENT.to_string(),
+                        data: serde_json::json!({
+                            "subscriber_id": queue.id,
+                            "dropped_event": event_type,
+                            "dropped_total": dropped,
+                            "strategy": format!("{:?}", strategy),
+                        }),
+                    });
+                    // try_send: the dispatcher must never wait on its own channel
+                    let _ = tx.try_send(overflow);
                 }
             }
         })
     }
 
+    /// Stop accepting events and deliver everything already queued
+    ///
+    /// Publishing after this call is a no-op. Waits until the processing
+    /// loop has dispatched the remaining events and every subscriber has
+    /// handled its queue, so the processing task completes on its own.
+    pub async fn shutdown(&self) {
+        self.tx.close();
+
+        let mut dispatchers = self.dispatchers.subscribe();
+        let _ = dispatchers.wait_for(|n| *n == 0).await;
+
+        let queues: Vec<SubscriberQueue> = self
+            .subscribers
+            .read()
+            .await
+            .iter()
+            .map(|s| s.queue.clone())
+            .collect();
+        let _ = tokio::task::spawn_blocking(move || {
+            for queue in queues {
+                queue.close_and_join();
+            }
+        })
+        .await;
+    }
+
+    /// Whether `shutdown` has been called
+    pub fn is_shut_down(&self) -> bool {
+        self.tx.is_closed()
+    }
+
     /// Get a clone of the sender for publishing events
     pub fn get_sender(&self) -> Sender<EventEnvelope> {
         self.tx.clone()
@@ -407,6 +759,13 @@ impl EventBus {
     }
 }
 
+fn is_overflow_event(envelope: &EventEnvelope) -> bool {
+    matches!(
+        &envelope.event,
+        LunaEvent::Custom { event_type, .. } if event_type == SUBSCRIBER_OVERFLOW_EVENT
+    )
+}
+
 impl Default for EventBus {
     fn default() -> Self {
         Self::new()
@@ -527,6 +886,220 @@ mod tests {
         handle.abort();
     }
 
+    #[tokio::test]
+    async fn test_subscribe_kinds() {
+        let bus = EventBus::new();
+        let handle = bus.start_processing().await;
+
+        let keywords = Arc::new(Mutex::new(Vec::new()));
+        let keywords_clone = keywords.clone();
+
+        bus.subscribe_kinds(&[EventKind::WakeWordDetected], move |envelope| {
+            if let Some(keyword) = event_payload!(
+                envelope,
+                LunaEvent::WakeWordDetected { keyword, .. } => keyword.clone()
+            ) {
+                keywords_clone.lock().unwrap().push(keyword);
+            }
+        })
+        .await;
+
+        bus.publish(LunaEvent::CommandTranscribed {
+            text: "open chrome".to_string(),
+            confidence: 0.9,
+        })
+        .await;
+        bus.publish(LunaEvent::WakeWordDetected {
+            keyword: "hey luna".to_string(),
+            confidence: 0.95,
+        })
+        .await;
+
+        tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+
+        assert_eq!(*keywords.lock().unwrap(), vec!["hey luna"]);
+
+        handle.abort();
+    }
+
+    #[test]
+    fn test_event_kind_matches_event_type() {
+        for event in one_of_each_event() {
+            let kind = event.kind();
+            assert_eq!(kind.as_str(), event.event_type());
+            assert_eq!(kind.as_str().parse::<EventKind>().unwrap(), kind);
+        }
+        assert_eq!(EventKind::ALL.len(), one_of_each_event().len());
+        assert!("wake_word_detcted".parse::<EventKind>().is_err());
+    }
+
+    #[test]
+    fn test_event_payload_mismatch() {
+        let envelope = EventEnvelope::new(LunaEvent::TtsCompleted { success: true });
+        let keyword = event_payload!(
+            envelope,
+            LunaEvent::WakeWordDetected { keyword, .. } => keyword.clone()
+        );
+        assert!(keyword.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_drains_queued_events() {
+        let bus = EventBus::new();
+
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let received_clone = received.clone();
+        bus.subscribe(vec!["state_changed"], move |envelope| {
+            // Slow handler so events are still queued at shutdown
+            std::thread::sleep(std::time::Duration::from_millis(5));
+            if let LunaEvent::StateChanged { to, .. } = &envelope.event {
+                received_clone.lock().unwrap().push(to.clone());
+            }
+        })
+        .await;
+
+        // Queue everything before the dispatcher starts
+        for i in 0..9 {
+            bus.publish(state_changed(i)).await;
+        }
+        bus.publish(LunaEvent::StateChanged {
+            from: "running".to_string(),
+            to: "stopped".to_string(),
+        })
+        .await;
+
+        let handle = bus.start_processing().await;
+        bus.shutdown().await;
+
+        // Everything was handled before shutdown returned
+        let received = received.lock().unwrap().clone();
+        assert_eq!(received.len(), 10);
+        assert_eq!(received.last().map(String::as_str), Some("stopped"));
+
+        // The processing task ends on its own
+        tokio::time::timeout(tokio::time::Duration::from_secs(1), handle)
+            .await
+            .expect("processing task did not finish")
+            .unwrap();
+
+        // Publishing after shutdown is ignored
+        assert!(bus.is_shut_down());
+        bus.publish(state_changed(10)).await;
+        assert_eq!(bus.rx.len(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_after_aborted_dispatcher() {
+        let bus = EventBus::new();
+        let handle = bus.start_processing().await;
+        handle.abort();
+        let _ = handle.await;
+
+        tokio::time::timeout(tokio::time::Duration::from_secs(1), bus.shutdown())
+            .await
+            .expect("shutdown waited on an aborted dispatcher");
+    }
+
+    fn state_changed(i: usize) -> LunaEvent {
+        LunaEvent::StateChanged {
+            from: i.to_string(),
+            to: (i + 1).to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_slow_subscriber_does_not_block_others() {
+        let bus = EventBus::new().with_backpressure(BackpressureStrategy::DropNewest);
+        let handle = bus.start_processing().await;
+
+        let slow_received = Arc::new(Mutex::new(Vec::new()));
+        let slow_clone = slow_received.clone();
+        bus.subscribe_with_queue(
+            vec!["state_changed"],
+            move |envelope| {
+                std::thread::sleep(std::time::Duration::from_millis(100));
+                slow_clone.lock().unwrap().push(envelope.id);
+            },
+            2,
+        )
+        .await;
+
+        let fast_received = Arc::new(Mutex::new(Vec::new()));
+        let fast_clone = fast_received.clone();
+        bus.subscribe(vec!["state_changed"], move |envelope| {
+            fast_clone.lock().unwrap().push(envelope.id);
+        })
+        .await;
+
+        let overflows = Arc::new(Mutex::new(Vec::new()));
+        let overflows_clone = overflows.clone();
+        bus.subscribe(vec!["custom"], move |envelope| {
+            if let LunaEvent::Custom { event_type, data } = &envelope.event {
+                if event_type == SUBSCRIBER_OVERFLOW_EVENT {
+                    overflows_clone.lock().unwrap().push(data.clone());
+                }
+            }
+        })
+        .await;
+
+        for i in 0..20 {
+            bus.publish(state_changed(i)).await;
+        }
+        tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+
+        // The fast subscriber saw everything while the slow one is still busy
+        assert_eq!(fast_received.lock().unwrap().len(), 20);
+        assert!(slow_received.lock().unwrap().len() < 20);
+
+        let overflows = overflows.lock().unwrap();
+        assert!(!overflows.is_empty());
+        assert_eq!(overflows[0]["subscriber_id"], 0);
+        assert_eq!(overflows[0]["strategy"], "DropNewest");
+
+        handle.abort();
+    }
+
+    #[tokio::test]
+    async fn test_drop_oldest_keeps_latest_events() {
+        let bus = EventBus::new();
+        let handle = bus.start_processing().await;
+
+        let (release_tx, release_rx) = std::sync::mpsc::channel::<()>();
+        let release_rx = Mutex::new(release_rx);
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let received_clone = received.clone();
+
+        // The handler holds its first event until released, so the
+        // queue fills up behind it
+        bus.subscribe_with_queue(
+            vec!["state_changed"],
+            move |envelope| {
+                let _ = release_rx.lock().unwrap().recv();
+                if let LunaEvent::StateChanged { from, .. } = &envelope.event {
+                    received_clone.lock().unwrap().push(from.clone());
+                }
+            },
+            2,
+        )
+        .await;
+
+        bus.publish(state_changed(0)).await;
+        tokio::time::sleep(tokio::time::Duration::from_millis(20)).await;
+        for i in 1..6 {
+            bus.publish(state_changed(i)).await;
+        }
+        tokio::time::sleep(tokio::time::Duration::from_millis(20)).await;
+
+        for _ in 0..3 {
+            release_tx.send(()).unwrap();
+        }
+        tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+
+        assert_eq!(*received.lock().unwrap(), vec!["0", "4", "5"]);
+
+        handle.abort();
+    }
+
     #[tokio::test]
     async fn test_event_envelope() {
         let event = LunaEvent::WakeWordDetected {
@@ -553,4 +1126,172 @@ mod tests {
 
         assert_eq!(envelope.correlation_id, Some(correlation_id));
     }
+
+    fn one_of_each_event() -> Vec<LunaEvent> {
+        let params = HashMap::from([("app_name".to_string(), "chrome".to_string())]);
+        vec![
+            LunaEvent::AudioCaptured {
+                samples: vec![0.1, -0.2],
+                timestamp: 42,
+            },
+            LunaEvent::WakeWordDetected {
+                keyword: "hey luna".to_string(),
+                confidence: 0.95,
+            },
+            LunaEvent::CommandTranscribed {
+                text: "open chrome".to_string(),
+                confidence: 0.9,
+            },
+            LunaEvent::CommandParsed {
+                command: "open chrome".to_string(),
+                intent: "LaunchApp".to_string(),
+                entities: params.clone(),
+                confidence: 0.95,
+            },
+            LunaEvent::TaskPlanned {
+                plan: "launch chrome".to_string(),
+                steps: 1,
+            },
+            LunaEvent::ActionStarted {
+                action_type: "LaunchApp".to_string(),
+                params,
+            },
+            LunaEvent::ActionCompleted {
+                action_type: "LaunchApp".to_string(),
+                success: true,
+                result: "Launched".to_string(),
+                duration_ms: 120,
+            },
+            LunaEvent::Error {
+                error: "not found".to_string(),
+                error_code: "E3001".to_string(),
+                context: HashMap::new(),
+                recoverable: true,
+            },
+            LunaEvent::StateChanged {
+                from: "idle".to_string(),
+                to: "running".to_string(),
+            },
+            LunaEvent::ConfigReloaded {
+                timestamp: 7,
+                changed_fields: vec!["audio.sample_rate".to_string()],
+            },
+            LunaEvent::MetricsSnapshot {
+                commands_processed: 3,
+                success_rate: 66.5,
+                avg_latency_ms: 250,
+            },
+            LunaEvent::ClarificationRequested {
+                command: "open".to_string(),
+                confidence: 0.4,
+                missing_slots: vec!["app_name".to_string()],
+                suggestions: vec!["chrome".to_string()],
+            },
+            LunaEvent::ClarificationAnswered {
+                original_command: "open".to_string(),
+                clarification: "chrome".to_string(),
+                resolved_command: "open chrome".to_string(),
+            },
+            LunaEvent::GrammarReloaded {
+                pattern_count: 12,
+                timestamp: 8,
+            },
+            LunaEvent::CacheInvalidated {
+                cache_type: "parse".to_string(),
+                reason: "grammar reload".to_string(),
+            },
+            LunaEvent::PlanStarted {
+                plan_id: "p1".to_string(),
+                step_count: 2,
+                parallel_groups: 1,
+            },
+            LunaEvent::PlanCompleted {
+                plan_id: "p1".to_string(),
+                success: false,
+                total_duration_ms: 900,
+                steps_completed: 1,
+                steps_failed: 1,
+            },
+            LunaEvent::CapabilityDetected {
+                capability: "media".to_string(),
+                available: true,
+                tool_name: Some("playerctl".to_string()),
+                version: None,
+            },
+            LunaEvent::ActionRetry {
+                action_type: "LaunchApp".to_string(),
+                attempt: 2,
+                max_attempts: 3,
+                error: "timeout".to_string(),
+            },
+            LunaEvent::PolicyGateTriggered {
+                action_type: "SystemControl".to_string(),
+                requires_confirmation: true,
+                reason: "shutdown".to_string(),
+            },
+            LunaEvent::TtsStarted {
+                text: "Done".to_string(),
+                kind: "Info".to_string(),
+            },
+            LunaEvent::TtsCompleted { success: true },
+            LunaEvent::TtsInterrupted {
+                reason: "wake word".to_string(),
+            },
+            LunaEvent::HealthIssueDetected {
+                issue_type: "HighMemory".to_string(),
+                severity: "Warning".to_string(),
+            },
+            LunaEvent::HealthRemediated {
+                issue_type: "HighMemory".to_string(),
+                action_taken: "cleared cache".to_string(),
+            },
+            LunaEvent::Custom {
+                event_type: "window_focused".to_string(),
+                data: serde_json::json!({ "title": "Terminal", "pid": 42 }),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_event_serde_round_trip() {
+        for event in one_of_each_event() {
+            let json = serde_json::to_value(&event).unwrap();
+            assert_eq!(json["type"], event.event_type());
+
+            let decoded: LunaEvent = serde_json::from_value(json).unwrap();
+            match (&event, &decoded) {
+                (
+                    LunaEvent::AudioCaptured { timestamp, .. },
+                    LunaEvent::AudioCaptured {
+                        samples,
+                        timestamp: decoded_timestamp,
+                    },
+                ) => {
+                    // Samples are dropped from the serialized form
+                    assert!(samples.is_empty());
+                    assert_eq!(timestamp, decoded_timestamp);
+                }
+                _ => assert_eq!(event, decoded),
+            }
+        }
+    }
+
+    #[test]
+    fn test_envelope_serde_round_trip() {
+        let envelope = EventEnvelope::with_correlation(
+            LunaEvent::StateChanged {
+                from: "idle".to_string(),
+                to: "running".to_string(),
+            },
+            Uuid::new_v4(),
+        );
+
+        let json = serde_json::to_string(&envelope).unwrap();
+        let decoded: EventEnvelope = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(decoded.id, envelope.id);
+        assert_eq!(decoded.timestamp, envelope.timestamp);
+        assert_eq!(decoded.correlation_id, envelope.correlation_id);
+        assert_eq!(decoded.event, envelope.event);
+    }
 }
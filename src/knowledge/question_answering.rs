@@ -8,6 +8,7 @@ use crate::knowledge::weather::WeatherService;
 use crate::knowledge::wikipedia::WikipediaClient;
 use crate::knowledge::graph::{Entity, EntityType, Fact, FactConfidence, KnowledgeGraph};
 use serde::{Deserialize, Serialize};
+use std::fmt;
 use tracing::{debug, info};
 
 /// Answer source
@@ -27,6 +28,20 @@ pub enum AnswerSource {
     DateTime,
 }
 
+impl fmt::Display for AnswerSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            AnswerSource::WebSearch => "web search",
+            AnswerSource::KnowledgeBase => "knowledge base",
+            AnswerSource::Wikipedia => "Wikipedia",
+            AnswerSource::Weather => "weather service",
+            AnswerSource::Calculation => "calculation",
+            AnswerSource::DateTime => "system clock",
+        };
+        f.write_str(name)
+    }
+}
+
 /// Answer to a question
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Answer {
@@ -73,6 +88,8 @@ pub struct QuestionAnswerer {
     weather: WeatherService,
     /// Knowledge graph for caching
     knowledge_graph: KnowledgeGraph,
+    /// Allow falling back to online web search (off by default for privacy)
+    web_search_enabled: bool,
 }
 
 impl QuestionAnswerer {
@@ -83,9 +100,21 @@ impl QuestionAnswerer {
             wikipedia: WikipediaClient::new(),
             weather: WeatherService::new(),
             knowledge_graph: KnowledgeGraph::new(),
+            web_search_enabled: false,
         }
     }
 
+    /// Enable or disable the web search fallback
+    pub fn with_web_search(mut self, enabled: bool) -> Self {
+        self.web_search_enabled = enabled;
+        self
+    }
+
+    /// Whether the web search fallback is enabled
+    pub fn web_search_enabled(&self) -> bool {
+        self.web_search_enabled
+    }
+
     /// Get reference to knowledge graph
     pub fn knowledge_graph(&self) -> &KnowledgeGraph {
         &self.knowledge_graph
@@ -279,7 +308,17 @@ impl QuestionAnswerer {
             });
         }
         
-        // Fallback to web search
+        // Fallback to web search (only when explicitly enabled)
+        if !self.web_search_enabled {
+            return Ok(Answer {
+                text: "I couldn't find a definition for that offline".to_string(),
+                confidence: 0.3,
+                source: AnswerSource::KnowledgeBase,
+                source_url: None,
+                context: None,
+            });
+        }
+
         let results = self.web_searcher.search(question, 1).await?;
         
         if let Some(result) = results.first() {
@@ -301,7 +340,7 @@ impl QuestionAnswerer {
         }
     }
 
-    /// Answer factual question using web search
+    /// Answer factual question using Wikipedia, then web search if enabled
     async fn answer_factual(&self, question: &str) -> Result<Answer> {
         // Try Wikipedia first for factual questions
         if let Ok(Some(summary)) = self.wikipedia.quick_fact(question).await {
@@ -323,7 +362,17 @@ impl QuestionAnswerer {
             });
         }
         
-        // Fallback to web search
+        // Fallback to web search (only when explicitly enabled)
+        if !self.web_search_enabled {
+            return Ok(Answer {
+                text: "I couldn't find an answer to that offline".to_string(),
+                confidence: 0.2,
+                source: AnswerSource::KnowledgeBase,
+                source_url: None,
+                context: None,
+            });
+        }
+
         let results = self.web_searcher.search(question, 3).await?;
         
         if let Some(result) = results.first() {
@@ -437,6 +486,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_web_search_disabled_by_default() {
+        let qa = QuestionAnswerer::new();
+        assert!(!qa.web_search_enabled());
+        assert!(QuestionAnswerer::new().with_web_search(true).web_search_enabled());
+    }
+
+    #[test]
+    fn test_answer_source_display() {
+        assert_eq!(AnswerSource::KnowledgeBase.to_string(), "knowledge base");
+        assert_eq!(AnswerSource::Wikipedia.to_string(), "Wikipedia");
+    }
+
     #[tokio::test]
     async fn test_time_answer() {
         let qa = QuestionAnswerer::new();
@@ -53,15 +53,18 @@ pub mod utils;
 
 // Re-export commonly used types
 pub use config::LunaConfig;
-pub use config_manager::ConfigManager;
+pub use config_manager::{ConfigManager, ConfigProvenance, ConfigSource};
 pub use error::{ErrorCode, LunaError, Result};
-pub use events::{BackpressureStrategy, EventBus, EventEnvelope, LunaEvent};
+pub use events::{BackpressureStrategy, EventBus, EventEnvelope, EventKind, LunaEvent};
 pub use metrics::{MetricPhase, MetricTimer, Metrics};
-pub use runtime::{Lifecycle, LunaRuntime, RuntimeState};
+pub use runtime::{
+    Lifecycle, LunaRuntime, MetricsReporter, RuntimeState, ScriptRunner, ScriptedCommand,
+};
 
 // Re-export brain enhancements
 pub use brain::{
     ActionType,
+    AppKnowledge,
     Brain,
     BrainCache,
     CompiledGrammar,
@@ -75,7 +78,9 @@ pub use brain::{
     FileSystemProvider,
     GrammarConfig,
     IntentType,
+    KnowledgeProvider,
     KnownAppProvider,
+    Locale,
 };
 
 // Re-export knowledge system (Phase B1)
@@ -10,7 +10,7 @@ use clap::Parser;
 use luna::cli::{run_cli, Cli};
 use luna::config::LunaConfig;
 use luna::error::Result;
-use luna::{ConfigManager, EventBus, Metrics};
+use luna::{ConfigManager, EventBus, Lifecycle, Metrics, MetricsReporter, RuntimeState};
 use tracing::info;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
 
@@ -33,11 +33,7 @@ async fn main() -> Result<()> {
 /// Run the full LUNA voice assistant
 async fn run_assistant(cli: Cli) -> Result<()> {
     // Load configuration with manager (layered + hot-reload)
-    let mut config_mgr = ConfigManager::new(cli.config).await?;
-
-    // Enable hot-reload for production
-    config_mgr.enable_hot_reload()?;
-
+    let config_mgr = ConfigManager::new(cli.config).await?;
     let config = config_mgr.get_clone().await;
 
     // Setup logging
@@ -56,9 +52,31 @@ async fn run_assistant(cli: Cli) -> Result<()> {
     // Initialize event bus
     info!("Initializing event bus...");
     let event_bus = std::sync::Arc::new(EventBus::new());
-    let _event_bus_handle = event_bus.start_processing().await;
+    let event_bus_handle = event_bus.start_processing().await;
     info!("✓ Event bus initialized");
 
+    // Enable hot-reload for production, announcing changes on the bus
+    let mut config_mgr = config_mgr.with_event_bus(std::sync::Arc::clone(&event_bus));
+    config_mgr.enable_hot_reload()?;
+
+    // Only audio changes require rebuilding the audio pipeline
+    let audio_config_changed = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+    let audio_changed_flag = audio_config_changed.clone();
+    event_bus
+        .subscribe_kinds(&[luna::EventKind::ConfigReloaded], move |envelope| {
+            if let luna::LunaEvent::ConfigReloaded { changed_fields, .. } = &envelope.event {
+                if changed_fields.iter().any(|path| path.starts_with("audio.")) {
+                    audio_changed_flag.store(true, std::sync::atomic::Ordering::SeqCst);
+                }
+            }
+        })
+        .await;
+
+    let mut runtime_state = RuntimeState::Stopped;
+    runtime_state
+        .transition_and_publish(RuntimeState::Starting, &event_bus)
+        .await?;
+
     // Initialize metrics
     info!("Initializing metrics...");
     let metrics = std::sync::Arc::new(Metrics::new());
@@ -74,8 +92,28 @@ async fn run_assistant(cli: Cli) -> Result<()> {
         }
     }
 
+    #[cfg(feature = "http-metrics")]
+    {
+        // Lightweight JSON endpoint when the Prometheus stack isn't wanted
+        if let Err(e) = luna::metrics::serve("127.0.0.1:9091", metrics.clone()).await {
+            tracing::error!("Failed to start metrics endpoint: {}", e);
+        }
+    }
+
     info!("✓ Metrics initialized");
 
+    // Periodic metrics snapshots on the event bus
+    let mut metrics_reporter = (config.performance.metrics_snapshot_interval_secs > 0).then(|| {
+        MetricsReporter::new(
+            metrics.clone(),
+            event_bus.clone(),
+            std::time::Duration::from_secs(config.performance.metrics_snapshot_interval_secs),
+        )
+    });
+    if let Some(ref mut reporter) = metrics_reporter {
+        reporter.start().await?;
+    }
+
     // Capability detection
     info!("Detecting system capabilities...");
     let _capabilities = luna::actions::CapabilityDetector::new()
@@ -84,6 +122,37 @@ async fn run_assistant(cli: Cli) -> Result<()> {
         .await;
     // Results are already logged by the detector
 
+    // Health checks; remediation follows [health] and is audited in the data dir
+    let os_manager = luna::os::UnifiedOsManager::new()
+        .with_event_bus(std::sync::Arc::clone(&event_bus))
+        .with_health_config(
+            config.health.clone(),
+            std::path::Path::new(&config.system.data_dir),
+        );
+    let health_monitor = std::sync::Arc::clone(&os_manager.health_monitor);
+    health_monitor.start_monitoring().await?;
+    let health_task = tokio::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(60));
+        let mut known_issues = std::collections::HashSet::new();
+        loop {
+            interval.tick().await;
+            let issues = health_monitor.detect_issues().await;
+
+            // Only act on (and audit) issues that weren't already present
+            let mut current = std::collections::HashSet::new();
+            for issue in issues {
+                let key = format!("{:?}", issue);
+                if !known_issues.contains(&key) {
+                    if let Err(e) = health_monitor.remediate(&issue).await {
+                        tracing::warn!("Health remediation failed: {}", e);
+                    }
+                }
+                current.insert(key);
+            }
+            known_issues = current;
+        }
+    });
+
     // Subscribe to events for logging and state tracking
     event_bus
         .subscribe(vec![], move |envelope| {
@@ -105,7 +174,8 @@ async fn run_assistant(cli: Cli) -> Result<()> {
     // Audio System
     let mut audio_system = luna::audio::ProductionAudioSystem::create(&config.audio, &config.brain)
         .await?
-        .with_event_bus(std::sync::Arc::clone(&event_bus));
+        .with_event_bus(std::sync::Arc::clone(&event_bus))
+        .with_capture_metrics(std::sync::Arc::clone(&metrics));
     info!("✓ Audio system initialized");
 
     if audio_system.is_stt_simulated() {
@@ -117,9 +187,31 @@ async fn run_assistant(cli: Cli) -> Result<()> {
     }
 
     // Brain/NLP System
-    let mut brain = luna::brain::Brain::new(&config.brain)?;
+    let mut brain = luna::brain::Brain::new(&config.brain)?
+        .with_event_bus(std::sync::Arc::clone(&event_bus));
     info!("✓ Brain system initialized");
 
+    // Watch the grammar file the brain loaded for its locale
+    let grammar_path = brain.grammar_file();
+
+    // Keep the watcher alive for the lifetime of the main loop
+    let _grammar_watcher = if grammar_path.exists() {
+        match brain.enable_grammar_hot_reload(&grammar_path) {
+            Ok(watcher) => Some(watcher),
+            Err(e) => {
+                tracing::warn!("Grammar hot-reload unavailable: {}", e);
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    // Track the focused app so references like "close it" follow focus
+    let mut active_app_watcher = luna::os::app_watcher::ActiveAppWatcher::new(brain.context())
+        .with_event_bus(std::sync::Arc::clone(&event_bus));
+    active_app_watcher.start().await?;
+
     // Task Executor
     // Create app database and discover applications
     let mut app_db_temp = luna::db::AppDatabase::new();
@@ -134,23 +226,57 @@ async fn run_assistant(cli: Cli) -> Result<()> {
             tracing::warn!("Failed to discover applications: {}", e);
         }
     }
+    // Carry launch history over from the saved database
+    let app_db_path = std::path::PathBuf::from(&config.system.data_dir).join("app_database.json");
+    if let Ok(saved) = luna::db::AppDatabase::load_from_disk(&app_db_path).await {
+        app_db_temp.merge_usage(&saved);
+    }
     // Add discovered apps to brain for classification boosting
     for app in app_db_temp.all() {
-        brain.add_known_app(app.name.clone());
+        brain.add_known_app_with_aliases(app.name.clone(), app.aliases.clone());
     }
     info!("✓ Added {} known apps to brain for classification boosting", app_db_temp.len());
 
-    let app_db = std::sync::Arc::new(app_db_temp);
+    let app_db = std::sync::Arc::new(parking_lot::RwLock::new(app_db_temp));
     let file_index = std::sync::Arc::new(luna::db::FileIndex::new());
-    let app_launcher = luna::actions::AppLauncher::new(app_db);
+    let app_launcher = luna::actions::AppLauncher::new(app_db)
+        .with_persistence(app_db_path)
+        .with_preferred_apps(config.system.preferred_apps());
+    // Kept to ask "which browser?" before executing category launches
+    let category_resolver = app_launcher.clone();
     let file_search = luna::actions::FileSearch::new(file_index);
-    let executor = luna::actions::TaskExecutor::new(app_launcher, file_search)
+    let question_answerer = std::sync::Arc::new(
+        luna::knowledge::QuestionAnswerer::new().with_web_search(config.brain.enable_web_search),
+    );
+    let clipboard_history =
+        std::sync::Arc::new(luna::os::clipboard_manager::ClipboardHistory::default());
+    if cfg!(feature = "clipboard") {
+        clipboard_history.spawn_polling(std::time::Duration::from_secs(2));
+    }
+    let mut executor = luna::actions::TaskExecutor::new(app_launcher, file_search)
         .with_event_bus(std::sync::Arc::clone(&event_bus))
-        .with_metrics(std::sync::Arc::clone(&metrics));
+        .with_metrics(std::sync::Arc::clone(&metrics))
+        .with_question_answerer(question_answerer)
+        .with_clipboard_history(clipboard_history)
+        .with_process_optimizer(std::sync::Arc::new(
+            luna::os::process_priority::ProcessOptimizer::new(),
+        ));
+    // Shutdown, commands and renice are confirmed by voice while the plan runs
+    let (confirmation_tx, mut confirmation_rx) = tokio::sync::mpsc::channel(1);
+    executor = executor.with_confirmation_channel(confirmation_tx);
+    if !config.system.skills_path.is_empty() {
+        let skills = luna::actions::SkillRegistry::load_from_file(&config.system.skills_path)?;
+        info!("✓ Loaded {} command skills", skills.len());
+        executor = executor.with_skills(std::sync::Arc::new(skills));
+    }
+    match luna::os::desktop_environment::DesktopEnvironment::detect().await {
+        Ok(environment) => executor = executor.with_desktop_environment(&environment),
+        Err(e) => tracing::warn!("Desktop environment detection failed: {}", e),
+    }
     info!("✓ Task executor initialized");
 
     // TTS System
-    let mut tts_system = match luna::tts::TtsSystem::new() {
+    let mut tts_system = match luna::tts::TtsSystem::with_config(config.tts.clone()) {
         Ok(tts) => {
             let tts = tts.with_event_bus(std::sync::Arc::clone(&event_bus));
             info!("✓ TTS system initialized");
@@ -165,13 +291,27 @@ async fn run_assistant(cli: Cli) -> Result<()> {
     // Start TTS worker if available
     if let Some(ref mut tts) = tts_system {
         tts.start().await?;
+
+        // Answer in the language commands are spoken in
+        let language = &config.brain.language;
+        if luna::brain::Locale::from_code(language) != Some(luna::brain::Locale::English) {
+            if let Err(e) = tts.set_language(language).await {
+                tracing::warn!("Failed to pick a TTS voice for '{}': {}", language, e);
+            }
+        }
     }
 
     // Conversation memory - load from disk if available
     let data_dir = std::path::PathBuf::from(&config.system.data_dir);
     let conversation_path = data_dir.join("conversation.json");
+    let conversation_log_path = data_dir.join("conversation.jsonl");
     
-    let mut conversation_memory = match luna::context::memory::ConversationMemory::load_from_disk(&conversation_path).await {
+    let loaded_memory = luna::context::memory::ConversationMemory::load_with_append_log(
+        &conversation_path,
+        &conversation_log_path,
+    )
+    .await;
+    let conversation_memory = match loaded_memory {
         Ok(memory) => {
             if !memory.is_empty() {
                 info!("✓ Loaded {} conversation entries from disk", memory.len());
@@ -181,8 +321,12 @@ async fn run_assistant(cli: Cli) -> Result<()> {
         Err(e) => {
             tracing::warn!("Failed to load conversation history: {}", e);
             luna::context::memory::ConversationMemory::with_capacity(100)
+                .with_append_log(&conversation_log_path)
         }
     };
+    // Entries that no longer fit are kept in cold storage
+    let mut conversation_memory =
+        conversation_memory.archive_to(data_dir.join("conversation_archive.jsonl"));
 
     info!("✅ All systems ready");
     info!("👂 LUNA is ready to listen!");
@@ -191,12 +335,9 @@ async fn run_assistant(cli: Cli) -> Result<()> {
     metrics.print_summary();
 
     // Publish startup event
-    event_bus
-        .publish(luna::LunaEvent::StateChanged {
-            from: "stopped".to_string(),
-            to: "running".to_string(),
-        })
-        .await;
+    runtime_state
+        .transition_and_publish(RuntimeState::Running, &event_bus)
+        .await?;
 
     // Start audio capture
     audio_system.start_listening()?;
@@ -234,6 +375,24 @@ async fn run_assistant(cli: Cli) -> Result<()> {
     let mut command_count = 0;
 
     while !shutdown_requested.load(std::sync::atomic::Ordering::SeqCst) {
+        if audio_config_changed.swap(false, std::sync::atomic::Ordering::SeqCst) {
+            info!("🔄 Audio configuration changed, rebuilding audio system...");
+            let new_config = config_mgr.get_clone().await;
+            match luna::audio::ProductionAudioSystem::create(&new_config.audio, &new_config.brain)
+                .await
+            {
+                Ok(new_audio) => {
+                    audio_system.stop()?;
+                    audio_system = new_audio
+                        .with_event_bus(std::sync::Arc::clone(&event_bus))
+                        .with_capture_metrics(std::sync::Arc::clone(&metrics));
+                    audio_system.start_listening()?;
+                    info!("✓ Audio system rebuilt");
+                }
+                Err(e) => tracing::error!("Failed to rebuild audio system: {}", e),
+            }
+        }
+
         // Wait for wake word
         match audio_system.wait_for_wake_word().await {
             Ok(true) => {
@@ -274,9 +433,17 @@ async fn run_assistant(cli: Cli) -> Result<()> {
 
                 info!("💬 Command received: \"{}\"", text);
 
+                // Traces this utterance from wake word through execution
+                let correlation_id = audio_system
+                    .correlation_id()
+                    .unwrap_or_else(uuid::Uuid::new_v4);
+
                 // Process command through brain
                 let start_time = std::time::Instant::now();
-                let mut final_plan = match brain.process_async(&text).await {
+                let mut final_plan = match brain
+                    .process_async_with_correlation(&text, correlation_id)
+                    .await
+                {
                     Ok(plan) => plan,
                     Err(e) => {
                         tracing::warn!("⚠️  Command not understood: {}", e);
@@ -301,6 +468,17 @@ async fn run_assistant(cli: Cli) -> Result<()> {
                     final_plan.classification.intent, final_plan.classification.confidence
                 );
 
+                // Cancelled/negated requests are acknowledged without executing anything
+                if final_plan.classification.intent == luna::brain::IntentType::Cancel {
+                    info!("🚫 Command cancelled");
+                    if let Some(ref tts) = tts_system {
+                        let _ = tts
+                            .speak_with(luna::tts::MessageKind::Confirmation, "Okay, cancelled")
+                            .await;
+                    }
+                    continue;
+                }
+
                 // Check confidence and request clarification if needed
                 if final_plan.classification.confidence < config.brain.confidence_threshold {
                     info!(
@@ -324,7 +502,10 @@ async fn run_assistant(cli: Cli) -> Result<()> {
                             info!("💬 Clarification received: \"{}\"", clarification_text);
 
                             // Re-process with clarification
-                            match brain.process_async(&clarification_text).await {
+                            match brain
+                                .process_async_with_correlation(&clarification_text, correlation_id)
+                                .await
+                            {
                                 Ok(new_plan) => {
                                     info!(
                                         "🧠 Clarification processed: {:?} (confidence: {:.2})",
@@ -334,11 +515,14 @@ async fn run_assistant(cli: Cli) -> Result<()> {
 
                                     // Publish clarification event
                                     event_bus
-                                        .publish(luna::LunaEvent::ClarificationAnswered {
-                                            original_command: text.clone(),
-                                            clarification: clarification_text.clone(),
-                                            resolved_command: clarification_text.clone(),
-                                        })
+                                        .publish_with_correlation(
+                                            luna::LunaEvent::ClarificationAnswered {
+                                                original_command: text.clone(),
+                                                clarification: clarification_text.clone(),
+                                                resolved_command: clarification_text.clone(),
+                                            },
+                                            correlation_id,
+                                        )
                                         .await;
 
                                     final_plan = new_plan;
@@ -373,10 +557,112 @@ async fn run_assistant(cli: Cli) -> Result<()> {
                     }
                 }
 
+                // "Open my browser" with no clear favourite: ask which one
+                let category_choice = final_plan
+                    .steps
+                    .iter()
+                    .position(|step| step.action == luna::brain::ActionType::LaunchApp)
+                    .and_then(|index| {
+                        let app_name = final_plan.steps[index].params.get("app_name")?;
+                        Some((index, category_resolver.category_choice(app_name)?))
+                    });
+                if let Some((index, choice)) = category_choice {
+                    let question = choice.question();
+                    info!("❓ {}", question);
+                    if let Some(ref tts) = tts_system {
+                        let _ = tts
+                            .speak_with(luna::tts::MessageKind::Info, &question)
+                            .await;
+                    }
+
+                    let reply = match audio_system.listen_and_transcribe(8).await {
+                        Ok(reply) => reply,
+                        Err(e) => {
+                            tracing::error!("❌ Failed to capture clarification: {}", e);
+                            continue;
+                        }
+                    };
+                    let Some(app) = choice.pick(&reply) else {
+                        info!("⚠️  No app chosen from \"{}\"", reply);
+                        if let Some(ref tts) = tts_system {
+                            let _ = tts
+                                .speak_with(luna::tts::MessageKind::Info, "Okay, never mind")
+                                .await;
+                        }
+                        continue;
+                    };
+
+                    info!("💬 Clarification received: \"{}\" → {}", reply, app.name);
+                    event_bus
+                        .publish_with_correlation(
+                            luna::LunaEvent::ClarificationAnswered {
+                                original_command: text.clone(),
+                                clarification: reply.clone(),
+                                resolved_command: format!("open {}", app.name),
+                            },
+                            correlation_id,
+                        )
+                        .await;
+                    final_plan.steps[index]
+                        .params
+                        .insert("app_name".to_string(), app.name.clone());
+                }
+
                 // Execute the plan
-                match executor.execute_plan(final_plan.clone()).await {
-                    Ok(response) => {
+                let mut result = execute_with_confirmation(
+                    &executor,
+                    final_plan.clone(),
+                    &mut confirmation_rx,
+                    &mut audio_system,
+                    tts_system.as_ref(),
+                )
+                .await;
+
+                // Nothing happened: offer the runner-up interpretation
+                if let Some(suggestion) = executor
+                    .suggest_alternative(&brain, &final_plan, &result)
+                    .await
+                {
+                    info!("❓ {}", suggestion.question);
+                    if let Some(ref tts) = tts_system {
+                        let _ = tts
+                            .speak_with(luna::tts::MessageKind::Info, &suggestion.question)
+                            .await;
+                    }
+
+                    match audio_system.listen_and_transcribe(8).await {
+                        Ok(reply) if suggestion.accepts(&reply) => {
+                            info!("💬 Alternative accepted: \"{}\"", reply);
+                            final_plan = suggestion.plan;
+                            final_plan.correlation_id = Some(correlation_id);
+                            result = execute_with_confirmation(
+                                &executor,
+                                final_plan.clone(),
+                                &mut confirmation_rx,
+                                &mut audio_system,
+                                tts_system.as_ref(),
+                            )
+                            .await;
+                        }
+                        Ok(reply) => info!("⚠️  Alternative declined: \"{}\"", reply),
+                        Err(e) => tracing::error!("❌ Failed to capture clarification: {}", e),
+                    }
+                }
+
+                let intent = final_plan.classification.intent.clone();
+                match result {
+                    Ok(outcome) => {
                         let total_time = start_time.elapsed();
+                        for step in &outcome.steps {
+                            info!(
+                                "   Step {} {:?} ({} ms): {}",
+                                step.step_index, step.action, step.duration_ms, step.message
+                            );
+                        }
+                        let response = outcome
+                            .last_message()
+                            .map(|msg| msg.to_string())
+                            .unwrap_or_else(|| outcome.summary());
                         info!("✅ Action completed in {:?}: {}", total_time, response);
 
                         // Speak response
@@ -397,7 +683,7 @@ async fn run_assistant(cli: Cli) -> Result<()> {
                         );
 
                         // Track metrics
-                        metrics.record_command_success();
+                        metrics.record_command_result(intent, true);
                         metrics.record_latency(
                             luna::metrics::MetricPhase::Parsing,
                             processing_time,
@@ -427,7 +713,7 @@ async fn run_assistant(cli: Cli) -> Result<()> {
                         );
 
                         // Track metrics
-                        metrics.record_command_failure();
+                        metrics.record_command_result(intent, false);
                         metrics.record_error("execution_failed", "executor");
                     }
                 }
@@ -441,15 +727,37 @@ async fn run_assistant(cli: Cli) -> Result<()> {
             }
         }
 
+        // Fold the append log into the snapshot now and then
+        if conversation_memory.needs_compaction() {
+            if let Err(e) = conversation_memory.compact(&conversation_path).await {
+                tracing::warn!("Failed to compact conversation history: {}", e);
+            }
+        }
+
         // Brief sleep to avoid busy loop (wake word detection has its own timing)
         tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
     }
 
     info!("Shutting down gracefully...");
+    runtime_state
+        .transition_and_publish(RuntimeState::Stopping, &event_bus)
+        .await?;
 
     // Stop audio system
     audio_system.stop()?;
 
+    // Stop metrics snapshots
+    if let Some(ref mut reporter) = metrics_reporter {
+        reporter.stop().await?;
+    }
+
+    // Stop focus tracking
+    active_app_watcher.stop().await?;
+
+    // Stop health checks
+    health_task.abort();
+    os_manager.health_monitor.stop_monitoring().await;
+
     // Stop TTS system
     if let Some(ref mut tts) = tts_system {
         tts.stop().await;
@@ -463,12 +771,13 @@ async fn run_assistant(cli: Cli) -> Result<()> {
     }
 
     // Publish shutdown event
-    event_bus
-        .publish(luna::LunaEvent::StateChanged {
-            from: "running".to_string(),
-            to: "stopped".to_string(),
-        })
-        .await;
+    runtime_state
+        .transition_and_publish(RuntimeState::Stopped, &event_bus)
+        .await?;
+
+    // Deliver everything still queued before exiting
+    event_bus.shutdown().await;
+    let _ = event_bus_handle.await;
 
     // Print final statistics
     println!("\n");
@@ -493,6 +802,42 @@ async fn run_assistant(cli: Cli) -> Result<()> {
     Ok(())
 }
 
+/// Execute a plan, asking aloud for any confirmation it needs along the way
+async fn execute_with_confirmation(
+    executor: &luna::actions::TaskExecutor,
+    plan: luna::brain::task_planner::TaskPlan,
+    confirmations: &mut tokio::sync::mpsc::Receiver<luna::actions::ConfirmationRequest>,
+    audio_system: &mut luna::audio::ProductionAudioSystem,
+    tts_system: Option<&luna::tts::TtsSystem>,
+) -> Result<luna::actions::PlanOutcome> {
+    let execution = executor.execute_plan_detailed(plan);
+    tokio::pin!(execution);
+
+    loop {
+        tokio::select! {
+            result = &mut execution => return result,
+            Some((question, reply)) = confirmations.recv() => {
+                info!("❓ {}", question);
+                if let Some(tts) = tts_system {
+                    let _ = tts.speak_with(luna::tts::MessageKind::Info, &question).await;
+                }
+
+                let confirmed = match audio_system.listen_and_transcribe(8).await {
+                    Ok(answer) => {
+                        info!("💬 Confirmation received: \"{}\"", answer);
+                        luna::actions::is_affirmative(&answer)
+                    }
+                    Err(e) => {
+                        tracing::error!("❌ Failed to capture confirmation: {}", e);
+                        false
+                    }
+                };
+                let _ = reply.send(confirmed);
+            }
+        }
+    }
+}
+
 /// Setup minimal logging for CLI commands
 fn setup_minimal_logging(log_level: &str) -> Result<()> {
     let env_filter =
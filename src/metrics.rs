@@ -3,6 +3,9 @@
 //! Tracks performance metrics, success rates, and latency measurements.
 //! Supports both atomic counters and external metrics crate integration.
 
+use crate::brain::IntentType;
+use parking_lot::Mutex;
+use std::collections::HashMap;
 use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::Instant;
@@ -11,6 +14,9 @@ use std::time::Instant;
 #[cfg(feature = "prometheus")]
 pub use metrics::{counter, gauge, histogram};
 
+#[cfg(feature = "http-metrics")]
+pub use http::serve;
+
 /// Performance metrics collector
 pub struct Metrics {
     // Counters
@@ -31,6 +37,32 @@ pub struct Metrics {
     stt_count: AtomicUsize,
     parsing_count: AtomicUsize,
     execution_count: AtomicUsize,
+
+    // Latency distributions, indexed by `MetricPhase::index`
+    histograms: [LatencyHistogram; MetricPhase::ALL.len()],
+
+    // Command outcomes by classified intent
+    intent_results: Mutex<HashMap<IntentType, IntentCounts>>,
+}
+
+/// Command outcomes for a single intent
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct IntentCounts {
+    /// Commands that completed
+    pub succeeded: usize,
+    /// Commands that failed
+    pub failed: usize,
+}
+
+impl IntentCounts {
+    /// Success rate as a percentage
+    pub fn success_rate(&self) -> f64 {
+        let total = self.succeeded + self.failed;
+        if total == 0 {
+            return 0.0;
+        }
+        self.succeeded as f64 / total as f64 * 100.0
+    }
 }
 
 impl Metrics {
@@ -50,6 +82,8 @@ impl Metrics {
             stt_count: AtomicUsize::new(0),
             parsing_count: AtomicUsize::new(0),
             execution_count: AtomicUsize::new(0),
+            histograms: std::array::from_fn(|_| LatencyHistogram::new()),
+            intent_results: Mutex::new(HashMap::new()),
         }
     }
 
@@ -77,6 +111,33 @@ impl Metrics {
         counter!("luna_commands_total", "status" => "failure").increment(1);
     }
 
+    /// Record a command's outcome under its classified intent
+    ///
+    /// Also counts it as an overall success or failure.
+    pub fn record_command_result(&self, intent: IntentType, success: bool) {
+        if success {
+            self.record_command_success();
+        } else {
+            self.record_command_failure();
+        }
+
+        #[cfg(feature = "prometheus")]
+        counter!(
+            "luna_intent_commands_total",
+            "intent" => format!("{:?}", intent),
+            "status" => if success { "success" } else { "failure" }
+        )
+        .increment(1);
+
+        let mut results = self.intent_results.lock();
+        let counts = results.entry(intent).or_default();
+        if success {
+            counts.succeeded += 1;
+        } else {
+            counts.failed += 1;
+        }
+    }
+
     /// Record a wake word detection
     pub fn record_wake_word(&self) {
         self.wake_words_detected.fetch_add(1, Ordering::Relaxed);
@@ -137,6 +198,8 @@ impl Metrics {
         let micros = duration.as_micros() as u64;
         let millis = duration.as_millis() as f64;
 
+        self.histograms[phase.index()].record(micros);
+
         // Record in atomic counters
         match phase {
             MetricPhase::AudioCapture => {
@@ -193,6 +256,19 @@ impl Metrics {
         (succeeded as f64 / processed as f64) * 100.0
     }
 
+    /// Get the success rate of one intent as a percentage
+    pub fn get_intent_success_rate(&self, intent: &IntentType) -> f64 {
+        self.intent_results
+            .lock()
+            .get(intent)
+            .map_or(0.0, IntentCounts::success_rate)
+    }
+
+    /// Get outcome counts for every intent seen so far
+    pub fn get_intent_counts(&self) -> HashMap<IntentType, IntentCounts> {
+        self.intent_results.lock().clone()
+    }
+
     /// Get the number of wake words detected
     pub fn get_wake_words_detected(&self) -> usize {
         self.wake_words_detected.load(Ordering::Relaxed)
@@ -208,6 +284,16 @@ impl Metrics {
         self.commands_failed.load(Ordering::Relaxed)
     }
 
+    /// Get average end-to-end processing latency in milliseconds
+    pub fn get_avg_total_ms(&self) -> f64 {
+        let count = self.commands_processed.load(Ordering::Relaxed);
+        if count > 0 {
+            (self.total_processing_latency.load(Ordering::Relaxed) / count as u64) as f64 / 1000.0
+        } else {
+            0.0
+        }
+    }
+
     /// Get average audio capture latency in milliseconds
     pub fn get_avg_audio_capture_ms(&self) -> f64 {
         let count = self.audio_capture_count.load(Ordering::Relaxed);
@@ -248,6 +334,42 @@ impl Metrics {
         }
     }
 
+    /// Get a latency percentile in milliseconds for a phase
+    ///
+    /// `q` is a quantile in `0.0..=1.0` (e.g. `0.99` for p99). Returns 0.0
+    /// when nothing has been recorded for the phase.
+    pub fn get_percentile(&self, phase: MetricPhase, q: f64) -> f64 {
+        self.histograms[phase.index()].percentile(q)
+    }
+
+    /// Snapshot of all metrics as JSON
+    pub fn to_json(&self) -> serde_json::Value {
+        let percentiles: serde_json::Map<String, serde_json::Value> = MetricPhase::ALL
+            .iter()
+            .map(|&phase| {
+                let values: serde_json::Map<String, serde_json::Value> = REPORTED_PERCENTILES
+                    .iter()
+                    .map(|&q| (percentile_name(q), self.get_percentile(phase, q).into()))
+                    .collect();
+                (phase.label().to_string(), values.into())
+            })
+            .collect();
+
+        serde_json::json!({
+            "commands_processed": self.get_commands_processed(),
+            "commands_succeeded": self.get_commands_succeeded(),
+            "commands_failed": self.get_commands_failed(),
+            "success_rate": self.get_success_rate(),
+            "wake_words_detected": self.get_wake_words_detected(),
+            "avg_audio_capture_ms": self.get_avg_audio_capture_ms(),
+            "avg_stt_ms": self.get_avg_stt_ms(),
+            "avg_parsing_ms": self.get_avg_parsing_ms(),
+            "avg_execution_ms": self.get_avg_execution_ms(),
+            "latency_percentiles_ms": percentiles,
+            "timestamp": chrono::Utc::now().to_rfc3339(),
+        })
+    }
+
     /// Print a summary of collected metrics
     pub fn print_summary(&self) {
         let processed = self.commands_processed.load(Ordering::Relaxed);
@@ -270,6 +392,22 @@ impl Metrics {
             }
         );
 
+        // Worst intents first, the ones most worth improving
+        let mut intents: Vec<_> = self.get_intent_counts().into_iter().collect();
+        if !intents.is_empty() {
+            intents.sort_by(|a, b| a.1.success_rate().total_cmp(&b.1.success_rate()));
+            println!("\n  Success rate by intent:");
+            for (intent, counts) in intents {
+                println!(
+                    "    {:<20} {:.1}% ({}/{})",
+                    format!("{:?}:", intent),
+                    counts.success_rate(),
+                    counts.succeeded,
+                    counts.succeeded + counts.failed
+                );
+            }
+        }
+
         if processed > 0 {
             println!("\n  Average latencies:");
 
@@ -304,6 +442,21 @@ impl Metrics {
                 let avg_exec = self.execution_latency.load(Ordering::Relaxed) / exec_count as u64;
                 println!("    Execution: {}ms", avg_exec / 1000);
             }
+
+            println!("\n  Latency percentiles (p50 / p95 / p99):");
+            for phase in MetricPhase::ALL {
+                if self.histograms[phase.index()].count() == 0 {
+                    continue;
+                }
+                let [p50, p95, p99] = REPORTED_PERCENTILES.map(|q| self.get_percentile(phase, q));
+                println!(
+                    "    {:<10} {:.1}ms / {:.1}ms / {:.1}ms",
+                    format!("{}:", phase.label()),
+                    p50,
+                    p95,
+                    p99
+                );
+            }
         }
         println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
     }
@@ -323,6 +476,10 @@ impl Metrics {
         self.stt_count.store(0, Ordering::Relaxed);
         self.parsing_count.store(0, Ordering::Relaxed);
         self.execution_count.store(0, Ordering::Relaxed);
+        for histogram in &self.histograms {
+            histogram.reset();
+        }
+        self.intent_results.lock().clear();
     }
 }
 
@@ -342,6 +499,134 @@ pub enum MetricPhase {
     Total,
 }
 
+impl MetricPhase {
+    /// Every phase, in reporting order
+    pub const ALL: [MetricPhase; 5] = [
+        MetricPhase::AudioCapture,
+        MetricPhase::SpeechToText,
+        MetricPhase::Parsing,
+        MetricPhase::Execution,
+        MetricPhase::Total,
+    ];
+
+    fn index(self) -> usize {
+        self as usize
+    }
+
+    /// Short name used in summaries and exports
+    pub fn label(self) -> &'static str {
+        match self {
+            MetricPhase::AudioCapture => "audio",
+            MetricPhase::SpeechToText => "stt",
+            MetricPhase::Parsing => "parse",
+            MetricPhase::Execution => "execution",
+            MetricPhase::Total => "total",
+        }
+    }
+}
+
+/// Percentiles shown in summaries and exports
+pub const REPORTED_PERCENTILES: [f64; 3] = [0.50, 0.95, 0.99];
+
+/// Export name of a percentile, e.g. "p95"
+pub fn percentile_name(q: f64) -> String {
+    format!("p{}", (q * 100.0).round())
+}
+
+/// Values below this are stored exactly, one bucket per microsecond
+const LINEAR_BUCKETS: u64 = 16;
+
+/// Sub-buckets per power of two above the linear range (~12.5% resolution)
+const SUB_BUCKET_BITS: u32 = 3;
+
+/// Largest tracked magnitude (2^40 µs, about 12 days); larger values clamp
+const MAX_MAGNITUDE: u32 = 40;
+
+const HISTOGRAM_BUCKETS: usize = LINEAR_BUCKETS as usize
+    + ((MAX_MAGNITUDE - LINEAR_BUCKETS.trailing_zeros() + 1) << SUB_BUCKET_BITS) as usize;
+
+/// Fixed-size, lock-free log-linear histogram of latencies in microseconds
+///
+/// Memory is constant regardless of how many samples are recorded.
+struct LatencyHistogram {
+    buckets: Box<[AtomicU64]>,
+    count: AtomicU64,
+}
+
+impl LatencyHistogram {
+    fn new() -> Self {
+        Self {
+            buckets: (0..HISTOGRAM_BUCKETS).map(|_| AtomicU64::new(0)).collect(),
+            count: AtomicU64::new(0),
+        }
+    }
+
+    fn record(&self, micros: u64) {
+        self.buckets[Self::bucket_index(micros)].fetch_add(1, Ordering::Relaxed);
+        self.count.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn count(&self) -> u64 {
+        self.count.load(Ordering::Relaxed)
+    }
+
+    fn reset(&self) {
+        for bucket in self.buckets.iter() {
+            bucket.store(0, Ordering::Relaxed);
+        }
+        self.count.store(0, Ordering::Relaxed);
+    }
+
+    /// Quantile `q` in milliseconds, using the midpoint of the matching bucket
+    fn percentile(&self, q: f64) -> f64 {
+        let count = self.count();
+        if count == 0 {
+            return 0.0;
+        }
+
+        let rank = ((q.clamp(0.0, 1.0) * count as f64).ceil() as u64).max(1);
+        let mut seen = 0;
+        for (index, bucket) in self.buckets.iter().enumerate() {
+            seen += bucket.load(Ordering::Relaxed);
+            if seen >= rank {
+                let (lower, width) = Self::bucket_range(index);
+                return (lower as f64 + width as f64 / 2.0) / 1000.0;
+            }
+        }
+
+        // Concurrent records can leave `count` ahead of the buckets
+        let (lower, width) = Self::bucket_range(HISTOGRAM_BUCKETS - 1);
+        (lower + width) as f64 / 1000.0
+    }
+
+    fn bucket_index(micros: u64) -> usize {
+        if micros < LINEAR_BUCKETS {
+            return micros as usize;
+        }
+
+        let magnitude = (63 - micros.leading_zeros()).min(MAX_MAGNITUDE);
+        let micros = micros.min((2 << MAX_MAGNITUDE) - 1);
+        let sub = (micros >> (magnitude - SUB_BUCKET_BITS)) as usize & ((1 << SUB_BUCKET_BITS) - 1);
+        let first_magnitude = LINEAR_BUCKETS.trailing_zeros();
+
+        LINEAR_BUCKETS as usize + (((magnitude - first_magnitude) as usize) << SUB_BUCKET_BITS) + sub
+    }
+
+    /// Lower bound and width of a bucket in microseconds
+    fn bucket_range(index: usize) -> (u64, u64) {
+        if index < LINEAR_BUCKETS as usize {
+            return (index as u64, 1);
+        }
+
+        let offset = index - LINEAR_BUCKETS as usize;
+        let magnitude = LINEAR_BUCKETS.trailing_zeros() + (offset >> SUB_BUCKET_BITS) as u32;
+        let sub = (offset & ((1 << SUB_BUCKET_BITS) - 1)) as u64;
+        let shift = magnitude - SUB_BUCKET_BITS;
+
+        (((1 << SUB_BUCKET_BITS) + sub) << shift, 1 << shift)
+    }
+}
+
 /// Helper to automatically measure execution time
 pub struct MetricTimer {
     start: Instant,
@@ -372,6 +657,37 @@ mod tests {
     use super::*;
     use std::time::Duration;
 
+    #[test]
+    fn test_intent_success_rates() {
+        let metrics = Metrics::new();
+
+        metrics.record_command_result(IntentType::LaunchApp, true);
+        metrics.record_command_result(IntentType::LaunchApp, true);
+        metrics.record_command_result(IntentType::FindFile, true);
+        metrics.record_command_result(IntentType::FindFile, false);
+        metrics.record_command_result(IntentType::FindFile, false);
+        metrics.record_command_result(IntentType::FindFile, false);
+
+        assert_eq!(
+            metrics.get_intent_success_rate(&IntentType::LaunchApp),
+            100.0
+        );
+        assert_eq!(metrics.get_intent_success_rate(&IntentType::FindFile), 25.0);
+        assert_eq!(metrics.get_intent_success_rate(&IntentType::GetTime), 0.0);
+        assert_eq!(
+            metrics.get_intent_counts()[&IntentType::FindFile],
+            IntentCounts {
+                succeeded: 1,
+                failed: 3
+            }
+        );
+        assert_eq!(metrics.get_commands_succeeded(), 3);
+        assert_eq!(metrics.get_commands_failed(), 3);
+
+        metrics.reset();
+        assert!(metrics.get_intent_counts().is_empty());
+    }
+
     #[test]
     fn test_metrics_creation() {
         let metrics = Metrics::new();
@@ -408,6 +724,46 @@ mod tests {
         assert_eq!(metrics.stt_count.load(Ordering::Relaxed), 1);
     }
 
+    #[test]
+    fn test_latency_percentiles() {
+        let metrics = Metrics::new();
+
+        // 95 fast transcriptions and a slow tail
+        for _ in 0..95 {
+            metrics.record_latency(MetricPhase::SpeechToText, Duration::from_millis(100));
+        }
+        for _ in 0..5 {
+            metrics.record_latency(MetricPhase::SpeechToText, Duration::from_millis(5000));
+        }
+
+        let p50 = metrics.get_percentile(MetricPhase::SpeechToText, 0.50);
+        let p99 = metrics.get_percentile(MetricPhase::SpeechToText, 0.99);
+
+        // Buckets are accurate to within 12.5%
+        assert!((p50 - 100.0).abs() / 100.0 < 0.125, "p50 was {}", p50);
+        assert!((p99 - 5000.0).abs() / 5000.0 < 0.125, "p99 was {}", p99);
+        assert!(p99 > p50 * 10.0);
+
+        // Other phases are tracked separately
+        assert_eq!(metrics.get_percentile(MetricPhase::Parsing, 0.99), 0.0);
+
+        metrics.reset();
+        assert_eq!(metrics.get_percentile(MetricPhase::SpeechToText, 0.50), 0.0);
+    }
+
+    #[test]
+    fn test_histogram_buckets_cover_range() {
+        for micros in [0, 1, 15, 16, 17, 31, 32, 1_000, 123_456, 1 << 40, u64::MAX] {
+            let index = LatencyHistogram::bucket_index(micros);
+            assert!(index < HISTOGRAM_BUCKETS);
+
+            let (lower, width) = LatencyHistogram::bucket_range(index);
+            if micros < 1 << 41 {
+                assert!(lower <= micros && micros < lower + width, "{}", micros);
+            }
+        }
+    }
+
     #[test]
     fn test_wake_word_tracking() {
         let metrics = Metrics::new();
@@ -538,3 +894,146 @@ pub mod prometheus {
         );
     }
 }
+
+/// Built-in HTTP endpoint for the atomic counters
+///
+/// A lighter alternative to the Prometheus exporter: `GET /metrics` returns
+/// `Metrics::to_json` and `GET /healthz` returns 200.
+#[cfg(feature = "http-metrics")]
+pub mod http {
+    use super::Metrics;
+    use crate::error::{LunaError, Result};
+    use std::net::SocketAddr;
+    use std::sync::Arc;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpListener, TcpStream};
+
+    /// Largest request head read before giving up
+    const MAX_REQUEST_BYTES: usize = 8192;
+
+    /// Serve metrics over HTTP in the background
+    ///
+    /// Returns the bound address, so `127.0.0.1:0` picks a free port.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use luna::metrics::{self, Metrics};
+    /// # use std::sync::Arc;
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let addr = metrics::serve("127.0.0.1:9091", Arc::new(Metrics::new())).await?;
+    /// println!("curl http://{}/metrics", addr);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn serve(addr: &str, metrics: Arc<Metrics>) -> Result<SocketAddr> {
+        let listener = TcpListener::bind(addr)
+            .await
+            .map_err(|e| LunaError::Config(format!("Failed to bind metrics server: {}", e)))?;
+        let local_addr = listener.local_addr()?;
+
+        tokio::spawn(async move {
+            loop {
+                let (stream, _) = match listener.accept().await {
+                    Ok(connection) => connection,
+                    Err(e) => {
+                        tracing::warn!("Metrics server accept failed: {}", e);
+                        continue;
+                    }
+                };
+                let metrics = Arc::clone(&metrics);
+                tokio::spawn(async move {
+                    if let Err(e) = handle(stream, &metrics).await {
+                        tracing::debug!("Metrics request failed: {}", e);
+                    }
+                });
+            }
+        });
+
+        tracing::info!(
+            "📊 Metrics endpoint started on http://{}/metrics",
+            local_addr
+        );
+        Ok(local_addr)
+    }
+
+    /// Answer a single request and close the connection
+    async fn handle(mut stream: TcpStream, metrics: &Metrics) -> std::io::Result<()> {
+        let mut request = Vec::new();
+        let mut buf = [0u8; 1024];
+        while !request.windows(4).any(|w| w == b"\r\n\r\n") && request.len() < MAX_REQUEST_BYTES {
+            let n = stream.read(&mut buf).await?;
+            if n == 0 {
+                break;
+            }
+            request.extend_from_slice(&buf[..n]);
+        }
+
+        let head = String::from_utf8_lossy(&request);
+        let mut parts = head.lines().next().unwrap_or_default().split_whitespace();
+        let method = parts.next().unwrap_or_default();
+        let path = parts.next().unwrap_or_default();
+
+        let (status, content_type, body) = match (method, path) {
+            ("GET", "/metrics") => ("200 OK", "application/json", metrics.to_json().to_string()),
+            ("GET", "/healthz") => ("200 OK", "text/plain", "ok".to_string()),
+            ("GET", _) => ("404 Not Found", "text/plain", "not found".to_string()),
+            _ => (
+                "405 Method Not Allowed",
+                "text/plain",
+                "method not allowed".to_string(),
+            ),
+        };
+
+        let response = format!(
+            "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status,
+            content_type,
+            body.len(),
+            body
+        );
+        stream.write_all(response.as_bytes()).await?;
+        stream.shutdown().await
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        async fn get(addr: SocketAddr, path: &str) -> (String, String) {
+            let mut stream = TcpStream::connect(addr).await.unwrap();
+            let request = format!("GET {} HTTP/1.1\r\nHost: localhost\r\n\r\n", path);
+            stream.write_all(request.as_bytes()).await.unwrap();
+
+            let mut response = String::new();
+            stream.read_to_string(&mut response).await.unwrap();
+            let (head, body) = response.split_once("\r\n\r\n").unwrap();
+            (head.lines().next().unwrap().to_string(), body.to_string())
+        }
+
+        #[tokio::test]
+        async fn test_serves_metrics_json() {
+            let metrics = Arc::new(Metrics::new());
+            metrics.record_command_processed();
+            metrics.record_command_success();
+            metrics.record_wake_word();
+
+            let addr = serve("127.0.0.1:0", Arc::clone(&metrics)).await.unwrap();
+
+            let (status, body) = get(addr, "/metrics").await;
+            assert_eq!(status, "HTTP/1.1 200 OK");
+            let json: serde_json::Value = serde_json::from_str(&body).unwrap();
+            assert_eq!(json["commands_processed"], 1);
+            assert_eq!(json["commands_succeeded"], 1);
+            assert_eq!(json["wake_words_detected"], 1);
+            assert_eq!(json["success_rate"], 100.0);
+            assert!(json["latency_percentiles_ms"]["total"]["p95"].is_number());
+
+            let (status, body) = get(addr, "/healthz").await;
+            assert_eq!(status, "HTTP/1.1 200 OK");
+            assert_eq!(body, "ok");
+
+            let (status, _) = get(addr, "/missing").await;
+            assert_eq!(status, "HTTP/1.1 404 Not Found");
+        }
+    }
+}
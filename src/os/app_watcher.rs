@@ -1,10 +1,25 @@
 //! Real-Time Application Change Detection (GOD-LEVEL)
 
-use crate::error::Result;
+use crate::brain::ConversationContext;
+use crate::context::StateManager;
+use crate::error::{LunaError, Result};
+use crate::events::{EventBus, LunaEvent};
+use crate::runtime::Lifecycle;
+use async_trait::async_trait;
 use notify::{Event, RecursiveMode, Watcher};
+use parking_lot::{Mutex, RwLock};
 use std::path::PathBuf;
-use tokio::sync::mpsc;
-use tracing::info;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{mpsc, oneshot};
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+/// Custom event type published when focus moves to another app
+pub const ACTIVE_APP_CHANGED: &str = "active_app_changed";
+
+/// Default interval between focus checks
+pub const DEFAULT_FOCUS_POLL_INTERVAL: Duration = Duration::from_millis(750);
 
 #[derive(Debug, Clone)]
 pub enum AppChange {
@@ -78,3 +93,318 @@ impl Default for AppWatcher {
         Self::new()
     }
 }
+
+/// Source of the currently focused application
+pub trait FocusSource: Send + Sync {
+    /// Name of the focused application, or `None` if it can't be determined
+    fn focused_app(&self) -> Option<String>;
+}
+
+/// Focus lookup through platform tools
+#[derive(Debug, Default)]
+pub struct SystemFocusSource;
+
+impl FocusSource for SystemFocusSource {
+    #[cfg(target_os = "linux")]
+    fn focused_app(&self) -> Option<String> {
+        let output = std::process::Command::new("xdotool")
+            .args(["getactivewindow", "getwindowpid"])
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        let pid = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        let comm = std::fs::read_to_string(format!("/proc/{}/comm", pid)).ok()?;
+        non_empty(&comm)
+    }
+
+    #[cfg(target_os = "macos")]
+    fn focused_app(&self) -> Option<String> {
+        let output = std::process::Command::new("osascript")
+            .args([
+                "-e",
+                "tell application \"System Events\" to get name of first process whose frontmost is true",
+            ])
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        non_empty(&String::from_utf8_lossy(&output.stdout))
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "macos")))]
+    fn focused_app(&self) -> Option<String> {
+        None
+    }
+}
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn non_empty(name: &str) -> Option<String> {
+    let name = name.trim();
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+/// Shared state updated on every focus change
+struct FocusTracker {
+    source: Arc<dyn FocusSource>,
+    context: Arc<RwLock<ConversationContext>>,
+    state: Option<Arc<RwLock<StateManager>>>,
+    event_bus: Option<Arc<EventBus>>,
+    last_app: Mutex<Option<String>>,
+}
+
+impl FocusTracker {
+    async fn poll(&self) -> Option<String> {
+        let source = Arc::clone(&self.source);
+        let focused = tokio::task::spawn_blocking(move || source.focused_app())
+            .await
+            .ok()
+            .flatten()?;
+
+        let previous = {
+            let mut last = self.last_app.lock();
+            if last.as_deref() == Some(focused.as_str()) {
+                return None;
+            }
+            last.replace(focused.clone())
+        };
+
+        debug!("Focus moved to {}", focused);
+        self.context.write().set_active_app(focused.clone());
+        if let Some(state) = &self.state {
+            state.write().set_active_app(focused.clone());
+        }
+        if let Some(bus) = &self.event_bus {
+            bus.publish(LunaEvent::Custom {
+                event_type: ACTIVE_APP_CHANGED.to_string(),
+                data: serde_json::json!({ "app": focused, "previous": previous }),
+            })
+            .await;
+        }
+
+        Some(focused)
+    }
+}
+
+/// Watches window focus and keeps the active app in context up to date
+///
+/// Each change updates the conversation context (and the state manager, if
+/// attached) and publishes a custom `active_app_changed` event.
+pub struct ActiveAppWatcher {
+    tracker: Arc<FocusTracker>,
+    interval: Duration,
+    shutdown_tx: Option<oneshot::Sender<()>>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl ActiveAppWatcher {
+    /// Create a watcher using the platform focus source
+    pub fn new(context: Arc<RwLock<ConversationContext>>) -> Self {
+        Self::with_source(context, Arc::new(SystemFocusSource))
+    }
+
+    /// Create a watcher reading focus from `source`
+    pub fn with_source(
+        context: Arc<RwLock<ConversationContext>>,
+        source: Arc<dyn FocusSource>,
+    ) -> Self {
+        Self {
+            tracker: Arc::new(FocusTracker {
+                source,
+                context,
+                state: None,
+                event_bus: None,
+                last_app: Mutex::new(None),
+            }),
+            interval: DEFAULT_FOCUS_POLL_INTERVAL,
+            shutdown_tx: None,
+            handle: None,
+        }
+    }
+
+    /// Also record focus changes in a state manager
+    pub fn with_state_manager(mut self, state: Arc<RwLock<StateManager>>) -> Self {
+        self.tracker_mut().state = Some(state);
+        self
+    }
+
+    /// Publish focus changes on the event bus
+    pub fn with_event_bus(mut self, event_bus: Arc<EventBus>) -> Self {
+        self.tracker_mut().event_bus = Some(event_bus);
+        self
+    }
+
+    /// Set how often focus is checked
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Check focus once, returning the new app if it changed
+    pub async fn poll(&self) -> Option<String> {
+        self.tracker.poll().await
+    }
+
+    /// Most recently observed focused app
+    pub fn active_app(&self) -> Option<String> {
+        self.tracker.last_app.lock().clone()
+    }
+
+    fn tracker_mut(&mut self) -> &mut FocusTracker {
+        Arc::get_mut(&mut self.tracker).expect("configure the watcher before starting it")
+    }
+}
+
+#[async_trait]
+impl Lifecycle for ActiveAppWatcher {
+    fn name(&self) -> &str {
+        "active_app_watcher"
+    }
+
+    async fn start(&mut self) -> Result<()> {
+        if self.interval.is_zero() {
+            return Err(LunaError::Config(
+                "Focus poll interval must be greater than zero".to_string(),
+            ));
+        }
+        if self.handle.is_some() {
+            return Ok(());
+        }
+
+        let (shutdown_tx, mut shutdown_rx) = oneshot::channel();
+        let tracker = Arc::clone(&self.tracker);
+        let interval = self.interval;
+
+        info!("Watching application focus every {:?}", interval);
+        self.handle = Some(tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                tokio::select! {
+                    _ = ticker.tick() => {
+                        tracker.poll().await;
+                    }
+                    _ = &mut shutdown_rx => break,
+                }
+            }
+        }));
+        self.shutdown_tx = Some(shutdown_tx);
+
+        Ok(())
+    }
+
+    async fn stop(&mut self) -> Result<()> {
+        if let Some(tx) = self.shutdown_tx.take() {
+            let _ = tx.send(());
+        }
+        if let Some(handle) = self.handle.take() {
+            if let Err(e) = handle.await {
+                warn!("Focus watcher task ended abnormally: {}", e);
+            }
+        }
+        Ok(())
+    }
+
+    fn is_running(&self) -> bool {
+        self.handle.as_ref().is_some_and(|h| !h.is_finished())
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod mock {
+    use super::*;
+    use std::collections::VecDeque;
+
+    /// Focus source replaying a scripted sequence, then repeating the last app
+    pub struct MockFocusSource {
+        sequence: Mutex<VecDeque<Option<String>>>,
+        current: Mutex<Option<String>>,
+    }
+
+    impl MockFocusSource {
+        pub fn new(apps: &[Option<&str>]) -> Self {
+            Self {
+                sequence: Mutex::new(apps.iter().map(|app| app.map(str::to_string)).collect()),
+                current: Mutex::new(None),
+            }
+        }
+    }
+
+    impl FocusSource for MockFocusSource {
+        fn focused_app(&self) -> Option<String> {
+            match self.sequence.lock().pop_front() {
+                Some(next) => {
+                    if next.is_some() {
+                        *self.current.lock() = next.clone();
+                    }
+                    next
+                }
+                None => self.current.lock().clone(),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::mock::MockFocusSource;
+    use super::*;
+    use crate::events::EventKind;
+
+    #[tokio::test]
+    async fn test_focus_changes_update_context() {
+        let bus = Arc::new(EventBus::new());
+        let bus_handle = bus.start_processing().await;
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let events_clone = events.clone();
+        bus.subscribe_kinds(&[EventKind::Custom], move |envelope| {
+            if let LunaEvent::Custom { event_type, data } = &envelope.event {
+                if event_type == ACTIVE_APP_CHANGED {
+                    events_clone.lock().push(data.clone());
+                }
+            }
+        })
+        .await;
+
+        let context = Arc::new(RwLock::new(ConversationContext::new()));
+        let state = Arc::new(RwLock::new(StateManager::new()));
+        let source = MockFocusSource::new(&[Some("firefox"), Some("firefox"), None, Some("code")]);
+        let watcher = ActiveAppWatcher::with_source(context.clone(), Arc::new(source))
+            .with_state_manager(state.clone())
+            .with_event_bus(bus.clone());
+
+        assert_eq!(watcher.poll().await.as_deref(), Some("firefox"));
+        assert_eq!(watcher.poll().await, None);
+        assert_eq!(watcher.poll().await, None);
+        assert_eq!(context.read().active_app(), Some("firefox"));
+
+        assert_eq!(watcher.poll().await.as_deref(), Some("code"));
+        assert_eq!(context.read().active_app(), Some("code"));
+        assert_eq!(state.read().get_active_app(), Some("code"));
+
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        let events = events.lock().clone();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[1]["app"], "code");
+        assert_eq!(events[1]["previous"], "firefox");
+
+        bus_handle.abort();
+    }
+
+    #[tokio::test]
+    async fn test_watcher_lifecycle() {
+        let context = Arc::new(RwLock::new(ConversationContext::new()));
+        let source = MockFocusSource::new(&[Some("terminal")]);
+        let mut watcher = ActiveAppWatcher::with_source(context.clone(), Arc::new(source))
+            .with_interval(Duration::from_millis(10));
+
+        watcher.start().await.unwrap();
+        assert!(watcher.is_running());
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        watcher.stop().await.unwrap();
+
+        assert!(!watcher.is_running());
+        assert_eq!(context.read().active_app(), Some("terminal"));
+    }
+}
@@ -1,10 +1,24 @@
 //! Advanced Clipboard Manager (GOD-LEVEL)
+//!
+//! `ClipboardHistory` remembers recent text copies so they can be recalled
+//! by position ("paste the thing before last").
 
 use crate::error::Result;
+use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tracing::debug;
 use uuid::Uuid;
 
+/// Entries kept by default
+pub const DEFAULT_HISTORY_SIZE: usize = 50;
+
+/// Longer copies are truncated before being stored
+pub const MAX_ENTRY_CHARS: usize = 10_000;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ClipboardEntry {
     pub id: Uuid,
@@ -14,6 +28,16 @@ pub struct ClipboardEntry {
     pub format: ClipboardFormat,
 }
 
+impl ClipboardEntry {
+    /// Text of the entry, if it holds text
+    pub fn text(&self) -> Option<&str> {
+        match &self.content {
+            ClipboardContent::Text(text) => Some(text),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ClipboardContent {
     Text(String),
@@ -96,3 +120,145 @@ impl Default for ClipboardManager {
         Self::new(100)
     }
 }
+
+/// Bounded history of text copies, newest first
+pub struct ClipboardHistory {
+    entries: RwLock<VecDeque<ClipboardEntry>>,
+    capacity: usize,
+    max_entry_chars: usize,
+}
+
+impl ClipboardHistory {
+    /// Create a history keeping up to `capacity` entries
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: RwLock::new(VecDeque::with_capacity(capacity)),
+            capacity: capacity.max(1),
+            max_entry_chars: MAX_ENTRY_CHARS,
+        }
+    }
+
+    /// Truncate stored entries to `max_chars` characters
+    pub fn with_max_entry_chars(mut self, max_chars: usize) -> Self {
+        self.max_entry_chars = max_chars;
+        self
+    }
+
+    /// Record a copy
+    ///
+    /// Blank text and a repeat of the newest entry are ignored. Returns
+    /// whether an entry was added.
+    pub fn push(&self, text: &str) -> bool {
+        if text.trim().is_empty() {
+            return false;
+        }
+        let text = match text.char_indices().nth(self.max_entry_chars) {
+            Some((cut, _)) => &text[..cut],
+            None => text,
+        };
+
+        let mut entries = self.entries.write();
+        if entries.front().and_then(ClipboardEntry::text) == Some(text) {
+            return false;
+        }
+
+        entries.push_front(ClipboardEntry {
+            id: Uuid::new_v4(),
+            content: ClipboardContent::Text(text.to_string()),
+            timestamp: chrono::Utc::now(),
+            source_app: None,
+            format: ClipboardFormat::PlainText,
+        });
+        entries.truncate(self.capacity);
+        true
+    }
+
+    /// Entry by position, 0 being the most recent copy
+    pub fn get(&self, index: usize) -> Option<ClipboardEntry> {
+        self.entries.read().get(index).cloned()
+    }
+
+    /// All entries, newest first
+    pub fn list(&self) -> Vec<ClipboardEntry> {
+        self.entries.read().iter().cloned().collect()
+    }
+
+    /// Number of entries
+    pub fn len(&self) -> usize {
+        self.entries.read().len()
+    }
+
+    /// Check if nothing has been recorded
+    pub fn is_empty(&self) -> bool {
+        self.entries.read().is_empty()
+    }
+
+    /// Poll the system clipboard, recording copies made in other apps
+    pub fn spawn_polling(self: &Arc<Self>, interval: Duration) -> JoinHandle<()> {
+        let history = Arc::clone(self);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                match crate::actions::clipboard::read_clipboard().await {
+                    Ok(text) => {
+                        history.push(&text);
+                    }
+                    Err(e) => debug!("Clipboard poll failed: {}", e),
+                }
+            }
+        })
+    }
+}
+
+impl Default for ClipboardHistory {
+    fn default() -> Self {
+        Self::new(DEFAULT_HISTORY_SIZE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts(history: &ClipboardHistory) -> Vec<String> {
+        history
+            .list()
+            .iter()
+            .filter_map(|entry| entry.text().map(str::to_string))
+            .collect()
+    }
+
+    #[test]
+    fn test_push_is_bounded() {
+        let history = ClipboardHistory::new(3);
+        for text in ["one", "two", "three", "four"] {
+            assert!(history.push(text));
+        }
+
+        assert_eq!(texts(&history), ["four", "three", "two"]);
+        assert!(!history.push("   "));
+    }
+
+    #[test]
+    fn test_consecutive_duplicates_are_skipped() {
+        let history = ClipboardHistory::new(10);
+        history.push("hello");
+        assert!(!history.push("hello"));
+        history.push("world");
+        assert!(history.push("hello"));
+
+        assert_eq!(texts(&history), ["hello", "world", "hello"]);
+    }
+
+    #[test]
+    fn test_recall_by_index() {
+        let history = ClipboardHistory::new(10).with_max_entry_chars(5);
+        history.push("first");
+        history.push("second copy");
+
+        assert_eq!(history.get(0).unwrap().text(), Some("secon"));
+        assert_eq!(history.get(1).unwrap().text(), Some("first"));
+        assert!(history.get(2).is_none());
+    }
+}
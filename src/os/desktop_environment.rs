@@ -1,4 +1,7 @@
 //! Desktop Environment Intelligence (GOD-LEVEL)
+//!
+//! Detection results drive which command-line tools window and audio
+//! commands use; see `DesktopEnvironment::select_backends`.
 
 use crate::error::Result;
 use serde::{Deserialize, Serialize};
@@ -17,6 +20,60 @@ pub enum DEType {
     Other(String),
 }
 
+/// Display server protocol of the graphical session
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SessionType {
+    X11,
+    Wayland,
+    Unknown,
+}
+
+impl SessionType {
+    /// Detect from `XDG_SESSION_TYPE`, falling back to `WAYLAND_DISPLAY`
+    pub fn detect() -> Self {
+        match std::env::var("XDG_SESSION_TYPE").as_deref() {
+            Ok("wayland") => Self::Wayland,
+            Ok("x11") => Self::X11,
+            _ if std::env::var_os("WAYLAND_DISPLAY").is_some() => Self::Wayland,
+            _ if std::env::var_os("DISPLAY").is_some() => Self::X11,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+/// Tool used for window commands
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WindowBackend {
+    /// EWMH via `wmctrl` (X11 and XWayland windows)
+    Wmctrl,
+    /// `xdotool` (X11 only)
+    Xdotool,
+    /// `wlrctl` for wlroots compositors (Sway, Hyprland, ...)
+    Wlrctl,
+    /// The platform's own API (Windows, macOS)
+    Native,
+    /// No usable tool found
+    Unsupported,
+}
+
+/// Tool used for volume commands
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AudioBackend {
+    /// PulseAudio or PipeWire via `pactl`
+    Pactl,
+    /// ALSA mixer via `amixer`
+    Amixer,
+    /// The platform's own API (Windows, macOS)
+    Native,
+}
+
+/// Backends chosen for the detected environment
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackendSelection {
+    pub window: WindowBackend,
+    pub audio: AudioBackend,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DEFeatures {
     pub has_system_tray: bool,
@@ -43,10 +100,14 @@ pub struct ToolAvailability {
     pub xdotool: bool,
     pub qdbus: bool,
     pub gnome_shell: bool,
+    pub wlrctl: bool,
+    pub pactl: bool,
+    pub amixer: bool,
 }
 
 pub struct DesktopEnvironment {
     pub de_type: DEType,
+    pub session: SessionType,
     pub compositor: Option<Compositor>,
     pub features: DEFeatures,
     pub tools: ToolAvailability,
@@ -73,6 +134,7 @@ impl DesktopEnvironment {
 
         Ok(Self {
             de_type,
+            session: SessionType::detect(),
             compositor: None,
             features,
             tools,
@@ -111,9 +173,72 @@ impl DesktopEnvironment {
             xdotool: Self::check_command("xdotool").await,
             qdbus: Self::check_command("qdbus").await,
             gnome_shell: Self::check_command("gnome-shell").await,
+            wlrctl: Self::check_command("wlrctl").await,
+            pactl: Self::check_command("pactl").await,
+            amixer: Self::check_command("amixer").await,
         }
     }
 
+    /// Pick window and audio tools for this environment
+    ///
+    /// Windows and macOS use their native APIs. On Linux, Wayland sessions
+    /// prefer `wlrctl` except under GNOME and KDE, whose compositors don't
+    /// speak the wlroots protocols; `wmctrl` then `xdotool` follow, which
+    /// still reach XWayland windows. Audio prefers `pactl` (PulseAudio and
+    /// PipeWire) over `amixer`.
+    pub fn select_backends(&self) -> BackendSelection {
+        if matches!(
+            self.de_type,
+            DEType::Windows10 | DEType::Windows11 | DEType::MacOS(_)
+        ) {
+            return BackendSelection {
+                window: WindowBackend::Native,
+                audio: AudioBackend::Native,
+            };
+        }
+
+        let wlroots = self.session == SessionType::Wayland
+            && !matches!(self.de_type, DEType::Gnome(_) | DEType::KDE(_));
+        let chain = if wlroots {
+            [
+                WindowBackend::Wlrctl,
+                WindowBackend::Wmctrl,
+                WindowBackend::Xdotool,
+            ]
+        } else {
+            [
+                WindowBackend::Wmctrl,
+                WindowBackend::Xdotool,
+                WindowBackend::Wlrctl,
+            ]
+        };
+        let window = chain
+            .into_iter()
+            .find(|backend| match backend {
+                WindowBackend::Wmctrl => self.tools.wmctrl,
+                WindowBackend::Xdotool => {
+                    // xdotool can't see native Wayland windows
+                    self.tools.xdotool && self.session != SessionType::Wayland
+                }
+                WindowBackend::Wlrctl => self.tools.wlrctl,
+                _ => false,
+            })
+            .unwrap_or(WindowBackend::Unsupported);
+
+        let audio = if self.tools.pactl || !self.tools.amixer {
+            AudioBackend::Pactl
+        } else {
+            AudioBackend::Amixer
+        };
+
+        let selection = BackendSelection { window, audio };
+        info!(
+            "Using {:?} for windows and {:?} for audio on {:?} ({:?})",
+            selection.window, selection.audio, self.de_type, self.session
+        );
+        selection
+    }
+
     async fn check_command(cmd: &str) -> bool {
         std::process::Command::new("which")
             .arg(cmd)
@@ -148,3 +273,76 @@ impl DesktopEnvironment {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn environment(de_type: DEType, session: SessionType, tools: &[&str]) -> DesktopEnvironment {
+        let has = |tool: &str| tools.contains(&tool);
+        DesktopEnvironment {
+            features: DesktopEnvironment::detect_features(&de_type),
+            de_type,
+            session,
+            compositor: None,
+            tools: ToolAvailability {
+                wmctrl: has("wmctrl"),
+                xdotool: has("xdotool"),
+                qdbus: has("qdbus"),
+                gnome_shell: has("gnome-shell"),
+                wlrctl: has("wlrctl"),
+                pactl: has("pactl"),
+                amixer: has("amixer"),
+            },
+        }
+    }
+
+    #[test]
+    fn test_x11_prefers_wmctrl_then_xdotool() {
+        let all = ["wmctrl", "xdotool", "wlrctl", "pactl", "amixer"];
+        let selection = environment(DEType::XFCE, SessionType::X11, &all).select_backends();
+        assert_eq!(selection.window, WindowBackend::Wmctrl);
+        assert_eq!(selection.audio, AudioBackend::Pactl);
+
+        let selection =
+            environment(DEType::XFCE, SessionType::X11, &["xdotool", "amixer"]).select_backends();
+        assert_eq!(selection.window, WindowBackend::Xdotool);
+        assert_eq!(selection.audio, AudioBackend::Amixer);
+    }
+
+    #[test]
+    fn test_wayland_selection() {
+        let tools = ["wmctrl", "xdotool", "wlrctl", "pactl"];
+        let sway = DEType::Other("sway".to_string());
+        assert_eq!(
+            environment(sway.clone(), SessionType::Wayland, &tools)
+                .select_backends()
+                .window,
+            WindowBackend::Wlrctl
+        );
+
+        // GNOME's compositor has no wlroots protocols; fall back to XWayland tools
+        let gnome = DEType::Gnome("45".to_string());
+        assert_eq!(
+            environment(gnome, SessionType::Wayland, &tools)
+                .select_backends()
+                .window,
+            WindowBackend::Wmctrl
+        );
+
+        // xdotool alone can't reach Wayland windows
+        assert_eq!(
+            environment(sway, SessionType::Wayland, &["xdotool"])
+                .select_backends()
+                .window,
+            WindowBackend::Unsupported
+        );
+    }
+
+    #[test]
+    fn test_native_platforms() {
+        let selection = environment(DEType::Windows11, SessionType::Unknown, &[]).select_backends();
+        assert_eq!(selection.window, WindowBackend::Native);
+        assert_eq!(selection.audio, AudioBackend::Native);
+    }
+}
@@ -57,7 +57,7 @@ async fn discover_linux_apps() -> Result<Vec<Application>> {
             for entry in entries.flatten() {
                 let path = entry.path();
                 if path.extension().and_then(|s| s.to_str()) == Some("desktop") {
-                    if let Ok(app) = parse_linux_desktop_file(&path) {
+                    if let Ok(Some(app)) = parse_linux_desktop_file(&path) {
                         apps.push(app);
                     }
                 }
@@ -88,19 +88,34 @@ async fn discover_linux_apps() -> Result<Vec<Application>> {
 }
 
 /// Parse a Linux .desktop file
+///
+/// Returns `Ok(None)` for entries that shouldn't be listed.
 #[cfg(target_os = "linux")]
-fn parse_linux_desktop_file(path: &std::path::Path) -> Result<Application> {
+fn parse_linux_desktop_file(path: &std::path::Path) -> Result<Option<Application>> {
     use crate::error::LunaError;
     use std::fs;
 
     let contents = fs::read_to_string(path)
         .map_err(|e| LunaError::SystemOperation(format!("Failed to read desktop file: {}", e)))?;
 
+    parse_desktop_entry(&contents)
+}
+
+/// Parse the `[Desktop Entry]` group of a .desktop file
+///
+/// Entries marked `NoDisplay` or `Hidden`, and entries whose `Type` isn't
+/// `Application`, yield `Ok(None)`.
+#[cfg(target_os = "linux")]
+fn parse_desktop_entry(contents: &str) -> Result<Option<Application>> {
+    use crate::error::LunaError;
+
     let mut name = String::new();
     let mut exec = String::new();
     let mut icon = None;
     let mut categories = Vec::new();
     let mut comment = None;
+    let mut entry_type = None;
+    let mut hidden = false;
     let mut in_desktop_entry = false;
 
     for line in contents.lines() {
@@ -116,16 +131,19 @@ fn parse_linux_desktop_file(path: &std::path::Path) -> Result<Application> {
             continue;
         }
 
-        if !in_desktop_entry {
+        if !in_desktop_entry || line.starts_with('#') {
             continue;
         }
 
         if let Some((key, value)) = line.split_once('=') {
+            let value = value.trim();
             match key.trim() {
-                "Name" => name = value.trim().to_string(),
-                "Exec" => exec = value.trim().to_string(),
-                "Icon" => icon = Some(PathBuf::from(value.trim())),
-                "Comment" => comment = Some(value.trim().to_string()),
+                "Name" => name = value.to_string(),
+                "Exec" => exec = value.to_string(),
+                "Icon" if !value.is_empty() => icon = Some(PathBuf::from(value)),
+                "Comment" if !value.is_empty() => comment = Some(value.to_string()),
+                "Type" => entry_type = Some(value.to_string()),
+                "NoDisplay" | "Hidden" => hidden |= value == "true",
                 "Categories" => {
                     categories = value
                         .split(';')
@@ -138,45 +156,145 @@ fn parse_linux_desktop_file(path: &std::path::Path) -> Result<Application> {
         }
     }
 
-    if name.is_empty() || exec.is_empty() {
+    if hidden || entry_type.is_some_and(|t| t != "Application") {
+        return Ok(None);
+    }
+
+    let mut command = split_exec(&exec);
+    if name.is_empty() || command.is_empty() {
         return Err(LunaError::SystemOperation(
             "Invalid desktop file: missing Name or Exec".to_string(),
         ));
     }
 
-    // Extract executable path (remove arguments)
-    let exec_path = exec
-        .split_whitespace()
-        .next()
-        .unwrap_or(&exec)
-        .trim_matches('"');
-
-    let mut app = Application::new(name, PathBuf::from(exec_path));
+    let mut app = Application::new(name, PathBuf::from(command.remove(0)));
+    app.launch_args = command;
     app.category = categorize_linux_app(&categories);
     app.icon = icon;
     app.description = comment;
 
-    Ok(app)
+    Ok(Some(app))
 }
 
-/// Categorize Linux app based on desktop categories
+/// Split an `Exec` value into a program and its arguments
+///
+/// Quoted arguments are unescaped, field codes such as `%U` and `%f` are
+/// dropped since LUNA launches without files or URLs, and a leading
+/// `env VAR=value` prefix is skipped.
 #[cfg(target_os = "linux")]
-fn categorize_linux_app(categories: &[String]) -> AppCategory {
-    for cat in categories {
-        match cat.as_str() {
-            "WebBrowser" | "Browser" => return AppCategory::Browser,
-            "Development" | "IDE" => return AppCategory::IDE,
-            "TextEditor" | "Editor" => return AppCategory::TextEditor,
-            "TerminalEmulator" | "Terminal" => return AppCategory::Terminal,
-            "AudioVideo" | "Audio" | "Video" => return AppCategory::Media,
-            "Network" | "InstantMessaging" | "Chat" => return AppCategory::Communication,
-            "Office" => return AppCategory::Office,
-            "Game" => return AppCategory::Games,
-            "System" | "Utility" => return AppCategory::System,
-            _ => continue,
+fn split_exec(exec: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut has_token = false;
+    let mut in_quotes = false;
+    let mut chars = exec.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                in_quotes = !in_quotes;
+                has_token = true;
+            }
+            '\\' if in_quotes => {
+                if let Some(escaped) = chars.next() {
+                    current.push(escaped);
+                }
+            }
+            c if c.is_whitespace() && !in_quotes => {
+                if has_token {
+                    tokens.push(std::mem::take(&mut current));
+                    has_token = false;
+                }
+            }
+            c => {
+                current.push(c);
+                has_token = true;
+            }
         }
     }
-    AppCategory::Other
+    if has_token {
+        tokens.push(current);
+    }
+
+    let mut tokens: Vec<String> = tokens
+        .iter()
+        // Flatpak wraps file arguments in @@ markers
+        .filter(|t| !matches!(t.as_str(), "@@" | "@@u"))
+        .filter_map(|t| strip_field_codes(t))
+        .collect();
+
+    if tokens.first().is_some_and(|t| t == "env") {
+        let vars = tokens[1..].iter().take_while(|t| t.contains('=')).count();
+        tokens.drain(..=vars);
+    }
+
+    tokens
+}
+
+/// Remove field codes from an `Exec` argument, unescaping `%%`
+///
+/// Returns `None` if the argument was nothing but field codes.
+#[cfg(target_os = "linux")]
+fn strip_field_codes(token: &str) -> Option<String> {
+    let mut out = String::with_capacity(token.len());
+    let mut stripped = false;
+    let mut chars = token.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('%') | None => out.push('%'),
+            Some(_) => stripped = true,
+        }
+    }
+
+    if stripped && out.is_empty() {
+        None
+    } else {
+        Some(out)
+    }
+}
+
+/// Categorize Linux app based on desktop categories
+///
+/// Specific categories win over the broad main categories they usually
+/// appear with, so `Network;WebBrowser;` is a browser.
+#[cfg(target_os = "linux")]
+fn categorize_linux_app(categories: &[String]) -> AppCategory {
+    const MAPPING: &[(&str, AppCategory)] = &[
+        ("WebBrowser", AppCategory::Browser),
+        ("IDE", AppCategory::IDE),
+        ("TextEditor", AppCategory::TextEditor),
+        ("TerminalEmulator", AppCategory::Terminal),
+        ("InstantMessaging", AppCategory::Communication),
+        ("Chat", AppCategory::Communication),
+        ("Email", AppCategory::Communication),
+        ("VideoConference", AppCategory::Communication),
+        ("WordProcessor", AppCategory::Office),
+        ("Spreadsheet", AppCategory::Office),
+        ("Presentation", AppCategory::Office),
+        ("Player", AppCategory::Media),
+        ("Development", AppCategory::IDE),
+        ("AudioVideo", AppCategory::Media),
+        ("Audio", AppCategory::Media),
+        ("Video", AppCategory::Media),
+        ("Graphics", AppCategory::Media),
+        ("Office", AppCategory::Office),
+        ("Game", AppCategory::Games),
+        ("Network", AppCategory::Communication),
+        ("System", AppCategory::System),
+        ("Settings", AppCategory::System),
+        ("Utility", AppCategory::System),
+    ];
+
+    MAPPING
+        .iter()
+        .find(|(name, _)| categories.iter().any(|c| c == name))
+        .map(|(_, category)| category.clone())
+        .unwrap_or(AppCategory::Other)
 }
 
 /// Discover applications on Windows
@@ -426,3 +544,103 @@ fn categorize_macos_app(name: &str) -> AppCategory {
         AppCategory::Other
     }
 }
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_desktop_entry() {
+        let contents = "\
+[Desktop Entry]
+Version=1.0
+Type=Application
+Name=Firefox
+Name[de]=Firefox Webbrowser
+Comment=Browse the World Wide Web
+Exec=/usr/lib/firefox/firefox %u
+Icon=firefox
+Categories=GNOME;GTK;Network;WebBrowser;
+
+[Desktop Action new-window]
+Name=Open a New Window
+Exec=/usr/lib/firefox/firefox --new-window %u
+";
+        let app = parse_desktop_entry(contents).unwrap().unwrap();
+        assert_eq!(app.name, "Firefox");
+        assert_eq!(app.executable, PathBuf::from("/usr/lib/firefox/firefox"));
+        assert!(app.launch_args.is_empty());
+        assert_eq!(app.category, AppCategory::Browser);
+        assert_eq!(app.icon, Some(PathBuf::from("firefox")));
+        assert_eq!(
+            app.description.as_deref(),
+            Some("Browse the World Wide Web")
+        );
+    }
+
+    #[test]
+    fn test_hidden_entries_are_skipped() {
+        let no_display =
+            "[Desktop Entry]\nType=Application\nName=Helper\nExec=helper\nNoDisplay=true\n";
+        let hidden = "[Desktop Entry]\nName=Old\nExec=old %F\nHidden=true\n";
+        let link = "[Desktop Entry]\nType=Link\nName=Docs\nURL=https://example.com\n";
+
+        assert!(parse_desktop_entry(no_display).unwrap().is_none());
+        assert!(parse_desktop_entry(hidden).unwrap().is_none());
+        assert!(parse_desktop_entry(link).unwrap().is_none());
+        assert!(parse_desktop_entry("[Desktop Entry]\nName=Broken\n").is_err());
+    }
+
+    #[test]
+    fn test_exec_field_codes() {
+        assert_eq!(
+            split_exec("code --unity-launch %F"),
+            ["code", "--unity-launch"]
+        );
+        assert_eq!(
+            split_exec("app --file=%f --name %c"),
+            ["app", "--file=", "--name"]
+        );
+        assert_eq!(split_exec("printf 100%% %U"), ["printf", "100%"]);
+        assert_eq!(
+            split_exec(r#""/opt/My App/app" "--title=\"x\"" %U"#),
+            ["/opt/My App/app", "--title=\"x\""]
+        );
+        assert_eq!(
+            split_exec("env GDK_BACKEND=x11 LANG=C /usr/bin/gimp %U"),
+            ["/usr/bin/gimp"]
+        );
+        assert_eq!(
+            split_exec("/usr/bin/flatpak run --branch=stable org.gimp.GIMP @@u %U @@"),
+            [
+                "/usr/bin/flatpak",
+                "run",
+                "--branch=stable",
+                "org.gimp.GIMP"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_categorize_linux_app() {
+        let categories =
+            |list: &str| -> Vec<String> { list.split(';').map(|s| s.to_string()).collect() };
+
+        assert_eq!(
+            categorize_linux_app(&categories("Network;InstantMessaging")),
+            AppCategory::Communication
+        );
+        assert_eq!(
+            categorize_linux_app(&categories("Utility;TextEditor")),
+            AppCategory::TextEditor
+        );
+        assert_eq!(
+            categorize_linux_app(&categories("Office;Spreadsheet")),
+            AppCategory::Office
+        );
+        assert_eq!(
+            categorize_linux_app(&categories("Education")),
+            AppCategory::Other
+        );
+    }
+}
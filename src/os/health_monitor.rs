@@ -1,8 +1,14 @@
 //! System Health Monitor with Auto-Remediation (GOD-LEVEL)
+//!
+//! Remediations change the system, so they only run when
+//! `auto_remediate` is on and the action is in `allowed_actions`. Every
+//! remediation, run or not, is appended to a JSON-lines audit log.
 
-use crate::error::Result;
+use crate::error::{LunaError, Result};
 use crate::events::{EventBus, LunaEvent};
 use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::RwLock;
@@ -38,6 +44,60 @@ pub enum RemediationAction {
     ReduceProcessPriority(u32),
 }
 
+impl RemediationAction {
+    /// Name used in `allowed_actions` and the audit log
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::KillProcess(_) => "kill_process",
+            Self::RestartApp(_) => "restart_app",
+            Self::ClearTempFiles => "clear_temp_files",
+            Self::CloseUnusedTabs => "close_unused_tabs",
+            Self::ReduceProcessPriority(_) => "reduce_process_priority",
+        }
+    }
+}
+
+/// File name of the remediation audit log under `data_dir`
+pub const AUDIT_LOG_FILE: &str = "health_audit.jsonl";
+
+/// Remediation settings; detect-only unless opted in
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HealthMonitorConfig {
+    /// Run remediations automatically instead of only reporting issues
+    pub auto_remediate: bool,
+    /// Remediations allowed to run, by name (e.g. "clear_temp_files")
+    pub allowed_actions: Vec<String>,
+}
+
+impl HealthMonitorConfig {
+    /// Whether an action may run, and why not if it can't
+    fn permits(&self, action: &RemediationAction) -> std::result::Result<(), String> {
+        if !self.auto_remediate {
+            Err("auto_remediate is disabled".to_string())
+        } else if !self.allowed_actions.iter().any(|a| a == action.name()) {
+            Err(format!("{} is not in allowed_actions", action.name()))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// One line of the remediation audit log
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RemediationRecord {
+    pub timestamp: chrono::DateTime<chrono::Local>,
+    pub issue: String,
+    pub action: String,
+    /// Whether the action actually ran
+    pub executed: bool,
+    /// What happened, or why it was skipped
+    pub outcome: String,
+}
+
+/// Runs a permitted remediation and describes what it did
+pub type RemediationRunner = Arc<dyn Fn(&RemediationAction) -> Result<String> + Send + Sync>;
+
 pub struct HealthCheck {
     pub name: String,
     pub check_fn: Arc<dyn Fn() -> HealthStatus + Send + Sync>,
@@ -58,6 +118,9 @@ pub struct HealthMonitor {
     remediation_actions: Arc<RwLock<Vec<(HealthIssue, RemediationAction)>>>,
     event_bus: Option<Arc<EventBus>>,
     monitoring_active: Arc<RwLock<bool>>,
+    config: HealthMonitorConfig,
+    audit_log: Option<PathBuf>,
+    runner: RemediationRunner,
 }
 
 impl HealthMonitor {
@@ -67,6 +130,9 @@ impl HealthMonitor {
             remediation_actions: Arc::new(RwLock::new(Vec::new())),
             event_bus: None,
             monitoring_active: Arc::new(RwLock::new(false)),
+            config: HealthMonitorConfig::default(),
+            audit_log: None,
+            runner: Arc::new(run_remediation),
         }
     }
 
@@ -75,6 +141,24 @@ impl HealthMonitor {
         self
     }
 
+    /// Apply remediation settings (detect-only by default)
+    pub fn with_config(mut self, config: HealthMonitorConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Append every remediation to a JSON-lines audit log
+    pub fn with_audit_log<P: AsRef<Path>>(mut self, path: P) -> Self {
+        self.audit_log = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Use a custom runner for permitted remediations
+    pub fn with_runner(mut self, runner: RemediationRunner) -> Self {
+        self.runner = runner;
+        self
+    }
+
     pub async fn start_monitoring(&self) -> Result<()> {
         let mut active = self.monitoring_active.write().await;
         *active = true;
@@ -153,31 +237,105 @@ impl HealthMonitor {
         }
     }
 
-    pub async fn remediate(&self, issue: &HealthIssue) -> Result<()> {
-        warn!("Remediating issue: {:?}", issue);
+    /// Remediation suggested for an issue, if any
+    pub fn plan_remediation(issue: &HealthIssue) -> Option<RemediationAction> {
+        match issue {
+            HealthIssue::HighMemoryUsage | HealthIssue::HighDiskUsage => {
+                Some(RemediationAction::ClearTempFiles)
+            }
+            HealthIssue::UnresponsiveApp(app) => Some(RemediationAction::RestartApp(app.clone())),
+            _ => None,
+        }
+    }
+
+    /// Remediate an issue with its planned action
+    pub async fn remediate(&self, issue: &HealthIssue) -> Result<RemediationRecord> {
+        match Self::plan_remediation(issue) {
+            Some(action) => self.remediate_with(issue, action).await,
+            None => {
+                let record = self.record(issue, "none", false, "no remediation available");
+                self.audit(&record)?;
+                Ok(record)
+            }
+        }
+    }
 
-        let action = match issue {
-            HealthIssue::HighMemoryUsage => {
-                info!("Clearing caches and optimizing memory");
-                "cleared_caches".to_string()
+    /// Remediate an issue with a specific action
+    ///
+    /// The action only runs if the config permits it; either way the attempt
+    /// is written to the audit log.
+    pub async fn remediate_with(
+        &self,
+        issue: &HealthIssue,
+        action: RemediationAction,
+    ) -> Result<RemediationRecord> {
+        let record = match self.config.permits(&action) {
+            Err(reason) => {
+                info!(
+                    "Detected {:?}; not running {} ({})",
+                    issue,
+                    action.name(),
+                    reason
+                );
+                self.record(issue, action.name(), false, &reason)
             }
-            HealthIssue::HighCpuUsage => {
-                info!("Reducing process priorities");
-                "reduced_priorities".to_string()
+            Ok(()) => {
+                warn!("Remediating {:?} with {}", issue, action.name());
+                let outcome = (self.runner)(&action);
+                let record = match &outcome {
+                    Ok(message) => self.record(issue, action.name(), true, message),
+                    Err(e) => self.record(issue, action.name(), true, &format!("failed: {}", e)),
+                };
+
+                if outcome.is_ok() {
+                    if let Some(ref bus) = self.event_bus {
+                        bus.publish(LunaEvent::HealthRemediated {
+                            issue_type: format!("{:?}", issue),
+                            action_taken: action.name().to_string(),
+                        })
+                        .await;
+                    }
+                }
+                record
             }
-            _ => "no_action".to_string(),
         };
 
-        // Emit remediation event
-        if let Some(ref bus) = self.event_bus {
-            let _ = bus
-                .publish(LunaEvent::HealthRemediated {
-                    issue_type: format!("{:?}", issue),
-                    action_taken: action,
-                })
-                .await;
+        self.audit(&record)?;
+        Ok(record)
+    }
+
+    fn record(
+        &self,
+        issue: &HealthIssue,
+        action: &str,
+        executed: bool,
+        outcome: &str,
+    ) -> RemediationRecord {
+        RemediationRecord {
+            timestamp: chrono::Local::now(),
+            issue: format!("{:?}", issue),
+            action: action.to_string(),
+            executed,
+            outcome: outcome.to_string(),
         }
+    }
+
+    /// Append a record to the audit log, if one is configured
+    fn audit(&self, record: &RemediationRecord) -> Result<()> {
+        let Some(path) = &self.audit_log else {
+            return Ok(());
+        };
 
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let line = serde_json::to_string(record)
+            .map_err(|e| LunaError::SystemOperation(format!("Failed to encode audit: {}", e)))?;
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)?;
+        writeln!(file, "{}", line)?;
         Ok(())
     }
 
@@ -196,3 +354,110 @@ impl Default for HealthMonitor {
         Self::new()
     }
 }
+
+/// Default remediation runner
+fn run_remediation(action: &RemediationAction) -> Result<String> {
+    match action {
+        RemediationAction::ClearTempFiles => {
+            // Only LUNA's own scratch files; never other apps' temp data
+            let mut removed = 0;
+            for entry in std::fs::read_dir(std::env::temp_dir())?.flatten() {
+                let name = entry.file_name();
+                if name.to_string_lossy().starts_with("luna-")
+                    && std::fs::remove_file(entry.path()).is_ok()
+                {
+                    removed += 1;
+                }
+            }
+            Ok(format!("removed {} temp files", removed))
+        }
+        other => {
+            debug!("No runner for {}", other.name());
+            Err(LunaError::SystemOperation(format!(
+                "{} is not implemented",
+                other.name()
+            )))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tempfile::tempdir;
+
+    fn read_audit(path: &Path) -> Vec<RemediationRecord> {
+        std::fs::read_to_string(path)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+
+    fn counting_runner(runs: Arc<AtomicUsize>) -> RemediationRunner {
+        Arc::new(move |_: &RemediationAction| {
+            runs.fetch_add(1, Ordering::SeqCst);
+            Ok("done".to_string())
+        })
+    }
+
+    #[tokio::test]
+    async fn test_disallowed_remediation_is_logged_not_run() {
+        let dir = tempdir().unwrap();
+        let audit = dir.path().join(AUDIT_LOG_FILE);
+        let runs = Arc::new(AtomicUsize::new(0));
+
+        let monitor = HealthMonitor::new()
+            .with_config(HealthMonitorConfig {
+                auto_remediate: true,
+                allowed_actions: vec!["clear_temp_files".to_string()],
+            })
+            .with_audit_log(&audit)
+            .with_runner(counting_runner(runs.clone()));
+
+        let record = monitor
+            .remediate_with(
+                &HealthIssue::HighCpuUsage,
+                RemediationAction::KillProcess(42),
+            )
+            .await
+            .unwrap();
+        assert!(!record.executed);
+        assert_eq!(runs.load(Ordering::SeqCst), 0);
+
+        let record = monitor
+            .remediate(&HealthIssue::HighMemoryUsage)
+            .await
+            .unwrap();
+        assert!(record.executed);
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+
+        let logged = read_audit(&audit);
+        assert_eq!(logged.len(), 2);
+        assert_eq!(logged[0].action, "kill_process");
+        assert!(!logged[0].executed);
+        assert_eq!(logged[1].action, "clear_temp_files");
+        assert!(logged[1].executed);
+    }
+
+    #[tokio::test]
+    async fn test_detect_only_by_default() {
+        let dir = tempdir().unwrap();
+        let audit = dir.path().join(AUDIT_LOG_FILE);
+        let runs = Arc::new(AtomicUsize::new(0));
+
+        let monitor = HealthMonitor::new()
+            .with_audit_log(&audit)
+            .with_runner(counting_runner(runs.clone()));
+
+        let record = monitor
+            .remediate(&HealthIssue::HighMemoryUsage)
+            .await
+            .unwrap();
+        assert!(!record.executed);
+        assert_eq!(record.outcome, "auto_remediate is disabled");
+        assert_eq!(runs.load(Ordering::SeqCst), 0);
+        assert_eq!(read_audit(&audit).len(), 1);
+    }
+}
@@ -218,6 +218,87 @@ pub fn set_volume_linux(level: u8) -> Result<()> {
     ))
 }
 
+/// Find the first backlight device under sysfs
+#[cfg(target_os = "linux")]
+fn backlight_device() -> Option<PathBuf> {
+    fs::read_dir("/sys/class/backlight")
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .find(|path| path.join("max_brightness").exists())
+}
+
+/// Get current screen brightness (0-100)
+#[cfg(target_os = "linux")]
+pub fn get_brightness_linux() -> Result<u8> {
+    // Try sysfs first (no external tools needed)
+    if let Some(device) = backlight_device() {
+        let read = |name: &str| -> Option<u64> {
+            fs::read_to_string(device.join(name))
+                .ok()?
+                .trim()
+                .parse()
+                .ok()
+        };
+
+        if let (Some(current), Some(max)) = (read("brightness"), read("max_brightness")) {
+            if let Some(percent) = (current * 100 + max / 2).checked_div(max) {
+                return Ok(percent.min(100) as u8);
+            }
+        }
+    }
+
+    // Fallback: brightnessctl machine-readable output "device,class,current,percent%,max"
+    if let Ok(output) = Command::new("brightnessctl").arg("-m").output() {
+        if output.status.success() {
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            if let Some(percent) = stdout.split(',').nth(3) {
+                if let Ok(level) = percent.trim().trim_end_matches('%').parse::<u8>() {
+                    return Ok(level.min(100));
+                }
+            }
+        }
+    }
+
+    Err(LunaError::SystemOperation(
+        "Brightness control unavailable: no backlight device found".to_string(),
+    ))
+}
+
+/// Set screen brightness (0-100)
+#[cfg(target_os = "linux")]
+pub fn set_brightness_linux(level: u8) -> Result<()> {
+    let level = level.min(100);
+
+    // Try brightnessctl first (works without root via udev rules)
+    let result = Command::new("brightnessctl")
+        .args(["set", &format!("{}%", level)])
+        .output();
+
+    if result.is_ok() && result.unwrap().status.success() {
+        return Ok(());
+    }
+
+    // Fallback: write sysfs directly (requires write permission)
+    if let Some(device) = backlight_device() {
+        let max: u64 = fs::read_to_string(device.join("max_brightness"))
+            .ok()
+            .and_then(|s| s.trim().parse().ok())
+            .unwrap_or(0);
+
+        if max > 0 {
+            let raw = max * level as u64 / 100;
+            if fs::write(device.join("brightness"), raw.to_string()).is_ok() {
+                return Ok(());
+            }
+        }
+    }
+
+    Err(LunaError::SystemOperation(
+        "Brightness control unavailable: install brightnessctl".to_string(),
+    ))
+}
+
 /// Get list of installed packages
 #[cfg(target_os = "linux")]
 pub fn get_installed_packages() -> Result<Vec<String>> {
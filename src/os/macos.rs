@@ -179,6 +179,58 @@ pub fn set_volume_macos(level: u8) -> Result<()> {
     Ok(())
 }
 
+/// Get current screen brightness (0-100)
+///
+/// Uses the `brightness` CLI (`brew install brightness`).
+#[cfg(target_os = "macos")]
+pub fn get_brightness_macos() -> Result<u8> {
+    let output = Command::new("brightness")
+        .arg("-l")
+        .output()
+        .map_err(|_| {
+            LunaError::SystemOperation(
+                "Brightness control unavailable: install the brightness tool".to_string(),
+            )
+        })?;
+
+    // Output contains lines like "display 0: brightness 0.750000"
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    for line in stdout.lines() {
+        if let Some(value) = line.split("brightness ").nth(1) {
+            if let Ok(fraction) = value.trim().parse::<f32>() {
+                return Ok((fraction * 100.0).round().clamp(0.0, 100.0) as u8);
+            }
+        }
+    }
+
+    Err(LunaError::SystemOperation(
+        "Brightness control unavailable: no display reported".to_string(),
+    ))
+}
+
+/// Set screen brightness (0-100)
+#[cfg(target_os = "macos")]
+pub fn set_brightness_macos(level: u8) -> Result<()> {
+    let level = level.min(100);
+
+    let status = Command::new("brightness")
+        .arg(format!("{}", level as f32 / 100.0))
+        .status()
+        .map_err(|_| {
+            LunaError::SystemOperation(
+                "Brightness control unavailable: install the brightness tool".to_string(),
+            )
+        })?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(LunaError::SystemOperation(
+            "Failed to set brightness".to_string(),
+        ))
+    }
+}
+
 /// Get list of running applications
 #[cfg(target_os = "macos")]
 pub fn get_running_apps() -> Result<Vec<String>> {
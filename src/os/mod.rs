@@ -46,6 +46,7 @@ pub mod unified_manager; // Unified OS Manager (Integration Layer)
 // Re-exports for convenience
 pub use unified_manager::{SystemHealth, UnifiedOsManager};
 
+use crate::actions::skills::ResolvedCommand;
 use crate::error::Result;
 use std::path::Path;
 
@@ -188,6 +189,109 @@ impl OsInterface {
             ))
         }
     }
+
+    /// Get screen brightness (0-100)
+    pub fn get_brightness(&self) -> Result<u8> {
+        get_brightness()
+    }
+
+    /// Set screen brightness (0-100)
+    pub fn set_brightness(&self, level: u8) -> Result<()> {
+        set_brightness(level)
+    }
+
+    /// Run a command resolved from the skill allowlist
+    pub async fn run_shell_intent(&self, command: &ResolvedCommand) -> Result<String> {
+        run_shell_intent(command).await
+    }
+}
+
+/// Run a command resolved from the skill allowlist
+///
+/// The program is spawned directly with its argv, never through a shell,
+/// so parameters can't be reinterpreted. Returns trimmed stdout.
+pub async fn run_shell_intent(command: &ResolvedCommand) -> Result<String> {
+    let output = tokio::process::Command::new(command.program())
+        .args(command.args())
+        .stdin(std::process::Stdio::null())
+        .kill_on_drop(true)
+        .output()
+        .await
+        .map_err(|e| {
+            crate::error::LunaError::SystemOperation(format!(
+                "Failed to run {}: {}",
+                command.skill(),
+                e
+            ))
+        })?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(crate::error::LunaError::SystemOperation(format!(
+            "{} failed ({}): {}",
+            command.skill(),
+            output.status,
+            stderr.trim()
+        )));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if stdout.is_empty() {
+        Ok(format!("Ran {}", command.skill()))
+    } else {
+        Ok(stdout)
+    }
+}
+
+/// Get screen brightness (0-100) for the current platform
+pub fn get_brightness() -> Result<u8> {
+    #[cfg(target_os = "linux")]
+    {
+        linux::get_brightness_linux()
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        windows::get_brightness_windows()
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        macos::get_brightness_macos()
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
+    {
+        Err(crate::error::LunaError::SystemOperation(
+            "Unsupported platform".to_string(),
+        ))
+    }
+}
+
+/// Set screen brightness (0-100) for the current platform
+pub fn set_brightness(level: u8) -> Result<()> {
+    #[cfg(target_os = "linux")]
+    {
+        linux::set_brightness_linux(level)
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        windows::set_brightness_windows(level)
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        macos::set_brightness_macos(level)
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
+    {
+        let _ = level;
+        Err(crate::error::LunaError::SystemOperation(
+            "Unsupported platform".to_string(),
+        ))
+    }
 }
 
 impl Default for OsInterface {
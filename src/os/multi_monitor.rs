@@ -50,6 +50,148 @@ impl Default for DisplayCapabilities {
     }
 }
 
+/// Monitor named in a command ("left monitor", "monitor 2", "external display")
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MonitorTarget {
+    /// One-based position, counting monitors left to right
+    Number(usize),
+    Left,
+    Right,
+    Primary,
+    /// First monitor that isn't the built-in panel or primary display
+    External,
+    /// Built-in laptop panel
+    Internal,
+}
+
+impl MonitorTarget {
+    /// Parse a spoken monitor reference
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.to_lowercase();
+        let words: Vec<&str> = text
+            .split(|c: char| !c.is_alphanumeric() && c != '-')
+            .filter(|w| !w.is_empty())
+            .collect();
+        let has = |w: &str| words.contains(&w);
+
+        if has("left") || has("leftmost") {
+            Some(Self::Left)
+        } else if has("right") || has("rightmost") {
+            Some(Self::Right)
+        } else if has("primary") || has("main") {
+            Some(Self::Primary)
+        } else if has("external") || has("other") {
+            Some(Self::External)
+        } else if has("laptop") || has("internal") || has("built-in") || has("builtin") {
+            Some(Self::Internal)
+        } else {
+            words.iter().find_map(|w| ordinal(w)).map(Self::Number)
+        }
+    }
+}
+
+/// "2", "two", "second", "2nd" -> 2
+fn ordinal(word: &str) -> Option<usize> {
+    const WORDS: &[(&str, &str, usize)] = &[
+        ("one", "first", 1),
+        ("two", "second", 2),
+        ("three", "third", 3),
+        ("four", "fourth", 4),
+        ("five", "fifth", 5),
+        ("six", "sixth", 6),
+    ];
+
+    let digits = word.trim_end_matches(|c: char| c.is_ascii_alphabetic());
+    if !digits.is_empty() {
+        return digits.parse().ok().filter(|n| *n > 0);
+    }
+    WORDS
+        .iter()
+        .find(|(cardinal, ordinal, _)| word == *cardinal || word == *ordinal)
+        .map(|(_, _, n)| *n)
+}
+
+/// Screen-space rectangle for a window
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WindowRect {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Where a window should go on a resolved monitor
+#[derive(Debug, Clone)]
+pub struct MonitorPlacement {
+    /// One-based position of the monitor, left to right
+    pub number: usize,
+    pub display: DisplayInfo,
+    pub rect: WindowRect,
+}
+
+/// Whether a display is a built-in laptop panel (by connector name)
+fn is_internal(display: &DisplayInfo) -> bool {
+    let name = display.name.to_lowercase();
+    ["edp", "lvds", "dsi"].iter().any(|p| name.starts_with(p))
+}
+
+/// Resolve a monitor target against displays
+///
+/// Returns the one-based left-to-right number and the display.
+pub fn resolve_monitor(
+    displays: &[DisplayInfo],
+    target: MonitorTarget,
+) -> Option<(usize, &DisplayInfo)> {
+    let mut ordered: Vec<&DisplayInfo> = displays.iter().collect();
+    ordered.sort_by_key(|d| (d.position.0, d.position.1));
+    let numbered: Vec<(usize, &DisplayInfo)> = ordered
+        .into_iter()
+        .enumerate()
+        .map(|(i, d)| (i + 1, d))
+        .collect();
+
+    let found = match target {
+        MonitorTarget::Number(n) => numbered.get(n.checked_sub(1)?),
+        MonitorTarget::Left => numbered.first(),
+        MonitorTarget::Right => numbered.last(),
+        MonitorTarget::Primary => numbered
+            .iter()
+            .find(|(_, d)| d.is_primary)
+            .or_else(|| numbered.first()),
+        MonitorTarget::External => numbered
+            .iter()
+            .find(|(_, d)| !is_internal(d) && !d.is_primary)
+            .or_else(|| numbered.iter().find(|(_, d)| !is_internal(d))),
+        MonitorTarget::Internal => numbered
+            .iter()
+            .find(|(_, d)| is_internal(d))
+            .or_else(|| numbered.iter().find(|(_, d)| d.is_primary)),
+    };
+    found.copied()
+}
+
+/// Rectangle covering a display, or centered at 80% of it when not maximizing
+pub fn target_rect(display: &DisplayInfo, maximize: bool) -> WindowRect {
+    let (x, y) = display.position;
+    let (width, height) = display.resolution;
+    if maximize {
+        return WindowRect {
+            x,
+            y,
+            width,
+            height,
+        };
+    }
+
+    let (w, h) = (width * 4 / 5, height * 4 / 5);
+    WindowRect {
+        x: x + ((width - w) / 2) as i32,
+        y: y + ((height - h) / 2) as i32,
+        width: w,
+        height: h,
+    }
+}
+
 /// Window layout configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WindowLayout {
@@ -274,12 +416,14 @@ impl MultiMonitorManager {
                 let parts: Vec<&str> = line.split_whitespace().collect();
                 let name = parts.first().unwrap_or(&"Unknown").to_string();
                 let is_primary = line.contains("primary");
+                // "2560x1440+1920+0" carries the size and position in the layout
+                let geometry = parts.iter().find_map(|p| parse_geometry(p));
 
                 current_display = Some(DisplayInfo {
                     id: format!("display_{}", display_index),
                     name: name.clone(),
-                    resolution: (1920, 1080), // Will be updated
-                    position: (0, 0),
+                    resolution: geometry.map(|g| g.0).unwrap_or((1920, 1080)),
+                    position: geometry.map(|g| g.1).unwrap_or((0, 0)),
                     scale_factor: 1.0,
                     refresh_rate: 60,
                     is_primary,
@@ -349,6 +493,33 @@ impl MultiMonitorManager {
         self.displays.read().await.clone()
     }
 
+    /// Replace the known displays (e.g. from another detector)
+    pub async fn set_displays(&self, displays: Vec<DisplayInfo>) {
+        *self.displays.write().await = displays;
+    }
+
+    /// Resolve a monitor target and the rect a window should take on it
+    ///
+    /// Detects displays first if none are known yet.
+    pub async fn placement_for(
+        &self,
+        target: MonitorTarget,
+        maximize: bool,
+    ) -> Result<Option<MonitorPlacement>> {
+        if self.displays.read().await.is_empty() {
+            self.detect_displays().await?;
+        }
+
+        let displays = self.displays.read().await;
+        Ok(
+            resolve_monitor(&displays, target).map(|(number, display)| MonitorPlacement {
+                number,
+                display: display.clone(),
+                rect: target_rect(display, maximize),
+            }),
+        )
+    }
+
     /// Save a window layout
     pub async fn save_layout(&self, name: String, layout: WindowLayout) {
         let mut layouts = self.layouts.write().await;
@@ -410,6 +581,18 @@ impl Default for MultiMonitorManager {
     }
 }
 
+/// Parse an xrandr geometry like `2560x1440+1920+0`
+#[cfg(target_os = "linux")]
+fn parse_geometry(text: &str) -> Option<((u32, u32), (i32, i32))> {
+    let (size, offset) = text.split_once('+')?;
+    let (w, h) = size.split_once('x')?;
+    let (x, y) = offset.split_once('+')?;
+    Some((
+        (w.parse().ok()?, h.parse().ok()?),
+        (x.parse().ok()?, y.parse().ok()?),
+    ))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -431,6 +614,123 @@ mod tests {
         assert!(!displays.is_empty());
     }
 
+    fn display(name: &str, resolution: (u32, u32), x: i32, is_primary: bool) -> DisplayInfo {
+        DisplayInfo {
+            id: name.to_lowercase(),
+            name: name.to_string(),
+            resolution,
+            position: (x, 0),
+            scale_factor: 1.0,
+            refresh_rate: 60,
+            is_primary,
+            rotation: DisplayRotation::Normal,
+            capabilities: DisplayCapabilities::default(),
+        }
+    }
+
+    /// External monitor to the right of a laptop panel, listed right-first
+    fn two_monitors() -> Vec<DisplayInfo> {
+        vec![
+            display("HDMI-1", (2560, 1440), 1920, false),
+            display("eDP-1", (1920, 1080), 0, true),
+        ]
+    }
+
+    #[test]
+    fn test_parse_monitor_target() {
+        assert_eq!(
+            MonitorTarget::parse("left monitor"),
+            Some(MonitorTarget::Left)
+        );
+        assert_eq!(
+            MonitorTarget::parse("monitor 2"),
+            Some(MonitorTarget::Number(2))
+        );
+        assert_eq!(
+            MonitorTarget::parse("my second screen"),
+            Some(MonitorTarget::Number(2))
+        );
+        assert_eq!(
+            MonitorTarget::parse("external display"),
+            Some(MonitorTarget::External)
+        );
+        assert_eq!(
+            MonitorTarget::parse("laptop screen"),
+            Some(MonitorTarget::Internal)
+        );
+        assert_eq!(MonitorTarget::parse("the monitor"), None);
+    }
+
+    #[test]
+    fn test_resolve_monitor_two_monitor_layout() {
+        let displays = two_monitors();
+        let name = |target| resolve_monitor(&displays, target).map(|(n, d)| (n, d.name.as_str()));
+
+        assert_eq!(name(MonitorTarget::Left), Some((1, "eDP-1")));
+        assert_eq!(name(MonitorTarget::Right), Some((2, "HDMI-1")));
+        assert_eq!(name(MonitorTarget::Number(2)), Some((2, "HDMI-1")));
+        assert_eq!(name(MonitorTarget::Primary), Some((1, "eDP-1")));
+        assert_eq!(name(MonitorTarget::External), Some((2, "HDMI-1")));
+        assert_eq!(name(MonitorTarget::Internal), Some((1, "eDP-1")));
+        assert_eq!(name(MonitorTarget::Number(3)), None);
+    }
+
+    #[tokio::test]
+    async fn test_placement_rects() {
+        let manager = MultiMonitorManager::new();
+        manager.set_displays(two_monitors()).await;
+
+        let placement = manager
+            .placement_for(MonitorTarget::External, true)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            placement.rect,
+            WindowRect {
+                x: 1920,
+                y: 0,
+                width: 2560,
+                height: 1440
+            }
+        );
+
+        let placement = manager
+            .placement_for(MonitorTarget::Number(2), false)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            placement.rect,
+            WindowRect {
+                x: 2176,
+                y: 144,
+                width: 2048,
+                height: 1152
+            }
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_parse_xrandr_positions() {
+        let output = b"Screen 0: minimum 8 x 8, current 4480 x 1440
+eDP-1 connected primary 1920x1080+0+0 (normal left inverted right) 344mm x 194mm
+   1920x1080     60.01*+
+HDMI-1 connected 2560x1440+1920+0 (normal left inverted right) 597mm x 336mm
+   2560x1440     59.95*+
+DP-1 disconnected (normal left inverted right)
+";
+
+        let displays = MultiMonitorManager::new()
+            .parse_xrandr_output(output)
+            .unwrap();
+        assert_eq!(displays.len(), 2);
+        assert_eq!(displays[1].name, "HDMI-1");
+        assert_eq!(displays[1].position, (1920, 0));
+        assert_eq!(displays[1].resolution, (2560, 1440));
+    }
+
     #[tokio::test]
     async fn test_placement_engine() {
         let engine = WindowPlacementEngine::new();
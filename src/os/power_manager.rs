@@ -1,7 +1,14 @@
 //! Smart Power Management (GOD-LEVEL)
+//!
+//! Power profiles are applied through a platform backend
+//! (`powerprofilesctl` on Linux, `powercfg` on Windows); battery state comes
+//! from the resource monitor's `BatteryInfo`.
 
-use crate::error::Result;
+use crate::error::{LunaError, Result};
+use crate::os::resource_monitor::BatteryInfo;
+use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 use tracing::{debug, info};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -12,6 +19,91 @@ pub enum PowerProfile {
     Custom(CustomProfile),
 }
 
+impl PowerProfile {
+    /// Parse a spoken profile ("battery saver", "performance mode")
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.to_lowercase();
+        if text.contains("saver") || text.contains("saving") || text.contains("low power") {
+            Some(Self::PowerSaver)
+        } else if text.contains("performance") {
+            Some(Self::MaxPerformance)
+        } else if text.contains("balanced") || text.contains("normal") {
+            Some(Self::Balanced)
+        } else {
+            None
+        }
+    }
+
+    /// Spoken name of the profile
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::MaxPerformance => "performance",
+            Self::Balanced => "balanced",
+            Self::PowerSaver => "battery saver",
+            Self::Custom(_) => "custom",
+        }
+    }
+}
+
+/// Platform access to power profiles and the battery
+pub trait PowerBackend: Send + Sync {
+    /// Apply a power profile
+    fn set_profile(&self, profile: &PowerProfile) -> Result<()>;
+
+    /// Current battery state, or None without a battery
+    fn battery(&self) -> Option<BatteryInfo>;
+}
+
+/// Backend using the platform's power tools
+pub struct SystemPowerBackend;
+
+impl PowerBackend for SystemPowerBackend {
+    fn set_profile(&self, profile: &PowerProfile) -> Result<()> {
+        #[cfg(target_os = "linux")]
+        let command = match profile {
+            PowerProfile::MaxPerformance => Some(("powerprofilesctl", ["set", "performance"])),
+            PowerProfile::Balanced => Some(("powerprofilesctl", ["set", "balanced"])),
+            PowerProfile::PowerSaver => Some(("powerprofilesctl", ["set", "power-saver"])),
+            PowerProfile::Custom(_) => None,
+        };
+
+        // SCHEME_MIN is minimum power saving, SCHEME_MAX maximum
+        #[cfg(target_os = "windows")]
+        let command = match profile {
+            PowerProfile::MaxPerformance => Some(("powercfg", ["/setactive", "SCHEME_MIN"])),
+            PowerProfile::Balanced => Some(("powercfg", ["/setactive", "SCHEME_BALANCED"])),
+            PowerProfile::PowerSaver => Some(("powercfg", ["/setactive", "SCHEME_MAX"])),
+            PowerProfile::Custom(_) => None,
+        };
+
+        #[cfg(not(any(target_os = "linux", target_os = "windows")))]
+        let command: Option<(&str, [&str; 2])> = None;
+
+        let Some((program, args)) = command else {
+            return Err(LunaError::SystemOperation(format!(
+                "The {} power profile is not supported on this platform",
+                profile.name()
+            )));
+        };
+
+        let status = std::process::Command::new(program)
+            .args(args)
+            .status()
+            .map_err(|e| LunaError::SystemOperation(format!("Failed to run {}: {}", program, e)))?;
+        if !status.success() {
+            return Err(LunaError::SystemOperation(format!(
+                "{} exited with {}",
+                program, status
+            )));
+        }
+        Ok(())
+    }
+
+    fn battery(&self) -> Option<BatteryInfo> {
+        BatteryInfo::read()
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CustomProfile {
     pub cpu_max_freq_percent: u8,
@@ -48,34 +140,94 @@ pub struct WakeTimer {
 }
 
 pub struct PowerManager {
-    current_profile: PowerProfile,
+    current_profile: RwLock<PowerProfile>,
     battery_optimizer: BatteryOptimizer,
     wake_timers: Vec<WakeTimer>,
+    backend: Arc<dyn PowerBackend>,
 }
 
 impl PowerManager {
     pub fn new() -> Self {
         Self {
-            current_profile: PowerProfile::Balanced,
+            current_profile: RwLock::new(PowerProfile::Balanced),
             battery_optimizer: BatteryOptimizer::new(),
             wake_timers: Vec::new(),
+            backend: Arc::new(SystemPowerBackend),
         }
     }
 
-    pub async fn set_profile(&mut self, profile: PowerProfile) -> Result<()> {
+    /// Use a custom power backend
+    pub fn with_backend(mut self, backend: Arc<dyn PowerBackend>) -> Self {
+        self.backend = backend;
+        self
+    }
+
+    pub async fn set_profile(&self, profile: PowerProfile) -> Result<()> {
         info!("Setting power profile: {:?}", profile);
-        self.current_profile = profile;
+        self.backend.set_profile(&profile)?;
+        *self.current_profile.write() = profile;
         Ok(())
     }
 
-    pub async fn enable_power_saver(&mut self) -> Result<()> {
+    /// Profile last applied through this manager
+    pub fn current_profile(&self) -> PowerProfile {
+        self.current_profile.read().clone()
+    }
+
+    pub async fn enable_power_saver(&self) -> Result<()> {
         self.set_profile(PowerProfile::PowerSaver).await
     }
 
-    pub async fn enable_max_performance(&mut self) -> Result<()> {
+    pub async fn enable_max_performance(&self) -> Result<()> {
         self.set_profile(PowerProfile::MaxPerformance).await
     }
 
+    /// Current battery state, or None without a battery
+    pub fn battery(&self) -> Option<BatteryInfo> {
+        self.backend.battery()
+    }
+
+    /// Battery status phrased for speech
+    pub fn battery_status(&self) -> String {
+        let Some(battery) = self.battery() else {
+            return "This computer doesn't have a battery".to_string();
+        };
+
+        let mut status = format!("Battery is at {}%", battery.charge_percent);
+        if battery.is_charging {
+            status.push_str(" and charging");
+        }
+        if let Some(mins) = battery.time_remaining_mins {
+            let until = if battery.is_charging {
+                "until full"
+            } else {
+                "remaining"
+            };
+            status.push_str(&format!(", about {} {}", spoken_minutes(mins), until));
+        }
+        status
+    }
+
+    /// Perform a named power action and describe the result
+    ///
+    /// Supported actions: profile (with a spoken profile name), status.
+    pub async fn perform(&self, action: &str, profile: Option<&str>) -> Result<String> {
+        match action {
+            "status" | "battery" => Ok(self.battery_status()),
+            "profile" | "set" => {
+                let Some(profile) = profile.and_then(PowerProfile::parse) else {
+                    return Ok(
+                        "Which power mode? Battery saver, balanced, or performance".to_string()
+                    );
+                };
+                let name = profile.name();
+                self.set_profile(profile).await?;
+                Ok(format!("Switched to {} mode", name))
+            }
+            _ => Ok(format!("Unknown power action: {}", action)),
+        }
+    }
+
     pub fn add_wake_timer(&mut self, timer: WakeTimer) {
         debug!("Added wake timer: {}", timer.action);
         self.wake_timers.push(timer);
@@ -109,3 +261,105 @@ impl Default for PowerManager {
         Self::new()
     }
 }
+
+/// "1 hour 5 minutes", "45 minutes"
+fn spoken_minutes(mins: u32) -> String {
+    let plural = |n: u32, unit: &str| format!("{} {}{}", n, unit, if n == 1 { "" } else { "s" });
+    match (mins / 60, mins % 60) {
+        (0, m) => plural(m, "minute"),
+        (h, 0) => plural(h, "hour"),
+        (h, m) => format!("{} {}", plural(h, "hour"), plural(m, "minute")),
+    }
+}
+
+/// Backend that records profile changes, for tests
+#[cfg(test)]
+pub(crate) mod mock {
+    use super::*;
+    use parking_lot::Mutex;
+
+    #[derive(Default)]
+    pub struct MockPowerBackend {
+        pub battery: Option<BatteryInfo>,
+        pub profiles: Mutex<Vec<String>>,
+    }
+
+    impl MockPowerBackend {
+        pub fn with_battery(battery: Option<BatteryInfo>) -> Arc<Self> {
+            Arc::new(Self {
+                battery,
+                profiles: Mutex::new(Vec::new()),
+            })
+        }
+    }
+
+    impl PowerBackend for MockPowerBackend {
+        fn set_profile(&self, profile: &PowerProfile) -> Result<()> {
+            self.profiles.lock().push(profile.name().to_string());
+            Ok(())
+        }
+
+        fn battery(&self) -> Option<BatteryInfo> {
+            self.battery.clone()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::mock::MockPowerBackend;
+    use super::*;
+
+    fn battery(charge_percent: u8, is_charging: bool, mins: Option<u32>) -> Option<BatteryInfo> {
+        Some(BatteryInfo {
+            charge_percent,
+            is_charging,
+            time_remaining_mins: mins,
+            health_percent: None,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_profile_actions() {
+        let backend = MockPowerBackend::with_battery(None);
+        let manager = PowerManager::new().with_backend(backend.clone());
+
+        assert_eq!(
+            manager
+                .perform("profile", Some("battery saver"))
+                .await
+                .unwrap(),
+            "Switched to battery saver mode"
+        );
+        manager
+            .perform("profile", Some("performance"))
+            .await
+            .unwrap();
+        assert!(manager.perform("profile", Some("turbo")).await.is_ok());
+
+        assert_eq!(*backend.profiles.lock(), ["battery saver", "performance"]);
+        assert!(matches!(
+            manager.current_profile(),
+            PowerProfile::MaxPerformance
+        ));
+    }
+
+    #[test]
+    fn test_battery_status() {
+        let status = |battery| {
+            PowerManager::new()
+                .with_backend(MockPowerBackend::with_battery(battery))
+                .battery_status()
+        };
+
+        assert_eq!(
+            status(battery(42, false, Some(125))),
+            "Battery is at 42%, about 2 hours 5 minutes remaining"
+        );
+        assert_eq!(
+            status(battery(80, true, Some(30))),
+            "Battery is at 80% and charging, about 30 minutes until full"
+        );
+        assert_eq!(status(None), "This computer doesn't have a battery");
+    }
+}
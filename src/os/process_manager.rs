@@ -313,6 +313,34 @@ impl ProcessManager {
         self.processes.get(&pid).map(|entry| entry.clone())
     }
 
+    /// Find running processes by name, including ones LUNA didn't spawn
+    ///
+    /// Matches case-insensitively on the process name or command line.
+    pub async fn find_by_name(&self, name: &str) -> Vec<Pid> {
+        let needle = name.trim().to_lowercase();
+        if needle.is_empty() {
+            return Vec::new();
+        }
+
+        let mut sys = self.system.write().await;
+        sys.refresh_processes();
+
+        let mut pids: Vec<Pid> = sys
+            .processes()
+            .iter()
+            .filter(|(_, process)| {
+                process.name().to_lowercase().contains(&needle)
+                    || process
+                        .cmd()
+                        .first()
+                        .is_some_and(|cmd| cmd.to_lowercase().contains(&needle))
+            })
+            .map(|(pid, _)| pid.as_u32())
+            .collect();
+        pids.sort_unstable();
+        pids
+    }
+
     /// List all tracked processes
     pub fn list_processes(&self) -> Vec<ProcessInfo> {
         self.processes.iter().map(|entry| entry.clone()).collect()
@@ -417,6 +445,16 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_find_by_name() {
+        let manager = ProcessManager::new();
+        assert!(manager.find_by_name("").await.is_empty());
+        assert!(manager
+            .find_by_name("no-such-process-luna-test")
+            .await
+            .is_empty());
+    }
+
     #[tokio::test]
     async fn test_monitoring() {
         let manager = ProcessManager::new();
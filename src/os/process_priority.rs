@@ -1,9 +1,17 @@
 //! Process Priority & Affinity Management (GOD-LEVEL)
+//!
+//! Processes are looked up by name through the `ProcessManager`; priority
+//! changes use `renice` and affinity uses `taskset`.
 
 use crate::error::{LunaError, Result};
+use crate::os::process_manager::{Pid, ProcessManager};
+use async_trait::async_trait;
+use parking_lot::RwLock;
 use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::info;
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PriorityLevel {
     Idle,
     Low,
@@ -14,71 +22,326 @@ pub enum PriorityLevel {
     Realtime,
 }
 
-#[derive(Debug, Clone)]
+impl PriorityLevel {
+    /// Parse a spoken priority ("lower", "below normal", "high")
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.trim().to_lowercase();
+        let level = match text.as_str() {
+            "idle" | "lowest" => Self::Idle,
+            "low" | "lower" | "reduce" | "decrease" => Self::Low,
+            "below normal" => Self::BelowNormal,
+            "normal" | "reset" | "default" => Self::Normal,
+            "above normal" => Self::AboveNormal,
+            "high" | "higher" | "raise" | "increase" | "boost" => Self::High,
+            "realtime" | "real time" => Self::Realtime,
+            _ => return None,
+        };
+        Some(level)
+    }
+
+    /// Unix niceness for this level
+    pub fn nice(&self) -> i32 {
+        match self {
+            Self::Idle => 19,
+            Self::Low => 10,
+            Self::BelowNormal => 5,
+            Self::Normal => 0,
+            Self::AboveNormal => -5,
+            Self::High => -10,
+            Self::Realtime => -20,
+        }
+    }
+
+    /// Spoken name of the level
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Idle => "idle",
+            Self::Low => "low",
+            Self::BelowNormal => "below normal",
+            Self::Normal => "normal",
+            Self::AboveNormal => "above normal",
+            Self::High => "high",
+            Self::Realtime => "realtime",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct CpuSet {
     pub cores: Vec<usize>,
 }
 
+impl CpuSet {
+    /// Parse a spoken core list ("0 and 1", "0, 2", "0-3")
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut cores = Vec::new();
+        let text = text.to_lowercase().replace(" to ", "-");
+        for part in text
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|part| !part.is_empty() && *part != "and")
+        {
+            match part.split_once('-') {
+                Some((start, end)) => {
+                    let (start, end): (usize, usize) = (start.parse().ok()?, end.parse().ok()?);
+                    if start > end {
+                        return None;
+                    }
+                    cores.extend(start..=end);
+                }
+                None => cores.push(part.parse().ok()?),
+            }
+        }
+
+        cores.sort_unstable();
+        cores.dedup();
+        (!cores.is_empty()).then_some(Self { cores })
+    }
+
+    /// Check every core exists on a machine with `core_count` CPUs
+    pub fn validate(&self, core_count: usize) -> Result<()> {
+        if self.cores.is_empty() {
+            return Err(LunaError::InvalidParameter(
+                "No CPU cores given".to_string(),
+            ));
+        }
+        if let Some(core) = self.cores.iter().find(|&&core| core >= core_count) {
+            return Err(LunaError::InvalidParameter(format!(
+                "Core {} doesn't exist; this machine has {} cores (0-{})",
+                core,
+                core_count,
+                core_count.saturating_sub(1)
+            )));
+        }
+        Ok(())
+    }
+
+    /// "0, 1 and 2"
+    fn spoken(&self) -> String {
+        let cores: Vec<String> = self.cores.iter().map(|c| c.to_string()).collect();
+        match cores.split_last() {
+            Some((last, rest)) if !rest.is_empty() => format!("{} and {}", rest.join(", "), last),
+            _ => cores.join(""),
+        }
+    }
+}
+
 pub struct PriorityRule {
     pub app_pattern: String,
     pub priority: PriorityLevel,
     pub affinity: Option<CpuSet>,
 }
 
+/// Process lookup and scheduling changes
+#[async_trait]
+pub trait PriorityBackend: Send + Sync {
+    /// PIDs of running processes matching a name
+    async fn find_processes(&self, name: &str) -> Vec<Pid>;
+
+    /// Change a process's scheduling priority
+    fn set_priority(&self, pid: Pid, priority: PriorityLevel) -> Result<()>;
+
+    /// Restrict a process to a set of CPU cores
+    fn set_affinity(&self, pid: Pid, cpuset: &CpuSet) -> Result<()>;
+}
+
+/// Backend resolving processes through the process manager
+pub struct SystemPriorityBackend {
+    processes: Arc<ProcessManager>,
+}
+
+impl SystemPriorityBackend {
+    pub fn new(processes: Arc<ProcessManager>) -> Self {
+        Self { processes }
+    }
+}
+
+#[async_trait]
+impl PriorityBackend for SystemPriorityBackend {
+    async fn find_processes(&self, name: &str) -> Vec<Pid> {
+        self.processes.find_by_name(name).await
+    }
+
+    fn set_priority(&self, pid: Pid, priority: PriorityLevel) -> Result<()> {
+        #[cfg(unix)]
+        {
+            run_tool(
+                "renice",
+                &[&priority.nice().to_string(), "-p", &pid.to_string()],
+            )
+        }
+
+        #[cfg(not(unix))]
+        {
+            let _ = (pid, priority);
+            Err(LunaError::SystemOperation(
+                "Changing process priority is not supported on this platform".to_string(),
+            ))
+        }
+    }
+
+    fn set_affinity(&self, pid: Pid, cpuset: &CpuSet) -> Result<()> {
+        #[cfg(target_os = "linux")]
+        {
+            let cpu_list = cpuset
+                .cores
+                .iter()
+                .map(|c| c.to_string())
+                .collect::<Vec<_>>()
+                .join(",");
+            run_tool("taskset", &["-p", "-c", &cpu_list, &pid.to_string()])
+        }
+
+        #[cfg(not(target_os = "linux"))]
+        {
+            let _ = (pid, cpuset);
+            Err(LunaError::SystemOperation(
+                "CPU affinity is not supported on this platform".to_string(),
+            ))
+        }
+    }
+}
+
+#[cfg(unix)]
+fn run_tool(program: &str, args: &[&str]) -> Result<()> {
+    let output = std::process::Command::new(program)
+        .args(args)
+        .output()
+        .map_err(|e| LunaError::SystemOperation(format!("Failed to run {}: {}", program, e)))?;
+    if !output.status.success() {
+        return Err(LunaError::SystemOperation(format!(
+            "{} failed: {}",
+            program,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(())
+}
+
 pub struct ProcessOptimizer {
     priority_rules: Vec<PriorityRule>,
-    affinity_map: HashMap<u32, CpuSet>,
+    affinity_map: RwLock<HashMap<u32, CpuSet>>,
+    backend: Arc<dyn PriorityBackend>,
+    core_count: usize,
 }
 
 impl ProcessOptimizer {
     pub fn new() -> Self {
+        Self::from_process_manager(Arc::new(ProcessManager::new()))
+    }
+
+    /// Create an optimizer that resolves processes through a shared manager
+    pub fn from_process_manager(processes: Arc<ProcessManager>) -> Self {
         Self {
             priority_rules: Vec::new(),
-            affinity_map: HashMap::new(),
+            affinity_map: RwLock::new(HashMap::new()),
+            backend: Arc::new(SystemPriorityBackend::new(processes)),
+            core_count: num_cpus::get(),
         }
     }
 
+    /// Use a custom priority backend
+    pub fn with_backend(mut self, backend: Arc<dyn PriorityBackend>) -> Self {
+        self.backend = backend;
+        self
+    }
+
+    /// Number of logical CPU cores affinity can target
+    pub fn core_count(&self) -> usize {
+        self.core_count
+    }
+
     pub fn add_rule(&mut self, rule: PriorityRule) {
         self.priority_rules.push(rule);
     }
 
     pub async fn set_priority(&self, pid: u32, priority: PriorityLevel) -> Result<()> {
-        #[cfg(target_os = "linux")]
-        {
-            let nice_value = match priority {
-                PriorityLevel::Idle => 19,
-                PriorityLevel::Low => 10,
-                PriorityLevel::BelowNormal => 5,
-                PriorityLevel::Normal => 0,
-                PriorityLevel::AboveNormal => -5,
-                PriorityLevel::High => -10,
-                PriorityLevel::Realtime => -20,
-            };
-
-            let _ = std::process::Command::new("renice")
-                .args([&nice_value.to_string(), "-p", &pid.to_string()])
-                .output();
-        }
+        info!("Setting priority of {} to {:?}", pid, priority);
+        self.backend.set_priority(pid, priority)
+    }
+
+    pub async fn set_affinity(&self, pid: u32, cpuset: CpuSet) -> Result<()> {
+        cpuset.validate(self.core_count)?;
+        info!("Pinning {} to cores {:?}", pid, cpuset.cores);
+        self.backend.set_affinity(pid, &cpuset)?;
+        self.affinity_map.write().insert(pid, cpuset);
         Ok(())
     }
 
-    pub async fn set_affinity(&mut self, pid: u32, cpuset: CpuSet) -> Result<()> {
-        self.affinity_map.insert(pid, cpuset.clone());
+    /// Affinity last applied to a process through this optimizer
+    pub fn affinity_of(&self, pid: u32) -> Option<CpuSet> {
+        self.affinity_map.read().get(&pid).cloned()
+    }
 
-        #[cfg(target_os = "linux")]
-        {
-            let cpu_list = cpuset
-                .cores
-                .iter()
-                .map(|c| c.to_string())
-                .collect::<Vec<_>>()
-                .join(",");
+    /// Perform a named action on every process matching `target`
+    ///
+    /// Supported actions: priority (value is a spoken level), affinity
+    /// (value is a core list).
+    pub async fn perform(&self, action: &str, target: &str, value: Option<&str>) -> Result<String> {
+        enum Change {
+            Priority(PriorityLevel),
+            Affinity(CpuSet),
+        }
+
+        // Validate before touching any process
+        let change = match action {
+            "priority" => {
+                let level = value.and_then(PriorityLevel::parse).ok_or_else(|| {
+                    LunaError::InvalidParameter(format!(
+                        "Unknown priority: {}",
+                        value.unwrap_or_default()
+                    ))
+                })?;
+                Change::Priority(level)
+            }
+            "affinity" => {
+                let cpuset = value.and_then(CpuSet::parse).ok_or_else(|| {
+                    LunaError::InvalidParameter(format!(
+                        "Unknown CPU cores: {}",
+                        value.unwrap_or_default()
+                    ))
+                })?;
+                cpuset.validate(self.core_count)?;
+                Change::Affinity(cpuset)
+            }
+            _ => return Ok(format!("Unknown process action: {}", action)),
+        };
 
-            let _ = std::process::Command::new("taskset")
-                .args(["-p", "-c", &cpu_list, &pid.to_string()])
-                .output();
+        let pids = self.backend.find_processes(target).await;
+        if pids.is_empty() {
+            return Ok(format!("I can't find a process called {}", target));
         }
-        Ok(())
+
+        for &pid in &pids {
+            match &change {
+                Change::Priority(level) => self.set_priority(pid, *level).await?,
+                Change::Affinity(cpuset) => self.set_affinity(pid, cpuset.clone()).await?,
+            }
+        }
+
+        let processes = if pids.len() == 1 {
+            String::new()
+        } else {
+            format!(" ({} processes)", pids.len())
+        };
+        Ok(match change {
+            Change::Priority(level) => {
+                format!("Set {} to {} priority{}", target, level.name(), processes)
+            }
+            Change::Affinity(cpuset) => {
+                let unit = if cpuset.cores.len() == 1 {
+                    "core"
+                } else {
+                    "cores"
+                };
+                format!(
+                    "Pinned {} to {} {}{}",
+                    target,
+                    unit,
+                    cpuset.spoken(),
+                    processes
+                )
+            }
+        })
     }
 
     pub async fn optimize_for_gaming(&self) -> Result<()> {
@@ -98,3 +361,123 @@ impl Default for ProcessOptimizer {
         Self::new()
     }
 }
+
+/// Backend that records requested changes, for tests
+#[cfg(test)]
+pub(crate) mod mock {
+    use super::*;
+    use parking_lot::Mutex;
+
+    #[derive(Default)]
+    pub struct MockPriorityBackend {
+        pub processes: HashMap<String, Vec<Pid>>,
+        calls: Mutex<Vec<String>>,
+    }
+
+    impl MockPriorityBackend {
+        pub fn with_process(name: &str, pids: &[Pid]) -> Arc<Self> {
+            let mut processes = HashMap::new();
+            processes.insert(name.to_string(), pids.to_vec());
+            Arc::new(Self {
+                processes,
+                calls: Mutex::new(Vec::new()),
+            })
+        }
+
+        /// Requested changes, e.g. "priority 42 10" or "affinity 42 0,1"
+        pub fn calls(&self) -> Vec<String> {
+            self.calls.lock().clone()
+        }
+    }
+
+    #[async_trait]
+    impl PriorityBackend for MockPriorityBackend {
+        async fn find_processes(&self, name: &str) -> Vec<Pid> {
+            self.processes.get(name).cloned().unwrap_or_default()
+        }
+
+        fn set_priority(&self, pid: Pid, priority: PriorityLevel) -> Result<()> {
+            self.calls
+                .lock()
+                .push(format!("priority {} {}", pid, priority.nice()));
+            Ok(())
+        }
+
+        fn set_affinity(&self, pid: Pid, cpuset: &CpuSet) -> Result<()> {
+            let cores: Vec<String> = cpuset.cores.iter().map(|c| c.to_string()).collect();
+            self.calls
+                .lock()
+                .push(format!("affinity {} {}", pid, cores.join(",")));
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::mock::MockPriorityBackend;
+    use super::*;
+
+    #[test]
+    fn test_parse_cpu_set() {
+        assert_eq!(CpuSet::parse("0 and 1").unwrap().cores, [0, 1]);
+        assert_eq!(CpuSet::parse("2, 0-1").unwrap().cores, [0, 1, 2]);
+        assert_eq!(CpuSet::parse("0 to 3").unwrap().cores, [0, 1, 2, 3]);
+        assert!(CpuSet::parse("the fast ones").is_none());
+
+        let cpuset = CpuSet { cores: vec![0, 4] };
+        assert!(cpuset.validate(8).is_ok());
+        assert!(cpuset.validate(4).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_perform_records_requests() {
+        let backend = MockPriorityBackend::with_process("chrome", &[41, 42]);
+        let optimizer = ProcessOptimizer::new().with_backend(backend.clone());
+
+        assert_eq!(
+            optimizer
+                .perform("priority", "chrome", Some("lower"))
+                .await
+                .unwrap(),
+            "Set chrome to low priority (2 processes)"
+        );
+        assert_eq!(
+            optimizer
+                .perform("affinity", "chrome", Some("0"))
+                .await
+                .unwrap(),
+            "Pinned chrome to core 0 (2 processes)"
+        );
+        assert_eq!(
+            backend.calls(),
+            [
+                "priority 41 10",
+                "priority 42 10",
+                "affinity 41 0",
+                "affinity 42 0"
+            ]
+        );
+        assert_eq!(optimizer.affinity_of(42).unwrap().cores, [0]);
+    }
+
+    #[tokio::test]
+    async fn test_perform_rejects_missing_cores() {
+        let backend = MockPriorityBackend::with_process("build", &[7]);
+        let optimizer = ProcessOptimizer::new().with_backend(backend.clone());
+        let missing = optimizer.core_count().to_string();
+
+        assert!(optimizer
+            .perform("affinity", "build", Some(missing.as_str()))
+            .await
+            .is_err());
+        assert_eq!(
+            optimizer
+                .perform("priority", "firefox", Some("high"))
+                .await
+                .unwrap(),
+            "I can't find a process called firefox"
+        );
+        assert!(backend.calls().is_empty());
+    }
+}
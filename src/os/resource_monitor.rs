@@ -8,11 +8,12 @@ use crate::events::{EventBus, LunaEvent};
 use crate::metrics::Metrics;
 use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use std::time::{Duration, Instant};
-use sysinfo::System;
+use std::time::{Duration, Instant, SystemTime};
+use sysinfo::{Networks, System};
 use tokio::sync::RwLock;
-use tracing::{debug, error, info, warn};
+use tracing::{debug, info, warn};
 
 /// System metrics snapshot
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -55,6 +56,15 @@ pub struct GpuMetrics {
     pub temperature_c: Option<f32>,
 }
 
+/// A running process and its resource usage
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProcessUsage {
+    pub pid: u32,
+    pub name: String,
+    pub cpu_percent: f32,
+    pub memory_mb: u64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BatteryInfo {
     pub charge_percent: u8,
@@ -63,6 +73,86 @@ pub struct BatteryInfo {
     pub health_percent: Option<u8>,
 }
 
+impl BatteryInfo {
+    /// Read the system battery, or None on machines without one
+    pub fn read() -> Option<Self> {
+        #[cfg(target_os = "linux")]
+        {
+            Self::from_power_supply(Path::new("/sys/class/power_supply"))
+        }
+
+        #[cfg(not(target_os = "linux"))]
+        {
+            None
+        }
+    }
+
+    /// Read the first battery under a sysfs `power_supply` directory
+    ///
+    /// Time remaining is until empty when discharging and until full when
+    /// charging, from energy (µWh/µW) or charge (µAh/µA) counters.
+    #[cfg(any(target_os = "linux", test))]
+    fn from_power_supply(dir: &Path) -> Option<Self> {
+        let read = |supply: &Path, name: &str| {
+            std::fs::read_to_string(supply.join(name))
+                .ok()
+                .map(|s| s.trim().to_string())
+        };
+        let number =
+            |supply: &Path, name: &str| read(supply, name).and_then(|s| s.parse::<f64>().ok());
+
+        let mut supplies: Vec<PathBuf> = std::fs::read_dir(dir)
+            .ok()?
+            .flatten()
+            .map(|entry| entry.path())
+            .collect();
+        supplies.sort();
+        let supply = supplies
+            .into_iter()
+            .find(|path| read(path, "type").as_deref() == Some("Battery"))?;
+
+        let charge_percent = number(&supply, "capacity")?.clamp(0.0, 100.0) as u8;
+        let is_charging = read(&supply, "status").as_deref() == Some("Charging");
+
+        let counters = ["energy", "charge"].iter().find_map(|kind| {
+            let now = number(&supply, &format!("{}_now", kind))?;
+            let full = number(&supply, &format!("{}_full", kind))?;
+            let design = number(&supply, &format!("{}_full_design", kind));
+            let rate = number(
+                &supply,
+                if *kind == "energy" {
+                    "power_now"
+                } else {
+                    "current_now"
+                },
+            )
+            .map(f64::abs);
+            Some((now, full, design, rate))
+        });
+
+        let (time_remaining_mins, health_percent) = match counters {
+            Some((now, full, design, rate)) => {
+                let remaining = if is_charging { full - now } else { now };
+                let time = rate
+                    .filter(|r| *r > 0.0 && remaining > 0.0)
+                    .map(|r| (remaining / r * 60.0).round() as u32);
+                let health = design
+                    .filter(|d| *d > 0.0)
+                    .map(|d| (full / d * 100.0).clamp(0.0, 100.0) as u8);
+                (time, health)
+            }
+            None => (None, None),
+        };
+
+        Some(Self {
+            charge_percent,
+            is_charging,
+            time_remaining_mins,
+            health_percent,
+        })
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Temperature {
     pub label: String,
@@ -81,6 +171,39 @@ pub struct ResourceThresholds {
     pub disk_critical: u8,
     pub temperature_warning: f32,
     pub temperature_critical: f32,
+    /// GPU temperature alerts (None disables)
+    #[serde(default)]
+    pub gpu_temperature_warning: Option<f32>,
+    #[serde(default)]
+    pub gpu_temperature_critical: Option<f32>,
+    /// Disk throughput (read + write) considered high
+    #[serde(default = "default_disk_io_warning_mb_per_sec")]
+    pub disk_io_warning_mb_per_sec: f64,
+    /// Network throughput (received + transmitted) considered high
+    #[serde(default = "default_network_io_warning_mb_per_sec")]
+    pub network_io_warning_mb_per_sec: f64,
+    /// Consecutive samples above an I/O threshold before alerting
+    #[serde(default = "default_io_sustained_samples")]
+    pub io_sustained_samples: usize,
+    /// Standard deviations from the recent mean that count as an anomaly
+    #[serde(default = "default_anomaly_z_score")]
+    pub anomaly_z_score: f64,
+}
+
+fn default_disk_io_warning_mb_per_sec() -> f64 {
+    100.0
+}
+
+fn default_network_io_warning_mb_per_sec() -> f64 {
+    50.0
+}
+
+fn default_io_sustained_samples() -> usize {
+    6
+}
+
+fn default_anomaly_z_score() -> f64 {
+    3.0
 }
 
 impl Default for ResourceThresholds {
@@ -94,8 +217,219 @@ impl Default for ResourceThresholds {
             disk_critical: 95,
             temperature_warning: 75.0,
             temperature_critical: 90.0,
+            gpu_temperature_warning: Some(80.0),
+            gpu_temperature_critical: Some(90.0),
+            disk_io_warning_mb_per_sec: default_disk_io_warning_mb_per_sec(),
+            network_io_warning_mb_per_sec: default_network_io_warning_mb_per_sec(),
+            io_sustained_samples: default_io_sustained_samples(),
+            anomaly_z_score: default_anomaly_z_score(),
+        }
+    }
+}
+
+/// GPU metrics source
+///
+/// Uses NVML on NVIDIA hardware when built with the `nvidia` feature; reports
+/// nothing otherwise.
+pub struct GpuProbe {
+    #[cfg(feature = "nvidia")]
+    nvml: Option<nvml_wrapper::Nvml>,
+}
+
+impl GpuProbe {
+    /// Initialize NVML if it's available
+    pub fn new() -> Self {
+        #[cfg(feature = "nvidia")]
+        {
+            let nvml = match nvml_wrapper::Nvml::init() {
+                Ok(nvml) => {
+                    info!("NVML initialized, GPU metrics enabled");
+                    Some(nvml)
+                }
+                Err(e) => {
+                    debug!("NVML unavailable, GPU metrics disabled: {}", e);
+                    None
+                }
+            };
+            Self { nvml }
+        }
+
+        #[cfg(not(feature = "nvidia"))]
+        Self {}
+    }
+
+    /// Whether GPU metrics can be collected
+    pub fn is_available(&self) -> bool {
+        #[cfg(feature = "nvidia")]
+        return self.nvml.is_some();
+
+        #[cfg(not(feature = "nvidia"))]
+        false
+    }
+
+    /// Sample GPU usage, aggregated across devices
+    ///
+    /// Reports the busiest device's utilization and temperature and the
+    /// summed memory of all devices. Returns None without a GPU.
+    pub fn sample(&self) -> Option<GpuMetrics> {
+        #[cfg(feature = "nvidia")]
+        {
+            use nvml_wrapper::enum_wrappers::device::TemperatureSensor;
+
+            let nvml = self.nvml.as_ref()?;
+            let count = nvml.device_count().ok()?;
+
+            let mut metrics: Option<GpuMetrics> = None;
+            for index in 0..count {
+                let Ok(device) = nvml.device_by_index(index) else {
+                    continue;
+                };
+                let usage = device
+                    .utilization_rates()
+                    .map(|u| u.gpu as f32)
+                    .unwrap_or(0.0);
+                let (used, total) = device
+                    .memory_info()
+                    .map(|m| (m.used / 1024 / 1024, m.total / 1024 / 1024))
+                    .unwrap_or((0, 0));
+                let temp = device
+                    .temperature(TemperatureSensor::Gpu)
+                    .ok()
+                    .map(|t| t as f32);
+
+                let gpu = metrics.get_or_insert(GpuMetrics {
+                    usage_percent: 0.0,
+                    memory_used_mb: 0,
+                    memory_total_mb: 0,
+                    temperature_c: None,
+                });
+                gpu.usage_percent = gpu.usage_percent.max(usage);
+                gpu.memory_used_mb += used;
+                gpu.memory_total_mb += total;
+                gpu.temperature_c = match (gpu.temperature_c, temp) {
+                    (Some(a), Some(b)) => Some(a.max(b)),
+                    (a, b) => a.or(b),
+                };
+            }
+
+            metrics
+        }
+
+        #[cfg(not(feature = "nvidia"))]
+        None
+    }
+}
+
+impl Default for GpuProbe {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Cumulative disk and network byte counters at a point in time
+#[derive(Debug, Clone)]
+pub struct IoCounters {
+    pub at: Instant,
+    pub disk_read_bytes: u64,
+    pub disk_written_bytes: u64,
+    pub net_received_bytes: u64,
+    pub net_transmitted_bytes: u64,
+}
+
+impl IoCounters {
+    /// Read totals from process disk usage and network interfaces
+    pub fn read(system: &System, networks: &Networks) -> Self {
+        let (disk_read_bytes, disk_written_bytes) =
+            system
+                .processes()
+                .values()
+                .fold((0u64, 0u64), |(read, written), process| {
+                    let usage = process.disk_usage();
+                    (
+                        read.saturating_add(usage.total_read_bytes),
+                        written.saturating_add(usage.total_written_bytes),
+                    )
+                });
+
+        let (net_received_bytes, net_transmitted_bytes) =
+            networks
+                .iter()
+                .fold((0u64, 0u64), |(received, transmitted), (_, data)| {
+                    (
+                        received.saturating_add(data.total_received()),
+                        transmitted.saturating_add(data.total_transmitted()),
+                    )
+                });
+
+        Self {
+            at: Instant::now(),
+            disk_read_bytes,
+            disk_written_bytes,
+            net_received_bytes,
+            net_transmitted_bytes,
+        }
+    }
+
+    /// Per-second rates since an earlier reading
+    ///
+    /// Counters that went backwards (exited processes, interface resets)
+    /// count as zero. Without a previous reading all rates are zero.
+    pub fn rates_since(&self, previous: Option<&IoCounters>) -> (DiskIO, NetworkIO) {
+        let elapsed = previous
+            .map(|p| self.at.saturating_duration_since(p.at).as_secs_f64())
+            .unwrap_or(0.0);
+        let rate = |current: u64, prev: fn(&IoCounters) -> u64| match previous {
+            Some(p) if elapsed > 0.0 => (current.saturating_sub(prev(p)) as f64 / elapsed) as u64,
+            _ => 0,
+        };
+
+        let disk_io = DiskIO {
+            read_bytes_per_sec: rate(self.disk_read_bytes, |p| p.disk_read_bytes),
+            write_bytes_per_sec: rate(self.disk_written_bytes, |p| p.disk_written_bytes),
+            total_read_bytes: self.disk_read_bytes,
+            total_write_bytes: self.disk_written_bytes,
+        };
+        let network_io = NetworkIO {
+            received_bytes_per_sec: rate(self.net_received_bytes, |p| p.net_received_bytes),
+            transmitted_bytes_per_sec: rate(self.net_transmitted_bytes, |p| {
+                p.net_transmitted_bytes
+            }),
+            total_received_bytes: self.net_received_bytes,
+            total_transmitted_bytes: self.net_transmitted_bytes,
+        };
+
+        (disk_io, network_io)
+    }
+}
+
+/// Tracks I/O counters between samples to produce rates
+pub struct IoTracker {
+    networks: Networks,
+    previous: Option<IoCounters>,
+}
+
+impl IoTracker {
+    pub fn new() -> Self {
+        Self {
+            networks: Networks::new_with_refreshed_list(),
+            previous: None,
         }
     }
+
+    /// Read current counters and return rates since the last call
+    pub fn sample(&mut self, system: &System) -> (DiskIO, NetworkIO) {
+        self.networks.refresh();
+        let current = IoCounters::read(system, &self.networks);
+        let rates = current.rates_since(self.previous.as_ref());
+        self.previous = Some(current);
+        rates
+    }
+}
+
+impl Default for IoTracker {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 /// Metrics history for trend analysis
@@ -123,6 +457,44 @@ impl MetricsHistory {
         &self.samples
     }
 
+    /// Whether the last `count` samples all satisfy `predicate`
+    pub fn sustained(&self, count: usize, predicate: impl Fn(&SystemMetrics) -> bool) -> bool {
+        count > 0
+            && self.samples.len() >= count
+            && self.samples.iter().rev().take(count).all(predicate)
+    }
+
+    /// Disk and network throughput that stayed above thresholds
+    ///
+    /// Returns `(resource, current MB/s)` pairs for alerting.
+    pub fn sustained_io_alerts(&self, thresholds: &ResourceThresholds) -> Vec<(&'static str, f64)> {
+        const MB: f64 = 1024.0 * 1024.0;
+        let disk_mb = |m: &SystemMetrics| {
+            (m.disk_io.read_bytes_per_sec + m.disk_io.write_bytes_per_sec) as f64 / MB
+        };
+        let network_mb = |m: &SystemMetrics| {
+            (m.network_io.received_bytes_per_sec + m.network_io.transmitted_bytes_per_sec) as f64
+                / MB
+        };
+
+        let Some(latest) = self.samples.back() else {
+            return Vec::new();
+        };
+
+        let mut alerts = Vec::new();
+        if self.sustained(thresholds.io_sustained_samples, |m| {
+            disk_mb(m) > thresholds.disk_io_warning_mb_per_sec
+        }) {
+            alerts.push(("disk_io", disk_mb(latest)));
+        }
+        if self.sustained(thresholds.io_sustained_samples, |m| {
+            network_mb(m) > thresholds.network_io_warning_mb_per_sec
+        }) {
+            alerts.push(("network_io", network_mb(latest)));
+        }
+        alerts
+    }
+
     /// Predict when memory will fill (linear extrapolation)
     pub fn predict_memory_full(&self) -> Option<Duration> {
         if self.samples.len() < 10 {
@@ -161,50 +533,238 @@ impl MetricsHistory {
         }
     }
 
-    /// Detect anomalies (sudden spikes)
-    pub fn detect_anomalies(&self) -> Vec<String> {
+    /// Detect anomalies in the latest sample
+    ///
+    /// Compares CPU and memory against the mean and standard deviation of
+    /// the preceding window and flags values more than `z_threshold`
+    /// standard deviations away.
+    pub fn detect_anomalies(&self, z_threshold: f64) -> Vec<String> {
         let mut anomalies = Vec::new();
 
-        if self.samples.len() < 5 {
+        let Some(latest) = self.samples.back() else {
+            return anomalies;
+        };
+        let earlier: Vec<&SystemMetrics> = self
+            .samples
+            .iter()
+            .rev()
+            .skip(1)
+            .take(ANOMALY_WINDOW)
+            .collect();
+        if earlier.len() < MIN_ANOMALY_SAMPLES {
             return anomalies;
         }
 
-        let recent: Vec<_> = self.samples.iter().rev().take(5).collect();
-
-        // Check for CPU spike
-        let avg_cpu: f32 = recent[1..].iter().map(|m| m.cpu_total).sum::<f32>() / 4.0;
-        if recent[0].cpu_total > avg_cpu * 1.5 {
-            anomalies.push(format!(
-                "CPU spike: {:.1}% (avg: {:.1}%)",
-                recent[0].cpu_total, avg_cpu
-            ));
+        let cpu: Vec<f64> = earlier.iter().map(|m| m.cpu_total as f64).collect();
+        if let Some(z) = z_score(latest.cpu_total as f64, &cpu) {
+            if z.abs() > z_threshold {
+                anomalies.push(format!(
+                    "CPU {}: {:.1}% (mean: {:.1}%, z={:.1})",
+                    if z > 0.0 { "spike" } else { "drop" },
+                    latest.cpu_total,
+                    mean(&cpu),
+                    z
+                ));
+            }
         }
 
-        // Check for memory spike
-        let avg_mem: u64 = recent[1..].iter().map(|m| m.memory_used_mb).sum::<u64>() / 4;
-        let mem_increase = recent[0].memory_used_mb as f64 / avg_mem as f64;
-        if mem_increase > 1.3 {
-            anomalies.push(format!(
-                "Memory spike: {}MB (avg: {}MB)",
-                recent[0].memory_used_mb, avg_mem
-            ));
+        let memory: Vec<f64> = earlier.iter().map(|m| m.memory_used_mb as f64).collect();
+        if let Some(z) = z_score(latest.memory_used_mb as f64, &memory) {
+            if z.abs() > z_threshold {
+                anomalies.push(format!(
+                    "Memory {}: {}MB (mean: {:.0}MB, z={:.1})",
+                    if z > 0.0 { "spike" } else { "drop" },
+                    latest.memory_used_mb,
+                    mean(&memory),
+                    z
+                ));
+            }
         }
 
         anomalies
     }
 }
 
+/// Minimum preceding samples before anomalies are reported
+const MIN_ANOMALY_SAMPLES: usize = 5;
+
+/// Number of preceding samples used for anomaly statistics
+const ANOMALY_WINDOW: usize = 60;
+
+fn mean(values: &[f64]) -> f64 {
+    values.iter().sum::<f64>() / values.len() as f64
+}
+
+/// Standard score of `value` against `window`, None if the window is flat
+fn z_score(value: f64, window: &[f64]) -> Option<f64> {
+    if window.is_empty() {
+        return None;
+    }
+
+    let mean = mean(window);
+    let variance = window.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / window.len() as f64;
+    let std_dev = variance.sqrt();
+
+    if std_dev < f64::EPSILON {
+        return None;
+    }
+    Some((value - mean) / std_dev)
+}
+
+/// Process names never killed by memory optimization
+const DEFAULT_PROTECTED_PROCESSES: &[&str] = &[
+    "systemd",
+    "init",
+    "launchd",
+    "kernel_task",
+    "Xorg",
+    "Xwayland",
+    "gnome-shell",
+    "kwin_x11",
+    "kwin_wayland",
+    "plasmashell",
+    "WindowServer",
+    "loginwindow",
+    "explorer.exe",
+    "dwm.exe",
+    "csrss.exe",
+    "dbus-daemon",
+    "pipewire",
+    "pulseaudio",
+    "sshd",
+    "luna",
+];
+
+/// Limits on what disk optimization may delete
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiskCleanupPolicy {
+    /// Only files not modified for at least this long are removed
+    pub min_age: Duration,
+    /// Directories cleanup may delete from
+    pub allowed_dirs: Vec<PathBuf>,
+    /// Stop once this many bytes have been freed
+    pub max_total_bytes: Option<u64>,
+}
+
+impl Default for DiskCleanupPolicy {
+    fn default() -> Self {
+        Self {
+            min_age: Duration::from_secs(7 * 24 * 60 * 60),
+            allowed_dirs: vec![std::env::temp_dir()],
+            max_total_bytes: None,
+        }
+    }
+}
+
+/// Space freed in one allowed directory
+#[derive(Debug, Clone, Serialize)]
+pub struct DirCleanup {
+    pub dir: PathBuf,
+    pub freed_bytes: u64,
+    pub files_removed: usize,
+}
+
+/// Result of a disk cleanup run
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DiskCleanupReport {
+    /// Per-directory results, in allowlist order
+    pub dirs: Vec<DirCleanup>,
+    /// Files were only counted, not deleted
+    pub dry_run: bool,
+}
+
+impl DiskCleanupReport {
+    /// Bytes freed across all directories
+    pub fn total_freed(&self) -> u64 {
+        self.dirs.iter().map(|d| d.freed_bytes).sum()
+    }
+
+    /// Files removed across all directories
+    pub fn files_removed(&self) -> usize {
+        self.dirs.iter().map(|d| d.files_removed).sum()
+    }
+}
+
+/// Memory used by a process that qualifies it for termination
+const MEMORY_HOG_MB: u64 = 500;
+
 /// Optimization engine
+///
+/// Starts in dry-run mode: optimizations report what they would do without
+/// killing processes or deleting files until `with_dry_run(false)`.
 pub struct OptimizationEngine {
     enabled: bool,
+    dry_run: bool,
+    protected: Vec<String>,
+    disk_policy: DiskCleanupPolicy,
 }
 
 impl OptimizationEngine {
     pub fn new() -> Self {
-        Self { enabled: true }
+        Self {
+            enabled: true,
+            dry_run: true,
+            protected: DEFAULT_PROTECTED_PROCESSES
+                .iter()
+                .map(|name| name.to_string())
+                .collect(),
+            disk_policy: DiskCleanupPolicy::default(),
+        }
+    }
+
+    /// Set what disk cleanup may delete
+    pub fn with_disk_policy(mut self, policy: DiskCleanupPolicy) -> Self {
+        self.disk_policy = policy;
+        self
+    }
+
+    /// Add process names that must never be killed
+    pub fn with_protected(mut self, names: Vec<String>) -> Self {
+        self.protected.extend(names);
+        self
+    }
+
+    /// Only report actions (true) or actually perform them (false)
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Whether actions are only reported
+    pub fn is_dry_run(&self) -> bool {
+        self.dry_run
+    }
+
+    /// Check if a process is protected by name or is LUNA itself
+    pub fn is_protected(&self, pid: u32, name: &str) -> bool {
+        pid == std::process::id()
+            || self
+                .protected
+                .iter()
+                .any(|protected| protected.eq_ignore_ascii_case(name))
+    }
+
+    /// Pick up to 5 of the largest unprotected processes above 500MB
+    ///
+    /// `processes` holds `(pid, name, memory bytes)`.
+    pub fn select_memory_hogs(&self, processes: &[(u32, String, u64)]) -> Vec<u32> {
+        let mut candidates: Vec<_> = processes
+            .iter()
+            .filter(|(pid, name, _)| !self.is_protected(*pid, name))
+            .filter(|(_, _, mem_bytes)| mem_bytes / 1024 / 1024 > MEMORY_HOG_MB)
+            .collect();
+        candidates.sort_by_key(|(_, _, mem)| std::cmp::Reverse(*mem));
+
+        candidates
+            .into_iter()
+            .take(5)
+            .map(|(pid, _, _)| *pid)
+            .collect()
     }
 
     /// Auto-kill memory hogs when threshold exceeded
+    ///
+    /// Returns the selected PIDs; in dry-run mode they are only logged.
     pub async fn optimize_memory(
         &self,
         system: &mut System,
@@ -225,76 +785,149 @@ impl OptimizationEngine {
         warn!("Memory usage at {}%, running optimization", usage_percent);
 
         // Find memory hogs
-        let mut processes: Vec<_> = system
+        let processes: Vec<_> = system
             .processes()
             .iter()
-            .map(|(pid, proc)| (*pid, proc.memory()))
+            .map(|(pid, proc)| (pid.as_u32(), proc.name().to_string(), proc.memory()))
             .collect();
-        processes.sort_by_key(|(_, mem)| std::cmp::Reverse(*mem));
-
-        let mut killed = Vec::new();
-
-        // Kill top memory consumers (excluding critical processes)
-        for (pid, mem_bytes) in processes.iter().take(5) {
-            let mem_mb = mem_bytes / 1024 / 1024;
-            if mem_mb > 500 {
-                warn!(
-                    "Killing memory hog: PID {} using {}MB",
-                    pid.as_u32(),
-                    mem_mb
+        let selected = self.select_memory_hogs(&processes);
+
+        // Kill top memory consumers (excluding protected processes)
+        for pid in &selected {
+            let mem_mb = processes
+                .iter()
+                .find(|(p, _, _)| p == pid)
+                .map(|(_, _, mem)| mem / 1024 / 1024)
+                .unwrap_or(0);
+
+            if self.dry_run {
+                info!(
+                    "Dry run: would kill memory hog PID {} using {}MB",
+                    pid, mem_mb
                 );
-                killed.push(pid.as_u32());
+                continue;
+            }
 
-                #[cfg(target_os = "linux")]
-                {
-                    use nix::sys::signal::{kill as nix_kill, Signal};
-                    use nix::unistd::Pid;
-                    let _ = nix_kill(Pid::from_raw(pid.as_u32() as i32), Signal::SIGTERM);
-                }
+            warn!("Killing memory hog: PID {} using {}MB", pid, mem_mb);
+
+            #[cfg(target_os = "linux")]
+            {
+                use nix::sys::signal::{kill as nix_kill, Signal};
+                use nix::unistd::Pid;
+                let _ = nix_kill(Pid::from_raw(*pid as i32), Signal::SIGTERM);
             }
         }
 
-        Ok(killed)
+        Ok(selected)
     }
 
-    /// Clear temp files to free disk space
-    pub async fn optimize_disk(&self) -> Result<u64> {
+    /// Clear old temp files within the cleanup policy's allowed directories
+    ///
+    /// Only files older than `min_age` are removed, and only subdirectories
+    /// owned by the current user are descended into.
+    pub async fn optimize_disk(&self) -> Result<DiskCleanupReport> {
+        let mut report = DiskCleanupReport {
+            dry_run: self.dry_run,
+            ..Default::default()
+        };
         if !self.enabled {
-            return Ok(0);
+            return Ok(report);
         }
 
         info!("Running disk optimization");
 
-        #[cfg(target_os = "linux")]
-        {
-            use std::fs;
-            let tmp_dirs = vec!["/tmp", "/var/tmp"];
-            let mut freed_bytes = 0u64;
-
-            for tmp_dir in tmp_dirs {
-                if let Ok(entries) = fs::read_dir(tmp_dir) {
-                    for entry in entries.flatten() {
-                        if let Ok(metadata) = entry.metadata() {
-                            if metadata.is_file() {
-                                freed_bytes += metadata.len();
-                                let _ = fs::remove_file(entry.path());
-                            }
-                        }
-                    }
+        let policy = &self.disk_policy;
+        let cutoff = SystemTime::now()
+            .checked_sub(policy.min_age)
+            .unwrap_or(SystemTime::UNIX_EPOCH);
+        let mut budget = policy.max_total_bytes;
+
+        for dir in &policy.allowed_dirs {
+            let mut cleanup = DirCleanup {
+                dir: dir.clone(),
+                freed_bytes: 0,
+                files_removed: 0,
+            };
+            self.clean_dir(dir, cutoff, &mut budget, &mut cleanup);
+            report.dirs.push(cleanup);
+        }
+
+        Ok(report)
+    }
+
+    fn clean_dir(
+        &self,
+        dir: &Path,
+        cutoff: SystemTime,
+        budget: &mut Option<u64>,
+        cleanup: &mut DirCleanup,
+    ) {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return;
+        };
+
+        for entry in entries.flatten() {
+            // Don't follow symlinks out of the allowed directory
+            let Ok(metadata) = entry.path().symlink_metadata() else {
+                continue;
+            };
+
+            if metadata.is_dir() {
+                if owned_by_current_user(&metadata) {
+                    self.clean_dir(&entry.path(), cutoff, budget, cleanup);
                 }
+                continue;
+            }
+            if !metadata.is_file() {
+                continue;
             }
 
-            return Ok(freed_bytes);
+            let modified = metadata.modified().unwrap_or_else(|_| SystemTime::now());
+            if modified > cutoff {
+                continue;
+            }
+
+            let len = metadata.len();
+            if budget.is_some_and(|remaining| len > remaining) {
+                continue;
+            }
+
+            if !self.dry_run && std::fs::remove_file(entry.path()).is_err() {
+                continue;
+            }
+
+            debug!("Removed old temp file: {}", entry.path().display());
+            cleanup.freed_bytes += len;
+            cleanup.files_removed += 1;
+            if let Some(remaining) = budget {
+                *remaining -= len;
+            }
         }
+    }
+}
 
-        #[cfg(not(target_os = "linux"))]
-        Ok(0)
+/// Whether the current user owns a filesystem entry
+///
+/// Conservatively false where ownership can't be checked.
+fn owned_by_current_user(metadata: &std::fs::Metadata) -> bool {
+    #[cfg(target_os = "linux")]
+    {
+        use std::os::unix::fs::MetadataExt;
+        metadata.uid() == nix::unistd::getuid().as_raw()
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = metadata;
+        false
     }
 }
 
 /// Resource monitor with real-time tracking and optimization
 pub struct ResourceMonitor {
     system: Arc<RwLock<System>>,
+    gpu: Arc<GpuProbe>,
+    io: Arc<RwLock<IoTracker>>,
     metrics: Arc<RwLock<SystemMetrics>>,
     history: Arc<RwLock<MetricsHistory>>,
     thresholds: ResourceThresholds,
@@ -310,10 +943,14 @@ impl ResourceMonitor {
         let mut system = System::new_all();
         system.refresh_all();
 
-        let initial_metrics = Self::capture_metrics(&system);
+        let gpu = GpuProbe::new();
+        let mut io = IoTracker::new();
+        let initial_metrics = Self::capture_metrics(&system, &gpu, &mut io);
 
         Self {
             system: Arc::new(RwLock::new(system)),
+            gpu: Arc::new(gpu),
+            io: Arc::new(RwLock::new(io)),
             metrics: Arc::new(RwLock::new(initial_metrics)),
             history: Arc::new(RwLock::new(MetricsHistory::new(720))), // 1 hour at 5s intervals
             thresholds: ResourceThresholds::default(),
@@ -342,6 +979,12 @@ impl ResourceMonitor {
         self
     }
 
+    /// Replace the optimization engine (dry-run by default)
+    pub fn with_optimization_engine(mut self, engine: OptimizationEngine) -> Self {
+        self.optimization_engine = engine;
+        self
+    }
+
     /// Start real-time monitoring
     pub async fn start_monitoring(&self) -> Result<()> {
         let mut active = self.monitoring_active.write().await;
@@ -354,6 +997,8 @@ impl ResourceMonitor {
         info!("Starting resource monitoring");
 
         let system = Arc::clone(&self.system);
+        let gpu = Arc::clone(&self.gpu);
+        let io = Arc::clone(&self.io);
         let metrics = Arc::clone(&self.metrics);
         let history = Arc::clone(&self.history);
         let active_flag = Arc::clone(&self.monitoring_active);
@@ -376,7 +1021,7 @@ impl ResourceMonitor {
                 sys.refresh_all();
 
                 // Capture metrics
-                let current_metrics = Self::capture_metrics(&sys);
+                let current_metrics = Self::capture_metrics(&sys, &gpu, &mut *io.write().await);
                 drop(sys);
 
                 // Update current metrics
@@ -386,12 +1031,12 @@ impl ResourceMonitor {
                 }
 
                 // Add to history
-                {
+                let io_alerts = {
                     let mut h = history.write().await;
                     h.add_sample(current_metrics.clone());
 
                     // Check for anomalies
-                    let anomalies = h.detect_anomalies();
+                    let anomalies = h.detect_anomalies(thresholds.anomaly_z_score);
                     for anomaly in anomalies {
                         warn!("Resource anomaly: {}", anomaly);
                     }
@@ -403,10 +1048,27 @@ impl ResourceMonitor {
                             time_to_fill.as_secs()
                         );
                     }
-                }
+
+                    h.sustained_io_alerts(&thresholds)
+                };
 
                 // Check thresholds
                 Self::check_thresholds(&current_metrics, &thresholds, &event_bus).await;
+
+                for (resource, mb_per_sec) in io_alerts {
+                    warn!("WARNING: Sustained {} at {:.1} MB/s", resource, mb_per_sec);
+                    if let Some(ref bus) = event_bus {
+                        bus.publish(LunaEvent::Custom {
+                            event_type: "ResourceAlert".to_string(),
+                            data: serde_json::json!({
+                                "resource": resource,
+                                "level": "warning",
+                                "value": mb_per_sec,
+                            }),
+                        })
+                        .await;
+                    }
+                }
             }
         });
 
@@ -421,7 +1083,7 @@ impl ResourceMonitor {
     }
 
     /// Capture current system metrics
-    fn capture_metrics(system: &System) -> SystemMetrics {
+    fn capture_metrics(system: &System, gpu: &GpuProbe, io: &mut IoTracker) -> SystemMetrics {
         let cpu_per_core: Vec<f32> = system.cpus().iter().map(|cpu| cpu.cpu_usage()).collect();
         let cpu_total = system.global_cpu_info().cpu_usage();
 
@@ -429,21 +1091,8 @@ impl ResourceMonitor {
         let memory_used_mb = system.used_memory() / 1024 / 1024;
         let memory_available_mb = system.available_memory() / 1024 / 1024;
 
-        // Network stats - simplified for sysinfo 0.30
-        let network_io = NetworkIO {
-            received_bytes_per_sec: 0,
-            transmitted_bytes_per_sec: 0,
-            total_received_bytes: 0,
-            total_transmitted_bytes: 0,
-        };
-
-        // Disk stats
-        let disk_io = DiskIO {
-            read_bytes_per_sec: 0,
-            write_bytes_per_sec: 0,
-            total_read_bytes: 0,
-            total_write_bytes: 0,
-        };
+        // Disk and network rates since the previous sample
+        let (disk_io, network_io) = io.sample(system);
 
         SystemMetrics {
             timestamp: Instant::now(),
@@ -454,8 +1103,8 @@ impl ResourceMonitor {
             memory_available_mb,
             disk_io,
             network_io,
-            gpu_usage: None,
-            battery: None,
+            gpu_usage: gpu.sample(),
+            battery: BatteryInfo::read(),
             temperatures: Vec::new(),
         }
     }
@@ -503,6 +1152,33 @@ impl ResourceMonitor {
         } else if memory_percent > thresholds.memory_warning {
             warn!("WARNING: Memory usage at {}%", memory_percent);
         }
+
+        // GPU temperature check
+        let gpu_temp = metrics.gpu_usage.as_ref().and_then(|g| g.temperature_c);
+        if let Some(temp) = gpu_temp {
+            if thresholds
+                .gpu_temperature_critical
+                .is_some_and(|limit| temp > limit)
+            {
+                warn!("CRITICAL: GPU temperature at {:.0}°C", temp);
+                if let Some(ref bus) = event_bus {
+                    bus.publish(LunaEvent::Custom {
+                        event_type: "ResourceAlert".to_string(),
+                        data: serde_json::json!({
+                            "resource": "gpu_temperature",
+                            "level": "critical",
+                            "value": temp,
+                        }),
+                    })
+                    .await;
+                }
+            } else if thresholds
+                .gpu_temperature_warning
+                .is_some_and(|limit| temp > limit)
+            {
+                warn!("WARNING: GPU temperature at {:.0}°C", temp);
+            }
+        }
     }
 
     /// Get current metrics
@@ -510,6 +1186,39 @@ impl ResourceMonitor {
         self.metrics.read().await.clone()
     }
 
+    /// Take a fresh sample now instead of waiting for the monitoring loop
+    ///
+    /// CPU usage is measured since the previous refresh, so call this at
+    /// least `sysinfo::MINIMUM_CPU_UPDATE_INTERVAL` apart.
+    pub async fn refresh(&self) -> SystemMetrics {
+        let mut sys = self.system.write().await;
+        sys.refresh_all();
+        let current = Self::capture_metrics(&sys, &self.gpu, &mut *self.io.write().await);
+        drop(sys);
+
+        *self.metrics.write().await = current.clone();
+        current
+    }
+
+    /// Processes using the most memory, as of the last refresh
+    pub async fn top_processes(&self, count: usize) -> Vec<ProcessUsage> {
+        let sys = self.system.read().await;
+        let mut processes: Vec<ProcessUsage> = sys
+            .processes()
+            .iter()
+            .map(|(pid, proc)| ProcessUsage {
+                pid: pid.as_u32(),
+                name: proc.name().to_string(),
+                cpu_percent: proc.cpu_usage(),
+                memory_mb: proc.memory() / 1024 / 1024,
+            })
+            .collect();
+
+        processes.sort_by_key(|p| std::cmp::Reverse(p.memory_mb));
+        processes.truncate(count);
+        processes
+    }
+
     /// Get metrics history
     pub async fn get_history(&self) -> Vec<SystemMetrics> {
         let history = self.history.read().await;
@@ -518,7 +1227,11 @@ impl ResourceMonitor {
 
     /// Run optimization
     pub async fn optimize(&self) -> Result<()> {
-        info!("Running system optimization");
+        let dry_run = self.optimization_engine.is_dry_run();
+        info!(
+            "Running system optimization{}",
+            if dry_run { " (dry run)" } else { "" }
+        );
 
         let mut system = self.system.write().await;
 
@@ -528,15 +1241,25 @@ impl ResourceMonitor {
             .optimize_memory(&mut system, 90)
             .await?;
         if !killed.is_empty() {
-            info!("Killed {} memory-intensive processes", killed.len());
+            info!(
+                "{} {} memory-intensive processes",
+                if dry_run { "Would kill" } else { "Killed" },
+                killed.len()
+            );
         }
 
         drop(system);
 
         // Optimize disk
-        let freed_bytes = self.optimization_engine.optimize_disk().await?;
-        if freed_bytes > 0 {
-            info!("Freed {} MB of disk space", freed_bytes / 1024 / 1024);
+        let report = self.optimization_engine.optimize_disk().await?;
+        for dir in report.dirs.iter().filter(|d| d.files_removed > 0) {
+            info!(
+                "{} {} MB in {} ({} files)",
+                if dry_run { "Would free" } else { "Freed" },
+                dir.freed_bytes / 1024 / 1024,
+                dir.dir.display(),
+                dir.files_removed
+            );
         }
 
         Ok(())
@@ -560,6 +1283,17 @@ mod tests {
         assert!(metrics.cpu_total >= 0.0);
     }
 
+    #[tokio::test]
+    async fn test_refresh_and_top_processes() {
+        let monitor = ResourceMonitor::new();
+        let metrics = monitor.refresh().await;
+        assert!(metrics.memory_total_mb > 0);
+
+        let top = monitor.top_processes(3).await;
+        assert!(top.len() <= 3);
+        assert!(top.windows(2).all(|w| w[0].memory_mb >= w[1].memory_mb));
+    }
+
     #[tokio::test]
     async fn test_monitoring() {
         let monitor = ResourceMonitor::new();
@@ -571,6 +1305,310 @@ mod tests {
         monitor.stop_monitoring().await;
     }
 
+    #[tokio::test]
+    async fn test_gpu_metrics_populated_or_none() {
+        let probe = GpuProbe::new();
+        match probe.sample() {
+            Some(gpu) => {
+                assert!(probe.is_available());
+                assert!((0.0..=100.0).contains(&gpu.usage_percent));
+                assert!(gpu.memory_used_mb <= gpu.memory_total_mb);
+            }
+            None => println!("No GPU metrics in test environment"),
+        }
+
+        // Monitor creation must not panic either way
+        let monitor = ResourceMonitor::new();
+        let _ = monitor.get_current_metrics().await.gpu_usage;
+    }
+
+    #[tokio::test]
+    async fn test_gpu_temperature_alert() {
+        use crate::events::EventKind;
+        use std::sync::atomic::{AtomicBool, Ordering};
+
+        let alerted = Arc::new(AtomicBool::new(false));
+        let alerted_clone = Arc::clone(&alerted);
+
+        let bus = Arc::new(EventBus::new());
+        let _handle = bus.start_processing().await;
+        bus.subscribe_kinds(&[EventKind::Custom], move |envelope| {
+            if let LunaEvent::Custom { data, .. } = &envelope.event {
+                if data["resource"] == "gpu_temperature" {
+                    alerted_clone.store(true, Ordering::SeqCst);
+                }
+            }
+        })
+        .await;
+
+        let mut metrics = ResourceMonitor::capture_metrics(
+            &System::new(),
+            &GpuProbe::new(),
+            &mut IoTracker::new(),
+        );
+        metrics.memory_total_mb = metrics.memory_total_mb.max(1);
+        metrics.gpu_usage = Some(GpuMetrics {
+            usage_percent: 50.0,
+            memory_used_mb: 1024,
+            memory_total_mb: 8192,
+            temperature_c: Some(95.0),
+        });
+
+        ResourceMonitor::check_thresholds(
+            &metrics,
+            &ResourceThresholds::default(),
+            &Some(Arc::clone(&bus)),
+        )
+        .await;
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(alerted.load(Ordering::SeqCst));
+
+        // Disabled threshold stays quiet
+        alerted.store(false, Ordering::SeqCst);
+        let thresholds = ResourceThresholds {
+            gpu_temperature_critical: None,
+            ..Default::default()
+        };
+        ResourceMonitor::check_thresholds(&metrics, &thresholds, &Some(Arc::clone(&bus))).await;
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(!alerted.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_protected_processes_never_selected() {
+        const MB: u64 = 1024 * 1024;
+        let engine = OptimizationEngine::new().with_protected(vec!["code".to_string()]);
+        let processes = vec![
+            (100, "Xorg".to_string(), 4000 * MB),
+            (101, "code".to_string(), 3000 * MB),
+            (std::process::id(), "luna-test".to_string(), 2500 * MB),
+            (102, "SYSTEMD".to_string(), 2000 * MB),
+            (103, "chrome".to_string(), 1500 * MB),
+            (104, "slack".to_string(), 900 * MB),
+            (105, "small".to_string(), 100 * MB),
+        ];
+
+        let selected = engine.select_memory_hogs(&processes);
+        assert_eq!(selected, vec![103, 104]);
+    }
+
+    #[test]
+    fn test_optimization_defaults_to_dry_run() {
+        assert!(OptimizationEngine::new().is_dry_run());
+        assert!(!OptimizationEngine::new().with_dry_run(false).is_dry_run());
+    }
+
+    fn age_file(path: &Path, age: Duration) {
+        std::fs::File::options()
+            .write(true)
+            .open(path)
+            .unwrap()
+            .set_modified(SystemTime::now() - age)
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_disk_cleanup_removes_only_old_files() {
+        const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+        let allowed = tempfile::tempdir().unwrap();
+        let outside = tempfile::tempdir().unwrap();
+
+        let old = allowed.path().join("old.log");
+        let new = allowed.path().join("new.log");
+        let nested_old = allowed.path().join("cache").join("stale.bin");
+        let outside_old = outside.path().join("old.log");
+        std::fs::create_dir(allowed.path().join("cache")).unwrap();
+        for (path, size) in [
+            (&old, 100),
+            (&new, 50),
+            (&nested_old, 25),
+            (&outside_old, 10),
+        ] {
+            std::fs::write(path, vec![0u8; size]).unwrap();
+        }
+        age_file(&old, 10 * DAY);
+        age_file(&nested_old, 30 * DAY);
+        age_file(&outside_old, 30 * DAY);
+
+        let engine = OptimizationEngine::new()
+            .with_dry_run(false)
+            .with_disk_policy(DiskCleanupPolicy {
+                allowed_dirs: vec![allowed.path().to_path_buf()],
+                ..Default::default()
+            });
+        let report = engine.optimize_disk().await.unwrap();
+
+        assert!(!old.exists());
+        assert!(new.exists());
+        assert!(outside_old.exists());
+        assert_eq!(report.dirs.len(), 1);
+        assert_eq!(report.dirs[0].dir, allowed.path());
+
+        #[cfg(target_os = "linux")]
+        {
+            assert!(!nested_old.exists());
+            assert_eq!(report.total_freed(), 125);
+            assert_eq!(report.files_removed(), 2);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_disk_cleanup_dry_run_and_budget() {
+        let dir = tempfile::tempdir().unwrap();
+        for (name, size) in [("a", 300), ("b", 300), ("c", 100)] {
+            let path = dir.path().join(name);
+            std::fs::write(&path, vec![0u8; size]).unwrap();
+            age_file(&path, Duration::from_secs(8 * 24 * 60 * 60));
+        }
+
+        let policy = DiskCleanupPolicy {
+            allowed_dirs: vec![dir.path().to_path_buf()],
+            max_total_bytes: Some(450),
+            ..Default::default()
+        };
+
+        // Dry run reports but deletes nothing
+        let engine = OptimizationEngine::new().with_disk_policy(policy.clone());
+        let report = engine.optimize_disk().await.unwrap();
+        assert!(report.dry_run);
+        assert_eq!(report.total_freed(), 400);
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 3);
+
+        // Budget caps what is actually removed
+        let engine = OptimizationEngine::new()
+            .with_dry_run(false)
+            .with_disk_policy(policy);
+        let report = engine.optimize_disk().await.unwrap();
+        assert_eq!(report.total_freed(), 400);
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_io_rates_from_counter_delta() {
+        let start = Instant::now();
+        let first = IoCounters {
+            at: start,
+            disk_read_bytes: 1_000_000,
+            disk_written_bytes: 500_000,
+            net_received_bytes: 2_000,
+            net_transmitted_bytes: 8_000,
+        };
+        let second = IoCounters {
+            at: start + Duration::from_secs(2),
+            disk_read_bytes: 5_000_000,
+            disk_written_bytes: 500_000,
+            net_received_bytes: 12_000,
+            // Interface reset: counter went backwards
+            net_transmitted_bytes: 1_000,
+        };
+
+        let (disk, network) = second.rates_since(Some(&first));
+        assert_eq!(disk.read_bytes_per_sec, 2_000_000);
+        assert_eq!(disk.write_bytes_per_sec, 0);
+        assert_eq!(disk.total_read_bytes, 5_000_000);
+        assert_eq!(network.received_bytes_per_sec, 5_000);
+        assert_eq!(network.transmitted_bytes_per_sec, 0);
+
+        // First sample has no baseline
+        let (disk, _) = first.rates_since(None);
+        assert_eq!(disk.read_bytes_per_sec, 0);
+    }
+
+    #[test]
+    fn test_sustained_io_alerts() {
+        let thresholds = ResourceThresholds {
+            io_sustained_samples: 3,
+            ..Default::default()
+        };
+        let mut history = MetricsHistory::new(10);
+        let busy_disk = |read_mb: u64| {
+            let mut m = sample(10.0, 1000);
+            m.disk_io.read_bytes_per_sec = read_mb * 1024 * 1024;
+            m
+        };
+
+        history.add_sample(busy_disk(200));
+        history.add_sample(busy_disk(200));
+        assert!(history.sustained_io_alerts(&thresholds).is_empty());
+
+        history.add_sample(busy_disk(150));
+        let alerts = history.sustained_io_alerts(&thresholds);
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].0, "disk_io");
+        assert!((alerts[0].1 - 150.0).abs() < 0.01);
+
+        // One quiet sample breaks the streak
+        history.add_sample(busy_disk(1));
+        assert!(history.sustained_io_alerts(&thresholds).is_empty());
+    }
+
+    #[test]
+    fn test_single_outlier_is_one_anomaly() {
+        let mut history = MetricsHistory::new(100);
+        let cpu = [
+            41.0, 43.5, 40.2, 42.8, 39.9, 44.1, 41.7, 42.2, 40.8, 43.0, 95.0, 41.3, 42.6, 40.5,
+            43.3, 41.9,
+        ];
+
+        let mut anomalies = Vec::new();
+        for (i, value) in cpu.iter().enumerate() {
+            // Memory wobbles a little but never spikes
+            history.add_sample(sample(*value, 2000 + (i as u64 % 3) * 10));
+            anomalies.extend(history.detect_anomalies(3.0));
+        }
+
+        assert_eq!(anomalies.len(), 1, "{:?}", anomalies);
+        assert!(anomalies[0].starts_with("CPU spike: 95.0%"));
+        assert!(anomalies[0].contains("z="));
+    }
+
+    #[test]
+    fn test_z_score_threshold_is_configurable() {
+        let mut history = MetricsHistory::new(20);
+        for value in [50.0, 52.0, 48.0, 51.0, 49.0, 50.0, 56.0] {
+            history.add_sample(sample(value, 2000));
+        }
+
+        // 56% is ~4.6 standard deviations above the mean of the rest
+        assert_eq!(history.detect_anomalies(3.0).len(), 1);
+        assert!(history.detect_anomalies(5.0).is_empty());
+    }
+
+    #[test]
+    fn test_no_anomalies_with_short_history() {
+        let mut history = MetricsHistory::new(20);
+        for value in [50.0, 50.5, 99.0] {
+            history.add_sample(sample(value, 2000));
+        }
+        assert!(history.detect_anomalies(3.0).is_empty());
+    }
+
+    fn sample(cpu_total: f32, memory_used_mb: u64) -> SystemMetrics {
+        SystemMetrics {
+            timestamp: Instant::now(),
+            cpu_per_core: vec![cpu_total],
+            cpu_total,
+            memory_used_mb,
+            memory_total_mb: 8000,
+            memory_available_mb: 8000 - memory_used_mb,
+            disk_io: DiskIO {
+                read_bytes_per_sec: 0,
+                write_bytes_per_sec: 0,
+                total_read_bytes: 0,
+                total_write_bytes: 0,
+            },
+            network_io: NetworkIO {
+                received_bytes_per_sec: 0,
+                transmitted_bytes_per_sec: 0,
+                total_received_bytes: 0,
+                total_transmitted_bytes: 0,
+            },
+            gpu_usage: None,
+            battery: None,
+            temperatures: Vec::new(),
+        }
+    }
+
     #[test]
     fn test_metrics_history() {
         let mut history = MetricsHistory::new(10);
@@ -604,4 +1642,31 @@ mod tests {
 
         assert_eq!(history.get_samples().len(), 10);
     }
+
+    #[test]
+    fn test_battery_from_power_supply() {
+        let dir = tempfile::tempdir().unwrap();
+        let write = |supply: &str, name: &str, value: &str| {
+            std::fs::create_dir_all(dir.path().join(supply)).unwrap();
+            std::fs::write(dir.path().join(supply).join(name), value).unwrap();
+        };
+        write("AC", "type", "Mains\n");
+        write("BAT0", "type", "Battery\n");
+        write("BAT0", "capacity", "42\n");
+        write("BAT0", "status", "Discharging\n");
+        write("BAT0", "energy_now", "21000000\n");
+        write("BAT0", "energy_full", "45000000\n");
+        write("BAT0", "energy_full_design", "50000000\n");
+        write("BAT0", "power_now", "9000000\n");
+
+        let battery = BatteryInfo::from_power_supply(dir.path()).unwrap();
+        assert_eq!(battery.charge_percent, 42);
+        assert!(!battery.is_charging);
+        assert_eq!(battery.time_remaining_mins, Some(140));
+        assert_eq!(battery.health_percent, Some(90));
+
+        // Desktops only have mains supplies
+        std::fs::remove_dir_all(dir.path().join("BAT0")).unwrap();
+        assert!(BatteryInfo::from_power_supply(dir.path()).is_none());
+    }
 }
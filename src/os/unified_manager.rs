@@ -6,8 +6,10 @@
 use crate::error::Result;
 use crate::events::EventBus;
 use crate::metrics::Metrics;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
+use super::health_monitor::{HealthMonitorConfig, AUDIT_LOG_FILE};
 use super::{
     automation::AutomationEngine, clipboard_manager::ClipboardManager,
     desktop_environment::DesktopEnvironment, health_monitor::HealthMonitor,
@@ -44,13 +46,21 @@ pub struct UnifiedOsManager {
     // Event bus and metrics
     event_bus: Option<Arc<EventBus>>,
     metrics: Option<Arc<Metrics>>,
+
+    // Health remediation settings, kept so rebuilding the monitor preserves them
+    health_config: HealthMonitorConfig,
+    health_audit_log: Option<PathBuf>,
 }
 
 impl UnifiedOsManager {
     /// Create a new unified OS manager
     pub fn new() -> Self {
+        let process_manager = Arc::new(ProcessManager::new());
         Self {
-            process_manager: Arc::new(ProcessManager::new()),
+            process_optimizer: Arc::new(ProcessOptimizer::from_process_manager(
+                process_manager.clone(),
+            )),
+            process_manager,
             multi_monitor: Arc::new(MultiMonitorManager::new()),
             resource_monitor: Arc::new(ResourceMonitor::new()),
             window_manager: Arc::new(WindowManager::new()),
@@ -59,12 +69,13 @@ impl UnifiedOsManager {
             health_monitor: Arc::new(HealthMonitor::new()),
             power_manager: Arc::new(PowerManager::new()),
             virtual_desktop: Arc::new(VirtualDesktopManager::new()),
-            process_optimizer: Arc::new(ProcessOptimizer::new()),
             clipboard_manager: Arc::new(ClipboardManager::new(100)),
             desktop_environment: None,
             performance_optimizer: Arc::new(PerformanceOptimizer::new()),
             event_bus: None,
             metrics: None,
+            health_config: HealthMonitorConfig::default(),
+            health_audit_log: None,
         }
     }
 
@@ -76,11 +87,31 @@ impl UnifiedOsManager {
         self.process_manager = Arc::new(ProcessManager::new().with_event_bus(event_bus.clone()));
         self.multi_monitor = Arc::new(MultiMonitorManager::new().with_event_bus(event_bus.clone()));
         self.resource_monitor = Arc::new(ResourceMonitor::new().with_event_bus(event_bus.clone()));
-        self.health_monitor = Arc::new(HealthMonitor::new().with_event_bus(event_bus.clone()));
+        self.health_monitor = Arc::new(self.build_health_monitor());
+
+        self
+    }
 
+    /// Configure health remediation, auditing to `data_dir`
+    pub fn with_health_config(mut self, config: HealthMonitorConfig, data_dir: &Path) -> Self {
+        self.health_config = config;
+        self.health_audit_log = Some(data_dir.join(AUDIT_LOG_FILE));
+        self.health_monitor = Arc::new(self.build_health_monitor());
         self
     }
 
+    /// Health monitor with the current remediation settings and event bus
+    fn build_health_monitor(&self) -> HealthMonitor {
+        let mut monitor = HealthMonitor::new().with_config(self.health_config.clone());
+        if let Some(path) = &self.health_audit_log {
+            monitor = monitor.with_audit_log(path);
+        }
+        if let Some(bus) = &self.event_bus {
+            monitor = monitor.with_event_bus(bus.clone());
+        }
+        monitor
+    }
+
     /// Add metrics to all components
     pub fn with_metrics(mut self, metrics: Arc<Metrics>) -> Self {
         self.metrics = Some(metrics.clone());
@@ -174,6 +205,21 @@ mod tests {
         assert!(manager.event_bus.is_none());
     }
 
+    #[tokio::test]
+    async fn test_event_bus_keeps_health_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let manager = UnifiedOsManager::new()
+            .with_health_config(HealthMonitorConfig::default(), dir.path())
+            .with_event_bus(Arc::new(EventBus::new()));
+
+        manager
+            .health_monitor
+            .remediate(&super::super::health_monitor::HealthIssue::HighMemoryUsage)
+            .await
+            .unwrap();
+        assert!(dir.path().join(AUDIT_LOG_FILE).exists());
+    }
+
     #[tokio::test]
     async fn test_initialization() {
         let mut manager = UnifiedOsManager::new();
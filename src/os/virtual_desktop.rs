@@ -1,77 +1,398 @@
 //! Virtual Desktop Management (GOD-LEVEL)
+//!
+//! Switches between, creates and moves windows across virtual desktops
+//! through a platform backend (`wmctrl` on Linux). Desktop ids are
+//! zero-based; the numbers users speak ("desktop 2") are one-based.
 
-use crate::error::Result;
+use crate::error::{LunaError, Result};
 use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::info;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct VirtualDesktop {
     pub id: usize,
     pub name: String,
-    pub windows: Vec<u32>,
+    /// Whether this is the desktop currently shown
+    pub active: bool,
+}
+
+/// Platform access to virtual desktops
+pub trait DesktopBackend: Send + Sync {
+    /// Whether the platform exposes virtual desktops at all
+    fn is_supported(&self) -> bool {
+        true
+    }
+
+    /// All desktops, in order
+    fn desktops(&self) -> Result<Vec<VirtualDesktop>>;
+
+    /// Show a desktop
+    fn switch_to(&self, desktop_id: usize) -> Result<()>;
+
+    /// Move the focused window to a desktop
+    fn move_active_window(&self, desktop_id: usize) -> Result<()>;
+
+    /// Change how many desktops exist
+    fn set_desktop_count(&self, count: usize) -> Result<()>;
+}
+
+/// Backend driving EWMH window managers through `wmctrl`
+pub struct WmctrlBackend;
+
+impl WmctrlBackend {
+    fn run(args: &[&str]) -> Result<String> {
+        let output = std::process::Command::new("wmctrl")
+            .args(args)
+            .output()
+            .map_err(|e| LunaError::SystemOperation(format!("Failed to run wmctrl: {}", e)))?;
+
+        if !output.status.success() {
+            return Err(LunaError::SystemOperation(format!(
+                "wmctrl {} failed: {}",
+                args.join(" "),
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    }
+}
+
+impl DesktopBackend for WmctrlBackend {
+    fn desktops(&self) -> Result<Vec<VirtualDesktop>> {
+        Self::run(&["-d"]).map(|out| parse_wmctrl_desktops(&out))
+    }
+
+    fn switch_to(&self, desktop_id: usize) -> Result<()> {
+        Self::run(&["-s", &desktop_id.to_string()]).map(|_| ())
+    }
+
+    fn move_active_window(&self, desktop_id: usize) -> Result<()> {
+        Self::run(&["-r", ":ACTIVE:", "-t", &desktop_id.to_string()]).map(|_| ())
+    }
+
+    fn set_desktop_count(&self, count: usize) -> Result<()> {
+        Self::run(&["-n", &count.to_string()]).map(|_| ())
+    }
+}
+
+/// Backend for platforms without virtual desktop control
+pub struct UnsupportedBackend;
+
+impl DesktopBackend for UnsupportedBackend {
+    fn is_supported(&self) -> bool {
+        false
+    }
+
+    fn desktops(&self) -> Result<Vec<VirtualDesktop>> {
+        Err(unsupported())
+    }
+
+    fn switch_to(&self, _desktop_id: usize) -> Result<()> {
+        Err(unsupported())
+    }
+
+    fn move_active_window(&self, _desktop_id: usize) -> Result<()> {
+        Err(unsupported())
+    }
+
+    fn set_desktop_count(&self, _count: usize) -> Result<()> {
+        Err(unsupported())
+    }
+}
+
+fn unsupported() -> LunaError {
+    LunaError::SystemOperation("Virtual desktops are not supported on this platform".to_string())
+}
+
+/// Parse `wmctrl -d` output
+///
+/// Lines look like `0  * DG: 1920x1080  VP: 0,0  WA: 0,24 1920x1056  Work`,
+/// where `*` marks the current desktop and the name follows the work area.
+fn parse_wmctrl_desktops(output: &str) -> Vec<VirtualDesktop> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let tokens: Vec<&str> = line.split_whitespace().collect();
+            let id = tokens.first()?.parse().ok()?;
+            let active = tokens.get(1) == Some(&"*");
+            let name_start = match tokens.iter().position(|t| *t == "WA:") {
+                Some(i) if tokens.get(i + 1) == Some(&"N/A") => i + 2,
+                Some(i) => i + 3,
+                None => tokens.len(),
+            };
+            let name = tokens.get(name_start..).unwrap_or_default().join(" ");
+            let name = if name.is_empty() {
+                format!("Desktop {}", id + 1)
+            } else {
+                name
+            };
+            Some(VirtualDesktop { id, name, active })
+        })
+        .collect()
 }
 
 pub struct VirtualDesktopManager {
-    desktops: Vec<VirtualDesktop>,
+    backend: Arc<dyn DesktopBackend>,
     app_rules: HashMap<String, usize>,
-    current_desktop: usize,
 }
 
 impl VirtualDesktopManager {
     pub fn new() -> Self {
+        let backend: Arc<dyn DesktopBackend> = if cfg!(target_os = "linux") {
+            Arc::new(WmctrlBackend)
+        } else {
+            Arc::new(UnsupportedBackend)
+        };
+
         Self {
-            desktops: vec![VirtualDesktop {
-                id: 0,
-                name: "Desktop 1".to_string(),
-                windows: Vec::new(),
-            }],
+            backend,
             app_rules: HashMap::new(),
-            current_desktop: 0,
         }
     }
 
-    pub async fn create_desktop(&mut self, name: String) -> Result<usize> {
-        let id = self.desktops.len();
-        self.desktops.push(VirtualDesktop {
-            id,
-            name,
-            windows: Vec::new(),
-        });
+    /// Use a custom desktop backend
+    pub fn with_backend(mut self, backend: Arc<dyn DesktopBackend>) -> Self {
+        self.backend = backend;
+        self
+    }
+
+    /// Whether desktops can be controlled on this platform
+    pub fn is_supported(&self) -> bool {
+        self.backend.is_supported()
+    }
+
+    pub fn list_desktops(&self) -> Result<Vec<VirtualDesktop>> {
+        self.backend.desktops()
+    }
+
+    /// Id of the desktop currently shown
+    pub fn current_desktop(&self) -> Result<usize> {
+        Ok(self
+            .list_desktops()?
+            .iter()
+            .find(|d| d.active)
+            .map(|d| d.id)
+            .unwrap_or(0))
+    }
+
+    /// Add a desktop at the end and return its id
+    pub async fn create_desktop(&self) -> Result<usize> {
+        let id = self.list_desktops()?.len();
+        self.backend.set_desktop_count(id + 1)?;
+        info!("Created virtual desktop {}", id + 1);
         Ok(id)
     }
 
-    pub async fn switch_to(&mut self, desktop_id: usize) -> Result<()> {
-        if desktop_id < self.desktops.len() {
-            self.current_desktop = desktop_id;
-            Ok(())
-        } else {
-            Err(crate::error::LunaError::SystemOperation(
-                "Invalid desktop ID".to_string(),
-            ))
-        }
+    pub async fn switch_to(&self, desktop_id: usize) -> Result<()> {
+        self.check_id(desktop_id)?;
+        self.backend.switch_to(desktop_id)
     }
 
-    pub async fn move_window_to(&mut self, window_id: u32, desktop_id: usize) -> Result<()> {
-        if desktop_id < self.desktops.len() {
-            self.desktops[desktop_id].windows.push(window_id);
-            Ok(())
-        } else {
-            Err(crate::error::LunaError::SystemOperation(
-                "Invalid desktop ID".to_string(),
-            ))
+    /// Switch by an offset from the current desktop, wrapping around
+    pub async fn switch_relative(&self, offset: isize) -> Result<usize> {
+        let desktops = self.list_desktops()?;
+        if desktops.is_empty() {
+            return Err(invalid_desktop());
         }
+
+        let current = desktops.iter().position(|d| d.active).unwrap_or(0) as isize;
+        let target = (current + offset).rem_euclid(desktops.len() as isize) as usize;
+        self.backend.switch_to(target)?;
+        Ok(target)
+    }
+
+    /// Move the focused window to a desktop
+    pub async fn move_active_window_to(&self, desktop_id: usize) -> Result<()> {
+        self.check_id(desktop_id)?;
+        self.backend.move_active_window(desktop_id)
     }
 
     pub fn set_app_rule(&mut self, app_name: String, desktop_id: usize) {
         self.app_rules.insert(app_name, desktop_id);
     }
 
-    pub fn list_desktops(&self) -> &[VirtualDesktop] {
-        &self.desktops
+    /// Desktop an app should open on, if a rule exists
+    pub fn app_rule(&self, app_name: &str) -> Option<usize> {
+        self.app_rules.get(app_name).copied()
+    }
+
+    /// Perform a named desktop action and describe the result
+    ///
+    /// Supported actions: switch, next, previous, move, create, list.
+    /// `number` is the one-based desktop number for switch and move.
+    pub async fn perform(&self, action: &str, number: Option<usize>) -> Result<String> {
+        if !self.is_supported() {
+            return Ok("Virtual desktops aren't supported on this platform".to_string());
+        }
+
+        match action {
+            "switch" | "move" => {
+                let Some(number) = number.filter(|n| *n > 0) else {
+                    return Ok("Which desktop?".to_string());
+                };
+                if action == "switch" {
+                    self.switch_to(number - 1).await?;
+                    Ok(format!("Switched to desktop {}", number))
+                } else {
+                    self.move_active_window_to(number - 1).await?;
+                    Ok(format!("Moved the window to desktop {}", number))
+                }
+            }
+            "next" | "previous" => {
+                let offset = if action == "next" { 1 } else { -1 };
+                let id = self.switch_relative(offset).await?;
+                Ok(format!("Switched to desktop {}", id + 1))
+            }
+            "create" => {
+                let id = self.create_desktop().await?;
+                Ok(format!("Created desktop {}", id + 1))
+            }
+            "list" => {
+                let names: Vec<String> = self
+                    .list_desktops()?
+                    .into_iter()
+                    .map(|d| {
+                        if d.active {
+                            format!("{} (current)", d.name)
+                        } else {
+                            d.name
+                        }
+                    })
+                    .collect();
+                Ok(format!(
+                    "You have {} desktops: {}",
+                    names.len(),
+                    names.join(", ")
+                ))
+            }
+            _ => Ok(format!("Unknown desktop action: {}", action)),
+        }
+    }
+
+    fn check_id(&self, desktop_id: usize) -> Result<()> {
+        if desktop_id < self.list_desktops()?.len() {
+            Ok(())
+        } else {
+            Err(invalid_desktop())
+        }
     }
 }
 
+fn invalid_desktop() -> LunaError {
+    LunaError::SystemOperation("Invalid desktop ID".to_string())
+}
+
 impl Default for VirtualDesktopManager {
     fn default() -> Self {
         Self::new()
     }
 }
+
+/// In-memory backend that records calls, for tests
+#[cfg(test)]
+pub(crate) mod mock {
+    use super::*;
+    use parking_lot::Mutex;
+
+    #[derive(Default)]
+    pub struct MockDesktopBackend {
+        pub count: Mutex<usize>,
+        pub current: Mutex<usize>,
+        pub calls: Mutex<Vec<String>>,
+    }
+
+    impl MockDesktopBackend {
+        pub fn with_desktops(count: usize) -> Arc<Self> {
+            let backend = Self::default();
+            *backend.count.lock() = count;
+            Arc::new(backend)
+        }
+
+        pub fn calls(&self) -> Vec<String> {
+            self.calls.lock().clone()
+        }
+    }
+
+    impl DesktopBackend for MockDesktopBackend {
+        fn desktops(&self) -> Result<Vec<VirtualDesktop>> {
+            let current = *self.current.lock();
+            Ok((0..*self.count.lock())
+                .map(|id| VirtualDesktop {
+                    id,
+                    name: format!("Desktop {}", id + 1),
+                    active: id == current,
+                })
+                .collect())
+        }
+
+        fn switch_to(&self, desktop_id: usize) -> Result<()> {
+            *self.current.lock() = desktop_id;
+            self.calls.lock().push(format!("switch {}", desktop_id));
+            Ok(())
+        }
+
+        fn move_active_window(&self, desktop_id: usize) -> Result<()> {
+            self.calls.lock().push(format!("move {}", desktop_id));
+            Ok(())
+        }
+
+        fn set_desktop_count(&self, count: usize) -> Result<()> {
+            *self.count.lock() = count;
+            self.calls.lock().push(format!("count {}", count));
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::mock::MockDesktopBackend;
+    use super::*;
+
+    #[test]
+    fn test_parse_wmctrl_desktops() {
+        let output = "0  - DG: 3840x1080  VP: 0,0  WA: 0,27 3840x1053  Web\n\
+                      1  * DG: 3840x1080  VP: N/A  WA: N/A  Code Review\n\
+                      2  - DG: 3840x1080  VP: 0,0  WA: 0,27 3840x1053  \n";
+
+        let desktops = parse_wmctrl_desktops(output);
+        assert_eq!(desktops.len(), 3);
+        assert_eq!(desktops[0].name, "Web");
+        assert!(!desktops[0].active);
+        assert_eq!(desktops[1].name, "Code Review");
+        assert!(desktops[1].active);
+        assert_eq!(desktops[2].name, "Desktop 3");
+    }
+
+    #[tokio::test]
+    async fn test_perform_actions() {
+        let backend = MockDesktopBackend::with_desktops(3);
+        let manager = VirtualDesktopManager::new().with_backend(backend.clone());
+
+        assert_eq!(
+            manager.perform("switch", Some(2)).await.unwrap(),
+            "Switched to desktop 2"
+        );
+        manager.perform("move", Some(3)).await.unwrap();
+        manager.perform("next", None).await.unwrap();
+        manager.perform("next", None).await.unwrap();
+        manager.perform("previous", None).await.unwrap();
+        manager.perform("create", None).await.unwrap();
+
+        assert_eq!(
+            backend.calls(),
+            ["switch 1", "move 2", "switch 2", "switch 0", "switch 2", "count 4"]
+        );
+        assert!(manager.perform("switch", Some(9)).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_unsupported_platform_is_friendly() {
+        let manager = VirtualDesktopManager::new().with_backend(Arc::new(UnsupportedBackend));
+        assert_eq!(
+            manager.perform("switch", Some(2)).await.unwrap(),
+            "Virtual desktops aren't supported on this platform"
+        );
+    }
+}
@@ -4,6 +4,7 @@
 //! snap layouts, borderless fullscreen.
 
 use crate::error::{LunaError, Result};
+use crate::os::multi_monitor::WindowRect;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use tracing::{debug, info};
@@ -40,6 +41,12 @@ pub enum WindowAction {
     BorderlessFullscreen,
     Snap(SnapPosition),
     Restore,
+    /// Move a window into a rect, optionally maximizing it there
+    MoveTo {
+        selector: WindowSelector,
+        rect: WindowRect,
+        maximize: bool,
+    },
 }
 
 #[derive(Debug, Clone)]
@@ -47,6 +54,8 @@ pub enum WindowSelector {
     ByPid(u32),
     ByTitle(String),
     ByClass(String),
+    /// The focused window
+    Active,
     All,
 }
 
@@ -74,6 +83,11 @@ impl WindowManager {
             WindowAction::Minimize(selector) => self.minimize_windows(selector).await,
             WindowAction::AlwaysOnTop(enable) => self.set_always_on_top(enable).await,
             WindowAction::Snap(position) => self.snap_window(position).await,
+            WindowAction::MoveTo {
+                selector,
+                rect,
+                maximize,
+            } => self.move_window(selector, rect, maximize).await,
             _ => Ok(()),
         }
     }
@@ -122,6 +136,64 @@ impl WindowManager {
         Ok(())
     }
 
+    async fn move_window(
+        &self,
+        selector: WindowSelector,
+        rect: WindowRect,
+        maximize: bool,
+    ) -> Result<()> {
+        info!("Moving window {:?} to {:?}", selector, rect);
+
+        #[cfg(target_os = "linux")]
+        {
+            let target: Vec<String> = match selector {
+                WindowSelector::Active => vec!["-r".into(), ":ACTIVE:".into()],
+                WindowSelector::ByTitle(title) => vec!["-r".into(), title],
+                WindowSelector::ByClass(class) => vec!["-x".into(), "-r".into(), class],
+                WindowSelector::ByPid(_) | WindowSelector::All => {
+                    return Err(LunaError::InvalidParameter(
+                        "Can only move a single window by title or class".to_string(),
+                    ))
+                }
+            };
+            let geometry = format!("0,{},{},{},{}", rect.x, rect.y, rect.width, rect.height);
+
+            // A maximized window ignores new geometry until it's restored
+            let mut steps = vec![
+                vec!["-b", "remove,maximized_vert,maximized_horz"],
+                vec!["-e", geometry.as_str()],
+            ];
+            if maximize {
+                steps.push(vec!["-b", "add,maximized_vert,maximized_horz"]);
+            }
+
+            for step in steps {
+                let status = std::process::Command::new("wmctrl")
+                    .args(&target)
+                    .args(&step)
+                    .status()
+                    .map_err(|e| {
+                        LunaError::SystemOperation(format!("Failed to run wmctrl: {}", e))
+                    })?;
+                if !status.success() {
+                    return Err(LunaError::SystemOperation(format!(
+                        "wmctrl {} failed for the window",
+                        step.join(" ")
+                    )));
+                }
+            }
+            Ok(())
+        }
+
+        #[cfg(not(target_os = "linux"))]
+        {
+            let _ = (selector, maximize);
+            Err(LunaError::SystemOperation(
+                "Moving windows between monitors is not supported on this platform".to_string(),
+            ))
+        }
+    }
+
     pub fn create_group(&mut self, name: String, windows: Vec<u32>) {
         self.groups
             .insert(name.clone(), WindowGroup { name, windows });
@@ -227,6 +227,56 @@ pub fn set_volume_windows(level: u8) -> Result<()> {
     Ok(())
 }
 
+/// Get current screen brightness (0-100) via WMI
+///
+/// Only internal panels expose `WmiMonitorBrightness`; external monitors usually don't.
+#[cfg(target_os = "windows")]
+pub fn get_brightness_windows() -> Result<u8> {
+    let output = Command::new("powershell")
+        .args([
+            "-Command",
+            "(Get-CimInstance -Namespace root/WMI -ClassName WmiMonitorBrightness).CurrentBrightness",
+        ])
+        .output()
+        .map_err(|e| LunaError::SystemOperation(format!("Failed to get brightness: {}", e)))?;
+
+    if output.status.success() {
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        if let Some(Ok(level)) = stdout.lines().next().map(|l| l.trim().parse::<u8>()) {
+            return Ok(level.min(100));
+        }
+    }
+
+    Err(LunaError::SystemOperation(
+        "Brightness control unavailable on this display".to_string(),
+    ))
+}
+
+/// Set screen brightness (0-100) via WMI
+#[cfg(target_os = "windows")]
+pub fn set_brightness_windows(level: u8) -> Result<()> {
+    let level = level.min(100);
+
+    let status = Command::new("powershell")
+        .args([
+            "-Command",
+            &format!(
+                "(Get-WmiObject -Namespace root/WMI -Class WmiMonitorBrightnessMethods).WmiSetBrightness(1, {})",
+                level
+            ),
+        ])
+        .status()
+        .map_err(|e| LunaError::SystemOperation(format!("Failed to set brightness: {}", e)))?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(LunaError::SystemOperation(
+            "Brightness control unavailable on this display".to_string(),
+        ))
+    }
+}
+
 /// Get list of running processes
 #[cfg(target_os = "windows")]
 pub fn get_running_processes() -> Result<Vec<String>> {
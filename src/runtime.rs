@@ -5,11 +5,23 @@
 //! - Graceful startup and shutdown
 //! - Signal handling (SIGINT, SIGTERM)
 //! - Component dependency ordering
+//! - Periodic metrics snapshots
+//! - Running scripted commands without audio
 
+use crate::actions::{is_affirmative, AppLauncher, ConfirmationRequest, PlanOutcome, TaskExecutor};
+use crate::brain::task_planner::TaskPlan;
+use crate::brain::{ActionType, Brain, IntentType};
+use crate::config::BrainConfig;
 use crate::error::{LunaError, Result};
+use crate::events::{EventBus, LunaEvent};
+use crate::metrics::Metrics;
+use crate::tts::TtsSystem;
 use async_trait::async_trait;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use std::time::Duration;
+use tokio::sync::{mpsc, oneshot, Mutex, RwLock};
+use tokio::task::JoinHandle;
 use tracing::{error, info, warn};
 
 /// Lifecycle trait for all LUNA components
@@ -42,12 +54,93 @@ pub enum RuntimeState {
     Starting,
     /// Running normally
     Running,
+    /// Running with wake word detection suspended
+    Paused,
     /// Shutting down
     Stopping,
     /// Error state
     Error,
 }
 
+impl RuntimeState {
+    /// Lowercase name used in `StateChanged` events
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Stopped => "stopped",
+            Self::Starting => "starting",
+            Self::Running => "running",
+            Self::Paused => "paused",
+            Self::Stopping => "stopping",
+            Self::Error => "error",
+        }
+    }
+
+    /// Whether moving from this state to `next` is legal
+    ///
+    /// Staying in the same state is always allowed.
+    pub fn can_transition_to(&self, next: RuntimeState) -> bool {
+        use RuntimeState::*;
+
+        *self == next
+            || matches!(
+                (*self, next),
+                (Stopped, Starting)
+                    | (Starting, Running)
+                    | (Starting, Stopping)
+                    | (Starting, Error)
+                    | (Running, Paused)
+                    | (Running, Stopping)
+                    | (Running, Error)
+                    | (Paused, Running)
+                    | (Paused, Stopping)
+                    | (Paused, Error)
+                    | (Stopping, Stopped)
+                    | (Stopping, Error)
+                    | (Error, Stopping)
+                    | (Error, Stopped)
+            )
+    }
+
+    /// Move to `next`, rejecting illegal transitions
+    pub fn transition_to(&mut self, next: RuntimeState) -> Result<()> {
+        if !self.can_transition_to(next) {
+            return Err(LunaError::SystemOperation(format!(
+                "Illegal runtime state transition: {} -> {}",
+                self, next
+            )));
+        }
+        *self = next;
+        Ok(())
+    }
+
+    /// Move to `next` and publish the matching `StateChanged` event
+    ///
+    /// Nothing is published when the state does not change.
+    pub async fn transition_and_publish(
+        &mut self,
+        next: RuntimeState,
+        event_bus: &EventBus,
+    ) -> Result<()> {
+        let from = *self;
+        self.transition_to(next)?;
+        if from != next {
+            event_bus
+                .publish(LunaEvent::StateChanged {
+                    from: from.as_str().to_string(),
+                    to: next.as_str().to_string(),
+                })
+                .await;
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for RuntimeState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 /// LUNA runtime that manages component lifecycle
 pub struct LunaRuntime {
     /// Registered components in startup order
@@ -58,6 +151,12 @@ pub struct LunaRuntime {
 
     /// Shutdown signal
     shutdown_tx: Option<tokio::sync::broadcast::Sender<()>>,
+
+    /// Event bus drained after all components stop
+    event_bus: Option<Arc<EventBus>>,
+
+    /// Set while paused; shared with the audio system
+    paused: Arc<AtomicBool>,
 }
 
 impl LunaRuntime {
@@ -67,18 +166,35 @@ impl LunaRuntime {
             components: Vec::new(),
             state: Arc::new(RwLock::new(RuntimeState::Stopped)),
             shutdown_tx: None,
+            event_bus: None,
+            paused: Arc::new(AtomicBool::new(false)),
         }
     }
 
+    /// Drain this event bus as the last step of `stop`
+    pub fn with_event_bus(mut self, event_bus: Arc<EventBus>) -> Self {
+        self.event_bus = Some(event_bus);
+        self
+    }
+
     /// Register a component (added to the end of startup order)
     pub fn register(&mut self, component: Box<dyn Lifecycle>) {
         info!("Registering component: {}", component.name());
         self.components.push(component);
     }
 
+    /// Validate and apply a state change, publishing it on the event bus
+    async fn set_state(&self, next: RuntimeState) -> Result<()> {
+        let mut state = self.state.write().await;
+        match &self.event_bus {
+            Some(bus) => state.transition_and_publish(next, bus).await,
+            None => state.transition_to(next),
+        }
+    }
+
     /// Start all components in order
     pub async fn start(&mut self) -> Result<()> {
-        *self.state.write().await = RuntimeState::Starting;
+        self.set_state(RuntimeState::Starting).await?;
         info!(
             "🚀 Starting LUNA runtime with {} components",
             self.components.len()
@@ -98,7 +214,8 @@ impl LunaRuntime {
                 }
                 Err(e) => {
                     error!("❌ Failed to start component {}: {}", component.name(), e);
-                    *self.state.write().await = RuntimeState::Error;
+                    // Starting -> Error is always legal
+                    let _ = self.set_state(RuntimeState::Error).await;
 
                     // Attempt to stop already-started components
                     self.stop_all_components().await;
@@ -108,7 +225,7 @@ impl LunaRuntime {
             }
         }
 
-        *self.state.write().await = RuntimeState::Running;
+        self.set_state(RuntimeState::Running).await?;
         info!("✅ All components started successfully");
 
         Ok(())
@@ -116,7 +233,11 @@ impl LunaRuntime {
 
     /// Stop all components in reverse order (graceful shutdown)
     pub async fn stop(&mut self) -> Result<()> {
-        *self.state.write().await = RuntimeState::Stopping;
+        if self.state().await == RuntimeState::Stopped {
+            return Ok(());
+        }
+        self.set_state(RuntimeState::Stopping).await?;
+        self.paused.store(false, Ordering::SeqCst);
         info!("🛑 Stopping LUNA runtime");
 
         // Signal shutdown to all listeners
@@ -126,7 +247,13 @@ impl LunaRuntime {
 
         self.stop_all_components().await;
 
-        *self.state.write().await = RuntimeState::Stopped;
+        self.set_state(RuntimeState::Stopped).await?;
+
+        // Deliver the final state change before the bus stops
+        if let Some(bus) = &self.event_bus {
+            bus.shutdown().await;
+        }
+
         info!("✅ LUNA runtime stopped");
 
         Ok(())
@@ -152,6 +279,27 @@ impl LunaRuntime {
         }
     }
 
+    /// Suspend wake word detection without stopping audio capture
+    pub async fn pause(&self) -> Result<()> {
+        self.set_state(RuntimeState::Paused).await?;
+        self.paused.store(true, Ordering::SeqCst);
+        info!("⏸️  LUNA paused");
+        Ok(())
+    }
+
+    /// Resume wake word detection after `pause`
+    pub async fn resume(&self) -> Result<()> {
+        self.set_state(RuntimeState::Running).await?;
+        self.paused.store(false, Ordering::SeqCst);
+        info!("▶️  LUNA resumed");
+        Ok(())
+    }
+
+    /// Flag to pass to `AudioSystem::with_pause_flag`
+    pub fn pause_flag(&self) -> Arc<AtomicBool> {
+        Arc::clone(&self.paused)
+    }
+
     /// Get current runtime state
     pub async fn state(&self) -> RuntimeState {
         *self.state.read().await
@@ -218,7 +366,9 @@ impl LunaRuntime {
                 }
             }
 
-            *state.write().await = RuntimeState::Stopping;
+            if let Err(e) = state.write().await.transition_to(RuntimeState::Stopping) {
+                warn!("{}", e);
+            }
             if let Some(tx) = shutdown_tx {
                 let _ = tx.send(());
             }
@@ -256,6 +406,326 @@ impl Default for LunaRuntime {
     }
 }
 
+/// Component that publishes `LunaEvent::MetricsSnapshot` at a fixed interval
+pub struct MetricsReporter {
+    metrics: Arc<Metrics>,
+    event_bus: Arc<EventBus>,
+    interval: Duration,
+    shutdown_tx: Option<oneshot::Sender<()>>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl MetricsReporter {
+    /// Create a reporter publishing every `interval`
+    pub fn new(metrics: Arc<Metrics>, event_bus: Arc<EventBus>, interval: Duration) -> Self {
+        Self {
+            metrics,
+            event_bus,
+            interval,
+            shutdown_tx: None,
+            handle: None,
+        }
+    }
+
+    /// Build the snapshot event from the current metrics
+    pub fn snapshot(metrics: &Metrics) -> LunaEvent {
+        LunaEvent::MetricsSnapshot {
+            commands_processed: metrics.get_commands_processed(),
+            success_rate: metrics.get_success_rate(),
+            avg_latency_ms: metrics.get_avg_total_ms().round() as u64,
+        }
+    }
+}
+
+#[async_trait]
+impl Lifecycle for MetricsReporter {
+    fn name(&self) -> &str {
+        "metrics_reporter"
+    }
+
+    async fn start(&mut self) -> Result<()> {
+        if self.interval.is_zero() {
+            return Err(LunaError::Config(
+                "Metrics snapshot interval must be greater than zero".to_string(),
+            ));
+        }
+        if self.handle.is_some() {
+            return Ok(());
+        }
+
+        let (shutdown_tx, mut shutdown_rx) = oneshot::channel();
+        let metrics = Arc::clone(&self.metrics);
+        let event_bus = Arc::clone(&self.event_bus);
+        let interval = self.interval;
+
+        self.handle = Some(tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            // The first tick completes immediately; skip it so snapshots
+            // reflect a full interval of activity
+            ticker.tick().await;
+
+            loop {
+                tokio::select! {
+                    _ = ticker.tick() => {
+                        event_bus.publish(MetricsReporter::snapshot(&metrics)).await;
+                    }
+                    _ = &mut shutdown_rx => break,
+                }
+            }
+        }));
+        self.shutdown_tx = Some(shutdown_tx);
+
+        Ok(())
+    }
+
+    async fn stop(&mut self) -> Result<()> {
+        if let Some(tx) = self.shutdown_tx.take() {
+            let _ = tx.send(());
+        }
+        if let Some(handle) = self.handle.take() {
+            if let Err(e) = handle.await {
+                warn!("Metrics reporter task ended abnormally: {}", e);
+            }
+        }
+        Ok(())
+    }
+
+    fn is_running(&self) -> bool {
+        self.handle.as_ref().is_some_and(|h| !h.is_finished())
+    }
+}
+
+/// Result of one command run from a script
+#[derive(Debug, Clone)]
+pub struct ScriptedCommand {
+    /// Script line the command came from
+    pub text: String,
+    /// Lines consumed as answers to clarification questions
+    pub clarifications: Vec<String>,
+    /// Plan that was executed, if the command got that far
+    pub plan: Option<TaskPlan>,
+    /// What LUNA would have said
+    pub response: String,
+    /// Whether the plan ran and every step succeeded
+    pub success: bool,
+}
+
+/// Feeds text commands through the brain and executor without a microphone
+///
+/// Follows the voice loop: low-confidence commands, ambiguous app categories
+/// and sensitive actions ask a question, which is answered by the next line
+/// instead of the microphone. Responses are spoken if TTS is attached.
+pub struct ScriptRunner {
+    brain: Arc<Brain>,
+    executor: Arc<TaskExecutor>,
+    confidence_threshold: f32,
+    category_resolver: Option<AppLauncher>,
+    dry_run: bool,
+    tts: Option<Arc<TtsSystem>>,
+    confirmations: Option<Mutex<mpsc::Receiver<ConfirmationRequest>>>,
+}
+
+impl ScriptRunner {
+    /// Create a runner using the default confidence threshold
+    pub fn new(brain: Arc<Brain>, executor: Arc<TaskExecutor>) -> Self {
+        Self {
+            brain,
+            executor,
+            confidence_threshold: BrainConfig::default().confidence_threshold,
+            category_resolver: None,
+            dry_run: false,
+            tts: None,
+            confirmations: None,
+        }
+    }
+
+    /// Ask for clarification below this confidence
+    pub fn with_confidence_threshold(mut self, threshold: f32) -> Self {
+        self.confidence_threshold = threshold;
+        self
+    }
+
+    /// Ask "which browser?" for category launches with no clear favourite
+    pub fn with_category_resolver(mut self, launcher: AppLauncher) -> Self {
+        self.category_resolver = Some(launcher);
+        self
+    }
+
+    /// Preview plans instead of executing them
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Speak each response
+    pub fn with_tts(mut self, tts: Arc<TtsSystem>) -> Self {
+        self.tts = Some(tts);
+        self
+    }
+
+    /// Answer the executor's confirmation questions from the script
+    ///
+    /// Pair with `TaskExecutor::with_confirmation_channel`. A sensitive
+    /// action only runs if the line after its command says yes; without
+    /// this the executor refuses it.
+    pub fn with_confirmations(mut self, requests: mpsc::Receiver<ConfirmationRequest>) -> Self {
+        self.confirmations = Some(Mutex::new(requests));
+        self
+    }
+
+    /// Run each line as a command
+    ///
+    /// Blank lines and lines starting with `#` are skipped. A line consumed
+    /// as a clarification is not run on its own.
+    pub async fn run_commands<S: AsRef<str>>(&self, lines: &[S]) -> Vec<ScriptedCommand> {
+        let mut lines = lines
+            .iter()
+            .map(|line| line.as_ref().trim())
+            .filter(|line| !line.is_empty() && !line.starts_with('#'));
+
+        let mut results = Vec::new();
+        while let Some(text) = lines.next() {
+            results.push(self.run_command(text, &mut lines).await);
+        }
+        results
+    }
+
+    async fn run_command<'a>(
+        &self,
+        text: &str,
+        replies: &mut impl Iterator<Item = &'a str>,
+    ) -> ScriptedCommand {
+        let result = self.process_command(text, replies).await;
+        self.speak(&result.response).await;
+        result
+    }
+
+    async fn process_command<'a>(
+        &self,
+        text: &str,
+        replies: &mut impl Iterator<Item = &'a str>,
+    ) -> ScriptedCommand {
+        let mut result = ScriptedCommand {
+            text: text.to_string(),
+            clarifications: Vec::new(),
+            plan: None,
+            response: String::new(),
+            success: false,
+        };
+
+        let mut plan = match self.brain.process_async(text).await {
+            Ok(plan) => plan,
+            Err(e) => {
+                warn!("Command not understood: {}", e);
+                result.response = "I didn't understand that. Could you please rephrase?".into();
+                return result;
+            }
+        };
+
+        if plan.classification.intent == IntentType::Cancel {
+            result.response = "Okay, cancelled".into();
+            return result;
+        }
+
+        if plan.classification.confidence < self.confidence_threshold {
+            let Some(reply) = replies.next() else {
+                result.response = "Okay, never mind".into();
+                return result;
+            };
+            result.clarifications.push(reply.to_string());
+
+            plan = match self.brain.process_async(reply).await {
+                Ok(plan) => plan,
+                Err(e) => {
+                    warn!("Clarification also unclear: {}", e);
+                    result.response = "Sorry, I still don't understand".into();
+                    return result;
+                }
+            };
+        }
+
+        let category_choice = self.category_resolver.as_ref().and_then(|resolver| {
+            let index = plan
+                .steps
+                .iter()
+                .position(|step| step.action == ActionType::LaunchApp)?;
+            let app_name = plan.steps[index].params.get("app_name")?;
+            Some((index, resolver.category_choice(app_name)?))
+        });
+        if let Some((index, choice)) = category_choice {
+            let app = replies.next().and_then(|reply| {
+                result.clarifications.push(reply.to_string());
+                choice.pick(reply)
+            });
+            let Some(app) = app else {
+                result.response = "Okay, never mind".into();
+                return result;
+            };
+            plan.steps[index]
+                .params
+                .insert("app_name".to_string(), app.name.clone());
+        }
+
+        let outcome = if self.dry_run {
+            self.executor.preview_plan_detailed(plan.clone()).await
+        } else {
+            self.execute(plan.clone(), &mut result, replies).await
+        };
+        match outcome {
+            Ok(outcome) => {
+                result.success = outcome.success();
+                result.response = outcome
+                    .last_message()
+                    .map(|msg| msg.to_string())
+                    .unwrap_or_else(|| outcome.summary());
+            }
+            Err(e) => {
+                error!("Action execution failed: {}", e);
+                result.response = "Sorry, I couldn't do that".into();
+            }
+        }
+        result.plan = Some(plan);
+        result
+    }
+
+    /// Execute a plan, answering confirmation questions from the next lines
+    async fn execute<'a>(
+        &self,
+        plan: TaskPlan,
+        result: &mut ScriptedCommand,
+        replies: &mut impl Iterator<Item = &'a str>,
+    ) -> Result<PlanOutcome> {
+        let Some(confirmations) = &self.confirmations else {
+            return self.executor.execute_plan_detailed(plan).await;
+        };
+        let mut confirmations = confirmations.lock().await;
+        let execution = self.executor.execute_plan_detailed(plan);
+        tokio::pin!(execution);
+
+        loop {
+            tokio::select! {
+                outcome = &mut execution => return outcome,
+                Some((question, reply)) = confirmations.recv() => {
+                    self.speak(&question).await;
+                    let confirmed = replies.next().is_some_and(|answer| {
+                        result.clarifications.push(answer.to_string());
+                        is_affirmative(answer)
+                    });
+                    let _ = reply.send(confirmed);
+                }
+            }
+        }
+    }
+
+    async fn speak(&self, text: &str) {
+        if let Some(ref tts) = self.tts {
+            if let Err(e) = tts.speak(text).await {
+                warn!("Failed to speak \"{}\": {}", text, e);
+            }
+        }
+    }
+}
+
 /// Helper to create a no-op component for testing
 #[cfg(test)]
 pub struct TestComponent {
@@ -298,6 +768,7 @@ impl Lifecycle for TestComponent {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::events::EventKind;
 
     #[tokio::test]
     async fn test_runtime_creation() {
@@ -326,6 +797,127 @@ mod tests {
         assert_eq!(runtime.state().await, RuntimeState::Stopped);
     }
 
+    #[test]
+    fn test_valid_transitions() {
+        let mut state = RuntimeState::Stopped;
+        for next in [
+            RuntimeState::Starting,
+            RuntimeState::Running,
+            RuntimeState::Stopping,
+            RuntimeState::Stopped,
+        ] {
+            state.transition_to(next).unwrap();
+            assert_eq!(state, next);
+        }
+
+        let mut state = RuntimeState::Starting;
+        state.transition_to(RuntimeState::Error).unwrap();
+        state.transition_to(RuntimeState::Stopped).unwrap();
+
+        // Staying put is allowed
+        state.transition_to(RuntimeState::Stopped).unwrap();
+    }
+
+    #[test]
+    fn test_invalid_transitions() {
+        for (from, to) in [
+            (RuntimeState::Stopped, RuntimeState::Running),
+            (RuntimeState::Stopped, RuntimeState::Stopping),
+            (RuntimeState::Running, RuntimeState::Starting),
+            (RuntimeState::Running, RuntimeState::Stopped),
+            (RuntimeState::Stopping, RuntimeState::Running),
+            (RuntimeState::Error, RuntimeState::Running),
+        ] {
+            let mut state = from;
+            assert!(state.transition_to(to).is_err(), "{} -> {}", from, to);
+            assert_eq!(state, from);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_transition_publishes_state_names() {
+        let bus = EventBus::new();
+        let handle = bus.start_processing().await;
+
+        let changes = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let changes_clone = changes.clone();
+        bus.subscribe_kinds(&[EventKind::StateChanged], move |envelope| {
+            if let LunaEvent::StateChanged { from, to } = &envelope.event {
+                changes_clone
+                    .lock()
+                    .unwrap()
+                    .push((from.clone(), to.clone()));
+            }
+        })
+        .await;
+
+        let mut state = RuntimeState::Stopped;
+        state
+            .transition_and_publish(RuntimeState::Starting, &bus)
+            .await
+            .unwrap();
+        state
+            .transition_and_publish(RuntimeState::Running, &bus)
+            .await
+            .unwrap();
+        // Rejected transitions publish nothing
+        assert!(state
+            .transition_and_publish(RuntimeState::Starting, &bus)
+            .await
+            .is_err());
+
+        bus.shutdown().await;
+        handle.await.unwrap();
+
+        assert_eq!(
+            *changes.lock().unwrap(),
+            vec![
+                ("stopped".to_string(), "starting".to_string()),
+                ("starting".to_string(), "running".to_string()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_pause_resume() {
+        let bus = Arc::new(EventBus::new());
+        let handle = bus.start_processing().await;
+
+        let changes = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let changes_clone = changes.clone();
+        bus.subscribe_kinds(&[EventKind::StateChanged], move |envelope| {
+            if let LunaEvent::StateChanged { from, to } = &envelope.event {
+                changes_clone
+                    .lock()
+                    .unwrap()
+                    .push(format!("{}->{}", from, to));
+            }
+        })
+        .await;
+
+        let mut runtime = LunaRuntime::new().with_event_bus(bus.clone());
+        let paused = runtime.pause_flag();
+
+        // Can't pause before running
+        assert!(runtime.pause().await.is_err());
+
+        runtime.start().await.unwrap();
+        runtime.pause().await.unwrap();
+        assert_eq!(runtime.state().await, RuntimeState::Paused);
+        assert!(paused.load(Ordering::SeqCst));
+
+        runtime.resume().await.unwrap();
+        assert_eq!(runtime.state().await, RuntimeState::Running);
+        assert!(!paused.load(Ordering::SeqCst));
+
+        runtime.stop().await.unwrap();
+        handle.await.unwrap();
+
+        let changes = changes.lock().unwrap();
+        assert!(changes.contains(&"running->paused".to_string()));
+        assert!(changes.contains(&"paused->running".to_string()));
+    }
+
     #[tokio::test]
     async fn test_health_check() {
         let mut runtime = LunaRuntime::new();
@@ -335,4 +927,88 @@ mod tests {
         let healthy = runtime.health_check().await.unwrap();
         assert!(healthy);
     }
+
+    #[tokio::test]
+    async fn test_stop_drains_event_bus() {
+        let bus = Arc::new(EventBus::new());
+        let bus_handle = bus.start_processing().await;
+
+        let states = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let states_clone = states.clone();
+        bus.subscribe_kinds(&[EventKind::StateChanged], move |envelope| {
+            if let LunaEvent::StateChanged { to, .. } = &envelope.event {
+                states_clone.lock().unwrap().push(to.clone());
+            }
+        })
+        .await;
+
+        let mut runtime = LunaRuntime::new().with_event_bus(bus.clone());
+        runtime.register(Box::new(TestComponent::new("test")));
+        runtime.start().await.unwrap();
+        runtime.stop().await.unwrap();
+
+        assert_eq!(
+            *states.lock().unwrap(),
+            vec!["starting", "running", "stopping", "stopped"]
+        );
+        assert!(bus.is_shut_down());
+        bus_handle.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_metrics_reporter_publishes_snapshots() {
+        let bus = Arc::new(EventBus::new());
+        let bus_handle = bus.start_processing().await;
+
+        let snapshots = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let snapshots_clone = snapshots.clone();
+        bus.subscribe_kinds(&[EventKind::MetricsSnapshot], move |envelope| {
+            snapshots_clone.lock().unwrap().push(envelope.event.clone());
+        })
+        .await;
+
+        let metrics = Arc::new(Metrics::new());
+        for succeeded in [true, true, false] {
+            metrics.record_command_processed();
+            if succeeded {
+                metrics.record_command_success();
+            } else {
+                metrics.record_command_failure();
+            }
+        }
+
+        let mut runtime = LunaRuntime::new();
+        runtime.register(Box::new(MetricsReporter::new(
+            metrics,
+            bus.clone(),
+            Duration::from_millis(20),
+        )));
+        runtime.start().await.unwrap();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        runtime.stop().await.unwrap();
+        assert!(!runtime.components[0].is_running());
+
+        // Let the bus drain anything published before shutdown
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        let received = snapshots.lock().unwrap().len();
+        assert!(received >= 1);
+
+        match &snapshots.lock().unwrap()[0] {
+            LunaEvent::MetricsSnapshot {
+                commands_processed,
+                success_rate,
+                ..
+            } => {
+                assert_eq!(*commands_processed, 3);
+                assert!((success_rate - 200.0 / 3.0).abs() < 1e-6);
+            }
+            other => panic!("unexpected event {:?}", other),
+        }
+
+        // No further snapshots after stop
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        assert_eq!(snapshots.lock().unwrap().len(), received);
+
+        bus_handle.abort();
+    }
 }
@@ -0,0 +1,162 @@
+//! JSON-lines recording and replay of the event stream
+//!
+//! Each line of a recording is one serialized `EventEnvelope`, so sessions
+//! can be captured live and replayed later onto a fresh bus.
+
+use crate::error::Result;
+use crate::events::{EventBus, EventEnvelope, EventKind};
+use parking_lot::Mutex;
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{error, warn};
+
+/// Subscriber that appends every event to a JSON-lines file
+#[derive(Clone)]
+pub struct FileEventSubscriber {
+    writer: Arc<Mutex<BufWriter<File>>>,
+}
+
+impl FileEventSubscriber {
+    /// Create (or truncate) the recording file
+    pub fn create(path: impl AsRef<Path>) -> Result<Self> {
+        let file = File::create(path)?;
+        Ok(Self {
+            writer: Arc::new(Mutex::new(BufWriter::new(file))),
+        })
+    }
+
+    /// Write one envelope as a line
+    ///
+    /// Lines are flushed immediately so a recording survives a crash.
+    pub fn record(&self, envelope: &EventEnvelope) -> Result<()> {
+        let line = serde_json::to_string(envelope)?;
+        let mut writer = self.writer.lock();
+        writeln!(writer, "{}", line)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Subscribe to the given event kinds (empty for all) and record them
+    pub async fn attach(&self, event_bus: &EventBus, kinds: &[EventKind]) -> usize {
+        let recorder = self.clone();
+        event_bus
+            .subscribe_kinds(kinds, move |envelope: &EventEnvelope| {
+                if let Err(e) = recorder.record(envelope) {
+                    error!("Failed to record event {}: {}", envelope.id, e);
+                }
+            })
+            .await
+    }
+}
+
+/// Read a JSON-lines recording
+///
+/// Blank lines are ignored; malformed lines are skipped with a warning.
+pub fn read_recording(path: impl AsRef<Path>) -> Result<Vec<EventEnvelope>> {
+    let reader = BufReader::new(File::open(path)?);
+    let mut envelopes = Vec::new();
+
+    for (line_no, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str(&line) {
+            Ok(envelope) => envelopes.push(envelope),
+            Err(e) => warn!("Skipping malformed event on line {}: {}", line_no + 1, e),
+        }
+    }
+
+    Ok(envelopes)
+}
+
+/// Re-publish recorded envelopes, preserving their relative timing
+///
+/// `speed` scales the gaps between events (2.0 replays twice as fast);
+/// a non-positive speed publishes everything without waiting.
+pub async fn replay(event_bus: &EventBus, envelopes: Vec<EventEnvelope>, speed: f64) -> usize {
+    let mut previous: Option<u64> = None;
+    let mut published = 0;
+
+    for envelope in envelopes {
+        if let (Some(prev), true) = (previous, speed > 0.0) {
+            let gap_micros = envelope.timestamp.saturating_sub(prev) as f64 / speed;
+            if gap_micros > 0.0 {
+                tokio::time::sleep(Duration::from_micros(gap_micros as u64)).await;
+            }
+        }
+        previous = Some(envelope.timestamp);
+
+        event_bus.publish_envelope(envelope).await;
+        published += 1;
+    }
+
+    published
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::LunaEvent;
+    use std::time::Instant;
+
+    #[tokio::test]
+    async fn test_record_and_replay() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.jsonl");
+
+        // Record a short session
+        let bus = EventBus::new();
+        let handle = bus.start_processing().await;
+        let recorder = FileEventSubscriber::create(&path).unwrap();
+        recorder.attach(&bus, &[]).await;
+
+        bus.publish(LunaEvent::CommandTranscribed {
+            text: "open chrome".to_string(),
+            confidence: 0.9,
+        })
+        .await;
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        bus.publish(LunaEvent::StateChanged {
+            from: "idle".to_string(),
+            to: "running".to_string(),
+        })
+        .await;
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        handle.abort();
+
+        let recorded = read_recording(&path).unwrap();
+        assert_eq!(recorded.len(), 2);
+        assert_eq!(recorded[0].event_type(), "command_transcribed");
+        assert!(recorded[1].timestamp >= recorded[0].timestamp + 30_000);
+
+        // Replay onto a fresh bus
+        let replay_bus = EventBus::new();
+        let replay_handle = replay_bus.start_processing().await;
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let received_clone = received.clone();
+        replay_bus
+            .subscribe(vec![], move |envelope| {
+                received_clone.lock().push(envelope.clone());
+            })
+            .await;
+
+        let started = Instant::now();
+        let ids: Vec<_> = recorded.iter().map(|e| e.id).collect();
+        assert_eq!(replay(&replay_bus, recorded, 1.0).await, 2);
+        assert!(started.elapsed() >= Duration::from_millis(30));
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let received = received.lock();
+        assert_eq!(received.iter().map(|e| e.id).collect::<Vec<_>>(), ids);
+        match &received[0].event {
+            LunaEvent::CommandTranscribed { text, .. } => assert_eq!(text, "open chrome"),
+            other => panic!("unexpected event {:?}", other),
+        }
+
+        replay_handle.abort();
+    }
+}
@@ -1,6 +1,11 @@
 //! Event subscribers for LUNA
 //!
-//! Provides built-in event subscribers for common tasks like logging and metrics.
+//! Provides built-in event subscribers for common tasks like logging, metrics,
+//! and recording sessions to disk.
+
+pub mod file;
+
+pub use file::FileEventSubscriber;
 
 use crate::events::{EventBus, EventEnvelope, LunaEvent};
 use crate::metrics::Metrics;
@@ -71,8 +76,8 @@ pub async fn setup_logging_subscriber(event_bus: &EventBus) -> usize {
                     timestamp
                 );
             }
-            LunaEvent::ConfigReloaded { .. } => {
-                info!("🔄 Configuration reloaded");
+            LunaEvent::ConfigReloaded { changed_fields, .. } => {
+                info!("🔄 Configuration reloaded: {:?}", changed_fields);
             }
             LunaEvent::MetricsSnapshot { .. } => {
                 debug!("📊 Metrics snapshot");
@@ -271,7 +276,6 @@ pub async fn setup_debug_subscriber(event_bus: &EventBus) -> usize {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::collections::HashMap;
     use tokio::time::{sleep, Duration};
 
     #[tokio::test]
@@ -22,6 +22,29 @@ pub enum Gender {
     Neutral,
 }
 
+/// Best voice for `language` ("es" or "es-MX")
+///
+/// Prefers a voice for the exact locale, then any voice for the language.
+pub fn voice_for_language<'a>(voices: &'a [VoiceInfo], language: &str) -> Option<&'a VoiceInfo> {
+    let wanted = normalize_locale(language);
+    let base = |locale: &str| locale.split('-').next().unwrap_or_default().to_string();
+    let locale_of = |voice: &VoiceInfo| voice.locale.as_deref().map(normalize_locale);
+
+    voices
+        .iter()
+        .find(|voice| locale_of(voice).as_deref() == Some(wanted.as_str()))
+        .or_else(|| {
+            voices
+                .iter()
+                .find(|voice| locale_of(voice).is_some_and(|l| base(&l) == base(&wanted)))
+        })
+}
+
+/// "es_MX" and "es-mx" compare equal
+fn normalize_locale(locale: &str) -> String {
+    locale.trim().replace('_', "-").to_lowercase()
+}
+
 /// TTS engine trait - abstraction over different TTS backends
 #[async_trait]
 pub trait TtsEngine: Send + Sync {
@@ -54,6 +77,13 @@ pub trait TtsEngine: Send + Sync {
 
     /// Get engine name
     fn name(&self) -> &str;
+
+    /// Whether `speak_ssml` honours markup natively
+    ///
+    /// Engines returning false are driven through `Ssml::parse` instead.
+    fn supports_ssml(&self) -> bool {
+        false
+    }
 }
 
 /// OS TTS Engine using the tts crate
@@ -153,14 +183,15 @@ impl TtsEngine for OsTtsEngine {
             .map(|v| VoiceInfo {
                 id: v.id().to_string(),
                 name: v.name().to_string(),
-                locale: None,
+                locale: Some(v.language().as_str().to_string()),
                 gender: None,
             })
             .collect())
     }
 
     fn is_speaking(&self) -> bool {
-        self.is_speaking
+        // Playback is asynchronous, ask the backend when it can tell us
+        self.tts.is_speaking().unwrap_or(self.is_speaking)
     }
 
     fn name(&self) -> &str {
@@ -0,0 +1,235 @@
+//! Pronunciation lexicon
+//!
+//! User-editable word overrides applied to text before synthesis. Entries map
+//! a word or phrase to a phonetic spelling, or to an SSML fragment such as
+//! `<phoneme alphabet="ipa" ph="ˈtiːmʌks">tmux</phoneme>`:
+//!
+//! ```yaml
+//! tmux: tee mux
+//! httpd: h t t p d
+//! nginx: '<phoneme alphabet="ipa" ph="ˈɛndʒɪnˈɛks">engine x</phoneme>'
+//! ```
+
+use crate::error::{LunaError, Result};
+use crate::tts::ssml::strip_ssml_tags;
+use regex::Regex;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+/// Replacement for a lexicon entry
+#[derive(Debug, Clone)]
+struct Pronunciation {
+    /// Text spoken by engines without SSML
+    plain: String,
+    /// Markup for engines with native SSML, if the entry is SSML
+    ssml: Option<String>,
+}
+
+/// Compiled pronunciation overrides
+#[derive(Debug, Clone, Default)]
+pub struct Lexicon {
+    pattern: Option<Regex>,
+    entries: HashMap<String, Pronunciation>,
+}
+
+impl Lexicon {
+    /// Load a lexicon from a YAML map of word to pronunciation
+    pub fn load_from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let content = std::fs::read_to_string(path.as_ref())
+            .map_err(|e| LunaError::Config(format!("Failed to read lexicon file: {}", e)))?;
+        let entries: BTreeMap<String, String> = serde_yaml::from_str(&content)
+            .map_err(|e| LunaError::Config(format!("Failed to parse lexicon YAML: {}", e)))?;
+
+        Self::from_entries(entries)
+    }
+
+    /// Compile word-boundary-aware replacements
+    ///
+    /// Matching is case-insensitive and longer entries win over shorter ones
+    /// starting at the same position.
+    pub fn from_entries<I, K, V>(entries: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        let mut compiled = HashMap::new();
+        for (word, replacement) in entries {
+            let word = word.as_ref().trim().to_lowercase();
+            let replacement = replacement.as_ref().trim();
+            if word.is_empty() {
+                continue;
+            }
+
+            let pronunciation = if replacement.starts_with('<') {
+                Pronunciation {
+                    plain: strip_ssml_tags(replacement),
+                    ssml: Some(replacement.to_string()),
+                }
+            } else {
+                Pronunciation {
+                    plain: replacement.to_string(),
+                    ssml: None,
+                }
+            };
+            compiled.insert(word, pronunciation);
+        }
+
+        if compiled.is_empty() {
+            return Ok(Self::default());
+        }
+
+        let mut words: Vec<&String> = compiled.keys().collect();
+        words.sort_by(|a, b| b.chars().count().cmp(&a.chars().count()).then(a.cmp(b)));
+
+        let alternatives: Vec<String> = words.iter().map(|w| bounded(w)).collect();
+        let pattern = Regex::new(&format!("(?i){}", alternatives.join("|")))
+            .map_err(|e| LunaError::Config(format!("Invalid lexicon entry: {}", e)))?;
+
+        Ok(Self {
+            pattern: Some(pattern),
+            entries: compiled,
+        })
+    }
+
+    /// Number of entries
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Check if the lexicon has no entries
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Rewrite text for engines that speak plain text
+    pub fn apply(&self, text: &str) -> String {
+        self.rewrite(text, |p| p.plain.clone(), str::to_string)
+    }
+
+    /// Whether any matched entry carries SSML markup
+    pub fn has_ssml_match(&self, text: &str) -> bool {
+        let Some(pattern) = &self.pattern else {
+            return false;
+        };
+
+        pattern.find_iter(text).any(|m| {
+            self.entries
+                .get(&m.as_str().to_lowercase())
+                .is_some_and(|p| p.ssml.is_some())
+        })
+    }
+
+    /// Rewrite text as an SSML body for engines with native SSML support
+    ///
+    /// Unmatched text is escaped so it can be wrapped in `<speak>`.
+    pub fn apply_ssml(&self, text: &str) -> String {
+        self.rewrite(
+            text,
+            |p| p.ssml.clone().unwrap_or_else(|| escape(&p.plain)),
+            escape,
+        )
+    }
+
+    fn rewrite(
+        &self,
+        text: &str,
+        replace: impl Fn(&Pronunciation) -> String,
+        gap: impl Fn(&str) -> String,
+    ) -> String {
+        let Some(pattern) = &self.pattern else {
+            return gap(text);
+        };
+
+        let mut out = String::with_capacity(text.len());
+        let mut cursor = 0;
+        for m in pattern.find_iter(text) {
+            out.push_str(&gap(&text[cursor..m.start()]));
+            match self.entries.get(&m.as_str().to_lowercase()) {
+                Some(pronunciation) => out.push_str(&replace(pronunciation)),
+                None => out.push_str(&gap(m.as_str())),
+            }
+            cursor = m.end();
+        }
+        out.push_str(&gap(&text[cursor..]));
+        out
+    }
+}
+
+/// Escape a word and anchor it at word boundaries where it has word characters
+fn bounded(word: &str) -> String {
+    let is_word = |c: char| c.is_alphanumeric() || c == '_';
+    let start = if word.starts_with(is_word) { r"\b" } else { "" };
+    let end = if word.ends_with(is_word) { r"\b" } else { "" };
+    format!("{}{}{}", start, regex::escape(word), end)
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_load_and_apply() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("lexicon.yaml");
+        std::fs::write(&path, "tmux: tee mux\nhttpd: h t t p d\n").unwrap();
+
+        let lexicon = Lexicon::load_from_file(&path).unwrap();
+        assert_eq!(lexicon.len(), 2);
+        assert_eq!(
+            lexicon.apply("Restart httpd in TMUX"),
+            "Restart h t t p d in tee mux"
+        );
+    }
+
+    #[test]
+    fn test_word_boundaries() {
+        let lexicon = Lexicon::from_entries([("tmux", "tee mux")]).unwrap();
+        assert_eq!(lexicon.apply("open tmuxinator"), "open tmuxinator");
+        assert_eq!(lexicon.apply("tmux, please"), "tee mux, please");
+    }
+
+    #[test]
+    fn test_longest_match_first() {
+        let lexicon =
+            Lexicon::from_entries([("code", "kode"), ("vs code", "visual studio code")]).unwrap();
+        assert_eq!(lexicon.apply("open VS Code"), "open visual studio code");
+        assert_eq!(lexicon.apply("write code"), "write kode");
+    }
+
+    #[test]
+    fn test_non_word_entries() {
+        let lexicon = Lexicon::from_entries([("c++", "c plus plus")]).unwrap();
+        assert_eq!(lexicon.apply("learn C++ today"), "learn c plus plus today");
+    }
+
+    #[test]
+    fn test_ssml_entries() {
+        let lexicon = Lexicon::from_entries([(
+            "nginx",
+            r#"<phoneme alphabet="ipa" ph="ˈɛndʒɪnˈɛks">engine x</phoneme>"#,
+        )])
+        .unwrap();
+
+        assert!(lexicon.has_ssml_match("restart nginx"));
+        assert_eq!(lexicon.apply("restart nginx"), "restart engine x");
+        assert_eq!(
+            lexicon.apply_ssml("a < b, nginx"),
+            r#"a &lt; b, <phoneme alphabet="ipa" ph="ˈɛndʒɪnˈɛks">engine x</phoneme>"#
+        );
+    }
+
+    #[test]
+    fn test_empty_lexicon_is_identity() {
+        let lexicon = Lexicon::default();
+        assert!(lexicon.is_empty());
+        assert_eq!(lexicon.apply("unchanged"), "unchanged");
+    }
+}
@@ -3,22 +3,34 @@
 //! Enhanced TTS with queuing, prioritization, SSML, and profiles.
 
 pub mod engine;
+pub mod lexicon;
+#[cfg(feature = "piper")]
+pub mod piper;
 pub mod queue;
+pub mod registry;
 pub mod ssml;
 pub mod synthesizer;
 pub mod types;
 
 use crate::error::Result;
 use crate::events::{EventBus, LunaEvent};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::{debug, error, info, warn};
 
 // Re-export main types
 pub use engine::{OsTtsEngine, TtsEngine, VoiceInfo};
+pub use lexicon::Lexicon;
+#[cfg(feature = "piper")]
+pub use piper::PiperTtsEngine;
 pub use queue::{TtsMessage, TtsQueue};
+pub use registry::TtsRegistry;
 pub use synthesizer::{Synthesizer, TextToSpeech};
-pub use types::{MessageKind, Priority, TtsConfig, TtsHandle, TtsPolicy, VoiceProfile};
+pub use types::{
+    MessageKind, PreemptionPolicy, Priority, TtsConfig, TtsEngineKind, TtsHandle, TtsPolicy,
+    VoiceProfile,
+};
 
 /// TTS system coordinator with god-level enhancements
 pub struct TtsSystem {
@@ -29,6 +41,8 @@ pub struct TtsSystem {
     enabled: Arc<RwLock<bool>>,
     worker_handle: Option<tokio::task::JoinHandle<()>>,
     stats: Arc<RwLock<TtsStats>>,
+    in_flight: Arc<AtomicBool>,
+    registry: Arc<TtsRegistry>,
 }
 
 /// TTS statistics
@@ -51,11 +65,20 @@ impl TtsSystem {
     pub fn with_config(config: TtsConfig) -> Result<Self> {
         info!("Initializing TTS system with engine: {}", config.engine);
 
-        let synthesizer = TextToSpeech::new()?;
+        let synthesizer = TextToSpeech::with_config(&config)?;
 
         info!("✅ TTS system initialized");
 
-        Ok(Self {
+        Ok(Self::from_synthesizer(synthesizer, config))
+    }
+
+    /// Create TTS system around a custom engine
+    pub fn with_engine(engine: Box<dyn TtsEngine>, config: TtsConfig) -> Self {
+        Self::from_synthesizer(TextToSpeech::with_engine(engine), config)
+    }
+
+    fn from_synthesizer(synthesizer: TextToSpeech, config: TtsConfig) -> Self {
+        Self {
             synthesizer: Arc::new(RwLock::new(synthesizer)),
             queue: Arc::new(TtsQueue::new()),
             config: Arc::new(RwLock::new(config)),
@@ -63,7 +86,34 @@ impl TtsSystem {
             enabled: Arc::new(RwLock::new(true)),
             worker_handle: None,
             stats: Arc::new(RwLock::new(TtsStats::default())),
-        })
+            in_flight: Arc::new(AtomicBool::new(false)),
+            registry: Arc::new(TtsRegistry::with_builtin()),
+        }
+    }
+
+    /// Use `registry` to look up engines for `set_engine`
+    pub fn with_registry(mut self, registry: Arc<TtsRegistry>) -> Self {
+        self.registry = registry;
+        self
+    }
+
+    /// Rebuild the active engine from the registry while running
+    ///
+    /// The current utterance is stopped; queued messages are spoken by the
+    /// new engine.
+    pub async fn set_engine(&self, name: &str) -> Result<()> {
+        let mut config = self.config.write().await;
+        let engine = self.registry.create(name, &config)?;
+        self.synthesizer.write().await.set_engine(engine)?;
+        config.engine = name.to_string();
+
+        info!("TTS engine switched to {}", name);
+        Ok(())
+    }
+
+    /// Name of the active engine
+    pub async fn engine_name(&self) -> String {
+        self.synthesizer.read().await.engine_name().to_string()
     }
 
     /// Set event bus for TTS events
@@ -82,9 +132,19 @@ impl TtsSystem {
         let enabled = Arc::clone(&self.enabled);
         let event_bus = self.event_bus.clone();
         let stats = Arc::clone(&self.stats);
+        let in_flight = Arc::clone(&self.in_flight);
 
         let handle = tokio::spawn(async move {
-            Self::worker_loop(synthesizer, queue, config, enabled, event_bus, stats).await;
+            Self::worker_loop(
+                synthesizer,
+                queue,
+                config,
+                enabled,
+                event_bus,
+                stats,
+                in_flight,
+            )
+            .await;
         });
 
         self.worker_handle = Some(handle);
@@ -208,6 +268,39 @@ impl TtsSystem {
         synth.voice_info()
     }
 
+    /// Speak in the best available voice for `language` ("es", "es-MX")
+    ///
+    /// Returns the chosen voice. When the engine has no voice for the
+    /// language, the current voice is kept and `None` is returned.
+    pub async fn set_language(&self, language: &str) -> Result<Option<VoiceInfo>> {
+        let mut synth = self.synthesizer.write().await;
+        let voices = synth.voice_info()?;
+        let Some(voice) = engine::voice_for_language(&voices, language) else {
+            warn!(
+                "No TTS voice for language '{}', keeping the current voice",
+                language
+            );
+            return Ok(None);
+        };
+
+        synth.set_voice(&voice.id)?;
+        info!("TTS voice for '{}': {}", language, voice.name);
+        Ok(Some(voice.clone()))
+    }
+
+    /// Wait until the queue is drained and nothing is playing
+    pub async fn wait_until_idle(&self) {
+        loop {
+            if !self.in_flight.load(Ordering::SeqCst)
+                && self.queue.is_empty().await
+                && !self.synthesizer.read().await.is_speaking()
+            {
+                return;
+            }
+            tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+        }
+    }
+
     /// Get queue size
     pub async fn queue_size(&self) -> usize {
         self.queue.len().await
@@ -232,10 +325,14 @@ impl TtsSystem {
         enabled: Arc<RwLock<bool>>,
         event_bus: Option<Arc<EventBus>>,
         stats: Arc<RwLock<TtsStats>>,
+        in_flight: Arc<AtomicBool>,
     ) {
         info!("TTS worker loop started");
 
         loop {
+            // Busy until the queue is seen empty
+            in_flight.store(true, Ordering::SeqCst);
+
             // Check for cancellations
             let cancelled = queue.check_cancellations().await;
             if !cancelled.is_empty() {
@@ -266,12 +363,14 @@ impl TtsSystem {
                 // Apply profile
                 let cfg = config.read().await;
                 let profile = cfg.policy.profile_for(message.kind);
+                let preemption = cfg.preemption;
 
                 let mut synth = synthesizer.write().await;
                 if let Err(e) = synth.apply_profile(profile) {
                     error!("Failed to apply profile: {}", e);
                 }
                 drop(synth);
+                drop(cfg);
 
                 // Speak
                 let result = if message.is_ssml {
@@ -287,6 +386,30 @@ impl TtsSystem {
                     }
                 };
 
+                // Let playback finish unless something more urgent arrives
+                if result.is_ok() && Self::wait_or_preempt(&synthesizer, &queue, &message).await {
+                    stats.write().await.total_interrupted += 1;
+
+                    if let Some(ref bus) = event_bus {
+                        let _ = bus
+                            .publish(LunaEvent::TtsInterrupted {
+                                reason: "preempted by higher-priority message".to_string(),
+                            })
+                            .await;
+                    }
+
+                    match preemption {
+                        PreemptionPolicy::Requeue => {
+                            debug!("Requeueing preempted utterance {}", message.id);
+                            queue.enqueue(message).await;
+                        }
+                        PreemptionPolicy::Drop => {
+                            debug!("Dropping preempted utterance {}", message.id);
+                        }
+                    }
+                    continue;
+                }
+
                 // Update stats
                 let mut st = stats.write().await;
                 st.total_utterances += 1;
@@ -309,10 +432,41 @@ impl TtsSystem {
                 }
             } else {
                 // No messages, sleep briefly
+                in_flight.store(false, Ordering::SeqCst);
                 tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
             }
         }
     }
+
+    /// Wait for the current utterance to finish playing
+    ///
+    /// Returns true if it was stopped because a higher-priority message is
+    /// waiting in the queue.
+    async fn wait_or_preempt(
+        synthesizer: &Arc<RwLock<TextToSpeech>>,
+        queue: &TtsQueue,
+        message: &TtsMessage,
+    ) -> bool {
+        loop {
+            if !synthesizer.read().await.is_speaking() {
+                return false;
+            }
+
+            if message.kind.is_interruptible() {
+                if let Some(next) = queue.peek_priority().await {
+                    if next > message.priority {
+                        info!("Preempting {:?} utterance for {:?}", message.kind, next);
+                        if let Err(e) = synthesizer.write().await.stop() {
+                            error!("Failed to stop preempted utterance: {}", e);
+                        }
+                        return true;
+                    }
+                }
+            }
+
+            tokio::time::sleep(tokio::time::Duration::from_millis(20)).await;
+        }
+    }
 }
 
 impl Default for TtsSystem {
@@ -382,4 +536,220 @@ mod tests {
             assert_eq!(system.queue_size().await, 1);
         }
     }
+
+    /// Engine whose playback lasts 5ms per character
+    #[derive(Default)]
+    struct TimedEngine {
+        spoken: Arc<std::sync::Mutex<Vec<String>>>,
+        speaking_until: Option<std::time::Instant>,
+        voices: Vec<VoiceInfo>,
+        voice: Arc<std::sync::Mutex<Option<String>>>,
+    }
+
+    #[async_trait::async_trait]
+    impl TtsEngine for TimedEngine {
+        async fn speak(&mut self, text: &str, _interrupt: bool) -> Result<()> {
+            self.spoken.lock().unwrap().push(text.to_string());
+            self.speaking_until = Some(
+                std::time::Instant::now() + std::time::Duration::from_millis(5 * text.len() as u64),
+            );
+            Ok(())
+        }
+
+        async fn speak_ssml(&mut self, ssml: &str, interrupt: bool) -> Result<()> {
+            self.speak(ssml, interrupt).await
+        }
+
+        fn stop(&mut self) -> Result<()> {
+            self.speaking_until = None;
+            Ok(())
+        }
+
+        fn set_voice(&mut self, voice_id: &str) -> Result<()> {
+            *self.voice.lock().unwrap() = Some(voice_id.to_string());
+            Ok(())
+        }
+
+        fn set_rate(&mut self, _rate: f32) -> Result<()> {
+            Ok(())
+        }
+
+        fn set_pitch(&mut self, _pitch: f32) -> Result<()> {
+            Ok(())
+        }
+
+        fn set_volume(&mut self, _volume: f32) -> Result<()> {
+            Ok(())
+        }
+
+        fn voices(&self) -> Result<Vec<VoiceInfo>> {
+            Ok(self.voices.clone())
+        }
+
+        fn is_speaking(&self) -> bool {
+            self.speaking_until
+                .is_some_and(|until| std::time::Instant::now() < until)
+        }
+
+        fn name(&self) -> &str {
+            "timed"
+        }
+    }
+
+    async fn wait_for_spoken(spoken: &std::sync::Mutex<Vec<String>>, count: usize) {
+        for _ in 0..100 {
+            if spoken.lock().unwrap().len() >= count {
+                return;
+            }
+            tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+        }
+        panic!("expected {} utterances", count);
+    }
+
+    #[tokio::test]
+    async fn test_critical_preempts_info() {
+        let engine = TimedEngine::default();
+        let spoken = Arc::clone(&engine.spoken);
+        let mut system = TtsSystem::with_engine(Box::new(engine), TtsConfig::default());
+        system.start().await.unwrap();
+
+        // ~3.5s of playback
+        let long_info = "status update ".repeat(50);
+        system
+            .speak_with(MessageKind::Info, &long_info)
+            .await
+            .unwrap();
+        wait_for_spoken(&spoken, 1).await;
+
+        system
+            .speak_with(MessageKind::Critical, "battery critical")
+            .await
+            .unwrap();
+        wait_for_spoken(&spoken, 3).await;
+
+        // Critical cut in, then the Info was requeued from the start
+        let spoken = spoken.lock().unwrap().clone();
+        assert_eq!(
+            spoken,
+            vec![long_info.clone(), "battery critical".to_string(), long_info]
+        );
+        assert_eq!(system.stats().await.total_interrupted, 1);
+
+        system.stop().await;
+    }
+
+    #[tokio::test]
+    async fn test_preempted_message_dropped_by_policy() {
+        let engine = TimedEngine::default();
+        let spoken = Arc::clone(&engine.spoken);
+        let config = TtsConfig {
+            preemption: PreemptionPolicy::Drop,
+            ..Default::default()
+        };
+        let mut system = TtsSystem::with_engine(Box::new(engine), config);
+        system.start().await.unwrap();
+
+        system
+            .speak_with(MessageKind::Info, &"status update ".repeat(50))
+            .await
+            .unwrap();
+        wait_for_spoken(&spoken, 1).await;
+        system
+            .speak_with(MessageKind::Error, "disk full")
+            .await
+            .unwrap();
+        wait_for_spoken(&spoken, 2).await;
+        tokio::time::sleep(tokio::time::Duration::from_millis(200)).await;
+
+        assert_eq!(spoken.lock().unwrap().len(), 2);
+        assert_eq!(system.queue_size().await, 0);
+        assert_eq!(system.stats().await.total_interrupted, 1);
+
+        system.stop().await;
+    }
+
+    #[tokio::test]
+    async fn test_set_engine_swaps_at_runtime() {
+        let engine = TimedEngine::default();
+        let first = Arc::clone(&engine.spoken);
+        let quiet = Arc::new(std::sync::Mutex::new(Vec::new()));
+
+        let mut registry = TtsRegistry::new();
+        let quiet_clone = Arc::clone(&quiet);
+        registry.register("quiet", move |_| {
+            Ok(Box::new(TimedEngine {
+                spoken: Arc::clone(&quiet_clone),
+                ..TimedEngine::default()
+            }))
+        });
+
+        let mut system = TtsSystem::with_engine(Box::new(engine), TtsConfig::default())
+            .with_registry(Arc::new(registry));
+        system.start().await.unwrap();
+
+        system.speak_with(MessageKind::Info, "one").await.unwrap();
+        wait_for_spoken(&first, 1).await;
+
+        system.set_engine("quiet").await.unwrap();
+        assert!(system.set_engine("missing").await.is_err());
+        system.speak_with(MessageKind::Info, "two").await.unwrap();
+        wait_for_spoken(&quiet, 1).await;
+
+        assert_eq!(*first.lock().unwrap(), vec!["one"]);
+        assert_eq!(*quiet.lock().unwrap(), vec!["two"]);
+        assert_eq!(system.config.read().await.engine, "quiet");
+        system.stop().await;
+    }
+
+    #[tokio::test]
+    async fn test_wait_until_idle() {
+        let engine = TimedEngine::default();
+        let spoken = Arc::clone(&engine.spoken);
+        let mut system = TtsSystem::with_engine(Box::new(engine), TtsConfig::default());
+        system.start().await.unwrap();
+
+        system.speak_with(MessageKind::Info, "one").await.unwrap();
+        system.speak_with(MessageKind::Info, "two").await.unwrap();
+        tokio::time::timeout(
+            tokio::time::Duration::from_secs(2),
+            system.wait_until_idle(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(*spoken.lock().unwrap(), vec!["one", "two"]);
+        system.stop().await;
+    }
+
+    #[tokio::test]
+    async fn test_set_language_picks_matching_voice() {
+        let voice = |id: &str, locale: &str| VoiceInfo {
+            id: id.to_string(),
+            name: id.to_string(),
+            locale: Some(locale.to_string()),
+            gender: None,
+        };
+        let engine = TimedEngine {
+            voices: vec![
+                voice("samantha", "en-US"),
+                voice("jorge", "es-ES"),
+                voice("paulina", "es_MX"),
+            ],
+            ..TimedEngine::default()
+        };
+        let selected = Arc::clone(&engine.voice);
+        let system = TtsSystem::with_engine(Box::new(engine), TtsConfig::default());
+
+        let chosen = system.set_language("es").await.unwrap().unwrap();
+        assert_eq!(chosen.id, "jorge");
+        assert_eq!(selected.lock().unwrap().as_deref(), Some("jorge"));
+
+        // Region match wins over the first voice for the language
+        let chosen = system.set_language("es-MX").await.unwrap().unwrap();
+        assert_eq!(chosen.id, "paulina");
+
+        // No French voice: keep the current one
+        assert!(system.set_language("fr").await.unwrap().is_none());
+        assert_eq!(selected.lock().unwrap().as_deref(), Some("paulina"));
+    }
 }
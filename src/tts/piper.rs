@@ -0,0 +1,282 @@
+//! Piper TTS engine
+//!
+//! Offline neural voices using the `piper` command-line synthesizer. Each
+//! utterance is rendered to a temporary WAV file and handed to the system
+//! audio player, so playback can be stopped like the OS engine.
+
+use crate::error::{LunaError, Result};
+use crate::tts::engine::{TtsEngine, VoiceInfo};
+use async_trait::async_trait;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use tokio::io::AsyncWriteExt;
+use tokio::process::{Child, Command};
+use tracing::{debug, warn};
+
+/// Name of the Piper executable looked up on `PATH`
+const PIPER_BINARY: &str = "piper";
+
+/// Piper TTS engine backed by a local `.onnx` voice model
+pub struct PiperTtsEngine {
+    model_path: PathBuf,
+    length_scale: f32,
+    volume: f32,
+    player: Option<Child>,
+    current_wav: Option<PathBuf>,
+}
+
+impl PiperTtsEngine {
+    /// Create a Piper engine for the given voice model
+    ///
+    /// Fails if the model file doesn't exist; the binary is only looked up
+    /// when speaking.
+    pub fn new<P: AsRef<Path>>(model_path: P) -> Result<Self> {
+        let model_path = model_path.as_ref().to_path_buf();
+        if !model_path.is_file() {
+            return Err(LunaError::tts_error(format!(
+                "Piper voice model not found: {}",
+                model_path.display()
+            )));
+        }
+
+        Ok(Self {
+            model_path,
+            length_scale: 1.0,
+            volume: 1.0,
+            player: None,
+            current_wav: None,
+        })
+    }
+
+    /// Path of the voice model in use
+    pub fn model_path(&self) -> &Path {
+        &self.model_path
+    }
+
+    /// Render text to a WAV file with Piper
+    async fn synthesize(&self, text: &str, output: &Path) -> Result<()> {
+        let mut child = Command::new(PIPER_BINARY)
+            .arg("--model")
+            .arg(&self.model_path)
+            .arg("--length_scale")
+            .arg(self.length_scale.to_string())
+            .arg("--output_file")
+            .arg(output)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| LunaError::tts_error(format!("Failed to run piper: {}", e)))?;
+
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin
+                .write_all(text.as_bytes())
+                .await
+                .map_err(|e| LunaError::tts_error(format!("Failed to write to piper: {}", e)))?;
+        }
+
+        let status = child
+            .wait()
+            .await
+            .map_err(|e| LunaError::tts_error(format!("Piper failed: {}", e)))?;
+        if !status.success() {
+            return Err(LunaError::tts_error(format!(
+                "Piper exited with {}",
+                status
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Wait for the current utterance to finish playing
+    async fn wait_for_player(&mut self) {
+        if let Some(mut child) = self.player.take() {
+            let _ = child.wait().await;
+        }
+        self.remove_current_wav();
+    }
+
+    fn remove_current_wav(&mut self) {
+        if let Some(path) = self.current_wav.take() {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
+/// Build the platform audio player command for a WAV file
+fn player_command(wav: &Path) -> Command {
+    #[cfg(target_os = "macos")]
+    {
+        let mut cmd = Command::new("afplay");
+        cmd.arg(wav);
+        cmd
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        let mut cmd = Command::new("powershell");
+        cmd.arg("-NoProfile").arg("-Command").arg(format!(
+            "(New-Object Media.SoundPlayer '{}').PlaySync()",
+            wav.display()
+        ));
+        cmd
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    {
+        let mut cmd = Command::new("aplay");
+        cmd.arg("-q").arg(wav);
+        cmd
+    }
+}
+
+#[async_trait]
+impl TtsEngine for PiperTtsEngine {
+    async fn speak(&mut self, text: &str, interrupt: bool) -> Result<()> {
+        if interrupt {
+            self.stop()?;
+        } else {
+            self.wait_for_player().await;
+        }
+
+        let wav = std::env::temp_dir().join(format!("luna-piper-{}.wav", uuid::Uuid::new_v4()));
+        self.synthesize(text, &wav).await?;
+        self.current_wav = Some(wav.clone());
+
+        let child = player_command(&wav)
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| LunaError::tts_error(format!("Failed to play audio: {}", e)))?;
+        self.player = Some(child);
+
+        Ok(())
+    }
+
+    async fn speak_ssml(&mut self, ssml: &str, interrupt: bool) -> Result<()> {
+        // Piper reads plain text, strip tags and speak
+        let plain = crate::tts::ssml::strip_ssml_tags(ssml);
+        self.speak(&plain, interrupt).await
+    }
+
+    fn stop(&mut self) -> Result<()> {
+        if let Some(mut child) = self.player.take() {
+            let _ = child.start_kill();
+        }
+        self.remove_current_wav();
+        Ok(())
+    }
+
+    fn set_voice(&mut self, voice_id: &str) -> Result<()> {
+        let direct = PathBuf::from(voice_id);
+        let sibling = self
+            .model_path
+            .with_file_name(format!("{}.onnx", voice_id.trim_end_matches(".onnx")));
+
+        match [direct, sibling].into_iter().find(|p| p.is_file()) {
+            Some(path) => {
+                debug!("Piper voice set to {}", path.display());
+                self.model_path = path;
+            }
+            None => warn!("Piper voice not found: {}", voice_id),
+        }
+
+        Ok(())
+    }
+
+    fn set_rate(&mut self, rate: f32) -> Result<()> {
+        // Piper controls speed through phoneme length, longer is slower
+        self.length_scale = 1.0 / rate.clamp(0.1, 10.0);
+        Ok(())
+    }
+
+    fn set_pitch(&mut self, pitch: f32) -> Result<()> {
+        debug!("Piper does not support pitch changes (requested {})", pitch);
+        Ok(())
+    }
+
+    fn set_volume(&mut self, volume: f32) -> Result<()> {
+        self.volume = volume.clamp(0.0, 1.0);
+        debug!("Piper volume is left to the system mixer ({})", self.volume);
+        Ok(())
+    }
+
+    fn voices(&self) -> Result<Vec<VoiceInfo>> {
+        let dir = self.model_path.parent().unwrap_or_else(|| Path::new("."));
+        let entries = std::fs::read_dir(dir)
+            .map_err(|e| LunaError::tts_error(format!("Failed to list Piper voices: {}", e)))?;
+
+        Ok(entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "onnx"))
+            .map(|path| {
+                let name = path
+                    .file_stem()
+                    .map(|s| s.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                VoiceInfo {
+                    id: path.display().to_string(),
+                    name,
+                    locale: None,
+                    gender: None,
+                }
+            })
+            .collect())
+    }
+
+    fn is_speaking(&self) -> bool {
+        self.player.is_some()
+    }
+
+    fn name(&self) -> &str {
+        "piper"
+    }
+}
+
+impl Drop for PiperTtsEngine {
+    fn drop(&mut self) {
+        let _ = self.stop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_missing_model_is_rejected() {
+        assert!(PiperTtsEngine::new("models/piper/missing.onnx").is_err());
+    }
+
+    #[test]
+    fn test_rate_maps_to_length_scale() {
+        let dir = tempdir().unwrap();
+        let model = dir.path().join("voice.onnx");
+        std::fs::write(&model, b"").unwrap();
+
+        let mut engine = PiperTtsEngine::new(&model).unwrap();
+        engine.set_rate(2.0).unwrap();
+        assert!((engine.length_scale - 0.5).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_voices_lists_models_in_directory() {
+        let dir = tempdir().unwrap();
+        let model = dir.path().join("en_US-lessac.onnx");
+        std::fs::write(&model, b"").unwrap();
+        std::fs::write(dir.path().join("de_DE-thorsten.onnx"), b"").unwrap();
+        std::fs::write(dir.path().join("en_US-lessac.onnx.json"), b"{}").unwrap();
+
+        let mut engine = PiperTtsEngine::new(&model).unwrap();
+        assert_eq!(engine.voices().unwrap().len(), 2);
+
+        engine.set_voice("de_DE-thorsten").unwrap();
+        assert_eq!(
+            engine.model_path(),
+            dir.path().join("de_DE-thorsten.onnx").as_path()
+        );
+    }
+}
@@ -121,6 +121,11 @@ impl TtsQueue {
         self.queue.write().await.pop().map(|m| m.0)
     }
 
+    /// Priority of the next message without removing it
+    pub async fn peek_priority(&self) -> Option<Priority> {
+        self.queue.read().await.peek().map(|m| m.0.priority)
+    }
+
     /// Check if a message should be cancelled
     pub async fn check_cancellations(&self) -> Vec<u64> {
         let mut cancelled = Vec::new();
@@ -191,6 +196,22 @@ mod tests {
         assert_eq!(msg3.kind, MessageKind::Background);
     }
 
+    #[tokio::test]
+    async fn test_peek_priority() {
+        let queue = TtsQueue::new();
+        assert_eq!(queue.peek_priority().await, None);
+
+        queue
+            .enqueue(TtsMessage::new("info".to_string(), MessageKind::Info))
+            .await;
+        queue
+            .enqueue(TtsMessage::new("error".to_string(), MessageKind::Error))
+            .await;
+
+        assert_eq!(queue.peek_priority().await, Some(Priority::Critical));
+        assert_eq!(queue.len().await, 2);
+    }
+
     #[tokio::test]
     async fn test_coalescing() {
         let queue = TtsQueue::new();
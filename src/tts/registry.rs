@@ -0,0 +1,87 @@
+//! TTS engine registry
+//!
+//! Maps engine names to factories so the active engine can be rebuilt while
+//! running, e.g. switching to a quieter voice at night.
+
+use crate::config_error;
+use crate::error::Result;
+use crate::tts::engine::{OsTtsEngine, TtsEngine};
+use crate::tts::types::TtsConfig;
+use std::collections::BTreeMap;
+
+/// Builds an engine from the TTS config
+pub type TtsFactory = Box<dyn Fn(&TtsConfig) -> Result<Box<dyn TtsEngine>> + Send + Sync>;
+
+/// Named TTS engine factories
+#[derive(Default)]
+pub struct TtsRegistry {
+    factories: BTreeMap<String, TtsFactory>,
+}
+
+impl TtsRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a registry with the engines compiled into this build
+    pub fn with_builtin() -> Self {
+        let mut registry = Self::new();
+        registry.register("os", |_| Ok(Box::new(OsTtsEngine::new()?)));
+        #[cfg(feature = "piper")]
+        registry.register("piper", |config| {
+            Ok(Box::new(crate::tts::piper::PiperTtsEngine::new(
+                &config.piper_model_path,
+            )?))
+        });
+        registry
+    }
+
+    /// Register a factory under `name`, replacing any existing one
+    pub fn register<F>(&mut self, name: &str, factory: F)
+    where
+        F: Fn(&TtsConfig) -> Result<Box<dyn TtsEngine>> + Send + Sync + 'static,
+    {
+        self.factories
+            .insert(name.to_lowercase(), Box::new(factory));
+    }
+
+    /// Build the engine registered under `name`
+    pub fn create(&self, name: &str, config: &TtsConfig) -> Result<Box<dyn TtsEngine>> {
+        let factory = self.factories.get(&name.to_lowercase()).ok_or_else(|| {
+            config_error!(
+                "Unknown TTS engine '{}' (available: {})",
+                name,
+                self.names().collect::<Vec<_>>().join(", ")
+            )
+        })?;
+        factory(config)
+    }
+
+    /// Check if an engine is registered
+    pub fn contains(&self, name: &str) -> bool {
+        self.factories.contains_key(&name.to_lowercase())
+    }
+
+    /// Registered engine names, sorted
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.factories.keys().map(String::as_str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unknown_engine_lists_available() {
+        let registry = TtsRegistry::with_builtin();
+        assert!(registry.contains("OS"));
+
+        let err = registry
+            .create("coqui", &TtsConfig::default())
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("available: os"));
+    }
+}
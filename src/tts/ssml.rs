@@ -1,117 +1,301 @@
 //! SSML-lite parser
 //!
-//! Simplified SSML support for enhanced speech synthesis.
+//! Simplified SSML support for enhanced speech synthesis. Engines without
+//! native SSML are driven segment by segment from `Ssml::parse`.
 
+use crate::error::{LunaError, Result};
 use once_cell::sync::Lazy;
 use regex::Regex;
 
-static BREAK_TAG: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r#"<break\s+(?:time|strength)="([^"]+)"\s*/>"#).unwrap());
+static TAG: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"<(/?)([a-zA-Z][a-zA-Z0-9:-]*)([^>]*?)(/?)>"#).unwrap());
 
-static EMPHASIS_TAG: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r#"<emphasis[^>]*>(.*?)</emphasis>"#).unwrap());
+static ATTRIBUTE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"([a-zA-Z-]+)\s*=\s*"([^"]*)""#).unwrap());
 
-static SAY_AS_TAG: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r#"<say-as\s+interpret-as="([^"]+)"[^>]*>(.*?)</say-as>"#).unwrap());
+/// A piece of parsed SSML
+#[derive(Debug, Clone, PartialEq)]
+pub enum SsmlSegment {
+    /// Text to speak with prosody multipliers relative to the active profile
+    Speech {
+        text: String,
+        rate: f32,
+        pitch: f32,
+        volume: f32,
+    },
+    /// Silence between spoken segments
+    Pause { ms: u32 },
+}
 
-static PROSODY_TAG: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r#"<prosody[^>]*>(.*?)</prosody>"#).unwrap());
+/// Fallback SSML interpreter for engines without native SSML support
+///
+/// Supports `<break>`, `<prosody>`, `<emphasis>`, and `<say-as>`; other tags
+/// are accepted and ignored.
+pub struct Ssml;
 
-/// Parse SSML-lite text into chunks with metadata
+/// Prosody in effect inside an element
 #[derive(Debug, Clone)]
-pub struct SsmlChunk {
-    pub text: String,
-    pub break_ms: Option<u32>,
-    pub rate_modifier: f32,
-    pub pitch_modifier: f32,
-    pub emphasis: bool,
-}
-
-impl Default for SsmlChunk {
-    fn default() -> Self {
-        Self {
-            text: String::new(),
-            break_ms: None,
-            rate_modifier: 1.0,
-            pitch_modifier: 1.0,
-            emphasis: false,
+struct Frame {
+    tag: String,
+    rate: f32,
+    pitch: f32,
+    volume: f32,
+    say_as: Option<String>,
+}
+
+impl Ssml {
+    /// Parse SSML into speech and pause segments
+    ///
+    /// Returns an error for mismatched or unclosed tags.
+    pub fn parse(ssml: &str) -> Result<Vec<SsmlSegment>> {
+        let mut segments = Vec::new();
+        let mut stack = vec![Frame {
+            tag: String::new(),
+            rate: 1.0,
+            pitch: 1.0,
+            volume: 1.0,
+            say_as: None,
+        }];
+        let mut cursor = 0;
+
+        for caps in TAG.captures_iter(ssml) {
+            let whole = caps.get(0).unwrap();
+            push_text(
+                &mut segments,
+                &ssml[cursor..whole.start()],
+                stack.last().unwrap(),
+            );
+            cursor = whole.end();
+
+            let closing = !caps[1].is_empty();
+            let self_closing = !caps[4].is_empty();
+            let tag = caps[2].to_lowercase();
+            let attrs = &caps[3];
+
+            if closing {
+                let frame = stack.pop().filter(|f| !f.tag.is_empty());
+                match frame {
+                    Some(frame) if frame.tag == tag => {}
+                    Some(frame) => {
+                        return Err(LunaError::tts_error(format!(
+                            "Invalid SSML: expected </{}>, found </{}>",
+                            frame.tag, tag
+                        )))
+                    }
+                    None => {
+                        return Err(LunaError::tts_error(format!(
+                            "Invalid SSML: unexpected </{}>",
+                            tag
+                        )))
+                    }
+                }
+                continue;
+            }
+
+            // Breaks are always empty elements
+            if tag == "break" {
+                let ms = attribute(attrs, "time")
+                    .or_else(|| attribute(attrs, "strength"))
+                    .map(|d| parse_break_duration(&d))
+                    .unwrap_or(200);
+                push_pause(&mut segments, ms);
+                continue;
+            }
+
+            if self_closing {
+                continue;
+            }
+
+            let parent = stack.last().unwrap();
+            let mut frame = Frame {
+                tag: tag.clone(),
+                ..parent.clone()
+            };
+            match tag.as_str() {
+                "prosody" => {
+                    if let Some(rate) = attribute(attrs, "rate") {
+                        frame.rate *= parse_rate(&rate);
+                    }
+                    if let Some(pitch) = attribute(attrs, "pitch") {
+                        frame.pitch *= parse_pitch(&pitch);
+                    }
+                    if let Some(volume) = attribute(attrs, "volume") {
+                        frame.volume *= parse_volume(&volume);
+                    }
+                }
+                "emphasis" => {
+                    let (rate, pitch, volume) =
+                        match attribute(attrs, "level").as_deref().unwrap_or("moderate") {
+                            "strong" => (0.85, 1.15, 1.2),
+                            "reduced" => (1.1, 0.95, 0.9),
+                            "none" => (1.0, 1.0, 1.0),
+                            _ => (0.9, 1.1, 1.1),
+                        };
+                    frame.rate *= rate;
+                    frame.pitch *= pitch;
+                    frame.volume *= volume;
+                }
+                "say-as" => {
+                    frame.say_as = attribute(attrs, "interpret-as");
+                }
+                _ => {}
+            }
+            stack.push(frame);
         }
+
+        push_text(&mut segments, &ssml[cursor..], stack.last().unwrap());
+
+        if let Some(open) = stack.iter().skip(1).last() {
+            return Err(LunaError::tts_error(format!(
+                "Invalid SSML: unclosed <{}>",
+                open.tag
+            )));
+        }
+
+        Ok(segments)
     }
 }
 
-/// Parse SSML-lite markup into chunks
-pub fn parse_ssml(text: &str) -> Vec<SsmlChunk> {
-    let mut chunks = Vec::new();
-    let mut remaining = text.to_string();
-
-    // Simple sequential processing - handle breaks first
-    while let Some(caps) = BREAK_TAG.captures(&remaining) {
-        let before = &remaining[..caps.get(0).unwrap().start()];
-        if !before.is_empty() {
-            chunks.push(SsmlChunk {
-                text: before.to_string(),
-                ..Default::default()
-            });
-        }
+/// Append text spoken with the frame's prosody, merging with the previous segment
+fn push_text(segments: &mut Vec<SsmlSegment>, raw: &str, frame: &Frame) {
+    let text = collapse_whitespace(&decode_entities(raw));
+    if text.is_empty() {
+        return;
+    }
+
+    let text = match frame.say_as.as_deref() {
+        Some(interpret_as) => say_as(interpret_as, &text),
+        None => text,
+    };
 
-        // Parse break duration
-        let duration = caps.get(1).unwrap().as_str();
-        let break_ms = parse_break_duration(duration);
-        chunks.push(SsmlChunk {
-            text: String::new(),
-            break_ms: Some(break_ms),
-            ..Default::default()
-        });
-
-        remaining = remaining[caps.get(0).unwrap().end()..].to_string();
-    }
-
-    // Handle emphasis
-    if let Some(caps) = EMPHASIS_TAG.captures(&remaining) {
-        let emphasized = caps.get(1).unwrap().as_str();
-        chunks.push(SsmlChunk {
-            text: emphasized.to_string(),
-            emphasis: true,
-            rate_modifier: 0.9,
-            pitch_modifier: 1.1,
-            ..Default::default()
-        });
-        remaining = EMPHASIS_TAG.replace(&remaining, "$1").to_string();
-    }
-
-    // Handle say-as
-    while let Some(caps) = SAY_AS_TAG.captures(&remaining) {
-        let interpret_as = caps.get(1).unwrap().as_str();
-        let content = caps.get(2).unwrap().as_str();
-
-        let processed = match interpret_as {
-            "characters" | "spell-out" => content
-                .chars()
-                .map(|c| format!("{} ", c))
-                .collect::<String>(),
-            "digits" => content
-                .chars()
-                .filter(|c| c.is_numeric())
-                .map(|c| format!("{} ", c))
-                .collect::<String>(),
-            _ => content.to_string(),
-        };
-
-        remaining = SAY_AS_TAG.replace(&remaining, &processed).to_string();
-    }
-
-    // Add final chunk if any text remains
-    if !remaining.is_empty() {
-        let clean = strip_ssml_tags(&remaining);
-        if !clean.is_empty() {
-            chunks.push(SsmlChunk {
-                text: clean,
-                ..Default::default()
-            });
+    if let Some(SsmlSegment::Speech {
+        text: prev,
+        rate,
+        pitch,
+        volume,
+    }) = segments.last_mut()
+    {
+        if *rate == frame.rate && *pitch == frame.pitch && *volume == frame.volume {
+            prev.push(' ');
+            prev.push_str(&text);
+            return;
         }
     }
 
-    chunks
+    segments.push(SsmlSegment::Speech {
+        text,
+        rate: frame.rate,
+        pitch: frame.pitch,
+        volume: frame.volume,
+    });
+}
+
+/// Append a pause, merging consecutive breaks
+fn push_pause(segments: &mut Vec<SsmlSegment>, ms: u32) {
+    if let Some(SsmlSegment::Pause { ms: prev }) = segments.last_mut() {
+        *prev += ms;
+    } else {
+        segments.push(SsmlSegment::Pause { ms });
+    }
+}
+
+/// Read a quoted attribute value
+fn attribute(attrs: &str, name: &str) -> Option<String> {
+    ATTRIBUTE
+        .captures_iter(attrs)
+        .find(|caps| caps[1].eq_ignore_ascii_case(name))
+        .map(|caps| caps[2].trim().to_string())
+}
+
+/// Render `<say-as>` content as speakable text
+fn say_as(interpret_as: &str, content: &str) -> String {
+    match interpret_as {
+        "characters" | "spell-out" => spaced(content.chars().filter(|c| !c.is_whitespace())),
+        "digits" => spaced(content.chars().filter(|c| c.is_ascii_digit())),
+        _ => content.to_string(),
+    }
+}
+
+fn spaced(chars: impl Iterator<Item = char>) -> String {
+    chars.map(String::from).collect::<Vec<_>>().join(" ")
+}
+
+/// Parse a relative multiplier like "80%", "+10%", or "1.2"
+fn parse_relative(value: &str) -> Option<f32> {
+    if let Some(percent) = value.strip_suffix('%') {
+        let n: f32 = percent.parse().ok()?;
+        Some(if value.starts_with(['+', '-']) {
+            1.0 + n / 100.0
+        } else {
+            n / 100.0
+        })
+    } else {
+        value.parse().ok()
+    }
+}
+
+/// Parse a prosody rate into a multiplier
+fn parse_rate(value: &str) -> f32 {
+    match value {
+        "x-slow" => 0.5,
+        "slow" => 0.75,
+        "medium" | "default" => 1.0,
+        "fast" => 1.25,
+        "x-fast" => 1.5,
+        _ => parse_relative(value).unwrap_or(1.0),
+    }
+    .max(0.0)
+}
+
+/// Parse a prosody pitch into a multiplier
+fn parse_pitch(value: &str) -> f32 {
+    match value {
+        "x-low" => 0.7,
+        "low" => 0.85,
+        "medium" | "default" => 1.0,
+        "high" => 1.15,
+        "x-high" => 1.3,
+        _ => match value.strip_suffix("st") {
+            Some(semitones) => semitones
+                .parse::<f32>()
+                .map(|st| 2f32.powf(st / 12.0))
+                .unwrap_or(1.0),
+            None => parse_relative(value).unwrap_or(1.0),
+        },
+    }
+    .max(0.0)
+}
+
+/// Parse a prosody volume into a multiplier
+fn parse_volume(value: &str) -> f32 {
+    match value {
+        "silent" => 0.0,
+        "x-soft" => 0.5,
+        "soft" => 0.75,
+        "medium" | "default" => 1.0,
+        "loud" => 1.25,
+        "x-loud" => 1.5,
+        _ => match value.strip_suffix("dB") {
+            Some(db) => db
+                .parse::<f32>()
+                .map(|db| 10f32.powf(db / 20.0))
+                .unwrap_or(1.0),
+            None => parse_relative(value).unwrap_or(1.0),
+        },
+    }
+    .max(0.0)
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
 }
 
 /// Parse break duration string to milliseconds
@@ -202,8 +386,63 @@ mod tests {
     #[test]
     fn test_parse_ssml_with_break() {
         let ssml = r#"Hello<break time="500ms"/>world"#;
-        let chunks = parse_ssml(ssml);
-        assert!(chunks.len() >= 2);
-        assert!(chunks.iter().any(|c| c.break_ms == Some(500)));
+        let segments = Ssml::parse(ssml).unwrap();
+        assert!(segments.len() >= 2);
+        assert!(segments.contains(&SsmlSegment::Pause { ms: 500 }));
+    }
+
+    fn speech(segment: &SsmlSegment) -> (&str, f32, f32, f32) {
+        match segment {
+            SsmlSegment::Speech {
+                text,
+                rate,
+                pitch,
+                volume,
+            } => (text.as_str(), *rate, *pitch, *volume),
+            SsmlSegment::Pause { ms } => panic!("expected speech, got pause of {}ms", ms),
+        }
+    }
+
+    #[test]
+    fn test_pause_between_segments() {
+        let ssml = r#"<speak>Saving <break time="1s"/><break strength="weak"/> done</speak>"#;
+        let segments = Ssml::parse(ssml).unwrap();
+
+        assert_eq!(segments.len(), 3);
+        assert_eq!(speech(&segments[0]).0, "Saving");
+        assert_eq!(segments[1], SsmlSegment::Pause { ms: 1100 });
+        assert_eq!(speech(&segments[2]).0, "done");
+    }
+
+    #[test]
+    fn test_prosody_adjusts_segment() {
+        let ssml = r#"Normal <prosody rate="slow" volume="+50%">slow and loud</prosody> normal"#;
+        let segments = Ssml::parse(ssml).unwrap();
+
+        assert_eq!(segments.len(), 3);
+        assert_eq!(speech(&segments[0]), ("Normal", 1.0, 1.0, 1.0));
+        assert_eq!(speech(&segments[1]), ("slow and loud", 0.75, 1.0, 1.5));
+        assert_eq!(speech(&segments[2]), ("normal", 1.0, 1.0, 1.0));
+    }
+
+    #[test]
+    fn test_nested_prosody_multiplies() {
+        let ssml = r#"<prosody rate="50%"><prosody rate="x-fast">text</prosody></prosody>"#;
+        let segments = Ssml::parse(ssml).unwrap();
+        assert_eq!(speech(&segments[0]).1, 0.75);
+    }
+
+    #[test]
+    fn test_say_as() {
+        let ssml = r#"Code <say-as interpret-as="characters">AB1</say-as>, call <say-as interpret-as="digits">5-5-0</say-as>"#;
+        let segments = Ssml::parse(ssml).unwrap();
+        assert_eq!(speech(&segments[0]).0, "Code A B 1 , call 5 5 0");
+    }
+
+    #[test]
+    fn test_unbalanced_tags_rejected() {
+        assert!(Ssml::parse("<prosody rate=\"slow\">unclosed").is_err());
+        assert!(Ssml::parse("stray</emphasis>").is_err());
+        assert!(Ssml::parse("<emphasis><prosody>x</emphasis></prosody>").is_err());
     }
 }
@@ -4,33 +4,90 @@
 
 use crate::error::Result;
 use crate::tts::engine::{OsTtsEngine, TtsEngine, VoiceInfo};
-use crate::tts::ssml::{chunk_text, parse_ssml, strip_ssml_tags};
-use crate::tts::types::VoiceProfile;
+use crate::tts::lexicon::Lexicon;
+use crate::tts::ssml::{chunk_text, strip_ssml_tags, Ssml, SsmlSegment};
+use crate::tts::types::{
+    TtsConfig, TtsEngineKind, VoiceProfile, PITCH_RANGE, RATE_RANGE, VOLUME_RANGE,
+};
+use std::ops::RangeInclusive;
 use std::time::Duration;
-use tracing::{debug, error, info};
+use tracing::{debug, error, info, warn};
 
 /// Text-to-speech synthesizer with enhanced capabilities
 pub struct TextToSpeech {
     engine: Box<dyn TtsEngine>,
     current_profile: VoiceProfile,
+    lexicon: Lexicon,
 }
 
 impl TextToSpeech {
-    /// Create a new text-to-speech synthesizer with OS engine
+    /// Create a new text-to-speech synthesizer with the default config
     pub fn new() -> Result<Self> {
+        Self::with_config(&TtsConfig::default())
+    }
+
+    /// Create a synthesizer using the engine selected by `config.engine`
+    ///
+    /// Piper falls back to the OS engine (with a warning) when the feature is
+    /// disabled or the voice model is missing.
+    pub fn with_config(config: &TtsConfig) -> Result<Self> {
         info!("Initializing TTS synthesizer...");
-        let engine = Box::new(OsTtsEngine::new()?);
+        let engine: Box<dyn TtsEngine> = match Self::resolve_engine(config) {
+            #[cfg(feature = "piper")]
+            TtsEngineKind::Piper => Box::new(crate::tts::piper::PiperTtsEngine::new(
+                &config.piper_model_path,
+            )?),
+            _ => Box::new(OsTtsEngine::new()?),
+        };
         info!(
             "✅ TTS synthesizer initialized with engine: {}",
             engine.name()
         );
 
+        let lexicon = match &config.lexicon_path {
+            Some(path) => match Lexicon::load_from_file(path) {
+                Ok(lexicon) => {
+                    info!("Loaded {} pronunciation overrides", lexicon.len());
+                    lexicon
+                }
+                Err(e) => {
+                    warn!("Ignoring lexicon {}: {}", path.display(), e);
+                    Lexicon::default()
+                }
+            },
+            None => Lexicon::default(),
+        };
+
         Ok(Self {
             engine,
             current_profile: VoiceProfile::default(),
+            lexicon,
         })
     }
 
+    /// Engine that `with_config` will build for this config
+    pub fn resolve_engine(config: &TtsConfig) -> TtsEngineKind {
+        let kind = config.engine.parse().unwrap_or_else(|e| {
+            warn!("{}, using OS TTS", e);
+            TtsEngineKind::Os
+        });
+        match kind {
+            TtsEngineKind::Piper if !cfg!(feature = "piper") => {
+                warn!("Piper not available (feature disabled), falling back to OS TTS");
+                TtsEngineKind::Os
+            }
+            TtsEngineKind::Piper if !config.piper_model_path.is_file() => {
+                warn!(
+                    "Piper voice model not found at: {}",
+                    config.piper_model_path.display()
+                );
+                warn!("TTS falling back to OS engine");
+                TtsEngineKind::Os
+            }
+            kind => kind,
+        }
+    }
+
     /// Create with custom engine
     pub fn with_engine(engine: Box<dyn TtsEngine>) -> Self {
         let engine_name = engine.name().to_string();
@@ -39,11 +96,36 @@ impl TextToSpeech {
         Self {
             engine,
             current_profile: VoiceProfile::default(),
+            lexicon: Lexicon::default(),
         }
     }
 
+    /// Replace the engine, carrying over the current voice profile
+    pub fn set_engine(&mut self, engine: Box<dyn TtsEngine>) -> Result<()> {
+        let _ = self.engine.stop();
+        info!(
+            "TTS synthesizer switching engine: {} -> {}",
+            self.engine.name(),
+            engine.name()
+        );
+        self.engine = engine;
+
+        let profile = self.current_profile.clone();
+        self.apply_profile(&profile)
+    }
+
+    /// Use pronunciation overrides for everything spoken
+    pub fn with_lexicon(mut self, lexicon: Lexicon) -> Self {
+        self.lexicon = lexicon;
+        self
+    }
+
     /// Apply voice profile settings
+    ///
+    /// Out-of-range profiles are rejected before anything reaches the engine.
     pub fn apply_profile(&mut self, profile: &VoiceProfile) -> Result<()> {
+        profile.validate()?;
+
         debug!(
             "Applying voice profile: rate={}, pitch={}, volume={}",
             profile.rate, profile.pitch, profile.volume
@@ -69,7 +151,7 @@ impl TextToSpeech {
             text.len()
         );
 
-        self.engine.speak(text, false).await
+        self.speak_text(text, false).await
     }
 
     /// Speak text with interrupt capability
@@ -79,7 +161,7 @@ impl TextToSpeech {
             text.chars().take(50).collect::<String>()
         );
 
-        self.engine.speak(text, true).await
+        self.speak_text(text, true).await
     }
 
     /// Speak text with SSML markup
@@ -90,31 +172,53 @@ impl TextToSpeech {
             ssml.len()
         );
 
-        let chunks = parse_ssml(ssml);
+        if self.engine.supports_ssml() {
+            return self.engine.speak_ssml(ssml, false).await;
+        }
 
-        for chunk in chunks {
-            if let Some(break_ms) = chunk.break_ms {
-                debug!("SSML break: {}ms", break_ms);
-                tokio::time::sleep(Duration::from_millis(break_ms as u64)).await;
-                continue;
+        let segments = match Ssml::parse(ssml) {
+            Ok(segments) => segments,
+            Err(e) => {
+                warn!("{}, speaking as plain text", e);
+                return self.speak_text(&strip_ssml_tags(ssml), false).await;
             }
-
-            if !chunk.text.is_empty() {
-                // Apply chunk-specific modifiers
-                let orig_rate = self.current_profile.rate;
-                let orig_pitch = self.current_profile.pitch;
-
-                if chunk.emphasis || chunk.rate_modifier != 1.0 || chunk.pitch_modifier != 1.0 {
-                    let _ = self.engine.set_rate(orig_rate * chunk.rate_modifier);
-                    let _ = self.engine.set_pitch(orig_pitch * chunk.pitch_modifier);
+        };
+
+        let profile = self.current_profile.clone();
+        for segment in segments {
+            match segment {
+                SsmlSegment::Pause { ms } => {
+                    debug!("SSML break: {}ms", ms);
+                    tokio::time::sleep(Duration::from_millis(ms as u64)).await;
                 }
-
-                self.engine.speak(&chunk.text, false).await?;
-
-                // Restore
-                if chunk.emphasis || chunk.rate_modifier != 1.0 || chunk.pitch_modifier != 1.0 {
-                    let _ = self.engine.set_rate(orig_rate);
-                    let _ = self.engine.set_pitch(orig_pitch);
+                SsmlSegment::Speech {
+                    text,
+                    rate,
+                    pitch,
+                    volume,
+                } => {
+                    let adjusted = rate != 1.0 || pitch != 1.0 || volume != 1.0;
+                    if adjusted {
+                        let _ = self
+                            .engine
+                            .set_rate(clamp_to(profile.rate * rate, RATE_RANGE));
+                        let _ = self
+                            .engine
+                            .set_pitch(clamp_to(profile.pitch * pitch, PITCH_RANGE));
+                        let _ = self
+                            .engine
+                            .set_volume(clamp_to(profile.volume * volume, VOLUME_RANGE));
+                    }
+
+                    let result = self.speak_text(&text, false).await;
+
+                    // Restore
+                    if adjusted {
+                        let _ = self.engine.set_rate(profile.rate);
+                        let _ = self.engine.set_pitch(profile.pitch);
+                        let _ = self.engine.set_volume(profile.volume);
+                    }
+                    result?;
                 }
             }
         }
@@ -134,7 +238,7 @@ impl TextToSpeech {
                 chunks.len(),
                 chunk.chars().take(30).collect::<String>()
             );
-            self.engine.speak(chunk, true).await?;
+            self.speak_text(chunk, true).await?;
         }
 
         Ok(())
@@ -145,7 +249,18 @@ impl TextToSpeech {
         info!("🔊 Speaking with voice '{}': \"{}\"", voice, text);
 
         self.engine.set_voice(voice)?;
-        self.engine.speak(text, false).await
+        self.speak_text(text, false).await
+    }
+
+    /// Apply the lexicon and hand text to the engine
+    async fn speak_text(&mut self, text: &str, interrupt: bool) -> Result<()> {
+        if self.engine.supports_ssml() && self.lexicon.has_ssml_match(text) {
+            let ssml = format!("<speak>{}</speak>", self.lexicon.apply_ssml(text));
+            return self.engine.speak_ssml(&ssml, interrupt).await;
+        }
+
+        let text = self.lexicon.apply(text);
+        self.engine.speak(&text, interrupt).await
     }
 
     /// Stop current speech
@@ -170,6 +285,12 @@ impl TextToSpeech {
         self.engine.is_speaking()
     }
 
+    /// Set the voice used for everything spoken
+    pub fn set_voice(&mut self, voice_id: &str) -> Result<()> {
+        self.current_profile.voice = Some(voice_id.to_string());
+        self.engine.set_voice(voice_id)
+    }
+
     /// Set speech rate (0.1 to 10.0, 1.0 is normal)
     pub fn set_rate(&mut self, rate: f32) -> Result<()> {
         self.current_profile.rate = rate;
@@ -188,11 +309,197 @@ impl TextToSpeech {
         self.engine.set_volume(volume)
     }
 
+    /// Profile most recently applied to the engine
+    pub fn current_profile(&self) -> &VoiceProfile {
+        &self.current_profile
+    }
+
     /// Get engine name
     pub fn engine_name(&self) -> &str {
         self.engine.name()
     }
 }
 
+fn clamp_to(value: f32, range: RangeInclusive<f32>) -> f32 {
+    value.clamp(*range.start(), *range.end())
+}
+
 // Backward compatibility alias
 pub type Synthesizer = TextToSpeech;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use std::sync::{Arc, Mutex};
+
+    /// Engine that records the parameters it was given
+    #[derive(Default)]
+    struct RecordingEngine {
+        params: Arc<Mutex<(f32, f32, f32)>>,
+        spoken: Arc<Mutex<Vec<(String, f32)>>>,
+    }
+
+    #[async_trait]
+    impl TtsEngine for RecordingEngine {
+        async fn speak(&mut self, text: &str, _interrupt: bool) -> Result<()> {
+            let rate = self.params.lock().unwrap().0;
+            self.spoken.lock().unwrap().push((text.to_string(), rate));
+            Ok(())
+        }
+
+        async fn speak_ssml(&mut self, _ssml: &str, _interrupt: bool) -> Result<()> {
+            Ok(())
+        }
+
+        fn stop(&mut self) -> Result<()> {
+            Ok(())
+        }
+
+        fn set_voice(&mut self, _voice_id: &str) -> Result<()> {
+            Ok(())
+        }
+
+        fn set_rate(&mut self, rate: f32) -> Result<()> {
+            self.params.lock().unwrap().0 = rate;
+            Ok(())
+        }
+
+        fn set_pitch(&mut self, pitch: f32) -> Result<()> {
+            self.params.lock().unwrap().1 = pitch;
+            Ok(())
+        }
+
+        fn set_volume(&mut self, volume: f32) -> Result<()> {
+            self.params.lock().unwrap().2 = volume;
+            Ok(())
+        }
+
+        fn voices(&self) -> Result<Vec<VoiceInfo>> {
+            Ok(Vec::new())
+        }
+
+        fn is_speaking(&self) -> bool {
+            false
+        }
+
+        fn name(&self) -> &str {
+            "recording"
+        }
+    }
+
+    #[test]
+    fn test_apply_profile_sets_engine_params() {
+        let engine = RecordingEngine::default();
+        let params = Arc::clone(&engine.params);
+        let mut tts = TextToSpeech::with_engine(Box::new(engine));
+
+        let profile = VoiceProfile {
+            rate: 0.8,
+            pitch: 0.9,
+            volume: 1.0,
+            ..Default::default()
+        };
+        tts.apply_profile(&profile).unwrap();
+
+        assert_eq!(*params.lock().unwrap(), (0.8, 0.9, 1.0));
+        assert_eq!(tts.current_profile().rate, 0.8);
+    }
+
+    #[test]
+    fn test_apply_profile_rejects_out_of_range() {
+        let engine = RecordingEngine::default();
+        let params = Arc::clone(&engine.params);
+        let mut tts = TextToSpeech::with_engine(Box::new(engine));
+
+        let profile = VoiceProfile {
+            rate: 3.0,
+            ..Default::default()
+        };
+        assert!(tts.apply_profile(&profile).is_err());
+
+        // Nothing reached the engine
+        assert_eq!(*params.lock().unwrap(), (0.0, 0.0, 0.0));
+        assert_eq!(tts.current_profile().rate, 1.0);
+    }
+
+    #[test]
+    fn test_engine_kind_from_str() {
+        let parse = |s: &str| s.parse::<TtsEngineKind>();
+        assert_eq!(parse("os").unwrap(), TtsEngineKind::Os);
+        assert_eq!(parse("Piper").unwrap(), TtsEngineKind::Piper);
+        assert!(parse("coqui").is_err());
+
+        let config = TtsConfig {
+            engine: "coqui".to_string(),
+            ..TtsConfig::default()
+        };
+        assert_eq!(TextToSpeech::resolve_engine(&config), TtsEngineKind::Os);
+    }
+
+    #[test]
+    fn test_missing_piper_model_falls_back_to_os() {
+        let config = TtsConfig {
+            engine: "piper".to_string(),
+            piper_model_path: "models/piper/missing.onnx".into(),
+            ..Default::default()
+        };
+        assert_eq!(TextToSpeech::resolve_engine(&config), TtsEngineKind::Os);
+    }
+
+    #[cfg(feature = "piper")]
+    #[test]
+    fn test_piper_selected_from_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let model = dir.path().join("voice.onnx");
+        std::fs::write(&model, b"").unwrap();
+
+        let config = TtsConfig {
+            engine: "piper".to_string(),
+            piper_model_path: model,
+            ..Default::default()
+        };
+        assert_eq!(TextToSpeech::resolve_engine(&config), TtsEngineKind::Piper);
+
+        let tts = TextToSpeech::with_config(&config).unwrap();
+        assert_eq!(tts.engine_name(), "piper");
+    }
+
+    #[tokio::test]
+    async fn test_ssml_fallback_speaks_segments_with_pauses() {
+        let engine = RecordingEngine::default();
+        let spoken = Arc::clone(&engine.spoken);
+        let mut tts = TextToSpeech::with_engine(Box::new(engine));
+        tts.apply_profile(&VoiceProfile::default()).unwrap();
+
+        let start = std::time::Instant::now();
+        tts.speak_ssml(r#"One <break time="50ms"/> <prosody rate="slow">two</prosody>"#)
+            .await
+            .unwrap();
+
+        assert!(start.elapsed() >= Duration::from_millis(50));
+        assert_eq!(
+            *spoken.lock().unwrap(),
+            vec![("One".to_string(), 1.0), ("two".to_string(), 0.75)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_lexicon_rewrites_spoken_text() {
+        let engine = RecordingEngine::default();
+        let spoken = Arc::clone(&engine.spoken);
+        let lexicon = Lexicon::from_entries([("tmux", "tee mux")]).unwrap();
+        let mut tts = TextToSpeech::with_engine(Box::new(engine)).with_lexicon(lexicon);
+
+        tts.speak("Opening TMUX").await.unwrap();
+        tts.speak_chunked("Attach to tmux.", 100).await.unwrap();
+
+        let spoken: Vec<String> = spoken
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(t, _)| t.clone())
+            .collect();
+        assert_eq!(spoken, vec!["Opening tee mux", "Attach to tee mux."]);
+    }
+}
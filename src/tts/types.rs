@@ -2,9 +2,18 @@
 //!
 //! Message kinds, profiles, policies, and configurations.
 
+use crate::error::{LunaError, Result};
 use serde::{Deserialize, Serialize};
+use std::ops::RangeInclusive;
 use std::path::PathBuf;
 
+/// Allowed speech rate for voice profiles (1.0 = normal)
+pub const RATE_RANGE: RangeInclusive<f32> = 0.5..=2.0;
+/// Allowed speech pitch for voice profiles (1.0 = normal)
+pub const PITCH_RANGE: RangeInclusive<f32> = 0.5..=2.0;
+/// Allowed volume for voice profiles
+pub const VOLUME_RANGE: RangeInclusive<f32> = 0.0..=1.0;
+
 /// Priority level for TTS messages
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum Priority {
@@ -61,14 +70,34 @@ impl MessageKind {
     }
 }
 
+impl std::str::FromStr for MessageKind {
+    type Err = LunaError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "critical" => Ok(Self::Critical),
+            "error" => Ok(Self::Error),
+            "confirmation" => Ok(Self::Confirmation),
+            "prompt" => Ok(Self::Prompt),
+            "reading" => Ok(Self::Reading),
+            "info" => Ok(Self::Info),
+            "background" => Ok(Self::Background),
+            _ => Err(LunaError::InvalidParameter(format!(
+                "Unknown message kind: {}",
+                s
+            ))),
+        }
+    }
+}
+
 /// Voice profile configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VoiceProfile {
     /// Voice ID or name
     pub voice: Option<String>,
-    /// Speech rate (0.1 - 10.0, 1.0 = normal)
+    /// Speech rate (0.5 - 2.0, 1.0 = normal)
     pub rate: f32,
-    /// Speech pitch (0.0 - 2.0, 1.0 = normal)
+    /// Speech pitch (0.5 - 2.0, 1.0 = normal)
     pub pitch: f32,
     /// Volume (0.0 - 1.0)
     pub volume: f32,
@@ -91,6 +120,31 @@ impl Default for VoiceProfile {
     }
 }
 
+impl VoiceProfile {
+    /// Check rate, pitch, and volume are within their allowed ranges
+    pub fn validate(&self) -> Result<()> {
+        let checks = [
+            ("rate", self.rate, RATE_RANGE),
+            ("pitch", self.pitch, PITCH_RANGE),
+            ("volume", self.volume, VOLUME_RANGE),
+        ];
+
+        for (name, value, range) in checks {
+            if !range.contains(&value) {
+                return Err(LunaError::InvalidParameter(format!(
+                    "Voice {} {} outside {}-{}",
+                    name,
+                    value,
+                    range.start(),
+                    range.end()
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
 /// TTS policy - maps message kinds to profiles
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct TtsPolicy {
@@ -116,13 +170,91 @@ impl TtsPolicy {
             MessageKind::Background => &self.background,
         }
     }
+
+    /// Get a mutable profile for a message kind
+    pub fn profile_for_mut(&mut self, kind: MessageKind) -> &mut VoiceProfile {
+        match kind {
+            MessageKind::Critical => &mut self.critical,
+            MessageKind::Error => &mut self.error,
+            MessageKind::Confirmation => &mut self.confirmation,
+            MessageKind::Prompt => &mut self.prompt,
+            MessageKind::Reading => &mut self.reading,
+            MessageKind::Info => &mut self.info,
+            MessageKind::Background => &mut self.background,
+        }
+    }
+
+    /// Validate every profile in the policy
+    pub fn validate(&self) -> Result<()> {
+        let profiles = [
+            ("critical", &self.critical),
+            ("error", &self.error),
+            ("confirmation", &self.confirmation),
+            ("prompt", &self.prompt),
+            ("reading", &self.reading),
+            ("info", &self.info),
+            ("background", &self.background),
+        ];
+
+        for (kind, profile) in profiles {
+            profile.validate().map_err(|e| match e {
+                LunaError::InvalidParameter(msg) => {
+                    LunaError::InvalidParameter(format!("tts.policy.{}: {}", kind, msg))
+                }
+                other => other,
+            })?;
+        }
+
+        Ok(())
+    }
+}
+
+/// TTS backend selected by `TtsConfig::engine`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TtsEngineKind {
+    /// Native OS speech (tts crate)
+    Os,
+    /// Piper neural voices (feature-gated)
+    Piper,
+}
+
+/// Parses the `tts.engine` config value
+impl std::str::FromStr for TtsEngineKind {
+    type Err = LunaError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "os" => Ok(TtsEngineKind::Os),
+            "piper" => Ok(TtsEngineKind::Piper),
+            _ => Err(LunaError::InvalidParameter(format!(
+                "Unknown TTS engine: {}",
+                s
+            ))),
+        }
+    }
+}
+
+/// What happens to an utterance cut off by a higher-priority message
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PreemptionPolicy {
+    /// Put it back in the queue to be spoken again from the start
+    #[default]
+    Requeue,
+    /// Discard it
+    Drop,
 }
 
 /// TTS configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct TtsConfig {
-    /// Engine to use: "os", "coqui", etc.
+    /// Engine to use: "os", "piper"
     pub engine: String,
+    /// Path to Piper voice model (used when `engine = "piper"`)
+    pub piper_model_path: PathBuf,
+    /// Pronunciation lexicon (YAML map of word to spelling or SSML phoneme)
+    pub lexicon_path: Option<PathBuf>,
     /// Default voice ID
     pub default_voice: Option<String>,
     /// Default rate
@@ -137,6 +269,8 @@ pub struct TtsConfig {
     pub duck_system_audio: bool,
     /// Enable earcons
     pub earcons_enabled: bool,
+    /// Handling of utterances interrupted by higher-priority messages
+    pub preemption: PreemptionPolicy,
     /// Voice profiles and policies
     pub policy: TtsPolicy,
 }
@@ -145,6 +279,8 @@ impl Default for TtsConfig {
     fn default() -> Self {
         Self {
             engine: "os".to_string(),
+            piper_model_path: PathBuf::from("models/piper/en_US-lessac-medium.onnx"),
+            lexicon_path: None,
             default_voice: None,
             rate: 1.0,
             pitch: 1.0,
@@ -152,6 +288,7 @@ impl Default for TtsConfig {
             barge_in: true,
             duck_system_audio: false,
             earcons_enabled: false,
+            preemption: PreemptionPolicy::default(),
             policy: TtsPolicy::default(),
         }
     }
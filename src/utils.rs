@@ -347,7 +347,7 @@ pub mod path_helpers {
 
 /// Time and duration parsing utilities
 pub mod time_helpers {
-    use chrono::Duration;
+    use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, TimeZone, Weekday};
 
     /// Parse human-readable duration strings
     ///
@@ -396,6 +396,170 @@ pub mod time_helpers {
         }
     }
 
+    /// Parse a spoken date/time relative to `now`
+    ///
+    /// # Supported formats
+    /// - Relative: "in 2 hours", "in 30 minutes"
+    /// - Days: "today", "tomorrow", "tonight", "monday", "next friday"
+    /// - Times: "9am", "9:30 pm", "21:00", "at 9", "noon", "midnight"
+    /// - Combinations: "tomorrow at 9am", "on friday at 9:30am", "monday morning"
+    ///
+    /// Ambiguous inputs resolve to the next future occurrence: "at 9" at
+    /// 10am means 9pm today, and "monday" on a Monday afternoon means next
+    /// week. Days without a time default to 9am.
+    ///
+    /// # Returns
+    /// `Some(DateTime)` strictly after `now`, or `None` if the input isn't
+    /// a recognizable time
+    pub fn parse_datetime(input: &str, now: DateTime<Local>) -> Option<DateTime<Local>> {
+        let input = input.trim().to_lowercase();
+
+        if let Some(rest) = input.strip_prefix("in ") {
+            if let Some(duration) = parse_duration(rest) {
+                return Some(now + duration);
+            }
+        }
+
+        let tokens: Vec<&str> = input
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|t| !t.is_empty())
+            .collect();
+
+        let mut day_offset: Option<i64> = None;
+        let mut weekday: Option<Weekday> = None;
+        let mut next_week = false;
+        // (hour, minute, hour is unambiguous)
+        let mut time: Option<(u32, u32, bool)> = None;
+
+        let mut i = 0;
+        while i < tokens.len() {
+            let token = tokens[i];
+            match token {
+                "at" | "on" | "this" | "the" | "in" | "by" => {}
+                "today" => day_offset = Some(0),
+                "tomorrow" => day_offset = Some(1),
+                "tonight" => {
+                    day_offset = Some(0);
+                    time = time.or(Some((20, 0, true)));
+                }
+                "next" => next_week = true,
+                "morning" => time = time.or(Some((9, 0, true))),
+                "afternoon" => time = time.or(Some((15, 0, true))),
+                "evening" => time = time.or(Some((19, 0, true))),
+                "noon" | "midday" => time = Some((12, 0, true)),
+                "midnight" => time = Some((0, 0, true)),
+                _ => {
+                    if let Some(day) = parse_weekday(token) {
+                        weekday = Some(day);
+                    } else {
+                        let (clock, used) = parse_clock(token, tokens.get(i + 1).copied())?;
+                        time = Some(clock);
+                        i += used - 1;
+                    }
+                }
+            }
+            i += 1;
+        }
+
+        if day_offset.is_none() && weekday.is_none() && time.is_none() {
+            return None;
+        }
+
+        let today = now.date_naive();
+        let (date, period) = match (weekday, day_offset) {
+            (Some(target), _) => {
+                let mut ahead = (target.num_days_from_monday() as i64
+                    - today.weekday().num_days_from_monday() as i64)
+                    .rem_euclid(7);
+                if ahead == 0 && next_week {
+                    ahead = 7;
+                }
+                (today + Duration::days(ahead), Duration::days(7))
+            }
+            (None, Some(offset)) => (today + Duration::days(offset), Duration::days(1)),
+            (None, None) => (today, Duration::days(1)),
+        };
+
+        let (hour, minute, unambiguous) = time.unwrap_or((9, 0, true));
+        let hours: Vec<u32> = if unambiguous || hour == 0 || hour > 12 {
+            vec![hour]
+        } else {
+            // 12-hour clock without am/pm: try both halves of the day
+            vec![hour, (hour + 12) % 24]
+        };
+
+        // Earliest future candidate, rolling forward a period if all have passed
+        (0..2)
+            .flat_map(|roll| {
+                let day = date + period * roll;
+                hours.iter().filter_map(move |h| at_local(day, *h, minute))
+            })
+            .filter(|candidate| *candidate > now)
+            .min()
+    }
+
+    /// Parse a weekday name or abbreviation
+    fn parse_weekday(token: &str) -> Option<Weekday> {
+        match token {
+            "monday" | "mon" => Some(Weekday::Mon),
+            "tuesday" | "tue" | "tues" => Some(Weekday::Tue),
+            "wednesday" | "wed" => Some(Weekday::Wed),
+            "thursday" | "thu" | "thurs" => Some(Weekday::Thu),
+            "friday" | "fri" => Some(Weekday::Fri),
+            "saturday" | "sat" => Some(Weekday::Sat),
+            "sunday" | "sun" => Some(Weekday::Sun),
+            _ => None,
+        }
+    }
+
+    /// Parse a clock time ("9", "9am", "9:30", "9:30 pm", "21:00")
+    ///
+    /// Returns `((hour, minute, unambiguous), tokens consumed)`.
+    fn parse_clock(token: &str, next: Option<&str>) -> Option<((u32, u32, bool), usize)> {
+        let (clock, meridiem, used) = if let Some(c) = token.strip_suffix("am") {
+            (c, Some(false), 1)
+        } else if let Some(c) = token.strip_suffix("pm") {
+            (c, Some(true), 1)
+        } else {
+            match next {
+                Some("am") | Some("a.m.") => (token, Some(false), 2),
+                Some("pm") | Some("p.m.") => (token, Some(true), 2),
+                _ => (token, None, 1),
+            }
+        };
+
+        let (hour, minute) = match clock.split_once(':') {
+            Some((h, m)) => (h.parse::<u32>().ok()?, m.parse::<u32>().ok()?),
+            None => (clock.parse::<u32>().ok()?, 0),
+        };
+        if minute >= 60 {
+            return None;
+        }
+
+        match meridiem {
+            Some(pm) => {
+                if hour == 0 || hour > 12 {
+                    return None;
+                }
+                let hour = match (hour, pm) {
+                    (12, false) => 0,
+                    (12, true) => 12,
+                    (h, true) => h + 12,
+                    (h, false) => h,
+                };
+                Some(((hour, minute, true), used))
+            }
+            None if hour < 24 => Some(((hour, minute, false), used)),
+            None => None,
+        }
+    }
+
+    /// Build a local datetime, skipping times that fall in a DST gap
+    fn at_local(date: NaiveDate, hour: u32, minute: u32) -> Option<DateTime<Local>> {
+        let naive = date.and_hms_opt(hour, minute, 0)?;
+        Local.from_local_datetime(&naive).earliest()
+    }
+
     /// Format duration in human-readable form
     pub fn format_duration(duration: Duration) -> String {
         let seconds = duration.num_seconds();
@@ -427,6 +591,39 @@ pub mod time_helpers {
             assert_eq!(parse_duration("5m"), Some(Duration::minutes(5)));
         }
 
+        #[test]
+        fn test_parse_datetime() {
+            // Wednesday 10:00
+            let now = Local.with_ymd_and_hms(2024, 3, 13, 10, 0, 0).unwrap();
+            let at = |d: u32, h: u32, m: u32| Local.with_ymd_and_hms(2024, 3, d, h, m, 0).unwrap();
+
+            assert_eq!(parse_datetime("in 2 hours", now), Some(now + Duration::hours(2)));
+            assert_eq!(parse_datetime("tomorrow at 9am", now), Some(at(14, 9, 0)));
+            assert_eq!(parse_datetime("at 3pm", now), Some(at(13, 15, 0)));
+            assert_eq!(parse_datetime("on friday at 9:30 am", now), Some(at(15, 9, 30)));
+            assert_eq!(parse_datetime("next monday", now), Some(at(18, 9, 0)));
+            assert_eq!(parse_datetime("tonight", now), Some(at(13, 20, 0)));
+
+            // Ambiguous: 9 o'clock has passed this morning, so 9pm
+            assert_eq!(parse_datetime("at 9", now), Some(at(13, 21, 0)));
+            // Already past today, so the same time tomorrow
+            assert_eq!(parse_datetime("at 8am", now), Some(at(14, 8, 0)));
+            // Today is Wednesday; "wednesday at 9am" is next week
+            assert_eq!(parse_datetime("wednesday at 9am", now), Some(at(20, 9, 0)));
+
+            assert_eq!(parse_datetime("whenever", now), None);
+            assert_eq!(parse_datetime("at 25:00", now), None);
+        }
+
+        #[test]
+        fn test_parse_datetime_is_future() {
+            let now = Local::now();
+            for phrase in ["tomorrow at 9am", "in 2 hours", "next monday", "at 9", "noon"] {
+                let resolved = parse_datetime(phrase, now).unwrap();
+                assert!(resolved > now, "{} resolved to the past", phrase);
+            }
+        }
+
         #[test]
         fn test_format_duration() {
             assert_eq!(format_duration(Duration::seconds(30)), "30 seconds");
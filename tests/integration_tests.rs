@@ -9,12 +9,14 @@ use luna::context::memory::ConversationMemory;
 use luna::db::{AppDatabase, FileIndex};
 use luna::events::EventBus;
 use luna::metrics::Metrics;
+use luna::runtime::ScriptRunner;
 use luna::tts::TtsSystem;
+use parking_lot::RwLock;
 use std::sync::Arc;
 use tokio::time::{timeout, Duration};
 
 /// Helper function to create test app database
-fn create_test_app_db() -> Arc<AppDatabase> {
+fn create_test_app_db() -> Arc<RwLock<AppDatabase>> {
     use luna::db::schema::{Application, AppCategory};
     use std::path::PathBuf;
     
@@ -25,27 +27,33 @@ fn create_test_app_db() -> Arc<AppDatabase> {
         id: "chrome".to_string(),
         name: "Chrome".to_string(),
         executable: PathBuf::from("/usr/bin/chrome"),
+        launch_args: Vec::new(),
         category: AppCategory::Browser,
         aliases: vec!["google-chrome".to_string(), "chromium".to_string()],
         description: Some("Google Chrome browser".to_string()),
         icon: None,
         version: None,
         install_date: None,
+        launch_count: 0,
+        last_used: None,
     });
     
     db.add_app(Application {
         id: "firefox".to_string(),
         name: "Firefox".to_string(),
         executable: PathBuf::from("/usr/bin/firefox"),
+        launch_args: Vec::new(),
         category: AppCategory::Browser,
         aliases: vec!["mozilla-firefox".to_string()],
         description: Some("Mozilla Firefox browser".to_string()),
         icon: None,
         version: None,
         install_date: None,
+        launch_count: 0,
+        last_used: None,
     });
     
-    Arc::new(db)
+    Arc::new(RwLock::new(db))
 }
 
 /// Helper function to create test file index
@@ -137,6 +145,172 @@ async fn test_full_pipeline_mock() {
     }
 }
 
+/// Test that previewing a command never runs any of its steps
+#[tokio::test]
+async fn test_preview_command_launches_nothing() {
+    let brain = Brain::new(&BrainConfig::default()).expect("Failed to create brain");
+
+    let app_db = create_test_app_db();
+    let app_launcher = AppLauncher::new(Arc::clone(&app_db));
+    let file_search = FileSearch::new(create_test_file_index());
+    let executor = TaskExecutor::new(app_launcher, file_search);
+
+    let preview = executor
+        .preview_command(&brain, "open chrome and set volume to 50")
+        .await
+        .expect("Preview failed");
+
+    assert!(preview.outcome.dry_run);
+    assert!(!preview.outcome.steps.is_empty());
+    assert_eq!(preview.outcome.steps.len(), preview.plan.steps.len());
+    for step in &preview.outcome.steps {
+        assert!(step.message.starts_with("[DRY-RUN]"), "{}", step.message);
+    }
+
+    // A real launch would have been counted
+    let launches: u32 = app_db.read().all().iter().map(|app| app.launch_count).sum();
+    assert_eq!(launches, 0);
+}
+
+/// Test that a script runs each command and reads clarifications from the next line
+#[tokio::test]
+async fn test_script_runner_plans_each_line() {
+    let brain = Arc::new(Brain::new(&BrainConfig::default()).expect("Failed to create brain"));
+    let app_launcher = AppLauncher::new(create_test_app_db());
+    let file_search = FileSearch::new(create_test_file_index());
+    let executor = Arc::new(TaskExecutor::new(app_launcher, file_search));
+
+    let script = [
+        "# morning routine",
+        "what time is it",
+        "",
+        "open chrome",
+        "volume up",
+    ];
+    let runner = ScriptRunner::new(Arc::clone(&brain), Arc::clone(&executor))
+        .with_confidence_threshold(0.0)
+        .with_dry_run(true);
+    let results = runner.run_commands(&script).await;
+
+    assert_eq!(results.len(), 3);
+    for result in &results {
+        let plan = result.plan.as_ref().expect("Every line should be planned");
+        assert!(!plan.steps.is_empty(), "No steps for: {}", result.text);
+        assert!(result.clarifications.is_empty());
+    }
+
+    // Every command asks for clarification, which is taken from the next line
+    let runner = ScriptRunner::new(brain, executor)
+        .with_confidence_threshold(1.1)
+        .with_dry_run(true);
+    let results = runner
+        .run_commands(&["do the thing", "what time is it"])
+        .await;
+
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].clarifications, vec!["what time is it"]);
+    let plan = results[0]
+        .plan
+        .as_ref()
+        .expect("Clarified command should be planned");
+    assert_eq!(plan.classification.intent, luna::brain::IntentType::GetTime);
+}
+
+/// Test that a script only runs a sensitive action the next line confirms
+#[tokio::test]
+async fn test_script_runner_asks_before_sensitive_actions() {
+    let brain = Arc::new(Brain::new(&BrainConfig::default()).expect("Failed to create brain"));
+    let app_launcher = AppLauncher::new(create_test_app_db());
+    let file_search = FileSearch::new(create_test_file_index());
+    let (confirmation_tx, confirmation_rx) = tokio::sync::mpsc::channel(1);
+    let executor = Arc::new(
+        TaskExecutor::new(app_launcher, file_search).with_confirmation_channel(confirmation_tx),
+    );
+
+    let runner = ScriptRunner::new(brain, executor)
+        .with_confidence_threshold(0.0)
+        .with_confirmations(confirmation_rx);
+    let results = runner
+        .run_commands(&["lock computer", "no", "what time is it"])
+        .await;
+
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].clarifications, vec!["no"]);
+    assert!(!results[0].success);
+    assert!(results[1].success);
+}
+
+/// Test that one utterance's events share a correlation ID end to end
+#[tokio::test]
+async fn test_utterance_events_share_correlation_id() {
+    use luna::audio::mocks::{
+        MockAudioCapture, MockAudioProcessor, MockSpeechToText, MockWakeWordDetector,
+    };
+    use luna::audio::AudioSystem;
+
+    let event_bus = Arc::new(EventBus::new());
+    let handle = event_bus.start_processing().await;
+    let seen = Arc::new(parking_lot::Mutex::new(Vec::new()));
+    let sink = Arc::clone(&seen);
+    event_bus
+        .subscribe(vec![], move |envelope| {
+            sink.lock()
+                .push((envelope.event.kind().as_str(), envelope.correlation_id));
+        })
+        .await;
+
+    let capture = MockAudioCapture::new();
+    capture.add_samples(vec![0.2; 1600]);
+    let wake_word = MockWakeWordDetector::new();
+    wake_word.queue_detection(true);
+    let stt = MockSpeechToText::new();
+    stt.queue_transcription("open chrome".to_string());
+    let mut audio = AudioSystem::new(capture, wake_word, stt, MockAudioProcessor::new())
+        .with_event_bus(Arc::clone(&event_bus));
+    audio.start_listening().unwrap();
+
+    let brain = Brain::new(&BrainConfig::default()).expect("Failed to create brain");
+    let executor = TaskExecutor::new(
+        AppLauncher::new(create_test_app_db()),
+        FileSearch::new(create_test_file_index()),
+    )
+    .with_event_bus(Arc::clone(&event_bus));
+
+    assert!(audio.wait_for_wake_word().await.unwrap());
+    let text = audio.listen_and_transcribe(1).await.unwrap();
+    let correlation_id = audio.correlation_id().expect("minted at wake word");
+    let plan = brain
+        .process_async_with_correlation(&text, correlation_id)
+        .await
+        .unwrap();
+    executor.preview_plan(plan).await.unwrap();
+
+    for _ in 0..100 {
+        if seen
+            .lock()
+            .iter()
+            .any(|(kind, _)| *kind == "plan_completed")
+        {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    handle.abort();
+
+    let seen = seen.lock().clone();
+    for kind in [
+        "wake_word_detected",
+        "command_transcribed",
+        "plan_started",
+        "action_started",
+    ] {
+        assert!(seen.iter().any(|(k, _)| *k == kind), "missing {}", kind);
+    }
+    for (kind, id) in &seen {
+        assert_eq!(*id, Some(correlation_id), "{} has a different ID", kind);
+    }
+}
+
 /// Test error recovery and handling
 #[tokio::test]
 async fn test_error_recovery() {